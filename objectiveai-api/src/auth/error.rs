@@ -0,0 +1,94 @@
+//! Error types for local authentication.
+
+/// Errors that can occur when authenticating callers or managing keys
+/// locally.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The request has no `Authorization` header.
+    #[error("missing API key")]
+    MissingApiKey,
+    /// The API key is malformed or unknown.
+    #[error("invalid API key")]
+    InvalidApiKey,
+    /// The API key has been disabled.
+    #[error("API key disabled")]
+    ApiKeyDisabled,
+    /// The API key has expired.
+    #[error("API key expired")]
+    ApiKeyExpired,
+    /// The caller may not manage API keys or BYOK keys.
+    #[error("forbidden: {0}")]
+    Forbidden(&'static str),
+    /// The API key to disable does not exist.
+    #[error("API key not found")]
+    ApiKeyNotFound,
+    /// The API key request is invalid.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    /// Failed to encrypt or decrypt a stored BYOK key.
+    #[error("encryption error")]
+    Encryption,
+    /// Failed to read or write the key store.
+    #[error("key store error: {0}")]
+    Store(String),
+}
+
+impl objectiveai::error::StatusError for Error {
+    fn status(&self) -> u16 {
+        match self {
+            Error::MissingApiKey => 401,
+            Error::InvalidApiKey => 401,
+            Error::ApiKeyDisabled => 401,
+            Error::ApiKeyExpired => 401,
+            Error::Forbidden(_) => 403,
+            Error::ApiKeyNotFound => 404,
+            Error::InvalidRequest(_) => 400,
+            Error::Encryption => 500,
+            Error::Store(_) => 500,
+        }
+    }
+
+    fn message(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "kind": "auth",
+            "error": match self {
+                Error::MissingApiKey => serde_json::json!({
+                    "kind": "missing_api_key",
+                    "error": "missing API key",
+                }),
+                Error::InvalidApiKey => serde_json::json!({
+                    "kind": "invalid_api_key",
+                    "error": "invalid API key",
+                }),
+                Error::ApiKeyDisabled => serde_json::json!({
+                    "kind": "api_key_disabled",
+                    "error": "API key disabled",
+                }),
+                Error::ApiKeyExpired => serde_json::json!({
+                    "kind": "api_key_expired",
+                    "error": "API key expired",
+                }),
+                Error::Forbidden(msg) => serde_json::json!({
+                    "kind": "forbidden",
+                    "error": msg,
+                }),
+                Error::ApiKeyNotFound => serde_json::json!({
+                    "kind": "api_key_not_found",
+                    "error": "API key not found",
+                }),
+                Error::InvalidRequest(msg) => serde_json::json!({
+                    "kind": "invalid_request",
+                    "error": msg,
+                }),
+                Error::Encryption => serde_json::json!({
+                    "kind": "encryption",
+                    "error": "failed to encrypt or decrypt stored key",
+                }),
+                Error::Store(msg) => serde_json::json!({
+                    "kind": "store",
+                    "error": msg,
+                }),
+            }
+        }))
+    }
+}
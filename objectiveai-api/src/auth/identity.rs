@@ -0,0 +1,18 @@
+//! The authenticated caller of a request.
+
+/// The caller of a request, as authenticated by the
+/// [`authenticate`](super::authenticate) middleware.
+///
+/// Inserted into the request's extensions, from which the server builds the
+/// request's [`ContextExt`](crate::ctx::ContextExt).
+#[derive(Debug, Clone)]
+pub struct Identity {
+    /// The fingerprint of the caller's API key, or `None` if the caller used
+    /// the server's admin key.
+    pub api_key: Option<objectiveai::auth::ApiKey>,
+    /// The ID of the tenant whose key created the caller's API key, if any.
+    pub tenant: Option<String>,
+    /// The account's OpenRouter BYOK key, if one is stored. Decrypted only
+    /// when a request uses it.
+    pub openrouter_byok: Option<super::StoredByok>,
+}
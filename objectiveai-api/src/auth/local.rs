@@ -0,0 +1,928 @@
+//! Self-hosted authentication backed by a local key store.
+
+use crate::ctx;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Length of the AES-GCM nonce prepended to each encrypted BYOK key.
+const NONCE_LEN: usize = 12;
+
+/// An API key as stored. Only the key's hash is kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredApiKey {
+    /// Hex-encoded SHA-256 hash of the API key.
+    hash: String,
+    /// The ID of the tenant whose key created this key, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tenant: Option<String>,
+    /// The key's metadata. `api_key` holds the key's fingerprint.
+    #[serde(flatten)]
+    metadata: objectiveai::auth::ApiKeyWithMetadata,
+}
+
+/// The contents of the key store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Store {
+    /// All API keys ever issued, including disabled ones.
+    #[serde(default)]
+    api_keys: Vec<StoredApiKey>,
+    /// The OpenRouter BYOK key, encrypted and base64-encoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    openrouter_byok_api_key: Option<String>,
+}
+
+/// The account's OpenRouter BYOK key as stored, decrypted the first time a
+/// request uses it.
+///
+/// Clones share the decrypted key, so it is decrypted at most once however
+/// many requests use it.
+#[derive(Clone)]
+pub struct StoredByok(std::sync::Arc<StoredByokInner>);
+
+struct StoredByokInner {
+    /// Cipher the key was encrypted with.
+    cipher: aes_gcm::Aes256Gcm,
+    /// The key, encrypted and base64-encoded.
+    encrypted: String,
+    /// The decrypted key, once decrypted, or `None` if decryption failed.
+    decrypted: std::sync::OnceLock<Option<String>>,
+}
+
+impl StoredByok {
+    fn new(cipher: aes_gcm::Aes256Gcm, encrypted: String) -> Self {
+        Self(std::sync::Arc::new(StoredByokInner {
+            cipher,
+            encrypted,
+            decrypted: std::sync::OnceLock::new(),
+        }))
+    }
+
+    /// The decrypted key.
+    ///
+    /// Fails with [`Error::Encryption`](super::Error::Encryption) if it cannot
+    /// be decrypted, e.g. because the encryption key changed since it was
+    /// stored, so that only the requests which use the key fail.
+    pub fn decrypt(&self) -> Result<&str, super::Error> {
+        self.0
+            .decrypted
+            .get_or_init(|| decrypt(&self.0.cipher, &self.0.encrypted).ok())
+            .as_deref()
+            .ok_or(super::Error::Encryption)
+    }
+}
+
+impl std::fmt::Debug for StoredByok {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StoredByok(<redacted>)")
+    }
+}
+
+/// Authentication client which issues and verifies API keys itself, for
+/// self-hosted servers.
+///
+/// All keys belong to one account. API keys are stored as SHA-256 hashes,
+/// so a key is only revealed when it is created; listed and disabled keys
+/// show the key's fingerprint instead (see [`fingerprint`](Self::fingerprint)).
+/// The OpenRouter BYOK key is stored encrypted with AES-256-GCM.
+///
+/// The admin key may create, list and disable every API key. A tenant's key
+/// (see [`Tenant`](ctx::Tenant)) may manage only the tenant's API keys, and
+/// keys it creates belong to the tenant. Only the admin key may set, read and
+/// delete the BYOK key. Other callers are rejected with 403.
+///
+/// The store is kept in memory and, if a path is given, persisted as JSON.
+/// Self-hosted servers do not bill, so credits and costs are always zero.
+pub struct LocalClient {
+    /// Path of the JSON key store, or `None` to keep keys in memory only.
+    pub path: Option<std::path::PathBuf>,
+    /// Cipher for BYOK keys at rest.
+    cipher: aes_gcm::Aes256Gcm,
+    /// SHA-256 hash of the admin key, which authenticates without being
+    /// issued. Used to create the first API key.
+    admin_api_key_hash: Option<[u8; 32]>,
+    /// The key store.
+    store: tokio::sync::RwLock<Store>,
+    /// The stored BYOK key last handed to a caller, so that its decryption
+    /// is shared until the key changes.
+    openrouter_byok: std::sync::Mutex<Option<StoredByok>>,
+}
+
+impl LocalClient {
+    /// Creates a new local authentication client, loading the key store at
+    /// `path` if it exists.
+    pub async fn new(
+        path: Option<std::path::PathBuf>,
+        encryption_key: &[u8; 32],
+        admin_api_key: Option<&str>,
+    ) -> Result<Self, super::Error> {
+        let store = match &path {
+            Some(path) => match tokio::fs::read_to_string(path).await {
+                Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                    super::Error::Store(format!(
+                        "failed to parse {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    Store::default()
+                }
+                Err(e) => {
+                    return Err(super::Error::Store(format!(
+                        "failed to read {}: {}",
+                        path.display(),
+                        e
+                    )));
+                }
+            },
+            None => Store::default(),
+        };
+        Ok(Self {
+            path,
+            cipher: aes_gcm::Aes256Gcm::new(encryption_key.into()),
+            admin_api_key_hash: admin_api_key
+                .filter(|key| !key.is_empty())
+                .map(hash),
+            store: tokio::sync::RwLock::new(store),
+            openrouter_byok: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// Decodes a base64-encoded 32-byte encryption key.
+    pub fn decode_encryption_key(
+        encryption_key: &str,
+    ) -> Result<[u8; 32], super::Error> {
+        base64::engine::general_purpose::STANDARD
+            .decode(encryption_key.trim())
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| {
+                super::Error::Store(
+                    "encryption key must be 32 bytes, base64-encoded"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// The fingerprint of an API key: a stable, non-secret identifier derived
+    /// from the key's hash, in the same format as the key.
+    pub fn fingerprint(
+        api_key: &objectiveai::auth::ApiKey,
+    ) -> objectiveai::auth::ApiKey {
+        fingerprint_of_hash(&hash(&api_key.to_string()))
+    }
+
+    /// Authenticates the caller from the value of its `Authorization`
+    /// header.
+    ///
+    /// The stored BYOK key is attached to the caller's identity encrypted,
+    /// and only decrypted if a request uses it.
+    pub async fn authenticate(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<super::Identity, super::Error> {
+        let token = authorization
+            .map(|value| value.strip_prefix("Bearer ").unwrap_or(value).trim())
+            .filter(|token| !token.is_empty())
+            .ok_or(super::Error::MissingApiKey)?;
+        let token_hash = hash(token);
+        let store = self.store.read().await;
+        let openrouter_byok = store
+            .openrouter_byok_api_key
+            .as_deref()
+            .map(|encrypted| self.stored_byok(encrypted));
+        if self.admin_api_key_hash == Some(token_hash) {
+            return Ok(super::Identity {
+                api_key: None,
+                tenant: None,
+                openrouter_byok,
+            });
+        }
+        let token_hash = hex(&token_hash);
+        let stored = store
+            .api_keys
+            .iter()
+            .find(|stored| stored.hash == token_hash)
+            .ok_or(super::Error::InvalidApiKey)?;
+        if stored.metadata.disabled.is_some() {
+            return Err(super::Error::ApiKeyDisabled);
+        }
+        if stored
+            .metadata
+            .expires
+            .is_some_and(|expires| expires <= chrono::Utc::now())
+        {
+            return Err(super::Error::ApiKeyExpired);
+        }
+        Ok(super::Identity {
+            api_key: Some(stored.metadata.api_key),
+            tenant: stored.tenant.clone(),
+            openrouter_byok,
+        })
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String, super::Error> {
+        let nonce = aes_gcm::Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| super::Error::Encryption)?;
+        let mut bytes = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// The [`StoredByok`] for the stored encrypted key, reusing the last one
+    /// while the key is unchanged.
+    fn stored_byok(&self, encrypted: &str) -> StoredByok {
+        let mut cached = self.openrouter_byok.lock().unwrap();
+        match &*cached {
+            Some(byok) if byok.0.encrypted == encrypted => byok.clone(),
+            _ => {
+                let byok =
+                    StoredByok::new(self.cipher.clone(), encrypted.to_string());
+                *cached = Some(byok.clone());
+                byok
+            }
+        }
+    }
+
+    /// Persists the store, if a path is configured. Writes to a temporary
+    /// file first so a crash never leaves a partial store.
+    async fn save(&self, store: &Store) -> Result<(), super::Error> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                super::Error::Store(format!(
+                    "failed to create {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+        let content = serde_json::to_vec_pretty(store)
+            .map_err(|e| super::Error::Store(e.to_string()))?;
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content).await.map_err(|e| {
+            super::Error::Store(format!(
+                "failed to write {}: {}",
+                tmp_path.display(),
+                e
+            ))
+        })?;
+        tokio::fs::rename(&tmp_path, path).await.map_err(|e| {
+            super::Error::Store(format!(
+                "failed to write {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+fn decrypt(
+    cipher: &aes_gcm::Aes256Gcm,
+    encrypted: &str,
+) -> Result<String, super::Error> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encrypted)
+        .map_err(|_| super::Error::Encryption)?;
+    if bytes.len() < NONCE_LEN {
+        return Err(super::Error::Encryption);
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let plaintext = cipher
+        .decrypt(nonce.into(), ciphertext)
+        .map_err(|_| super::Error::Encryption)?;
+    String::from_utf8(plaintext).map_err(|_| super::Error::Encryption)
+}
+
+fn hash(value: &str) -> [u8; 32] {
+    Sha256::digest(value.as_bytes()).into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn fingerprint_of_hash(hash: &[u8; 32]) -> objectiveai::auth::ApiKey {
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    objectiveai::auth::ApiKey::from(uuid::Uuid::from_bytes(bytes))
+}
+
+fn response_error(error: super::Error) -> objectiveai::error::ResponseError {
+    objectiveai::error::ResponseError::from(&error)
+}
+
+/// Rejects callers other than the server's admin key. Issued API keys may not
+/// manage or read the account's BYOK key.
+fn require_admin<CTXEXT: ctx::ContextExt>(
+    ctx: &ctx::Context<CTXEXT>,
+) -> Result<(), objectiveai::error::ResponseError> {
+    match ctx.ext.identity() {
+        Some(super::Identity { api_key: None, .. }) => Ok(()),
+        _ => Err(response_error(super::Error::Forbidden(
+            "only the admin key may manage the BYOK key",
+        ))),
+    }
+}
+
+/// The API keys a caller may manage.
+enum Scope<'a> {
+    /// The admin key manages every API key.
+    All,
+    /// A tenant's key manages the tenant's API keys.
+    Tenant(&'a ctx::Tenant),
+}
+
+impl<'a> Scope<'a> {
+    /// The scope of the caller. Rejects callers which are neither the admin
+    /// key nor a tenant's key.
+    fn of<CTXEXT: ctx::ContextExt>(
+        ctx: &'a ctx::Context<CTXEXT>,
+    ) -> Result<Self, objectiveai::error::ResponseError> {
+        match (ctx.ext.identity(), ctx.ext.tenant()) {
+            (Some(super::Identity { api_key: None, .. }), _) => Ok(Scope::All),
+            (Some(_), Some(tenant)) => Ok(Scope::Tenant(tenant)),
+            _ => Err(response_error(super::Error::Forbidden(
+                "only the admin key and tenant keys may manage API keys",
+            ))),
+        }
+    }
+
+    /// The tenant which keys created in this scope belong to.
+    fn tenant_id(&self) -> Option<String> {
+        match self {
+            Scope::All => None,
+            Scope::Tenant(tenant) => Some(tenant.id.clone()),
+        }
+    }
+
+    /// Whether the key is in this scope: created by one of the tenant's keys,
+    /// or assigned to the tenant in its configuration.
+    fn contains(&self, stored: &StoredApiKey) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::Tenant(tenant) => {
+                stored.tenant.as_deref() == Some(tenant.id.as_str())
+                    || tenant.api_keys.contains(&stored.metadata.api_key)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT> super::Client<CTXEXT> for LocalClient
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    async fn create_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::CreateApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::CreateApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        let scope = Scope::of(&ctx)?;
+        let now = chrono::Utc::now();
+        if request.name.trim().is_empty() {
+            return Err(response_error(super::Error::InvalidRequest(
+                "`name` cannot be empty".to_string(),
+            )));
+        }
+        if request.expires.is_some_and(|expires| expires <= now) {
+            return Err(response_error(super::Error::InvalidRequest(
+                "`expires` must be in the future".to_string(),
+            )));
+        }
+        let api_key = objectiveai::auth::ApiKey::new();
+        let api_key_hash = hash(&api_key.to_string());
+        let metadata = objectiveai::auth::ApiKeyWithMetadata {
+            api_key: fingerprint_of_hash(&api_key_hash),
+            created: now,
+            expires: request.expires,
+            disabled: None,
+            name: request.name,
+            description: request.description,
+        };
+        let mut store = self.store.write().await;
+        store.api_keys.push(StoredApiKey {
+            hash: hex(&api_key_hash),
+            tenant: scope.tenant_id(),
+            metadata: metadata.clone(),
+        });
+        if let Err(e) = self.save(&store).await {
+            store.api_keys.pop();
+            return Err(response_error(e));
+        }
+        Ok(objectiveai::auth::ApiKeyWithMetadata {
+            api_key,
+            ..metadata
+        })
+    }
+
+    async fn create_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::CreateOpenRouterByokApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        require_admin(&ctx)?;
+        if request.api_key.trim().is_empty() {
+            return Err(response_error(super::Error::InvalidRequest(
+                "`api_key` cannot be empty".to_string(),
+            )));
+        }
+        let encrypted =
+            self.encrypt(&request.api_key).map_err(response_error)?;
+        let mut store = self.store.write().await;
+        let previous = store.openrouter_byok_api_key.replace(encrypted);
+        if let Err(e) = self.save(&store).await {
+            store.openrouter_byok_api_key = previous;
+            return Err(response_error(e));
+        }
+        Ok(
+            objectiveai::auth::response::GetOpenRouterByokApiKeyResponse {
+                api_key: Some(request.api_key),
+            },
+        )
+    }
+
+    async fn disable_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::DisableApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::DisableApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        let scope = Scope::of(&ctx)?;
+        // accept either the key itself or its fingerprint
+        let fingerprint = LocalClient::fingerprint(&request.api_key);
+        let mut store = self.store.write().await;
+        let stored = store
+            .api_keys
+            .iter_mut()
+            .find(|stored| {
+                (stored.metadata.api_key == request.api_key
+                    || stored.metadata.api_key == fingerprint)
+                    && scope.contains(stored)
+            })
+            .ok_or_else(|| response_error(super::Error::ApiKeyNotFound))?;
+        if stored.metadata.disabled.is_some() {
+            return Ok(stored.metadata.clone());
+        }
+        stored.metadata.disabled = Some(chrono::Utc::now());
+        let metadata = stored.metadata.clone();
+        if let Err(e) = self.save(&store).await {
+            if let Some(stored) = store
+                .api_keys
+                .iter_mut()
+                .find(|stored| stored.metadata.api_key == metadata.api_key)
+            {
+                stored.metadata.disabled = None;
+            }
+            return Err(response_error(e));
+        }
+        Ok(metadata)
+    }
+
+    async fn delete_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<(), objectiveai::error::ResponseError> {
+        require_admin(&ctx)?;
+        let mut store = self.store.write().await;
+        let previous = store.openrouter_byok_api_key.take();
+        if let Err(e) = self.save(&store).await {
+            store.openrouter_byok_api_key = previous;
+            return Err(response_error(e));
+        }
+        Ok(())
+    }
+
+    async fn list_api_keys(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::ListApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        let scope = Scope::of(&ctx)?;
+        let store = self.store.read().await;
+        Ok(objectiveai::auth::response::ListApiKeyResponse {
+            data: store
+                .api_keys
+                .iter()
+                .filter(|stored| scope.contains(stored))
+                .map(|stored| objectiveai::auth::response::ListApiKeyItem {
+                    inner: stored.metadata.clone(),
+                    cost: rust_decimal::Decimal::ZERO,
+                })
+                .collect(),
+        })
+    }
+
+    async fn get_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::GetOpenRouterByokApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        require_admin(&ctx)?;
+        let store = self.store.read().await;
+        let api_key = match &store.openrouter_byok_api_key {
+            Some(encrypted) => {
+                Some(decrypt(&self.cipher, encrypted).map_err(response_error)?)
+            }
+            None => None,
+        };
+        Ok(
+            objectiveai::auth::response::GetOpenRouterByokApiKeyResponse {
+                api_key,
+            },
+        )
+    }
+
+    async fn get_credits(
+        &self,
+        _ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::GetCreditsResponse,
+        objectiveai::error::ResponseError,
+    > {
+        Ok(objectiveai::auth::response::GetCreditsResponse {
+            credits: rust_decimal::Decimal::ZERO,
+            total_credits_purchased: rust_decimal::Decimal::ZERO,
+            total_credits_used: rust_decimal::Decimal::ZERO,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Client;
+
+    fn ctx_as(
+        api_key: Option<objectiveai::auth::ApiKey>,
+    ) -> ctx::Context<ctx::DefaultContextExt> {
+        ctx::Context::new(
+            std::sync::Arc::new(ctx::DefaultContextExt {
+                openrouter_byok: None,
+                identity: Some(super::super::Identity {
+                    api_key,
+                    tenant: None,
+                    openrouter_byok: None,
+                }),
+            }),
+            rust_decimal::Decimal::ONE,
+        )
+    }
+
+    fn ctx() -> ctx::Context<ctx::DefaultContextExt> {
+        ctx_as(None)
+    }
+
+    async fn client(path: Option<std::path::PathBuf>) -> LocalClient {
+        LocalClient::new(path, &[7; 32], Some("admin"))
+            .await
+            .unwrap()
+    }
+
+    fn create_request() -> objectiveai::auth::request::CreateApiKeyRequest {
+        objectiveai::auth::request::CreateApiKeyRequest {
+            expires: None,
+            name: "test".to_string(),
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn issued_key_authenticates_until_disabled() {
+        let client = client(None).await;
+        let created = client
+            .create_api_key(ctx(), create_request())
+            .await
+            .unwrap();
+        let bearer = format!("Bearer {}", created.api_key);
+        let identity = client.authenticate(Some(&bearer)).await.unwrap();
+        let fingerprint = LocalClient::fingerprint(&created.api_key);
+        assert_eq!(identity.api_key, Some(fingerprint));
+
+        // only the fingerprint is listed
+        let listed = client.list_api_keys(ctx()).await.unwrap();
+        assert_eq!(listed.data.len(), 1);
+        assert_eq!(listed.data[0].inner.api_key, fingerprint);
+
+        let disabled = client
+            .disable_api_key(
+                ctx(),
+                objectiveai::auth::request::DisableApiKeyRequest {
+                    api_key: fingerprint,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(disabled.disabled.is_some());
+        assert!(matches!(
+            client.authenticate(Some(&bearer)).await,
+            Err(super::super::Error::ApiKeyDisabled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_and_unknown_keys() {
+        let client = client(None).await;
+        assert!(matches!(
+            client.authenticate(None).await,
+            Err(super::super::Error::MissingApiKey)
+        ));
+        let unknown = format!("Bearer {}", objectiveai::auth::ApiKey::new());
+        assert!(matches!(
+            client.authenticate(Some(&unknown)).await,
+            Err(super::super::Error::InvalidApiKey)
+        ));
+        let admin = client.authenticate(Some("Bearer admin")).await.unwrap();
+        assert_eq!(admin.api_key, None);
+    }
+
+    #[tokio::test]
+    async fn byok_is_encrypted_at_rest_and_persisted() {
+        let path = std::env::temp_dir()
+            .join(format!("objectiveai-auth-{}", uuid::Uuid::new_v4()))
+            .join("auth.json");
+        let client = client(Some(path.clone())).await;
+        client
+            .create_openrouter_byok_api_key(
+                ctx(),
+                objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest {
+                    api_key: "sk-or-secret".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let created = client
+            .create_api_key(ctx(), create_request())
+            .await
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("sk-or-secret"));
+        assert!(!content.contains(&created.api_key.to_string()));
+
+        let reloaded = self::client(Some(path.clone())).await;
+        let identity = reloaded
+            .authenticate(Some(&created.api_key.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            identity.openrouter_byok.unwrap().decrypt().unwrap(),
+            "sk-or-secret"
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn undecryptable_byok_does_not_fail_authentication() {
+        let path = std::env::temp_dir()
+            .join(format!("objectiveai-auth-{}", uuid::Uuid::new_v4()))
+            .join("auth.json");
+        let client = client(Some(path.clone())).await;
+        client
+            .create_openrouter_byok_api_key(
+                ctx(),
+                objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest {
+                    api_key: "sk-or-secret".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        let created = client
+            .create_api_key(ctx(), create_request())
+            .await
+            .unwrap();
+
+        // the encryption key was rotated
+        let rotated =
+            LocalClient::new(Some(path.clone()), &[8; 32], Some("admin"))
+                .await
+                .unwrap();
+        for bearer in ["admin".to_string(), created.api_key.to_string()] {
+            let identity = rotated.authenticate(Some(&bearer)).await.unwrap();
+            let byok = identity.openrouter_byok.clone().unwrap();
+            assert!(matches!(
+                byok.decrypt(),
+                Err(super::super::Error::Encryption)
+            ));
+            // requests which use the stored BYOK key fail instead
+            let ext = ctx::DefaultContextExt {
+                openrouter_byok: None,
+                identity: Some(identity),
+            };
+            let error = ctx::ContextExt::get_byok(
+                &ext,
+                crate::chat::completions::upstream::Upstream::OpenRouter,
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(error.code, 500);
+        }
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn byok_is_decrypted_once_until_it_changes() {
+        let client = client(None).await;
+        let set = |api_key: &str| {
+            client.create_openrouter_byok_api_key(
+                ctx(),
+                objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest {
+                    api_key: api_key.to_string(),
+                },
+            )
+        };
+        set("sk-or-first").await.unwrap();
+        let first = client.authenticate(Some("admin")).await.unwrap();
+        let again = client.authenticate(Some("admin")).await.unwrap();
+        let (first, again) = (
+            first.openrouter_byok.unwrap(),
+            again.openrouter_byok.unwrap(),
+        );
+        assert_eq!(first.decrypt().unwrap(), "sk-or-first");
+        // the second caller shares the first caller's decryption
+        assert!(std::sync::Arc::ptr_eq(&first.0, &again.0));
+
+        set("sk-or-second").await.unwrap();
+        let changed = client.authenticate(Some("admin")).await.unwrap();
+        assert_eq!(
+            changed.openrouter_byok.unwrap().decrypt().unwrap(),
+            "sk-or-second"
+        );
+    }
+
+    fn forbidden<T>(
+        result: Result<T, objectiveai::error::ResponseError>,
+    ) -> bool {
+        result.is_err_and(|e| e.code == 403)
+    }
+
+    #[tokio::test]
+    async fn non_admin_keys_are_forbidden() {
+        let client = client(None).await;
+        let created = client
+            .create_api_key(ctx(), create_request())
+            .await
+            .unwrap();
+        let fingerprint = LocalClient::fingerprint(&created.api_key);
+        let issued = || ctx_as(Some(fingerprint));
+        assert!(forbidden(
+            client.create_api_key(issued(), create_request()).await
+        ));
+        assert!(forbidden(client.list_api_keys(issued()).await));
+        assert!(forbidden(
+            client
+                .disable_api_key(
+                    issued(),
+                    objectiveai::auth::request::DisableApiKeyRequest {
+                        api_key: fingerprint,
+                    },
+                )
+                .await
+        ));
+        assert!(forbidden(
+            client
+                .create_openrouter_byok_api_key(
+                    issued(),
+                    objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest {
+                        api_key: "sk-or-secret".to_string(),
+                    },
+                )
+                .await
+        ));
+        assert!(forbidden(
+            client.get_openrouter_byok_api_key(issued()).await
+        ));
+        assert!(forbidden(
+            client.delete_openrouter_byok_api_key(issued()).await
+        ));
+        // callers which were not authenticated are not the admin either
+        let anonymous = ctx::Context::new(
+            std::sync::Arc::new(ctx::DefaultContextExt {
+                openrouter_byok: None,
+                identity: None,
+            }),
+            rust_decimal::Decimal::ONE,
+        );
+        assert!(forbidden(client.list_api_keys(anonymous).await));
+
+        // the key itself is untouched
+        let listed = client.list_api_keys(ctx()).await.unwrap();
+        assert!(listed.data[0].inner.disabled.is_none());
+    }
+
+    fn tenant_ctx(
+        tenant: &std::sync::Arc<ctx::Tenant>,
+        api_key: objectiveai::auth::ApiKey,
+    ) -> ctx::Context<ctx::TenantContextExt> {
+        ctx::Context::new(
+            std::sync::Arc::new(ctx::TenantContextExt {
+                inner: ctx::DefaultContextExt {
+                    openrouter_byok: None,
+                    identity: Some(super::super::Identity {
+                        api_key: Some(api_key),
+                        tenant: None,
+                        openrouter_byok: None,
+                    }),
+                },
+                tenant: Some(tenant.clone()),
+            }),
+            rust_decimal::Decimal::ONE,
+        )
+    }
+
+    #[tokio::test]
+    async fn tenant_keys_manage_only_their_tenants_keys() {
+        let client = client(None).await;
+        let mut tenants = Vec::new();
+        for id in ["acme", "globex"] {
+            let created = client
+                .create_api_key(ctx(), create_request())
+                .await
+                .unwrap();
+            let fingerprint = LocalClient::fingerprint(&created.api_key);
+            let tenant = std::sync::Arc::new(ctx::Tenant {
+                id: id.to_string(),
+                api_keys: vec![fingerprint],
+                byok: std::collections::HashMap::new(),
+                cost_multiplier: None,
+                allowed_models: None,
+                quota: None,
+            });
+            tenants.push((tenant, fingerprint));
+        }
+        let (acme, acme_key) = &tenants[0];
+        let (_, globex_key) = &tenants[1];
+        let as_acme = || tenant_ctx(acme, *acme_key);
+
+        // keys created by a tenant's key belong to the tenant
+        let created = client
+            .create_api_key(as_acme(), create_request())
+            .await
+            .unwrap();
+        let identity = client
+            .authenticate(Some(&created.api_key.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(identity.tenant.as_deref(), Some("acme"));
+
+        // only the tenant's keys are listed
+        let mut listed = client
+            .list_api_keys(as_acme())
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|item| item.inner.api_key)
+            .collect::<Vec<_>>();
+        listed.sort();
+        let mut expected = vec![*acme_key, identity.api_key.unwrap()];
+        expected.sort();
+        assert_eq!(listed, expected);
+
+        // other tenants' keys cannot be disabled
+        let disabled = client
+            .disable_api_key(
+                as_acme(),
+                objectiveai::auth::request::DisableApiKeyRequest {
+                    api_key: *globex_key,
+                },
+            )
+            .await;
+        assert!(disabled.is_err_and(|e| e.code == 404));
+        client
+            .disable_api_key(
+                as_acme(),
+                objectiveai::auth::request::DisableApiKeyRequest {
+                    api_key: created.api_key,
+                },
+            )
+            .await
+            .unwrap();
+
+        // the account's BYOK key is not readable
+        assert!(forbidden(
+            client.get_openrouter_byok_api_key(as_acme()).await
+        ));
+    }
+}
@@ -0,0 +1,46 @@
+//! Axum middleware which authenticates every request.
+
+use objectiveai::functions::executions::websocket::API_KEY_PROTOCOL_PREFIX;
+use std::sync::Arc;
+
+/// Authenticates the request's `Authorization` header against the local key
+/// store, and inserts the caller's [`Identity`](super::Identity) into the
+/// request's extensions. Rejects unauthenticated requests with 401.
+///
+/// WebSocket handshakes without the header may offer the API key as a
+/// subprotocol instead, since browsers can't set headers on WebSockets. See
+/// [`API_KEY_PROTOCOL_PREFIX`].
+///
+/// Use with [`axum::middleware::from_fn_with_state`].
+pub async fn authenticate(
+    axum::extract::State(client): axum::extract::State<Arc<super::LocalClient>>,
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let authorization = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| websocket_api_key(request.headers()));
+    match client.authenticate(authorization).await {
+        Ok(identity) => {
+            request.extensions_mut().insert(identity);
+            next.run(request).await
+        }
+        Err(e) => crate::error::ResponseErrorExt::into_response(
+            objectiveai::error::ResponseError::from(&e),
+        ),
+    }
+}
+
+/// The API key offered in the `Sec-WebSocket-Protocol` header, if any.
+fn websocket_api_key(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get_all(axum::http::header::SEC_WEBSOCKET_PROTOCOL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .find_map(|protocol| {
+            protocol.trim().strip_prefix(API_KEY_PROTOCOL_PREFIX)
+        })
+}
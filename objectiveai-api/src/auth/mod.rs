@@ -2,9 +2,26 @@
 //!
 //! This module provides traits and implementations for managing API keys,
 //! BYOK (Bring Your Own Key) OpenRouter keys, and credit balances.
+//!
+//! By default, the server delegates to the hosted ObjectiveAI service and
+//! accepts any caller. Self-hosted servers can instead use [`LocalClient`],
+//! which issues and verifies keys itself, together with the [`authenticate`]
+//! middleware.
 
 mod client;
+mod error;
+mod identity;
+mod local;
+mod middleware;
+mod mode;
 mod objectiveai;
+mod router;
 
 pub use client::*;
+pub use error::*;
+pub use identity::*;
+pub use local::*;
+pub use middleware::*;
+pub use mode::*;
 pub use objectiveai::*;
+pub use router::*;
@@ -0,0 +1,34 @@
+//! How the server authenticates callers.
+
+use serde::{Deserialize, Serialize};
+
+/// How the server authenticates callers and manages API keys.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum AuthMode {
+    /// Delegate key management to the hosted ObjectiveAI service, and accept
+    /// any caller.
+    #[default]
+    #[serde(rename = "objectiveai")]
+    ObjectiveAi,
+    /// Issue and verify API keys locally with [`LocalClient`](super::LocalClient),
+    /// and reject unauthenticated callers.
+    #[serde(rename = "local")]
+    Local,
+}
+
+impl std::str::FromStr for AuthMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "objectiveai" => Ok(AuthMode::ObjectiveAi),
+            "local" => Ok(AuthMode::Local),
+            _ => Err(format!(
+                "invalid auth mode `{}`, expected `objectiveai` or `local`",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Router that dispatches to the hosted or local authentication client.
+
+use crate::ctx;
+use std::sync::Arc;
+
+/// Routes authentication requests to the client for the server's auth mode:
+/// the hosted ObjectiveAI service, or the local key store.
+pub enum ClientRouter<R, L> {
+    /// Delegates to the hosted service.
+    Remote(Arc<R>),
+    /// Issues and verifies keys locally.
+    Local(Arc<L>),
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, R, L> super::Client<CTXEXT> for ClientRouter<R, L>
+where
+    CTXEXT: Send + Sync + 'static,
+    R: super::Client<CTXEXT> + Send + Sync + 'static,
+    L: super::Client<CTXEXT> + Send + Sync + 'static,
+{
+    async fn create_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::CreateApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::CreateApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => {
+                client.create_api_key(ctx, request).await
+            }
+            ClientRouter::Local(client) => {
+                client.create_api_key(ctx, request).await
+            }
+        }
+    }
+
+    async fn create_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::CreateOpenRouterByokApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => {
+                client.create_openrouter_byok_api_key(ctx, request).await
+            }
+            ClientRouter::Local(client) => {
+                client.create_openrouter_byok_api_key(ctx, request).await
+            }
+        }
+    }
+
+    async fn disable_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: objectiveai::auth::request::DisableApiKeyRequest,
+    ) -> Result<
+        objectiveai::auth::response::DisableApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => {
+                client.disable_api_key(ctx, request).await
+            }
+            ClientRouter::Local(client) => {
+                client.disable_api_key(ctx, request).await
+            }
+        }
+    }
+
+    async fn delete_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<(), objectiveai::error::ResponseError> {
+        match self {
+            ClientRouter::Remote(client) => {
+                client.delete_openrouter_byok_api_key(ctx).await
+            }
+            ClientRouter::Local(client) => {
+                client.delete_openrouter_byok_api_key(ctx).await
+            }
+        }
+    }
+
+    async fn list_api_keys(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::ListApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => client.list_api_keys(ctx).await,
+            ClientRouter::Local(client) => client.list_api_keys(ctx).await,
+        }
+    }
+
+    async fn get_openrouter_byok_api_key(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::GetOpenRouterByokApiKeyResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => {
+                client.get_openrouter_byok_api_key(ctx).await
+            }
+            ClientRouter::Local(client) => {
+                client.get_openrouter_byok_api_key(ctx).await
+            }
+        }
+    }
+
+    async fn get_credits(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+    ) -> Result<
+        objectiveai::auth::response::GetCreditsResponse,
+        objectiveai::error::ResponseError,
+    > {
+        match self {
+            ClientRouter::Remote(client) => client.get_credits(ctx).await,
+            ClientRouter::Local(client) => client.get_credits(ctx).await,
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub fn response_id(created: u64) -> String {
 /// Handles Ensemble LLM fetching, upstream provider selection with fallbacks,
 /// retry logic with exponential backoff, and usage tracking.
 #[derive(Debug, Clone)]
-pub struct Client<CTXEXT, FENSLLM, CUSG> {
+pub struct Client<CTXEXT, FENSLLM: ?Sized, CUSG: ?Sized> {
     /// Caching fetcher for Ensemble LLM definitions.
     pub ensemble_llm_fetcher:
         Arc<crate::ensemble_llm::fetcher::CachingFetcher<CTXEXT, FENSLLM>>,
@@ -37,9 +37,11 @@ pub struct Client<CTXEXT, FENSLLM, CUSG> {
     pub backoff_max_interval: Duration,
     /// Maximum total time to spend on retries.
     pub backoff_max_elapsed_time: Duration,
+    /// Chunk timeouts for upstream streams.
+    pub timeouts: super::ChunkTimeouts,
 }
 
-impl<CTXEXT, FENSLLM, CUSG> Client<CTXEXT, FENSLLM, CUSG> {
+impl<CTXEXT, FENSLLM: ?Sized, CUSG: ?Sized> Client<CTXEXT, FENSLLM, CUSG> {
     /// Creates a new chat completions client.
     pub fn new(
         ensemble_llm_fetcher: Arc<
@@ -53,6 +55,7 @@ impl<CTXEXT, FENSLLM, CUSG> Client<CTXEXT, FENSLLM, CUSG> {
         backoff_multiplier: f64,
         backoff_max_interval: Duration,
         backoff_max_elapsed_time: Duration,
+        timeouts: super::ChunkTimeouts,
     ) -> Self {
         Self {
             ensemble_llm_fetcher,
@@ -64,11 +67,12 @@ impl<CTXEXT, FENSLLM, CUSG> Client<CTXEXT, FENSLLM, CUSG> {
             backoff_multiplier,
             backoff_max_interval,
             backoff_max_elapsed_time,
+            timeouts,
         }
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG> Client<CTXEXT, FENSLLM, CUSG>
+impl<CTXEXT, FENSLLM: ?Sized, CUSG: ?Sized> Client<CTXEXT, FENSLLM, CUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
     FENSLLM:
@@ -247,7 +251,7 @@ where
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG> Client<CTXEXT, FENSLLM, CUSG>
+impl<CTXEXT, FENSLLM: ?Sized, CUSG: ?Sized> Client<CTXEXT, FENSLLM, CUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
     FENSLLM:
@@ -328,7 +332,7 @@ where
             }),
         );
 
-        // backoff and timeouts
+        // backoff
         let backoff = backoff::ExponentialBackoff {
             current_interval: self.backoff_current_interval,
             initial_interval: self.backoff_initial_interval,
@@ -345,20 +349,6 @@ where
             ),
             clock: backoff::SystemClock::default(),
         };
-        let first_chunk_timeout = Duration::from_millis(
-            request
-                .first_chunk_timeout
-                .unwrap_or(10_000) // default 10 seconds
-                .min(10_000) // at least 10 seconds
-                .max(120_000), // at most 2 minutes
-        );
-        let other_chunk_timeout = Duration::from_millis(
-            request
-                .other_chunk_timeout
-                .unwrap_or(40_000) // default 40 seconds
-                .min(40_000) // at least 40 seconds
-                .max(120_000), // at most 2 minutes
-        );
 
         // try each model in order
         backoff::future::retry(backoff, || async {
@@ -393,7 +383,20 @@ where
                         }
                     }
                 });
+                // skip models the caller may not use
+                if !ctx.ext.allows_model(&ensemble_llm.base.model) {
+                    errors.push(super::Error::ModelNotAllowed(
+                        ensemble_llm.base.model.clone(),
+                    ));
+                    continue;
+                }
                 // try to create streaming completion
+                let (first_chunk_timeout, other_chunk_timeout) =
+                    self.timeouts.resolve(
+                        &ensemble_llm.base.model,
+                        request.first_chunk_timeout,
+                        request.other_chunk_timeout,
+                    );
                 match self.upstream_client.create_streaming(
                     ctx.clone(),
                     response_id.clone(),
@@ -415,6 +418,14 @@ where
             }
             if errors.is_empty() {
                 Err(backoff::Error::permanent(super::Error::NoUpstreamsFound))
+            } else if errors
+                .iter()
+                .all(|e| matches!(e, super::Error::ModelNotAllowed(_)))
+            {
+                // retrying cannot help
+                Err(backoff::Error::permanent(super::Error::MultipleErrors(
+                    errors,
+                )))
             } else {
                 Err(backoff::Error::transient(super::Error::MultipleErrors(
                     errors,
@@ -467,7 +478,7 @@ where
             models.extend(fallbacks.into_iter().map(Arc::new));
         }
 
-        // backoff and timeouts
+        // backoff
         let backoff = backoff::ExponentialBackoff {
             current_interval: self.backoff_current_interval,
             initial_interval: self.backoff_initial_interval,
@@ -484,26 +495,25 @@ where
             ),
             clock: backoff::SystemClock::default(),
         };
-        let first_chunk_timeout = Duration::from_millis(
-            request
-                .first_chunk_timeout
-                .unwrap_or(10_000) // default 10 seconds
-                .min(10_000) // at least 10 seconds
-                .max(120_000), // at most 2 minutes
-        );
-        let other_chunk_timeout = Duration::from_millis(
-            request
-                .other_chunk_timeout
-                .unwrap_or(40_000) // default 40 seconds
-                .min(40_000) // at least 40 seconds
-                .max(120_000), // at most 2 minutes
-        );
 
         // try each model in order
         backoff::future::retry(backoff, || async {
             let mut errors = Vec::new();
             for (i, ensemble_llm) in models.iter().cloned().enumerate() {
+                // skip models the caller may not use
+                if !ctx.ext.allows_model(&ensemble_llm.base.model) {
+                    errors.push(super::Error::ModelNotAllowed(
+                        ensemble_llm.base.model.clone(),
+                    ));
+                    continue;
+                }
                 // try to create streaming completion
+                let (first_chunk_timeout, other_chunk_timeout) =
+                    self.timeouts.resolve(
+                        &ensemble_llm.base.model,
+                        request.first_chunk_timeout,
+                        request.other_chunk_timeout,
+                    );
                 match self
                     .upstream_client
                     .create_streaming(
@@ -530,6 +540,14 @@ where
             }
             if errors.is_empty() {
                 Err(backoff::Error::permanent(super::Error::NoUpstreamsFound))
+            } else if errors
+                .iter()
+                .all(|e| matches!(e, super::Error::ModelNotAllowed(_)))
+            {
+                // retrying cannot help
+                Err(backoff::Error::permanent(super::Error::MultipleErrors(
+                    errors,
+                )))
             } else {
                 Err(backoff::Error::transient(super::Error::MultipleErrors(
                     errors,
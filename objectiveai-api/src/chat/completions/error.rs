@@ -18,6 +18,9 @@ pub enum Error {
     /// The Ensemble LLM definition is invalid.
     #[error("invalid Ensemble LLM: {0}")]
     InvalidEnsembleLlm(String),
+    /// The caller may not use the Ensemble LLM's upstream model.
+    #[error("model not allowed: {0}")]
+    ModelNotAllowed(String),
     /// Multiple errors occurred during fallback attempts.
     #[error("multiple errors: {0:?}")]
     MultipleErrors(Vec<Error>),
@@ -31,6 +34,7 @@ impl objectiveai::error::StatusError for Error {
             Error::FetchEnsembleLlm(e) => e.status(),
             Error::EnsembleLlmNotFound => 404,
             Error::InvalidEnsembleLlm(_) => 400,
+            Error::ModelNotAllowed(_) => 403,
             Error::MultipleErrors(_) => 500,
         }
     }
@@ -59,6 +63,10 @@ impl objectiveai::error::StatusError for Error {
                     "kind": "invalid_ensemble_llm",
                     "error": msg,
                 }),
+                Error::ModelNotAllowed(model) => serde_json::json!({
+                    "kind": "model_not_allowed",
+                    "error": format!("model not allowed: {}", model),
+                }),
                 Error::MultipleErrors(errors) => serde_json::json!({
                     "kind": "multiple_errors",
                     "errors": errors.iter().map(|e| {
@@ -2,6 +2,7 @@
 
 mod client;
 mod error;
+mod timeouts;
 /// Upstream provider clients (e.g., OpenRouter).
 pub mod upstream;
 /// Usage tracking handlers.
@@ -9,3 +10,4 @@ pub mod usage_handler;
 
 pub use client::*;
 pub use error::*;
+pub use timeouts::*;
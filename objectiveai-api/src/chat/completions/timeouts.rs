@@ -0,0 +1,111 @@
+//! Chunk timeouts for upstream streams, with per-model defaults.
+
+use std::{collections::HashMap, time::Duration};
+
+/// Shortest chunk timeout a request or default may set.
+pub const MIN_CHUNK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest chunk timeout a request or default may set.
+pub const MAX_CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long to wait for chunks of an upstream stream.
+///
+/// A request's own `first_chunk_timeout` and `other_chunk_timeout` take
+/// precedence, then the defaults of the upstream model, then the global
+/// defaults. Every timeout is clamped to
+/// [`MIN_CHUNK_TIMEOUT`]..=[`MAX_CHUNK_TIMEOUT`].
+#[derive(Debug, Clone)]
+pub struct ChunkTimeouts {
+    /// Default timeout for the first chunk.
+    pub first_chunk: Duration,
+    /// Default timeout for each chunk after the first.
+    pub other_chunk: Duration,
+    /// Defaults for specific upstream models, keyed by model name
+    /// (e.g. `openai/gpt-4o`).
+    pub models: HashMap<String, ModelChunkTimeouts>,
+}
+
+/// Chunk timeout defaults for a single upstream model. Unset timeouts fall
+/// back to the global defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModelChunkTimeouts {
+    /// Default timeout for the first chunk.
+    pub first_chunk: Option<Duration>,
+    /// Default timeout for each chunk after the first.
+    pub other_chunk: Option<Duration>,
+}
+
+impl Default for ChunkTimeouts {
+    fn default() -> Self {
+        Self {
+            first_chunk: Duration::from_secs(120),
+            other_chunk: Duration::from_secs(120),
+            models: HashMap::new(),
+        }
+    }
+}
+
+impl ChunkTimeouts {
+    /// Returns the first and other chunk timeouts for a request to `model`,
+    /// given the request's own timeouts in milliseconds.
+    pub fn resolve(
+        &self,
+        model: &str,
+        first_chunk_timeout: Option<u64>,
+        other_chunk_timeout: Option<u64>,
+    ) -> (Duration, Duration) {
+        let model = self.models.get(model).copied().unwrap_or_default();
+        let first_chunk = first_chunk_timeout
+            .map(Duration::from_millis)
+            .or(model.first_chunk)
+            .unwrap_or(self.first_chunk);
+        let other_chunk = other_chunk_timeout
+            .map(Duration::from_millis)
+            .or(model.other_chunk)
+            .unwrap_or(self.other_chunk);
+        (
+            first_chunk.clamp(MIN_CHUNK_TIMEOUT, MAX_CHUNK_TIMEOUT),
+            other_chunk.clamp(MIN_CHUNK_TIMEOUT, MAX_CHUNK_TIMEOUT),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_then_model_then_global() {
+        let timeouts = ChunkTimeouts {
+            models: HashMap::from([(
+                "openai/o3".to_string(),
+                ModelChunkTimeouts {
+                    first_chunk: Some(Duration::from_secs(90)),
+                    other_chunk: None,
+                },
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            timeouts.resolve("openai/o3", None, None),
+            (Duration::from_secs(90), Duration::from_secs(120)),
+        );
+        assert_eq!(
+            timeouts.resolve("openai/o3", Some(20_000), None),
+            (Duration::from_secs(20), Duration::from_secs(120)),
+        );
+        assert_eq!(
+            timeouts.resolve("openai/gpt-4o", None, None),
+            (Duration::from_secs(120), Duration::from_secs(120)),
+        );
+    }
+
+    #[test]
+    fn clamps_to_bounds() {
+        let timeouts = ChunkTimeouts::default();
+        assert_eq!(
+            timeouts.resolve("openai/gpt-4o", Some(1), Some(600_000)),
+            (MIN_CHUNK_TIMEOUT, MAX_CHUNK_TIMEOUT),
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! A local OpenRouter upstream which stands in for the LLMs in tests.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use tokio::sync::{Semaphore, mpsc};
+
+/// The message of every chat completion which isn't a vector completion.
+pub const MESSAGE: &str = "Hello from upstream.";
+
+/// A mock upstream which answers every chat completion in one chunk.
+///
+/// Vector completion requests get the first response key of their JSON
+/// schema, and every other request gets [`MESSAGE`].
+pub struct MockUpstream {
+    /// Base URL of the upstream.
+    pub api_base: String,
+    /// Receives a message for each chat completion request, as it arrives.
+    pub received: mpsc::UnboundedReceiver<()>,
+}
+
+impl MockUpstream {
+    /// Serves an upstream which answers each request immediately.
+    pub async fn spawn() -> Self {
+        Self::serve(None).await
+    }
+
+    /// Serves an upstream whose chat completions each wait for a permit of
+    /// `release` before answering.
+    pub async fn spawn_gated(release: Arc<Semaphore>) -> Self {
+        Self::serve(Some(release)).await
+    }
+
+    async fn serve(release: Option<Arc<Semaphore>>) -> Self {
+        let (received, receiver) = mpsc::unbounded_channel();
+        let app = axum::Router::new()
+            .route("/chat/completions", axum::routing::post(chat_completions))
+            .with_state((release, received));
+        let listener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Self {
+            api_base: format!("http://{}", address),
+            received: receiver,
+        }
+    }
+}
+
+async fn chat_completions(
+    axum::extract::State((release, received)): axum::extract::State<(
+        Option<Arc<Semaphore>>,
+        mpsc::UnboundedSender<()>,
+    )>,
+    axum::Json(body): axum::Json<serde_json::Value>,
+) -> impl axum::response::IntoResponse {
+    let _ = received.send(());
+    if let Some(release) = release {
+        release.acquire().await.unwrap().forget();
+    }
+    let content = match body.pointer(
+        "/response_format/json_schema/schema/properties/response_key/enum/0",
+    ) {
+        Some(key) => serde_json::json!({ "response_key": key }).to_string(),
+        None => MESSAGE.to_string(),
+    };
+    let chunk = serde_json::json!({
+        "id": "upstream",
+        "choices": [{
+            "index": 0,
+            "delta": { "role": "assistant", "content": content },
+            "finish_reason": "stop",
+        }],
+        "created": 0,
+        "model": body["model"],
+        "object": "chat.completion.chunk",
+    });
+    (
+        [("content-type", "text/event-stream")],
+        format!("data: {}\n\ndata: [DONE]\n\n", chunk),
+    )
+}
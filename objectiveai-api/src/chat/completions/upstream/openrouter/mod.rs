@@ -12,3 +12,6 @@ pub mod response;
 
 pub use client::*;
 pub use error::*;
+
+#[cfg(test)]
+pub mod mock_upstream;
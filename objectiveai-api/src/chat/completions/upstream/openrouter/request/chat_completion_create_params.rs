@@ -159,7 +159,8 @@ impl ChatCompletionCreateParams {
             messages: super::prompt::new_for_vector(
                 &request.responses,
                 vector_pfx_indices,
-                ensemble_llm.base.output_mode,
+                request.rating.as_ref(),
+                super::prompt::VectorPromptTemplate::new(&ensemble_llm.base),
                 ensemble_llm.base.prefix_messages.as_deref(),
                 &request.messages,
                 ensemble_llm.base.suffix_messages.as_deref(),
@@ -40,18 +40,49 @@ pub fn new_for_chat(
     }
 }
 
+/// How an Ensemble LLM words the voting prompt.
+#[derive(Debug, Clone, Copy)]
+pub struct VectorPromptTemplate<'a> {
+    /// Delimiters wrapped around each response key.
+    pub label_wrap: objectiveai::ensemble_llm::LabelWrap,
+    /// The prompt template, overriding the default wording, if any.
+    pub prompt_template: Option<&'a objectiveai::ensemble_llm::PromptTemplate>,
+    /// How the LLM is asked to output its vote.
+    pub output_mode: objectiveai::ensemble_llm::OutputMode,
+}
+
+impl<'a> VectorPromptTemplate<'a> {
+    /// The voting prompt wording of an Ensemble LLM.
+    pub fn new(ensemble_llm: &'a objectiveai::ensemble_llm::EnsembleLlmBase) -> Self {
+        Self {
+            label_wrap: ensemble_llm.label_wrap(),
+            prompt_template: ensemble_llm.prompt_template.as_ref(),
+            output_mode: ensemble_llm.output_mode,
+        }
+    }
+}
+
 /// Constructs the message array for a vector completion vote.
 ///
 /// Appends the labeled response options to the last user message and adds
-/// voting instructions to the system message based on the output mode.
+/// voting instructions to the system message based on the output mode. If
+/// rating, the options are presented as the values of the scale. The Ensemble
+/// LLM's prompt template, if any, overrides the default wording.
 pub fn new_for_vector(
     vector_responses: &[objectiveai::chat::completions::request::RichContent],
     vector_pfx_indices: &[(String, usize)],
-    ensemble_llm_output_mode: objectiveai::ensemble_llm::OutputMode,
+    vector_rating: Option<&objectiveai::vector::completions::request::RatingScale>,
+    ensemble_llm_template: VectorPromptTemplate<'_>,
     ensemble_llm_prefix: Option<&[objectiveai::chat::completions::request::Message]>,
     request: &[objectiveai::chat::completions::request::Message],
     ensemble_llm_suffix: Option<&[objectiveai::chat::completions::request::Message]>,
 ) -> Vec<objectiveai::chat::completions::request::Message> {
+    let VectorPromptTemplate {
+        label_wrap: ensemble_llm_label_wrap,
+        prompt_template: ensemble_llm_prompt_template,
+        output_mode: ensemble_llm_output_mode,
+    } = ensemble_llm_template;
+
     // convert vector responses into rich content parts for prompt
     let vector_responses_for_prompt = vector::completions::vector_responses::into_parts_for_prompt(
         vector_responses,
@@ -106,16 +137,54 @@ pub fn new_for_vector(
         }
         objectiveai::chat::completions::request::RichContent::Parts(parts) => parts,
     };
+    let keys = vector_pfx_indices
+        .iter()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>();
+    let select_template = match vector_rating {
+        Some(_) => ensemble_llm_prompt_template.and_then(|t| t.select_rating.as_deref()),
+        None => ensemble_llm_prompt_template.and_then(|t| t.select.as_deref()),
+    };
+    let (select, select_after) = match select_template {
+        Some(template) => {
+            let filled = objectiveai::ensemble_llm::PromptTemplate::fill(
+                template,
+                &keys,
+                ensemble_llm_label_wrap,
+                vector_rating.map(|rating| (rating.min, rating.max)),
+            );
+            let (before, after) =
+                objectiveai::ensemble_llm::PromptTemplate::split_responses(&filled);
+            (before.to_string(), after.to_string())
+        }
+        None => (
+            match vector_rating {
+                Some(rating) => format!(
+                    "Select the rating, from {} to {}:\n\n",
+                    rating.min, rating.max
+                ),
+                None => "Select the response:\n\n".to_string(),
+            },
+            String::new(),
+        ),
+    };
     user_append_content_parts.push(
         objectiveai::chat::completions::request::RichContentPart::Text {
             text: if user_append_content_parts.is_empty() {
-                "Select the response:\n\n".to_string()
+                select
             } else {
-                "\n\nSelect the response:\n\n".to_string()
+                format!("\n\n{}", select)
             },
         },
     );
     user_append_content_parts.extend(vector_responses_for_prompt);
+    if !select_after.is_empty() {
+        user_append_content_parts.push(
+            objectiveai::chat::completions::request::RichContentPart::Text {
+                text: select_after,
+            },
+        );
+    }
 
     // handle system message transform
     // append instruction to last system message, or create one if none
@@ -165,27 +234,32 @@ pub fn new_for_vector(
             }
             objectiveai::chat::completions::request::SimpleContent::Parts(parts) => parts,
         };
-        // TODO: optimize this, it allocates a vec and clones strings
+        let instruction_template =
+            ensemble_llm_prompt_template.and_then(|t| t.instruction.as_deref());
+        let instruction = match instruction_template {
+            Some(template) => objectiveai::ensemble_llm::PromptTemplate::fill(
+                template,
+                &keys,
+                ensemble_llm_label_wrap,
+                vector_rating.map(|rating| (rating.min, rating.max)),
+            ),
+            None if system_append_content_parts.is_empty() => format!(
+                "Output one response key including {}\n- {}",
+                ensemble_llm_label_wrap.name(),
+                keys.join("\n- ")
+            ),
+            None => format!(
+                "Output one response key including {}:\n- {}",
+                ensemble_llm_label_wrap.name(),
+                keys.join("\n- ")
+            ),
+        };
         system_append_content_parts.push(
             objectiveai::chat::completions::request::SimpleContentPart::Text {
                 text: if system_append_content_parts.is_empty() {
-                    format!(
-                        "Output one response key including backticks\n- {}",
-                        vector_pfx_indices
-                            .iter()
-                            .map(|(key, _)| key.clone())
-                            .collect::<Vec<_>>()
-                            .join("\n- ")
-                    )
+                    instruction
                 } else {
-                    format!(
-                        "\n\nOutput one response key including backticks:\n- {}",
-                        vector_pfx_indices
-                            .iter()
-                            .map(|(key, _)| key.clone())
-                            .collect::<Vec<_>>()
-                            .join("\n- ")
-                    )
+                    format!("\n\n{}", instruction)
                 },
             },
         );
@@ -72,3 +72,12 @@ impl<CTXEXT> Context<CTXEXT> {
         }
     }
 }
+
+impl<CTXEXT: super::FromParts> Context<CTXEXT> {
+    /// Creates a new context for an incoming request.
+    pub fn from_parts(parts: &axum::http::request::Parts) -> Self {
+        let ext = CTXEXT::from_parts(parts);
+        let cost_multiplier = ext.cost_multiplier();
+        Self::new(Arc::new(ext), cost_multiplier)
+    }
+}
@@ -1,6 +1,6 @@
 //! Context extension trait for per-request customization.
 
-use crate::chat;
+use crate::{auth, chat};
 
 /// Extension trait for providing per-request customization.
 ///
@@ -17,4 +17,23 @@ pub trait ContextExt {
         &self,
         upstream: chat::completions::upstream::Upstream,
     ) -> Result<Option<String>, objectiveai::error::ResponseError>;
+
+    /// Returns the authenticated caller of the request.
+    ///
+    /// Returns `None` if the server does not authenticate callers.
+    fn identity(&self) -> Option<&auth::Identity> {
+        None
+    }
+
+    /// Returns the caller's tenant.
+    ///
+    /// Returns `None` if the caller has no tenant, or the server has none.
+    fn tenant(&self) -> Option<&super::Tenant> {
+        None
+    }
+
+    /// Returns whether the caller may use the given upstream model.
+    fn allows_model(&self, _model: &str) -> bool {
+        true
+    }
 }
@@ -1,12 +1,14 @@
-use crate::chat;
-use axum::http::HeaderMap;
+use crate::{auth, chat};
+use axum::http::{HeaderMap, request::Parts};
 
 /// Default context extension that extracts OpenRouter BYOK from request headers.
 #[derive(Clone)]
 pub struct DefaultContextExt {
     /// OpenRouter API key from the `authorization_openrouter` header.
-    /// None if the header was not provided.
+    /// None if not provided.
     pub openrouter_byok: Option<String>,
+    /// The authenticated caller, if the server authenticates callers.
+    pub identity: Option<auth::Identity>,
 }
 
 impl DefaultContextExt {
@@ -26,20 +28,58 @@ impl DefaultContextExt {
                 }
             });
 
-        Self { openrouter_byok }
+        Self {
+            openrouter_byok,
+            identity: None,
+        }
+    }
+
+    /// Extracts the OpenRouter BYOK from request headers, and the caller's
+    /// [`Identity`](auth::Identity) from request extensions if it was
+    /// authenticated.
+    pub fn from_parts(parts: &Parts) -> Self {
+        let mut ext = Self::from_headers(&parts.headers);
+        ext.identity = parts.extensions.get::<auth::Identity>().cloned();
+        ext
+    }
+}
+
+impl super::FromParts for DefaultContextExt {
+    fn from_parts(parts: &Parts) -> Self {
+        DefaultContextExt::from_parts(parts)
     }
 }
 
 #[async_trait::async_trait]
 impl super::ContextExt for DefaultContextExt {
+    /// The `authorization_openrouter` header takes precedence over the
+    /// caller's stored BYOK key. Fails if the stored key is used but cannot
+    /// be decrypted.
     async fn get_byok(
         &self,
         upstream: chat::completions::upstream::Upstream,
     ) -> Result<Option<String>, objectiveai::error::ResponseError> {
         match upstream {
             chat::completions::upstream::Upstream::OpenRouter => {
-                Ok(self.openrouter_byok.clone())
+                if let Some(byok) = &self.openrouter_byok {
+                    return Ok(Some(byok.clone()));
+                }
+                match self
+                    .identity
+                    .as_ref()
+                    .and_then(|identity| identity.openrouter_byok.as_ref())
+                {
+                    Some(byok) => match byok.decrypt() {
+                        Ok(byok) => Ok(Some(byok.to_string())),
+                        Err(e) => Err(objectiveai::error::ResponseError::from(&e)),
+                    },
+                    None => Ok(None),
+                }
             }
         }
     }
+
+    fn identity(&self) -> Option<&auth::Identity> {
+        self.identity.as_ref()
+    }
 }
@@ -0,0 +1,46 @@
+//! Error types for resolving request contexts.
+
+/// Errors that can occur when resolving a caller's tenant.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The caller's API key belongs to no tenant, and tenants are required.
+    #[error("API key belongs to no tenant")]
+    NoTenant,
+    /// The tenant has used up its request quota for the current window.
+    #[error("tenant {tenant} exceeded its request quota")]
+    QuotaExceeded {
+        /// The tenant's ID.
+        tenant: String,
+        /// Seconds until the quota window resets.
+        retry_after_seconds: u64,
+    },
+}
+
+impl objectiveai::error::StatusError for Error {
+    fn status(&self) -> u16 {
+        match self {
+            Error::NoTenant => 403,
+            Error::QuotaExceeded { .. } => 429,
+        }
+    }
+
+    fn message(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "kind": "tenant",
+            "error": match self {
+                Error::NoTenant => serde_json::json!({
+                    "kind": "no_tenant",
+                    "error": "API key belongs to no tenant",
+                }),
+                Error::QuotaExceeded {
+                    tenant,
+                    retry_after_seconds,
+                } => serde_json::json!({
+                    "kind": "quota_exceeded",
+                    "error": format!("tenant {} exceeded its request quota", tenant),
+                    "retry_after_seconds": retry_after_seconds,
+                }),
+            }
+        }))
+    }
+}
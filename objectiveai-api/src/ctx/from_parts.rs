@@ -0,0 +1,19 @@
+//! Construction of context extensions from incoming requests.
+
+use axum::http::request::Parts;
+
+/// A context extension which the server can build from an incoming request.
+///
+/// Per-request state, such as the caller's [`Identity`](crate::auth::Identity)
+/// or [`Tenant`](super::Tenant), is inserted into the request's extensions by
+/// middleware and read back here.
+pub trait FromParts: Sized {
+    /// Builds the context extension from the request's headers and
+    /// extensions.
+    fn from_parts(parts: &Parts) -> Self;
+
+    /// The multiplier applied to the request's costs. Defaults to 1.
+    fn cost_multiplier(&self) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::ONE
+    }
+}
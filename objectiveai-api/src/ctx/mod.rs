@@ -2,12 +2,22 @@
 //!
 //! The context system allows per-request state and customization through
 //! the `ContextExt` trait. This enables features like BYOK (Bring Your Own Key)
-//! support where users can provide their own upstream API keys.
+//! support where users can provide their own upstream API keys, and
+//! multi-tenant servers where each tenant has its own credentials, pricing,
+//! allowed models and quotas.
 
 mod ctx;
 mod ctx_ext;
 mod default_ctx_ext;
+mod error;
+mod from_parts;
+mod tenant;
+mod tenant_ctx_ext;
 
 pub use ctx::*;
 pub use ctx_ext::*;
 pub use default_ctx_ext::*;
+pub use error::*;
+pub use from_parts::*;
+pub use tenant::*;
+pub use tenant_ctx_ext::*;
@@ -0,0 +1,439 @@
+//! Tenants: per-customer credentials, pricing, model access and quotas.
+
+use crate::{auth, chat};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A tenant of a multi-tenant server, resolved from the caller's API key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tenant {
+    /// Unique identifier of the tenant.
+    pub id: String,
+    /// Fingerprints of the API keys which belong to the tenant (see
+    /// [`LocalClient::fingerprint`](auth::LocalClient::fingerprint)).
+    pub api_keys: Vec<objectiveai::auth::ApiKey>,
+    /// The tenant's BYOK API keys for each upstream. Upstreams without a key
+    /// fall back to the request's BYOK, then the server's key.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub byok: HashMap<chat::completions::upstream::Upstream, String>,
+    /// Multiplier applied to the tenant's costs. Defaults to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_multiplier: Option<rust_decimal::Decimal>,
+    /// Upstream models the tenant may use (e.g. `openai/gpt-4o`). A trailing
+    /// `*` matches any suffix (e.g. `openai/*`). If `None`, all models are
+    /// allowed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_models: Option<Vec<String>>,
+    /// The tenant's request quota. If `None`, requests are unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<Quota>,
+}
+
+impl Tenant {
+    /// Whether the tenant may use the given upstream model.
+    pub fn allows_model(&self, model: &str) -> bool {
+        match &self.allowed_models {
+            Some(allowed_models) => allowed_models.iter().any(|allowed| {
+                match allowed.strip_suffix('*') {
+                    Some(prefix) => model.starts_with(prefix),
+                    None => model == allowed,
+                }
+            }),
+            None => true,
+        }
+    }
+}
+
+/// A fixed-window request quota.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Quota {
+    /// Maximum number of requests per window.
+    pub requests: u64,
+    /// Length of the window, in seconds.
+    pub period_seconds: u64,
+}
+
+/// The tenants configuration file.
+///
+/// ```json
+/// {
+///   "require_tenant": true,
+///   "tenants": [
+///     {
+///       "id": "acme",
+///       "api_keys": ["apk0123456789abcdef0123456789abcdef"],
+///       "byok": { "open_router": "sk-or-..." },
+///       "cost_multiplier": 1.2,
+///       "allowed_models": ["openai/*", "anthropic/claude-sonnet-4"],
+///       "quota": { "requests": 600, "period_seconds": 60 }
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenantsConfig {
+    /// Whether to reject callers whose API key belongs to no tenant. The
+    /// server's admin key is always accepted.
+    #[serde(default)]
+    pub require_tenant: bool,
+    /// The tenants.
+    #[serde(default)]
+    pub tenants: Vec<Tenant>,
+}
+
+impl TenantsConfig {
+    /// Parses a configuration file in the format given by its extension:
+    /// JSON, or TOML and YAML as for [`Settings`](crate::server::Settings).
+    pub fn parse(
+        path: &std::path::Path,
+        content: &str,
+    ) -> Result<Self, String> {
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            return serde_json::from_str(content).map_err(|e| e.to_string());
+        }
+        match crate::server::Format::from_path(path) {
+            Some(crate::server::Format::Toml) => {
+                toml::from_str(content).map_err(|e| e.to_string())
+            }
+            Some(crate::server::Format::Yaml) => {
+                serde_yaml::from_str(content).map_err(|e| e.to_string())
+            }
+            None => {
+                Err("unsupported format, expected .json, .toml, .yaml or .yml"
+                    .to_string())
+            }
+        }
+    }
+}
+
+/// Resolves tenants from API keys and enforces their quotas.
+#[derive(Debug)]
+pub struct Tenants {
+    /// Whether to reject callers whose API key belongs to no tenant.
+    pub require_tenant: bool,
+    /// Tenants by API key fingerprint.
+    by_api_key: HashMap<objectiveai::auth::ApiKey, Arc<Tenant>>,
+    /// Tenants by ID, for API keys created by a tenant's key.
+    by_id: HashMap<String, Arc<Tenant>>,
+    /// Start and request count of each tenant's current quota window.
+    windows: DashMap<String, (std::time::Instant, u64)>,
+}
+
+impl Tenants {
+    /// Validates the configuration and creates a tenant registry.
+    pub fn new(config: TenantsConfig) -> Result<Self, String> {
+        let mut ids = std::collections::HashSet::new();
+        let mut by_api_key = HashMap::new();
+        let mut by_id = HashMap::new();
+        for tenant in config.tenants {
+            if tenant.id.is_empty() {
+                return Err("tenant `id` cannot be empty".to_string());
+            }
+            if !ids.insert(tenant.id.clone()) {
+                return Err(format!("duplicate tenant `{}`", tenant.id));
+            }
+            if tenant
+                .cost_multiplier
+                .is_some_and(|m| m < rust_decimal::Decimal::ZERO)
+            {
+                return Err(format!(
+                    "tenant `{}`: `cost_multiplier` cannot be negative",
+                    tenant.id
+                ));
+            }
+            if let Some(quota) = tenant.quota
+                && (quota.requests == 0 || quota.period_seconds == 0)
+            {
+                return Err(format!(
+                    "tenant `{}`: `quota` must allow at least 1 request per \
+                     non-empty period",
+                    tenant.id
+                ));
+            }
+            let tenant = Arc::new(tenant);
+            for api_key in &tenant.api_keys {
+                if by_api_key.insert(*api_key, tenant.clone()).is_some() {
+                    return Err(format!(
+                        "API key `{}` belongs to more than one tenant",
+                        api_key
+                    ));
+                }
+            }
+            by_id.insert(tenant.id.clone(), tenant);
+        }
+        Ok(Self {
+            require_tenant: config.require_tenant,
+            by_api_key,
+            by_id,
+            windows: DashMap::new(),
+        })
+    }
+
+    /// Reads, validates, and creates a tenant registry from a `.json`,
+    /// `.toml`, `.yaml` or `.yml` configuration file.
+    pub async fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let config = TenantsConfig::parse(path, &content).map_err(|e| {
+            format!("failed to parse {}: {}", path.display(), e)
+        })?;
+        Self::new(config)
+    }
+
+    /// Resolves the caller's tenant and counts the request against its
+    /// quota.
+    ///
+    /// A key created by a tenant's key belongs to that tenant, and is
+    /// rejected if the tenant no longer exists. Returns `Ok(None)` for
+    /// callers without a tenant, if allowed.
+    pub fn resolve(
+        &self,
+        identity: Option<&auth::Identity>,
+    ) -> Result<Option<Arc<Tenant>>, super::Error> {
+        let tenant = match identity {
+            // the admin key has no tenant
+            Some(auth::Identity { api_key: None, .. }) => return Ok(None),
+            Some(auth::Identity {
+                tenant: Some(id), ..
+            }) => Some(
+                self.by_id.get(id).cloned().ok_or(super::Error::NoTenant)?,
+            ),
+            Some(auth::Identity {
+                api_key: Some(api_key),
+                ..
+            }) => self.by_api_key.get(api_key).cloned(),
+            None => None,
+        };
+        match tenant {
+            Some(tenant) => {
+                self.acquire(&tenant)?;
+                Ok(Some(tenant))
+            }
+            None if self.require_tenant => Err(super::Error::NoTenant),
+            None => Ok(None),
+        }
+    }
+
+    /// Counts a request against the tenant's quota.
+    fn acquire(&self, tenant: &Tenant) -> Result<(), super::Error> {
+        let Some(quota) = tenant.quota else {
+            return Ok(());
+        };
+        let now = std::time::Instant::now();
+        let period = std::time::Duration::from_secs(quota.period_seconds);
+        let mut window =
+            self.windows.entry(tenant.id.clone()).or_insert((now, 0));
+        if now.duration_since(window.0) >= period {
+            *window = (now, 0);
+        }
+        if window.1 >= quota.requests {
+            let retry_after =
+                period.saturating_sub(now.duration_since(window.0));
+            return Err(super::Error::QuotaExceeded {
+                tenant: tenant.id.clone(),
+                retry_after_seconds: retry_after.as_secs().max(1),
+            });
+        }
+        window.1 += 1;
+        Ok(())
+    }
+}
+
+/// Axum middleware which resolves the caller's [`Tenant`] and inserts it into
+/// the request's extensions. Rejects callers over quota with 429, and callers
+/// without a tenant with 403 if tenants are required.
+///
+/// Must run after [`auth::authenticate`], which provides the caller's
+/// identity. Use with [`axum::middleware::from_fn_with_state`].
+pub async fn resolve_tenant(
+    axum::extract::State(tenants): axum::extract::State<Arc<Tenants>>,
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let identity = request.extensions().get::<auth::Identity>();
+    match tenants.resolve(identity) {
+        Ok(Some(tenant)) => {
+            request.extensions_mut().insert(tenant);
+            next.run(request).await
+        }
+        Ok(None) => next.run(request).await,
+        Err(e) => crate::error::ResponseErrorExt::into_response(
+            objectiveai::error::ResponseError::from(&e),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(api_key: objectiveai::auth::ApiKey) -> auth::Identity {
+        auth::Identity {
+            api_key: Some(api_key),
+            tenant: None,
+            openrouter_byok: None,
+        }
+    }
+
+    fn tenant(id: &str, api_key: objectiveai::auth::ApiKey) -> Tenant {
+        Tenant {
+            id: id.to_string(),
+            api_keys: vec![api_key],
+            byok: HashMap::new(),
+            cost_multiplier: None,
+            allowed_models: None,
+            quota: None,
+        }
+    }
+
+    #[test]
+    fn allowed_models_match_exactly_or_by_prefix() {
+        let mut tenant = tenant("acme", objectiveai::auth::ApiKey::new());
+        tenant.allowed_models =
+            Some(vec!["openai/*".to_string(), "x-ai/grok-4".to_string()]);
+        assert!(tenant.allows_model("openai/gpt-4o"));
+        assert!(tenant.allows_model("x-ai/grok-4"));
+        assert!(!tenant.allows_model("x-ai/grok-4-fast"));
+        assert!(!tenant.allows_model("anthropic/claude-sonnet-4"));
+    }
+
+    #[test]
+    fn quota_rejects_requests_over_limit() {
+        let api_key = objectiveai::auth::ApiKey::new();
+        let mut acme = tenant("acme", api_key);
+        acme.quota = Some(Quota {
+            requests: 2,
+            period_seconds: 60,
+        });
+        let tenants = Tenants::new(TenantsConfig {
+            require_tenant: false,
+            tenants: vec![acme],
+        })
+        .unwrap();
+        let identity = identity(api_key);
+        assert!(tenants.resolve(Some(&identity)).unwrap().is_some());
+        assert!(tenants.resolve(Some(&identity)).unwrap().is_some());
+        assert!(matches!(
+            tenants.resolve(Some(&identity)),
+            Err(super::super::Error::QuotaExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_callers_rejected_if_tenant_required() {
+        let tenants = Tenants::new(TenantsConfig {
+            require_tenant: true,
+            tenants: vec![tenant("acme", objectiveai::auth::ApiKey::new())],
+        })
+        .unwrap();
+        let unknown = identity(objectiveai::auth::ApiKey::new());
+        assert!(matches!(
+            tenants.resolve(Some(&unknown)),
+            Err(super::super::Error::NoTenant)
+        ));
+        let admin = auth::Identity {
+            api_key: None,
+            tenant: None,
+            openrouter_byok: None,
+        };
+        assert!(tenants.resolve(Some(&admin)).unwrap().is_none());
+    }
+
+    #[test]
+    fn config_parses_json_toml_and_yaml() {
+        let api_key = objectiveai::auth::ApiKey::new();
+        let json = format!(
+            r#"{{ "require_tenant": true, "tenants": [{{ "id": "acme", "api_keys": ["{}"], "byok": {{ "open_router": "sk-or-acme" }}, "cost_multiplier": 1.5 }}] }}"#,
+            api_key
+        );
+        let toml = format!(
+            "require_tenant = true\n\n[[tenants]]\nid = \"acme\"\napi_keys = [\"{}\"]\ncost_multiplier = 1.5\n\n[tenants.byok]\nopen_router = \"sk-or-acme\"\n",
+            api_key
+        );
+        let yaml = format!(
+            "require_tenant: true\ntenants:\n  - id: acme\n    api_keys: [\"{}\"]\n    byok:\n      open_router: sk-or-acme\n    cost_multiplier: 1.5\n",
+            api_key
+        );
+        for (path, content) in [
+            ("tenants.json", json),
+            ("tenants.toml", toml),
+            ("tenants.yml", yaml),
+        ] {
+            let config =
+                TenantsConfig::parse(std::path::Path::new(path), &content)
+                    .unwrap();
+            assert!(config.require_tenant);
+            let tenant = &config.tenants[0];
+            assert_eq!(tenant.id, "acme");
+            assert_eq!(tenant.api_keys, vec![api_key]);
+            assert_eq!(
+                tenant
+                    .byok
+                    .get(&chat::completions::upstream::Upstream::OpenRouter)
+                    .map(String::as_str),
+                Some("sk-or-acme")
+            );
+            assert_eq!(
+                tenant.cost_multiplier,
+                Some(rust_decimal::Decimal::new(15, 1))
+            );
+        }
+        assert!(
+            TenantsConfig::parse(std::path::Path::new("tenants.txt"), "")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn config_rejects_shared_api_keys() {
+        let api_key = objectiveai::auth::ApiKey::new();
+        assert!(
+            Tenants::new(TenantsConfig {
+                require_tenant: false,
+                tenants: vec![tenant("a", api_key), tenant("b", api_key)],
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn keys_created_by_a_tenant_resolve_to_it() {
+        let mut acme = tenant("acme", objectiveai::auth::ApiKey::new());
+        acme.quota = Some(Quota {
+            requests: 1,
+            period_seconds: 60,
+        });
+        let tenants = Tenants::new(TenantsConfig {
+            require_tenant: false,
+            tenants: vec![acme],
+        })
+        .unwrap();
+        let created = auth::Identity {
+            tenant: Some("acme".to_string()),
+            ..identity(objectiveai::auth::ApiKey::new())
+        };
+        let resolved = tenants.resolve(Some(&created)).unwrap().unwrap();
+        assert_eq!(resolved.id, "acme");
+        // the created key shares the tenant's quota
+        assert!(matches!(
+            tenants.resolve(Some(&created)),
+            Err(super::super::Error::QuotaExceeded { .. })
+        ));
+        // and is rejected once its tenant is gone, even if tenants are not
+        // required
+        let orphan = auth::Identity {
+            tenant: Some("globex".to_string()),
+            ..identity(objectiveai::auth::ApiKey::new())
+        };
+        assert!(matches!(
+            tenants.resolve(Some(&orphan)),
+            Err(super::super::Error::NoTenant)
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+//! Tenant-aware context extension.
+
+use crate::{auth, chat};
+use axum::http::request::Parts;
+use std::sync::Arc;
+
+/// Context extension for multi-tenant servers.
+///
+/// Supplies the tenant's BYOK keys, cost multiplier, and allowed models,
+/// falling back to [`DefaultContextExt`](super::DefaultContextExt) for
+/// callers without a tenant.
+#[derive(Clone)]
+pub struct TenantContextExt {
+    /// The BYOK keys and identity from the request.
+    pub inner: super::DefaultContextExt,
+    /// The caller's tenant, as resolved by
+    /// [`resolve_tenant`](super::resolve_tenant).
+    pub tenant: Option<Arc<super::Tenant>>,
+}
+
+impl TenantContextExt {
+    /// Extracts the request's BYOK keys and identity, and the caller's
+    /// [`Tenant`](super::Tenant) from request extensions if it was resolved.
+    pub fn from_parts(parts: &Parts) -> Self {
+        Self {
+            inner: super::DefaultContextExt::from_parts(parts),
+            tenant: parts.extensions.get::<Arc<super::Tenant>>().cloned(),
+        }
+    }
+
+    /// The multiplier applied to the caller's costs.
+    pub fn cost_multiplier(&self) -> rust_decimal::Decimal {
+        self.tenant
+            .as_ref()
+            .and_then(|tenant| tenant.cost_multiplier)
+            .unwrap_or(rust_decimal::Decimal::ONE)
+    }
+}
+
+impl super::FromParts for TenantContextExt {
+    fn from_parts(parts: &Parts) -> Self {
+        TenantContextExt::from_parts(parts)
+    }
+
+    fn cost_multiplier(&self) -> rust_decimal::Decimal {
+        TenantContextExt::cost_multiplier(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl super::ContextExt for TenantContextExt {
+    /// A tenant's callers use the tenant's BYOK key, else the request's. They
+    /// never use the account's stored BYOK key, which callers without a
+    /// tenant fall back to.
+    async fn get_byok(
+        &self,
+        upstream: chat::completions::upstream::Upstream,
+    ) -> Result<Option<String>, objectiveai::error::ResponseError> {
+        let Some(tenant) = &self.tenant else {
+            return self.inner.get_byok(upstream).await;
+        };
+        match tenant.byok.get(&upstream) {
+            Some(byok) => Ok(Some(byok.clone())),
+            None => match upstream {
+                chat::completions::upstream::Upstream::OpenRouter => {
+                    Ok(self.inner.openrouter_byok.clone())
+                }
+            },
+        }
+    }
+
+    fn identity(&self) -> Option<&auth::Identity> {
+        self.inner.identity()
+    }
+
+    fn tenant(&self) -> Option<&super::Tenant> {
+        self.tenant.as_deref()
+    }
+
+    fn allows_model(&self, model: &str) -> bool {
+        self.tenant
+            .as_ref()
+            .is_none_or(|tenant| tenant.allows_model(model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ContextExt;
+    use super::*;
+    use chat::completions::upstream::Upstream;
+
+    /// A caller's identity, with the account's stored BYOK key.
+    async fn identity() -> auth::Identity {
+        let client = auth::LocalClient::new(None, &[7; 32], Some("admin"))
+            .await
+            .unwrap();
+        let admin = super::super::Context::new(
+            Arc::new(super::super::DefaultContextExt {
+                openrouter_byok: None,
+                identity: Some(auth::Identity {
+                    api_key: None,
+                    tenant: None,
+                    openrouter_byok: None,
+                }),
+            }),
+            rust_decimal::Decimal::ONE,
+        );
+        auth::Client::create_openrouter_byok_api_key(
+            &client,
+            admin,
+            objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest {
+                api_key: "sk-or-account".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        client.authenticate(Some("admin")).await.unwrap()
+    }
+
+    async fn ext(
+        header_byok: Option<&str>,
+        tenant_byok: Option<&str>,
+        has_tenant: bool,
+    ) -> TenantContextExt {
+        let tenant = super::super::Tenant {
+            id: "acme".to_string(),
+            api_keys: Vec::new(),
+            byok: tenant_byok
+                .map(|byok| (Upstream::OpenRouter, byok.to_string()))
+                .into_iter()
+                .collect(),
+            cost_multiplier: None,
+            allowed_models: None,
+            quota: None,
+        };
+        TenantContextExt {
+            inner: super::super::DefaultContextExt {
+                openrouter_byok: header_byok.map(str::to_string),
+                identity: Some(identity().await),
+            },
+            tenant: has_tenant.then(|| Arc::new(tenant)),
+        }
+    }
+
+    #[tokio::test]
+    async fn tenants_never_use_the_account_byok() {
+        let byok = |ext: TenantContextExt| async move {
+            ext.get_byok(Upstream::OpenRouter).await.unwrap()
+        };
+        assert_eq!(
+            byok(ext(None, Some("sk-or-acme"), true).await)
+                .await
+                .as_deref(),
+            Some("sk-or-acme")
+        );
+        assert_eq!(
+            byok(ext(Some("sk-or-header"), None, true).await)
+                .await
+                .as_deref(),
+            Some("sk-or-header")
+        );
+        assert_eq!(byok(ext(None, None, true).await).await, None);
+        // callers without a tenant fall back to the account's key
+        assert_eq!(
+            byok(ext(None, None, false).await).await.as_deref(),
+            Some("sk-or-account")
+        );
+    }
+}
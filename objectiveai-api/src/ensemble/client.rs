@@ -7,7 +7,7 @@ use std::sync::Arc;
 ///
 /// Combines a caching fetcher for ensemble definitions with a retrieval
 /// client for listing and usage statistics.
-pub struct Client<CTXEXT, FENS, RTRVL> {
+pub struct Client<CTXEXT, FENS: ?Sized, RTRVL: ?Sized> {
     /// Caching fetcher for ensemble definitions.
     pub ensemble_fetcher: Arc<super::fetcher::CachingFetcher<CTXEXT, FENS>>,
     /// Client for listing ensembles and getting usage.
@@ -16,7 +16,7 @@ pub struct Client<CTXEXT, FENS, RTRVL> {
     pub _ctx_ext: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FENS, RTRVL> Client<CTXEXT, FENS, RTRVL> {
+impl<CTXEXT, FENS: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FENS, RTRVL> {
     /// Creates a new ensemble client.
     pub fn new(
         ensemble_fetcher: Arc<
@@ -32,7 +32,7 @@ impl<CTXEXT, FENS, RTRVL> Client<CTXEXT, FENS, RTRVL> {
     }
 }
 
-impl<CTXEXT, FENS, RTRVL> Client<CTXEXT, FENS, RTRVL>
+impl<CTXEXT, FENS: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FENS, RTRVL>
 where
     CTXEXT: Send + Sync + 'static,
     FENS: super::fetcher::Fetcher<CTXEXT>
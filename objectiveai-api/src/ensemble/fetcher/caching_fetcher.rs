@@ -10,13 +10,13 @@ use std::sync::Arc;
 /// ensures only one actual fetch is performed. Subsequent requests for the
 /// same ensemble ID within the same request context share the result.
 #[derive(Debug, Clone)]
-pub struct CachingFetcher<CTXEXT, FENS> {
+pub struct CachingFetcher<CTXEXT, FENS: ?Sized> {
     /// The underlying fetcher to delegate to on cache miss.
     pub inner: Arc<FENS>,
     _marker: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FENS> CachingFetcher<CTXEXT, FENS> {
+impl<CTXEXT, FENS: ?Sized> CachingFetcher<CTXEXT, FENS> {
     /// Creates a new caching fetcher wrapping the given inner fetcher.
     pub fn new(inner: Arc<FENS>) -> Self {
         Self {
@@ -26,7 +26,7 @@ impl<CTXEXT, FENS> CachingFetcher<CTXEXT, FENS> {
     }
 }
 
-impl<CTXEXT, FENS> CachingFetcher<CTXEXT, FENS>
+impl<CTXEXT, FENS: ?Sized> CachingFetcher<CTXEXT, FENS>
 where
     CTXEXT: Send + Sync + 'static,
     FENS: super::Fetcher<CTXEXT> + Send + Sync + 'static,
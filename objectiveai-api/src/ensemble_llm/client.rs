@@ -7,7 +7,7 @@ use std::sync::Arc;
 ///
 /// Combines a caching fetcher for Ensemble LLM definitions with a retrieval
 /// client for listing and usage statistics.
-pub struct Client<CTXEXT, FENSLLM, RTRVL> {
+pub struct Client<CTXEXT, FENSLLM: ?Sized, RTRVL: ?Sized> {
     /// Caching fetcher for Ensemble LLM definitions.
     pub ensemble_llm_fetcher: Arc<super::fetcher::CachingFetcher<CTXEXT, FENSLLM>>,
     /// Client for listing Ensemble LLMs and getting usage.
@@ -16,7 +16,7 @@ pub struct Client<CTXEXT, FENSLLM, RTRVL> {
     pub _ctx_ext: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FENSLLM, RTRVL> Client<CTXEXT, FENSLLM, RTRVL> {
+impl<CTXEXT, FENSLLM: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FENSLLM, RTRVL> {
     /// Creates a new Ensemble LLM client.
     pub fn new(
         ensemble_llm_fetcher: Arc<
@@ -35,7 +35,7 @@ impl<CTXEXT, FENSLLM, RTRVL> Client<CTXEXT, FENSLLM, RTRVL> {
     }
 }
 
-impl<CTXEXT, FENSLLM, RTRVL> Client<CTXEXT, FENSLLM, RTRVL>
+impl<CTXEXT, FENSLLM: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FENSLLM, RTRVL>
 where
     CTXEXT: Send + Sync + 'static,
     FENSLLM: super::fetcher::Fetcher<CTXEXT>
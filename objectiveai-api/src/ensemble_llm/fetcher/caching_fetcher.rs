@@ -10,13 +10,13 @@ use std::sync::Arc;
 /// ensures only one actual fetch is performed. Subsequent requests for the
 /// same Ensemble LLM ID within the same request context share the result.
 #[derive(Debug, Clone)]
-pub struct CachingFetcher<CTXEXT, FENSLLM> {
+pub struct CachingFetcher<CTXEXT, FENSLLM: ?Sized> {
     /// The underlying fetcher to delegate to on cache miss.
     pub inner: Arc<FENSLLM>,
     _marker: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FENSLLM> CachingFetcher<CTXEXT, FENSLLM> {
+impl<CTXEXT, FENSLLM: ?Sized> CachingFetcher<CTXEXT, FENSLLM> {
     /// Creates a new caching fetcher wrapping the given inner fetcher.
     pub fn new(inner: Arc<FENSLLM>) -> Self {
         Self {
@@ -26,7 +26,7 @@ impl<CTXEXT, FENSLLM> CachingFetcher<CTXEXT, FENSLLM> {
     }
 }
 
-impl<CTXEXT, FENSLLM> CachingFetcher<CTXEXT, FENSLLM>
+impl<CTXEXT, FENSLLM: ?Sized> CachingFetcher<CTXEXT, FENSLLM>
 where
     CTXEXT: Send + Sync + 'static,
     FENSLLM: super::Fetcher<CTXEXT> + Send + Sync + 'static,
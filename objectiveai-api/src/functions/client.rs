@@ -4,7 +4,7 @@ use crate::ctx;
 use std::sync::Arc;
 
 /// Client for function operations.
-pub struct Client<CTXEXT, FFN, RTRVL> {
+pub struct Client<CTXEXT, FFN: ?Sized, RTRVL: ?Sized> {
     /// Fetcher for Function definitions.
     pub function_fetcher: Arc<FFN>,
     /// Client for listing functions and getting usage statistics.
@@ -12,7 +12,7 @@ pub struct Client<CTXEXT, FFN, RTRVL> {
     pub _ctx_ext: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FFN, RTRVL> Client<CTXEXT, FFN, RTRVL> {
+impl<CTXEXT, FFN: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FFN, RTRVL> {
     /// Creates a new functions client.
     pub fn new(
         function_fetcher: Arc<FFN>,
@@ -26,7 +26,7 @@ impl<CTXEXT, FFN, RTRVL> Client<CTXEXT, FFN, RTRVL> {
     }
 }
 
-impl<CTXEXT, FFN, RTRVL> Client<CTXEXT, FFN, RTRVL>
+impl<CTXEXT, FFN: ?Sized, RTRVL: ?Sized> Client<CTXEXT, FFN, RTRVL>
 where
     CTXEXT: Send + Sync + 'static,
     FFN: super::function_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     hash::Hasher,
-    sync::Arc,
+    sync::{Arc, atomic::AtomicU64},
     time,
 };
 
@@ -26,19 +26,25 @@ pub fn vector_response_id(created: u64) -> String {
     format!("vctfnc-{}-{}", uuid.simple(), created)
 }
 
-/// Computes the final function output as a weighted average of task outputs.
+/// Computes the final function output by aggregating task outputs, by
+/// default as a weighted average.
 ///
 /// All task outputs are already validated `FunctionOutput` (scalar or vector)
-/// by their respective output expressions. This function is deterministically
-/// infallible - all inputs are assumed valid.
+/// by their respective output expressions. Built-in aggregation methods are
+/// deterministically infallible - all inputs are assumed valid. An
+/// aggregation expression may fail, or produce an invalid output.
 ///
 /// The weights are L1-normalized for the indices that are present (non-None, non-error).
 fn compute_weighted_function_output(
     function_type: &functions::FunctionType,
     profile_weights: &[rust_decimal::Decimal],
     task_outputs: &[Option<objectiveai::functions::expression::FunctionOutput>],
-) -> objectiveai::functions::expression::FunctionOutput {
+    aggregation: Option<&objectiveai::functions::Aggregation>,
+    input: &objectiveai::functions::expression::Input,
+    definitions: Option<&objectiveai::functions::expression::Definitions>,
+) -> Result<objectiveai::functions::expression::FunctionOutput, super::Error> {
     use objectiveai::functions::expression::FunctionOutput;
+    use objectiveai::functions::{Aggregation, AggregationMethod};
     use rust_decimal::Decimal;
 
     // Collect (weight, FunctionOutput) pairs from present task outputs
@@ -67,25 +73,59 @@ fn compute_weighted_function_output(
 
     // If no valid outputs, return error (shouldn't happen if caller filters properly)
     if weighted_outputs.is_empty() || total_weight == Decimal::ZERO {
-        return FunctionOutput::Err(serde_json::Value::Null);
+        return Ok(FunctionOutput::Err(serde_json::Value::Null));
     }
 
-    // Compute weighted average with L1-normalized weights
+    let method = match aggregation {
+        None => AggregationMethod::WeightedMean,
+        Some(Aggregation::Method(method)) => *method,
+        Some(Aggregation::Expression(expression)) => {
+            return aggregate_with_expression(
+                function_type,
+                profile_weights,
+                task_outputs,
+                expression,
+                input,
+                definitions,
+            );
+        }
+    };
+
+    // raw weights for product of experts, L1-normalized weights otherwise
+    let raw_weights: Vec<Decimal> =
+        weighted_outputs.iter().map(|(weight, _)| *weight).collect();
+    let weights: Vec<Decimal> = raw_weights
+        .iter()
+        .map(|weight| *weight / total_weight)
+        .collect();
+
     match function_type {
         functions::FunctionType::Scalar => {
-            let mut weighted_sum = Decimal::ZERO;
-            for (weight, fn_output) in &weighted_outputs {
-                match fn_output {
-                    FunctionOutput::Scalar(s) => {
-                        // L1-normalize: weight / total_weight
-                        weighted_sum += (*weight / total_weight) * s;
-                    }
+            let values: Vec<Decimal> = weighted_outputs
+                .iter()
+                .map(|(_, fn_output)| match fn_output {
+                    FunctionOutput::Scalar(s) => *s,
                     _ => {
                         panic!("expected scalar output in scalar function, got {:?}", fn_output);
                     }
+                })
+                .collect();
+            let output = match method {
+                // the probability of a binary outcome, against its complement
+                AggregationMethod::ProductOfExperts => {
+                    let complements: Vec<Decimal> =
+                        values.iter().map(|s| Decimal::ONE - s).collect();
+                    let yes = weighted_product(&values, &raw_weights);
+                    let no = weighted_product(&complements, &raw_weights);
+                    if yes + no == Decimal::ZERO {
+                        Decimal::new(5, 1)
+                    } else {
+                        yes / (yes + no)
+                    }
                 }
-            }
-            FunctionOutput::Scalar(weighted_sum)
+                method => aggregate_values(method, &values, &weights),
+            };
+            Ok(FunctionOutput::Scalar(output))
         }
         functions::FunctionType::Vector { .. } => {
             // Get vector length from first output
@@ -97,28 +137,177 @@ fn compute_weighted_function_output(
                 })
                 .expect("expected at least one vector output");
 
-            // Compute weighted average for each element with L1-normalized weights
-            let mut result = vec![Decimal::ZERO; vec_len];
-            for (weight, fn_output) in &weighted_outputs {
-                match fn_output {
+            let vectors: Vec<&[Decimal]> = weighted_outputs
+                .iter()
+                .map(|(_, fn_output)| match fn_output {
                     FunctionOutput::Vector(v) => {
                         if v.len() != vec_len {
                             panic!("vector length mismatch: expected {}, got {}", vec_len, v.len());
                         }
-                        let normalized_weight = *weight / total_weight;
-                        for (j, val) in v.iter().enumerate() {
-                            result[j] += normalized_weight * val;
-                        }
+                        v.as_slice()
                     }
                     _ => {
                         panic!("expected vector output in vector function, got {:?}", fn_output);
                     }
+                })
+                .collect();
+
+            // Aggregate each element across task outputs
+            let mut result = Vec::with_capacity(vec_len);
+            for j in 0..vec_len {
+                let values: Vec<Decimal> =
+                    vectors.iter().map(|v| v[j]).collect();
+                result.push(match method {
+                    AggregationMethod::ProductOfExperts => {
+                        weighted_product(&values, &raw_weights)
+                    }
+                    method => aggregate_values(method, &values, &weights),
+                });
+            }
+
+            // The weighted mean of vectors summing to 1 already sums to 1
+            if method != AggregationMethod::WeightedMean {
+                let sum: Decimal = result.iter().sum();
+                if sum == Decimal::ZERO {
+                    result.fill(Decimal::ONE / Decimal::from(vec_len));
+                } else {
+                    for x in &mut result {
+                        *x /= sum;
+                    }
+                }
+            }
+            Ok(FunctionOutput::Vector(result))
+        }
+    }
+}
+
+/// Aggregates values with L1-normalized weights using a built-in method.
+fn aggregate_values(
+    method: objectiveai::functions::AggregationMethod,
+    values: &[rust_decimal::Decimal],
+    weights: &[rust_decimal::Decimal],
+) -> rust_decimal::Decimal {
+    use objectiveai::functions::AggregationMethod;
+    use rust_decimal::Decimal;
+
+    match method {
+        AggregationMethod::WeightedMean => values
+            .iter()
+            .zip(weights)
+            .map(|(value, weight)| value * weight)
+            .sum(),
+        AggregationMethod::WeightedMedian => {
+            let mut pairs: Vec<(Decimal, Decimal)> = values
+                .iter()
+                .copied()
+                .zip(weights.iter().copied())
+                .collect();
+            pairs.sort_by_key(|(value, _)| *value);
+            let half = Decimal::new(5, 1);
+            let mut cumulative = Decimal::ZERO;
+            for (value, weight) in &pairs {
+                cumulative += weight;
+                if cumulative >= half {
+                    return *value;
                 }
             }
-            FunctionOutput::Vector(result)
+            pairs.last().map(|(value, _)| *value).unwrap_or(Decimal::ZERO)
+        }
+        AggregationMethod::GeometricMean
+        | AggregationMethod::ProductOfExperts => {
+            weighted_product(values, weights)
+        }
+        AggregationMethod::Max => {
+            values.iter().copied().max().unwrap_or(Decimal::ZERO)
+        }
+        AggregationMethod::Min => {
+            values.iter().copied().min().unwrap_or(Decimal::ZERO)
+        }
+    }
+}
+
+/// Computes the product of values, each raised to the power of its weight.
+///
+/// Zero if any value is zero (or negative).
+fn weighted_product(
+    values: &[rust_decimal::Decimal],
+    weights: &[rust_decimal::Decimal],
+) -> rust_decimal::Decimal {
+    use rust_decimal::{Decimal, MathematicalOps};
+
+    let mut log_sum = Decimal::ZERO;
+    for (value, weight) in values.iter().zip(weights) {
+        if *value <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        match value.checked_ln() {
+            Some(ln) => log_sum += ln * weight,
+            None => return Decimal::ZERO,
         }
     }
+    log_sum.checked_exp().unwrap_or(Decimal::ZERO)
 }
+
+/// Computes the final function output with an aggregation expression.
+///
+/// The expression receives `tasks` (every task output, `null` if skipped or
+/// failed) and `weights` (every task's profile weight), and its output is
+/// validated against the function type.
+fn aggregate_with_expression(
+    function_type: &functions::FunctionType,
+    profile_weights: &[rust_decimal::Decimal],
+    task_outputs: &[Option<objectiveai::functions::expression::FunctionOutput>],
+    expression: &objectiveai::functions::expression::Expression,
+    input: &objectiveai::functions::expression::Input,
+    definitions: Option<&objectiveai::functions::expression::Definitions>,
+) -> Result<objectiveai::functions::expression::FunctionOutput, super::Error> {
+    use objectiveai::functions::expression::{FunctionOutput, Params, ParamsRef};
+    use rust_decimal::Decimal;
+
+    let tasks: Vec<Option<FunctionOutput>> = task_outputs
+        .iter()
+        .map(|task_output| {
+            task_output
+                .clone()
+                .filter(|output| !matches!(output, FunctionOutput::Err(_)))
+        })
+        .collect();
+    let params = Params::Ref(ParamsRef {
+        input,
+        output: None,
+        map: None,
+        definitions,
+        tasks: Some(&tasks),
+        weights: Some(profile_weights),
+    });
+
+    match (function_type, expression.compile_one::<FunctionOutput>(&params)?) {
+        (functions::FunctionType::Scalar, FunctionOutput::Scalar(s))
+            if s >= rust_decimal::dec!(-0.01) && s <= rust_decimal::dec!(1.01) =>
+        {
+            Ok(FunctionOutput::Scalar(s))
+        }
+        (functions::FunctionType::Scalar, _) => {
+            Err(super::Error::InvalidScalarOutput)
+        }
+        (functions::FunctionType::Vector { output_length, .. }, output) => {
+            if let FunctionOutput::Vector(v) = &output {
+                let sum: Decimal = v.iter().cloned().sum();
+                let len_ok = output_length.is_none_or(|len| len == v.len() as u64);
+                let sum_ok = sum >= rust_decimal::dec!(0.99) && sum <= rust_decimal::dec!(1.01);
+                if len_ok && sum_ok {
+                    return Ok(output);
+                }
+            }
+            let err_len = match &output {
+                FunctionOutput::Vector(v) => output_length.unwrap_or(v.len() as u64),
+                _ => output_length.unwrap_or_default(),
+            };
+            Err(super::Error::InvalidVectorOutput(err_len as usize))
+        }
+    }
+}
+
 /// Applies a task's output expression to transform a raw task output into a FunctionOutput.
 ///
 /// The expression receives `output` which is one of 4 variants:
@@ -133,6 +322,7 @@ fn compute_weighted_function_output(
 /// Returns the output (possibly as `FunctionOutput::Err` if invalid) and an optional error.
 fn apply_task_output_expression(
     input: &objectiveai::functions::expression::Input,
+    definitions: Option<&objectiveai::functions::expression::Definitions>,
     task_output: objectiveai::functions::expression::TaskOutputOwned,
     output_expression: &objectiveai::functions::expression::Expression,
     invert_output: bool,
@@ -176,6 +366,9 @@ fn apply_task_output_expression(
         input,
         output: Some(TaskOutput::Owned(task_output)),
         map: None,
+        definitions,
+        tasks: None,
+        weights: None,
     });
 
     // Evaluate the expression - it transforms the raw output into FunctionOutput
@@ -257,6 +450,53 @@ fn apply_task_output_expression(
     }
 }
 
+/// Resolves once `token` is cancelled, or never if there is no token.
+async fn skipped(token: Option<&tokio_util::sync::CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Returns a task's output expression and whether to invert its output.
+fn task_output_expression(
+    task: &functions::FlatTaskProfile,
+) -> Option<(objectiveai::functions::expression::Expression, bool)> {
+    match task {
+        functions::FlatTaskProfile::Function(f) => {
+            f.task_output.clone().map(|expr| (expr, f.invert_output))
+        }
+        functions::FlatTaskProfile::MapFunction(mf) => Some((mf.task_output.clone(), mf.invert_output)),
+        functions::FlatTaskProfile::VectorCompletion(vc) => Some((vc.output.clone(), vc.invert_output)),
+        functions::FlatTaskProfile::MapVectorCompletion(mvc) => Some((mvc.task_output.clone(), mvc.invert_output)),
+        functions::FlatTaskProfile::PlaceholderScalarFunction(p) => Some((p.output.clone(), p.invert_output)),
+        functions::FlatTaskProfile::MapPlaceholderScalarFunction(p) => Some((p.task_output.clone(), p.invert_output)),
+        functions::FlatTaskProfile::PlaceholderVectorFunction(p) => Some((p.output.clone(), p.invert_output)),
+        functions::FlatTaskProfile::MapPlaceholderVectorFunction(p) => Some((p.task_output.clone(), p.invert_output)),
+    }
+}
+
+/// Returns the raw output of a mapped task with no elements.
+fn empty_task_output(
+    task: &functions::FlatTaskProfile,
+) -> objectiveai::functions::expression::TaskOutputOwned {
+    match task {
+        functions::FlatTaskProfile::MapFunction(_) => {
+            objectiveai::functions::expression::TaskOutputOwned::MapFunction(Vec::new())
+        }
+        functions::FlatTaskProfile::MapVectorCompletion(_) => {
+            objectiveai::functions::expression::TaskOutputOwned::MapVectorCompletion(
+                Vec::new(),
+            )
+        }
+        functions::FlatTaskProfile::MapPlaceholderScalarFunction(_)
+        | functions::FlatTaskProfile::MapPlaceholderVectorFunction(_) => {
+            objectiveai::functions::expression::TaskOutputOwned::MapFunction(Vec::new())
+        }
+        _ => panic!("encountered non-map FlatTaskProfile with length of 0"),
+    }
+}
+
 /// Client for executing Functions.
 ///
 /// Orchestrates Function execution by flattening the Function and Profile
@@ -264,15 +504,15 @@ fn apply_task_output_expression(
 /// Functions) with streaming output support.
 pub struct Client<
     CTXEXT,
-    FENSLLM,
-    CUSG,
-    FENS,
-    FVVOTE,
-    FCVOTE,
-    VUSG,
-    FFN,
-    FPFL,
-    FUSG,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+    FFN: ?Sized,
+    FPFL: ?Sized,
+    FUSG: ?Sized,
 > {
     /// Chat completions client for reasoning summaries.
     pub chat_client: Arc<chat::completions::Client<CTXEXT, FENSLLM, CUSG>>,
@@ -299,7 +539,18 @@ pub struct Client<
     pub usage_handler: Arc<FUSG>,
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+    FFN: ?Sized,
+    FPFL: ?Sized,
+    FUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
 {
     /// Creates a new Function execution client.
@@ -334,7 +585,18 @@ impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+    FFN: ?Sized,
+    FPFL: ?Sized,
+    FUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
@@ -408,7 +670,7 @@ where
             let mut any_usage = false;
             let stream = match self
                 .clone()
-                .create_streaming(ctx.clone(), request.clone())
+                .create_streaming(ctx.clone(), request.clone(), None)
                 .await
             {
                 Ok(stream) => stream,
@@ -446,7 +708,18 @@ where
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+    FFN: ?Sized,
+    FPFL: ?Sized,
+    FUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
@@ -477,11 +750,13 @@ where
     ///
     /// Fetches the Function and Profile, flattens them into tasks, and
     /// executes all tasks with streaming output. Handles reasoning summaries
-    /// if requested.
+    /// if requested. If `control` is provided, the execution can be paused
+    /// and its remaining tasks skipped through it.
     pub async fn create_streaming(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
         request: Arc<objectiveai::functions::executions::request::Request>,
+        control: Option<super::ExecutionControl>,
     ) -> Result<
         impl Stream<Item = objectiveai::functions::executions::response::streaming::FunctionExecutionChunk>
         + Send
@@ -587,57 +862,12 @@ where
                         .filter_map(|task| task.as_ref())
                         .flat_map(|task| task.vector_completion_ftps())
                     {
-                        let mut completion_index_map = Vec::with_capacity(
-                            vector_completion_ftp.responses.len(),
-                        );
-                        for response in &vector_completion_ftp.responses {
-                            let mut response = response.clone();
-                            response.prepare();
-                            let response_string =
-                                serde_json::to_string(&response)
-                                    .unwrap_or_default();
-                            if response_string.is_empty() {
-                                continue;
-                            }
-                            let mut hasher = ahash::AHasher::default();
-                            hasher.write(response_string.as_bytes());
-                            let response_hash = hasher.finish();
-                            let mut found = false;
-                            for (i, confidence_response) in
-                                confidence_responses.iter_mut().enumerate()
-                            {
-                                if confidence_response.response_hash
-                                    == response_hash
-                                {
-                                    confidence_response.paths.push(
-                                        vector_completion_ftp.path.clone(),
-                                    );
-                                    confidence_response.confidence_count +=
-                                        rust_decimal::Decimal::ONE;
-                                    completion_index_map.push(i);
-                                    found = true;
-                                    break;
-                                }
-                            }
-                            if !found {
-                                completion_index_map
-                                    .push(confidence_responses.len());
-                                confidence_responses.push(ConfidenceResponse {
-                                    response_hash,
-                                    paths: vec![
-                                        vector_completion_ftp.path.clone(),
-                                    ],
-                                    confidence_count:
-                                        rust_decimal::Decimal::ONE,
-                                    response,
-                                    confidence: rust_decimal::Decimal::ZERO,
-                                    reasoning: Vec::new(),
-                                });
-                            }
-                        }
                         index_map.insert(
                             vector_completion_ftp.path.clone(),
-                            completion_index_map,
+                            ConfidenceResponse::index(
+                                &mut confidence_responses,
+                                vector_completion_ftp,
+                            ),
                         );
                     }
                     (index_map, confidence_responses)
@@ -671,6 +901,9 @@ where
                 rounds,
             }
         ) = &request.base().strategy {
+            // take definitions for input_split and input_merge
+            let definitions = ftp.definitions.clone();
+
             // take and unwrap input_split and input_merge
             let (input_split, input_merge) = match &ftp.r#type {
                 functions::FunctionType::Vector {
@@ -701,6 +934,9 @@ where
                         input: &request.base().input,
                         output: None,
                         map: None,
+                        definitions: definitions.as_ref(),
+                        tasks: None,
+                        weights: None,
                     }
                 ),
             )?;
@@ -725,6 +961,9 @@ where
                             ),
                             output: None,
                             map: None,
+                            definitions: definitions.clone().map(Box::new),
+                            tasks: None,
+                            weights: None,
                         }
                     )
                 )?;
@@ -751,31 +990,12 @@ where
                         .filter_map(|task| task.as_ref())
                         .flat_map(|task| task.vector_completion_ftps())
                     {
-                        let mut completion_index_map = Vec::with_capacity(
-                            vector_completion_ftp.responses.len(),
-                        );
-                        for response in &vector_completion_ftp.responses {
-                            let mut response = response.clone();
-                            response.prepare();
-                            let response_string =
-                                serde_json::to_string(&response).unwrap_or_default();
-                            if response_string.is_empty() {
-                                continue;
-                            }
-                            let mut hasher = ahash::AHasher::default();
-                            hasher.write(response_string.as_bytes());
-                            let response_hash = hasher.finish();
-                            // find matching confidence_response by hash
-                            for (i, confidence_response) in confidence_responses.iter().enumerate() {
-                                if confidence_response.response_hash == response_hash {
-                                    completion_index_map.push(i);
-                                    break;
-                                }
-                            }
-                        }
                         ftp_index_map.insert(
                             vector_completion_ftp.path.clone(),
-                            completion_index_map,
+                            ConfidenceResponse::find(
+                                &confidence_responses,
+                                vector_completion_ftp,
+                            ),
                         );
                     }
                     index_maps.insert((1, pool_idx), ftp_index_map);
@@ -859,23 +1079,30 @@ where
                         streams.push((
                             i,
                             self.clone().execute_function_ftp_streaming(
-                                ctx.clone(),
-                                request.clone(),
-                                if is_first_round {
-                                    retry_token.clone().map(|retry_token| {
-                                        Arc::new(retry_token.clone_slice(
-                                            retry_token_index..retry_token_index + task_index_len,
-                                        ))
-                                    })
-                                } else {
-                                    None
+                                ExecutionContext {
+                                    ctx: ctx.clone(),
+                                    request: request.clone(),
+                                    control: control.clone(),
+                                    root_retry_token: if is_first_round {
+                                        retry_token.clone().map(|retry_token| {
+                                            Arc::new(retry_token.clone_slice(
+                                                retry_token_index..retry_token_index + task_index_len,
+                                            ))
+                                        })
+                                    } else {
+                                        None
+                                    },
+                                    created,
+                                    swiss_round: Some(current_round as u64),
+                                    swiss_pool_index: Some(i as u64),
+                                    dependent_task_indices: Arc::new(
+                                        DependentTaskIndices::new(task_index_len),
+                                    ),
                                 },
                                 ftp,
-                                created,
                                 0,
                                 Arc::new(ChoiceIndexer::new(0)),
-                                Some(current_round as u64),
-                                Some(i as u64),
+                                true,
                             ).boxed(),
                         ));
                         retry_token_indices.push(retry_token_index);
@@ -934,10 +1161,35 @@ where
                                     usage: None,
                                 };
                             }
-                            FtpStreamChunk::OutputChunk { retry_token: chunk_retry_token, .. } => {
-                                // capture retry tokens from first round only
+                            FtpStreamChunk::DependentTaskChunk(vector_completion_ftps) => {
+                                // index the flattened dependent task for reasoning
+                                if let (Some(index_maps), Some(confidence_responses)) = (&mut swiss_index_maps, &swiss_confidence_responses) {
+                                    let ftp_index_map = index_maps
+                                        .entry((current_round as u64, pool_idx))
+                                        .or_default();
+                                    for vector_completion_ftp in &vector_completion_ftps {
+                                        ftp_index_map.insert(
+                                            vector_completion_ftp.path.clone(),
+                                            ConfidenceResponse::find(
+                                                confidence_responses,
+                                                vector_completion_ftp,
+                                            ),
+                                        );
+                                    }
+                                }
+                            }
+                            FtpStreamChunk::OutputChunk { retry_token: mut chunk_retry_token, .. } => {
+                                // capture retry tokens from first round only,
+                                // without the task indices reserved for
+                                // dependent tasks, which would overlap the
+                                // next pool's
                                 if is_first_round {
                                     let insert_idx = retry_token_indices.get(pool_idx).copied().unwrap_or(0);
+                                    let end = retry_token_indices
+                                        .get(pool_idx + 1)
+                                        .copied()
+                                        .unwrap_or(first_round_retry_token.0.len());
+                                    chunk_retry_token.0.truncate(end - insert_idx);
                                     first_round_retry_token.insert(insert_idx, chunk_retry_token);
                                 }
                             }
@@ -988,6 +1240,13 @@ where
                     }
                     round_outputs.push(this_round_scores);
 
+                    // skipping the remaining tasks also skips the remaining rounds
+                    if control.as_ref().is_some_and(|control| {
+                        control.skip_remaining_tasks.is_cancelled()
+                    }) {
+                        break 'rounds;
+                    }
+
                     // if not last round, re-sort and prepare next round
                     if !is_last_round {
                         // create sorted indices by cumulative score (descending), with original index as tie-breaker
@@ -1029,6 +1288,9 @@ where
                                         ),
                                         output: None,
                                         map: None,
+                                        definitions: definitions.clone().map(Box::new),
+                                        tasks: None,
+                                        weights: None,
                                     }
                                 )
                             ) {
@@ -1070,31 +1332,12 @@ where
                                     .filter_map(|task| task.as_ref())
                                     .flat_map(|task| task.vector_completion_ftps())
                                 {
-                                    let mut completion_index_map = Vec::with_capacity(
-                                        vector_completion_ftp.responses.len(),
-                                    );
-                                    for response in &vector_completion_ftp.responses {
-                                        let mut response = response.clone();
-                                        response.prepare();
-                                        let response_string =
-                                            serde_json::to_string(&response).unwrap_or_default();
-                                        if response_string.is_empty() {
-                                            continue;
-                                        }
-                                        let mut hasher = ahash::AHasher::default();
-                                        hasher.write(response_string.as_bytes());
-                                        let response_hash = hasher.finish();
-                                        // find matching confidence_response by hash
-                                        for (i, confidence_response) in confidence_responses.iter().enumerate() {
-                                            if confidence_response.response_hash == response_hash {
-                                                completion_index_map.push(i);
-                                                break;
-                                            }
-                                        }
-                                    }
                                     ftp_index_map.insert(
                                         vector_completion_ftp.path.clone(),
-                                        completion_index_map,
+                                        ConfidenceResponse::find(
+                                            confidence_responses,
+                                            vector_completion_ftp,
+                                        ),
                                     );
                                 }
                                 index_maps.insert((next_round as u64, pool_idx), ftp_index_map);
@@ -1138,10 +1381,11 @@ where
                     let objectiveai::functions::executions::request::Reasoning {
                         model,
                         models,
+                        ..
                     } = request.base().reasoning.as_ref().unwrap();
 
                     // iterate over vector completion chunks
-                    for (_, (round, pool_idx, mut vector_completion)) in vector_completions.into_iter() {
+                    for (_, (round, pool_idx, vector_completion)) in vector_completions.into_iter() {
                         // get index_map for this round/pool
                         if let Some(ftp_index_map) = index_maps.get(&(round, pool_idx)) {
                             if let Some(indices) = ftp_index_map.get(&vector_completion.task_path) {
@@ -1167,43 +1411,21 @@ where
                                         }
                                         if let Some(&idx) = indices.get(winning_index) {
                                             let confidence_response = &mut confidence_responses[idx];
-                                            let completion = vector_completion
-                                                .inner
-                                                .completions
-                                                .iter_mut()
-                                                .find(|c| c.index == completion_index)
-                                                .expect("missing completion for vote completion index");
-                                            let delta = &mut completion.inner.choices[0].delta;
-                                            if let Some(reasoning) = delta.reasoning.take() {
-                                                confidence_response.reasoning.push(reasoning);
-                                            }
-                                            if let Some(content) = delta.content.take()
-                                                && let Ok(vector::completions::ResponseKey {
-                                                    _think: Some(reasoning),
-                                                    ..
-                                                }) = serde_json::from_str(&content)
-                                            {
-                                                confidence_response.reasoning.push(reasoning);
-                                            }
-                                            if let Some(tool_calls) = delta.tool_calls.take() {
-                                                for tool_call in tool_calls {
-                                                    if let objectiveai::chat::completions::response::streaming::ToolCall {
-                                                        function: Some(
-                                                            objectiveai::chat::completions::response::streaming::ToolCallFunction {
-                                                                arguments: Some(arguments),
-                                                                ..
-                                                            }
-                                                        ),
-                                                        ..
-                                                    } = tool_call
-                                                        && let Ok(vector::completions::ResponseKey {
-                                                            _think: Some(reasoning),
-                                                            ..
-                                                        }) = serde_json::from_str(&arguments)
-                                                    {
-                                                        confidence_response.reasoning.push(reasoning);
-                                                    }
-                                                }
+                                            let rationale = match vote.rationale {
+                                                Some(rationale) => Some(rationale),
+                                                None => vector::completions::ResponseKey::rationale(
+                                                    &vector_completion
+                                                        .inner
+                                                        .completions
+                                                        .iter()
+                                                        .find(|c| c.index == completion_index)
+                                                        .expect("missing completion for vote completion index")
+                                                        .inner
+                                                        .choices[0],
+                                                ),
+                                            };
+                                            if let Some(rationale) = rationale {
+                                                confidence_response.reasoning.push(rationale);
                                             }
                                         }
                                     }
@@ -1282,26 +1504,55 @@ where
             }))
         } else {
             // get function stream
+            let dependent_task_indices =
+                Arc::new(DependentTaskIndices::new(ftp.task_index_len()));
             let stream = self
                 .clone()
                 .execute_function_ftp_streaming(
-                    ctx.clone(),
-                    request.clone(),
-                    retry_token,
+                    ExecutionContext {
+                        ctx: ctx.clone(),
+                        request: request.clone(),
+                        control,
+                        root_retry_token: retry_token,
+                        created,
+                        swiss_round: None,
+                        swiss_pool_index: None,
+                        dependent_task_indices,
+                    },
                     ftp,
-                    created,
                     0,
                     Arc::new(ChoiceIndexer::new(0)),
-                    None,
-                    None,
+                    true,
                 );
 
             Ok(futures::future::Either::Right(async_stream::stream! {
                 futures::pin_mut!(stream);
                 // stream all chunks
-                while let Some(
-                    FtpStreamChunk::FunctionExecutionChunk(chunk)
-                ) = stream.next().await {
+                while let Some(chunk) = stream.next().await {
+                    let chunk = match chunk {
+                        FtpStreamChunk::FunctionExecutionChunk(chunk) => chunk,
+                        FtpStreamChunk::DependentTaskChunk(vector_completion_ftps) => {
+                            // index the flattened dependent task for reasoning
+                            if let Some((_, (index_map, confidence_responses), _)) =
+                                &mut reasoning_data
+                            {
+                                for vector_completion_ftp in &vector_completion_ftps {
+                                    index_map.insert(
+                                        vector_completion_ftp.path.clone(),
+                                        ConfidenceResponse::index(
+                                            confidence_responses,
+                                            vector_completion_ftp,
+                                        ),
+                                    );
+                                }
+                            }
+                            continue;
+                        }
+                        FtpStreamChunk::OutputChunk { .. } => break,
+                        FtpStreamChunk::VectorCompletionTaskChunk(_) => {
+                            unreachable!()
+                        }
+                    };
                     // handle reasoning tasks if needed
                     if reasoning {
                         // unwrap reasoning data
@@ -1348,6 +1599,7 @@ where
                     let objectiveai::functions::executions::request::Reasoning {
                         model,
                         models,
+                        ..
                     } = request.base().reasoning.as_ref().unwrap();
                     let (
                         vector_completions,
@@ -1360,9 +1612,10 @@ where
                     let mut final_chunk = final_chunk.unwrap();
 
                     // iterate over vector completion chat completions
-                    for mut vector_completion in vector_completions.into_values() {
-                        let indices = index_map.get(&vector_completion.task_path)
-                            .expect("missing index map for vector completion task path");
+                    for vector_completion in vector_completions.into_values() {
+                        let indices = index_map
+                            .get(&vector_completion.task_path)
+                            .expect("missing index map for vector completion");
                         for (i, score) in vector_completion
                             .inner
                             .scores
@@ -1386,50 +1639,23 @@ where
                                 }
                                 let confidence_response =
                                     &mut confidence_responses[indices[winning_index]];
-                                let completion = vector_completion
-                                    .inner
-                                    .completions
-                                    .iter_mut()
-                                    .find(|c| c.index == completion_index)
-                                    .expect(
-                                        "missing completion for vote completion index",
-                                    );
-                                let delta = &mut completion
-                                    .inner
-                                    .choices[0]
-                                    .delta;
-                                if let Some(reasoning) = delta.reasoning.take() {
-                                    confidence_response.reasoning.push(reasoning);
-                                }
-                                if let Some(content) = delta.content.take()
-                                    && let Ok(vector::completions::ResponseKey {
-                                        _think: Some(reasoning),
-                                        ..
-                                    }) = serde_json::from_str(&content)
-                                {
-                                    confidence_response.reasoning.push(reasoning);
-                                }
-                                if let Some(tool_calls) = delta.tool_calls.take() {
-                                    for tool_call in tool_calls {
-                                        if let objectiveai::chat::completions::response::streaming::ToolCall {
-                                            function: Some(
-                                                objectiveai::chat::completions::response::streaming::ToolCallFunction {
-                                                    arguments: Some(arguments),
-                                                    ..
-                                                }
-                                            ),
-                                            ..
-                                        } = tool_call
-                                            && let Ok(vector::completions::ResponseKey {
-                                                _think: Some(reasoning),
-                                                ..
-                                            }) = serde_json::from_str(&arguments)
-                                        {
-                                            confidence_response.reasoning.push(
-                                                reasoning,
-                                            );
-                                        }
-                                    }
+                                let rationale = match vote.rationale {
+                                    Some(rationale) => Some(rationale),
+                                    None => vector::completions::ResponseKey::rationale(
+                                        &vector_completion
+                                            .inner
+                                            .completions
+                                            .iter()
+                                            .find(|c| c.index == completion_index)
+                                            .expect(
+                                                "missing completion for vote completion index",
+                                            )
+                                            .inner
+                                            .choices[0],
+                                    ),
+                                };
+                                if let Some(rationale) = rationale {
+                                    confidence_response.reasoning.push(rationale);
                                 }
                             }
                         }
@@ -1617,72 +1843,63 @@ where
 
     fn execute_ftp_streaming(
         self: Arc<Self>,
-        ctx: ctx::Context<CTXEXT>,
-        request: Arc<objectiveai::functions::executions::request::Request>,
-        root_retry_token: Option<
-            Arc<objectiveai::functions::executions::RetryToken>,
-        >,
+        execution: ExecutionContext<CTXEXT>,
         ftp: functions::FlatTaskProfile,
-        created: u64,
         task_index: u64,
         choice_indexer: Arc<ChoiceIndexer>,
-        swiss_round: Option<u64>,
-        swiss_pool_index: Option<u64>,
     ) -> futures::stream::BoxStream<'static, FtpStreamChunk> {
         match ftp {
             functions::FlatTaskProfile::Function(function_ftp) => self
                 .clone()
                 .execute_function_ftp_streaming(
-                    ctx,
-                    request,
-                    root_retry_token,
+                    execution,
                     function_ftp,
-                    created,
                     task_index,
                     choice_indexer,
-                    swiss_round,
-                    swiss_pool_index,
+                    false,
                 )
                 .boxed(),
             functions::FlatTaskProfile::MapFunction(map_function_ftp) => self
                 .clone()
                 .execute_map_function_ftp_streaming(
-                    ctx,
-                    request,
-                    root_retry_token,
+                    execution,
                     map_function_ftp,
-                    created,
                     task_index,
                     choice_indexer,
-                    swiss_round,
-                    swiss_pool_index,
                 )
                 .boxed(),
+            // vector completion tasks wait to start while paused
             functions::FlatTaskProfile::VectorCompletion(vector_ftp) => {
-                futures::stream::once(
-                    self.clone().execute_vector_ftp_streaming(
-                        ctx,
-                        request,
-                        root_retry_token,
-                        vector_ftp,
-                        task_index,
-                        choice_indexer,
-                    ),
-                )
+                futures::stream::once(async move {
+                    if let Some(control) = &execution.control {
+                        control.new_tasks_unpaused().await;
+                    }
+                    self.clone()
+                        .execute_vector_ftp_streaming(
+                            execution,
+                            vector_ftp,
+                            task_index,
+                            choice_indexer,
+                        )
+                        .await
+                })
                 .flatten()
                 .boxed()
             }
             functions::FlatTaskProfile::MapVectorCompletion(map_vector_ftp) => {
-                futures::stream::once(
-                    self.clone().execute_map_vector_ftp_streaming(
-                        ctx,
-                        request,
-                        root_retry_token,
-                        map_vector_ftp,
-                        task_index,
-                        choice_indexer,
-                    ),
-                )
+                futures::stream::once(async move {
+                    if let Some(control) = &execution.control {
+                        control.new_tasks_unpaused().await;
+                    }
+                    self.clone()
+                        .execute_map_vector_ftp_streaming(
+                            execution,
+                            map_vector_ftp,
+                            task_index,
+                            choice_indexer,
+                        )
+                        .await
+                })
                 .flatten()
                 .boxed()
             }
@@ -1779,17 +1996,10 @@ where
 
     fn execute_map_function_ftp_streaming(
         self: Arc<Self>,
-        ctx: ctx::Context<CTXEXT>,
-        request: Arc<objectiveai::functions::executions::request::Request>,
-        root_retry_token: Option<
-            Arc<objectiveai::functions::executions::RetryToken>,
-        >,
+        execution: ExecutionContext<CTXEXT>,
         ftp: functions::MapFunctionFlatTaskProfile,
-        created: u64,
         task_index: u64,
         choice_indexer: Arc<ChoiceIndexer>,
-        swiss_round: Option<u64>,
-        swiss_pool_index: Option<u64>,
     ) -> impl Stream<Item = FtpStreamChunk> + Send + 'static {
         // initialize output and task indices
         let ftp_inner_len = ftp.len();
@@ -1821,15 +2031,11 @@ where
         let stream = futures::stream::iter(
             ftp.functions.into_iter().enumerate().map(move |(i, ftp)| {
                 self.clone().execute_function_ftp_streaming(
-                    ctx.clone(),
-                    request.clone(),
-                    root_retry_token.clone(),
+                    execution.clone(),
                     ftp,
-                    created,
                     task_index + outer_task_indices[i],
                     choice_indexer.clone(),
-                    swiss_round,
-                    swiss_pool_index,
+                    false,
                 )
             }),
         )
@@ -1843,6 +2049,9 @@ where
                     FtpStreamChunk::FunctionExecutionChunk(chunk) => {
                         yield FtpStreamChunk::FunctionExecutionChunk(chunk);
                     }
+                    FtpStreamChunk::DependentTaskChunk(chunk) => {
+                        yield FtpStreamChunk::DependentTaskChunk(chunk);
+                    }
                     FtpStreamChunk::OutputChunk {
                         task_index: chunk_task_index,
                         output: chunk_output,
@@ -1856,7 +2065,10 @@ where
                             })
                             .unwrap();
                         // insert retry token into correct position
-                        retry_token.insert(local_index, chunk_retry_token);
+                        retry_token.insert(
+                            task_indices[local_index] as usize,
+                            chunk_retry_token,
+                        );
                         // insert output into correct position
                         output[local_index] = match chunk_output {
                             objectiveai::functions::expression::TaskOutputOwned::Function(output) => output,
@@ -1878,20 +2090,24 @@ where
         }
     }
 
+    /// Executes a Function's tasks and aggregates their outputs.
+    ///
+    /// If `root`, the remaining tasks are dropped once the execution control's
+    /// `skip_remaining_tasks` token is cancelled, and only the outputs of the
+    /// tasks which have finished are aggregated. The root Function's retry
+    /// token also covers the task indices reserved for dependent tasks.
     fn execute_function_ftp_streaming(
         self: Arc<Self>,
-        ctx: ctx::Context<CTXEXT>,
-        request: Arc<objectiveai::functions::executions::request::Request>,
-        root_retry_token: Option<
-            Arc<objectiveai::functions::executions::RetryToken>,
-        >,
-        ftp: functions::FunctionFlatTaskProfile,
-        created: u64,
+        execution: ExecutionContext<CTXEXT>,
+        mut ftp: functions::FunctionFlatTaskProfile,
         task_index: u64,
         choice_indexer: Arc<ChoiceIndexer>,
-        swiss_round: Option<u64>,
-        swiss_pool_index: Option<u64>,
+        root: bool,
     ) -> impl Stream<Item = FtpStreamChunk> + Send + 'static {
+        let created = execution.created;
+        let swiss_round = execution.swiss_round;
+        let swiss_pool_index = execution.swiss_pool_index;
+
         // identify the completion and get response type
         let (response_id, object) = match ftp.r#type {
             functions::FunctionType::Scalar => (
@@ -1908,27 +2124,15 @@ where
         let task_indices = ftp.task_indices();
 
         // extract output expressions from each task for later transformation
-        let task_output_expressions: Vec<Option<(objectiveai::functions::expression::Expression, bool)>> =
+        let mut task_output_expressions: Vec<Option<(objectiveai::functions::expression::Expression, bool)>> =
             ftp.tasks
                 .iter()
-                .map(|task| {
-                    task.as_ref().and_then(|t| match t {
-                        functions::FlatTaskProfile::Function(f) => {
-                            f.task_output.clone().map(|expr| (expr, f.invert_output))
-                        }
-                        functions::FlatTaskProfile::MapFunction(mf) => Some((mf.task_output.clone(), mf.invert_output)),
-                        functions::FlatTaskProfile::VectorCompletion(vc) => Some((vc.output.clone(), vc.invert_output)),
-                        functions::FlatTaskProfile::MapVectorCompletion(mvc) => Some((mvc.task_output.clone(), mvc.invert_output)),
-                        functions::FlatTaskProfile::PlaceholderScalarFunction(p) => Some((p.output.clone(), p.invert_output)),
-                        functions::FlatTaskProfile::MapPlaceholderScalarFunction(p) => Some((p.task_output.clone(), p.invert_output)),
-                        functions::FlatTaskProfile::PlaceholderVectorFunction(p) => Some((p.output.clone(), p.invert_output)),
-                        functions::FlatTaskProfile::MapPlaceholderVectorFunction(p) => Some((p.task_output.clone(), p.invert_output)),
-                    })
-                })
+                .map(|task| task.as_ref().and_then(task_output_expression))
                 .collect();
 
         // store function input and type for expression evaluation
         let ftp_input = ftp.input.clone();
+        let ftp_definitions = ftp.definitions.clone();
         let ftp_type = ftp.r#type.clone();
 
         // initialize output_input (stores validated FunctionOutputs directly)
@@ -1941,26 +2145,13 @@ where
         for (i, task) in ftp.tasks.iter().enumerate() {
             if task.as_ref().is_some_and(|task| task.len() == 0) {
                 // empty map task - apply output expression to empty result
-                let raw_output = match task.as_ref() {
-                    Some(functions::FlatTaskProfile::MapFunction(_)) => {
-                        objectiveai::functions::expression::TaskOutputOwned::MapFunction(Vec::new())
-                    }
-                    Some(functions::FlatTaskProfile::MapVectorCompletion(_)) => {
-                        objectiveai::functions::expression::TaskOutputOwned::MapVectorCompletion(
-                            Vec::new(),
-                        )
-                    }
-                    Some(functions::FlatTaskProfile::MapPlaceholderScalarFunction(_))
-                    | Some(functions::FlatTaskProfile::MapPlaceholderVectorFunction(_)) => {
-                        objectiveai::functions::expression::TaskOutputOwned::MapFunction(Vec::new())
-                    }
-                    _ => panic!("encountered non-map FlatTaskProfile with length of 0"),
-                };
+                let raw_output = empty_task_output(task.as_ref().unwrap());
                 let (expr, invert_output) = task_output_expressions[i]
                     .as_ref()
                     .expect("empty map task must have output expression");
                 let (transformed, error) = apply_task_output_expression(
                     &ftp_input,
+                    ftp_definitions.as_ref(),
                     raw_output,
                     expr,
                     *invert_output,
@@ -1993,6 +2184,23 @@ where
         // create new choice indexer for children
         let child_choice_indexer = Arc::new(ChoiceIndexer::new(0));
 
+        // only root functions stop early when the remaining tasks are skipped
+        let skip_remaining_tasks = execution
+            .control
+            .as_ref()
+            .filter(|_| root)
+            .map(|control| control.skip_remaining_tasks.clone());
+
+        // tasks with depends_on run once the other tasks have finished, in
+        // order, each in task indices reserved past the root Function's, and
+        // keyed by the first of them
+        let mut dependent_tasks =
+            std::mem::take(&mut ftp.dependent_tasks).into_iter();
+        let mut dependent_task_indices: Vec<(u64, usize)> = Vec::new();
+        let dependent_client = self.clone();
+        let dependent_execution = execution.clone();
+        let dependent_choice_indexer = child_choice_indexer.clone();
+
         // combine all streams into one
         let outer_task_indices = task_indices.clone();
         let stream = futures::stream::iter(
@@ -2002,15 +2210,10 @@ where
                         .map(|inner_ftp| {
                             if inner_ftp.len() > 0 {
                                 Some(self.clone().execute_ftp_streaming(
-                                    ctx.clone(),
-                                    request.clone(),
-                                    root_retry_token.clone(),
+                                    execution.clone(),
                                     inner_ftp,
-                                    created,
                                     task_index + task_indices[i],
                                     child_choice_indexer.clone(),
-                                    swiss_round,
-                                    swiss_pool_index,
                                 ))
                             } else {
                                 None
@@ -2020,7 +2223,8 @@ where
                 },
             ),
         )
-        .flatten();
+        .flatten()
+        .boxed();
         let task_indices = outer_task_indices;
 
         // track whether child errors occurred
@@ -2041,8 +2245,113 @@ where
 
         // return stream, yielding chunks and updating retry token and output
         async_stream::stream! {
-            futures::pin_mut!(stream);
-            while let Some(chunk) = stream.next().await {
+            let mut stream = stream;
+            loop {
+                let next = tokio::select! {
+                    biased;
+                    _ = skipped(skip_remaining_tasks.as_ref()) => break,
+                    next = stream.next() => next,
+                };
+                let chunk = match next {
+                    Some(chunk) => chunk,
+                    None => {
+                        // all tasks before the next dependent task have finished
+                        let Some(dependent_task) = dependent_tasks.next() else {
+                            break;
+                        };
+                        let local_index = dependent_task.index;
+                        let inner_ftp = match functions::get_dependent_flat_task_profile(
+                            dependent_execution.ctx.clone(),
+                            dependent_task,
+                            &ftp_input,
+                            &output_input,
+                            dependent_client.function_fetcher.clone(),
+                            dependent_client.profile_fetcher.clone(),
+                            dependent_client.ensemble_fetcher.clone(),
+                        )
+                        .await
+                        {
+                            Ok(Some(inner_ftp)) => inner_ftp,
+                            // skipped
+                            Ok(None) => continue,
+                            Err(e) => {
+                                tasks_errors = true;
+                                yield FtpStreamChunk::FunctionExecutionChunk(
+                                    objectiveai::functions::executions::response::streaming::FunctionExecutionTaskChunk {
+                                        index: choice_indexer.get(
+                                            task_index as usize,
+                                        ),
+                                        task_index,
+                                        task_path: ftp.path.clone(),
+                                        swiss_round,
+                                        swiss_pool_index,
+                                        inner: objectiveai::functions::executions::response::streaming::FunctionExecutionChunk {
+                                            id: response_id.clone(),
+                                            tasks: Vec::new(),
+                                            tasks_errors: Some(true),
+                                            reasoning: None,
+                                            output: None,
+                                            error: Some(objectiveai::error::ResponseError::from(
+                                                &super::Error::DependentTask {
+                                                    task_index: local_index,
+                                                    error: Box::new(e),
+                                                },
+                                            )),
+                                            retry_token: None,
+                                            created,
+                                            function: function.clone(),
+                                            profile: profile.clone(),
+                                            object,
+                                            usage: None,
+                                        },
+                                    },
+                                );
+                                continue;
+                            }
+                        };
+                        task_output_expressions[local_index] =
+                            task_output_expression(&inner_ftp);
+                        if inner_ftp.len() == 0 {
+                            // empty map task - apply output expression to empty result
+                            let (expr, invert_output) = task_output_expressions[local_index]
+                                .as_ref()
+                                .expect("empty map task must have output expression");
+                            let (transformed, error) = apply_task_output_expression(
+                                &ftp_input,
+                                ftp_definitions.as_ref(),
+                                empty_task_output(&inner_ftp),
+                                expr,
+                                *invert_output,
+                                &ftp_type,
+                            );
+                            if let Some(err) = error {
+                                task_output_errors.push(super::TaskOutputExpressionError {
+                                    task_index: local_index,
+                                    message: err.message.to_string(),
+                                });
+                            } else {
+                                output_input[local_index] = Some(transformed);
+                            }
+                            continue;
+                        }
+                        if dependent_execution.request.base().reasoning.is_some() {
+                            yield FtpStreamChunk::DependentTaskChunk(
+                                inner_ftp.vector_completion_ftps().cloned().collect(),
+                            );
+                        }
+                        let dependent_task_index = dependent_execution
+                            .dependent_task_indices
+                            .reserve(inner_ftp.task_index_len());
+                        dependent_task_indices.push((dependent_task_index, local_index));
+                        stream = dependent_client.clone().execute_ftp_streaming(
+                            dependent_execution.clone(),
+                            inner_ftp,
+                            dependent_task_index,
+                            dependent_choice_indexer.clone(),
+                        );
+                        continue;
+                    }
+                };
                 match chunk {
                     FtpStreamChunk::VectorCompletionTaskChunk(chunk) => {
                         tasks_errors |= chunk.error.is_some() || chunk
@@ -2127,20 +2436,41 @@ where
                             },
                         );
                     }
+                    FtpStreamChunk::DependentTaskChunk(chunk) => {
+                        yield FtpStreamChunk::DependentTaskChunk(chunk);
+                    }
                     FtpStreamChunk::OutputChunk {
                         task_index: chunk_task_index,
                         output: chunk_output,
                         retry_token: chunk_retry_token,
                     } => {
-                        // get local index
-                        let local_index = task_indices
+                        // get local index and insert retry token into correct
+                        // position
+                        let local_index = match dependent_task_indices
                             .iter()
-                            .position(|&ti| {
-                                ti == (chunk_task_index - task_index)
-                            })
-                            .unwrap();
-                        // insert retry token into correct position
-                        retry_token.insert(local_index, chunk_retry_token);
+                            .find(|(ti, _)| *ti == chunk_task_index)
+                        {
+                            Some(&(_, local_index)) => {
+                                dependent_execution.dependent_task_indices.insert(
+                                    chunk_task_index,
+                                    chunk_retry_token,
+                                );
+                                local_index
+                            }
+                            None => {
+                                let local_index = task_indices
+                                    .iter()
+                                    .position(|&ti| {
+                                        ti == (chunk_task_index - task_index)
+                                    })
+                                    .unwrap();
+                                retry_token.insert(
+                                    task_indices[local_index] as usize,
+                                    chunk_retry_token,
+                                );
+                                local_index
+                            }
+                        };
                         // apply task output expression to transform raw output into FunctionOutput
                         // All non-skipped tasks have required output expressions
                         let (expr, invert_output) = task_output_expressions[local_index]
@@ -2148,6 +2478,7 @@ where
                             .expect("non-skipped task must have output expression");
                         let (transformed_output, transform_error) = apply_task_output_expression(
                             &ftp_input,
+                            ftp_definitions.as_ref(),
                             chunk_output,
                             expr,
                             *invert_output,
@@ -2168,15 +2499,32 @@ where
                 }
             }
 
-            // compute final output as weighted average of task outputs
-            let output = compute_weighted_function_output(
+            // compute final output by aggregating task outputs
+            let (output, aggregation_error) = match compute_weighted_function_output(
                 &ftp.r#type,
                 &ftp.profile,
                 &output_input,
-            );
+                ftp.aggregation.as_ref(),
+                &ftp_input,
+                ftp_definitions.as_ref(),
+            ) {
+                Ok(output) => (output, None),
+                Err(e) => (
+                    objectiveai::functions::expression::FunctionOutput::Err(serde_json::Value::Null),
+                    Some(e),
+                ),
+            };
+
+            // the root Function's retry token also covers dependent tasks
+            if root {
+                dependent_execution.dependent_task_indices.extend(&mut retry_token);
+            }
 
-            // build error from task output expression errors if any
-            let output_error = if !task_output_errors.is_empty() {
+            // build error from the aggregation error or task output
+            // expression errors if any
+            let output_error = if let Some(e) = aggregation_error {
+                Some(objectiveai::error::ResponseError::from(&e))
+            } else if !task_output_errors.is_empty() {
                 Some(objectiveai::error::ResponseError::from(
                     &super::Error::TaskOutputExpressionErrors(task_output_errors),
                 ))
@@ -2226,11 +2574,7 @@ where
 
     async fn execute_map_vector_ftp_streaming(
         self: Arc<Self>,
-        ctx: ctx::Context<CTXEXT>,
-        request: Arc<objectiveai::functions::executions::request::Request>,
-        root_retry_token: Option<
-            Arc<objectiveai::functions::executions::RetryToken>,
-        >,
+        execution: ExecutionContext<CTXEXT>,
         ftp: functions::MapVectorCompletionFlatTaskProfile,
         task_index: u64,
         choice_indexer: Arc<ChoiceIndexer>,
@@ -2245,6 +2589,7 @@ where
                     votes: Vec::new(),
                     scores: Vec::new(),
                     weights: Vec::new(),
+                    rating: None,
                 },
             );
         }
@@ -2264,9 +2609,7 @@ where
                 move |(i, ftp)| {
                     futures::stream::once(
                         self.clone().execute_vector_ftp_streaming(
-                            ctx.clone(),
-                            request.clone(),
-                            root_retry_token.clone(),
+                            execution.clone(),
                             ftp,
                             task_index + i as u64,
                             choice_indexer.clone(),
@@ -2302,7 +2645,8 @@ where
                             _ => unreachable!(),
                         };
                     }
-                    FtpStreamChunk::FunctionExecutionChunk(_) => {
+                    FtpStreamChunk::FunctionExecutionChunk(_)
+                    | FtpStreamChunk::DependentTaskChunk(_) => {
                         unreachable!();
                     }
                 }
@@ -2318,20 +2662,23 @@ where
 
     async fn execute_vector_ftp_streaming(
         self: Arc<Self>,
-        ctx: ctx::Context<CTXEXT>,
-        request: Arc<objectiveai::functions::executions::request::Request>,
-        root_retry_token: Option<
-            Arc<objectiveai::functions::executions::RetryToken>,
-        >,
+        execution: ExecutionContext<CTXEXT>,
         ftp: functions::VectorCompletionFlatTaskProfile,
         task_index: u64,
         choice_indexer: Arc<ChoiceIndexer>,
     ) -> impl Stream<Item = FtpStreamChunk> + Send + 'static {
+        let ExecutionContext {
+            ctx,
+            request,
+            root_retry_token,
+            ..
+        } = execution;
         let request_base = request.base();
         let retry_token = root_retry_token
             .and_then(|rt| rt.0.get(task_index as usize).cloned())
             .flatten();
         let request_responses_len = ftp.responses.len();
+        let rating = ftp.rating;
         let mut stream = match self
             .vector_client
             .clone()
@@ -2355,7 +2702,18 @@ where
                             .backoff_max_elapsed_time,
                         first_chunk_timeout: request_base.first_chunk_timeout,
                         other_chunk_timeout: request_base.other_chunk_timeout,
-                        responses: ftp.responses,
+                        // the values of the scale are the responses
+                        responses: match rating {
+                            Some(_) => Vec::new(),
+                            None => ftp.responses,
+                        },
+                        rating,
+                        comparison_mode: None,
+                        early_stopping: None,
+                        include_rationales: request_base
+                            .reasoning
+                            .as_ref()
+                            .and_then(|reasoning| reasoning.include_rationales),
                     },
                 ),
             )
@@ -2375,17 +2733,25 @@ where
                                 inner: objectiveai::vector::completions::response::streaming::VectorCompletionChunk::default_from_request_responses_len(
                                     request_responses_len,
                                 ),
-                                error: Some(objectiveai::error::ResponseError::from(&e))
+                                error: Some(objectiveai::error::ResponseError::from(&e)),
+                                reset: None,
                             }
                         ),
                     ).chain(StreamOnce::new(
                         FtpStreamChunk::OutputChunk {
                             task_index,
-                            output: objectiveai::functions::expression::TaskOutputOwned::VectorCompletion(
-                                objectiveai::functions::expression::VectorCompletionOutput::default_from_request_responses_len(
+                            output: objectiveai::functions::expression::TaskOutputOwned::VectorCompletion({
+                                let mut output = objectiveai::functions::expression::VectorCompletionOutput::default_from_request_responses_len(
                                     request_responses_len,
-                                ),
-                            ),
+                                );
+                                output.rating = rating.map(|scale| {
+                                    objectiveai::vector::completions::response::Rating::from_scores(
+                                        &scale,
+                                        &output.scores,
+                                    )
+                                });
+                                output
+                            }),
                             retry_token: objectiveai::functions::executions::RetryToken(vec![retry_token]),
                         }
                     )),
@@ -2418,6 +2784,7 @@ where
                         task_path: ftp.path.clone(),
                         inner: chunk,
                         error: None,
+                        reset: None,
                     }
                 );
             }
@@ -2629,6 +2996,105 @@ where
     }
 }
 
+/// The parts of a Function execution shared by all of its tasks.
+struct ExecutionContext<CTXEXT> {
+    /// The request context.
+    ctx: ctx::Context<CTXEXT>,
+    /// The Function execution request.
+    request: Arc<objectiveai::functions::executions::request::Request>,
+    /// Pauses the execution or skips its remaining tasks, if controllable.
+    control: Option<super::ExecutionControl>,
+    /// The retry token of the whole execution, indexed by task index.
+    root_retry_token:
+        Option<Arc<objectiveai::functions::executions::RetryToken>>,
+    /// Unix timestamp of when the execution was created.
+    created: u64,
+    /// The Swiss System round the tasks belong to, if any.
+    swiss_round: Option<u64>,
+    /// The Swiss System pool the tasks belong to, if any.
+    swiss_pool_index: Option<u64>,
+    /// The task indices reserved for dependent tasks.
+    dependent_task_indices: Arc<DependentTaskIndices>,
+}
+
+impl<CTXEXT> Clone for ExecutionContext<CTXEXT> {
+    fn clone(&self) -> Self {
+        Self {
+            ctx: self.ctx.clone(),
+            request: self.request.clone(),
+            control: self.control.clone(),
+            root_retry_token: self.root_retry_token.clone(),
+            created: self.created,
+            swiss_round: self.swiss_round,
+            swiss_pool_index: self.swiss_pool_index,
+            dependent_task_indices: self.dependent_task_indices.clone(),
+        }
+    }
+}
+
+/// Reserves the task indices of dependent tasks, past the root Function's
+/// own task indices, and collects their retry tokens.
+///
+/// A dependent task's subtree is only known once the tasks it depends on have
+/// finished, so its task indices cannot be laid out with the root Function's.
+/// Tasks run in order, so a retried execution reserves the same task indices
+/// for the same dependent tasks, and its retry token stays flat.
+struct DependentTaskIndices {
+    /// The root Function's task index length.
+    start: u64,
+    /// The next task index to reserve.
+    next: AtomicU64,
+    /// Retry token entries of the reserved task indices, from `start`.
+    retry_token: std::sync::Mutex<objectiveai::functions::executions::RetryToken>,
+}
+
+impl DependentTaskIndices {
+    /// Creates an empty reservation past `task_index_len` task indices.
+    fn new(task_index_len: usize) -> Self {
+        Self {
+            start: task_index_len as u64,
+            next: AtomicU64::new(task_index_len as u64),
+            retry_token: std::sync::Mutex::new(
+                objectiveai::functions::executions::RetryToken(Vec::new()),
+            ),
+        }
+    }
+
+    /// Reserves `task_index_len` task indices, returning the first.
+    fn reserve(&self, task_index_len: usize) -> u64 {
+        let task_index = self.next.fetch_add(
+            task_index_len as u64,
+            std::sync::atomic::Ordering::SeqCst,
+        );
+        let end = (task_index + task_index_len as u64 - self.start) as usize;
+        let mut retry_token = self.retry_token.lock().unwrap();
+        if retry_token.0.len() < end {
+            retry_token.0.resize(end, None);
+        }
+        task_index
+    }
+
+    /// Inserts the retry token of the dependent task at `task_index`.
+    fn insert(
+        &self,
+        task_index: u64,
+        retry_token: objectiveai::functions::executions::RetryToken,
+    ) {
+        self.retry_token
+            .lock()
+            .unwrap()
+            .insert((task_index - self.start) as usize, retry_token);
+    }
+
+    /// Appends the reserved task indices to the root Function's retry token.
+    fn extend(
+        &self,
+        retry_token: &mut objectiveai::functions::executions::RetryToken,
+    ) {
+        retry_token.push(self.retry_token.lock().unwrap().clone());
+    }
+}
+
 /// Internal chunk type for streaming execution.
 ///
 /// Represents different kinds of chunks produced during flattened task
@@ -2643,6 +3109,9 @@ enum FtpStreamChunk {
     FunctionExecutionChunk(
         objectiveai::functions::executions::response::streaming::FunctionExecutionTaskChunk,
     ),
+    /// The Vector Completion tasks of a task with `depends_on`, once it has
+    /// been flattened. Only produced when reasoning is requested.
+    DependentTaskChunk(Vec<functions::VectorCompletionFlatTaskProfile>),
     /// The final output of a task with its retry token.
     OutputChunk {
         /// Index of the task in the flattened structure.
@@ -2679,6 +3148,82 @@ struct ConfidenceResponse {
 }
 
 impl ConfidenceResponse {
+    /// Returns the indices of a Vector Completion task's responses in
+    /// `confidence_responses`, adding the responses which are not yet present.
+    pub fn index(
+        confidence_responses: &mut Vec<ConfidenceResponse>,
+        vector_completion_ftp: &functions::VectorCompletionFlatTaskProfile,
+    ) -> Vec<usize> {
+        let mut completion_index_map =
+            Vec::with_capacity(vector_completion_ftp.responses.len());
+        for response in &vector_completion_ftp.responses {
+            let Some((response, response_hash)) = Self::hash(response) else {
+                continue;
+            };
+            match confidence_responses
+                .iter()
+                .position(|c| c.response_hash == response_hash)
+            {
+                Some(i) => {
+                    let confidence_response = &mut confidence_responses[i];
+                    confidence_response
+                        .paths
+                        .push(vector_completion_ftp.path.clone());
+                    confidence_response.confidence_count +=
+                        rust_decimal::Decimal::ONE;
+                    completion_index_map.push(i);
+                }
+                None => {
+                    completion_index_map.push(confidence_responses.len());
+                    confidence_responses.push(ConfidenceResponse {
+                        response_hash,
+                        paths: vec![vector_completion_ftp.path.clone()],
+                        confidence_count: rust_decimal::Decimal::ONE,
+                        response,
+                        confidence: rust_decimal::Decimal::ZERO,
+                        reasoning: Vec::new(),
+                    });
+                }
+            }
+        }
+        completion_index_map
+    }
+
+    /// Returns the indices of a Vector Completion task's responses in
+    /// `confidence_responses`, skipping the responses which are not present.
+    pub fn find(
+        confidence_responses: &[ConfidenceResponse],
+        vector_completion_ftp: &functions::VectorCompletionFlatTaskProfile,
+    ) -> Vec<usize> {
+        vector_completion_ftp
+            .responses
+            .iter()
+            .filter_map(Self::hash)
+            .filter_map(|(_, response_hash)| {
+                confidence_responses
+                    .iter()
+                    .position(|c| c.response_hash == response_hash)
+            })
+            .collect()
+    }
+
+    /// Prepares a response and hashes it for deduplication.
+    fn hash(
+        response: &objectiveai::chat::completions::request::RichContent,
+    ) -> Option<(objectiveai::chat::completions::request::RichContent, u64)>
+    {
+        let mut response = response.clone();
+        response.prepare();
+        let response_string =
+            serde_json::to_string(&response).unwrap_or_default();
+        if response_string.is_empty() {
+            return None;
+        }
+        let mut hasher = ahash::AHasher::default();
+        hasher.write(response_string.as_bytes());
+        Some((response, hasher.finish()))
+    }
+
     /// Formats all confidence responses as assertion parts for the reasoning prompt.
     pub fn assertions(
         confidence_responses: Vec<ConfidenceResponse>,
@@ -2797,6 +3342,7 @@ mod invert_output_tests {
         let expr = Expression::Starlark("output".to_string());
         let (out, err) = apply_task_output_expression(
             &input,
+            None,
             raw,
             &expr,
             true,
@@ -2820,6 +3366,7 @@ mod invert_output_tests {
         let expr = Expression::Starlark("output".to_string());
         let (out, err) = apply_task_output_expression(
             &input,
+            None,
             raw,
             &expr,
             true,
@@ -2845,10 +3392,12 @@ mod invert_output_tests {
             votes: Vec::new(),
             scores: vec![dec!(0.75), dec!(0.25), dec!(0.0)],
             weights: vec![dec!(1.0), dec!(1.0), dec!(1.0)],
+            rating: None,
         });
         let expr = Expression::Starlark("output['scores']".to_string());
         let (out, err) = apply_task_output_expression(
             &input,
+            None,
             raw,
             &expr,
             true,
@@ -2867,3 +3416,188 @@ mod invert_output_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod aggregation_tests {
+    use super::*;
+    use objectiveai::functions::expression::{Expression, FunctionOutput};
+    use objectiveai::functions::{Aggregation, AggregationMethod};
+    use rust_decimal::dec;
+
+    fn empty_input() -> objectiveai::functions::expression::Input {
+        objectiveai::functions::expression::Input::Object(indexmap::IndexMap::new())
+    }
+
+    fn vector_type() -> functions::FunctionType {
+        functions::FunctionType::Vector {
+            output_length: Some(2),
+            input_split: None,
+            input_merge: None,
+        }
+    }
+
+    fn aggregate_scalar(
+        aggregation: Option<&Aggregation>,
+        weights: &[rust_decimal::Decimal],
+        outputs: &[Option<FunctionOutput>],
+    ) -> rust_decimal::Decimal {
+        let input = empty_input();
+        match compute_weighted_function_output(
+            &functions::FunctionType::Scalar,
+            weights,
+            outputs,
+            aggregation,
+            &input,
+            None,
+        ) {
+            Ok(FunctionOutput::Scalar(s)) => s,
+            other => panic!("expected scalar output, got {:?}", other),
+        }
+    }
+
+    fn noisy_scalar_outputs() -> Vec<Option<FunctionOutput>> {
+        vec![
+            Some(FunctionOutput::Scalar(dec!(0.8))),
+            Some(FunctionOutput::Scalar(dec!(0.7))),
+            Some(FunctionOutput::Scalar(dec!(0.0))),
+            None,
+        ]
+    }
+
+    #[test]
+    fn default_is_weighted_mean() {
+        let weights = [dec!(1), dec!(1), dec!(2), dec!(1)];
+        let outputs = noisy_scalar_outputs();
+        let mean = aggregate_scalar(None, &weights, &outputs);
+        assert_eq!(mean, dec!(0.375));
+        let explicit = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::WeightedMean)),
+            &weights,
+            &outputs,
+        );
+        assert_eq!(explicit, mean);
+    }
+
+    #[test]
+    fn weighted_median_ignores_outlier() {
+        let weights = [dec!(1), dec!(1), dec!(1), dec!(1)];
+        let median = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::WeightedMedian)),
+            &weights,
+            &noisy_scalar_outputs(),
+        );
+        assert_eq!(median, dec!(0.7));
+    }
+
+    #[test]
+    fn max_and_min() {
+        let weights = [dec!(1), dec!(1), dec!(1), dec!(1)];
+        let outputs = noisy_scalar_outputs();
+        let max = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::Max)),
+            &weights,
+            &outputs,
+        );
+        let min = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::Min)),
+            &weights,
+            &outputs,
+        );
+        assert_eq!(max, dec!(0.8));
+        assert_eq!(min, dec!(0.0));
+    }
+
+    #[test]
+    fn geometric_mean_scalar() {
+        let weights = [dec!(1), dec!(1)];
+        let outputs = [
+            Some(FunctionOutput::Scalar(dec!(0.25))),
+            Some(FunctionOutput::Scalar(dec!(1))),
+        ];
+        let geometric = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::GeometricMean)),
+            &weights,
+            &outputs,
+        );
+        assert!((geometric - dec!(0.5)).abs() < dec!(0.0001), "{}", geometric);
+    }
+
+    #[test]
+    fn product_of_experts_scalar_sharpens() {
+        let weights = [dec!(1), dec!(1)];
+        let outputs = [
+            Some(FunctionOutput::Scalar(dec!(0.75))),
+            Some(FunctionOutput::Scalar(dec!(0.75))),
+        ];
+        let product = aggregate_scalar(
+            Some(&Aggregation::Method(AggregationMethod::ProductOfExperts)),
+            &weights,
+            &outputs,
+        );
+        // 0.75^2 / (0.75^2 + 0.25^2)
+        assert!((product - dec!(0.9)).abs() < dec!(0.0001), "{}", product);
+    }
+
+    #[test]
+    fn product_of_experts_vector_is_normalized() {
+        let input = empty_input();
+        let outputs = [
+            Some(FunctionOutput::Vector(vec![dec!(0.5), dec!(0.5)])),
+            Some(FunctionOutput::Vector(vec![dec!(0.8), dec!(0.2)])),
+        ];
+        let output = compute_weighted_function_output(
+            &vector_type(),
+            &[dec!(1), dec!(1)],
+            &outputs,
+            Some(&Aggregation::Method(AggregationMethod::ProductOfExperts)),
+            &input,
+            None,
+        );
+        match output {
+            Ok(FunctionOutput::Vector(v)) => {
+                let sum: rust_decimal::Decimal = v.iter().sum();
+                assert!((sum - dec!(1)).abs() < dec!(0.0001), "{:?}", v);
+                assert!((v[0] - dec!(0.8)).abs() < dec!(0.0001), "{:?}", v);
+            }
+            other => panic!("expected vector output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn expression_receives_tasks_and_weights() {
+        let input = empty_input();
+        let expression = Aggregation::Expression(Expression::Starlark(
+            "max([t for t, w in zip(tasks, weights) if t != None and w > 1])"
+                .to_string(),
+        ));
+        let output = compute_weighted_function_output(
+            &functions::FunctionType::Scalar,
+            &[dec!(1), dec!(2), dec!(3), dec!(1)],
+            &noisy_scalar_outputs(),
+            Some(&expression),
+            &input,
+            None,
+        );
+        match output {
+            Ok(FunctionOutput::Scalar(s)) => assert_eq!(s, dec!(0.7)),
+            other => panic!("expected scalar output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn expression_invalid_output_is_error() {
+        let input = empty_input();
+        let expression = Aggregation::Expression(Expression::Starlark(
+            "[0.9, 0.9]".to_string(),
+        ));
+        let output = compute_weighted_function_output(
+            &vector_type(),
+            &[dec!(1)],
+            &[Some(FunctionOutput::Vector(vec![dec!(0.5), dec!(0.5)]))],
+            Some(&expression),
+            &input,
+            None,
+        );
+        assert!(matches!(output, Err(super::super::Error::InvalidVectorOutput(2))));
+    }
+}
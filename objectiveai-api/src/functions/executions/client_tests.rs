@@ -1,13 +1,16 @@
 //! Tests for the function execution client.
 //!
 //! These tests use mock implementations of all fetcher traits and set
-//! `from_rng: true` on all requests to avoid network traffic.
+//! `from_rng: true` on requests to avoid network traffic, except where a
+//! local mock upstream serves the LLMs.
 
+use crate::chat::completions::upstream::openrouter::mock_upstream::MockUpstream;
 use crate::{chat, ctx, ensemble, ensemble_llm, functions, vector};
 use futures::StreamExt;
 use indexmap::IndexMap;
 use rust_decimal::Decimal;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
 
 // ============================================================================
 // Mock Types
@@ -63,7 +66,14 @@ impl ensemble::fetcher::Fetcher<MockContextExt> for MockEnsembleFetcher {
     }
 }
 
-/// Mock completion votes fetcher that returns None.
+/// Votes of the Vector Completions recorded by `MockVectorUsageHandler`,
+/// keyed by ID.
+static COMPLETION_VOTES: LazyLock<
+    Mutex<HashMap<String, Vec<objectiveai::vector::completions::response::Vote>>>,
+> = LazyLock::new(Default::default);
+
+/// Mock completion votes fetcher that returns the votes recorded by
+/// `MockVectorUsageHandler`, or None.
 #[derive(Debug, Clone)]
 struct MockCompletionVotesFetcher;
 
@@ -74,12 +84,12 @@ impl vector::completions::completion_votes_fetcher::Fetcher<MockContextExt>
     async fn fetch(
         &self,
         _ctx: ctx::Context<MockContextExt>,
-        _id: &str,
+        id: &str,
     ) -> Result<
         Option<Vec<objectiveai::vector::completions::response::Vote>>,
         objectiveai::error::ResponseError,
     > {
-        Ok(None)
+        Ok(COMPLETION_VOTES.lock().unwrap().get(id).cloned())
     }
 }
 
@@ -107,7 +117,9 @@ impl vector::completions::cache_vote_fetcher::Fetcher<MockContextExt>
     }
 }
 
-/// Mock function fetcher that always returns None.
+/// Mock function fetcher that returns the nested scalar Function from
+/// `create_nested_dependent_function` for `test/dependent`, and None
+/// otherwise.
 #[derive(Debug, Clone)]
 struct MockFunctionFetcher;
 
@@ -116,15 +128,24 @@ impl functions::function_fetcher::Fetcher<MockContextExt> for MockFunctionFetche
     async fn fetch(
         &self,
         _ctx: ctx::Context<MockContextExt>,
-        _remote: objectiveai::functions::Remote,
-        _owner: &str,
-        _repository: &str,
-        _commit: Option<&str>,
+        remote: objectiveai::functions::Remote,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
     ) -> Result<
         Option<objectiveai::functions::response::GetFunction>,
         objectiveai::error::ResponseError,
     > {
-        Ok(None)
+        if owner != "test" || repository != "dependent" {
+            return Ok(None);
+        }
+        Ok(Some(objectiveai::functions::response::GetFunction {
+            remote,
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+            commit: commit.unwrap_or("main").to_string(),
+            inner: create_nested_dependent_function(),
+        }))
     }
 }
 
@@ -169,7 +190,8 @@ impl chat::completions::usage_handler::UsageHandler<MockContextExt>
     }
 }
 
-/// Mock vector completions usage handler that does nothing.
+/// Mock vector completions usage handler that records the votes of each
+/// Vector Completion.
 #[derive(Debug, Clone)]
 struct MockVectorUsageHandler;
 
@@ -183,9 +205,12 @@ impl vector::completions::usage_handler::UsageHandler<MockContextExt>
         _request: Arc<
             objectiveai::vector::completions::request::VectorCompletionCreateParams,
         >,
-        _response: objectiveai::vector::completions::response::unary::VectorCompletion,
+        response: objectiveai::vector::completions::response::unary::VectorCompletion,
     ) {
-        // Do nothing
+        COMPLETION_VOTES
+            .lock()
+            .unwrap()
+            .insert(response.id, response.votes);
     }
 }
 
@@ -249,6 +274,13 @@ fn create_test_context() -> ctx::Context<MockContextExt> {
 
 /// Creates a test chat completions client with mock dependencies.
 fn create_test_chat_client() -> Arc<TestChatClient> {
+    create_test_chat_client_with_upstream("https://openrouter.ai/api/v1")
+}
+
+/// Creates a test chat completions client whose upstream is at `api_base`.
+fn create_test_chat_client_with_upstream(
+    api_base: &str,
+) -> Arc<TestChatClient> {
     let ensemble_llm_fetcher = Arc::new(
         ensemble_llm::fetcher::CachingFetcher::new(Arc::new(MockEnsembleLlmFetcher)),
     );
@@ -257,7 +289,7 @@ fn create_test_chat_client() -> Arc<TestChatClient> {
     // Create OpenRouter client with dummy values (won't be used since from_rng=true)
     let openrouter_client = chat::completions::upstream::openrouter::Client::new(
         reqwest::Client::new(),
-        "https://openrouter.ai/api/v1".to_string(),
+        api_base.to_string(),
         "dummy-api-key".to_string(),
         None, // user_agent
         None, // x_title
@@ -275,6 +307,7 @@ fn create_test_chat_client() -> Arc<TestChatClient> {
         1.5,
         std::time::Duration::from_secs(60),
         std::time::Duration::from_secs(300),
+        chat::completions::ChunkTimeouts::default(),
     ))
 }
 
@@ -344,9 +377,11 @@ fn empty_input() -> objectiveai::functions::expression::Input {
 /// Creates a simple inline vector function with one vector completion task.
 fn create_simple_vector_function() -> objectiveai::functions::InlineFunction {
     objectiveai::functions::InlineFunction::Vector {
+        definitions: None,
         input_maps: None,
         tasks: vec![objectiveai::functions::TaskExpression::VectorCompletion(
             objectiveai::functions::VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: objectiveai::functions::expression::WithExpression::Value(vec![
@@ -381,6 +416,7 @@ fn create_simple_vector_function() -> objectiveai::functions::InlineFunction {
                 ),
             },
         )],
+        aggregation: None,
         input_split: None,
         input_merge: None,
     }
@@ -409,9 +445,11 @@ fn create_simple_profile() -> objectiveai::functions::InlineProfile {
 /// Creates a simple inline scalar function with one vector completion task.
 fn create_simple_scalar_function() -> objectiveai::functions::InlineFunction {
     objectiveai::functions::InlineFunction::Scalar {
+        definitions: None,
         input_maps: None,
         tasks: vec![objectiveai::functions::TaskExpression::VectorCompletion(
             objectiveai::functions::VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: objectiveai::functions::expression::WithExpression::Value(vec![
@@ -447,6 +485,7 @@ fn create_simple_scalar_function() -> objectiveai::functions::InlineFunction {
                 ),
             },
         )],
+        aggregation: None,
     }
 }
 
@@ -470,6 +509,95 @@ fn create_simple_scalar_profile() -> objectiveai::functions::InlineProfile {
     })
 }
 
+/// Creates a remote scalar function whose second task depends on its first.
+fn create_nested_dependent_function() -> objectiveai::functions::RemoteFunction {
+    serde_json::from_value(serde_json::json!({
+        "type": "scalar.function",
+        "description": "Rates an item, then rates it again.",
+        "input_schema": { "type": "object", "properties": {} },
+        "tasks": [
+            {
+                "type": "vector.completion",
+                "messages": [{ "role": "user", "content": "Rate this" }],
+                "responses": ["Good", "Bad"],
+                "output": { "$starlark": "output['scores'][0]" },
+            },
+            {
+                "type": "vector.completion",
+                "depends_on": [0],
+                "messages": [{ "role": "user", "content": "Rate this again" }],
+                "responses": ["Good", "Bad"],
+                "output": { "$starlark": "output['scores'][0]" },
+            },
+        ],
+    }))
+    .unwrap()
+}
+
+/// Creates a request for an inline scalar function whose first task runs the
+/// nested Function from `create_nested_dependent_function`, and whose second
+/// task depends on it. `base` is merged into the request body.
+fn create_nested_dependent_request(
+    base: serde_json::Value,
+) -> Arc<objectiveai::functions::executions::request::Request> {
+    let mut body = serde_json::json!({
+        "function": {
+            "type": "scalar.function",
+            "tasks": [
+                {
+                    "type": "scalar.function",
+                    "remote": "github",
+                    "owner": "test",
+                    "repository": "dependent",
+                    "commit": "main",
+                    "input": {},
+                    "output": { "$starlark": "output" },
+                },
+                {
+                    "type": "vector.completion",
+                    "depends_on": [0],
+                    "messages": [{ "role": "user", "content": "Rate this" }],
+                    "responses": ["Good", "Bad"],
+                    "output": { "$starlark": "output['scores'][0]" },
+                },
+            ],
+        },
+        "profile": {
+            "tasks": [
+                {
+                    "ensemble": {
+                        "llms": [
+                            { "count": 1, "model": "openai/gpt-4o" },
+                            { "count": 1, "model": "openai/gpt-4o-mini" },
+                        ],
+                    },
+                    "profile": [1.0, 1.0],
+                },
+                {
+                    "ensemble": {
+                        "llms": [
+                            { "count": 1, "model": "openai/gpt-4o" },
+                            { "count": 1, "model": "openai/gpt-4o-mini" },
+                        ],
+                    },
+                    "profile": [1.0, 1.0],
+                },
+            ],
+            "profile": [0.5, 0.5],
+        },
+        "input": {},
+        "from_rng": true,
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(base.as_object().unwrap().clone());
+    Arc::new(
+        objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+            body: serde_json::from_value(body).unwrap(),
+        },
+    )
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -584,6 +712,66 @@ mod tests {
         }
     }
 
+    /// Tests that a scalar completion task rates the prompt on its scale,
+    /// without sending the values of the scale as responses.
+    #[tokio::test]
+    async fn test_scalar_completion_task_with_rng() {
+        let chat_client = create_test_chat_client();
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        let request = Arc::new(
+            objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+                body: serde_json::from_value(serde_json::json!({
+                    "function": {
+                        "type": "scalar.function",
+                        "tasks": [{
+                            "type": "scalar.completion",
+                            "messages": [{ "role": "user", "content": "Rate this" }],
+                            "scale": { "min": 1, "max": 5 },
+                            "output": {
+                                "$starlark": "(output['rating']['mean'] - 1) / 4",
+                            },
+                        }],
+                    },
+                    "profile": {
+                        "tasks": [{
+                            "ensemble": {
+                                "llms": [
+                                    { "count": 1, "model": "openai/gpt-4o" },
+                                    { "count": 1, "model": "openai/gpt-4o-mini" },
+                                ],
+                            },
+                            "profile": [1.0, 1.0],
+                        }],
+                        "profile": [1.0],
+                    },
+                    "input": {},
+                    "from_rng": true,
+                }))
+                .unwrap(),
+            },
+        );
+
+        let response = function_client
+            .create_unary_handle_usage(create_test_context(), request)
+            .await
+            .expect("Function execution should succeed");
+
+        assert!(!response.tasks_errors, "{:?}", response.tasks);
+        match &response.output {
+            objectiveai::functions::expression::FunctionOutput::Scalar(score) => {
+                assert!(
+                    *score >= Decimal::ZERO && *score <= Decimal::ONE,
+                    "Scalar score should be between 0 and 1, got {}",
+                    score
+                );
+            }
+            other => panic!("Expected scalar output, got {:?}", other),
+        }
+    }
+
     /// Tests streaming function execution with from_rng.
     #[tokio::test]
     async fn test_streaming_function_execution_with_rng() {
@@ -649,6 +837,64 @@ mod tests {
         }
     }
 
+    /// Tests that skipping the remaining tasks before any task finishes
+    /// runs no tasks.
+    #[tokio::test]
+    async fn test_skip_remaining_tasks_with_rng() {
+        let chat_client = create_test_chat_client();
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        let ctx = create_test_context();
+        let control = functions::executions::ExecutionControl::new();
+        control.skip_remaining_tasks.cancel();
+
+        let request = Arc::new(objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+            body: objectiveai::functions::executions::request::FunctionInlineProfileInlineRequestBody {
+                function: create_simple_vector_function(),
+                profile: create_simple_profile(),
+                base: objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody {
+                    retry_token: None,
+                    from_cache: None,
+                    from_rng: Some(true),
+                    reasoning: None,
+                    strategy: None,
+                    input: empty_input(),
+                    provider: None,
+                    seed: None,
+                    stream: Some(true),
+                    backoff_max_elapsed_time: None,
+                    first_chunk_timeout: None,
+                    other_chunk_timeout: None,
+                },
+            },
+        });
+
+        let stream = function_client
+            .create_streaming(ctx, request, Some(control))
+            .await
+            .expect("Streaming should start");
+        let chunks: Vec<_> = stream.collect().await;
+        let mut aggregate = chunks[0].clone();
+        for chunk in chunks.iter().skip(1) {
+            aggregate.push(chunk);
+        }
+        let response: objectiveai::functions::executions::response::unary::FunctionExecution =
+            aggregate.into();
+
+        assert!(response.tasks.is_empty(), "No task should have run");
+
+        // the skipped task is left for a retry
+        let retry_token =
+            objectiveai::functions::executions::RetryToken::try_from_string(
+                response.retry_token.as_deref().expect("Should have a retry token"),
+            )
+            .expect("Retry token should parse");
+        assert_eq!(retry_token.0.len(), 1);
+        assert!(retry_token.0[0].is_none(), "Skipped task should not be retried from cache");
+    }
+
     /// Tests that multiple vector completion tasks are combined correctly.
     #[tokio::test]
     async fn test_multi_task_function_execution_with_rng() {
@@ -661,10 +907,12 @@ mod tests {
 
         // Create a function with two tasks
         let function = objectiveai::functions::InlineFunction::Vector {
+            definitions: None,
             input_maps: None,
             tasks: vec![
                 objectiveai::functions::TaskExpression::VectorCompletion(
                     objectiveai::functions::VectorCompletionTaskExpression {
+                        depends_on: None,
                         skip: None,
                         map: None,
                         messages: objectiveai::functions::expression::WithExpression::Value(vec![
@@ -701,6 +949,7 @@ mod tests {
                 ),
                 objectiveai::functions::TaskExpression::VectorCompletion(
                     objectiveai::functions::VectorCompletionTaskExpression {
+                        depends_on: None,
                         skip: None,
                         map: None,
                         messages: objectiveai::functions::expression::WithExpression::Value(vec![
@@ -736,6 +985,7 @@ mod tests {
                             },
                 ),
             ],
+            aggregation: None,
             input_split: None,
             input_merge: None,
         };
@@ -912,4 +1162,426 @@ mod tests {
             "Should have 1 task"
         );
     }
+
+
+    /// Tests that a task with `depends_on` runs after, and can read, the task
+    /// it depends on.
+    #[tokio::test]
+    async fn test_dependent_task_function_execution_with_rng() {
+        let chat_client = create_test_chat_client();
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        let ctx = create_test_context();
+
+        // Create a function whose second task depends on the first
+        let function = objectiveai::functions::InlineFunction::Vector {
+            definitions: None,
+            input_maps: None,
+            tasks: vec![
+                objectiveai::functions::TaskExpression::VectorCompletion(
+                    objectiveai::functions::VectorCompletionTaskExpression {
+                        depends_on: None,
+                        skip: None,
+                        map: None,
+                        messages: objectiveai::functions::expression::WithExpression::Value(vec![
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::MessageExpression::User(
+                                    objectiveai::chat::completions::request::UserMessageExpression {
+                                        content: objectiveai::functions::expression::WithExpression::Value(
+                                            objectiveai::chat::completions::request::RichContentExpression::Text(
+                                                "Task 1: Which is better?".to_string(),
+                                            ),
+                                        ),
+                                        name: None,
+                                    },
+                                ),
+                            ),
+                        ]),
+                        tools: None,
+                        responses: objectiveai::functions::expression::WithExpression::Value(vec![
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::RichContentExpression::Text(
+                                    "A".to_string(),
+                                ),
+                            ),
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::RichContentExpression::Text(
+                                    "B".to_string(),
+                                ),
+                            ),
+                        ]),
+                        output: objectiveai::functions::expression::Expression::Starlark(
+                            "output['scores']".to_string(),
+                        ),
+                            },
+                ),
+                objectiveai::functions::TaskExpression::VectorCompletion(
+                    objectiveai::functions::VectorCompletionTaskExpression {
+                        depends_on: Some(vec![0]),
+                        skip: Some(objectiveai::functions::expression::Expression::Starlark(
+                            "tasks[0] == None".to_string(),
+                        )),
+                        map: None,
+                        messages: objectiveai::functions::expression::WithExpression::Value(vec![
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::MessageExpression::User(
+                                    objectiveai::chat::completions::request::UserMessageExpression {
+                                        content: objectiveai::functions::expression::WithExpression::Value(
+                                            objectiveai::chat::completions::request::RichContentExpression::Text(
+                                                "Task 2: Which is better?".to_string(),
+                                            ),
+                                        ),
+                                        name: None,
+                                    },
+                                ),
+                            ),
+                        ]),
+                        tools: None,
+                        responses: objectiveai::functions::expression::WithExpression::Value(vec![
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::RichContentExpression::Text(
+                                    "A".to_string(),
+                                ),
+                            ),
+                            objectiveai::functions::expression::WithExpression::Value(
+                                objectiveai::chat::completions::request::RichContentExpression::Text(
+                                    "B".to_string(),
+                                ),
+                            ),
+                        ]),
+                        output: objectiveai::functions::expression::Expression::Starlark(
+                            "output['scores']".to_string(),
+                        ),
+                            },
+                ),
+            ],
+            aggregation: None,
+            input_split: None,
+            input_merge: None,
+        };
+
+        // Create a profile with equal weights for both tasks
+        let profile = objectiveai::functions::InlineProfile::Tasks(objectiveai::functions::InlineTasksProfile {
+            tasks: vec![
+                objectiveai::functions::TaskProfile::Inline(
+                    objectiveai::functions::InlineProfile::Auto(
+                        objectiveai::functions::InlineAutoProfile {
+                            ensemble: create_simple_ensemble(),
+                            profile: objectiveai::vector::completions::request::Profile::Weights(
+                                vec![Decimal::ONE],
+                            ),
+                        },
+                    ),
+                ),
+                objectiveai::functions::TaskProfile::Inline(
+                    objectiveai::functions::InlineProfile::Auto(
+                        objectiveai::functions::InlineAutoProfile {
+                            ensemble: create_simple_ensemble(),
+                            profile: objectiveai::vector::completions::request::Profile::Weights(
+                                vec![Decimal::ONE],
+                            ),
+                        },
+                    ),
+                ),
+            ],
+            profile: objectiveai::vector::completions::request::Profile::Weights(
+                vec![Decimal::new(5, 1), Decimal::new(5, 1)],
+            ), // 0.5, 0.5
+        });
+
+        let request = Arc::new(objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+            body: objectiveai::functions::executions::request::FunctionInlineProfileInlineRequestBody {
+                function,
+                profile,
+                base: objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody {
+                    retry_token: None,
+                    from_cache: None,
+                    from_rng: Some(true),
+                    reasoning: None,
+                    strategy: None,
+                    input: empty_input(),
+                    provider: None,
+                    seed: None,
+                    stream: None,
+                    backoff_max_elapsed_time: None,
+                    first_chunk_timeout: None,
+                    other_chunk_timeout: None,
+                },
+            },
+        });
+
+        let result = function_client
+            .create_unary_handle_usage(ctx, request)
+            .await;
+
+        assert!(result.is_ok(), "Dependent task function should succeed: {:?}", result.err());
+
+        let response = result.unwrap();
+
+        // The dependent task ran since the first task's output was available
+        assert_eq!(response.tasks.len(), 2, "Should have 2 tasks");
+
+        // Verify the output is a valid vector
+        match &response.output {
+            objectiveai::functions::expression::FunctionOutput::Vector(scores) => {
+                assert_eq!(scores.len(), 2, "Should have 2 scores");
+                let sum: Decimal = scores.iter().cloned().sum();
+                assert!(
+                    sum >= Decimal::new(99, 2) && sum <= Decimal::new(101, 2),
+                    "Scores should sum to approximately 1, got {}",
+                    sum
+                );
+            }
+            other => panic!("Expected vector output, got {:?}", other),
+        }
+    }
+
+    /// Tests that dependent tasks of a nested Function and of its parent run
+    /// in their reserved task indices and are covered by the retry token.
+    #[tokio::test]
+    async fn test_nested_dependent_task_function_execution_with_rng() {
+        let chat_client = create_test_chat_client();
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        let ctx = create_test_context();
+
+        let request = create_nested_dependent_request(serde_json::json!({}));
+
+        let response = function_client
+            .create_unary_handle_usage(ctx, request)
+            .await
+            .expect("Nested dependent task function should succeed");
+
+        assert_eq!(response.tasks.len(), 2, "Should have 2 tasks");
+        assert!(!response.tasks_errors, "Should have no task errors");
+        let mut indices: Vec<u64> = response
+            .tasks
+            .iter()
+            .map(|task| match task {
+                objectiveai::functions::executions::response::unary::Task::FunctionExecution(f) => {
+                    assert_eq!(f.inner.tasks.len(), 2, "Nested function should have 2 tasks");
+                    f.index
+                }
+                objectiveai::functions::executions::response::unary::Task::VectorCompletion(v) => {
+                    v.index
+                }
+            })
+            .collect();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1], "Task indices should be distinct");
+
+        // [nested task 0, nested dependent task 1, dependent task 1], then
+        // the task indices reserved for the two dependent tasks
+        let retry_token =
+            objectiveai::functions::executions::RetryToken::try_from_string(
+                response.retry_token.as_deref().expect("Should have a retry token"),
+            )
+            .expect("Retry token should decode");
+        assert_eq!(retry_token.0.len(), 5, "Retry token should have 5 entries");
+        let mut task_indices: Vec<u64> = response
+            .tasks
+            .iter()
+            .flat_map(|task| match task {
+                objectiveai::functions::executions::response::unary::Task::FunctionExecution(f) => f
+                    .inner
+                    .tasks
+                    .iter()
+                    .map(|task| match task {
+                        objectiveai::functions::executions::response::unary::Task::VectorCompletion(v) => {
+                            v.task_index
+                        }
+                        _ => panic!("Nested function should only have vector completion tasks"),
+                    })
+                    .collect(),
+                objectiveai::functions::executions::response::unary::Task::VectorCompletion(v) => {
+                    vec![v.task_index]
+                }
+            })
+            .collect();
+        task_indices.sort();
+        assert_eq!(
+            task_indices,
+            vec![0, 3, 4],
+            "Dependent tasks should run in the reserved task indices"
+        );
+
+        match &response.output {
+            objectiveai::functions::expression::FunctionOutput::Scalar(score) => {
+                assert!(
+                    *score >= Decimal::ZERO && *score <= Decimal::ONE,
+                    "Score should be in [0, 1], got {}",
+                    score
+                );
+            }
+            other => panic!("Expected scalar output, got {:?}", other),
+        }
+    }
+
+    /// Tests that the Vector Completion tasks of dependent tasks are indexed
+    /// for the reasoning summary.
+    #[tokio::test]
+    async fn test_nested_dependent_task_reasoning_with_mock_upstream() {
+        let chat_client =
+            create_test_chat_client_with_upstream(&MockUpstream::spawn().await.api_base);
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        let ctx = create_test_context();
+
+        // vector completions need ids to be summarized, so they run upstream
+        let ensemble = serde_json::json!({
+            "llms": [
+                { "count": 1, "model": "openai/gpt-4o", "output_mode": "json_schema" },
+                { "count": 1, "model": "openai/gpt-4o-mini", "output_mode": "json_schema" },
+            ],
+        });
+        let request = create_nested_dependent_request(serde_json::json!({
+            "profile": {
+                "tasks": [
+                    { "ensemble": ensemble, "profile": [1.0, 1.0] },
+                    { "ensemble": ensemble, "profile": [1.0, 1.0] },
+                ],
+                "profile": [0.5, 0.5],
+            },
+            "reasoning": { "model": { "model": "openai/gpt-4o" } },
+            "from_rng": false,
+        }));
+
+        let response = function_client
+            .create_unary_handle_usage(ctx, request)
+            .await
+            .expect("Nested dependent task function should succeed");
+
+        assert_eq!(response.tasks.len(), 2, "Should have 2 tasks");
+        assert!(!response.tasks_errors, "Should have no task errors");
+        let reasoning = response
+            .reasoning
+            .expect("Should have a reasoning summary");
+        assert!(reasoning.error.is_none(), "Reasoning should succeed");
+    }
+
+    /// Tests that an execution with dependent tasks resumes from its retry
+    /// token, reusing the votes at every task index, including those reserved
+    /// for the dependent tasks.
+    #[tokio::test]
+    async fn test_nested_dependent_task_retry_with_mock_upstream() {
+        let chat_client =
+            create_test_chat_client_with_upstream(&MockUpstream::spawn().await.api_base);
+        let vector_client = create_test_vector_client(chat_client.clone());
+        let function_client =
+            create_test_function_client(chat_client, vector_client);
+
+        // vector completions need ids to be retried, so they run upstream
+        let ensemble = serde_json::json!({
+            "llms": [
+                { "count": 1, "model": "openai/gpt-4o", "output_mode": "json_schema" },
+                { "count": 1, "model": "openai/gpt-4o-mini", "output_mode": "json_schema" },
+            ],
+        });
+        let base = serde_json::json!({
+            "profile": {
+                "tasks": [
+                    { "ensemble": ensemble, "profile": [1.0, 1.0] },
+                    { "ensemble": ensemble, "profile": [1.0, 1.0] },
+                ],
+                "profile": [0.5, 0.5],
+            },
+            "from_rng": false,
+        });
+
+        let first = function_client
+            .clone()
+            .create_unary_handle_usage(
+                create_test_context(),
+                create_nested_dependent_request(base.clone()),
+            )
+            .await
+            .expect("Nested dependent task function should succeed");
+        assert!(!first.tasks_errors, "Should have no task errors");
+        let retry_token_string =
+            first.retry_token.clone().expect("Should have a retry token");
+        let retry_token =
+            objectiveai::functions::executions::RetryToken::try_from_string(
+                &retry_token_string,
+            )
+            .expect("Retry token should decode");
+        assert_eq!(retry_token.0.len(), 5, "Retry token should have 5 entries");
+        assert!(
+            retry_token.0[1].is_none() && retry_token.0[2].is_none(),
+            "Dependent tasks' own task indices should be empty"
+        );
+        let ids: Vec<String> = [0, 3, 4]
+            .into_iter()
+            .map(|i| {
+                retry_token.0[i]
+                    .clone()
+                    .expect("Vector completion should be covered")
+            })
+            .collect();
+
+        // votes are recorded once the vector completions' streams end
+        for _ in 0..100 {
+            let recorded = {
+                let votes = COMPLETION_VOTES.lock().unwrap();
+                ids.iter().all(|id| votes.contains_key(id))
+            };
+            if recorded {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let mut body = base;
+        body["retry_token"] = serde_json::json!(retry_token_string);
+        let second = function_client
+            .create_unary_handle_usage(
+                create_test_context(),
+                create_nested_dependent_request(body),
+            )
+            .await
+            .expect("Resumed nested dependent task function should succeed");
+        assert!(!second.tasks_errors, "Should have no task errors");
+
+        let mut vector_completions = Vec::new();
+        for task in &second.tasks {
+            match task {
+                objectiveai::functions::executions::response::unary::Task::FunctionExecution(f) => {
+                    for task in &f.inner.tasks {
+                        match task {
+                            objectiveai::functions::executions::response::unary::Task::VectorCompletion(v) => {
+                                vector_completions.push(v);
+                            }
+                            _ => panic!("Nested function should only have vector completion tasks"),
+                        }
+                    }
+                }
+                objectiveai::functions::executions::response::unary::Task::VectorCompletion(v) => {
+                    vector_completions.push(v);
+                }
+            }
+        }
+        assert_eq!(vector_completions.len(), 3, "Should have 3 vector completions");
+        for v in vector_completions {
+            assert_eq!(
+                Some(&v.inner.id),
+                retry_token.0[v.task_index as usize].as_ref(),
+                "Vector completion should reuse the votes at its task index"
+            );
+            assert!(
+                !v.inner.votes.is_empty()
+                    && v.inner.votes.iter().all(|vote| vote.retry == Some(true)),
+                "Every vote should be retried"
+            );
+        }
+        assert_eq!(
+            second.retry_token,
+            Some(retry_token_string),
+            "Retry token should be unchanged"
+        );
+    }
 }
@@ -0,0 +1,44 @@
+//! Control of a running Function execution.
+
+/// Pauses or stops a running Function execution.
+///
+/// Created by callers which let the client control an execution, such as the
+/// WebSocket handler, and passed to [`Client::create_streaming`]. Executions
+/// created without one can't be paused or stopped early.
+///
+/// [`Client::create_streaming`]: super::Client::create_streaming
+#[derive(Debug, Clone)]
+pub struct ExecutionControl {
+    /// Cancelled to skip the remaining tasks of the execution and aggregate
+    /// the outputs of the tasks which have finished.
+    pub skip_remaining_tasks: tokio_util::sync::CancellationToken,
+    /// Set to pause the execution. Tasks which have started keep running,
+    /// but no new tasks start until it is unset.
+    pub pause_new_tasks: tokio::sync::watch::Sender<bool>,
+}
+
+impl ExecutionControl {
+    /// Creates a control for an execution which is neither paused nor
+    /// skipping its remaining tasks.
+    pub fn new() -> Self {
+        Self {
+            skip_remaining_tasks: tokio_util::sync::CancellationToken::new(),
+            pause_new_tasks: tokio::sync::watch::Sender::new(false),
+        }
+    }
+
+    /// Waits until new tasks may start, i.e. `pause_new_tasks` is unset.
+    pub async fn new_tasks_unpaused(&self) {
+        let _ = self
+            .pause_new_tasks
+            .subscribe()
+            .wait_for(|paused| !*paused)
+            .await;
+    }
+}
+
+impl Default for ExecutionControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
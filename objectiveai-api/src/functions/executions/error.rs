@@ -66,6 +66,18 @@ pub enum Error {
     /// No valid task outputs to combine.
     #[error("no valid task outputs")]
     NoValidTaskOutputs,
+    /// A task's `depends_on` lists itself or a later task.
+    #[error("invalid task dependency: {0}")]
+    InvalidTaskDependency(String),
+    /// A task with `depends_on` could not be compiled or flattened once the
+    /// tasks it depends on had finished.
+    #[error("dependent task {task_index} error: {error}")]
+    DependentTask {
+        /// Index of the task that failed.
+        task_index: usize,
+        /// The error compiling or flattening the task.
+        error: Box<Error>,
+    },
     /// One or more task output expressions failed.
     #[error("task output expression errors: {0:?}")]
     TaskOutputExpressionErrors(Vec<TaskOutputExpressionError>),
@@ -103,6 +115,8 @@ impl objectiveai::error::StatusError for Error {
             Error::InvalidStrategy(_) => 400,
             Error::NoValidTaskOutputs => 400,
             Error::TaskOutputExpressionErrors(_) => 400,
+            Error::InvalidTaskDependency(_) => 400,
+            Error::DependentTask { error, .. } => error.status(),
         }
     }
 
@@ -193,6 +207,15 @@ impl objectiveai::error::StatusError for Error {
                         "message": e.message,
                     })).collect::<Vec<_>>(),
                 }),
+                Error::InvalidTaskDependency(msg) => serde_json::json!({
+                    "kind": "invalid_task_dependency",
+                    "error": msg,
+                }),
+                Error::DependentTask { task_index, error } => serde_json::json!({
+                    "kind": "dependent_task",
+                    "task_index": task_index,
+                    "error": error.message(),
+                }),
             }
         }))
     }
@@ -5,6 +5,7 @@
 //! streaming output, retry tokens, and reasoning summaries.
 
 mod client;
+mod control;
 mod error;
 pub mod usage_handler;
 
@@ -12,4 +13,5 @@ pub mod usage_handler;
 mod client_tests;
 
 pub use client::*;
+pub use control::*;
 pub use error::*;
@@ -201,6 +201,17 @@ pub struct FunctionFlatTaskProfile {
     pub profile: Vec<rust_decimal::Decimal>,
     /// The Function type (scalar or vector).
     pub r#type: FunctionType,
+    /// Named definitions from the Function definition, available to the
+    /// output expressions of this Function's tasks.
+    pub definitions: Option<objectiveai::functions::expression::Definitions>,
+    /// How task outputs are combined into the final output. None for the
+    /// weighted mean.
+    pub aggregation: Option<objectiveai::functions::Aggregation>,
+    /// Tasks with `depends_on`, in order. Their entries in `tasks` are None
+    /// until they are flattened during execution. Each keeps a single, empty
+    /// task index here, and the flattened task runs in task indices reserved
+    /// past the root Function's.
+    pub dependent_tasks: Vec<DependentTaskProfile>,
     /// Expression to transform the task result from the parent task definition.
     /// Receives: `input` (function input), `output` (the raw FunctionOutput).
     /// None for root-level functions (not called as a task from a parent).
@@ -246,6 +257,27 @@ impl FunctionFlatTaskProfile {
     }
 }
 
+/// A task with `depends_on`, awaiting the outputs of the tasks before it.
+///
+/// Compiled and flattened with [`get_dependent_flat_task_profile`] once the
+/// tasks it depends on have finished.
+#[derive(Debug, Clone)]
+pub struct DependentTaskProfile {
+    /// Index of the task in the Function's tasks.
+    pub index: usize,
+    /// Path to this task in the Function tree (indices into tasks arrays).
+    pub path: Vec<u64>,
+    /// The Function the task belongs to.
+    pub function: Arc<objectiveai::functions::Function>,
+    /// The task's profile, if the Function's Profile is tasks-based.
+    pub task_profile: Option<objectiveai::functions::TaskProfile>,
+    /// The ensemble and profile to use, if the Function's Profile is auto.
+    pub auto_profile: Option<objectiveai::functions::InlineAutoProfile>,
+    /// Whether to invert the compiled output after applying the task's
+    /// output expression.
+    pub invert_output: bool,
+}
+
 /// The type of a Function's output.
 #[derive(Debug, Clone)]
 pub enum FunctionType {
@@ -316,6 +348,9 @@ pub struct VectorCompletionFlatTaskProfile {
     pub tools: Option<Vec<objectiveai::chat::completions::request::Tool>>,
     /// The compiled response options the LLMs will vote on.
     pub responses: Vec<objectiveai::chat::completions::request::RichContent>,
+    /// The rating scale, if the LLMs rate the prompt. The responses are then
+    /// the values of the scale.
+    pub rating: Option<objectiveai::vector::completions::request::RatingScale>,
     /// Expression to transform the raw VectorCompletionOutput into a FunctionOutput.
     /// Receives: `output` (the raw VectorCompletionOutput).
     pub output: objectiveai::functions::expression::Expression,
@@ -464,15 +499,27 @@ pub async fn get_flat_task_profile<CTXEXT>(
     task_output: Option<objectiveai::functions::expression::Expression>,
     invert_output: bool,
     function_fetcher: Arc<
-        impl super::function_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+        impl ?Sized
+        + super::function_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
     >,
     profile_fetcher: Arc<
-        impl super::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+        impl ?Sized
+        + super::profile_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
     >,
     ensemble_fetcher: Arc<
         crate::ensemble::fetcher::CachingFetcher<
             CTXEXT,
-            impl crate::ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + crate::ensemble::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
         >,
     >,
 ) -> Result<super::FunctionFlatTaskProfile, super::executions::Error>
@@ -616,16 +663,11 @@ where
     }
 
     // extract profile data based on profile type (tasks-based or auto)
-    struct AutoConfig {
-        ensemble: objectiveai::vector::completions::request::Ensemble,
-        vc_profile: objectiveai::vector::completions::request::Profile,
-    }
-
     let function_tasks_len = function.tasks().len();
     let mut profile_weights: Vec<rust_decimal::Decimal>;
     let mut profile_invert_flags: Vec<bool>;
     let task_profiles: Option<Vec<objectiveai::functions::TaskProfile>>;
-    let auto_config: Option<AutoConfig>;
+    let auto_profile: Option<objectiveai::functions::InlineAutoProfile>;
 
     match profile {
         objectiveai::functions::Profile::Remote(
@@ -648,7 +690,7 @@ where
             profile_weights = w;
             profile_invert_flags = i;
             task_profiles = Some(rp.tasks);
-            auto_config = None;
+            auto_profile = None;
         }
         objectiveai::functions::Profile::Inline(
             objectiveai::functions::InlineProfile::Tasks(ip),
@@ -670,7 +712,7 @@ where
             profile_weights = w;
             profile_invert_flags = i;
             task_profiles = Some(ip.tasks);
-            auto_config = None;
+            auto_profile = None;
         }
         objectiveai::functions::Profile::Remote(
             objectiveai::functions::RemoteProfile::Auto(rp),
@@ -678,9 +720,9 @@ where
             profile_weights = Vec::new();
             profile_invert_flags = Vec::new();
             task_profiles = None;
-            auto_config = Some(AutoConfig {
+            auto_profile = Some(objectiveai::functions::InlineAutoProfile {
                 ensemble: rp.ensemble,
-                vc_profile: rp.profile,
+                profile: rp.profile,
             });
         }
         objectiveai::functions::Profile::Inline(
@@ -689,15 +731,14 @@ where
             profile_weights = Vec::new();
             profile_invert_flags = Vec::new();
             task_profiles = None;
-            auto_config = Some(AutoConfig {
-                ensemble: ip.ensemble,
-                vc_profile: ip.profile,
-            });
+            auto_profile = Some(ip);
         }
     }
 
-    // take description
+    // take description, definitions, and aggregation
     let description = function.description().map(str::to_owned);
+    let definitions = function.definitions().cloned();
+    let aggregation = function.aggregation().cloned();
 
     // take type, compile output_length if needed
     let r#type = match function {
@@ -717,6 +758,9 @@ where
                     input: &input,
                     output: None,
                     map: None,
+                    definitions: definitions.as_ref(),
+                    tasks: None,
+                    weights: None,
                 },
             );
             FunctionType::Vector {
@@ -743,11 +787,34 @@ where
         },
     };
 
+    // validate that tasks only depend on earlier tasks
+    for (i, task) in function.tasks().iter().enumerate() {
+        if let Some(j) = task
+            .depends_on()
+            .and_then(|depends_on| depends_on.iter().find(|&&j| j >= i as u64))
+        {
+            return Err(super::executions::Error::InvalidTaskDependency(
+                format!(
+                    "task {} depends on task {}, but tasks may only depend on earlier tasks",
+                    i, j
+                ),
+            ));
+        }
+    }
+
+    // keep the function to compile tasks with depends_on during execution
+    let dependent_function = function
+        .tasks()
+        .iter()
+        .any(|task| task.depends_on().is_some())
+        .then(|| Arc::new(function.clone()));
+    let mut dependent_tasks = Vec::new();
+
     // compile function tasks
     let tasks = function.compile_tasks(&input)?;
 
     // for auto profiles, compute equal weights after task compilation
-    if auto_config.is_some() {
+    if auto_profile.is_some() {
         let num_tasks = tasks.len();
         let weight = if num_tasks > 0 {
             rust_decimal::Decimal::ONE / rust_decimal::Decimal::from(num_tasks as u64)
@@ -769,14 +836,6 @@ where
     {
         // get task profile if in tasks-based mode
         let task_profile = task_profiles_iter.as_mut().map(|iter| iter.next().unwrap());
-        // if skip, push None to flat tasks
-        let task = match task {
-            Some(task) => task,
-            None => {
-                flat_tasks_or_futs.push(TaskFut::SkipTask);
-                continue;
-            }
-        };
 
         // task path
         let task_path = {
@@ -786,468 +845,623 @@ where
             p
         };
 
-        // switch by task type
-        match task {
-            objectiveai::functions::CompiledTask::One(
-                objectiveai::functions::Task::ScalarFunction(
-                    objectiveai::functions::ScalarFunctionTask {
-                        remote,
+        // tasks with depends_on are compiled to None here, and flattened
+        // during execution once the tasks they depend on have finished
+        let task_profile = if let Some(function) = &dependent_function
+            && function.tasks()[i].depends_on().is_some()
+        {
+            dependent_tasks.push(DependentTaskProfile {
+                index: i,
+                path: task_path.clone(),
+                function: function.clone(),
+                task_profile,
+                auto_profile: auto_profile.clone(),
+                invert_output: profile_invert_flags[i],
+            });
+            None
+        } else {
+            task_profile
+        };
+
+        flat_tasks_or_futs.push(get_task_flat_task_profile(
+            ctx.clone(),
+            FunctionTask {
+                path: task_path,
+                task,
+                task_profile,
+                auto_profile: auto_profile.as_ref(),
+                invert_output: profile_invert_flags[i],
+                definitions: definitions.as_ref(),
+            },
+            function_fetcher.clone(),
+            profile_fetcher.clone(),
+            ensemble_fetcher.clone(),
+        ));
+    }
+
+    // await all futs
+    let tasks = futures::future::try_join_all(flat_tasks_or_futs).await?;
+
+    // return flat function task
+    Ok(super::FunctionFlatTaskProfile {
+        path,
+        description,
+        full_function_id: function_full_id,
+        full_profile_id: profile_full_id,
+        input,
+        tasks,
+        profile: profile_weights,
+        r#type,
+        definitions,
+        aggregation,
+        dependent_tasks,
+        task_output,
+        invert_output,
+    })
+}
+
+/// Compiles and flattens a task with `depends_on`.
+///
+/// `task_outputs` holds the outputs of the Function's earlier tasks, after
+/// their output expressions (None if skipped or failed). Returns `None` if
+/// the task is skipped.
+pub async fn get_dependent_flat_task_profile<CTXEXT>(
+    ctx: ctx::Context<CTXEXT>,
+    task: DependentTaskProfile,
+    input: &objectiveai::functions::expression::Input,
+    task_outputs: &[Option<objectiveai::functions::expression::FunctionOutput>],
+    function_fetcher: Arc<
+        impl ?Sized
+        + super::function_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    profile_fetcher: Arc<
+        impl ?Sized
+        + super::profile_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ensemble_fetcher: Arc<
+        crate::ensemble::fetcher::CachingFetcher<
+            CTXEXT,
+            impl ?Sized
+            + crate::ensemble::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+) -> Result<Option<super::FlatTaskProfile>, super::executions::Error>
+where
+    CTXEXT: Send + Sync + 'static,
+{
+    let compiled_task =
+        task.function.compile_task(task.index, input, task_outputs)?;
+    get_task_flat_task_profile(
+        ctx,
+        FunctionTask {
+            path: task.path,
+            task: compiled_task,
+            task_profile: task.task_profile,
+            auto_profile: task.auto_profile.as_ref(),
+            invert_output: task.invert_output,
+            definitions: task.function.definitions(),
+        },
+        function_fetcher,
+        profile_fetcher,
+        ensemble_fetcher,
+    )
+    .await
+}
+
+/// A compiled task of a Function, with the profile it runs under.
+struct FunctionTask<'a> {
+    /// Path to the task in the Function tree (indices into tasks arrays).
+    path: Vec<u64>,
+    /// The compiled task (None if skipped).
+    task: Option<objectiveai::functions::CompiledTask>,
+    /// The task's profile, if the Function's Profile is tasks-based.
+    task_profile: Option<objectiveai::functions::TaskProfile>,
+    /// The ensemble and profile to use, if the Function's Profile is auto.
+    auto_profile: Option<&'a objectiveai::functions::InlineAutoProfile>,
+    /// Whether to invert the compiled output after applying the task's
+    /// output expression.
+    invert_output: bool,
+    /// Named definitions from the Function definition.
+    definitions: Option<&'a objectiveai::functions::expression::Definitions>,
+}
+
+/// Flattens a single compiled task of a Function.
+///
+/// Returns `None` if the task was skipped.
+async fn get_task_flat_task_profile<CTXEXT>(
+    ctx: ctx::Context<CTXEXT>,
+    FunctionTask {
+        path: task_path,
+        task,
+        task_profile,
+        auto_profile,
+        invert_output,
+        definitions,
+    }: FunctionTask<'_>,
+    function_fetcher: Arc<
+        impl ?Sized
+        + super::function_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    profile_fetcher: Arc<
+        impl ?Sized
+        + super::profile_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ensemble_fetcher: Arc<
+        crate::ensemble::fetcher::CachingFetcher<
+            CTXEXT,
+            impl ?Sized
+            + crate::ensemble::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+) -> Result<Option<super::FlatTaskProfile>, super::executions::Error>
+where
+    CTXEXT: Send + Sync + 'static,
+{
+    // if skip, there is nothing to flatten
+    let task = match task {
+        Some(task) => task,
+        None => return Ok(None),
+    };
+
+    // switch by task type
+    let task_fut = match task {
+        objectiveai::functions::CompiledTask::One(
+            objectiveai::functions::Task::ScalarFunction(
+                objectiveai::functions::ScalarFunctionTask {
+                    remote,
+                    owner,
+                    repository,
+                    commit,
+                    input,
+                    output,
+                },
+            ),
+        )
+        | objectiveai::functions::CompiledTask::One(
+            objectiveai::functions::Task::VectorFunction(
+                objectiveai::functions::VectorFunctionTask {
+                    remote,
+                    owner,
+                    repository,
+                    commit,
+                    input,
+                    output,
+                },
+            ),
+        ) => {
+            let profile_param = if let Some(task_profile) = task_profile {
+                match task_profile {
+                    objectiveai::functions::TaskProfile::Remote {
+                        remote: tp_remote,
                         owner,
                         repository,
                         commit,
-                        input,
-                        output,
-                    },
-                ),
-            )
-            | objectiveai::functions::CompiledTask::One(
-                objectiveai::functions::Task::VectorFunction(
-                    objectiveai::functions::VectorFunctionTask {
-                        remote,
+                    } => ProfileParam::Remote {
+                        remote: tp_remote,
                         owner,
                         repository,
                         commit,
-                        input,
-                        output,
                     },
-                ),
-            ) => {
-                let effective_invert_output = profile_invert_flags[i];
-                let profile_param = if let Some(task_profile) = task_profile {
-                    match task_profile {
-                        objectiveai::functions::TaskProfile::Remote {
-                            remote: tp_remote,
-                            owner,
-                            repository,
-                            commit,
-                        } => ProfileParam::Remote {
-                            remote: tp_remote,
-                            owner,
-                            repository,
-                            commit,
-                        },
-                        objectiveai::functions::TaskProfile::Inline(
-                            profile,
-                        ) => ProfileParam::FetchedOrInline {
-                            full_id: None,
-                            profile: objectiveai::functions::Profile::Inline(
-                                profile,
-                            ),
-                        },
-                        _ => return Err(super::executions::Error::InvalidProfile(
-                            "expected function profile (Remote or Inline) for function task".to_string()
-                        )),
-                    }
-                } else {
-                    let auto = auto_config.as_ref().unwrap();
-                    ProfileParam::FetchedOrInline {
+                    objectiveai::functions::TaskProfile::Inline(
+                        profile,
+                    ) => ProfileParam::FetchedOrInline {
                         full_id: None,
                         profile: objectiveai::functions::Profile::Inline(
-                            objectiveai::functions::InlineProfile::Auto(
-                                objectiveai::functions::InlineAutoProfile {
-                                    ensemble: auto.ensemble.clone(),
-                                    profile: auto.vc_profile.clone(),
-                                },
-                            ),
+                            profile,
                         ),
-                    }
-                };
-                flat_tasks_or_futs.push(TaskFut::FunctionTaskFut(Box::pin(
-                    get_flat_task_profile(
-                        ctx.clone(),
-                        task_path,
-                        FunctionParam::Remote {
-                            remote,
-                            owner,
-                            repository,
-                            commit: Some(commit),
-                        },
-                        profile_param,
-                        input,
-                        Some(output),
-                        effective_invert_output,
-                        function_fetcher.clone(),
-                        profile_fetcher.clone(),
-                        ensemble_fetcher.clone(),
-                    )
-                )));
-            }
-            objectiveai::functions::CompiledTask::One(
-                objectiveai::functions::Task::VectorCompletion(task),
-            ) => {
-                let (ensemble, vc_profile) = if let Some(task_profile) = task_profile {
-                    match task_profile {
-                        objectiveai::functions::TaskProfile::Inline(
-                            objectiveai::functions::InlineProfile::Auto(auto),
-                        ) => (auto.ensemble, auto.profile),
-                        _ => return Err(super::executions::Error::InvalidProfile(
-                            "expected Inline(Auto) profile for vector completion task".to_string()
-                        )),
-                    }
-                } else {
-                    let auto = auto_config.as_ref().unwrap();
-                    (auto.ensemble.clone(), auto.vc_profile.clone())
-                };
-                let effective_invert_output = profile_invert_flags[i];
-                flat_tasks_or_futs.push(TaskFut::VectorTaskFut(Box::pin(
-                    get_vector_completion_flat_task_profile(
-                        ctx.clone(),
-                        task_path,
-                        task,
-                        ensemble,
-                        vc_profile,
-                        effective_invert_output,
-                        ensemble_fetcher.clone(),
-                    ),
-                )));
-            }
-            objectiveai::functions::CompiledTask::One(
-                objectiveai::functions::Task::PlaceholderScalarFunction(task),
-            ) => {
-                if let Some(task_profile) = task_profile {
-                    match task_profile {
-                        objectiveai::functions::TaskProfile::Placeholder {} => {}
-                        _ => return Err(super::executions::Error::InvalidProfile(
-                            "expected Placeholder profile for placeholder scalar function task".to_string()
-                        )),
-                    }
+                    },
+                    _ => return Err(super::executions::Error::InvalidProfile(
+                        "expected function profile (Remote or Inline) for function task".to_string()
+                    )),
                 }
-                let effective_invert_output = profile_invert_flags[i];
-                flat_tasks_or_futs.push(TaskFut::Task(Some(
-                    FlatTaskProfile::PlaceholderScalarFunction(
-                        PlaceholderScalarFunctionFlatTaskProfile {
-                            path: task_path,
-                            input: task.input,
-                            output: task.output,
-                            invert_output: effective_invert_output,
-                        },
+            } else {
+                let auto = auto_profile.unwrap();
+                ProfileParam::FetchedOrInline {
+                    full_id: None,
+                    profile: objectiveai::functions::Profile::Inline(
+                        objectiveai::functions::InlineProfile::Auto(
+                            auto.clone(),
+                        ),
                     ),
-                )));
-            }
-            objectiveai::functions::CompiledTask::One(
-                objectiveai::functions::Task::PlaceholderVectorFunction(task),
-            ) => {
-                if let Some(task_profile) = task_profile {
-                    match task_profile {
-                        objectiveai::functions::TaskProfile::Placeholder {} => {}
-                        _ => return Err(super::executions::Error::InvalidProfile(
-                            "expected Placeholder profile for placeholder vector function task".to_string()
-                        )),
-                    }
                 }
-                let effective_invert_output = profile_invert_flags[i];
-                // compile output_length using the task's input as params context
-                let params = objectiveai::functions::expression::Params::Ref(
-                    objectiveai::functions::expression::ParamsRef {
-                        input: &task.input,
-                        output: None,
-                        map: None,
+            };
+            TaskFut::FunctionTaskFut(Box::pin(
+                get_flat_task_profile(
+                    ctx.clone(),
+                    task_path,
+                    FunctionParam::Remote {
+                        remote,
+                        owner,
+                        repository,
+                        commit: Some(commit),
                     },
-                );
-                let output_length = task.output_length.clone().compile_one(&params)?;
-                flat_tasks_or_futs.push(TaskFut::Task(Some(
-                    FlatTaskProfile::PlaceholderVectorFunction(
-                        PlaceholderVectorFunctionFlatTaskProfile {
-                            path: task_path,
-                            input: task.input,
-                            output_length,
-                            input_split: task.input_split,
-                            input_merge: task.input_merge,
-                            output: task.output,
-                            invert_output: effective_invert_output,
-                        },
-                    ),
-                )));
+                    profile_param,
+                    input,
+                    Some(output),
+                    invert_output,
+                    function_fetcher.clone(),
+                    profile_fetcher.clone(),
+                    ensemble_fetcher.clone(),
+                )
+            ))
+        }
+        objectiveai::functions::CompiledTask::One(
+            objectiveai::functions::Task::VectorCompletion(task),
+        ) => {
+            let (ensemble, vc_profile) = if let Some(task_profile) = task_profile {
+                match task_profile {
+                    objectiveai::functions::TaskProfile::Inline(
+                        objectiveai::functions::InlineProfile::Auto(auto),
+                    ) => (auto.ensemble, auto.profile),
+                    _ => return Err(super::executions::Error::InvalidProfile(
+                        "expected Inline(Auto) profile for vector completion task".to_string()
+                    )),
+                }
+            } else {
+                let auto = auto_profile.unwrap();
+                (auto.ensemble.clone(), auto.profile.clone())
+            };
+            TaskFut::VectorTaskFut(Box::pin(
+                get_vector_completion_flat_task_profile(
+                    ctx.clone(),
+                    task_path,
+                    task,
+                    ensemble,
+                    vc_profile,
+                    invert_output,
+                    ensemble_fetcher.clone(),
+                ),
+            ))
+        }
+        objectiveai::functions::CompiledTask::One(
+            objectiveai::functions::Task::PlaceholderScalarFunction(task),
+        ) => {
+            if let Some(task_profile) = task_profile {
+                match task_profile {
+                    objectiveai::functions::TaskProfile::Placeholder {} => {}
+                    _ => return Err(super::executions::Error::InvalidProfile(
+                        "expected Placeholder profile for placeholder scalar function task".to_string()
+                    )),
+                }
             }
-            objectiveai::functions::CompiledTask::Many(tasks) => {
-                enum MapTaskType {
-                    VectorCompletion,
-                    Function,
-                    PlaceholderScalar,
-                    PlaceholderVector,
+            TaskFut::Task(Some(
+                FlatTaskProfile::PlaceholderScalarFunction(
+                    PlaceholderScalarFunctionFlatTaskProfile {
+                        path: task_path,
+                        input: task.input,
+                        output: task.output,
+                        invert_output,
+                    },
+                ),
+            ))
+        }
+        objectiveai::functions::CompiledTask::One(
+            objectiveai::functions::Task::PlaceholderVectorFunction(task),
+        ) => {
+            if let Some(task_profile) = task_profile {
+                match task_profile {
+                    objectiveai::functions::TaskProfile::Placeholder {} => {}
+                    _ => return Err(super::executions::Error::InvalidProfile(
+                        "expected Placeholder profile for placeholder vector function task".to_string()
+                    )),
                 }
+            }
+            // compile output_length using the task's input as params context
+            let params = objectiveai::functions::expression::Params::Ref(
+                objectiveai::functions::expression::ParamsRef {
+                    input: &task.input,
+                    output: None,
+                    map: None,
+                    definitions,
+                    tasks: None,
+                    weights: None,
+                },
+            );
+            let output_length = task.output_length.clone().compile_one(&params)?;
+            TaskFut::Task(Some(
+                FlatTaskProfile::PlaceholderVectorFunction(
+                    PlaceholderVectorFunctionFlatTaskProfile {
+                        path: task_path,
+                        input: task.input,
+                        output_length,
+                        input_split: task.input_split,
+                        input_merge: task.input_merge,
+                        output: task.output,
+                        invert_output,
+                    },
+                ),
+            ))
+        }
+        objectiveai::functions::CompiledTask::Many(tasks) => {
+            enum MapTaskType {
+                VectorCompletion,
+                Function,
+                PlaceholderScalar,
+                PlaceholderVector,
+            }
 
-                // Determine task type and extract shared output expression before consuming tasks
-                let (map_type, map_task_output) = match tasks.first() {
-                    Some(objectiveai::functions::Task::VectorCompletion(vc)) => {
-                        (MapTaskType::VectorCompletion, vc.output.clone())
-                    }
-                    Some(objectiveai::functions::Task::ScalarFunction(sf)) => {
-                        (MapTaskType::Function, sf.output.clone())
-                    }
-                    Some(objectiveai::functions::Task::VectorFunction(vf)) => {
-                        (MapTaskType::Function, vf.output.clone())
-                    }
-                    Some(objectiveai::functions::Task::PlaceholderScalarFunction(p)) => {
-                        (MapTaskType::PlaceholderScalar, p.output.clone())
-                    }
-                    Some(objectiveai::functions::Task::PlaceholderVectorFunction(p)) => {
-                        (MapTaskType::PlaceholderVector, p.output.clone())
-                    }
-                    None => {
-                        // Empty mapped task - need a placeholder expression
-                        // This case shouldn't normally happen, but handle gracefully
-                        (MapTaskType::VectorCompletion, objectiveai::functions::expression::Expression::JMESPath(
-                            "output".to_string()
-                        ))
-                    }
-                };
+            // Determine task type and extract shared output expression before consuming tasks
+            let (map_type, map_task_output) = match tasks.first() {
+                Some(objectiveai::functions::Task::VectorCompletion(vc)) => {
+                    (MapTaskType::VectorCompletion, vc.output.clone())
+                }
+                Some(objectiveai::functions::Task::ScalarFunction(sf)) => {
+                    (MapTaskType::Function, sf.output.clone())
+                }
+                Some(objectiveai::functions::Task::VectorFunction(vf)) => {
+                    (MapTaskType::Function, vf.output.clone())
+                }
+                Some(objectiveai::functions::Task::PlaceholderScalarFunction(p)) => {
+                    (MapTaskType::PlaceholderScalar, p.output.clone())
+                }
+                Some(objectiveai::functions::Task::PlaceholderVectorFunction(p)) => {
+                    (MapTaskType::PlaceholderVector, p.output.clone())
+                }
+                None => {
+                    // Empty mapped task - need a placeholder expression
+                    // This case shouldn't normally happen, but handle gracefully
+                    (MapTaskType::VectorCompletion, objectiveai::functions::expression::Expression::JMESPath(
+                        "output".to_string()
+                    ))
+                }
+            };
 
-                let map_invert_output = profile_invert_flags[i];
+            let map_invert_output = invert_output;
 
-                match map_type {
-                    MapTaskType::VectorCompletion => {
-                        let mut futs = Vec::with_capacity(tasks.len());
-                        for (j, task) in tasks.into_iter().enumerate() {
-                            let mut task_path = task_path.clone();
-                            task_path.push(j as u64);
-                            let (ensemble, vc_profile) = if let Some(ref task_profile) = task_profile {
-                                match task_profile {
-                                    objectiveai::functions::TaskProfile::Inline(
-                                        objectiveai::functions::InlineProfile::Auto(auto),
-                                    ) => (auto.ensemble.clone(), auto.profile.clone()),
-                                    _ => return Err(super::executions::Error::InvalidProfile(
-                                        "expected Inline(Auto) profile for mapped vector completion task".to_string()
-                                    )),
-                                }
-                            } else {
-                                let auto = auto_config.as_ref().unwrap();
-                                (auto.ensemble.clone(), auto.vc_profile.clone())
-                            };
-                            futs.push(get_vector_completion_flat_task_profile(
-                                ctx.clone(),
-                                task_path,
-                                match task {
-                                    objectiveai::functions::Task::VectorCompletion(
-                                        vc_task,
-                                    ) => vc_task,
-                                    _ => unreachable!(),
-                                },
-                                ensemble,
-                                vc_profile,
-                                map_invert_output,
-                                ensemble_fetcher.clone(),
-                            ));
-                        }
-                        flat_tasks_or_futs.push(TaskFut::MapVectorTaskFut((
+            match map_type {
+                MapTaskType::VectorCompletion => {
+                    let mut futs = Vec::with_capacity(tasks.len());
+                    for (j, task) in tasks.into_iter().enumerate() {
+                        let mut task_path = task_path.clone();
+                        task_path.push(j as u64);
+                        let (ensemble, vc_profile) = if let Some(ref task_profile) = task_profile {
+                            match task_profile {
+                                objectiveai::functions::TaskProfile::Inline(
+                                    objectiveai::functions::InlineProfile::Auto(auto),
+                                ) => (auto.ensemble.clone(), auto.profile.clone()),
+                                _ => return Err(super::executions::Error::InvalidProfile(
+                                    "expected Inline(Auto) profile for mapped vector completion task".to_string()
+                                )),
+                            }
+                        } else {
+                            let auto = auto_profile.unwrap();
+                            (auto.ensemble.clone(), auto.profile.clone())
+                        };
+                        futs.push(get_vector_completion_flat_task_profile(
+                            ctx.clone(),
                             task_path,
-                            map_task_output,
+                            match task {
+                                objectiveai::functions::Task::VectorCompletion(
+                                    vc_task,
+                                ) => vc_task,
+                                _ => unreachable!(),
+                            },
+                            ensemble,
+                            vc_profile,
                             map_invert_output,
-                            futures::future::try_join_all(futs),
-                        )));
+                            ensemble_fetcher.clone(),
+                        ));
                     }
-                    MapTaskType::Function => {
-                        let mut futs = Vec::with_capacity(tasks.len());
-                        for (j, task) in tasks.into_iter().enumerate() {
-                            let mut task_path = task_path.clone();
-                            task_path.push(j as u64);
-                            futs.push(get_flat_task_profile(
-                                ctx.clone(),
-                                task_path,
-                                FunctionParam::Remote {
-                                    remote: match &task {
-                                        objectiveai::functions::Task::ScalarFunction(
-                                            sf_task,
-                                        ) => sf_task.remote,
-                                        objectiveai::functions::Task::VectorFunction(
-                                            vf_task,
-                                        ) => vf_task.remote,
-                                        _ => unreachable!(),
-                                    },
-                                    owner: match &task {
-                                        objectiveai::functions::Task::ScalarFunction(
-                                            sf_task,
-                                        ) => sf_task.owner.clone(),
-                                        objectiveai::functions::Task::VectorFunction(
-                                            vf_task,
-                                        ) => vf_task.owner.clone(),
-                                        _ => unreachable!(),
-                                    },
-                                    repository: match &task {
-                                        objectiveai::functions::Task::ScalarFunction(
-                                            sf_task,
-                                        ) => sf_task.repository.clone(),
-                                        objectiveai::functions::Task::VectorFunction(
-                                            vf_task,
-                                        ) => vf_task.repository.clone(),
-                                        _ => unreachable!(),
-                                    },
-                                    commit: Some(match &task {
-                                        objectiveai::functions::Task::ScalarFunction(
-                                            sf_task,
-                                        ) => sf_task.commit.clone(),
-                                        objectiveai::functions::Task::VectorFunction(
-                                            vf_task,
-                                        ) => vf_task.commit.clone(),
-                                        _ => unreachable!(),
-                                    }),
+                    TaskFut::MapVectorTaskFut((
+                        task_path,
+                        map_task_output,
+                        map_invert_output,
+                        futures::future::try_join_all(futs),
+                    ))
+                }
+                MapTaskType::Function => {
+                    let mut futs = Vec::with_capacity(tasks.len());
+                    for (j, task) in tasks.into_iter().enumerate() {
+                        let mut task_path = task_path.clone();
+                        task_path.push(j as u64);
+                        futs.push(get_flat_task_profile(
+                            ctx.clone(),
+                            task_path,
+                            FunctionParam::Remote {
+                                remote: match &task {
+                                    objectiveai::functions::Task::ScalarFunction(
+                                        sf_task,
+                                    ) => sf_task.remote,
+                                    objectiveai::functions::Task::VectorFunction(
+                                        vf_task,
+                                    ) => vf_task.remote,
+                                    _ => unreachable!(),
                                 },
-                                if let Some(ref task_profile) = task_profile {
-                                    match task_profile {
-                                        objectiveai::functions::TaskProfile::Remote {
-                                            remote: tp_remote,
-                                            owner,
-                                            repository,
-                                            commit,
-                                        } => ProfileParam::Remote {
-                                            remote: *tp_remote,
-                                            owner: owner.clone(),
-                                            repository: repository.clone(),
-                                            commit: commit.clone(),
-                                        },
-                                        objectiveai::functions::TaskProfile::Inline(
-                                            profile,
-                                        ) => ProfileParam::FetchedOrInline {
-                                            full_id: None,
-                                            profile: objectiveai::functions::Profile::Inline(
-                                                profile.clone(),
-                                            ),
-                                        },
-                                        _ => return Err(super::executions::Error::InvalidProfile(
-                                            "expected function profile (Remote or Inline) for mapped function task".to_string()
-                                        )),
-                                    }
-                                } else {
-                                    let auto = auto_config.as_ref().unwrap();
-                                    ProfileParam::FetchedOrInline {
-                                        full_id: None,
-                                        profile: objectiveai::functions::Profile::Inline(
-                                            objectiveai::functions::InlineProfile::Auto(
-                                                objectiveai::functions::InlineAutoProfile {
-                                                    ensemble: auto.ensemble.clone(),
-                                                    profile: auto.vc_profile.clone(),
-                                                },
-                                            ),
-                                        ),
-                                    }
+                                owner: match &task {
+                                    objectiveai::functions::Task::ScalarFunction(
+                                        sf_task,
+                                    ) => sf_task.owner.clone(),
+                                    objectiveai::functions::Task::VectorFunction(
+                                        vf_task,
+                                    ) => vf_task.owner.clone(),
+                                    _ => unreachable!(),
                                 },
-                                match &task {
+                                repository: match &task {
                                     objectiveai::functions::Task::ScalarFunction(
                                         sf_task,
-                                    ) => sf_task.input.clone(),
+                                    ) => sf_task.repository.clone(),
                                     objectiveai::functions::Task::VectorFunction(
                                         vf_task,
-                                    ) => vf_task.input.clone(),
+                                    ) => vf_task.repository.clone(),
                                     _ => unreachable!(),
                                 },
-                                // Pass None for individual mapped functions - the task_output is stored on MapFunctionFlatTaskProfile
-                                None,
-                                false,
-                                function_fetcher.clone(),
-                                profile_fetcher.clone(),
-                                ensemble_fetcher.clone(),
-                            ));
-                        }
-                        flat_tasks_or_futs.push(TaskFut::MapFunctionTaskFut((
-                            task_path,
-                            map_task_output,
-                            map_invert_output,
-                            futures::future::try_join_all(futs),
-                        )));
-                    }
-                    MapTaskType::PlaceholderScalar => {
-                        if let Some(ref task_profile) = task_profile {
-                            match task_profile {
-                                objectiveai::functions::TaskProfile::Placeholder {} => {}
-                                _ => return Err(super::executions::Error::InvalidProfile(
-                                    "expected Placeholder profile for mapped placeholder scalar function task".to_string()
-                                )),
-                            }
-                        }
-                        let mut placeholders = Vec::with_capacity(tasks.len());
-                        for (j, task) in tasks.into_iter().enumerate() {
-                            let mut tp = task_path.clone();
-                            tp.push(j as u64);
-                            let task = match task {
-                                objectiveai::functions::Task::PlaceholderScalarFunction(t) => t,
+                                commit: Some(match &task {
+                                    objectiveai::functions::Task::ScalarFunction(
+                                        sf_task,
+                                    ) => sf_task.commit.clone(),
+                                    objectiveai::functions::Task::VectorFunction(
+                                        vf_task,
+                                    ) => vf_task.commit.clone(),
+                                    _ => unreachable!(),
+                                }),
+                            },
+                            if let Some(ref task_profile) = task_profile {
+                                match task_profile {
+                                    objectiveai::functions::TaskProfile::Remote {
+                                        remote: tp_remote,
+                                        owner,
+                                        repository,
+                                        commit,
+                                    } => ProfileParam::Remote {
+                                        remote: *tp_remote,
+                                        owner: owner.clone(),
+                                        repository: repository.clone(),
+                                        commit: commit.clone(),
+                                    },
+                                    objectiveai::functions::TaskProfile::Inline(
+                                        profile,
+                                    ) => ProfileParam::FetchedOrInline {
+                                        full_id: None,
+                                        profile: objectiveai::functions::Profile::Inline(
+                                            profile.clone(),
+                                        ),
+                                    },
+                                    _ => return Err(super::executions::Error::InvalidProfile(
+                                        "expected function profile (Remote or Inline) for mapped function task".to_string()
+                                    )),
+                                }
+                            } else {
+                                let auto = auto_profile.unwrap();
+                                ProfileParam::FetchedOrInline {
+                                    full_id: None,
+                                    profile: objectiveai::functions::Profile::Inline(
+                                        objectiveai::functions::InlineProfile::Auto(
+                                            auto.clone(),
+                                        ),
+                                    ),
+                                }
+                            },
+                            match &task {
+                                objectiveai::functions::Task::ScalarFunction(
+                                    sf_task,
+                                ) => sf_task.input.clone(),
+                                objectiveai::functions::Task::VectorFunction(
+                                    vf_task,
+                                ) => vf_task.input.clone(),
                                 _ => unreachable!(),
-                            };
-                            placeholders.push(PlaceholderScalarFunctionFlatTaskProfile {
-                                path: tp,
-                                input: task.input,
-                                output: task.output,
-                                invert_output: map_invert_output,
-                            });
-                        }
-                        flat_tasks_or_futs.push(TaskFut::Task(Some(
-                            FlatTaskProfile::MapPlaceholderScalarFunction(
-                                MapPlaceholderScalarFunctionFlatTaskProfile {
-                                    path: task_path,
-                                    placeholders,
-                                    task_output: map_task_output,
-                                    invert_output: map_invert_output,
-                                },
-                            ),
-                        )));
+                            },
+                            // Pass None for individual mapped functions - the task_output is stored on MapFunctionFlatTaskProfile
+                            None,
+                            false,
+                            function_fetcher.clone(),
+                            profile_fetcher.clone(),
+                            ensemble_fetcher.clone(),
+                        ));
                     }
-                    MapTaskType::PlaceholderVector => {
-                        if let Some(ref task_profile) = task_profile {
-                            match task_profile {
-                                objectiveai::functions::TaskProfile::Placeholder {} => {}
-                                _ => return Err(super::executions::Error::InvalidProfile(
-                                    "expected Placeholder profile for mapped placeholder vector function task".to_string()
-                                )),
-                            }
+                    TaskFut::MapFunctionTaskFut((
+                        task_path,
+                        map_task_output,
+                        map_invert_output,
+                        futures::future::try_join_all(futs),
+                    ))
+                }
+                MapTaskType::PlaceholderScalar => {
+                    if let Some(ref task_profile) = task_profile {
+                        match task_profile {
+                            objectiveai::functions::TaskProfile::Placeholder {} => {}
+                            _ => return Err(super::executions::Error::InvalidProfile(
+                                "expected Placeholder profile for mapped placeholder scalar function task".to_string()
+                            )),
                         }
-                        let mut placeholders = Vec::with_capacity(tasks.len());
-                        for (j, task) in tasks.into_iter().enumerate() {
-                            let mut tp = task_path.clone();
-                            tp.push(j as u64);
-                            let task = match task {
-                                objectiveai::functions::Task::PlaceholderVectorFunction(t) => t,
-                                _ => unreachable!(),
-                            };
-                            // compile output_length using the task's input
-                            let params = objectiveai::functions::expression::Params::Ref(
-                                objectiveai::functions::expression::ParamsRef {
-                                    input: &task.input,
-                                    output: None,
-                                    map: None,
-                                },
-                            );
-                            let output_length = task.output_length.clone().compile_one(&params)?;
-                            placeholders.push(PlaceholderVectorFunctionFlatTaskProfile {
-                                path: tp,
-                                input: task.input,
-                                output_length,
-                                input_split: task.input_split,
-                                input_merge: task.input_merge,
-                                output: task.output,
+                    }
+                    let mut placeholders = Vec::with_capacity(tasks.len());
+                    for (j, task) in tasks.into_iter().enumerate() {
+                        let mut tp = task_path.clone();
+                        tp.push(j as u64);
+                        let task = match task {
+                            objectiveai::functions::Task::PlaceholderScalarFunction(t) => t,
+                            _ => unreachable!(),
+                        };
+                        placeholders.push(PlaceholderScalarFunctionFlatTaskProfile {
+                            path: tp,
+                            input: task.input,
+                            output: task.output,
+                            invert_output: map_invert_output,
+                        });
+                    }
+                    TaskFut::Task(Some(
+                        FlatTaskProfile::MapPlaceholderScalarFunction(
+                            MapPlaceholderScalarFunctionFlatTaskProfile {
+                                path: task_path,
+                                placeholders,
+                                task_output: map_task_output,
                                 invert_output: map_invert_output,
-                            });
+                            },
+                        ),
+                    ))
+                }
+                MapTaskType::PlaceholderVector => {
+                    if let Some(ref task_profile) = task_profile {
+                        match task_profile {
+                            objectiveai::functions::TaskProfile::Placeholder {} => {}
+                            _ => return Err(super::executions::Error::InvalidProfile(
+                                "expected Placeholder profile for mapped placeholder vector function task".to_string()
+                            )),
                         }
-                        flat_tasks_or_futs.push(TaskFut::Task(Some(
-                            FlatTaskProfile::MapPlaceholderVectorFunction(
-                                MapPlaceholderVectorFunctionFlatTaskProfile {
-                                    path: task_path,
-                                    placeholders,
-                                    task_output: map_task_output,
-                                    invert_output: map_invert_output,
-                                },
-                            ),
-                        )));
                     }
+                    let mut placeholders = Vec::with_capacity(tasks.len());
+                    for (j, task) in tasks.into_iter().enumerate() {
+                        let mut tp = task_path.clone();
+                        tp.push(j as u64);
+                        let task = match task {
+                            objectiveai::functions::Task::PlaceholderVectorFunction(t) => t,
+                            _ => unreachable!(),
+                        };
+                        // compile output_length using the task's input
+                        let params = objectiveai::functions::expression::Params::Ref(
+                            objectiveai::functions::expression::ParamsRef {
+                                input: &task.input,
+                                output: None,
+                                map: None,
+                                definitions,
+                                tasks: None,
+                                weights: None,
+                            },
+                        );
+                        let output_length = task.output_length.clone().compile_one(&params)?;
+                        placeholders.push(PlaceholderVectorFunctionFlatTaskProfile {
+                            path: tp,
+                            input: task.input,
+                            output_length,
+                            input_split: task.input_split,
+                            input_merge: task.input_merge,
+                            output: task.output,
+                            invert_output: map_invert_output,
+                        });
+                    }
+                    TaskFut::Task(Some(
+                        FlatTaskProfile::MapPlaceholderVectorFunction(
+                            MapPlaceholderVectorFunctionFlatTaskProfile {
+                                path: task_path,
+                                placeholders,
+                                task_output: map_task_output,
+                                invert_output: map_invert_output,
+                            },
+                        ),
+                    ))
                 }
             }
         }
-    }
-
-    // await all futs
-    let tasks = futures::future::try_join_all(flat_tasks_or_futs).await?;
-
-    // return flat function task
-    Ok(super::FunctionFlatTaskProfile {
-        path,
-        description,
-        full_function_id: function_full_id,
-        full_profile_id: profile_full_id,
-        input,
-        tasks,
-        profile: profile_weights,
-        r#type,
-        task_output,
-        invert_output,
-    })
+    };
+    task_fut.await
 }
 
 async fn get_vector_completion_flat_task_profile<CTXEXT>(
@@ -1260,7 +1474,11 @@ async fn get_vector_completion_flat_task_profile<CTXEXT>(
     ensemble_fetcher: Arc<
         crate::ensemble::fetcher::CachingFetcher<
             CTXEXT,
-            impl crate::ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + crate::ensemble::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
         >,
     >,
 ) -> Result<super::VectorCompletionFlatTaskProfile, super::executions::Error>
@@ -1320,6 +1538,7 @@ where
         messages: task.messages,
         tools: task.tools,
         responses: task.responses,
+        rating: task.rating,
         output: task.output,
         invert_output,
     })
@@ -1339,7 +1558,6 @@ enum TaskFut<
         >,
     >,
 > {
-    SkipTask,
     Task(Option<super::FlatTaskProfile>),
     VectorTaskFut(Pin<Box<VFUT>>),
     MapVectorTaskFut(
@@ -1383,7 +1601,6 @@ where
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Self::Output> {
         match self.get_mut() {
-            TaskFut::SkipTask => Poll::Ready(Ok(None)),
             TaskFut::Task(task) => Poll::Ready(Ok(task.take())),
             TaskFut::VectorTaskFut(fut) => Pin::new(fut)
                 .poll(cx)
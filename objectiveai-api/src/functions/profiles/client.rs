@@ -7,7 +7,7 @@ use std::sync::Arc;
 ///
 /// Provides methods to list Profiles, retrieve Profile definitions,
 /// and get Profile usage statistics.
-pub struct Client<CTXEXT, PFN, RTRVL> {
+pub struct Client<CTXEXT, PFN: ?Sized, RTRVL: ?Sized> {
     /// Fetcher for Profile definitions.
     pub profile_fetcher: Arc<PFN>,
     /// Client for listing Profiles and getting usage statistics.
@@ -16,7 +16,7 @@ pub struct Client<CTXEXT, PFN, RTRVL> {
     pub _ctx_ext: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, PFN, RTRVL> Client<CTXEXT, PFN, RTRVL> {
+impl<CTXEXT, PFN: ?Sized, RTRVL: ?Sized> Client<CTXEXT, PFN, RTRVL> {
     /// Creates a new Profile client.
     pub fn new(
         profile_fetcher: Arc<PFN>,
@@ -30,7 +30,7 @@ impl<CTXEXT, PFN, RTRVL> Client<CTXEXT, PFN, RTRVL> {
     }
 }
 
-impl<CTXEXT, PFN, RTRVL> Client<CTXEXT, PFN, RTRVL>
+impl<CTXEXT, PFN: ?Sized, RTRVL: ?Sized> Client<CTXEXT, PFN, RTRVL>
 where
     CTXEXT: Send + Sync + 'static,
     PFN: crate::functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
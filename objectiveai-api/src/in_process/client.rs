@@ -0,0 +1,273 @@
+//! In-process implementation of [`objectiveai::Client`].
+
+use crate::{ctx, server};
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Runs chat completions, vector completions and Function executions in the
+/// current process, without going through an HTTP server.
+///
+/// Every request runs with a fresh [`ctx::Context`] built from `ext` and
+/// `cost_multiplier`, as if it were a separate HTTP request.
+pub struct InProcessClient<CTXEXT> {
+    /// Function executions client, which also holds the chat completions and
+    /// vector completions clients.
+    pub executions_client: Arc<server::ExecutionsClient<CTXEXT>>,
+    /// Context extension shared by every request.
+    pub ext: Arc<CTXEXT>,
+    /// Multiplier applied to the cost of every request.
+    pub cost_multiplier: rust_decimal::Decimal,
+}
+
+impl<CTXEXT> InProcessClient<CTXEXT>
+where
+    CTXEXT: Send + Sync + 'static,
+{
+    /// Builds the chat completions, vector completions and Function execution
+    /// pipelines the same way the server does.
+    pub fn new(config: server::Config, ext: CTXEXT) -> Self {
+        Self::from_client(
+            server::ServerBuilder::<CTXEXT>::new(config).executions_client(),
+            ext,
+        )
+    }
+
+    /// Creates an in-process client from an existing Function executions
+    /// client, e.g. one shared with a server in the same process.
+    pub fn from_client(
+        executions_client: Arc<server::ExecutionsClient<CTXEXT>>,
+        ext: CTXEXT,
+    ) -> Self {
+        Self {
+            executions_client,
+            ext: Arc::new(ext),
+            cost_multiplier: rust_decimal::Decimal::ONE,
+        }
+    }
+
+    /// Creates the context for a single request.
+    fn context(&self) -> ctx::Context<CTXEXT> {
+        ctx::Context::new(self.ext.clone(), self.cost_multiplier)
+    }
+}
+
+impl<CTXEXT> objectiveai::Client for InProcessClient<CTXEXT>
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    type Error = objectiveai::error::ResponseError;
+
+    async fn create_chat_completion_unary(
+        &self,
+        mut params: objectiveai::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        objectiveai::chat::completions::response::unary::ChatCompletion,
+        Self::Error,
+    > {
+        params.stream = None;
+        self.executions_client
+            .chat_client
+            .clone()
+            .create_unary_for_chat_handle_usage(
+                self.context(),
+                Arc::new(params),
+            )
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))
+    }
+
+    async fn create_chat_completion_streaming(
+        &self,
+        mut params: objectiveai::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        objectiveai::ClientStream<
+            objectiveai::chat::completions::response::streaming::ChatCompletionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    >{
+        params.stream = Some(true);
+        let stream = self
+            .executions_client
+            .chat_client
+            .clone()
+            .create_streaming_for_chat_handle_usage(
+                self.context(),
+                Arc::new(params),
+            )
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))?;
+        Ok(Box::pin(stream.map(|result| {
+            result.map_err(|e| objectiveai::error::ResponseError::from(&e))
+        })))
+    }
+
+    async fn create_vector_completion_unary(
+        &self,
+        mut params: objectiveai::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        objectiveai::vector::completions::response::unary::VectorCompletion,
+        Self::Error,
+    > {
+        params.stream = None;
+        self.executions_client
+            .vector_client
+            .clone()
+            .create_unary_handle_usage(self.context(), Arc::new(params))
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))
+    }
+
+    async fn create_vector_completion_streaming(
+        &self,
+        mut params: objectiveai::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        objectiveai::ClientStream<
+            objectiveai::vector::completions::response::streaming::VectorCompletionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    >{
+        params.stream = Some(true);
+        let stream = self
+            .executions_client
+            .vector_client
+            .clone()
+            .create_streaming_handle_usage(self.context(), Arc::new(params))
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))?;
+        Ok(Box::pin(stream.map(Ok)))
+    }
+
+    async fn create_function_execution_unary(
+        &self,
+        mut request: objectiveai::functions::executions::request::Request,
+    ) -> Result<
+        objectiveai::functions::executions::response::unary::FunctionExecution,
+        Self::Error,
+    > {
+        request.base_mut().stream = None;
+        self.executions_client
+            .clone()
+            .create_unary_handle_usage(self.context(), Arc::new(request))
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))
+    }
+
+    async fn create_function_execution_streaming(
+        &self,
+        mut request: objectiveai::functions::executions::request::Request,
+    ) -> Result<
+        objectiveai::ClientStream<
+            objectiveai::functions::executions::response::streaming::FunctionExecutionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    >{
+        request.base_mut().stream = Some(true);
+        let stream = self
+            .executions_client
+            .clone()
+            .create_streaming_handle_usage(self.context(), Arc::new(request))
+            .await
+            .map_err(|e| objectiveai::error::ResponseError::from(&e))?;
+        Ok(Box::pin(stream.map(Ok)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chat::completions::upstream::openrouter::mock_upstream::{
+        self, MockUpstream,
+    };
+    use futures::StreamExt;
+    use objectiveai::{Client, error::StatusError};
+
+    fn client() -> super::InProcessClient<crate::ctx::DefaultContextExt> {
+        client_with_config(crate::server::Config::default())
+    }
+
+    fn client_with_config(
+        config: crate::server::Config,
+    ) -> super::InProcessClient<crate::ctx::DefaultContextExt> {
+        super::InProcessClient::new(
+            config,
+            crate::ctx::DefaultContextExt {
+                openrouter_byok: None,
+                identity: None,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn streams_chat_completions_from_upstream() {
+        let client = client_with_config(crate::server::Config {
+            openrouter_api_base: MockUpstream::spawn().await.api_base,
+            ..Default::default()
+        });
+        let params = serde_json::from_value(serde_json::json!({
+            "messages": [{ "role": "user", "content": "Say hello" }],
+            "model": { "model": "openai/gpt-4o" },
+        }))
+        .unwrap();
+        let mut stream = client
+            .create_chat_completion_streaming(params)
+            .await
+            .unwrap();
+        let mut content = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = serde_json::to_value(chunk.unwrap()).unwrap();
+            if let Some(delta) = chunk
+                .pointer("/choices/0/delta/content")
+                .and_then(serde_json::Value::as_str)
+            {
+                content.push_str(delta);
+            }
+        }
+        assert_eq!(content, mock_upstream::MESSAGE);
+    }
+
+    #[tokio::test]
+    async fn runs_vector_completions_against_upstream() {
+        let client = client_with_config(crate::server::Config {
+            openrouter_api_base: MockUpstream::spawn().await.api_base,
+            ..Default::default()
+        });
+        let params = serde_json::from_value(serde_json::json!({
+            "messages": [{ "role": "user", "content": "Pick one" }],
+            "ensemble": {
+                "llms": [
+                    { "count": 1, "model": "openai/gpt-4o", "output_mode": "json_schema" },
+                    { "count": 1, "model": "openai/gpt-4o-mini", "output_mode": "json_schema" },
+                ],
+            },
+            "profile": [1, 1],
+            "responses": ["first", "second"],
+        }))
+        .unwrap();
+        let completion =
+            client.create_vector_completion_unary(params).await.unwrap();
+        assert_eq!(completion.votes.len(), 2);
+        assert_eq!(completion.scores.len(), 2);
+        assert_eq!(
+            completion.scores.iter().sum::<rust_decimal::Decimal>(),
+            rust_decimal::Decimal::ONE,
+        );
+    }
+
+    #[tokio::test]
+    async fn vector_completion_errors_are_response_errors() {
+        let params = serde_json::from_value(serde_json::json!({
+            "messages": [{ "role": "user", "content": "Pick one" }],
+            "ensemble": "ensemble",
+            "profile": [1],
+            "responses": ["only one"],
+        }))
+        .unwrap();
+        let error = client()
+            .create_vector_completion_unary(params)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), 400);
+    }
+}
@@ -0,0 +1,11 @@
+//! In-process client which runs the API's pipelines without HTTP.
+//!
+//! [`InProcessClient`] implements [`objectiveai::Client`], so code written
+//! against that trait works the same whether it calls a remote server through
+//! [`objectiveai::HttpClient`] or links this crate and runs the chat
+//! completions, vector completions and Function execution pipelines
+//! directly.
+
+mod client;
+
+pub use client::*;
@@ -12,6 +12,8 @@
 //! - [`ensemble_llm`] - Ensemble LLM management and retrieval
 //! - [`error`] - Error response handling
 //! - [`functions`] - Function execution and profile management
+//! - [`in_process`] - In-process client which runs pipelines without HTTP
+//! - [`server`] - Builder which assembles the server's routes
 //! - [`util`] - Utility types for streaming and indexing
 //! - [`vector`] - Vector completions for scoring and ranking
 
@@ -29,6 +31,10 @@ pub mod ensemble_llm;
 pub mod error;
 /// Function execution, profile management, and computations.
 pub mod functions;
+/// In-process client implementing the SDK's client trait.
+pub mod in_process;
+/// Server assembly from customizable components.
+pub mod server;
 /// Utility types for streaming and choice indexing.
 pub mod util;
 /// Vector completions for scoring and ranking responses.
@@ -0,0 +1,1540 @@
+//! Builder which assembles the server's components into an
+//! [`axum::Router`].
+
+use super::handlers;
+use crate::{auth, chat, ctx, ensemble, ensemble_llm, functions, vector};
+use axum::{
+    Json,
+    extract::{Path, ws::WebSocketUpgrade},
+    http::{Method, request::Parts},
+    routing::MethodFilter,
+};
+use std::{sync::Arc, time::Duration};
+
+/// A type-erased Ensemble LLM fetcher.
+pub type DynEnsembleLlmFetcher<CTXEXT> =
+    dyn ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync;
+
+/// A type-erased chat completion usage handler.
+pub type DynChatUsageHandler<CTXEXT> =
+    dyn chat::completions::usage_handler::UsageHandler<CTXEXT> + Send + Sync;
+
+/// A type-erased Ensemble fetcher.
+pub type DynEnsembleFetcher<CTXEXT> =
+    dyn ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync;
+
+/// A type-erased fetcher for votes from historical vector completions.
+pub type DynCompletionVotesFetcher<CTXEXT> =
+    dyn vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync;
+
+/// A type-erased fetcher for votes from the global vote cache.
+pub type DynCacheVoteFetcher<CTXEXT> =
+    dyn vector::completions::cache_vote_fetcher::Fetcher<CTXEXT> + Send + Sync;
+
+/// A type-erased vector completion usage handler.
+pub type DynVectorUsageHandler<CTXEXT> =
+    dyn vector::completions::usage_handler::UsageHandler<CTXEXT> + Send + Sync;
+
+/// A type-erased Function fetcher.
+pub type DynFunctionFetcher<CTXEXT> =
+    dyn functions::function_fetcher::Fetcher<CTXEXT> + Send + Sync;
+
+/// A type-erased Profile fetcher.
+pub type DynProfileFetcher<CTXEXT> =
+    dyn functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync;
+
+/// A type-erased Function execution usage handler.
+pub type DynFunctionExecutionUsageHandler<CTXEXT> =
+    dyn functions::executions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync;
+
+/// A type-erased client for listing Functions and their usage.
+pub type DynFunctionRetrievalClient<CTXEXT> =
+    dyn functions::retrieval_client::Client<CTXEXT> + Send + Sync;
+
+/// A type-erased client for listing Profiles and their usage.
+pub type DynProfileRetrievalClient<CTXEXT> =
+    dyn functions::profiles::retrieval_client::Client<CTXEXT> + Send + Sync;
+
+/// A type-erased client for listing Function-Profile pairs and their usage.
+pub type DynPairRetrievalClient<CTXEXT> =
+    dyn functions::pair_retrieval_client::Client<CTXEXT> + Send + Sync;
+
+/// A type-erased client for API keys and credits.
+pub type DynAuthClient<CTXEXT> = dyn auth::Client<CTXEXT> + Send + Sync;
+
+/// A type-erased client for listing Ensembles and their usage.
+pub type DynEnsembleRetrievalClient<CTXEXT> =
+    dyn ensemble::retrieval_client::Client<CTXEXT> + Send + Sync;
+
+/// A type-erased client for listing Ensemble LLMs and their usage.
+pub type DynEnsembleLlmRetrievalClient<CTXEXT> =
+    dyn ensemble_llm::retrieval_client::Client<CTXEXT> + Send + Sync;
+
+/// The Function executions client built by a [`ServerBuilder`], which also
+/// holds the chat completions and vector completions clients.
+pub type ExecutionsClient<CTXEXT> = functions::executions::Client<
+    CTXEXT,
+    DynEnsembleLlmFetcher<CTXEXT>,
+    DynChatUsageHandler<CTXEXT>,
+    DynEnsembleFetcher<CTXEXT>,
+    DynCompletionVotesFetcher<CTXEXT>,
+    DynCacheVoteFetcher<CTXEXT>,
+    DynVectorUsageHandler<CTXEXT>,
+    DynFunctionFetcher<CTXEXT>,
+    DynProfileFetcher<CTXEXT>,
+    DynFunctionExecutionUsageHandler<CTXEXT>,
+>;
+
+/// Assembles the server's components into an [`axum::Router`].
+///
+/// Start from [`ServerBuilder::new`], which uses the default components,
+/// replace any component with its setter, and call [`build`](Self::build). Each request's context extension
+/// is built with [`ctx::FromParts`]; middleware such as
+/// [`auth::authenticate`] and [`ctx::resolve_tenant`] can be layered onto the
+/// returned router.
+///
+/// ```ignore
+/// let app = ServerBuilder::<ctx::DefaultContextExt>::new(config)
+///     .profile_fetcher(Arc::new(MyProfileFetcher::new()))
+///     .build();
+/// ```
+pub struct ServerBuilder<CTXEXT> {
+    /// Client for upstream providers.
+    upstream_client: chat::completions::upstream::Client,
+    /// Current backoff interval for chat completion retries.
+    chat_completions_backoff_current_interval: Duration,
+    /// Initial backoff interval for chat completion retries.
+    chat_completions_backoff_initial_interval: Duration,
+    /// Randomization factor for chat completion backoff jitter.
+    chat_completions_backoff_randomization_factor: f64,
+    /// Multiplier for chat completion backoff growth.
+    chat_completions_backoff_multiplier: f64,
+    /// Maximum backoff interval for chat completion retries.
+    chat_completions_backoff_max_interval: Duration,
+    /// Maximum total time to spend on chat completion retries.
+    chat_completions_backoff_max_elapsed_time: Duration,
+    /// Chunk timeouts for upstream chat completion streams.
+    chat_completions_timeouts: chat::completions::ChunkTimeouts,
+    /// Client for Profile computations.
+    profile_computations_client:
+        Arc<functions::profiles::computations::ObjectiveAiClient>,
+    /// Fetcher for Ensemble LLM definitions, wrapped in a
+    /// [`CachingFetcher`](ensemble_llm::fetcher::CachingFetcher).
+    ensemble_llm_fetcher: Arc<DynEnsembleLlmFetcher<CTXEXT>>,
+    /// Handler for chat completion usage.
+    chat_usage_handler: Arc<DynChatUsageHandler<CTXEXT>>,
+    /// Fetcher for Ensemble definitions, wrapped in a
+    /// [`CachingFetcher`](ensemble::fetcher::CachingFetcher).
+    ensemble_fetcher: Arc<DynEnsembleFetcher<CTXEXT>>,
+    /// Fetcher for votes from historical vector completions.
+    completion_votes_fetcher: Arc<DynCompletionVotesFetcher<CTXEXT>>,
+    /// Fetcher for votes from the global vote cache.
+    cache_vote_fetcher: Arc<DynCacheVoteFetcher<CTXEXT>>,
+    /// Handler for vector completion usage.
+    vector_usage_handler: Arc<DynVectorUsageHandler<CTXEXT>>,
+    /// Fetcher for Function definitions.
+    function_fetcher: Arc<DynFunctionFetcher<CTXEXT>>,
+    /// Fetcher for Profile definitions.
+    profile_fetcher: Arc<DynProfileFetcher<CTXEXT>>,
+    /// Handler for Function execution usage.
+    function_execution_usage_handler:
+        Arc<DynFunctionExecutionUsageHandler<CTXEXT>>,
+    /// Client for listing Functions and their usage.
+    function_retrieval_client: Arc<DynFunctionRetrievalClient<CTXEXT>>,
+    /// Client for listing Profiles and their usage.
+    profile_retrieval_client: Arc<DynProfileRetrievalClient<CTXEXT>>,
+    /// Client for listing Function-Profile pairs and their usage.
+    pair_retrieval_client: Arc<DynPairRetrievalClient<CTXEXT>>,
+    /// Client for API keys and credits.
+    auth_client: Arc<DynAuthClient<CTXEXT>>,
+    /// Client for listing Ensembles and their usage.
+    ensemble_retrieval_client: Arc<DynEnsembleRetrievalClient<CTXEXT>>,
+    /// Client for listing Ensemble LLMs and their usage.
+    ensemble_llm_retrieval_client: Arc<DynEnsembleLlmRetrievalClient<CTXEXT>>,
+}
+
+impl<CTXEXT> ServerBuilder<CTXEXT>
+where
+    CTXEXT: Send + Sync + 'static,
+{
+    /// Creates a builder with the default components, configured by
+    /// `config`.
+    pub fn new(config: super::Config) -> Self {
+        let super::Config {
+            objectiveai_api_base,
+            objectiveai_api_key,
+            openrouter_api_base,
+            openrouter_api_key,
+            user_agent,
+            http_referer,
+            x_title,
+            chat_completions_backoff_current_interval,
+            chat_completions_backoff_initial_interval,
+            chat_completions_backoff_randomization_factor,
+            chat_completions_backoff_multiplier,
+            chat_completions_backoff_max_interval,
+            chat_completions_backoff_max_elapsed_time,
+            chat_completions_timeouts,
+            functions_dir,
+            profiles_dir,
+        } = config;
+
+        let http_client = reqwest::Client::new();
+        let objectiveai_http_client = Arc::new(objectiveai::HttpClient::new(
+            http_client.clone(),
+            Some(objectiveai_api_base),
+            objectiveai_api_key,
+            user_agent.clone(),
+            x_title.clone(),
+            http_referer.clone(),
+        ));
+
+        Self {
+            upstream_client: chat::completions::upstream::Client::new(
+                chat::completions::upstream::openrouter::Client::new(
+                    http_client,
+                    openrouter_api_base,
+                    openrouter_api_key,
+                    user_agent,
+                    x_title,
+                    http_referer,
+                ),
+            ),
+            chat_completions_backoff_current_interval,
+            chat_completions_backoff_initial_interval,
+            chat_completions_backoff_randomization_factor,
+            chat_completions_backoff_multiplier,
+            chat_completions_backoff_max_interval,
+            chat_completions_backoff_max_elapsed_time,
+            chat_completions_timeouts,
+            profile_computations_client: Arc::new(
+                functions::profiles::computations::ObjectiveAiClient::new(
+                    objectiveai_http_client.clone(),
+                ),
+            ),
+            ensemble_llm_fetcher: Arc::new(ensemble_llm::fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            chat_usage_handler: Arc::new(chat::completions::usage_handler::LogUsageHandler),
+            ensemble_fetcher: Arc::new(ensemble::fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            completion_votes_fetcher: Arc::new(vector::completions::completion_votes_fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            cache_vote_fetcher: Arc::new(vector::completions::cache_vote_fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            vector_usage_handler: Arc::new(vector::completions::usage_handler::LogUsageHandler),
+            function_fetcher: Arc::new(functions::function_fetcher::FetcherRouter::new(
+                Arc::new(
+                    functions::function_fetcher::github::ObjectiveAiFetcher::new(
+                        objectiveai_http_client.clone(),
+                    ),
+                ),
+                Arc::new(
+                    functions::function_fetcher::filesystem::FilesystemFetcher::new(
+                        functions_dir,
+                    ),
+                ),
+            )),
+            profile_fetcher: Arc::new(functions::profile_fetcher::FetcherRouter::new(
+                Arc::new(
+                    functions::profile_fetcher::github::ObjectiveAiFetcher::new(
+                        objectiveai_http_client.clone(),
+                    ),
+                ),
+                Arc::new(
+                    functions::profile_fetcher::filesystem::FilesystemFetcher::new(
+                        profiles_dir,
+                    ),
+                ),
+            )),
+            function_execution_usage_handler: Arc::new(functions::executions::usage_handler::LogUsageHandler),
+            function_retrieval_client: Arc::new(functions::retrieval_client::ObjectiveAiClient::new(
+                objectiveai_http_client.clone(),
+            )),
+            profile_retrieval_client: Arc::new(functions::profiles::retrieval_client::ObjectiveAiClient::new(
+                objectiveai_http_client.clone(),
+            )),
+            pair_retrieval_client: Arc::new(functions::pair_retrieval_client::ObjectiveAiClient::new(
+                objectiveai_http_client.clone(),
+            )),
+            auth_client: Arc::new(auth::ObjectiveAiClient::new(objectiveai_http_client.clone())),
+            ensemble_retrieval_client: Arc::new(ensemble::retrieval_client::ObjectiveAiClient::new(
+                objectiveai_http_client.clone(),
+            )),
+            ensemble_llm_retrieval_client: Arc::new(ensemble_llm::retrieval_client::ObjectiveAiClient::new(
+                objectiveai_http_client.clone(),
+            )),
+        }
+    }
+}
+
+impl<CTXEXT> ServerBuilder<CTXEXT> {
+    /// Replaces the client for upstream providers.
+    pub fn upstream_client(
+        mut self,
+        upstream_client: chat::completions::upstream::Client,
+    ) -> Self {
+        self.upstream_client = upstream_client;
+        self
+    }
+
+    /// Replaces the client for Profile computations.
+    pub fn profile_computations_client(
+        mut self,
+        profile_computations_client: Arc<
+            functions::profiles::computations::ObjectiveAiClient,
+        >,
+    ) -> Self {
+        self.profile_computations_client = profile_computations_client;
+        self
+    }
+
+    /// Replaces the fetcher for Ensemble LLM definitions, wrapped in a
+    /// [`CachingFetcher`](ensemble_llm::fetcher::CachingFetcher).
+    pub fn ensemble_llm_fetcher(
+        mut self,
+        ensemble_llm_fetcher: Arc<DynEnsembleLlmFetcher<CTXEXT>>,
+    ) -> Self {
+        self.ensemble_llm_fetcher = ensemble_llm_fetcher;
+        self
+    }
+
+    /// Replaces the handler for chat completion usage.
+    pub fn chat_usage_handler(
+        mut self,
+        chat_usage_handler: Arc<DynChatUsageHandler<CTXEXT>>,
+    ) -> Self {
+        self.chat_usage_handler = chat_usage_handler;
+        self
+    }
+
+    /// Replaces the fetcher for Ensemble definitions, wrapped in a
+    /// [`CachingFetcher`](ensemble::fetcher::CachingFetcher).
+    pub fn ensemble_fetcher(
+        mut self,
+        ensemble_fetcher: Arc<DynEnsembleFetcher<CTXEXT>>,
+    ) -> Self {
+        self.ensemble_fetcher = ensemble_fetcher;
+        self
+    }
+
+    /// Replaces the fetcher for votes from historical vector completions.
+    pub fn completion_votes_fetcher(
+        mut self,
+        completion_votes_fetcher: Arc<DynCompletionVotesFetcher<CTXEXT>>,
+    ) -> Self {
+        self.completion_votes_fetcher = completion_votes_fetcher;
+        self
+    }
+
+    /// Replaces the fetcher for votes from the global vote cache.
+    pub fn cache_vote_fetcher(
+        mut self,
+        cache_vote_fetcher: Arc<DynCacheVoteFetcher<CTXEXT>>,
+    ) -> Self {
+        self.cache_vote_fetcher = cache_vote_fetcher;
+        self
+    }
+
+    /// Replaces the handler for vector completion usage.
+    pub fn vector_usage_handler(
+        mut self,
+        vector_usage_handler: Arc<DynVectorUsageHandler<CTXEXT>>,
+    ) -> Self {
+        self.vector_usage_handler = vector_usage_handler;
+        self
+    }
+
+    /// Replaces the fetcher for Function definitions.
+    pub fn function_fetcher(
+        mut self,
+        function_fetcher: Arc<DynFunctionFetcher<CTXEXT>>,
+    ) -> Self {
+        self.function_fetcher = function_fetcher;
+        self
+    }
+
+    /// Replaces the fetcher for Profile definitions.
+    pub fn profile_fetcher(
+        mut self,
+        profile_fetcher: Arc<DynProfileFetcher<CTXEXT>>,
+    ) -> Self {
+        self.profile_fetcher = profile_fetcher;
+        self
+    }
+
+    /// Replaces the handler for Function execution usage.
+    pub fn function_execution_usage_handler(
+        mut self,
+        function_execution_usage_handler: Arc<
+            DynFunctionExecutionUsageHandler<CTXEXT>,
+        >,
+    ) -> Self {
+        self.function_execution_usage_handler =
+            function_execution_usage_handler;
+        self
+    }
+
+    /// Replaces the client for listing Functions and their usage.
+    pub fn function_retrieval_client(
+        mut self,
+        function_retrieval_client: Arc<DynFunctionRetrievalClient<CTXEXT>>,
+    ) -> Self {
+        self.function_retrieval_client = function_retrieval_client;
+        self
+    }
+
+    /// Replaces the client for listing Profiles and their usage.
+    pub fn profile_retrieval_client(
+        mut self,
+        profile_retrieval_client: Arc<DynProfileRetrievalClient<CTXEXT>>,
+    ) -> Self {
+        self.profile_retrieval_client = profile_retrieval_client;
+        self
+    }
+
+    /// Replaces the client for listing Function-Profile pairs and their usage.
+    pub fn pair_retrieval_client(
+        mut self,
+        pair_retrieval_client: Arc<DynPairRetrievalClient<CTXEXT>>,
+    ) -> Self {
+        self.pair_retrieval_client = pair_retrieval_client;
+        self
+    }
+
+    /// Replaces the client for API keys and credits.
+    pub fn auth_client(
+        mut self,
+        auth_client: Arc<DynAuthClient<CTXEXT>>,
+    ) -> Self {
+        self.auth_client = auth_client;
+        self
+    }
+
+    /// Replaces the client for listing Ensembles and their usage.
+    pub fn ensemble_retrieval_client(
+        mut self,
+        ensemble_retrieval_client: Arc<DynEnsembleRetrievalClient<CTXEXT>>,
+    ) -> Self {
+        self.ensemble_retrieval_client = ensemble_retrieval_client;
+        self
+    }
+
+    /// Replaces the client for listing Ensemble LLMs and their usage.
+    pub fn ensemble_llm_retrieval_client(
+        mut self,
+        ensemble_llm_retrieval_client: Arc<
+            DynEnsembleLlmRetrievalClient<CTXEXT>,
+        >,
+    ) -> Self {
+        self.ensemble_llm_retrieval_client = ensemble_llm_retrieval_client;
+        self
+    }
+
+    /// Builds the Function executions client, which also holds the chat
+    /// completions and vector completions clients.
+    pub fn executions_client(&self) -> Arc<ExecutionsClient<CTXEXT>> {
+        let chat_client = Arc::new(chat::completions::Client::new(
+            Arc::new(ensemble_llm::fetcher::CachingFetcher::new(
+                self.ensemble_llm_fetcher.clone(),
+            )),
+            self.chat_usage_handler.clone(),
+            self.upstream_client.clone(),
+            self.chat_completions_backoff_current_interval,
+            self.chat_completions_backoff_initial_interval,
+            self.chat_completions_backoff_randomization_factor,
+            self.chat_completions_backoff_multiplier,
+            self.chat_completions_backoff_max_interval,
+            self.chat_completions_backoff_max_elapsed_time,
+            self.chat_completions_timeouts.clone(),
+        ));
+        let ensemble_fetcher =
+            Arc::new(ensemble::fetcher::CachingFetcher::new(
+                self.ensemble_fetcher.clone(),
+            ));
+        let vector_client = Arc::new(vector::completions::Client::new(
+            chat_client.clone(),
+            ensemble_fetcher.clone(),
+            self.completion_votes_fetcher.clone(),
+            self.cache_vote_fetcher.clone(),
+            self.vector_usage_handler.clone(),
+        ));
+        Arc::new(functions::executions::Client::new(
+            chat_client,
+            ensemble_fetcher,
+            vector_client,
+            self.function_fetcher.clone(),
+            self.profile_fetcher.clone(),
+            self.function_execution_usage_handler.clone(),
+        ))
+    }
+}
+
+impl<CTXEXT> ServerBuilder<CTXEXT>
+where
+    CTXEXT: ctx::ContextExt + ctx::FromParts + Send + Sync + 'static,
+{
+    /// Builds the server's clients and routes.
+    pub fn build(self) -> axum::Router {
+        self.routes().router
+    }
+
+    /// Builds the server's clients and registers a route for each endpoint.
+    fn routes(self) -> Routes {
+        let function_executions_client = self.executions_client();
+        let chat_completions_client =
+            function_executions_client.chat_client.clone();
+        let vector_completions_client =
+            function_executions_client.vector_client.clone();
+        let vector_completions_cache_client =
+            Arc::new(vector::completions::cache::Client::new(
+                self.completion_votes_fetcher.clone(),
+                self.cache_vote_fetcher.clone(),
+            ));
+        let functions_client = Arc::new(functions::Client::new(
+            self.function_fetcher.clone(),
+            self.function_retrieval_client.clone(),
+        ));
+        let profiles_client = Arc::new(functions::profiles::Client::new(
+            self.profile_fetcher.clone(),
+            self.profile_retrieval_client.clone(),
+        ));
+        let pairs_client = self.pair_retrieval_client.clone();
+        let profile_computations_client =
+            self.profile_computations_client.clone();
+        let auth_client = self.auth_client.clone();
+        let ensemble_client = Arc::new(ensemble::Client::new(
+            function_executions_client.ensemble_fetcher.clone(),
+            self.ensemble_retrieval_client.clone(),
+        ));
+        let ensemble_llm_client = Arc::new(ensemble_llm::Client::new(
+            chat_completions_client.ensemble_llm_fetcher.clone(),
+            self.ensemble_llm_retrieval_client.clone(),
+        ));
+
+        Routes::default()
+            // Chat Completions - create
+            .route(
+                Method::POST,
+                &["/chat/completions"],
+                move |parts: Parts, Json(body): Json<
+                    objectiveai::chat::completions::request::ChatCompletionCreateParams,
+                >| {
+                    handlers::create_chat_completion(
+                        chat_completions_client,
+                        ctx::Context::from_parts(&parts),
+                        body,
+                    )
+                },
+            )
+            // Vector Completions - create
+            .route(
+                Method::POST,
+                &["/vector/completions"],
+                move |parts: Parts, Json(body): Json<
+                    objectiveai::vector::completions::request::VectorCompletionCreateParams,
+                >| {
+                    handlers::create_vector_completion(
+                        vector_completions_client,
+                        ctx::Context::from_parts(&parts),
+                        body,
+                    )
+                },
+            )
+            // Vector Completions - get completion votes
+            .route(
+                Method::POST,
+                &["/vector/completions/{id}"],
+                {
+                    let client = vector_completions_cache_client.clone();
+                    move |parts: Parts, Path(id): Path<String>| {
+                        handlers::get_vector_completion_votes(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            id,
+                        )
+                    }
+                },
+            )
+            // Vector Completions - get cache vote
+            .route(
+                Method::POST,
+                &["/vector/completions/cache"],
+                move |parts: Parts, Json(body): Json<
+                    objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
+                >| {
+                    handlers::get_vector_cache_vote(
+                        vector_completions_cache_client,
+                        ctx::Context::from_parts(&parts),
+                        body,
+                    )
+                },
+            )
+            // Functions - list
+            .route(
+                Method::GET,
+                &["/functions"],
+                {
+                    let client = functions_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_functions(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Function Executions - create
+            // inline function
+            // inline profile
+            .route(
+                Method::POST,
+                &["/functions"],
+                {
+                    let client = function_executions_client.clone();
+                    move |parts: Parts, Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionInlineProfileInlineRequestBody,
+                    >| {
+                        handlers::execute_function(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Function Executions - create over WebSocket
+            .route(
+                Method::GET,
+                &["/functions/executions/ws"],
+                {
+                    let client = function_executions_client.clone();
+                    move |parts: Parts, upgrade: WebSocketUpgrade| {
+                        handlers::execute_function_websocket(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            upgrade,
+                        )
+                    }
+                },
+            )
+            // Function Profiles - list
+            .route(
+                Method::GET,
+                &["/functions/profiles"],
+                {
+                    let client = profiles_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_profiles(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Function-Profile Pairs - list
+            .route(
+                Method::GET,
+                &["/functions/profiles/pairs"],
+                {
+                    let client = pairs_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_function_profile_pairs(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Function Profile Computations - create
+            // inline function
+            .route(
+                Method::POST,
+                &["/functions/profiles/compute"],
+                {
+                    let client = profile_computations_client.clone();
+                    move |parts: Parts, Json(body): Json<
+                        objectiveai::functions::profiles::computations::request::FunctionInlineRequestBody,
+                    >| {
+                        handlers::create_profile_computation(
+                            client,
+                            ctx::Context::<CTXEXT>::from_parts(&parts),
+                            objectiveai::functions::profiles::computations::request::Request::FunctionInline {
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Routes with an optional commit are registered once per
+            // variant, with the commit path parameters deserialized as
+            // `Option`s.
+            // Functions - get
+            .route(
+                Method::GET,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}",
+                ],
+                {
+                    let client = functions_client.clone();
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
+                    >| {
+                        handlers::get_function(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path.fremote,
+                            path.fowner,
+                            path.frepository,
+                            path.fcommit,
+                        )
+                    }
+                },
+            )
+            // Function Executions - create
+            // remote function
+            // inline profile
+            .route(
+                Method::POST,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}",
+                ],
+                {
+                    let client = function_executions_client.clone();
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
+                    >, Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestBody,
+                    >| {
+                        handlers::execute_function(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileInline {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Functions - get usage
+            .route(
+                Method::GET,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}/usage",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/usage",
+                ],
+                {
+                    let client = functions_client;
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
+                    >| {
+                        handlers::get_function_usage(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path.fremote,
+                            path.fowner,
+                            path.frepository,
+                            path.fcommit,
+                        )
+                    }
+                },
+            )
+            // Function Profiles - get
+            .route(
+                Method::GET,
+                &[
+                    "/functions/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                ],
+                {
+                    let client = profiles_client.clone();
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
+                    >| {
+                        handlers::get_profile(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path.premote,
+                            path.powner,
+                            path.prepository,
+                            path.pcommit,
+                        )
+                    }
+                },
+            )
+            // Function Executions - create
+            // inline function
+            // remote profile
+            .route(
+                Method::POST,
+                &[
+                    "/functions/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                ],
+                {
+                    let client = function_executions_client.clone();
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
+                    >, Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestBody,
+                    >| {
+                        handlers::execute_function(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            objectiveai::functions::executions::request::Request::FunctionInlineProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Function Profiles - get usage
+            .route(
+                Method::GET,
+                &[
+                    "/functions/profiles/{premote}/{powner}/{prepository}/usage",
+                    "/functions/profiles/{premote}/{powner}/{prepository}/{pcommit}/usage",
+                ],
+                {
+                    let client = profiles_client;
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
+                    >| {
+                        handlers::get_profile_usage(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path.premote,
+                            path.powner,
+                            path.prepository,
+                            path.pcommit,
+                        )
+                    }
+                },
+            )
+            // Function-Profile Pairs - get
+            .route(
+                Method::GET,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                ],
+                {
+                    let client = pairs_client.clone();
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >| {
+                        handlers::get_function_profile_pair(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path,
+                        )
+                    }
+                },
+            )
+            // Function Executions - create
+            // remote function
+            // remote profile
+            .route(
+                Method::POST,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+                ],
+                {
+                    let client = function_executions_client;
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >, Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
+                    >| {
+                        handlers::execute_function(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Function-Profile Pairs - get usage
+            .route(
+                Method::GET,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}/usage",
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}/{pcommit}/usage",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}/usage",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}/{pcommit}/usage",
+                ],
+                {
+                    let client = pairs_client;
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >| {
+                        handlers::get_function_profile_pair_usage(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            path,
+                        )
+                    }
+                },
+            )
+            // Function Profile Computations - create
+            // remote function
+            .route(
+                Method::POST,
+                &[
+                    "/functions/{fremote}/{fowner}/{frepository}/profiles/compute",
+                    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/compute",
+                ],
+                {
+                    let client = profile_computations_client;
+                    move |parts: Parts, Path(path): Path<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestPath,
+                    >, Json(body): Json<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestBody,
+                    >| {
+                        handlers::create_profile_computation(
+                            client,
+                            ctx::Context::<CTXEXT>::from_parts(&parts),
+                            objectiveai::functions::profiles::computations::request::Request::FunctionRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                },
+            )
+            // Auth - create API key
+            .route(
+                Method::POST,
+                &["/auth/keys"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts, Json(body): Json<
+                        objectiveai::auth::request::CreateApiKeyRequest,
+                    >| {
+                        handlers::create_api_key(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            body,
+                        )
+                    }
+                },
+            )
+            // Auth - disable API key
+            .route(
+                Method::DELETE,
+                &["/auth/keys"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts, Json(body): Json<
+                        objectiveai::auth::request::DisableApiKeyRequest,
+                    >| {
+                        handlers::disable_api_key(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            body,
+                        )
+                    }
+                },
+            )
+            // Auth - list API keys
+            .route(
+                Method::GET,
+                &["/auth/keys"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_api_keys(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Auth - create OpenRouter BYOK API key
+            .route(
+                Method::POST,
+                &["/auth/keys/openrouter"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts, Json(body): Json<
+                        objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+                    >| {
+                        handlers::create_openrouter_byok_api_key(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            body,
+                        )
+                    }
+                },
+            )
+            // Auth - delete OpenRouter BYOK API key
+            .route(
+                Method::DELETE,
+                &["/auth/keys/openrouter"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts| {
+                        handlers::delete_openrouter_byok_api_key(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Auth - get OpenRouter BYOK API key
+            .route(
+                Method::GET,
+                &["/auth/keys/openrouter"],
+                {
+                    let client = auth_client.clone();
+                    move |parts: Parts| {
+                        handlers::get_openrouter_byok_api_key(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Auth - get credits
+            .route(
+                Method::GET,
+                &["/auth/credits"],
+                move |parts: Parts| {
+                    handlers::get_credits(
+                        auth_client,
+                        ctx::Context::from_parts(&parts),
+                    )
+                },
+            )
+            // Ensemble - list
+            .route(
+                Method::GET,
+                &["/ensembles"],
+                {
+                    let client = ensemble_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_ensembles(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Ensemble - get
+            .route(
+                Method::GET,
+                &["/ensembles/{id}"],
+                {
+                    let client = ensemble_client.clone();
+                    move |parts: Parts, Path(id): Path<String>| {
+                        handlers::get_ensemble(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            id,
+                        )
+                    }
+                },
+            )
+            // Ensemble - get usage
+            .route(
+                Method::GET,
+                &["/ensembles/{id}/usage"],
+                move |parts: Parts, Path(id): Path<String>| {
+                    handlers::get_ensemble_usage(
+                        ensemble_client,
+                        ctx::Context::from_parts(&parts),
+                        id,
+                    )
+                },
+            )
+            // Ensemble LLM - list
+            .route(
+                Method::GET,
+                &["/ensemble_llms"],
+                {
+                    let client = ensemble_llm_client.clone();
+                    move |parts: Parts| {
+                        handlers::list_ensemble_llms(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                        )
+                    }
+                },
+            )
+            // Ensemble LLM - get
+            .route(
+                Method::GET,
+                &["/ensemble_llms/{id}"],
+                {
+                    let client = ensemble_llm_client.clone();
+                    move |parts: Parts, Path(id): Path<String>| {
+                        handlers::get_ensemble_llm(
+                            client,
+                            ctx::Context::from_parts(&parts),
+                            id,
+                        )
+                    }
+                },
+            )
+            // Ensemble LLM - get usage
+            .route(
+                Method::GET,
+                &["/ensemble_llms/{id}/usage"],
+                move |parts: Parts, Path(id): Path<String>| {
+                    handlers::get_ensemble_llm_usage(
+                        ensemble_llm_client,
+                        ctx::Context::from_parts(&parts),
+                        id,
+                    )
+                },
+            )
+            // OpenAPI - get document
+            .route(
+                Method::GET,
+                &[super::OPENAPI_PATH],
+                {
+                    let document = Arc::new(super::openapi());
+                    move || async move { Json(document.as_ref().clone()) }
+                },
+            )
+    }
+}
+
+/// The server's router under construction, and the method and path of every
+/// route registered on it, each of which the OpenAPI document describes.
+#[derive(Default)]
+struct Routes {
+    router: axum::Router,
+    registered: Vec<(Method, &'static str)>,
+}
+
+impl Routes {
+    /// Registers `handler` for `method` at each of `paths`.
+    fn route<H, T>(
+        mut self,
+        method: Method,
+        paths: &[&'static str],
+        handler: H,
+    ) -> Self
+    where
+        H: axum::handler::Handler<T, ()>,
+        T: 'static,
+    {
+        let method_router = axum::routing::on(
+            MethodFilter::try_from(method.clone()).unwrap(),
+            handler,
+        );
+        for path in paths {
+            self.router = self.router.route(path, method_router.clone());
+            self.registered.push((method.clone(), path));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chat::completions::upstream::openrouter::mock_upstream::MockUpstream;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tower::ServiceExt;
+
+    fn app() -> axum::Router {
+        app_with_config(super::super::Config::default())
+    }
+
+    fn app_with_config(config: super::super::Config) -> axum::Router {
+        super::ServerBuilder::<crate::ctx::DefaultContextExt>::new(config)
+            .build()
+    }
+
+    /// Sends a masked text frame, as a WebSocket client does.
+    async fn send_text(stream: &mut tokio::net::TcpStream, text: &str) {
+        let mask = [1, 2, 3, 4];
+        let mut frame = vec![0x81];
+        if text.len() < 126 {
+            frame.push(0x80 | text.len() as u8);
+        } else {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(text.len() as u16).to_be_bytes());
+        }
+        frame.extend_from_slice(&mask);
+        frame.extend(
+            text.bytes().enumerate().map(|(i, byte)| byte ^ mask[i % 4]),
+        );
+        stream.write_all(&frame).await.unwrap();
+    }
+
+    /// Receives an unmasked frame, returning its opcode and payload.
+    async fn recv_frame(stream: &mut tokio::net::TcpStream) -> (u8, Vec<u8>) {
+        let mut header = [0; 2];
+        stream.read_exact(&mut header).await.unwrap();
+        let len = match header[1] {
+            126 => stream.read_u16().await.unwrap() as usize,
+            127 => stream.read_u64().await.unwrap() as usize,
+            len => len as usize,
+        };
+        let mut payload = vec![0; len];
+        stream.read_exact(&mut payload).await.unwrap();
+        (header[0] & 0x0F, payload)
+    }
+
+    async fn recv_json(
+        stream: &mut tokio::net::TcpStream,
+    ) -> serde_json::Value {
+        let (opcode, payload) = recv_frame(stream).await;
+        assert_eq!(opcode, 0x1, "expected a text frame");
+        serde_json::from_slice(&payload).unwrap()
+    }
+
+    /// Serves `app` and opens a WebSocket to the execution endpoint.
+    async fn connect_websocket(app: axum::Router) -> tokio::net::TcpStream {
+        let (stream, _) = connect_websocket_with_headers(app, "").await;
+        stream
+    }
+
+    /// Serves `app` and opens a WebSocket to the execution endpoint, sending
+    /// `headers` (each ending in CRLF) with the handshake. Returns the
+    /// handshake response's head alongside the stream.
+    async fn connect_websocket_with_headers(
+        app: axum::Router,
+        headers: &str,
+    ) -> (tokio::net::TcpStream, String) {
+        let listener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(
+                format!(
+                    "GET /functions/executions/ws HTTP/1.1\r\n\
+                    Host: localhost\r\n\
+                    Connection: Upgrade\r\n\
+                    Upgrade: websocket\r\n\
+                    Sec-WebSocket-Version: 13\r\n\
+                    Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                    {headers}\r\n",
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="), "{head}");
+        (stream, head)
+    }
+
+    /// A start message for a single vector completion task, from RNG.
+    fn start_message() -> serde_json::Value {
+        serde_json::json!({
+            "type": "start",
+            "request": {
+                "body": {
+                    "function": {
+                        "type": "vector.function",
+                        "tasks": [{
+                            "type": "vector.completion",
+                            "messages": [
+                                { "role": "user", "content": "Which is better?" },
+                            ],
+                            "responses": ["Option A", "Option B"],
+                            "output": { "$starlark": "output['scores']" },
+                        }],
+                    },
+                    "profile": {
+                        "tasks": [{
+                            "ensemble": {
+                                "llms": [{ "count": 1, "model": "openai/gpt-4o" }],
+                            },
+                            "profile": [1.0],
+                        }],
+                        "profile": [1.0],
+                    },
+                    "input": {},
+                    "from_rng": true,
+                },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn rejects_websocket_requests_without_a_handshake() {
+        let response = app()
+            .oneshot(
+                Request::get("/functions/executions/ws")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn documents_every_route() {
+        let routes =
+            super::ServerBuilder::<crate::ctx::DefaultContextExt>::new(
+                super::super::Config::default(),
+            )
+            .routes();
+        let document = super::super::openapi();
+        let paths = document["paths"].as_object().unwrap();
+        for (method, path) in &routes.registered {
+            let method = method.as_str().to_lowercase();
+            assert!(
+                paths.get(*path).and_then(|p| p.get(&method)).is_some(),
+                "{method} {path} is not documented",
+            );
+        }
+        for (path, operations) in paths {
+            for method in operations.as_object().unwrap().keys() {
+                assert!(
+                    routes.registered.iter().any(|(m, p)| {
+                        p == path && m.as_str().eq_ignore_ascii_case(method)
+                    }),
+                    "{method} {path} is documented but not registered",
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_function_executions_over_websocket() {
+        let mut stream = connect_websocket(app()).await;
+
+        // control messages are rejected until the execution starts
+        send_text(&mut stream, r#"{"type":"pause"}"#).await;
+        assert_eq!(recv_json(&mut stream).await["type"], "error");
+
+        send_text(&mut stream, &start_message().to_string()).await;
+
+        let mut output = None;
+        loop {
+            let message = recv_json(&mut stream).await;
+            match message["type"].as_str().unwrap() {
+                "chunk" => {
+                    if !message["chunk"]["output"].is_null() {
+                        output = Some(message["chunk"]["output"].clone());
+                    }
+                }
+                "done" => break,
+                other => panic!("unexpected {other} message: {message}"),
+            }
+        }
+        let output = output.expect("the execution should have an output");
+        assert_eq!(output.as_array().map(Vec::len), Some(2), "{output}");
+        let (opcode, _) = recv_frame(&mut stream).await;
+        assert_eq!(opcode, 0x8, "expected a close frame");
+    }
+
+    #[tokio::test]
+    async fn authenticates_websockets_by_subprotocol() {
+        let auth_client = Arc::new(
+            crate::auth::LocalClient::new(None, &[7; 32], Some("admin"))
+                .await
+                .unwrap(),
+        );
+        let app = app().layer(axum::middleware::from_fn_with_state(
+            auth_client,
+            crate::auth::authenticate,
+        ));
+
+        // without the Authorization header or an API key subprotocol, the
+        // handshake is rejected
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/functions/executions/ws")
+                    .header("sec-websocket-protocol", "objectiveai")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // the API key subprotocol authenticates, and only the protocol's own
+        // subprotocol is selected
+        let (mut stream, head) = connect_websocket_with_headers(
+            app,
+            "Sec-WebSocket-Protocol: objectiveai, objectiveai.bearer.admin\r\n",
+        )
+        .await;
+        assert!(
+            head.contains("sec-websocket-protocol: objectiveai\r\n"),
+            "{head}"
+        );
+        send_text(&mut stream, &start_message().to_string()).await;
+        loop {
+            let message = recv_json(&mut stream).await;
+            match message["type"].as_str().unwrap() {
+                "chunk" => {}
+                "done" => break,
+                other => panic!("unexpected {other} message: {message}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn pause_holds_chunks_back_and_starts_no_new_tasks() {
+        use std::time::Duration;
+        let release = Arc::new(tokio::sync::Semaphore::new(0));
+        let MockUpstream {
+            api_base,
+            mut received,
+        } = MockUpstream::spawn_gated(release.clone()).await;
+        let mut stream =
+            connect_websocket(app_with_config(super::super::Config {
+                openrouter_api_base: api_base,
+                ..Default::default()
+            }))
+            .await;
+
+        // two vector completion tasks, which run one after the other
+        let task = serde_json::json!({
+            "type": "vector.completion",
+            "messages": [{ "role": "user", "content": "Which is better?" }],
+            "responses": ["Option A", "Option B"],
+            "output": { "$starlark": "output['scores']" },
+        });
+        let task_profile = serde_json::json!({
+            "ensemble": {
+                "llms": [
+                    { "count": 1, "model": "openai/gpt-4o", "output_mode": "json_schema" },
+                    { "count": 1, "model": "openai/gpt-4o-mini", "output_mode": "json_schema" },
+                ],
+            },
+            "profile": [0.5, 0.5],
+        });
+        let start = serde_json::json!({
+            "type": "start",
+            "request": {
+                "body": {
+                    "function": {
+                        "type": "vector.function",
+                        "tasks": [task, task],
+                    },
+                    "profile": {
+                        "tasks": [task_profile, task_profile],
+                        "profile": [0.5, 0.5],
+                    },
+                    "input": {},
+                },
+            },
+        });
+        send_text(&mut stream, &start.to_string()).await;
+
+        // pause while the first task is in flight
+        for _ in 0..2 {
+            received.recv().await.unwrap();
+        }
+        send_text(&mut stream, r#"{"type":"pause"}"#).await;
+        loop {
+            let message = recv_json(&mut stream).await;
+            match message["type"].as_str().unwrap() {
+                "chunk" => {}
+                "paused" => break,
+                other => panic!("unexpected {other} message: {message}"),
+            }
+        }
+
+        // the first task finishes, but neither its chunks are sent nor the
+        // second task started
+        release.add_permits(2);
+        let idle = Duration::from_millis(300);
+        assert!(
+            tokio::time::timeout(idle, received.recv()).await.is_err(),
+            "no task should start while paused"
+        );
+        assert!(
+            tokio::time::timeout(idle, stream.read_u8()).await.is_err(),
+            "no message should be sent while paused"
+        );
+
+        // once resumed, the held back chunks follow and the second task runs
+        send_text(&mut stream, r#"{"type":"resume"}"#).await;
+        assert_eq!(recv_json(&mut stream).await["type"], "resumed");
+        for _ in 0..2 {
+            received.recv().await.unwrap();
+        }
+        release.add_permits(2);
+        let mut output = None;
+        loop {
+            let message = recv_json(&mut stream).await;
+            match message["type"].as_str().unwrap() {
+                "chunk" => {
+                    if !message["chunk"]["output"].is_null() {
+                        output = Some(message["chunk"]["output"].clone());
+                    }
+                }
+                "done" => break,
+                other => panic!("unexpected {other} message: {message}"),
+            }
+        }
+        let output = output.expect("the execution should have an output");
+        assert_eq!(output.as_array().map(Vec::len), Some(2), "{output}");
+    }
+
+    #[tokio::test]
+    async fn routes_optional_commits_to_the_same_handler() {
+        let app = super::ServerBuilder::<crate::ctx::DefaultContextExt>::new(
+            super::super::Config::default(),
+        )
+        .build();
+        for uri in [
+            "/functions/github/owner/repository",
+            "/functions/github/owner/repository/0123abcd",
+            "/functions/github/owner/repository/profiles/github/owner/repository",
+            "/functions/github/owner/repository/0123abcd/profiles/github/owner/repository/0123abcd",
+        ] {
+            // the path parses, so the empty body is what gets rejected
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::post(uri)
+                        .header("content-type", "application/json")
+                        .body(Body::from("{}"))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                response.status(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{uri}"
+            );
+        }
+    }
+}
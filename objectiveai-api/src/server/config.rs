@@ -0,0 +1,82 @@
+//! Configuration for the default server components.
+
+use std::time::Duration;
+
+/// Configuration for the default components of a
+/// [`ServerBuilder`](super::ServerBuilder).
+///
+/// The defaults match the server binary's defaults.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Base URL of the ObjectiveAI API, from which Ensembles, Ensemble LLMs,
+    /// Functions, Profiles and cached votes are fetched.
+    pub objectiveai_api_base: String,
+    /// API key for the ObjectiveAI API.
+    pub objectiveai_api_key: Option<String>,
+    /// Base URL of the OpenRouter API.
+    pub openrouter_api_base: String,
+    /// API key for the OpenRouter API.
+    pub openrouter_api_key: String,
+    /// Value for the `User-Agent` header of outgoing requests.
+    pub user_agent: Option<String>,
+    /// Value for the `HTTP-Referer` header of outgoing requests.
+    pub http_referer: Option<String>,
+    /// Value for the `X-Title` header of outgoing requests.
+    pub x_title: Option<String>,
+    /// Current backoff interval for chat completion retries.
+    pub chat_completions_backoff_current_interval: Duration,
+    /// Initial backoff interval for chat completion retries.
+    pub chat_completions_backoff_initial_interval: Duration,
+    /// Randomization factor for chat completion backoff jitter.
+    pub chat_completions_backoff_randomization_factor: f64,
+    /// Multiplier for chat completion backoff growth.
+    pub chat_completions_backoff_multiplier: f64,
+    /// Maximum backoff interval for chat completion retries.
+    pub chat_completions_backoff_max_interval: Duration,
+    /// Maximum total time to spend on chat completion retries.
+    pub chat_completions_backoff_max_elapsed_time: Duration,
+    /// Chunk timeouts for upstream chat completion streams.
+    pub chat_completions_timeouts: crate::chat::completions::ChunkTimeouts,
+    /// Base directory for local Function repositories. Defaults to
+    /// `~/.objectiveai/functions`.
+    pub functions_dir: std::path::PathBuf,
+    /// Base directory for local Profile repositories. Defaults to
+    /// `~/.objectiveai/functions`.
+    pub profiles_dir: std::path::PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            objectiveai_api_base: "https://api.objective-ai.io".to_string(),
+            objectiveai_api_key: None,
+            openrouter_api_base: "https://openrouter.ai/api/v1".to_string(),
+            openrouter_api_key: String::new(),
+            user_agent: None,
+            http_referer: None,
+            x_title: None,
+            chat_completions_backoff_current_interval: Duration::from_millis(
+                100,
+            ),
+            chat_completions_backoff_initial_interval: Duration::from_millis(
+                100,
+            ),
+            chat_completions_backoff_randomization_factor: 0.5,
+            chat_completions_backoff_multiplier: 1.5,
+            chat_completions_backoff_max_interval: Duration::from_secs(1),
+            chat_completions_backoff_max_elapsed_time: Duration::from_secs(40),
+            chat_completions_timeouts: Default::default(),
+            functions_dir: default_filesystem_dir(),
+            profiles_dir: default_filesystem_dir(),
+        }
+    }
+}
+
+/// The default base directory for local Function and Profile repositories,
+/// `~/.objectiveai/functions`.
+pub fn default_filesystem_dir() -> std::path::PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join(".objectiveai")
+        .join("functions")
+}
@@ -0,0 +1,39 @@
+//! Error types for loading server settings.
+
+/// Errors that can occur when loading [`Settings`](super::Settings).
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The settings file could not be read.
+    #[error("failed to read {path}: {source}")]
+    Read {
+        /// Path of the settings file.
+        path: std::path::PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// The settings file's extension is neither `.toml`, `.yaml` nor `.yml`.
+    #[error(
+        "unsupported settings file {0}, expected a .toml, .yaml or .yml file"
+    )]
+    UnsupportedFormat(std::path::PathBuf),
+    /// The settings file is not valid TOML, or does not match the settings.
+    #[error("invalid TOML settings: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// The settings file is not valid YAML, or does not match the settings.
+    #[error("invalid YAML settings: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    /// The settings could not be serialized.
+    #[error("failed to serialize settings: {0}")]
+    Serialize(String),
+    /// An environment variable override could not be parsed.
+    #[error("invalid value for {var}: {message}")]
+    Env {
+        /// Name of the environment variable.
+        var: &'static str,
+        /// Why the value is invalid.
+        message: String,
+    },
+    /// The settings are well-formed but invalid.
+    #[error("invalid settings:\n{}", .0.iter().map(|p| format!("  - {}", p)).collect::<Vec<_>>().join("\n"))]
+    Invalid(Vec<String>),
+}
@@ -0,0 +1,1054 @@
+//! HTTP handlers for the server's routes.
+
+use crate::{
+    auth, chat, ctx, ensemble, ensemble_llm,
+    error::ResponseErrorExt,
+    functions::{self, profiles::computations::Client},
+    util::StreamOnce,
+    vector,
+};
+use axum::{
+    Json,
+    extract::ws::{self, WebSocket, WebSocketUpgrade},
+    response::{IntoResponse, Sse, sse::Event},
+};
+use objectiveai::{
+    error::ResponseError,
+    functions::executions::websocket::{
+        ClientMessage, PAUSE_MAX_DURATION, PROTOCOL, ServerMessage,
+    },
+};
+use std::{convert::Infallible, sync::Arc};
+use tokio_stream::StreamExt;
+
+// Chat Completions
+
+pub async fn create_chat_completion<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        chat::completions::Client<
+            CTXEXT,
+            impl ?Sized
+            + ensemble_llm::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::chat::completions::request::ChatCompletionCreateParams,
+) -> axum::response::Response {
+    if body.stream.unwrap_or(false) {
+        match client
+            .create_streaming_for_chat_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|result| {
+                        Ok::<Event, Infallible>(
+                            Event::default().data(
+                                match result {
+                                    Ok(chunk) => serde_json::to_string(&chunk),
+                                    Err(e) => serde_json::to_string(
+                                        &ResponseError::from(&e),
+                                    ),
+                                }
+                                .unwrap(),
+                            ),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client
+            .create_unary_for_chat_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+// Vector Completions
+
+pub async fn create_vector_completion<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        vector::completions::Client<
+            CTXEXT,
+            impl ?Sized
+            + ensemble_llm::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized + ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::vector::completions::request::VectorCompletionCreateParams,
+) -> axum::response::Response {
+    if body.stream.unwrap_or(false) {
+        match client
+            .create_streaming_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|chunk| {
+                        Ok::<Event, Infallible>(
+                            Event::default()
+                                .data(serde_json::to_string(&chunk).unwrap()),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client.create_unary_handle_usage(ctx, Arc::new(body)).await {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+// Functions
+
+pub async fn list_functions<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list_functions(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub async fn get_function_usage<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    remote: objectiveai::functions::Remote,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response {
+    match client
+        .get_function_usage(ctx, remote, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub async fn execute_function<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<super::ExecutionsClient<CTXEXT>>,
+    ctx: ctx::Context<CTXEXT>,
+    request: objectiveai::functions::executions::request::Request,
+) -> axum::response::Response {
+    if request.base().stream.unwrap_or(false) {
+        match client
+            .create_streaming_handle_usage(ctx, Arc::new(request))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|chunk| {
+                        Ok::<Event, Infallible>(
+                            Event::default()
+                                .data(serde_json::to_string(&chunk).unwrap()),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client
+            .create_unary_handle_usage(ctx, Arc::new(request))
+            .await
+        {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+pub async fn execute_function_websocket<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<super::ExecutionsClient<CTXEXT>>,
+    ctx: ctx::Context<CTXEXT>,
+    upgrade: WebSocketUpgrade,
+) -> axum::response::Response {
+    upgrade
+        .max_message_size(MAX_WEBSOCKET_MESSAGE_SIZE)
+        .protocols([PROTOCOL])
+        .on_upgrade(move |mut socket| async move {
+        // wait for the execution to start
+        let request = loop {
+            match recv_client_message(&mut socket).await {
+                Some(Ok(ClientMessage::Start { request })) => {
+                    break Arc::new(*request);
+                }
+                Some(Ok(_)) => {
+                    let error = ResponseError {
+                        code: 400,
+                        message: serde_json::json!("expected a start message"),
+                    };
+                    if send_server_message(
+                        &mut socket,
+                        ServerMessage::Error { error },
+                    )
+                    .await
+                    .is_err()
+                    {
+                        return;
+                    }
+                }
+                Some(Err(error)) => {
+                    if send_server_message(
+                        &mut socket,
+                        ServerMessage::Error { error },
+                    )
+                    .await
+                    .is_err()
+                    {
+                        return;
+                    }
+                }
+                None => return,
+            }
+        };
+
+        let control = functions::executions::ExecutionControl::new();
+        let stream = match client
+            .clone()
+            .create_streaming(
+                ctx.clone(),
+                request.clone(),
+                Some(control.clone()),
+            )
+            .await
+        {
+            Ok(stream) => stream,
+            Err(e) => {
+                let error = ResponseError::from(&e);
+                let _ = send_server_message(
+                    &mut socket,
+                    ServerMessage::Error { error },
+                )
+                .await;
+                close_websocket(&mut socket).await;
+                return;
+            }
+        };
+        let mut stream = Box::pin(stream);
+
+        // stream chunks until the execution finishes or the client cancels
+        // it, handling control messages in between. While paused, tasks which
+        // have started keep running and their chunks are held back until the
+        // execution resumes
+        let mut aggregate: Option<
+            objectiveai::functions::executions::response::streaming::FunctionExecutionChunk,
+        > = None;
+        let mut any_usage = false;
+        let mut paused_until: Option<tokio::time::Instant> = None;
+        let mut held = Vec::new();
+        let mut finished = false;
+        let last_message = 'stream: loop {
+            let reply = tokio::select! {
+                message = socket.recv() => match parse_client_message(message) {
+                    Received::Message(Ok(ClientMessage::Start { .. })) => {
+                        ServerMessage::Error {
+                            error: ResponseError {
+                                code: 400,
+                                message: serde_json::json!(
+                                    "the execution has already started"
+                                ),
+                            },
+                        }
+                    }
+                    Received::Message(Ok(ClientMessage::Pause)) => {
+                        control.pause_new_tasks.send_replace(true);
+                        paused_until.get_or_insert_with(|| {
+                            tokio::time::Instant::now() + PAUSE_MAX_DURATION
+                        });
+                        ServerMessage::Paused
+                    }
+                    Received::Message(Ok(ClientMessage::Resume)) => {
+                        control.pause_new_tasks.send_replace(false);
+                        paused_until = None;
+                        ServerMessage::Resumed
+                    }
+                    Received::Message(Ok(ClientMessage::Cancel)) => {
+                        break Some(ServerMessage::Cancelled);
+                    }
+                    Received::Message(Ok(ClientMessage::SkipRemainingTasks)) => {
+                        control.skip_remaining_tasks.cancel();
+                        continue;
+                    }
+                    Received::Message(Err(error)) => {
+                        ServerMessage::Error { error }
+                    }
+                    Received::Ignored => continue,
+                    Received::Closed => break None,
+                },
+                // resume on our own once paused for too long
+                _ = tokio::time::sleep_until(
+                    paused_until.unwrap_or_else(tokio::time::Instant::now),
+                ), if paused_until.is_some() => {
+                    control.pause_new_tasks.send_replace(false);
+                    paused_until = None;
+                    ServerMessage::Resumed
+                }
+                chunk = stream.next(), if !finished => match chunk {
+                    Some(chunk) => {
+                        any_usage |= chunk.any_usage();
+                        match &mut aggregate {
+                            Some(aggregate) => aggregate.push(&chunk),
+                            None => aggregate = Some(chunk.clone()),
+                        }
+                        let reply = ServerMessage::Chunk {
+                            chunk: Box::new(chunk),
+                        };
+                        if paused_until.is_some() {
+                            held.push(reply);
+                            continue;
+                        }
+                        reply
+                    }
+                    None if paused_until.is_some() => {
+                        finished = true;
+                        continue;
+                    }
+                    None => break Some(ServerMessage::Done),
+                },
+            };
+            if send_server_message(&mut socket, reply).await.is_err() {
+                break None;
+            }
+
+            // once resumed, send what was held back while paused
+            if paused_until.is_none() {
+                for reply in held.drain(..) {
+                    if send_server_message(&mut socket, reply).await.is_err() {
+                        break 'stream None;
+                    }
+                }
+                if finished {
+                    break Some(ServerMessage::Done);
+                }
+            }
+        };
+        drop(stream);
+
+        if let Some(message) = last_message
+            && send_server_message(&mut socket, message).await.is_ok()
+        {
+            close_websocket(&mut socket).await;
+        }
+        if any_usage {
+            client
+                .usage_handler
+                .handle_usage(ctx, request, aggregate.unwrap().into())
+                .await;
+        }
+    })
+}
+
+/// Largest message accepted over the Function execution WebSocket, in bytes:
+/// the same as axum's default limit on request bodies, as a start message
+/// carries a whole request.
+const MAX_WEBSOCKET_MESSAGE_SIZE: usize = 2 << 20;
+
+/// What was received over a Function execution WebSocket.
+enum Received {
+    /// A message from the client, or the error parsing it.
+    Message(Result<ClientMessage, ResponseError>),
+    /// A frame which needs no handling, such as a ping, which the socket
+    /// answers itself.
+    Ignored,
+    /// The connection closed or broke.
+    Closed,
+}
+
+fn parse_client_message(
+    message: Option<Result<ws::Message, axum::Error>>,
+) -> Received {
+    let parsed = match message {
+        Some(Ok(ws::Message::Text(text))) => {
+            serde_json::from_str(text.as_str())
+        }
+        Some(Ok(ws::Message::Binary(data))) => serde_json::from_slice(&data),
+        Some(Ok(ws::Message::Ping(_) | ws::Message::Pong(_))) => {
+            return Received::Ignored;
+        }
+        Some(Ok(ws::Message::Close(_))) | Some(Err(_)) | None => {
+            return Received::Closed;
+        }
+    };
+    Received::Message(parsed.map_err(|e| ResponseError {
+        code: 400,
+        message: serde_json::json!(format!("invalid message: {}", e)),
+    }))
+}
+
+/// Receives the next message from the client.
+async fn recv_client_message(
+    socket: &mut WebSocket,
+) -> Option<Result<ClientMessage, ResponseError>> {
+    loop {
+        match parse_client_message(socket.recv().await) {
+            Received::Message(message) => return Some(message),
+            Received::Ignored => {}
+            Received::Closed => return None,
+        }
+    }
+}
+
+async fn send_server_message(
+    socket: &mut WebSocket,
+    message: ServerMessage,
+) -> Result<(), axum::Error> {
+    socket
+        .send(ws::Message::Text(
+            serde_json::to_string(&message).unwrap().into(),
+        ))
+        .await
+}
+
+/// Closes the WebSocket normally, ignoring a connection which already broke.
+async fn close_websocket(socket: &mut WebSocket) {
+    let _ = socket
+        .send(ws::Message::Close(Some(ws::CloseFrame {
+            code: ws::close_code::NORMAL,
+            reason: "".into(),
+        })))
+        .await;
+}
+
+// Profiles
+
+pub async fn list_profiles<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::profile_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list_profiles(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub async fn get_profile_usage<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::profile_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    remote: objectiveai::functions::Remote,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response {
+    match client
+        .get_profile_usage(ctx, remote, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+// Function-Profile Pairs
+
+pub async fn list_function_profile_pairs<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        impl ?Sized
+        + functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list_function_profile_pairs(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_function_profile_pair<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        impl ?Sized
+        + functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    path: objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+) -> axum::response::Response {
+    match client
+        .get_function_profile_pair(
+            ctx,
+            path.fremote,
+            &path.fowner,
+            &path.frepository,
+            path.fcommit.as_deref(),
+            path.premote,
+            &path.powner,
+            &path.prepository,
+            path.pcommit.as_deref(),
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_function_profile_pair_usage<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        impl ?Sized
+        + functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    path: objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+) -> axum::response::Response {
+    match client
+        .get_function_profile_pair_usage(
+            ctx,
+            path.fremote,
+            &path.fowner,
+            &path.frepository,
+            path.fcommit.as_deref(),
+            path.premote,
+            &path.powner,
+            &path.prepository,
+            path.pcommit.as_deref(),
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Vector Completions Cache
+
+pub async fn get_vector_completion_votes<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        vector::completions::cache::Client<
+            CTXEXT,
+            impl ?Sized
+            + vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response {
+    match client.fetch_completion_votes(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_vector_cache_vote<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        vector::completions::cache::Client<
+            CTXEXT,
+            impl ?Sized
+            + vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
+) -> axum::response::Response {
+    match client
+        .fetch_cache_vote(
+            ctx,
+            &body.model,
+            body.models.as_deref(),
+            &body.messages,
+            body.tools.as_deref(),
+            &body.responses,
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Functions - get
+
+pub async fn get_function<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    remote: objectiveai::functions::Remote,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response {
+    match client
+        .get_function(ctx, remote, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Profiles - get
+
+pub async fn get_profile<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl ?Sized
+            + functions::profile_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    remote: objectiveai::functions::Remote,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response {
+    match client
+        .get_profile(ctx, remote, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Profile Computations
+
+pub async fn create_profile_computation<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    // client: Arc<
+    //     impl ?Sized + functions::profiles::computations::Client<CTXEXT>
+    //     + Send
+    //     + Sync
+    //     + 'static,
+    // >,
+    // https://github.com/rust-lang/rust/issues/100013
+    // using a concrete type for client instead
+    client: Arc<functions::profiles::computations::ObjectiveAiClient>,
+    ctx: ctx::Context<CTXEXT>,
+    request: objectiveai::functions::profiles::computations::request::Request,
+) -> axum::response::Response {
+    if request.base().stream.unwrap_or(false) {
+        match client.create_streaming(ctx, Arc::new(request)).await {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|result| {
+                        Ok::<Event, Infallible>(
+                            Event::default().data(
+                                match result {
+                                    Ok(chunk) => serde_json::to_string(&chunk),
+                                    Err(e) => serde_json::to_string(&e),
+                                }
+                                .unwrap(),
+                            ),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => e.into_response(),
+        }
+    } else {
+        match client.create_unary(ctx, Arc::new(request)).await {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+// Auth
+
+pub async fn create_api_key<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::CreateApiKeyRequest,
+) -> axum::response::Response {
+    match client.create_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn create_openrouter_byok_api_key<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+) -> axum::response::Response {
+    match client.create_openrouter_byok_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn disable_api_key<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::DisableApiKeyRequest,
+) -> axum::response::Response {
+    match client.disable_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_openrouter_byok_api_key<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.delete_openrouter_byok_api_key(ctx).await {
+        Ok(()) => axum::http::StatusCode::OK.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_api_keys<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list_api_keys(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_openrouter_byok_api_key<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.get_openrouter_byok_api_key(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_credits<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<impl ?Sized + auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.get_credits(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Ensemble
+
+pub async fn list_ensembles<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ?Sized + ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + ensemble::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_ensemble<CTXEXT: ctx::ContextExt + Send + Sync + 'static>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ?Sized + ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + ensemble::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response {
+    match client.get(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_ensemble_usage<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ?Sized + ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ?Sized
+            + ensemble::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response {
+    match client.get_usage(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Ensemble LLM
+
+pub async fn list_ensemble_llms<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ?Sized
+            + ensemble_llm::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response {
+    match client.list(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_ensemble_llm<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ?Sized
+            + ensemble_llm::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response {
+    match client.get(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_ensemble_llm_usage<
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ?Sized
+            + ensemble_llm::fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ?Sized
+            + ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response {
+    match client.get_usage(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,23 @@
+//! Assembly of the server's components into an [`axum::Router`].
+//!
+//! [`ServerBuilder`] wires the fetchers, usage handlers, upstream client and
+//! retrieval clients into the chat completions, vector completions, Function
+//! execution, and retrieval clients, and routes every endpoint to them.
+//! Downstream servers can replace any single component without rewriting
+//! the rest of the wiring. [`Settings`] are the server binary's settings,
+//! loaded from a TOML or YAML file and overridden by environment variables.
+//! [`openapi`] describes every route as an OpenAPI 3.1 document, served at
+//! [`OPENAPI_PATH`].
+
+mod builder;
+mod config;
+mod error;
+mod handlers;
+mod openapi;
+mod settings;
+
+pub use builder::*;
+pub use config::*;
+pub use error::*;
+pub use openapi::*;
+pub use settings::*;
@@ -0,0 +1,539 @@
+//! OpenAPI 3.1 document describing the routes registered by
+//! [`ServerBuilder::build`](super::ServerBuilder::build).
+
+use indexmap::IndexMap;
+use objectiveai::{
+    auth, chat, ensemble, ensemble_llm, error::ResponseError, functions, vector,
+};
+use schemars::{JsonSchema, Schema, SchemaGenerator, generate::SchemaSettings};
+use serde_json::{Map, Value, json};
+
+/// Path at which the OpenAPI document is served.
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Paths of a route with an optional Function commit.
+const FUNCTION_PATHS: [&str; 2] = [
+    "/functions/{fremote}/{fowner}/{frepository}",
+    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}",
+];
+
+/// Paths of a route with an optional Profile commit.
+const PROFILE_PATHS: [&str; 2] = [
+    "/functions/profiles/{premote}/{powner}/{prepository}",
+    "/functions/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+];
+
+/// Paths of a route with an optional Function commit and an optional
+/// Profile commit.
+const PAIR_PATHS: [&str; 4] = [
+    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}",
+    "/functions/{fremote}/{fowner}/{frepository}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}",
+    "/functions/{fremote}/{fowner}/{frepository}/{fcommit}/profiles/{premote}/{powner}/{prepository}/{pcommit}",
+];
+
+/// Builds the OpenAPI 3.1 document for the server's routes.
+///
+/// Request and response schemas are generated from the `objectiveai` types
+/// and collected under `#/components/schemas`. Endpoints which stream when
+/// the request sets `stream: true` document both the unary
+/// `application/json` response and the `text/event-stream` chunk schema.
+pub fn openapi() -> Value {
+    let mut spec = Spec::new();
+
+    // Chat Completions
+    let body =
+        spec.schema::<chat::completions::request::ChatCompletionCreateParams>();
+    let response = spec.streaming::<
+        chat::completions::response::unary::ChatCompletion,
+        chat::completions::response::streaming::ChatCompletionChunk,
+    >();
+    spec.operation(
+        "post",
+        &["/chat/completions"],
+        "Create a chat completion",
+        Some(body),
+        response,
+    );
+
+    // Vector Completions
+    let body = spec
+        .schema::<vector::completions::request::VectorCompletionCreateParams>();
+    let response = spec.streaming::<
+        vector::completions::response::unary::VectorCompletion,
+        vector::completions::response::streaming::VectorCompletionChunk,
+    >();
+    spec.operation(
+        "post",
+        &["/vector/completions"],
+        "Create a vector completion",
+        Some(body),
+        response,
+    );
+    let response =
+        spec.unary::<vector::completions::cache::response::CompletionVotes>();
+    spec.operation(
+        "post",
+        &["/vector/completions/{id}"],
+        "Get the votes of a vector completion",
+        None,
+        response,
+    );
+    let body = spec
+        .schema::<vector::completions::cache::request::CacheVoteRequestOwned>();
+    let response =
+        spec.unary::<vector::completions::cache::response::CacheVote>();
+    spec.operation(
+        "post",
+        &["/vector/completions/cache"],
+        "Get a cached vote",
+        Some(body),
+        response,
+    );
+
+    // Functions
+    let response = spec.unary::<functions::response::ListFunction>();
+    spec.operation("get", &["/functions"], "List Functions", None, response);
+    let response = spec.unary::<functions::response::GetFunction>();
+    spec.operation("get", &FUNCTION_PATHS, "Get a Function", None, response);
+    let response = spec.unary::<functions::response::UsageFunction>();
+    spec.operation(
+        "get",
+        &FUNCTION_PATHS.map(usage_path),
+        "Get the usage of a Function",
+        None,
+        response,
+    );
+
+    // Function Profiles
+    let response = spec.unary::<functions::profiles::response::ListProfile>();
+    spec.operation(
+        "get",
+        &["/functions/profiles"],
+        "List Profiles",
+        None,
+        response,
+    );
+    let response = spec.unary::<functions::profiles::response::GetProfile>();
+    spec.operation("get", &PROFILE_PATHS, "Get a Profile", None, response);
+    let response = spec.unary::<functions::profiles::response::UsageProfile>();
+    spec.operation(
+        "get",
+        &PROFILE_PATHS.map(usage_path),
+        "Get the usage of a Profile",
+        None,
+        response,
+    );
+
+    // Function-Profile Pairs
+    let response = spec.unary::<functions::response::ListFunctionProfilePair>();
+    spec.operation(
+        "get",
+        &["/functions/profiles/pairs"],
+        "List Function-Profile pairs",
+        None,
+        response,
+    );
+    let response = spec.unary::<functions::response::GetFunctionProfilePair>();
+    spec.operation(
+        "get",
+        &PAIR_PATHS,
+        "Get a Function-Profile pair",
+        None,
+        response,
+    );
+    let response =
+        spec.unary::<functions::response::UsageFunctionProfilePair>();
+    spec.operation(
+        "get",
+        &PAIR_PATHS.map(usage_path),
+        "Get the usage of a Function-Profile pair",
+        None,
+        response,
+    );
+
+    // Function Executions
+    for (paths, body, summary) in [
+        (
+            &["/functions"][..],
+            spec.schema::<functions::executions::request::FunctionInlineProfileInlineRequestBody>(),
+            "Execute an inline Function with an inline Profile",
+        ),
+        (
+            &FUNCTION_PATHS[..],
+            spec.schema::<functions::executions::request::FunctionRemoteProfileInlineRequestBody>(),
+            "Execute a remote Function with an inline Profile",
+        ),
+        (
+            &PROFILE_PATHS[..],
+            spec.schema::<functions::executions::request::FunctionInlineProfileRemoteRequestBody>(),
+            "Execute an inline Function with a remote Profile",
+        ),
+        (
+            &PAIR_PATHS[..],
+            spec.schema::<functions::executions::request::FunctionRemoteProfileRemoteRequestBody>(),
+            "Execute a remote Function with a remote Profile",
+        ),
+    ] {
+        let response = spec.streaming::<
+            functions::executions::response::unary::FunctionExecution,
+            functions::executions::response::streaming::FunctionExecutionChunk,
+        >();
+        spec.operation("post", paths, summary, Some(body), response);
+    }
+    spec.websocket::<
+        functions::executions::websocket::ClientMessage,
+        functions::executions::websocket::ServerMessage,
+    >(
+        "/functions/executions/ws",
+        "Execute a Function over a WebSocket",
+    );
+
+    // Function Profile Computations
+    for (paths, body, summary) in [
+        (
+            vec!["/functions/profiles/compute".to_string()],
+            spec.schema::<functions::profiles::computations::request::FunctionInlineRequestBody>(),
+            "Compute a Profile for an inline Function",
+        ),
+        (
+            FUNCTION_PATHS.map(compute_path).to_vec(),
+            spec.schema::<functions::profiles::computations::request::FunctionRemoteRequestBody>(),
+            "Compute a Profile for a remote Function",
+        ),
+    ] {
+        let response = spec.streaming::<
+            functions::profiles::computations::response::unary::FunctionProfileComputation,
+            functions::profiles::computations::response::streaming::FunctionProfileComputationChunk,
+        >();
+        spec.operation("post", &paths, summary, Some(body), response);
+    }
+
+    // Auth
+    let body = spec.schema::<auth::request::CreateApiKeyRequest>();
+    let response = spec.unary::<auth::response::CreateApiKeyResponse>();
+    spec.operation(
+        "post",
+        &["/auth/keys"],
+        "Create an API key",
+        Some(body),
+        response,
+    );
+    let body = spec.schema::<auth::request::DisableApiKeyRequest>();
+    let response = spec.unary::<auth::response::DisableApiKeyResponse>();
+    spec.operation(
+        "delete",
+        &["/auth/keys"],
+        "Disable an API key",
+        Some(body),
+        response,
+    );
+    let response = spec.unary::<auth::response::ListApiKeyResponse>();
+    spec.operation("get", &["/auth/keys"], "List API keys", None, response);
+    let body =
+        spec.schema::<auth::request::CreateOpenRouterByokApiKeyRequest>();
+    let response =
+        spec.unary::<auth::response::CreateOpenRouterByokApiKeyResponse>();
+    spec.operation(
+        "post",
+        &["/auth/keys/openrouter"],
+        "Create an OpenRouter BYOK API key",
+        Some(body),
+        response,
+    );
+    spec.operation(
+        "delete",
+        &["/auth/keys/openrouter"],
+        "Delete the OpenRouter BYOK API key",
+        None,
+        json!({ "description": "The key was deleted." }),
+    );
+    let response =
+        spec.unary::<auth::response::GetOpenRouterByokApiKeyResponse>();
+    spec.operation(
+        "get",
+        &["/auth/keys/openrouter"],
+        "Get the OpenRouter BYOK API key",
+        None,
+        response,
+    );
+    let response = spec.unary::<auth::response::GetCreditsResponse>();
+    spec.operation("get", &["/auth/credits"], "Get credits", None, response);
+
+    // Ensembles
+    let response = spec.unary::<ensemble::response::ListEnsemble>();
+    spec.operation("get", &["/ensembles"], "List Ensembles", None, response);
+    let response = spec.unary::<ensemble::response::GetEnsemble>();
+    spec.operation(
+        "get",
+        &["/ensembles/{id}"],
+        "Get an Ensemble",
+        None,
+        response,
+    );
+    let response = spec.unary::<ensemble::response::UsageEnsemble>();
+    spec.operation(
+        "get",
+        &["/ensembles/{id}/usage"],
+        "Get the usage of an Ensemble",
+        None,
+        response,
+    );
+
+    // Ensemble LLMs
+    let response = spec.unary::<ensemble_llm::response::ListEnsembleLlm>();
+    spec.operation(
+        "get",
+        &["/ensemble_llms"],
+        "List Ensemble LLMs",
+        None,
+        response,
+    );
+    let response = spec.unary::<ensemble_llm::response::GetEnsembleLlm>();
+    spec.operation(
+        "get",
+        &["/ensemble_llms/{id}"],
+        "Get an Ensemble LLM",
+        None,
+        response,
+    );
+    let response = spec.unary::<ensemble_llm::response::UsageEnsembleLlm>();
+    spec.operation(
+        "get",
+        &["/ensemble_llms/{id}/usage"],
+        "Get the usage of an Ensemble LLM",
+        None,
+        response,
+    );
+
+    // OpenAPI
+    let response = json!({
+        "description": "This OpenAPI document.",
+        "content": {
+            "application/json": { "schema": { "type": "object" } },
+        },
+    });
+    spec.operation(
+        "get",
+        &[OPENAPI_PATH],
+        "Get the OpenAPI document",
+        None,
+        response,
+    );
+
+    spec.into_document()
+}
+
+fn usage_path(path: &str) -> String {
+    format!("{path}/usage")
+}
+
+fn compute_path(path: &str) -> String {
+    format!("{path}/profiles/compute")
+}
+
+/// An OpenAPI document under construction.
+struct Spec {
+    generator: SchemaGenerator,
+    paths: IndexMap<String, Map<String, Value>>,
+}
+
+impl Spec {
+    fn new() -> Self {
+        let generator = SchemaSettings::draft2020_12()
+            .with(|settings| {
+                settings.definitions_path = "/components/schemas".into();
+                settings.meta_schema = None;
+            })
+            .into_generator();
+        Self {
+            generator,
+            paths: IndexMap::new(),
+        }
+    }
+
+    /// Returns a reference to the schema of `T`, registering its definition.
+    fn schema<T: JsonSchema>(&mut self) -> Schema {
+        self.generator.subschema_for::<T>()
+    }
+
+    /// A `200` response with a JSON body of type `T`.
+    fn unary<T: JsonSchema>(&mut self) -> Value {
+        json!({
+            "description": "Successful response.",
+            "content": {
+                "application/json": { "schema": self.schema::<T>() },
+            },
+        })
+    }
+
+    /// A `200` response with a JSON body of type `T`, or, when the request
+    /// sets `stream: true`, server-sent events of type `C`.
+    fn streaming<T: JsonSchema, C: JsonSchema>(&mut self) -> Value {
+        json!({
+            "description": "Successful response. When the request sets \
+                `stream: true`, a stream of server-sent events whose `data` \
+                is a chunk or an error, terminated by a `[DONE]` event.",
+            "content": {
+                "application/json": { "schema": self.schema::<T>() },
+                "text/event-stream": {
+                    "schema": {
+                        "oneOf": [
+                            self.schema::<C>(),
+                            self.schema::<ResponseError>(),
+                        ],
+                    },
+                },
+            },
+        })
+    }
+
+    /// Registers a WebSocket at `path`, whose client sends JSON messages of
+    /// type `C` and whose server sends JSON messages of type `S`.
+    ///
+    /// OpenAPI cannot describe WebSocket messages, so their schemas are
+    /// listed under the `x-websocket-messages` extension.
+    fn websocket<C: JsonSchema, S: JsonSchema>(
+        &mut self,
+        path: &str,
+        summary: &str,
+    ) {
+        let operation = json!({
+            "summary": summary,
+            "responses": {
+                "101": { "description": "Switched to the WebSocket protocol." },
+                "400": { "description": "Not a WebSocket handshake." },
+            },
+            "x-websocket-messages": {
+                "client": self.schema::<C>(),
+                "server": self.schema::<S>(),
+            },
+        });
+        self.paths
+            .entry(path.to_string())
+            .or_default()
+            .insert("get".to_string(), operation);
+    }
+
+    /// Registers an operation at each of `paths`.
+    fn operation<P: AsRef<str>>(
+        &mut self,
+        method: &str,
+        paths: &[P],
+        summary: &str,
+        body: Option<Schema>,
+        response: Value,
+    ) {
+        let error = self.schema::<ResponseError>();
+        for path in paths {
+            let path = path.as_ref();
+            let mut operation = Map::new();
+            operation.insert("summary".to_string(), json!(summary));
+            let parameters = path_parameters(path);
+            if !parameters.is_empty() {
+                operation
+                    .insert("parameters".to_string(), Value::Array(parameters));
+            }
+            if let Some(body) = &body {
+                operation.insert(
+                    "requestBody".to_string(),
+                    json!({
+                        "required": true,
+                        "content": {
+                            "application/json": { "schema": body },
+                        },
+                    }),
+                );
+            }
+            operation.insert(
+                "responses".to_string(),
+                json!({
+                    "200": response,
+                    "default": {
+                        "description": "Error response.",
+                        "content": {
+                            "application/json": { "schema": error },
+                        },
+                    },
+                }),
+            );
+            self.paths
+                .entry(path.to_string())
+                .or_default()
+                .insert(method.to_string(), Value::Object(operation));
+        }
+    }
+
+    fn into_document(mut self) -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "ObjectiveAI API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": self.paths,
+            "components": {
+                "schemas": self.generator.take_definitions(true),
+            },
+        })
+    }
+}
+
+/// The `{name}` segments of `path`, as required string path parameters.
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| {
+            segment.strip_prefix('{').and_then(|s| s.strip_suffix('}'))
+        })
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(r)) => refs.push(r),
+                        _ => collect_refs(value, refs),
+                    }
+                }
+            }
+            Value::Array(values) => {
+                values.iter().for_each(|value| collect_refs(value, refs))
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn documents_streaming_responses() {
+        let document = super::openapi();
+        let chunk = &document["paths"]["/functions"]["post"]["responses"]["200"]
+            ["content"]["text/event-stream"];
+        assert!(chunk.is_object());
+    }
+
+    #[test]
+    fn resolves_every_reference() {
+        let document = super::openapi();
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for r in refs {
+            let pointer = r.strip_prefix('#').unwrap();
+            assert!(document.pointer(pointer).is_some(), "{r}");
+        }
+    }
+}
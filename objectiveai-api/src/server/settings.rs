@@ -0,0 +1,748 @@
+//! Structured settings for the server binary, loaded from a TOML or YAML
+//! file and overridden by environment variables.
+
+use crate::{auth, chat};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, time::Duration};
+
+/// Placeholder which replaces secrets in [`Settings::redacted`].
+pub const REDACTED: &str = "<redacted>";
+
+/// Settings for the server binary.
+///
+/// Every field has a default, so a settings file only needs the fields it
+/// changes. Durations are in milliseconds.
+///
+/// ```toml
+/// [listen]
+/// port = 8080
+///
+/// [upstreams.openrouter]
+/// api_key = "sk-or-..."
+///
+/// [timeouts]
+/// first_chunk_timeout = 15000
+///
+/// [timeouts.models."openai/o3"]
+/// first_chunk_timeout = 90000
+///
+/// [auth]
+/// mode = "local"
+/// encryption_key = "..."
+///
+/// [filesystem]
+/// functions_dir = "/srv/objectiveai/functions"
+/// profiles_dir = "/srv/objectiveai/profiles"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Where the server listens.
+    pub listen: ListenSettings,
+    /// Upstream APIs.
+    pub upstreams: UpstreamsSettings,
+    /// Chunk timeouts for upstream chat completion streams.
+    pub timeouts: TimeoutsSettings,
+    /// Retries of upstream chat completions.
+    pub backoff: BackoffSettings,
+    /// How callers are authenticated.
+    pub auth: AuthSettings,
+    /// Where server state is stored.
+    pub storage: StorageSettings,
+    /// Where local Functions and Profiles are read from.
+    pub filesystem: FilesystemSettings,
+}
+
+/// Where the server listens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListenSettings {
+    /// Bind address. Env: `ADDRESS`.
+    pub address: String,
+    /// Bind port. Env: `PORT`.
+    pub port: u16,
+}
+
+impl Default for ListenSettings {
+    fn default() -> Self {
+        Self {
+            address: "0.0.0.0".to_string(),
+            port: 5000,
+        }
+    }
+}
+
+/// Upstream APIs, and the headers sent to them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpstreamsSettings {
+    /// The ObjectiveAI API.
+    pub objectiveai: ObjectiveAiUpstreamSettings,
+    /// The OpenRouter API.
+    pub openrouter: OpenRouterUpstreamSettings,
+    /// Value for the `User-Agent` header. Env: `USER_AGENT`.
+    pub user_agent: Option<String>,
+    /// Value for the `HTTP-Referer` header. Env: `HTTP_REFERER`.
+    pub http_referer: Option<String>,
+    /// Value for the `X-Title` header. Env: `X_TITLE`.
+    pub x_title: Option<String>,
+}
+
+/// The ObjectiveAI API, from which Ensembles, Ensemble LLMs, Functions,
+/// Profiles and cached votes are fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObjectiveAiUpstreamSettings {
+    /// Base URL. Env: `OBJECTIVEAI_API_BASE`.
+    pub api_base: String,
+    /// API key. Env: `OBJECTIVEAI_API_KEY`.
+    pub api_key: Option<String>,
+}
+
+impl Default for ObjectiveAiUpstreamSettings {
+    fn default() -> Self {
+        Self {
+            api_base: "https://api.objective-ai.io".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+/// The OpenRouter API, through which LLMs are called.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenRouterUpstreamSettings {
+    /// Base URL. Env: `OPENROUTER_API_BASE`.
+    pub api_base: String,
+    /// API key. Env: `OPENROUTER_API_KEY`.
+    pub api_key: Option<String>,
+}
+
+impl Default for OpenRouterUpstreamSettings {
+    fn default() -> Self {
+        Self {
+            api_base: "https://openrouter.ai/api/v1".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+/// Chunk timeouts for upstream chat completion streams, in milliseconds.
+/// Requests may still set their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeoutsSettings {
+    /// Default timeout for the first chunk. Env:
+    /// `CHAT_COMPLETIONS_FIRST_CHUNK_TIMEOUT`.
+    pub first_chunk_timeout: u64,
+    /// Default timeout for each chunk after the first. Env:
+    /// `CHAT_COMPLETIONS_OTHER_CHUNK_TIMEOUT`.
+    pub other_chunk_timeout: u64,
+    /// Defaults for specific upstream models, keyed by model name
+    /// (e.g. `openai/gpt-4o`).
+    pub models: IndexMap<String, ModelTimeoutsSettings>,
+}
+
+impl Default for TimeoutsSettings {
+    fn default() -> Self {
+        Self {
+            first_chunk_timeout: 120_000,
+            other_chunk_timeout: 120_000,
+            models: IndexMap::new(),
+        }
+    }
+}
+
+/// Chunk timeouts for a single upstream model, in milliseconds. Unset
+/// timeouts fall back to the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModelTimeoutsSettings {
+    /// Timeout for the first chunk.
+    pub first_chunk_timeout: Option<u64>,
+    /// Timeout for each chunk after the first.
+    pub other_chunk_timeout: Option<u64>,
+}
+
+/// Exponential backoff for retrying upstream chat completions, in
+/// milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BackoffSettings {
+    /// Current interval. Env: `CHAT_COMPLETIONS_BACKOFF_CURRENT_INTERVAL`.
+    pub current_interval: u64,
+    /// Initial interval. Env: `CHAT_COMPLETIONS_BACKOFF_INITIAL_INTERVAL`.
+    pub initial_interval: u64,
+    /// Randomization factor, between 0 and 1. Env:
+    /// `CHAT_COMPLETIONS_BACKOFF_RANDOMIZATION_FACTOR`.
+    pub randomization_factor: f64,
+    /// Growth multiplier, at least 1. Env:
+    /// `CHAT_COMPLETIONS_BACKOFF_MULTIPLIER`.
+    pub multiplier: f64,
+    /// Maximum interval. Env: `CHAT_COMPLETIONS_BACKOFF_MAX_INTERVAL`.
+    pub max_interval: u64,
+    /// Maximum total time. Env: `CHAT_COMPLETIONS_BACKOFF_MAX_ELAPSED_TIME`.
+    pub max_elapsed_time: u64,
+}
+
+impl Default for BackoffSettings {
+    fn default() -> Self {
+        Self {
+            current_interval: 100,
+            initial_interval: 100,
+            randomization_factor: 0.5,
+            multiplier: 1.5,
+            max_interval: 1_000,
+            max_elapsed_time: 40_000,
+        }
+    }
+}
+
+/// How callers are authenticated.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthSettings {
+    /// Auth mode. Env: `AUTH_MODE`.
+    pub mode: auth::AuthMode,
+    /// Base64-encoded 32-byte key encrypting stored BYOK keys, required in
+    /// local mode. Env: `AUTH_ENCRYPTION_KEY`.
+    pub encryption_key: Option<String>,
+    /// Secret which authenticates without being issued. Env:
+    /// `AUTH_ADMIN_API_KEY`.
+    pub admin_api_key: Option<String>,
+}
+
+/// Where server state is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSettings {
+    /// API key store, in local auth mode. Env: `AUTH_STORE_PATH`.
+    pub auth_store_path: PathBuf,
+    /// Tenant registry, in local auth mode. Env: `TENANTS_PATH`.
+    pub tenants_path: Option<PathBuf>,
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            auth_store_path: dirs::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(".objectiveai")
+                .join("auth.json"),
+            tenants_path: None,
+        }
+    }
+}
+
+/// Where local Functions and Profiles are read from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilesystemSettings {
+    /// Base directory for local Function repositories. Env: `FUNCTIONS_DIR`.
+    pub functions_dir: PathBuf,
+    /// Base directory for local Profile repositories. Env: `PROFILES_DIR`.
+    pub profiles_dir: PathBuf,
+}
+
+impl Default for FilesystemSettings {
+    fn default() -> Self {
+        Self {
+            functions_dir: super::default_filesystem_dir(),
+            profiles_dir: super::default_filesystem_dir(),
+        }
+    }
+}
+
+/// Format of a settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// TOML, for `.toml` files.
+    Toml,
+    /// YAML, for `.yaml` and `.yml` files.
+    Yaml,
+}
+
+impl Format {
+    /// Returns the format of a settings file from its extension.
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Format::Toml),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+}
+
+impl Settings {
+    /// Reads settings from a `.toml`, `.yaml` or `.yml` file.
+    pub async fn from_file(
+        path: &std::path::Path,
+    ) -> Result<Self, super::Error> {
+        let format = Format::from_path(path)
+            .ok_or_else(|| super::Error::UnsupportedFormat(path.to_owned()))?;
+        let content =
+            tokio::fs::read_to_string(path).await.map_err(|source| {
+                super::Error::Read {
+                    path: path.to_owned(),
+                    source,
+                }
+            })?;
+        Self::parse(&content, format)
+    }
+
+    /// Parses settings from a string.
+    pub fn parse(content: &str, format: Format) -> Result<Self, super::Error> {
+        match format {
+            Format::Toml => Ok(toml::from_str(content)?),
+            Format::Yaml => Ok(serde_yaml::from_str(content)?),
+        }
+    }
+
+    /// Serializes the settings to a string.
+    pub fn to_string(&self, format: Format) -> Result<String, super::Error> {
+        match format {
+            Format::Toml => toml::to_string_pretty(self)
+                .map_err(|e| super::Error::Serialize(e.to_string())),
+            Format::Yaml => serde_yaml::to_string(self)
+                .map_err(|e| super::Error::Serialize(e.to_string())),
+        }
+    }
+
+    /// Returns a copy of the settings with API keys and other secrets
+    /// replaced by [`REDACTED`].
+    pub fn redacted(&self) -> Self {
+        let redact = |secret: &Option<String>| {
+            secret.as_ref().map(|_| REDACTED.to_string())
+        };
+        let mut settings = self.clone();
+        settings.upstreams.objectiveai.api_key =
+            redact(&self.upstreams.objectiveai.api_key);
+        settings.upstreams.openrouter.api_key =
+            redact(&self.upstreams.openrouter.api_key);
+        settings.auth.encryption_key = redact(&self.auth.encryption_key);
+        settings.auth.admin_api_key = redact(&self.auth.admin_api_key);
+        settings
+    }
+
+    /// Overrides settings with the environment variables named in each
+    /// field's documentation, looked up with `var`.
+    pub fn apply_env(
+        &mut self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), super::Error> {
+        if let Some(value) = var("ADDRESS") {
+            self.listen.address = value;
+        }
+        if let Some(value) = var("PORT") {
+            self.listen.port = parse_env("PORT", &value)?;
+        }
+        if let Some(value) = var("OBJECTIVEAI_API_BASE") {
+            self.upstreams.objectiveai.api_base = value;
+        }
+        if let Some(value) = var("OBJECTIVEAI_API_KEY") {
+            self.upstreams.objectiveai.api_key = Some(value);
+        }
+        if let Some(value) = var("OPENROUTER_API_BASE") {
+            self.upstreams.openrouter.api_base = value;
+        }
+        if let Some(value) = var("OPENROUTER_API_KEY") {
+            self.upstreams.openrouter.api_key = Some(value);
+        }
+        if let Some(value) = var("USER_AGENT") {
+            self.upstreams.user_agent = Some(value);
+        }
+        if let Some(value) = var("HTTP_REFERER") {
+            self.upstreams.http_referer = Some(value);
+        }
+        if let Some(value) = var("X_TITLE") {
+            self.upstreams.x_title = Some(value);
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_FIRST_CHUNK_TIMEOUT") {
+            self.timeouts.first_chunk_timeout =
+                parse_env("CHAT_COMPLETIONS_FIRST_CHUNK_TIMEOUT", &value)?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_OTHER_CHUNK_TIMEOUT") {
+            self.timeouts.other_chunk_timeout =
+                parse_env("CHAT_COMPLETIONS_OTHER_CHUNK_TIMEOUT", &value)?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_BACKOFF_CURRENT_INTERVAL") {
+            self.backoff.current_interval =
+                parse_env("CHAT_COMPLETIONS_BACKOFF_CURRENT_INTERVAL", &value)?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_BACKOFF_INITIAL_INTERVAL") {
+            self.backoff.initial_interval =
+                parse_env("CHAT_COMPLETIONS_BACKOFF_INITIAL_INTERVAL", &value)?;
+        }
+        if let Some(value) =
+            var("CHAT_COMPLETIONS_BACKOFF_RANDOMIZATION_FACTOR")
+        {
+            self.backoff.randomization_factor = parse_env(
+                "CHAT_COMPLETIONS_BACKOFF_RANDOMIZATION_FACTOR",
+                &value,
+            )?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_BACKOFF_MULTIPLIER") {
+            self.backoff.multiplier =
+                parse_env("CHAT_COMPLETIONS_BACKOFF_MULTIPLIER", &value)?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_BACKOFF_MAX_INTERVAL") {
+            self.backoff.max_interval =
+                parse_env("CHAT_COMPLETIONS_BACKOFF_MAX_INTERVAL", &value)?;
+        }
+        if let Some(value) = var("CHAT_COMPLETIONS_BACKOFF_MAX_ELAPSED_TIME") {
+            self.backoff.max_elapsed_time =
+                parse_env("CHAT_COMPLETIONS_BACKOFF_MAX_ELAPSED_TIME", &value)?;
+        }
+        if let Some(value) = var("AUTH_MODE") {
+            self.auth.mode = parse_env("AUTH_MODE", &value)?;
+        }
+        if let Some(value) = var("AUTH_ENCRYPTION_KEY") {
+            self.auth.encryption_key = Some(value);
+        }
+        if let Some(value) = var("AUTH_ADMIN_API_KEY") {
+            self.auth.admin_api_key = Some(value);
+        }
+        if let Some(value) = var("AUTH_STORE_PATH") {
+            self.storage.auth_store_path = PathBuf::from(value);
+        }
+        if let Some(value) = var("TENANTS_PATH") {
+            self.storage.tenants_path = Some(PathBuf::from(value));
+        }
+        if let Some(value) = var("FUNCTIONS_DIR") {
+            self.filesystem.functions_dir = PathBuf::from(value);
+        }
+        if let Some(value) = var("PROFILES_DIR") {
+            self.filesystem.profiles_dir = PathBuf::from(value);
+        }
+        Ok(())
+    }
+
+    /// Checks the settings, reporting every problem at once.
+    pub fn validate(&self) -> Result<(), super::Error> {
+        let mut problems = Vec::new();
+
+        for (field, api_base) in [
+            (
+                "upstreams.objectiveai.api_base",
+                &self.upstreams.objectiveai.api_base,
+            ),
+            (
+                "upstreams.openrouter.api_base",
+                &self.upstreams.openrouter.api_base,
+            ),
+        ] {
+            match reqwest::Url::parse(api_base) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                Ok(_) => {
+                    problems.push(format!("`{}` must be an http(s) URL", field))
+                }
+                Err(e) => problems
+                    .push(format!("`{}` is not a valid URL: {}", field, e)),
+            }
+        }
+
+        let mut check_timeout = |field: String, ms: u64| {
+            let timeout = Duration::from_millis(ms);
+            if timeout < chat::completions::MIN_CHUNK_TIMEOUT
+                || timeout > chat::completions::MAX_CHUNK_TIMEOUT
+            {
+                problems.push(format!(
+                    "`{}` must be between {} and {} milliseconds",
+                    field,
+                    chat::completions::MIN_CHUNK_TIMEOUT.as_millis(),
+                    chat::completions::MAX_CHUNK_TIMEOUT.as_millis(),
+                ));
+            }
+        };
+        check_timeout(
+            "timeouts.first_chunk_timeout".to_string(),
+            self.timeouts.first_chunk_timeout,
+        );
+        check_timeout(
+            "timeouts.other_chunk_timeout".to_string(),
+            self.timeouts.other_chunk_timeout,
+        );
+        for (model, timeouts) in &self.timeouts.models {
+            if let Some(ms) = timeouts.first_chunk_timeout {
+                check_timeout(
+                    format!(
+                        "timeouts.models.\"{}\".first_chunk_timeout",
+                        model
+                    ),
+                    ms,
+                );
+            }
+            if let Some(ms) = timeouts.other_chunk_timeout {
+                check_timeout(
+                    format!(
+                        "timeouts.models.\"{}\".other_chunk_timeout",
+                        model
+                    ),
+                    ms,
+                );
+            }
+        }
+        if self.timeouts.models.contains_key("") {
+            problems.push(
+                "`timeouts.models` cannot have an empty model".to_string(),
+            );
+        }
+
+        if !(0.0..=1.0).contains(&self.backoff.randomization_factor) {
+            problems.push(
+                "`backoff.randomization_factor` must be between 0 and 1"
+                    .to_string(),
+            );
+        }
+        if !(1.0..).contains(&self.backoff.multiplier) {
+            problems
+                .push("`backoff.multiplier` must be at least 1".to_string());
+        }
+        if self.backoff.initial_interval > self.backoff.max_interval {
+            problems.push(
+                "`backoff.initial_interval` cannot exceed `backoff.max_interval`"
+                    .to_string(),
+            );
+        }
+
+        match (&self.auth.mode, &self.auth.encryption_key) {
+            (auth::AuthMode::Local, None) => problems.push(
+                "`auth.encryption_key` is required when `auth.mode` is local"
+                    .to_string(),
+            ),
+            (auth::AuthMode::Local, Some(encryption_key)) => {
+                if let Err(e) =
+                    auth::LocalClient::decode_encryption_key(encryption_key)
+                {
+                    problems.push(format!("`auth.encryption_key`: {}", e));
+                }
+            }
+            (auth::AuthMode::ObjectiveAi, _) => {}
+        }
+        if self.storage.tenants_path.is_some()
+            && self.auth.mode != auth::AuthMode::Local
+        {
+            problems.push(
+                "`storage.tenants_path` requires `auth.mode` to be local"
+                    .to_string(),
+            );
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(super::Error::Invalid(problems))
+        }
+    }
+
+    /// Returns the configuration of the default server components.
+    pub fn server_config(&self) -> super::Config {
+        super::Config {
+            objectiveai_api_base: self.upstreams.objectiveai.api_base.clone(),
+            objectiveai_api_key: self.upstreams.objectiveai.api_key.clone(),
+            openrouter_api_base: self.upstreams.openrouter.api_base.clone(),
+            openrouter_api_key: self
+                .upstreams
+                .openrouter
+                .api_key
+                .clone()
+                .unwrap_or_default(),
+            user_agent: self.upstreams.user_agent.clone(),
+            http_referer: self.upstreams.http_referer.clone(),
+            x_title: self.upstreams.x_title.clone(),
+            chat_completions_backoff_current_interval: Duration::from_millis(
+                self.backoff.current_interval,
+            ),
+            chat_completions_backoff_initial_interval: Duration::from_millis(
+                self.backoff.initial_interval,
+            ),
+            chat_completions_backoff_randomization_factor: self
+                .backoff
+                .randomization_factor,
+            chat_completions_backoff_multiplier: self.backoff.multiplier,
+            chat_completions_backoff_max_interval: Duration::from_millis(
+                self.backoff.max_interval,
+            ),
+            chat_completions_backoff_max_elapsed_time: Duration::from_millis(
+                self.backoff.max_elapsed_time,
+            ),
+            chat_completions_timeouts: chat::completions::ChunkTimeouts {
+                first_chunk: Duration::from_millis(
+                    self.timeouts.first_chunk_timeout,
+                ),
+                other_chunk: Duration::from_millis(
+                    self.timeouts.other_chunk_timeout,
+                ),
+                models: self
+                    .timeouts
+                    .models
+                    .iter()
+                    .map(|(model, timeouts)| {
+                        (
+                            model.clone(),
+                            chat::completions::ModelChunkTimeouts {
+                                first_chunk: timeouts
+                                    .first_chunk_timeout
+                                    .map(Duration::from_millis),
+                                other_chunk: timeouts
+                                    .other_chunk_timeout
+                                    .map(Duration::from_millis),
+                            },
+                        )
+                    })
+                    .collect(),
+            },
+            functions_dir: self.filesystem.functions_dir.clone(),
+            profiles_dir: self.filesystem.profiles_dir.clone(),
+        }
+    }
+}
+
+fn parse_env<T>(var: &'static str, value: &str) -> Result<T, super::Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| super::Error::Env {
+        var,
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const ENCRYPTION_KEY: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    #[test]
+    fn toml_and_yaml_fill_in_defaults() {
+        let toml = Settings::parse(
+            r#"
+                [listen]
+                port = 8080
+
+                [upstreams.openrouter]
+                api_key = "sk-or-test"
+
+                [timeouts.models."openai/o3"]
+                first_chunk_timeout = 90000
+            "#,
+            Format::Toml,
+        )
+        .unwrap();
+        let yaml = Settings::parse(
+            r#"
+                listen:
+                  port: 8080
+                upstreams:
+                  openrouter:
+                    api_key: sk-or-test
+                timeouts:
+                  models:
+                    openai/o3:
+                      first_chunk_timeout: 90000
+            "#,
+            Format::Yaml,
+        )
+        .unwrap();
+        assert_eq!(toml, yaml);
+        assert_eq!(toml.listen.address, "0.0.0.0");
+        assert_eq!(
+            toml.upstreams.openrouter.api_base,
+            "https://openrouter.ai/api/v1"
+        );
+        let config = toml.server_config();
+        assert_eq!(config.openrouter_api_key, "sk-or-test");
+        assert_eq!(
+            config
+                .chat_completions_timeouts
+                .resolve("openai/o3", None, None),
+            (Duration::from_secs(90), Duration::from_secs(120)),
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let err = Settings::parse("[listen]\nprot = 8080\n", Format::Toml)
+            .unwrap_err();
+        assert!(err.to_string().contains("prot"));
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let mut settings =
+            Settings::parse("[listen]\nport = 8080\n", Format::Toml).unwrap();
+        let env = HashMap::from([
+            ("PORT", "9090"),
+            ("FUNCTIONS_DIR", "/srv/functions"),
+            ("AUTH_MODE", "local"),
+        ]);
+        settings
+            .apply_env(|var| env.get(var).map(|value| value.to_string()))
+            .unwrap();
+        assert_eq!(settings.listen.port, 9090);
+        assert_eq!(
+            settings.filesystem.functions_dir,
+            PathBuf::from("/srv/functions")
+        );
+        assert_eq!(settings.auth.mode, auth::AuthMode::Local);
+
+        let err = settings
+            .apply_env(|var| (var == "PORT").then(|| "http".to_string()))
+            .unwrap_err();
+        assert!(matches!(err, crate::server::Error::Env { var: "PORT", .. }));
+    }
+
+    #[test]
+    fn validate_reports_every_problem() {
+        let mut settings = Settings::default();
+        settings.validate().unwrap();
+
+        settings.upstreams.openrouter.api_base = "openrouter.ai".to_string();
+        settings.timeouts.models.insert(
+            "openai/o3".to_string(),
+            ModelTimeoutsSettings {
+                first_chunk_timeout: Some(600_000),
+                other_chunk_timeout: None,
+            },
+        );
+        settings.backoff.multiplier = 0.5;
+        settings.auth.mode = auth::AuthMode::Local;
+        let crate::server::Error::Invalid(problems) =
+            settings.validate().unwrap_err()
+        else {
+            panic!("expected invalid settings");
+        };
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+
+        settings.upstreams.openrouter.api_base =
+            "https://openrouter.ai/api/v1".to_string();
+        settings.timeouts.models.clear();
+        settings.backoff.multiplier = 1.5;
+        settings.auth.encryption_key = Some(ENCRYPTION_KEY.to_string());
+        settings.storage.tenants_path = Some(PathBuf::from("tenants.json"));
+        settings.validate().unwrap();
+    }
+
+    #[test]
+    fn printed_settings_are_redacted_and_round_trip() {
+        let mut settings = Settings::default();
+        settings.upstreams.openrouter.api_key = Some("sk-or-test".to_string());
+        settings.timeouts.models.insert(
+            "openai/o3".to_string(),
+            ModelTimeoutsSettings {
+                first_chunk_timeout: Some(90_000),
+                other_chunk_timeout: None,
+            },
+        );
+        let redacted = settings.redacted();
+        for format in [Format::Toml, Format::Yaml] {
+            let printed = redacted.to_string(format).unwrap();
+            assert!(!printed.contains("sk-or-test"));
+            assert_eq!(Settings::parse(&printed, format).unwrap(), redacted);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Bradley-Terry fit of pairwise votes.
+//!
+//! Converts an LLM's votes over comparisons of subsets of the responses into
+//! a single vote over all of the responses. Each response is assigned a
+//! strength such that the probability of it being chosen from a comparison is
+//! its strength divided by the total strength of the compared responses
+//! (Luce's choice axiom, which reduces to Bradley-Terry for pairs). Strengths
+//! are fit by maximum likelihood with the minorization-maximization algorithm.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// Maximum number of minorization-maximization iterations.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Iteration stops once no strength changes by more than this.
+const TOLERANCE: f64 = 1e-12;
+
+/// Fits strengths to the votes of each comparison, returning them as a vote
+/// over `responses_len` responses which sums to 1.
+///
+/// Responses which never received any share of a vote have a strength of 0.
+/// If there are no votes at all, the result is uniform.
+pub fn bradley_terry(
+    responses_len: usize,
+    comparisons: &[objectiveai::vector::completions::response::VoteComparison],
+) -> Vec<Decimal> {
+    let comparisons = comparisons
+        .iter()
+        .map(|comparison| {
+            // drop out of range responses together with their share of the
+            // vote, so the rest stay paired with their own shares
+            comparison
+                .responses
+                .iter()
+                .zip(&comparison.vote)
+                .map(|(&response, v)| {
+                    (response as usize, v.to_f64().unwrap_or(0.0).max(0.0))
+                })
+                .filter(|&(response, _)| response < responses_len)
+                .unzip::<_, _, Vec<_>, Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the total share of votes won by each response
+    let mut wins = vec![0.0; responses_len];
+    for (responses, vote) in &comparisons {
+        for (&response, v) in responses.iter().zip(vote) {
+            wins[response] += v;
+        }
+    }
+    if wins.iter().all(|w| *w <= 0.0) {
+        return vec![
+            Decimal::ONE / Decimal::from(responses_len.max(1));
+            responses_len
+        ];
+    }
+
+    let mut strengths = vec![1.0 / responses_len as f64; responses_len];
+    for _ in 0..MAX_ITERATIONS {
+        let mut denominators = vec![0.0; responses_len];
+        for (responses, vote) in &comparisons {
+            let total: f64 = vote.iter().sum();
+            let strength: f64 =
+                responses.iter().map(|&response| strengths[response]).sum();
+            if strength > 0.0 {
+                for &response in responses {
+                    denominators[response] += total / strength;
+                }
+            }
+        }
+        let mut next = wins
+            .iter()
+            .zip(&denominators)
+            .map(|(w, d)| if *w > 0.0 && *d > 0.0 { w / d } else { 0.0 })
+            .collect::<Vec<_>>();
+        let sum: f64 = next.iter().sum();
+        for s in &mut next {
+            *s /= sum;
+        }
+        let delta = next
+            .iter()
+            .zip(&strengths)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        strengths = next;
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+
+    strengths
+        .into_iter()
+        .map(|s| Decimal::from_f64(s).unwrap_or_default())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectiveai::vector::completions::response::VoteComparison;
+    use rust_decimal::dec;
+
+    fn comparison(i: u64, j: u64, vote: [Decimal; 2]) -> VoteComparison {
+        VoteComparison {
+            responses: vec![i, j],
+            vote: vote.to_vec(),
+            permutations: None,
+            rationale: None,
+        }
+    }
+
+    fn assert_close(actual: &[Decimal], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            let a = a.to_f64().unwrap();
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn single_pair_matches_vote() {
+        let scores =
+            bradley_terry(2, &[comparison(0, 1, [dec!(0.75), dec!(0.25)])]);
+        assert_close(&scores, &[0.75, 0.25]);
+    }
+
+    #[test]
+    fn consistent_preferences_are_ordered() {
+        let scores = bradley_terry(
+            3,
+            &[
+                comparison(0, 1, [dec!(0.8), dec!(0.2)]),
+                comparison(1, 2, [dec!(0.8), dec!(0.2)]),
+                comparison(0, 2, [dec!(0.9), dec!(0.1)]),
+            ],
+        );
+        assert!(scores[0] > scores[1] && scores[1] > scores[2]);
+        let sum: Decimal = scores.iter().sum();
+        assert!((sum - Decimal::ONE).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn balanced_preferences_are_uniform() {
+        let scores = bradley_terry(
+            3,
+            &[
+                comparison(0, 1, [dec!(1), dec!(0)]),
+                comparison(1, 2, [dec!(1), dec!(0)]),
+                comparison(2, 0, [dec!(1), dec!(0)]),
+            ],
+        );
+        assert_close(&scores, &[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn response_which_never_wins_scores_zero() {
+        let scores = bradley_terry(
+            3,
+            &[
+                comparison(0, 1, [dec!(0.5), dec!(0.5)]),
+                comparison(0, 2, [dec!(1), dec!(0)]),
+                comparison(1, 2, [dec!(1), dec!(0)]),
+            ],
+        );
+        assert_close(&scores, &[0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn out_of_range_responses_are_dropped_with_their_votes() {
+        let scores = bradley_terry(
+            3,
+            &[
+                comparison(0, 1, [dec!(0.6), dec!(0.4)]),
+                // response 5 does not exist; response 2 keeps its own share
+                comparison(5, 2, [dec!(0.9), dec!(0.1)]),
+                comparison(1, 2, [dec!(0.7), dec!(0.3)]),
+                comparison(0, 2, [dec!(0.8), dec!(0.2)]),
+            ],
+        );
+        let expected = bradley_terry(
+            3,
+            &[
+                comparison(0, 1, [dec!(0.6), dec!(0.4)]),
+                VoteComparison {
+                    responses: vec![2],
+                    vote: vec![dec!(0.1)],
+                    permutations: None,
+                    rationale: None,
+                },
+                comparison(1, 2, [dec!(0.7), dec!(0.3)]),
+                comparison(0, 2, [dec!(0.8), dec!(0.2)]),
+            ],
+        );
+        assert_eq!(scores, expected);
+        assert!(scores[0] > scores[1] && scores[1] > scores[2]);
+    }
+
+    #[test]
+    fn no_votes_is_uniform() {
+        assert_eq!(bradley_terry(2, &[]), vec![dec!(0.5), dec!(0.5)]);
+    }
+}
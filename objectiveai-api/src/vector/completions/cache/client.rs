@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 /// Client for retrieving cached votes.
 #[derive(Debug, Clone)]
-pub struct Client<CTXEXT, FVVOTE, FCVOTE> {
+pub struct Client<CTXEXT, FVVOTE: ?Sized, FCVOTE: ?Sized> {
     /// Fetcher for votes from historical completions.
     pub completion_votes_fetcher: Arc<FVVOTE>,
     /// Fetcher for votes from the global cache.
@@ -13,7 +13,7 @@ pub struct Client<CTXEXT, FVVOTE, FCVOTE> {
     _marker: std::marker::PhantomData<CTXEXT>,
 }
 
-impl<CTXEXT, FVVOTE, FCVOTE> Client<CTXEXT, FVVOTE, FCVOTE> {
+impl<CTXEXT, FVVOTE: ?Sized, FCVOTE: ?Sized> Client<CTXEXT, FVVOTE, FCVOTE> {
     /// Creates a new cache client.
     pub fn new(
         completion_votes_fetcher: Arc<FVVOTE>,
@@ -27,7 +27,7 @@ impl<CTXEXT, FVVOTE, FCVOTE> Client<CTXEXT, FVVOTE, FCVOTE> {
     }
 }
 
-impl<CTXEXT, FVVOTE, FCVOTE> Client<CTXEXT, FVVOTE, FCVOTE>
+impl<CTXEXT, FVVOTE: ?Sized, FCVOTE: ?Sized> Client<CTXEXT, FVVOTE, FCVOTE>
 where
     CTXEXT: Send + Sync + 'static,
     FVVOTE: vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
@@ -9,6 +9,9 @@ use rand::Rng;
 use rust_decimal::Decimal;
 use std::{collections::HashMap, sync::Arc, time};
 
+/// Maximum number of pairs each LLM compares at once in pairwise mode.
+const PAIRWISE_CONCURRENCY: usize = 8;
+
 /// Generates a unique response ID for a vector completion.
 pub fn response_id(created: u64) -> String {
     let uuid = uuid::Uuid::new_v4();
@@ -36,11 +39,308 @@ fn invert_and_l1_normalize(mut xs: Vec<Decimal>) -> Vec<Decimal> {
     xs
 }
 
+/// Combines the votes an LLM cast over multiple permutations or samples into
+/// their mean, recording every permutation and rationale. Returns None if
+/// there are no votes.
+fn mean_vote(
+    votes: Vec<objectiveai::vector::completions::response::Vote>,
+) -> Option<objectiveai::vector::completions::response::Vote> {
+    let mut votes = votes.into_iter();
+    let mut mean = votes.next()?;
+    let mut count = Decimal::ONE;
+    for vote in votes {
+        for (m, v) in mean.vote.iter_mut().zip(&vote.vote) {
+            *m += *v;
+        }
+        if let Some(permutations) = vote.permutations {
+            mean.permutations
+                .get_or_insert_with(Vec::new)
+                .extend(permutations);
+        }
+        if let Some(rationale) = vote.rationale {
+            match &mut mean.rationale {
+                Some(mean_rationale) => {
+                    mean_rationale.push_str("\n\n");
+                    mean_rationale.push_str(&rationale);
+                }
+                None => mean.rationale = Some(rationale),
+            }
+        }
+        count += Decimal::ONE;
+    }
+    for m in &mut mean.vote {
+        *m /= count;
+    }
+    Some(mean)
+}
+
+/// Fits an LLM's pairwise votes into a single vote over all
+/// `responses_len` responses, then calibrates and inverts it like a vote cast
+/// over all of the responses at once.
+///
+/// Calibrations are fit on votes over all of the responses, so they apply to
+/// the fitted vote rather than to each pairwise vote.
+fn pairwise_vote(
+    responses_len: usize,
+    comparisons: &[objectiveai::vector::completions::response::VoteComparison],
+    calibration: Option<&objectiveai::vector::completions::request::Calibration>,
+    invert_vote: bool,
+) -> Vec<Decimal> {
+    calibrate_vote(
+        super::bradley_terry(responses_len, comparisons),
+        calibration,
+        invert_vote,
+    )
+}
+
+/// Combines the votes an LLM cast over multiple permutations or samples into
+/// their mean (see [`mean_vote`]), then calibrates and inverts it like a vote
+/// cast over a single permutation. Returns None if there are no votes.
+///
+/// Calibrations are fit on an LLM's vote distribution, so they apply to the
+/// mean vote rather than to each one-hot sample or permutation vote.
+fn sampled_vote(
+    votes: Vec<objectiveai::vector::completions::response::Vote>,
+    calibration: Option<&objectiveai::vector::completions::request::Calibration>,
+    invert_vote: bool,
+) -> Option<objectiveai::vector::completions::response::Vote> {
+    let mut vote = mean_vote(votes)?;
+    vote.vote = calibrate_vote(vote.vote, calibration, invert_vote);
+    Some(vote)
+}
+
+/// Applies an LLM's calibration, if any, to its vote, then inverts it if the
+/// LLM's votes are inverted.
+fn calibrate_vote(
+    vote: Vec<Decimal>,
+    calibration: Option<&objectiveai::vector::completions::request::Calibration>,
+    invert_vote: bool,
+) -> Vec<Decimal> {
+    let vote = match calibration {
+        Some(calibration) => calibration.apply(&vote),
+        None => vote,
+    };
+    if invert_vote {
+        invert_and_l1_normalize(vote)
+    } else {
+        vote
+    }
+}
+
+/// A single LLM in the ensemble, with everything its completions and votes
+/// are created with. Built once per LLM and cloned for each of its
+/// completions.
+#[derive(Clone)]
+struct LlmSettings {
+    /// The vector completion's ID.
+    id: String,
+    /// The vector completion's creation timestamp.
+    created: u64,
+    /// The ID of the Ensemble.
+    ensemble: String,
+    /// Indexer of the vector completion's chat completions.
+    indexer: Arc<ChoiceIndexer>,
+    /// The LLM and its fallbacks.
+    llm: objectiveai::ensemble_llm::EnsembleLlmWithFallbacksAndCount,
+    /// Index of the LLM in the Ensemble.
+    ensemble_index: usize,
+    /// Index of the LLM in the Ensemble, with each LLM repeated `count`
+    /// times.
+    flat_ensemble_index: usize,
+    /// Weight of the LLM's votes.
+    weight: Decimal,
+    /// Whether the LLM's votes are inverted.
+    invert_vote: bool,
+    /// Calibration applied to the LLM's votes.
+    calibration: Option<objectiveai::vector::completions::request::Calibration>,
+    /// The ID of the prompt.
+    prompt_id: String,
+    /// The ID of the tools, if any.
+    tools_id: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rust_decimal::dec;
 
+    fn permutation_vote(
+        vote: Vec<Decimal>,
+        order: Vec<u64>,
+    ) -> objectiveai::vector::completions::response::Vote {
+        objectiveai::vector::completions::response::Vote {
+            model: "openai/gpt-4o".to_string(),
+            ensemble_index: 0,
+            flat_ensemble_index: 0,
+            prompt_id: String::new(),
+            tools_id: None,
+            responses_ids: Vec::new(),
+            vote,
+            weight: Decimal::ONE,
+            retry: None,
+            from_cache: None,
+            from_rng: None,
+            permutations: Some(vec![
+                objectiveai::vector::completions::response::VotePermutation {
+                    keys: order.iter().map(|i| format!("`{}`", i)).collect(),
+                    order,
+                },
+            ]),
+            samples: None,
+            comparisons: None,
+            rationale: None,
+            completion_index: Some(0),
+        }
+    }
+
+    #[test]
+    fn mean_vote_averages_permutations() {
+        let vote = mean_vote(vec![
+            permutation_vote(vec![dec!(1), dec!(0)], vec![0, 1]),
+            permutation_vote(vec![dec!(0), dec!(1)], vec![1, 0]),
+            permutation_vote(vec![dec!(1), dec!(0)], vec![0, 1]),
+            permutation_vote(vec![dec!(1), dec!(0)], vec![1, 0]),
+        ])
+        .unwrap();
+        assert_eq!(vote.vote, vec![dec!(0.75), dec!(0.25)]);
+        let permutations = vote.permutations.unwrap();
+        assert_eq!(permutations.len(), 4);
+        assert_eq!(permutations[1].order, vec![1, 0]);
+        assert_eq!(vote.completion_index, Some(0));
+    }
+
+    #[test]
+    fn mean_vote_of_one_hot_samples_is_empirical() {
+        let sample = |vote: Vec<Decimal>| {
+            objectiveai::vector::completions::response::Vote {
+                permutations: None,
+                ..permutation_vote(vote, Vec::new())
+            }
+        };
+        let vote = mean_vote(vec![
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+            sample(vec![dec!(0), dec!(0), dec!(1)]),
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+        ])
+        .unwrap();
+        assert_eq!(vote.vote, vec![dec!(0.75), dec!(0), dec!(0.25)]);
+        assert!(vote.permutations.is_none());
+    }
+
+    #[test]
+    fn mean_vote_joins_rationales() {
+        let sample = |vote: Vec<Decimal>, rationale: Option<&str>| {
+            objectiveai::vector::completions::response::Vote {
+                permutations: None,
+                rationale: rationale.map(str::to_string),
+                ..permutation_vote(vote, Vec::new())
+            }
+        };
+        let vote = mean_vote(vec![
+            sample(vec![dec!(1), dec!(0)], Some("A is correct.")),
+            sample(vec![dec!(1), dec!(0)], None),
+            sample(vec![dec!(0), dec!(1)], Some("B is correct.")),
+        ])
+        .unwrap();
+        assert_eq!(
+            vote.rationale.as_deref(),
+            Some("A is correct.\n\nB is correct.")
+        );
+    }
+
+    #[test]
+    fn mean_vote_of_nothing_is_none() {
+        assert!(mean_vote(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn sampled_vote_calibrates_the_mean_vote() {
+        let sample = |vote: Vec<Decimal>| {
+            objectiveai::vector::completions::response::Vote {
+                permutations: None,
+                ..permutation_vote(vote, Vec::new())
+            }
+        };
+        let samples = vec![
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+            sample(vec![dec!(0), dec!(0), dec!(1)]),
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+            sample(vec![dec!(1), dec!(0), dec!(0)]),
+        ];
+        let calibration =
+            objectiveai::vector::completions::request::Calibration::Temperature {
+                temperature: dec!(2),
+            };
+        let mean = mean_vote(samples.clone()).unwrap().vote;
+
+        let vote =
+            sampled_vote(samples.clone(), Some(&calibration), false).unwrap();
+        assert_eq!(vote.vote, calibration.apply(&mean));
+        assert!(vote.vote[0] < dec!(0.75) && vote.vote[2] > dec!(0.25));
+        // calibrating each one-hot sample instead leaves the mean unchanged
+        let calibrated_samples = samples
+            .iter()
+            .map(|vote| objectiveai::vector::completions::response::Vote {
+                vote: calibration.apply(&vote.vote),
+                ..vote.clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(mean_vote(calibrated_samples).unwrap().vote, mean);
+        assert_ne!(vote.vote, mean);
+
+        let inverted =
+            sampled_vote(samples.clone(), Some(&calibration), true).unwrap();
+        assert_eq!(
+            inverted.vote,
+            invert_and_l1_normalize(calibration.apply(&mean))
+        );
+        assert_eq!(sampled_vote(samples, None, false).unwrap().vote, mean);
+    }
+
+    #[test]
+    fn pairwise_vote_calibrates_the_fitted_vote() {
+        let comparison = |i: u64, j: u64, vote: [Decimal; 2]| {
+            objectiveai::vector::completions::response::VoteComparison {
+                responses: vec![i, j],
+                vote: vote.to_vec(),
+                permutations: None,
+                rationale: None,
+            }
+        };
+        let comparisons = [
+            comparison(0, 1, [dec!(0.7), dec!(0.3)]),
+            comparison(1, 2, [dec!(0.6), dec!(0.4)]),
+            comparison(0, 2, [dec!(0.9), dec!(0.1)]),
+        ];
+        let calibration =
+            objectiveai::vector::completions::request::Calibration::Temperature {
+                temperature: dec!(2),
+            };
+        let fitted = super::super::bradley_terry(3, &comparisons);
+
+        let vote = pairwise_vote(3, &comparisons, Some(&calibration), false);
+        assert_eq!(vote, calibration.apply(&fitted));
+        // calibrating each pairwise vote instead gives a different vote
+        let calibrated_pairs = comparisons
+            .iter()
+            .map(|comparison| {
+                objectiveai::vector::completions::response::VoteComparison {
+                    vote: calibration.apply(&comparison.vote),
+                    ..comparison.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(vote, super::super::bradley_terry(3, &calibrated_pairs));
+
+        let inverted = pairwise_vote(3, &comparisons, Some(&calibration), true);
+        assert_eq!(
+            inverted,
+            invert_and_l1_normalize(calibration.apply(&fitted))
+        );
+        assert_eq!(pairwise_vote(3, &comparisons, None, false), fitted);
+    }
+
     #[test]
     fn invert_and_l1_normalize_example() {
         let v = vec![dec!(0.75), dec!(0.25), dec!(0.0)];
@@ -61,7 +361,15 @@ mod tests {
 ///
 /// Orchestrates multiple LLM chat completions to vote on response options,
 /// combining their votes using weights to produce final scores.
-pub struct Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG> {
+pub struct Client<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+> {
     /// The underlying chat completion client.
     pub chat_client: Arc<chat::completions::Client<CTXEXT, FENSLLM, CUSG>>,
     /// Fetcher for Ensemble definitions.
@@ -75,7 +383,15 @@ pub struct Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG> {
     pub usage_handler: Arc<VUSG>,
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
 {
     /// Creates a new vector completion client.
@@ -98,7 +414,15 @@ impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
@@ -212,7 +536,15 @@ where
     }
 }
 
-impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
+impl<
+    CTXEXT,
+    FENSLLM: ?Sized,
+    CUSG: ?Sized,
+    FENS: ?Sized,
+    FVVOTE: ?Sized,
+    FCVOTE: ?Sized,
+    VUSG: ?Sized,
+>
     Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
 where
     CTXEXT: ctx::ContextExt + Send + Sync + 'static,
@@ -253,6 +585,35 @@ where
             .as_secs();
         let response_id = response_id(created);
 
+        // if rating, the values of the scale are the responses
+        let request = match request.rating {
+            Some(rating) => {
+                rating.validate().map_err(super::Error::InvalidRating)?;
+                if !request.responses.is_empty() {
+                    return Err(super::Error::InvalidRating(
+                        "`responses` must be empty when `rating` is set"
+                            .to_string(),
+                    ));
+                }
+                if let Some(
+                    objectiveai::vector::completions::request::ComparisonMode::Pairwise,
+                ) = request.comparison_mode
+                {
+                    return Err(super::Error::InvalidRating(
+                        "`rating` cannot be used with pairwise comparisons"
+                            .to_string(),
+                    ));
+                }
+                Arc::new(
+                    objectiveai::vector::completions::request::VectorCompletionCreateParams {
+                        responses: rating.responses(),
+                        ..(*request).clone()
+                    },
+                )
+            }
+            None => request,
+        };
+
         // validate response count
         let request_responses_len = request.responses.len();
         if request_responses_len < 2 {
@@ -260,6 +621,11 @@ where
                 request_responses_len,
             ));
         }
+        if let Some(comparison_mode) = request.comparison_mode {
+            comparison_mode
+                .validate(request_responses_len)
+                .map_err(super::Error::InvalidComparisonMode)?;
+        }
 
         // validate credits + fetch ensemble if needed + fetch retry votes if needed
         let (ensemble, mut static_votes, profile) = match (
@@ -368,6 +734,7 @@ where
         // normalize profile into (weight, invert) pairs
         let profile_pairs: Vec<(Decimal, bool)> =
             profile.to_weights_and_invert();
+        let calibrations = profile.calibrations();
 
         // validate profile
         if profile_pairs.len() != ensemble.llms.len() {
@@ -392,6 +759,9 @@ where
                 "profile must have two or more positive weights".to_string(),
             ));
         }
+        for calibration in calibrations.iter().flatten() {
+            calibration.validate().map_err(super::Error::InvalidProfile)?;
+        }
 
         // compute hash IDs
         let prompt_id = {
@@ -417,6 +787,13 @@ where
             responses_ids
         };
 
+        // completion indices of each permutation are keyed past this
+        let flat_ensemble_len = ensemble
+            .llms
+            .iter()
+            .map(|llm| llm.count as usize)
+            .sum::<usize>();
+
         // create a vector of LLMs with useful info
         // only ones that may stream
         let mut llms = ensemble
@@ -565,7 +942,10 @@ where
                 for v in &mut vote {
                     *v /= sum;
                 }
-                // optionally invert the vote based on the profile
+                // optionally calibrate and invert the vote based on the profile
+                if let Some(calibration) = &calibrations[*ensemble_index] {
+                    vote = calibration.apply(&vote);
+                }
                 if *invert {
                     vote = invert_and_l1_normalize(vote);
                 }
@@ -583,6 +963,10 @@ where
                         retry: None,
                         from_cache: None,
                         from_rng: Some(true),
+                        permutations: None,
+                        samples: None,
+                        comparisons: None,
+                        rationale: None,
                         completion_index: None,
                     },
                 );
@@ -614,30 +998,83 @@ where
         // completion chunk indices are first come first served
         let indexer = Arc::new(ChoiceIndexer::new(0));
 
+        // run the LLMs in waves if stopping early, otherwise all at once
+        let mut waves = std::collections::VecDeque::new();
+        match &request.early_stopping {
+            Some(early_stopping) => {
+                early_stopping
+                    .validate()
+                    .map_err(super::Error::InvalidEarlyStopping)?;
+                llms.sort_by_key(|llm| std::cmp::Reverse(llm.3));
+                let wave_size = early_stopping.wave_size() as usize;
+                while !llms.is_empty() {
+                    let rest = llms.split_off(wave_size.min(llms.len()));
+                    waves.push_back(std::mem::replace(&mut llms, rest));
+                }
+            }
+            None => waves.push_back(llms),
+        }
+
         // stream votes from each LLM in the ensemble
-        let mut vote_stream =
-            futures::stream::select_all(llms.into_iter().map(
-                |(flat_ensemble_index, ensemble_index, llm, weight, invert)| {
-                    futures::stream::once(self.clone().llm_create_streaming(
-                        ctx.clone(),
-                        response_id.clone(),
-                        created,
-                        ensemble.id.clone(),
-                        indexer.clone(),
-                        llm,
-                        ensemble_index,
-                        flat_ensemble_index,
-                        weight,
-                        invert,
-                        request.clone(),
-                        prompt_id.clone(),
-                        tools_id.clone(),
-                        responses_ids.clone(),
-                    ))
+        let llm_stream = {
+            let this = self.clone();
+            let ctx = ctx.clone();
+            let response_id = response_id.clone();
+            let ensemble_id = ensemble.id.clone();
+            let indexer = indexer.clone();
+            let request = request.clone();
+            let prompt_id = prompt_id.clone();
+            let tools_id = tools_id.clone();
+            let responses_ids = responses_ids.clone();
+            move |(flat_ensemble_index, ensemble_index, llm, weight, invert)| {
+                let settings = LlmSettings {
+                    id: response_id.clone(),
+                    created,
+                    ensemble: ensemble_id.clone(),
+                    indexer: indexer.clone(),
+                    llm,
+                    ensemble_index,
+                    flat_ensemble_index,
+                    weight,
+                    invert_vote: invert,
+                    calibration: calibrations[ensemble_index].clone(),
+                    prompt_id: prompt_id.clone(),
+                    tools_id: tools_id.clone(),
+                };
+                if let Some(
+                    objectiveai::vector::completions::request::ComparisonMode::Pairwise,
+                ) = request.comparison_mode
+                {
+                    futures::stream::once(
+                        this.clone().llm_create_streaming_pairwise(
+                            ctx.clone(),
+                            settings,
+                            flat_ensemble_len,
+                            request.clone(),
+                            responses_ids.clone(),
+                        ),
+                    )
                     .flatten()
                     .boxed()
-                },
-            ));
+                } else {
+                    futures::stream::once(
+                        this.clone().llm_create_streaming(
+                            ctx.clone(),
+                            settings,
+                            flat_ensemble_index,
+                            flat_ensemble_len,
+                            request.clone(),
+                            responses_ids.clone(),
+                        ),
+                    )
+                    .flatten()
+                    .boxed()
+                }
+            }
+        };
+        let mut vote_stream = futures::stream::select_all(
+            waves.pop_front().unwrap_or_default().into_iter().map(&llm_stream),
+        );
 
         // validate there is at least one retried vote
         if vote_stream.len() == 0 {
@@ -655,6 +1092,12 @@ where
                         *score = weights[i] / weight_sum;
                     }
                 }
+                let rating = request.rating.map(|scale| {
+                    objectiveai::vector::completions::response::Rating::from_scores(
+                        &scale,
+                        &scores,
+                    )
+                });
                 // return stream of existing votes
                 return Ok(futures::future::Either::Left(StreamOnce::new(
                     objectiveai::vector::completions::response::streaming::VectorCompletionChunk {
@@ -667,6 +1110,8 @@ where
                         ensemble: ensemble.id,
                         object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                         usage: None,
+                        skipped: None,
+                        rating,
                     }
                 )));
             } else {
@@ -683,12 +1128,13 @@ where
             }
         };
 
+        // votes cast so far, for deciding whether to stop early
+        let mut cast_votes: Vec<(Vec<Decimal>, Decimal)> = Vec::new();
+        let mut skipped = Vec::new();
+
         Ok(futures::future::Either::Right(async_stream::stream! {
             // stream all chunks
             while let Some(mut chunk) = next_chunk.take() {
-                // prepare next chunk
-                next_chunk = vote_stream.next().await;
-
                 // if retry votes were provided, add them to the first chunk
                 if static_votes.len() > 0 {
                     for vote in chunk.votes.drain(..) {
@@ -712,6 +1158,7 @@ where
                     for (i, v) in vote.vote.iter().enumerate() {
                         weights[i] += *v * vote.weight;
                     }
+                    cast_votes.push((vote.vote.clone(), vote.weight));
                 }
 
                 // update scores if votes were found
@@ -728,9 +1175,55 @@ where
                 chunk.weights = weights.clone();
                 chunk.scores = scores.clone();
 
-                // if on last chunk, add usage
+                // prepare next chunk, starting the next wave unless decided
+                next_chunk = vote_stream.next().await;
+                while next_chunk.is_none()
+                    && let Some(wave) = waves.pop_front()
+                {
+                    if let Some(early_stopping) = &request.early_stopping {
+                        let remaining_weight = wave
+                            .iter()
+                            .chain(waves.iter().flatten())
+                            .map(|(_, _, _, weight, _)| *weight)
+                            .sum();
+                        let votes = cast_votes
+                            .iter()
+                            .map(|(vote, weight)| (vote.as_slice(), *weight))
+                            .collect::<Vec<_>>();
+                        if super::is_decisive(
+                            early_stopping,
+                            &votes,
+                            remaining_weight,
+                        ) {
+                            skipped = std::iter::once(wave)
+                                .chain(waves.drain(..))
+                                .flatten()
+                                .map(|(flat_ensemble_index, _, _, _, _)| {
+                                    flat_ensemble_index as u64
+                                })
+                                .collect::<Vec<_>>();
+                            skipped.sort_unstable();
+                            break;
+                        }
+                    }
+                    vote_stream = futures::stream::select_all(
+                        wave.into_iter().map(&llm_stream),
+                    );
+                    next_chunk = vote_stream.next().await;
+                }
+
+                // if on last chunk, add usage, skipped LLMs, and rating
                 if next_chunk.is_none() {
                     chunk.usage = Some(usage.clone());
+                    if !skipped.is_empty() {
+                        chunk.skipped = Some(std::mem::take(&mut skipped));
+                    }
+                    chunk.rating = request.rating.map(|scale| {
+                        objectiveai::vector::completions::response::Rating::from_scores(
+                            &scale,
+                            &scores,
+                        )
+                    });
                 }
 
                 yield chunk;
@@ -738,29 +1231,257 @@ where
         }))
     }
 
+    /// Creates a streaming completion for each pair of responses, for a single
+    /// LLM in the ensemble.
+    ///
+    /// Yields the Bradley-Terry fit of the pairwise votes, calibrated and
+    /// inverted (see [`pairwise_vote`]), along with the uncalibrated pairwise
+    /// votes themselves, with the last chunk.
+    async fn llm_create_streaming_pairwise(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        settings: LlmSettings,
+        flat_ensemble_len: usize,
+        request: Arc<objectiveai::vector::completions::request::VectorCompletionCreateParams>,
+        responses_ids: Vec<String>,
+    ) -> impl Stream<Item = objectiveai::vector::completions::response::streaming::VectorCompletionChunk> + Send + 'static
+    {
+        let request_responses_len = request.responses.len();
+        let pairs = (0..request_responses_len)
+            .flat_map(|i| (i + 1..request_responses_len).map(move |j| (i, j)))
+            .collect::<Vec<_>>();
+        let pairs_len = pairs.len();
+
+        // calibrate and invert the fitted vote rather than each pairwise vote
+        let pair_settings = LlmSettings {
+            invert_vote: false,
+            calibration: None,
+            ..settings.clone()
+        };
+
+        // stream a completion for each pair, over a request with only the pair,
+        // with at most PAIRWISE_CONCURRENCY pairs in flight
+        let mut stream = futures::stream::iter(
+            pairs.into_iter().enumerate().map(|(pair_index, (i, j))| {
+                let pair_request = Arc::new(
+                    objectiveai::vector::completions::request::VectorCompletionCreateParams {
+                        responses: vec![
+                            request.responses[i].clone(),
+                            request.responses[j].clone(),
+                        ],
+                        comparison_mode: None,
+                        ..(*request).clone()
+                    },
+                );
+                futures::stream::once(self.clone().llm_create_streaming(
+                    ctx.clone(),
+                    pair_settings.clone(),
+                    pair_index * flat_ensemble_len
+                        + pair_settings.flat_ensemble_index,
+                    pairs_len * flat_ensemble_len,
+                    pair_request,
+                    vec![responses_ids[i].clone(), responses_ids[j].clone()],
+                ))
+                .flatten()
+                .map(move |chunk| ((i, j), chunk))
+                .boxed()
+            })
+            .collect::<Vec<_>>(),
+        )
+        .flatten_unordered(PAIRWISE_CONCURRENCY);
+
+        async_stream::stream! {
+            let mut first_vote = None;
+            let mut comparisons = Vec::with_capacity(pairs_len);
+            let mut next_chunk = stream.next().await;
+            while let Some(((i, j), mut chunk)) = next_chunk.take() {
+                next_chunk = stream.next().await;
+
+                // hold pairwise votes back until every pair has been voted on
+                for vote in chunk.votes.drain(..) {
+                    let pair = [i as u64, j as u64];
+                    comparisons.push(
+                        objectiveai::vector::completions::response::VoteComparison {
+                            responses: pair.to_vec(),
+                            vote: vote.vote.clone(),
+                            permutations: vote.permutations.clone().map(
+                                |permutations| {
+                                    permutations
+                                        .into_iter()
+                                        .map(|mut permutation| {
+                                            for index in &mut permutation.order {
+                                                *index = pair[*index as usize];
+                                            }
+                                            permutation
+                                        })
+                                        .collect()
+                                },
+                            ),
+                            rationale: vote.rationale.clone(),
+                        },
+                    );
+                    first_vote.get_or_insert(vote);
+                }
+
+                // fit the pairwise votes into a single vote
+                if next_chunk.is_none()
+                    && let Some(first_vote) = first_vote.take()
+                {
+                    chunk.votes.push(objectiveai::vector::completions::response::Vote {
+                        responses_ids: responses_ids.clone(),
+                        vote: pairwise_vote(
+                            request_responses_len,
+                            &comparisons,
+                            settings.calibration.as_ref(),
+                            settings.invert_vote,
+                        ),
+                        permutations: None,
+                        comparisons: Some(std::mem::take(&mut comparisons)),
+                        rationale: None,
+                        ..first_vote
+                    });
+                }
+
+                yield chunk;
+            }
+        }
+    }
+
     /// Creates a streaming completion for a single LLM in the ensemble.
     ///
-    /// Generates prefix data for vote extraction, streams the chat completion,
-    /// and extracts votes from the LLM's response.
+    /// If the LLM votes over multiple permutations or samples, streams one
+    /// completion per sample of each permutation and yields the mean of their
+    /// votes, calibrated and inverted (see [`sampled_vote`]), with the last
+    /// chunk. Each completion is indexed by `indexer_key`
+    /// offset by a multiple of `indexer_stride`.
     async fn llm_create_streaming(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
-        id: String,
-        created: u64,
-        ensemble: String,
-        indexer: Arc<ChoiceIndexer>,
-        llm: objectiveai::ensemble_llm::EnsembleLlmWithFallbacksAndCount,
-        ensemble_index: usize,
-        flat_ensemble_index: usize,
-        weight: Decimal,
-        invert_vote: bool,
+        settings: LlmSettings,
+        indexer_key: usize,
+        indexer_stride: usize,
         request: Arc<objectiveai::vector::completions::request::VectorCompletionCreateParams>,
-        prompt_id: String,
-        tools_id: Option<String>,
         responses_ids: Vec<String>,
     ) -> impl Stream<Item = objectiveai::vector::completions::response::streaming::VectorCompletionChunk> + Send + 'static
     {
+        let permutations = match settings.llm.inner.base.permutations {
+            Some(0) | Some(1) | None => 1,
+            Some(permutations) => permutations as usize,
+        };
+        let samples = match settings.llm.inner.base.samples {
+            Some(0) | Some(1) | None => 1,
+            Some(samples) => samples as usize,
+        };
+        if permutations == 1 && samples == 1 {
+            return futures::future::Either::Left(
+                self.llm_create_streaming_permutation(
+                    ctx,
+                    settings,
+                    indexer_key,
+                    request,
+                    responses_ids,
+                    false,
+                )
+                .await,
+            );
+        }
+
+        // calibrate and invert the mean vote rather than each vote
+        let run_settings = LlmSettings {
+            invert_vote: false,
+            calibration: None,
+            ..settings.clone()
+        };
+
+        // stream a completion for each sample of each permutation
+        let mut stream = futures::stream::select_all(
+            (0..permutations * samples).map(|run| {
+                // vary the seed across samples
+                let sample = run / permutations;
+                let request = match request.seed {
+                    Some(seed) if sample > 0 => Arc::new(
+                        objectiveai::vector::completions::request::VectorCompletionCreateParams {
+                            seed: Some(seed.wrapping_add(sample as i64)),
+                            ..(*request).clone()
+                        },
+                    ),
+                    _ => request.clone(),
+                };
+                futures::stream::once(
+                    self.clone().llm_create_streaming_permutation(
+                        ctx.clone(),
+                        run_settings.clone(),
+                        indexer_key + run * indexer_stride,
+                        request,
+                        responses_ids.clone(),
+                        permutations > 1,
+                    ),
+                )
+                .flatten()
+                .boxed()
+            }),
+        );
+
+        futures::future::Either::Right(async_stream::stream! {
+            let mut votes = Vec::with_capacity(permutations * samples);
+            let mut next_chunk = stream.next().await;
+            while let Some(mut chunk) = next_chunk.take() {
+                next_chunk = stream.next().await;
+
+                // hold votes back until every completion has voted
+                votes.append(&mut chunk.votes);
+                if next_chunk.is_none() {
+                    let votes_len = votes.len() as u64;
+                    if let Some(mut vote) = sampled_vote(
+                        std::mem::take(&mut votes),
+                        settings.calibration.as_ref(),
+                        settings.invert_vote,
+                    ) {
+                        if samples > 1 {
+                            vote.samples = Some(votes_len);
+                        }
+                        chunk.votes.push(vote);
+                    }
+                }
+
+                yield chunk;
+            }
+        })
+    }
+
+    /// Creates a streaming completion for a single permutation of the
+    /// responses, for a single LLM in the ensemble.
+    ///
+    /// Generates prefix data for vote extraction, streams the chat completion,
+    /// and extracts votes from the LLM's response. Completion indices are
+    /// keyed by `indexer_key`. If `record_permutation` is true, each vote
+    /// records the permutation it was cast over.
+    async fn llm_create_streaming_permutation(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        settings: LlmSettings,
+        indexer_key: usize,
+        request: Arc<objectiveai::vector::completions::request::VectorCompletionCreateParams>,
+        responses_ids: Vec<String>,
+        record_permutation: bool,
+    ) -> impl Stream<Item = objectiveai::vector::completions::response::streaming::VectorCompletionChunk> + Send + 'static
+    {
+        let LlmSettings {
+            id,
+            created,
+            ensemble,
+            indexer,
+            llm,
+            ensemble_index,
+            flat_ensemble_index,
+            weight,
+            invert_vote,
+            calibration,
+            prompt_id,
+            tools_id,
+        } = settings;
         let request_responses_len = request.responses.len();
+        let include_rationales = request.include_rationales.unwrap_or(false);
 
         // create pfx data for each LLM
         let (vector_pfx_data, vector_pfx_indices) = {
@@ -778,36 +1499,40 @@ where
                     .flatten(),
             ) {
                 // create the prefixes
+                let pfx_labels = super::PfxLabels::new(&llm.base);
                 let pfx_tree = super::PfxTree::new(
                     &mut rng,
                     request_responses_len,
-                    match llm.base.top_logprobs {
-                        Some(0) | Some(1) | None => 20,
-                        Some(top_logprobs) => top_logprobs as usize,
-                    },
+                    &pfx_labels,
                 );
 
                 // map prefix to response index
-                let pfx_indices =
-                    pfx_tree.pfx_indices(&mut rng, request_responses_len);
+                let pfx_indices = Arc::new(pfx_tree.pfx_indices(
+                    &mut rng,
+                    &pfx_labels,
+                    request_responses_len,
+                ));
 
                 let (
                     // regex capture pattern matching response keys as-is
                     responses_key_pattern,
-                    // regex capture pattern matching response keys stripped of first and last tick
+                    // regex capture pattern matching response keys stripped of first and last delimiter
                     responses_key_pattern_stripped,
-                ) = pfx_tree.regex_patterns(&pfx_indices);
+                ) = pfx_tree.regex_patterns(&pfx_labels, &pfx_indices);
 
                 vector_pfx_data.insert(
                     llm.id.clone(),
                     super::PfxData {
                         pfx_tree,
+                        pfx_labels,
+                        pfx_indices: pfx_indices.clone(),
                         responses_key_pattern,
                         responses_key_pattern_stripped,
                         invert_vote,
+                        calibration: calibration.clone(),
                     },
                 );
-                vector_pfx_indices.push(Arc::new(pfx_indices));
+                vector_pfx_indices.push(pfx_indices);
             }
             (vector_pfx_data, vector_pfx_indices)
         };
@@ -829,7 +1554,7 @@ where
                 return futures::future::Either::Left(
                     Self::llm_create_streaming_vector_error(
                         id,
-                        indexer.get(flat_ensemble_index),
+                        indexer.get(indexer_key),
                         e,
                         created,
                         ensemble,
@@ -845,7 +1570,7 @@ where
                 return futures::future::Either::Left(
                     Self::llm_create_streaming_vector_error(
                         id,
-                        indexer.get(flat_ensemble_index),
+                        indexer.get(indexer_key),
                         e,
                         created,
                         ensemble,
@@ -888,7 +1613,7 @@ where
                     id: id.clone(),
                     completions: vec![
                         objectiveai::vector::completions::response::streaming::ChatCompletionChunk {
-                            index: indexer.get(flat_ensemble_index),
+                            index: indexer.get(indexer_key),
                             inner: chat_chunk,
                             error,
                         },
@@ -900,6 +1625,8 @@ where
                     ensemble: ensemble.clone(),
                     object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                     usage: None,
+                    skipped: None,
+                    rating: None,
                 };
 
                 // push the chunk into the aggregate
@@ -919,20 +1646,28 @@ where
                         // get pfx data for this LLM
                         let super::PfxData {
                             pfx_tree,
+                            pfx_labels,
+                            pfx_indices,
                             responses_key_pattern,
                             responses_key_pattern_stripped,
                             invert_vote,
+                            calibration,
                         } = &vector_pfx_data[&completion.inner.model];
 
                         // try to get votes for each choice
                         for choice in completion.inner.choices {
                             if let Some(vote) = super::get_vote(
-                                pfx_tree.clone(),
+                                pfx_tree,
+                                pfx_labels,
                                 &responses_key_pattern,
                                 &responses_key_pattern_stripped,
                                 request_responses_len,
                                 &choice,
                             ) {
+                                let vote = match calibration {
+                                    Some(calibration) => calibration.apply(&vote),
+                                    None => vote,
+                                };
                                 let vote = if *invert_vote {
                                     invert_and_l1_normalize(vote)
                                 } else {
@@ -950,6 +1685,18 @@ where
                                     retry: None,
                                     from_cache: None,
                                     from_rng: None,
+                                    permutations: if record_permutation {
+                                        Some(vec![objectiveai::vector::completions::response::VotePermutation::from_pfx_indices(pfx_indices)])
+                                    } else {
+                                        None
+                                    },
+                                    samples: None,
+                                    comparisons: None,
+                                    rationale: if include_rationales {
+                                        super::ResponseKey::rationale(&choice)
+                                    } else {
+                                        None
+                                    },
                                     completion_index: Some(completion.index),
                                 });
                             }
@@ -992,6 +1739,8 @@ where
                 ensemble,
                 object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                 usage: None,
+                skipped: None,
+                rating: None,
             }
         )
     }
@@ -0,0 +1,133 @@
+//! Decision rule for adaptive early stopping of ensemble voting.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Returns true if the outcome of the votes cast so far is decided, such
+/// that the LLMs with `remaining_weight` in total need not be run.
+///
+/// See [`objectiveai::vector::completions::request::EarlyStopping`].
+pub fn is_decisive(
+    early_stopping: &objectiveai::vector::completions::request::EarlyStopping,
+    votes: &[(&[Decimal], Decimal)],
+    remaining_weight: Decimal,
+) -> bool {
+    let Some(responses_len) = votes.iter().map(|(vote, _)| vote.len()).max()
+    else {
+        return false;
+    };
+    if responses_len < 2 {
+        return false;
+    }
+
+    // total weight of each response
+    let mut weights = vec![Decimal::ZERO; responses_len];
+    for (vote, weight) in votes {
+        for (w, v) in weights.iter_mut().zip(vote.iter()) {
+            *w += *v * *weight;
+        }
+    }
+    let mut ranked = (0..responses_len).collect::<Vec<_>>();
+    ranked.sort_by_key(|i| std::cmp::Reverse(weights[*i]));
+    let (leader, runner_up) = (ranked[0], ranked[1]);
+
+    // the remaining LLMs cannot overturn the leader
+    if weights[leader] - weights[runner_up] > remaining_weight {
+        return true;
+    }
+
+    if (votes.len() as u64) < early_stopping.min_votes() {
+        return false;
+    }
+
+    // weighted mean and standard error of the margin across votes
+    let margins = votes
+        .iter()
+        .map(|(vote, weight)| {
+            let share =
+                |i: usize| vote.get(i).and_then(|v| v.to_f64()).unwrap_or(0.0);
+            (
+                share(leader) - share(runner_up),
+                weight.to_f64().unwrap_or(0.0),
+            )
+        })
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect::<Vec<_>>();
+    let total_weight: f64 = margins.iter().map(|(_, w)| w).sum();
+    if total_weight <= 0.0 {
+        return false;
+    }
+    let mean = margins.iter().map(|(m, w)| m * w).sum::<f64>() / total_weight;
+    let variance = margins
+        .iter()
+        .map(|(m, w)| w * (m - mean).powi(2))
+        .sum::<f64>()
+        / total_weight;
+    let effective_len =
+        total_weight.powi(2) / margins.iter().map(|(_, w)| w * w).sum::<f64>();
+    let standard_error = (variance / effective_len).sqrt();
+    let z =
+        normal_quantile(early_stopping.confidence().to_f64().unwrap_or(0.95));
+    mean - z * standard_error > 0.0
+}
+
+/// Approximate quantile of the standard normal distribution, for `p` in
+/// (0.5, 1). Abramowitz and Stegun 26.2.23, accurate to 4.5e-4.
+fn normal_quantile(p: f64) -> f64 {
+    let q = 1.0 - p.clamp(0.5, 1.0 - 1e-12);
+    let t = (-2.0 * q.ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectiveai::vector::completions::request::EarlyStopping;
+    use rust_decimal::dec;
+
+    fn decisive(votes: &[[Decimal; 2]], remaining_weight: Decimal) -> bool {
+        let votes = votes
+            .iter()
+            .map(|vote| (vote.as_slice(), Decimal::ONE))
+            .collect::<Vec<_>>();
+        is_decisive(&EarlyStopping::default(), &votes, remaining_weight)
+    }
+
+    #[test]
+    fn normal_quantile_is_close() {
+        assert!((normal_quantile(0.95) - 1.645).abs() < 1e-3);
+        assert!((normal_quantile(0.975) - 1.960).abs() < 1e-3);
+    }
+
+    #[test]
+    fn decisive_when_remaining_weight_cannot_overturn() {
+        let votes = [[dec!(1), dec!(0)], [dec!(1), dec!(0)]];
+        assert!(decisive(&votes, dec!(1.5)));
+        assert!(!decisive(&votes, dec!(2)));
+    }
+
+    #[test]
+    fn decisive_when_unanimous_with_enough_votes() {
+        let votes = [[dec!(1), dec!(0)]; 4];
+        assert!(decisive(&votes, dec!(100)));
+        assert!(!decisive(&votes[..3], dec!(100)));
+    }
+
+    #[test]
+    fn not_decisive_when_split() {
+        let votes = [
+            [dec!(1), dec!(0)],
+            [dec!(0), dec!(1)],
+            [dec!(1), dec!(0)],
+            [dec!(0), dec!(1)],
+            [dec!(1), dec!(0)],
+        ];
+        assert!(!decisive(&votes, dec!(100)));
+    }
+
+    #[test]
+    fn not_decisive_without_votes() {
+        assert!(!decisive(&[], dec!(0)));
+    }
+}
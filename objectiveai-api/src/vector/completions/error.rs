@@ -27,6 +27,15 @@ pub enum Error {
     /// Vector completions require at least two response options.
     #[error("expected two or more request vector responses, got {0}")]
     ExpectedTwoOrMoreRequestVectorResponses(usize),
+    /// The early stopping configuration is invalid.
+    #[error("invalid early stopping: {0}")]
+    InvalidEarlyStopping(String),
+    /// The rating scale is invalid.
+    #[error("invalid rating: {0}")]
+    InvalidRating(String),
+    /// The comparison mode is invalid for the request.
+    #[error("invalid comparison mode: {0}")]
+    InvalidComparisonMode(String),
 }
 
 impl objectiveai::error::StatusError for Error {
@@ -40,6 +49,9 @@ impl objectiveai::error::StatusError for Error {
             Error::EnsembleNotFound => 404,
             Error::InvalidEnsemble(_) => 400,
             Error::ExpectedTwoOrMoreRequestVectorResponses(_) => 400,
+            Error::InvalidEarlyStopping(_) => 400,
+            Error::InvalidRating(_) => 400,
+            Error::InvalidComparisonMode(_) => 400,
         }
     }
 
@@ -79,6 +91,18 @@ impl objectiveai::error::StatusError for Error {
                     "kind": "expected_two_or_more_request_vector_responses",
                     "error": format!("expected two or more request vector responses, got {}", n),
                 }),
+                Error::InvalidEarlyStopping(msg) => serde_json::json!({
+                    "kind": "invalid_early_stopping",
+                    "error": msg,
+                }),
+                Error::InvalidRating(msg) => serde_json::json!({
+                    "kind": "invalid_rating",
+                    "error": msg,
+                }),
+                Error::InvalidComparisonMode(msg) => serde_json::json!({
+                    "kind": "invalid_comparison_mode",
+                    "error": msg,
+                }),
             }
         }))
     }
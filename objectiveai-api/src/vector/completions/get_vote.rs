@@ -15,9 +15,10 @@ use rust_decimal::MathematicalOps;
 ///
 /// Returns None if no response key is found in the content.
 pub fn get_vote(
-    mut pfx_tree: super::PfxTree,
-    with_ticks_pattern: &str,
-    without_ticks_pattern: &str,
+    pfx_tree: &super::PfxTree,
+    pfx_labels: &super::PfxLabels,
+    key_pattern: &str,
+    key_pattern_stripped: &str,
     responses_len: usize,
     choice: &objectiveai::chat::completions::response::streaming::Choice,
 ) -> Option<Vec<rust_decimal::Decimal>> {
@@ -31,14 +32,14 @@ pub fn get_vote(
     let content = content_owned.as_str();
 
     // extract response keys, return if not found
-    let with_ticks_re = Regex::new(with_ticks_pattern).unwrap();
-    let mut key_matches = with_ticks_re.find_iter(content).collect::<Vec<_>>();
-    let without_ticks_re = match key_matches.len() {
-        0 => Some(Regex::new(without_ticks_pattern).unwrap()),
+    let key_re = Regex::new(key_pattern).unwrap();
+    let mut key_matches = key_re.find_iter(content).collect::<Vec<_>>();
+    let key_stripped_re = match key_matches.len() {
+        0 => Some(Regex::new(key_pattern_stripped).unwrap()),
         _ => None,
     };
-    if let Some(without_ticks_re) = without_ticks_re.as_ref() {
-        key_matches = without_ticks_re.find_iter(content).collect::<Vec<_>>();
+    if let Some(key_stripped_re) = key_stripped_re.as_ref() {
+        key_matches = key_stripped_re.find_iter(content).collect::<Vec<_>>();
     }
 
     // return None if no keys found
@@ -50,11 +51,19 @@ pub fn get_vote(
     let key_matches_len_decimal =
         rust_decimal::Decimal::from(key_matches.len());
 
-    // reverse matches for processing
+    // reverse matches for processing, restoring stripped delimiters
     let keys_rev = key_matches
         .into_iter()
         .rev()
-        .map(|cap| cap.as_str())
+        .map(|cap| match key_stripped_re {
+            Some(_) => format!(
+                "{}{}{}",
+                pfx_labels.wrap.open(),
+                cap.as_str(),
+                pfx_labels.wrap.close()
+            ),
+            None => cap.as_str().to_string(),
+        })
         .collect::<Vec<_>>();
 
     // prepare vote
@@ -64,34 +73,24 @@ pub fn get_vote(
     let mut logprob_i = 0;
 
     for key in keys_rev {
-        // get the final prefix
-        let (final_pfx_char, final_pfx) = key
-            .chars()
-            .rev()
-            .map(|c| (c, super::Pfx::from_char(c)))
-            .filter(|(_, pfx)| pfx.is_some())
-            .next()
-            .unwrap();
-        let final_pfx = final_pfx.unwrap();
+        // split the key into its labels
+        let pfxs = pfx_labels.split(&key).unwrap();
+        let (final_pfx, parent_pfxs) = pfxs.split_last().unwrap();
 
         // get to the lowest pfx tree branch
-        let mut i = pfx_tree.depth() - 1;
-        if i > 0 {
-            for c in key.chars() {
-                if let Some(pfx) = super::Pfx::from_char(c) {
-                    pfx_tree = pfx_tree.get(pfx).unwrap();
-                    i -= 1;
-                    if i == 0 {
-                        break;
-                    }
-                }
-            }
+        let mut branch = pfx_tree.clone();
+        for pfx in parent_pfxs {
+            branch = branch.get(pfx).unwrap();
         }
-        let pfx_tree = match pfx_tree.clone() {
+        let branch = match branch {
             super::PfxTree::Branch(branch) => branch,
             super::PfxTree::Leaf(_) => unreachable!(),
         };
 
+        // the final label starts this many bytes from the end of the key
+        let final_pfx_rev_offset =
+            final_pfx.len() + pfx_labels.wrap.close().len();
+
         // try to get probabilities from logprobs
         let mut from_logprobs = false;
         if let Some(objectiveai::chat::completions::response::Logprobs {
@@ -119,8 +118,11 @@ pub fn get_vote(
                         // match
                         // remove the matched char from the slice
                         key_rev_slice = &key_rev_slice[c.len_utf8()..];
-                        // keep the logprob that contains the final pfx
-                        if key_logprob.is_none() && c == final_pfx_char {
+                        // keep the logprob where the final label starts
+                        if key_logprob.is_none()
+                            && key_rev.len() - key_rev_slice.len()
+                                == final_pfx_rev_offset
+                        {
                             key_logprob = Some(logprob);
                             key_logprob_index = i;
                         }
@@ -143,6 +145,7 @@ pub fn get_vote(
             // matching logprob segment found
             if key_rev_slice.is_empty() {
                 // collect probabilities
+                // labels split across tokens match no alternatives, and fall back
                 let mut probabilities =
                     vec![rust_decimal::Decimal::ZERO; responses_len];
                 let mut probabilities_sum = rust_decimal::Decimal::ZERO;
@@ -152,13 +155,11 @@ pub fn get_vote(
                     ..
                 } in &key_logprob.as_ref().unwrap().top_logprobs
                 {
-                    if key_logprob_index < token.len()
-                        && let Some(logprob) = logprob
-                        && let Some((_, c)) = token
-                            .char_indices()
-                            .find(|(i, _)| *i == key_logprob_index)
-                        && let Some(pfx) = super::Pfx::from_char(c)
-                        && let Some(leaf) = pfx_tree.get(&pfx)
+                    if let Some(logprob) = logprob
+                        && let Some(pfx) = token
+                            .get(key_logprob_index..)
+                            .and_then(|rest| rest.get(..final_pfx.len()))
+                        && let Some(leaf) = branch.get(pfx)
                     {
                         // logprobs sourced vote successful
                         from_logprobs = true;
@@ -185,7 +186,7 @@ pub fn get_vote(
 
         // fallback, set vote indexed to selected response to 1.0
         if !from_logprobs {
-            vote[pfx_tree.get(&final_pfx).unwrap().unwrap_leaf()] =
+            vote[branch.get(*final_pfx).unwrap().unwrap_leaf()] =
                 rust_decimal::Decimal::ONE / key_matches_len_decimal;
         }
     }
@@ -264,3 +265,141 @@ impl<'s> Content<'s> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::completions::{PfxLabels, PfxTree};
+    use objectiveai::chat::completions::response::{
+        Logprob, Logprobs, TopLogprob, streaming::Choice,
+    };
+    use objectiveai::ensemble_llm::{LabelAlphabet, LabelWrap};
+    use rust_decimal::{Decimal, dec};
+
+    fn choice(content: &str, logprobs: Option<Vec<Logprob>>) -> Choice {
+        Choice {
+            delta: objectiveai::chat::completions::response::streaming::Delta {
+                content: Some(content.to_string()),
+                ..Default::default()
+            },
+            finish_reason: None,
+            index: 0,
+            logprobs: logprobs.map(|content| Logprobs {
+                content: Some(content),
+                refusal: None,
+            }),
+        }
+    }
+
+    fn logprob(token: &str, top: &[(&str, Decimal)]) -> Logprob {
+        Logprob {
+            token: token.to_string(),
+            bytes: None,
+            logprob: Decimal::ZERO,
+            top_logprobs: top
+                .iter()
+                .map(|(token, logprob)| TopLogprob {
+                    token: token.to_string(),
+                    bytes: None,
+                    logprob: Some(*logprob),
+                })
+                .collect(),
+        }
+    }
+
+    struct Setup {
+        tree: PfxTree,
+        labels: PfxLabels,
+        keys: Vec<(String, usize)>,
+        patterns: (String, String),
+    }
+
+    fn setup(alphabet: LabelAlphabet, wrap: LabelWrap, len: usize) -> Setup {
+        let mut rng = rand::rng();
+        let labels = PfxLabels {
+            alphabet: alphabet.labels(),
+            wrap,
+            width: 20.min(alphabet.count()),
+        };
+        let tree = PfxTree::new(&mut rng, len, &labels);
+        let keys = tree.pfx_indices(&mut rng, &labels, len);
+        let patterns = tree.regex_patterns(&labels, &keys);
+        Setup {
+            tree,
+            labels,
+            keys,
+            patterns,
+        }
+    }
+
+    fn vote(setup: &Setup, choice: &Choice) -> Option<Vec<Decimal>> {
+        get_vote(
+            &setup.tree,
+            &setup.labels,
+            &setup.patterns.0,
+            &setup.patterns.1,
+            setup.keys.len(),
+            choice,
+        )
+    }
+
+    #[test]
+    fn discrete_vote_without_logprobs() {
+        let setup = setup(LabelAlphabet::Digits, LabelWrap::Bracket, 3);
+        let (key, index) = &setup.keys[1];
+        let vote = vote(&setup, &choice(&format!("Answer: {key}"), None));
+        let mut expected = vec![Decimal::ZERO; 3];
+        expected[*index] = Decimal::ONE;
+        assert_eq!(vote.unwrap(), expected);
+    }
+
+    #[test]
+    fn stripped_key_is_matched() {
+        let setup = setup(LabelAlphabet::Letters, LabelWrap::Parenthesis, 2);
+        let (key, index) = &setup.keys[0];
+        let vote = vote(&setup, &choice(&key[1..key.len() - 1], None));
+        assert_eq!(vote.unwrap()[*index], Decimal::ONE);
+    }
+
+    #[test]
+    fn no_key_is_no_vote() {
+        let setup = setup(LabelAlphabet::Letters, LabelWrap::Backtick, 2);
+        assert!(vote(&setup, &choice("no idea.", None)).is_none());
+    }
+
+    #[test]
+    fn logprobs_vote_with_letter_pairs() {
+        let setup = setup(LabelAlphabet::LetterPairs, LabelWrap::Backtick, 2);
+        let (key_a, index_a) = &setup.keys[0];
+        let (key_b, index_b) = &setup.keys[1];
+        let pfx_a = &key_a[1..3];
+        let pfx_b = &key_b[1..3];
+        let logprobs = vec![
+            logprob("`", &[]),
+            logprob(
+                pfx_a,
+                &[
+                    (pfx_a, dec!(-0.2876820724517809)),
+                    (pfx_b, dec!(-1.3862943611198906)),
+                ],
+            ),
+            logprob("`", &[]),
+        ];
+        let vote = vote(&setup, &choice(key_a, Some(logprobs))).unwrap();
+        assert_eq!(vote[*index_a].round_dp(2), dec!(0.75));
+        assert_eq!(vote[*index_b].round_dp(2), dec!(0.25));
+    }
+
+    #[test]
+    fn label_split_across_tokens_falls_back_to_discrete() {
+        let setup = setup(LabelAlphabet::LetterPairs, LabelWrap::Backtick, 2);
+        let (key, index) = &setup.keys[0];
+        let logprobs = vec![
+            logprob("`", &[]),
+            logprob(&key[1..2], &[(&key[1..2], dec!(-0.1))]),
+            logprob(&key[2..], &[]),
+        ];
+        let vote = vote(&setup, &choice(key, Some(logprobs))).unwrap();
+        assert_eq!(vote[*index], Decimal::ONE);
+    }
+}
@@ -5,9 +5,11 @@
 
 /// Vote caching client for the global ObjectiveAI cache.
 pub mod cache;
+mod bradley_terry;
 /// Fetcher for retrieving votes from the global cache.
 pub mod cache_vote_fetcher;
 mod client;
+mod early_stopping;
 /// Fetcher for retrieving votes from historical completions.
 pub mod completion_votes_fetcher;
 mod error;
@@ -19,7 +21,9 @@ pub mod usage_handler;
 /// Vector response transformation utilities.
 pub mod vector_responses;
 
+pub use bradley_terry::*;
 pub use client::*;
+pub use early_stopping::*;
 pub use error::*;
 pub use get_vote::*;
 pub use pfx::*;
@@ -1,11 +1,12 @@
 //! Prefix tree for response key generation.
 //!
 //! Generates unique prefix keys (e.g., `` `A` ``, `` `B` ``) for labeling vector responses.
-//! The LLM sees these keys and responds with its choice.
+//! The LLM sees these keys and responds with its choice. The label alphabet, the
+//! delimiters, and the branch width are configurable per Ensemble LLM (see [`PfxLabels`]).
 //!
 //! The tree structure is designed around logprobs for probabilistic voting. Instead of
 //! relying on the LLM's final sampled answer, we use logprobs to capture a probability
-//! distribution over responses. The leaf width matches the number of logprobs the LLM
+//! distribution over responses. The leaf width should match the number of logprobs the LLM
 //! generates (e.g., 20 logprobs = 20 leaves per branch). For large response sets, nested
 //! structures (`` `A` `` `` `A` ``, `` `A` `` `` `B` ``) allow capturing preferences across
 //! more responses than a single logprobs batch allows.
@@ -18,117 +19,59 @@ use indexmap::IndexMap;
 use rand::{Rng, seq::SliceRandom};
 use std::sync::Arc;
 
-/// Single-character prefix labels A-T.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Pfx {
-    A,
-    B,
-    C,
-    D,
-    E,
-    F,
-    G,
-    H,
-    I,
-    J,
-    K,
-    L,
-    M,
-    N,
-    O,
-    P,
-    Q,
-    R,
-    S,
-    T,
-}
+/// A single label of a response key (e.g. `A`).
+pub type Pfx = &'static str;
 
-impl Pfx {
-    /// Converts this prefix to its character representation.
-    pub fn to_char(&self) -> char {
-        match self {
-            Pfx::A => 'A',
-            Pfx::B => 'B',
-            Pfx::C => 'C',
-            Pfx::D => 'D',
-            Pfx::E => 'E',
-            Pfx::F => 'F',
-            Pfx::G => 'G',
-            Pfx::H => 'H',
-            Pfx::I => 'I',
-            Pfx::J => 'J',
-            Pfx::K => 'K',
-            Pfx::L => 'L',
-            Pfx::M => 'M',
-            Pfx::N => 'N',
-            Pfx::O => 'O',
-            Pfx::P => 'P',
-            Pfx::Q => 'Q',
-            Pfx::R => 'R',
-            Pfx::S => 'S',
-            Pfx::T => 'T',
-        }
-    }
+/// The labels used to build one LLM's response keys.
+///
+/// Resolved from the Ensemble LLM's [`labels`](objectiveai::ensemble_llm::EnsembleLlmBase::labels),
+/// with defaults for its model family.
+#[derive(Debug, Clone)]
+pub struct PfxLabels {
+    /// All labels which may be used at each level of the tree.
+    pub alphabet: Vec<Pfx>,
+    /// The delimiters wrapped around each label.
+    pub wrap: objectiveai::ensemble_llm::LabelWrap,
+    /// The maximum number of labels per branch.
+    pub width: usize,
+}
 
-    /// Parses a character into a prefix, if valid.
-    pub fn from_char(c: char) -> Option<Self> {
-        match c {
-            'A' => Some(Pfx::A),
-            'B' => Some(Pfx::B),
-            'C' => Some(Pfx::C),
-            'D' => Some(Pfx::D),
-            'E' => Some(Pfx::E),
-            'F' => Some(Pfx::F),
-            'G' => Some(Pfx::G),
-            'H' => Some(Pfx::H),
-            'I' => Some(Pfx::I),
-            'J' => Some(Pfx::J),
-            'K' => Some(Pfx::K),
-            'L' => Some(Pfx::L),
-            'M' => Some(Pfx::M),
-            'N' => Some(Pfx::N),
-            'O' => Some(Pfx::O),
-            'P' => Some(Pfx::P),
-            'Q' => Some(Pfx::Q),
-            'R' => Some(Pfx::R),
-            'S' => Some(Pfx::S),
-            'T' => Some(Pfx::T),
-            _ => None,
+impl PfxLabels {
+    /// Resolves the labels for an Ensemble LLM.
+    pub fn new(llm: &objectiveai::ensemble_llm::EnsembleLlmBase) -> Self {
+        Self {
+            alphabet: llm.label_alphabet().labels(),
+            wrap: llm.label_wrap(),
+            width: llm.label_width(),
         }
     }
 
-    /// Returns all prefixes in randomized order.
-    pub fn rng_vec(rng: &mut impl Rng) -> Vec<Self> {
-        let mut vec = vec![
-            Pfx::A,
-            Pfx::B,
-            Pfx::C,
-            Pfx::D,
-            Pfx::E,
-            Pfx::F,
-            Pfx::G,
-            Pfx::H,
-            Pfx::I,
-            Pfx::J,
-            Pfx::K,
-            Pfx::L,
-            Pfx::M,
-            Pfx::N,
-            Pfx::O,
-            Pfx::P,
-            Pfx::Q,
-            Pfx::R,
-            Pfx::S,
-            Pfx::T,
-        ];
+    /// Returns all labels in randomized order.
+    pub fn rng_vec(&self, rng: &mut impl Rng) -> Vec<Pfx> {
+        let mut vec = self.alphabet.clone();
         vec.shuffle(rng);
         vec
     }
-}
 
-impl std::fmt::Display for Pfx {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_char())
+    /// Wraps a label in its delimiters.
+    pub fn wrapped(&self, pfx: Pfx) -> String {
+        format!("{}{}{}", self.wrap.open(), pfx, self.wrap.close())
+    }
+
+    /// Splits a response key into its labels.
+    ///
+    /// All labels of an alphabet have the same length, so this is
+    /// unambiguous. Returns None if the key is malformed.
+    pub fn split<'k>(&self, key: &'k str) -> Option<Vec<&'k str>> {
+        let pfx_len = self.alphabet.first()?.len();
+        let mut pfxs = Vec::new();
+        let mut rest = key;
+        while !rest.is_empty() {
+            rest = rest.strip_prefix(self.wrap.open())?;
+            pfxs.push(rest.get(..pfx_len)?);
+            rest = rest.get(pfx_len..)?.strip_prefix(self.wrap.close())?;
+        }
+        Some(pfxs)
     }
 }
 
@@ -149,26 +92,27 @@ pub enum PfxTree {
 impl PfxTree {
     /// Creates a new prefix tree for the given number of responses.
     ///
-    /// The `max_branch_len` should match the number of logprobs the LLM generates,
+    /// The width of `labels` should match the number of logprobs the LLM generates,
     /// ensuring each branch fits within one logprobs batch for probability capture.
     pub fn new(
         rng: &mut impl Rng,
         source_len: usize,
-        max_branch_len: usize,
+        labels: &PfxLabels,
     ) -> Self {
         let mut source: Vec<usize> = (0..source_len).collect();
         source.shuffle(rng);
-        Self::new_inner(rng, &source, max_branch_len, false)
+        Self::new_inner(rng, &source, labels, false)
     }
 
     /// Internal recursive constructor.
     pub fn new_inner(
         rng: &mut impl Rng,
         source: &[usize],
-        max_branch_len: usize,
+        labels: &PfxLabels,
         force_sub_branch: bool,
     ) -> Self {
-        let pfxs = Pfx::rng_vec(rng);
+        let max_branch_len = labels.width;
+        let pfxs = labels.rng_vec(rng);
         if !force_sub_branch && source.len() <= max_branch_len {
             // return a single branch containing all leaves
             let mut branch = IndexMap::with_capacity(source.len());
@@ -201,7 +145,7 @@ impl PfxTree {
                     PfxTree::new_inner(
                         rng,
                         &source[count..count + branch_len],
-                        max_branch_len,
+                        labels,
                         force_sub_branch,
                     ),
                 );
@@ -218,10 +162,11 @@ impl PfxTree {
     pub fn pfx_indices(
         &self,
         rng: &mut impl Rng,
+        labels: &PfxLabels,
         source_len: usize,
     ) -> Vec<(String, usize)> {
         let mut indices = Vec::with_capacity(source_len);
-        self.pfx_indices_inner(None, &mut indices);
+        self.pfx_indices_inner(labels, None, &mut indices);
         indices.shuffle(rng);
         indices
     }
@@ -229,6 +174,7 @@ impl PfxTree {
     /// Internal recursive method for generating prefix indices.
     pub fn pfx_indices_inner(
         &self,
+        labels: &PfxLabels,
         parent_pfx: Option<String>,
         indices: &mut Vec<(String, usize)>,
     ) {
@@ -236,10 +182,12 @@ impl PfxTree {
             PfxTree::Branch(branch) => {
                 for (pfx, child) in branch.as_ref() {
                     let parent_pfx = Some(match &parent_pfx {
-                        Some(parent_pfx) => format!("{}`{}`", parent_pfx, pfx),
-                        None => format!("`{}`", pfx),
+                        Some(parent_pfx) => {
+                            format!("{}{}", parent_pfx, labels.wrapped(pfx))
+                        }
+                        None => labels.wrapped(pfx),
                     });
-                    child.pfx_indices_inner(parent_pfx, indices);
+                    child.pfx_indices_inner(labels, parent_pfx, indices);
                 }
             }
             PfxTree::Leaf(index) => {
@@ -248,10 +196,10 @@ impl PfxTree {
         }
     }
 
-    /// Gets a child node by prefix character.
-    pub fn get(&self, pfx: Pfx) -> Option<PfxTree> {
+    /// Gets a child node by label.
+    pub fn get(&self, pfx: &str) -> Option<PfxTree> {
         match self {
-            PfxTree::Branch(branch) => branch.get(&pfx).cloned(),
+            PfxTree::Branch(branch) => branch.get(pfx).cloned(),
             PfxTree::Leaf(_) => None,
         }
     }
@@ -284,32 +232,31 @@ impl PfxTree {
 
     /// Generates regex patterns for matching response keys.
     ///
-    /// Returns (pattern with backticks, pattern without backticks).
-    pub fn regex_patterns(&self, keys: &[(String, usize)]) -> (String, String) {
-        let depth = self.depth();
-        let mut with_ticks = String::with_capacity(
-            (keys.len() - 1) // '|' characters
-                + (keys.len() * depth * 3) // each key
-                + keys.len() * 2, // parentheses
-        );
-        let mut without_ticks = String::with_capacity(
-            (keys.len() - 1) // for '|' characters
-                + keys.len() * (depth * 3 - 2) // each key stripped of ticks
-                + keys.len() * 2, // parentheses
-        );
+    /// Returns (pattern with delimiters, pattern without the outermost delimiters).
+    pub fn regex_patterns(
+        &self,
+        labels: &PfxLabels,
+        keys: &[(String, usize)],
+    ) -> (String, String) {
+        let open_len = labels.wrap.open().len();
+        let close_len = labels.wrap.close().len();
+        let mut with_wrap = String::new();
+        let mut without_wrap = String::new();
         for (key, _) in keys {
-            if with_ticks.len() > 0 {
-                with_ticks.push('|');
-                without_ticks.push('|');
+            if with_wrap.len() > 0 {
+                with_wrap.push('|');
+                without_wrap.push('|');
             }
-            with_ticks.push('(');
-            without_ticks.push('(');
-            with_ticks.push_str(key);
-            without_ticks.push_str(&key[1..key.len() - 1]); // strip ticks
-            with_ticks.push(')');
-            without_ticks.push(')');
+            with_wrap.push('(');
+            without_wrap.push('(');
+            with_wrap.push_str(&regex::escape(key));
+            without_wrap.push_str(&regex::escape(
+                &key[open_len..key.len() - close_len], // strip delimiters
+            ));
+            with_wrap.push(')');
+            without_wrap.push(')');
         }
-        (with_ticks, without_ticks)
+        (with_wrap, without_wrap)
     }
 }
 
@@ -318,10 +265,99 @@ impl PfxTree {
 pub struct PfxData {
     /// The prefix tree for this LLM.
     pub pfx_tree: PfxTree,
-    /// Regex pattern matching response keys with backticks.
+    /// The labels the prefix tree was built from.
+    pub pfx_labels: PfxLabels,
+    /// The (key, response index) pairs in the order they were presented.
+    pub pfx_indices: Arc<Vec<(String, usize)>>,
+    /// Regex pattern matching response keys with delimiters.
     pub responses_key_pattern: String,
-    /// Regex pattern matching response keys without backticks.
+    /// Regex pattern matching response keys without the outermost delimiters.
     pub responses_key_pattern_stripped: String,
     /// Whether to invert this LLM's vote distribution.
     pub invert_vote: bool,
+    /// The calibration applied to this LLM's vote distribution, if any.
+    pub calibration:
+        Option<objectiveai::vector::completions::request::Calibration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectiveai::ensemble_llm::{LabelAlphabet, LabelWrap};
+    use regex::Regex;
+
+    fn labels(
+        alphabet: LabelAlphabet,
+        wrap: LabelWrap,
+        width: usize,
+    ) -> PfxLabels {
+        PfxLabels {
+            alphabet: alphabet.labels(),
+            wrap,
+            width,
+        }
+    }
+
+    #[test]
+    fn nested_keys_cover_all_responses_and_split_back() {
+        let mut rng = rand::rng();
+        for labels in [
+            labels(LabelAlphabet::Letters, LabelWrap::Backtick, 20),
+            labels(LabelAlphabet::Digits, LabelWrap::Bracket, 10),
+            labels(LabelAlphabet::LetterPairs, LabelWrap::Parenthesis, 5),
+        ] {
+            let tree = PfxTree::new(&mut rng, 37, &labels);
+            let depth = tree.depth();
+            assert!(depth > 1);
+            let mut indices = tree.pfx_indices(&mut rng, &labels, 37);
+            for (key, index) in &indices {
+                let pfxs = labels.split(key).unwrap();
+                assert_eq!(pfxs.len(), depth);
+                let mut node = tree.clone();
+                for pfx in pfxs {
+                    node = node.get(pfx).unwrap();
+                }
+                assert_eq!(node.unwrap_leaf(), *index);
+            }
+            indices.sort_by_key(|(_, index)| *index);
+            assert!(indices.iter().map(|(_, i)| *i).eq(0..37));
+        }
+    }
+
+    #[test]
+    fn branches_respect_width() {
+        let mut rng = rand::rng();
+        let labels = labels(LabelAlphabet::Letters, LabelWrap::Backtick, 5);
+        let tree = PfxTree::new(&mut rng, 5, &labels);
+        assert_eq!(tree.depth(), 1);
+        let tree = PfxTree::new(&mut rng, 6, &labels);
+        assert_eq!(tree.depth(), 2);
+    }
+
+    #[test]
+    fn regex_patterns_escape_delimiters() {
+        let mut rng = rand::rng();
+        let labels = labels(LabelAlphabet::Digits, LabelWrap::Bracket, 10);
+        let tree = PfxTree::new(&mut rng, 3, &labels);
+        let indices = tree.pfx_indices(&mut rng, &labels, 3);
+        let (with_wrap, without_wrap) = tree.regex_patterns(&labels, &indices);
+        let key = &indices[0].0;
+        let with_wrap = Regex::new(&with_wrap).unwrap();
+        assert_eq!(
+            with_wrap.find(&format!("I pick {key}.")).unwrap().as_str(),
+            key
+        );
+        assert!(with_wrap.find(&key[1..2]).is_none());
+        let without_wrap = Regex::new(&without_wrap).unwrap();
+        assert!(without_wrap.find(&key[1..2]).is_some());
+    }
+
+    #[test]
+    fn split_rejects_malformed_keys() {
+        let labels =
+            labels(LabelAlphabet::LetterPairs, LabelWrap::Backtick, 20);
+        assert_eq!(labels.split("`AB``CD`").unwrap(), vec!["AB", "CD"]);
+        assert!(labels.split("`A`").is_none());
+        assert!(labels.split("`AB").is_none());
+    }
 }
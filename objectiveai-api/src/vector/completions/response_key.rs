@@ -14,6 +14,48 @@ pub struct ResponseKey {
 }
 
 impl ResponseKey {
+    /// Extracts the rationale behind a vote from an LLM choice.
+    ///
+    /// Combines the native reasoning of reasoning models with the `_think`
+    /// field of synthetic reasoning, from either the content or the
+    /// `response_key` tool call. Returns None if there is neither.
+    pub fn rationale(
+        choice: &objectiveai::chat::completions::response::streaming::Choice,
+    ) -> Option<String> {
+        let mut rationales = Vec::new();
+        if let Some(reasoning) = &choice.delta.reasoning {
+            rationales.push(reasoning.clone());
+        }
+        if let Some(content) = &choice.delta.content
+            && let Ok(ResponseKey {
+                _think: Some(think),
+                ..
+            }) = serde_json::from_str(content)
+        {
+            rationales.push(think);
+        }
+        for tool_call in choice.delta.tool_calls.iter().flatten() {
+            if let Some(
+                objectiveai::chat::completions::response::streaming::ToolCallFunction {
+                    arguments: Some(arguments),
+                    ..
+                },
+            ) = &tool_call.function
+                && let Ok(ResponseKey {
+                    _think: Some(think),
+                    ..
+                }) = serde_json::from_str(arguments)
+            {
+                rationales.push(think);
+            }
+        }
+        if rationales.is_empty() {
+            None
+        } else {
+            Some(rationales.join("\n\n"))
+        }
+    }
+
     /// Creates a JSON schema for response key selection.
     fn schema(
         vector_response_keys: Vec<String>,
@@ -113,3 +155,65 @@ impl ResponseKey {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectiveai::chat::completions::response::streaming::{
+        Choice, Delta, ToolCall, ToolCallFunction,
+    };
+
+    fn choice(delta: Delta) -> Choice {
+        Choice {
+            delta,
+            finish_reason: None,
+            index: 0,
+            logprobs: None,
+        }
+    }
+
+    #[test]
+    fn rationale_combines_reasoning_and_think() {
+        let choice = choice(Delta {
+            reasoning: Some("Both are plausible.".to_string()),
+            content: Some(
+                r#"{"_think":"B cites a source.","response_key":"`B`"}"#
+                    .to_string(),
+            ),
+            ..Default::default()
+        });
+        assert_eq!(
+            ResponseKey::rationale(&choice).unwrap(),
+            "Both are plausible.\n\nB cites a source."
+        );
+    }
+
+    #[test]
+    fn rationale_from_tool_call() {
+        let choice = choice(Delta {
+            tool_calls: Some(vec![ToolCall {
+                index: 0,
+                r#type: None,
+                id: None,
+                function: Some(ToolCallFunction {
+                    arguments: Some(
+                        r#"{"_think":"A is shorter.","response_key":"`A`"}"#
+                            .to_string(),
+                    ),
+                    ..Default::default()
+                }),
+            }]),
+            ..Default::default()
+        });
+        assert_eq!(ResponseKey::rationale(&choice).unwrap(), "A is shorter.");
+    }
+
+    #[test]
+    fn no_rationale_for_bare_key() {
+        let choice = choice(Delta {
+            content: Some("`A`".to_string()),
+            ..Default::default()
+        });
+        assert!(ResponseKey::rationale(&choice).is_none());
+    }
+}
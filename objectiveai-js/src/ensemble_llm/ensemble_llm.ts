@@ -1,5 +1,7 @@
 import z from "zod";
+import { LabelsSchema } from "./labels";
 import { OutputModeSchema } from "./output_mode";
+import { PromptTemplateSchema } from "./prompt_template";
 import { MessagesSchema } from "src/chat/completions/request/message";
 import { StopSchema } from "./stop";
 import { ProviderSchema } from "./provider";
@@ -27,6 +29,26 @@ export const EnsembleLlmBaseSchema = z
       .describe(
         "For Vector Completions only, whether to use logprobs to make the vote probabilistic. This means that the LLM can vote for multiple keys based on their logprobabilities. Allows LLMs to express native uncertainty when voting."
       ),
+    permutations: z
+      .int()
+      .min(0)
+      .max(10)
+      .optional()
+      .nullable()
+      .describe(
+        "For Vector Completions only, the number of permutations of the responses to vote over. When greater than 1, the LLM votes once per permutation, each time seeing the responses in a different order with different keys, and its vote is the mean of those votes. Mitigates position bias."
+      ),
+    samples: z
+      .int()
+      .min(0)
+      .max(20)
+      .optional()
+      .nullable()
+      .describe(
+        "For Vector Completions only, the number of completions to sample for each vote. When greater than 1, the LLM votes once per sample, and its vote is the mean of those votes. For LLMs which do not provide logprobs, this is the empirical distribution of the keys they selected. Samples are drawn with repeated requests, each with a different seed if the request has one."
+      ),
+    labels: LabelsSchema.optional().nullable(),
+    prompt_template: PromptTemplateSchema.optional().nullable(),
     prefix_messages: MessagesSchema.optional()
       .nullable()
       .describe(
@@ -1,6 +1,8 @@
 export * from "./ensemble_llm";
 export * from "./http";
+export * from "./labels";
 export * from "./output_mode";
+export * from "./prompt_template";
 export * from "./provider";
 export * from "./reasoning";
 export * from "./stop";
@@ -0,0 +1,38 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../json_schema";
+
+export const LabelAlphabetSchema = z
+  .enum(["letters", "digits", "letter_pairs"])
+  .describe(
+    "The labels which response keys are made of. `letters` is `A` through `T`, `digits` is `0` through `9`, and `letter_pairs` is `AA` through `TT`, for tokenizers which encode each pair as a single token."
+  );
+export type LabelAlphabet = z.infer<typeof LabelAlphabetSchema>;
+export const LabelAlphabetJsonSchema: JSONSchema = convert(LabelAlphabetSchema);
+
+export const LabelWrapSchema = z
+  .enum(["backtick", "bracket", "parenthesis"])
+  .describe(
+    "The delimiters wrapped around each label of a response key: `` `A` ``, `[A]`, or `(A)`."
+  );
+export type LabelWrap = z.infer<typeof LabelWrapSchema>;
+export const LabelWrapJsonSchema: JSONSchema = convert(LabelWrapSchema);
+
+export const LabelsSchema = z
+  .object({
+    alphabet: LabelAlphabetSchema.optional().nullable(),
+    wrap: LabelWrapSchema.optional().nullable(),
+    width: z
+      .int()
+      .min(2)
+      .max(20)
+      .optional()
+      .nullable()
+      .describe(
+        "Maximum number of labels per level. Responses beyond this are given nested keys. Should not exceed the number of logprobs the provider returns. Defaults to `top_logprobs`, or 20."
+      ),
+  })
+  .describe(
+    "For Vector Completions only, how response keys are labeled. Unset fields fall back to defaults: letters, and brackets for Llama, Mistral, and Gemma models or backticks for all others. The bracket default is written into the labels, and so into the ID."
+  );
+export type Labels = z.infer<typeof LabelsSchema>;
+export const LabelsJsonSchema: JSONSchema = convert(LabelsSchema);
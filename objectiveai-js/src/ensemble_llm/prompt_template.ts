@@ -0,0 +1,34 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../json_schema";
+
+export const PromptTemplateSchema = z
+  .object({
+    select: z
+      .string()
+      .optional()
+      .nullable()
+      .describe(
+        "Appended to the last user message to present the responses. Defaults to `Select the response:\\n\\n{responses}`."
+      ),
+    select_rating: z
+      .string()
+      .optional()
+      .nullable()
+      .describe(
+        "Appended to the last user message to present the values of the scale, when the request has a rating scale. Defaults to `Select the rating, from {min} to {max}:\\n\\n{responses}`."
+      ),
+    instruction: z
+      .string()
+      .optional()
+      .nullable()
+      .describe(
+        "Appended to the system message with the voting instructions, when `output_mode` is `instruction`. Defaults to `Output one response key including {wrap}\\n{keys}`."
+      ),
+  })
+  .describe(
+    "For Vector Completions only, overrides the wording of the voting prompt. Unset templates keep the default wording. Placeholders: `{responses}` is the labeled responses (if absent, they follow the text), `{keys}` is the response keys, one per line, each prefixed by `- `, `{wrap}` is the name of the delimiters around labels, and `{min}` and `{max}` are the bounds of the rating scale."
+  );
+export type PromptTemplate = z.infer<typeof PromptTemplateSchema>;
+export const PromptTemplateJsonSchema: JSONSchema = convert(
+  PromptTemplateSchema
+);
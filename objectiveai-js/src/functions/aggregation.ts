@@ -0,0 +1,50 @@
+import z from "zod";
+import { ExpressionSchema } from "./expression/expression";
+import { convert, type JSONSchema } from "../json_schema";
+
+export const AggregationMethodSchema = z
+  .enum([
+    "weighted_mean",
+    "weighted_median",
+    "geometric_mean",
+    "max",
+    "min",
+    "product_of_experts",
+  ])
+  .describe(
+    "A built-in method of combining task outputs. " +
+      "`weighted_mean`: the weighted arithmetic mean. " +
+      "`weighted_median`: the weighted median, robust to a minority of outlying tasks. " +
+      "`geometric_mean`: the weighted geometric mean. " +
+      "`max` / `min`: the maximum / minimum. " +
+      "`product_of_experts`: the product of task outputs, each raised to the power of its weight; scalar outputs are treated as the probability of a binary outcome. " +
+      "Vector methods apply element-wise, and vector results are L1-normalized.",
+  )
+  .meta({ title: "AggregationMethod" });
+export type AggregationMethod = z.infer<typeof AggregationMethodSchema>;
+export const AggregationMethodJsonSchema: JSONSchema = convert(
+  AggregationMethodSchema,
+);
+
+export const AggregationExpressionSchema = ExpressionSchema.describe(
+  "An expression which computes the function's output from its task outputs. " +
+    "Must return a FunctionOutput valid for the function's type. " +
+    "Receives: `input`, `tasks` (the output of every task, `null` if skipped or failed), `weights` (the profile weight of every task).",
+).meta({ title: "Expression", wrapper: true });
+export type AggregationExpression = z.infer<
+  typeof AggregationExpressionSchema
+>;
+export const AggregationExpressionJsonSchema: JSONSchema = convert(
+  AggregationExpressionSchema,
+);
+
+export const AggregationSchema = z
+  .union([AggregationMethodSchema, AggregationExpressionSchema])
+  .describe(
+    "How task outputs are combined into the function's output. " +
+      "Task outputs which were skipped or failed are excluded, as are tasks with a profile weight of zero. " +
+      "Defaults to `weighted_mean`.",
+  )
+  .meta({ title: "Aggregation" });
+export type Aggregation = z.infer<typeof AggregationSchema>;
+export const AggregationJsonSchema: JSONSchema = convert(AggregationSchema);
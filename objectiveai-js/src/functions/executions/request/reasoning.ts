@@ -9,6 +9,13 @@ export const ReasoningSchema = z
   .object({
     model: ModelSchema,
     models: FallbackModelsSchema.optional().nullable(),
+    include_rationales: z
+      .boolean()
+      .optional()
+      .nullable()
+      .describe(
+        "If true, every vote in the execution's Vector Completion tasks carries the rationale of the Ensemble LLM which cast it, so reviewers can see why each LLM voted as it did. The summary is based on these rationales either way.",
+      ),
   })
   .describe(
     "If provided, a reasoning summary for the Function Execution will be generated. This reasoning summary attempts to detail why the final Output is what it is, based on AI assertions made during execution.",
@@ -0,0 +1,24 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../../json_schema";
+
+export const DefinitionsSchema = z
+  .object({
+    starlark: z
+      .record(z.string(), z.string())
+      .optional()
+      .describe(
+        "Starlark source keyed by the name it defines (e.g. `def clamp(x): ...`). Loaded into every Starlark expression of the function.",
+      ),
+    jmespath: z
+      .record(z.string(), z.string())
+      .optional()
+      .describe(
+        "JMESPath fragments keyed by name. `name(x)` in any JMESPath expression evaluates the fragment with `x` as the current node; `name()` uses the current node.",
+      ),
+  })
+  .describe(
+    "Named Starlark functions and JMESPath fragments callable from any expression in the function.",
+  )
+  .meta({ title: "Definitions" });
+export type Definitions = z.infer<typeof DefinitionsSchema>;
+export const DefinitionsJsonSchema: JSONSchema = convert(DefinitionsSchema);
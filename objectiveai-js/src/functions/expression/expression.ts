@@ -21,9 +21,19 @@ export const StarlarkExpressionSchema = z
 export type StarlarkExpression = z.infer<typeof StarlarkExpressionSchema>;
 export const StarlarkExpressionJsonSchema: JSONSchema = convert(StarlarkExpressionSchema);
 
+export const CelExpressionSchema = z
+  .object({
+    $cel: z.string().describe("A CEL expression."),
+  })
+  .strict()
+  .describe("A CEL (Common Expression Language) expression which evaluates to a value.")
+  .meta({ title: "CelExpression" });
+export type CelExpression = z.infer<typeof CelExpressionSchema>;
+export const CelExpressionJsonSchema: JSONSchema = convert(CelExpressionSchema);
+
 export const ExpressionSchema = z
-  .union([JMESPathExpressionSchema, StarlarkExpressionSchema])
-  .describe("An expression (JMESPath or Starlark) which evaluates to a value.")
+  .union([JMESPathExpressionSchema, StarlarkExpressionSchema, CelExpressionSchema])
+  .describe("An expression (JMESPath, Starlark, or CEL) which evaluates to a value.")
   .meta({ title: "Expression" });
 export type Expression = z.infer<typeof ExpressionSchema>;
 export const ExpressionJsonSchema: JSONSchema = convert(ExpressionSchema);
@@ -1,3 +1,4 @@
+export * from "./definitions";
 export * from "./expression";
 export * from "./input";
 export * from "./params";
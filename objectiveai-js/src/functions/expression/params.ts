@@ -1,4 +1,5 @@
 import { JsonValueSchema } from "src/json";
+import { RatingSchema } from "src/vector/completions/response/rating";
 import { ScoresSchema } from "src/vector/completions/response/scores";
 import { VectorCompletionChunk } from "src/vector/completions/response/streaming";
 import { VectorCompletion } from "src/vector/completions/response/unary";
@@ -26,6 +27,9 @@ export const VectorCompletionOutputSchema = z
     votes: VotesSchema,
     scores: ScoresSchema,
     weights: WeightsSchema,
+    rating: RatingSchema.optional().describe(
+      "The combined rating, present for scalar.completion tasks."
+    ),
   })
   .describe("The output of a vector completion task.")
   .meta({ title: "VectorCompletionOutput" });
@@ -54,6 +58,7 @@ export function compileVectorCompletionOutput(
     votes: completion.votes,
     scores: completion.scores,
     weights: completion.weights,
+    ...(completion.rating !== undefined ? { rating: completion.rating } : {}),
   };
 }
 
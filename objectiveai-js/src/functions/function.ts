@@ -12,6 +12,8 @@ import {
   QualityLeafVectorTasksExpressionsSchema,
   TaskExpressionsSchema,
 } from "./task";
+import { AggregationSchema } from "./aggregation";
+import { DefinitionsSchema } from "./expression/definitions";
 import { ExpressionSchema } from "./expression/expression";
 import { convert, type JSONSchema } from "../json_schema";
 
@@ -20,11 +22,13 @@ import { convert, type JSONSchema } from "../json_schema";
 export const InlineScalarFunctionSchema = z
   .object({
     type: z.literal("scalar.function"),
+    definitions: DefinitionsSchema.optional().nullable(),
     input_maps: InputMapsExpressionSchema.optional().nullable(),
     tasks: TaskExpressionsSchema,
+    aggregation: AggregationSchema.optional().nullable(),
   })
   .describe(
-    "A scalar function defined inline. Each task's output expression must return a number in [0,1]. The function's output is the weighted average of all task outputs using profile weights. If there is only one task, its output becomes the function's output directly.",
+    "A scalar function defined inline. Each task's output expression must return a number in [0,1]. The function's output is the aggregation of all task outputs, by default the weighted average using profile weights. If there is only one task, its output becomes the function's output directly.",
   )
   .meta({ title: "InlineScalarFunction" });
 export type InlineScalarFunction = z.infer<typeof InlineScalarFunctionSchema>;
@@ -35,8 +39,10 @@ export const InlineScalarFunctionJsonSchema: JSONSchema = convert(
 export const InlineVectorFunctionSchema = z
   .object({
     type: z.literal("vector.function"),
+    definitions: DefinitionsSchema.optional().nullable(),
     input_maps: InputMapsExpressionSchema.optional().nullable(),
     tasks: TaskExpressionsSchema,
+    aggregation: AggregationSchema.optional().nullable(),
     input_split: ExpressionSchema.optional()
       .nullable()
       .describe(
@@ -57,7 +63,7 @@ export const InlineVectorFunctionSchema = z
       ),
   })
   .describe(
-    "A vector function defined inline. Each task's output expression must return an array of numbers summing to ~1. The function's output is the weighted average of all task outputs using profile weights. If there is only one task, its output becomes the function's output directly.",
+    "A vector function defined inline. Each task's output expression must return an array of numbers summing to ~1. The function's output is the aggregation of all task outputs, by default the weighted average using profile weights. If there is only one task, its output becomes the function's output directly.",
   )
   .meta({ title: "InlineVectorFunction" });
 export type InlineVectorFunction = z.infer<typeof InlineVectorFunctionSchema>;
@@ -1,3 +1,4 @@
+export * from "./aggregation";
 export * as Executions from "./executions";
 export * as Expression from "./expression";
 export * from "./function";
@@ -22,19 +22,33 @@ import {
   VectorResponsesExpressionSchema,
   VectorResponsesSchema,
 } from "src/vector/completions/request/vector_response";
+import { RatingScaleSchema } from "src/vector/completions/request/rating";
 import { convert, type JSONSchema } from "../json_schema";
 import { RemoteSchema } from "./remote";
 
 // Task Expression
 
 export const TaskExpressionSkipSchema = ExpressionSchema.describe(
-  "An expression which evaluates to a boolean indicating whether to skip this task. Receives: `input`.",
+  "An expression which evaluates to a boolean indicating whether to skip this task. Receives: `input`, `tasks` (if `depends_on` is set).",
 ).meta({ title: "Expression", wrapper: true });
 export type TaskExpressionSkip = z.infer<typeof TaskExpressionSkipSchema>;
 export const TaskExpressionSkipJsonSchema: JSONSchema = convert(
   TaskExpressionSkipSchema,
 );
 
+export const TaskExpressionDependsOnSchema = z
+  .array(z.uint32())
+  .describe(
+    "Indices of earlier tasks whose outputs this task reads. " +
+      "If set, the task is compiled only after those tasks finish, with their outputs available as `tasks`.",
+  );
+export type TaskExpressionDependsOn = z.infer<
+  typeof TaskExpressionDependsOnSchema
+>;
+export const TaskExpressionDependsOnJsonSchema: JSONSchema = convert(
+  TaskExpressionDependsOnSchema,
+);
+
 export const TaskExpressionMapSchema = z
   .uint32()
   .describe(
@@ -64,7 +78,7 @@ export const TaskOutputExpressionSchema = ExpressionSchema.describe(
     "a VectorCompletionOutput (for non-mapped vector completion tasks), or an array of VectorCompletionOutputs (for mapped vector completion tasks). " +
     "Must return a FunctionOutput valid for the parent function's type: " +
     "scalar functions require a number in [0,1], vector functions require an array of numbers summing to ~1. " +
-    "The function's final output is the weighted average of all task outputs using profile weights. " +
+    "The function's final output is the aggregation of all task outputs, by default the weighted average using profile weights. " +
     "Receives: `input`, `output`.",
 ).meta({ title: "Expression", wrapper: true });
 export type TaskOutputExpression = z.infer<typeof TaskOutputExpressionSchema>;
@@ -87,6 +101,7 @@ export const ScalarFunctionTaskExpressionSchema = z
       .describe(
         "The commit SHA of the repository containing the function.",
       ),
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
     skip: TaskExpressionSkipSchema.optional().nullable(),
     map: TaskExpressionMapSchema.optional().nullable(),
     input: InputValueExpressionSchema,
@@ -108,6 +123,7 @@ export const QualityScalarFunctionTaskExpressionSchema = z
     owner: ScalarFunctionTaskExpressionSchema.shape.owner,
     repository: ScalarFunctionTaskExpressionSchema.shape.repository,
     commit: ScalarFunctionTaskExpressionSchema.shape.commit,
+    depends_on: ScalarFunctionTaskExpressionSchema.shape.depends_on,
     skip: ScalarFunctionTaskExpressionSchema.shape.skip,
     map: QualityTaskExpressionMapSchema.optional().nullable(),
     input: ScalarFunctionTaskExpressionSchema.shape.input,
@@ -136,6 +152,7 @@ export const VectorFunctionTaskExpressionSchema = z
       .describe(
         "The commit SHA of the repository containing the function.",
       ),
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
     skip: TaskExpressionSkipSchema.optional().nullable(),
     map: TaskExpressionMapSchema.optional().nullable(),
     input: InputValueExpressionSchema,
@@ -157,6 +174,7 @@ export const QualityVectorFunctionTaskExpressionSchema = z
     owner: VectorFunctionTaskExpressionSchema.shape.owner,
     repository: VectorFunctionTaskExpressionSchema.shape.repository,
     commit: VectorFunctionTaskExpressionSchema.shape.commit,
+    depends_on: VectorFunctionTaskExpressionSchema.shape.depends_on,
     skip: VectorFunctionTaskExpressionSchema.shape.skip,
     map: QualityTaskExpressionMapSchema.optional().nullable(),
     input: VectorFunctionTaskExpressionSchema.shape.input,
@@ -173,6 +191,7 @@ export const QualityVectorFunctionTaskExpressionJsonSchema: JSONSchema =
 export const VectorCompletionTaskExpressionSchema = z
   .object({
     type: z.literal("vector.completion"),
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
     skip: TaskExpressionSkipSchema.optional().nullable(),
     map: TaskExpressionMapSchema.optional().nullable(),
     messages: MessagesExpressionSchema,
@@ -196,6 +215,7 @@ export const VectorCompletionTaskExpressionJsonSchema: JSONSchema = convert(
 export const QualityScalarVectorCompletionTaskExpressionSchema = z
   .object({
     type: VectorCompletionTaskExpressionSchema.shape.type,
+    depends_on: VectorCompletionTaskExpressionSchema.shape.depends_on,
     skip: VectorCompletionTaskExpressionSchema.shape.skip,
     map: z.undefined(),
     messages: QualityMessagesExpressionSchema,
@@ -214,9 +234,54 @@ export type QualityScalarVectorCompletionTaskExpression = z.infer<
 export const QualityScalarVectorCompletionTaskExpressionJsonSchema: JSONSchema =
   convert(QualityScalarVectorCompletionTaskExpressionSchema);
 
+export const ScalarCompletionTaskExpressionSchema = z
+  .object({
+    type: z.literal("scalar.completion"),
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
+    skip: TaskExpressionSkipSchema.optional().nullable(),
+    map: TaskExpressionMapSchema.optional().nullable(),
+    messages: MessagesExpressionSchema,
+    tools: VectorCompletionTaskExpressionSchema.shape.tools,
+    scale: RatingScaleSchema.describe("The scale the LLMs rate the prompt on."),
+    output: TaskOutputExpressionSchema,
+  })
+  .describe(
+    "A scalar completion task expression. The Ensemble LLMs rate the prompt on a numeric scale, and `output` receives the `scores` over the values of the scale along with the combined `rating` (`mean` and `std_dev`).",
+  )
+  .meta({ title: "ScalarCompletionTaskExpression" });
+export type ScalarCompletionTaskExpression = z.infer<
+  typeof ScalarCompletionTaskExpressionSchema
+>;
+export const ScalarCompletionTaskExpressionJsonSchema: JSONSchema = convert(
+  ScalarCompletionTaskExpressionSchema,
+);
+
+export const QualityScalarCompletionTaskExpressionSchema = z
+  .object({
+    type: ScalarCompletionTaskExpressionSchema.shape.type,
+    depends_on: ScalarCompletionTaskExpressionSchema.shape.depends_on,
+    skip: ScalarCompletionTaskExpressionSchema.shape.skip,
+    map: z.undefined(),
+    messages: QualityMessagesExpressionSchema,
+    tools: ScalarCompletionTaskExpressionSchema.shape.tools,
+    scale: ScalarCompletionTaskExpressionSchema.shape.scale,
+    output: ScalarCompletionTaskExpressionSchema.shape.output,
+  })
+  .describe(
+    ScalarCompletionTaskExpressionSchema.description! +
+      " Message content must be arrays of content parts, not plain strings.",
+  )
+  .meta({ title: "QualityScalarCompletionTaskExpression" });
+export type QualityScalarCompletionTaskExpression = z.infer<
+  typeof QualityScalarCompletionTaskExpressionSchema
+>;
+export const QualityScalarCompletionTaskExpressionJsonSchema: JSONSchema =
+  convert(QualityScalarCompletionTaskExpressionSchema);
+
 export const QualityVectorVectorCompletionTaskExpressionSchema = z
   .object({
     type: VectorCompletionTaskExpressionSchema.shape.type,
+    depends_on: VectorCompletionTaskExpressionSchema.shape.depends_on,
     skip: VectorCompletionTaskExpressionSchema.shape.skip,
     map: z.undefined(),
     messages: QualityMessagesExpressionSchema,
@@ -239,6 +304,7 @@ export const PlaceholderScalarFunctionTaskExpressionSchema = z
   .object({
     type: z.literal("placeholder.scalar.function"),
     input_schema: InputSchemaSchema,
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
     skip: TaskExpressionSkipSchema.optional().nullable(),
     map: TaskExpressionMapSchema.optional().nullable(),
     input: InputValueExpressionSchema,
@@ -272,6 +338,7 @@ export const PlaceholderVectorFunctionTaskExpressionSchema = z
     input_merge: ExpressionSchema.describe(
       "Recombines a variable-size, arbitrarily-ordered subset of sub-inputs back into one input. Receives: `input` (an array of sub-inputs).",
     ),
+    depends_on: TaskExpressionDependsOnSchema.optional().nullable(),
     skip: TaskExpressionSkipSchema.optional().nullable(),
     map: TaskExpressionMapSchema.optional().nullable(),
     input: InputValueExpressionSchema,
@@ -292,6 +359,7 @@ export const TaskExpressionSchema = z
     ScalarFunctionTaskExpressionSchema,
     VectorFunctionTaskExpressionSchema,
     VectorCompletionTaskExpressionSchema,
+    ScalarCompletionTaskExpressionSchema,
     PlaceholderScalarFunctionTaskExpressionSchema,
     PlaceholderVectorFunctionTaskExpressionSchema,
   ])
@@ -448,9 +516,14 @@ export const QualityBranchVectorFunctionTasksExpressionsJsonSchema: JSONSchema =
 // Quality Depth-0 Tasks: only vector.completion
 
 export const QualityLeafScalarTasksExpressionsSchema = z
-  .array(QualityScalarVectorCompletionTaskExpressionSchema)
+  .array(
+    z.discriminatedUnion("type", [
+      QualityScalarVectorCompletionTaskExpressionSchema,
+      QualityScalarCompletionTaskExpressionSchema,
+    ]),
+  )
   .describe(
-    "Tasks at depth 0 of a scalar function. Only vector.completion tasks are allowed. " +
+    "Tasks at depth 0 of a scalar function. Only vector.completion and scalar.completion tasks are allowed. " +
       "Responses must be arrays of content parts. " +
       "Must contain at least 1 task. Task count must be within min_width and max_width from parameters.",
   );
@@ -537,6 +610,9 @@ export const VectorCompletionTaskSchema = z
     messages: MessagesSchema,
     tools: ToolsSchema.optional(),
     responses: VectorResponsesSchema,
+    rating: RatingScaleSchema.optional().describe(
+      "The rating scale, if compiled from a scalar.completion task. The responses are then the values of the scale.",
+    ),
     output: ExpressionSchema.describe(
       "Expression to transform the task result into a valid function output. Receives: `input`, `output` (the raw VectorCompletionOutput).",
     ),
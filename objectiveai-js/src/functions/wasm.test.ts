@@ -0,0 +1,89 @@
+import { describe, it, expect } from "vitest";
+import { Functions } from "../index.js";
+
+// ── helpers ──────────────────────────────────────────────────────────
+
+const contentParts = [{ type: "text" as const, text: "Hello" }];
+
+const inputSchema = {
+  type: "object",
+  properties: {
+    name: { type: "string" },
+    tags: { type: "array", items: { type: "string" } },
+  },
+  required: ["name", "tags"],
+};
+
+const input = { name: "alice", tags: ["a", "b"] };
+
+function celVcTask(text: string, map?: number) {
+  return {
+    type: "vector.completion" as const,
+    messages: [
+      {
+        role: "user" as const,
+        content: [{ type: "text" as const, text: { $cel: text } }],
+      },
+    ],
+    responses: [contentParts, contentParts],
+    output: { $cel: "output.scores[0]" },
+    ...(map !== undefined ? { map } : {}),
+  };
+}
+
+function leafScalar(tasks: unknown[], inputMaps?: unknown) {
+  return {
+    type: "scalar.function",
+    description: "test",
+    input_schema: inputSchema,
+    tasks,
+    ...(inputMaps !== undefined ? { input_maps: inputMaps } : {}),
+  };
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+describe("$cel expressions", () => {
+  it("compiles tasks", () => {
+    const f = leafScalar([
+      celVcTask("'Rate ' + input.name.upperAscii()"),
+      {
+        ...celVcTask("input.name"),
+        skip: { $cel: "size(input.tags) > 1" },
+      },
+    ]);
+    const tasks = Functions.compileFunctionTasks(f as any, input) as any[];
+    expect(tasks[0].messages[0].content[0].text).toBe("Rate ALICE");
+    expect(tasks[1]).toBeNull();
+  });
+
+  it("compiles mapped tasks", () => {
+    const f = leafScalar(
+      [celVcTask("'Tag ' + map", 0)],
+      { $cel: "input.tags.map(t, t + '!')" },
+    );
+    const tasks = Functions.compileFunctionTasks(f as any, input) as any[];
+    expect(
+      tasks[0].map((task: any) => task.messages[0].content[0].text),
+    ).toEqual(["Tag a!", "Tag b!"]);
+  });
+
+  it("surfaces evaluation errors", () => {
+    const f = leafScalar([celVcTask("input.nickname")]);
+    expect(() => Functions.compileFunctionTasks(f as any, input)).toThrow(
+      /No such key: nickname/,
+    );
+  });
+
+  it("passes the quality check", () => {
+    const f = leafScalar([celVcTask("'Rate ' + input.name")]);
+    expect(() => Functions.Quality.checkLeafScalarFunction(f as any)).not.toThrow();
+  });
+
+  it("rejects unknown properties in the quality check", () => {
+    const f = leafScalar([celVcTask("'Rate ' + input.nme")]);
+    expect(() => Functions.Quality.checkLeafScalarFunction(f as any)).toThrow(
+      /has no property 'nme'/,
+    );
+  });
+});
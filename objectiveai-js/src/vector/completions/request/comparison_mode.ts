@@ -0,0 +1,13 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../../../json_schema";
+
+export const ComparisonModeSchema = z
+  .enum(["all", "pairwise"])
+  .describe(
+    "How each Ensemble LLM compares the responses. `all`: each LLM votes once, over all of the responses at once. `pairwise`: each LLM votes once for every pair of responses, and its vote is the Bradley-Terry fit of those pairwise votes. More reliable when candidates are close, at the cost of n * (n - 1) / 2 completions per LLM. Pairwise requests may have at most 16 responses."
+  )
+  .meta({ title: "VectorCompletionComparisonMode" });
+export type ComparisonMode = z.infer<typeof ComparisonModeSchema>;
+export const ComparisonModeJsonSchema: JSONSchema = convert(
+  ComparisonModeSchema
+);
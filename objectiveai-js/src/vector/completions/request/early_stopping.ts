@@ -0,0 +1,34 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../../../json_schema";
+
+export const EarlyStoppingSchema = z
+  .object({
+    wave_size: z
+      .uint32()
+      .min(1)
+      .optional()
+      .nullable()
+      .describe("Number of LLMs to run in each wave. Defaults to 4."),
+    confidence: z
+      .number()
+      .gt(0.5)
+      .lt(1)
+      .optional()
+      .nullable()
+      .describe(
+        "Confidence that the leading response would remain ahead, required to stop early. Defaults to 0.95."
+      ),
+    min_votes: z
+      .uint32()
+      .optional()
+      .nullable()
+      .describe(
+        "Minimum number of votes before stopping on confidence. Defaults to 4."
+      ),
+  })
+  .describe(
+    "Runs the Ensemble LLMs in waves, in order of descending profile weight, skipping the remaining waves once the outcome is decided: either the remaining LLMs cannot overturn the leading response, or the weighted mean margin between the leading response and the runner-up is positive at the given confidence. Skipped LLMs are never run, and are reported in the response."
+  )
+  .meta({ title: "VectorCompletionEarlyStopping" });
+export type EarlyStopping = z.infer<typeof EarlyStoppingSchema>;
+export const EarlyStoppingJsonSchema: JSONSchema = convert(EarlyStoppingSchema);
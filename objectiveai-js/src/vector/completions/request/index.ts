@@ -1,4 +1,7 @@
+export * from "./comparison_mode";
+export * from "./early_stopping";
 export * from "./ensemble";
 export * from "./profile";
+export * from "./rating";
 export * from "./vector_completion_create_params";
 export * from "./vector_response";
@@ -1,6 +1,52 @@
 import z from "zod";
 import { convert, type JSONSchema } from "../../../json_schema";
 
+export const CalibrationPointSchema = z
+  .object({
+    x: z.number().describe("The vote probability, between 0 and 1."),
+    y: z.number().describe("The calibrated probability, between 0 and 1."),
+  })
+  .describe("A point of an isotonic calibration function.")
+  .meta({ title: "VectorCompletionCalibrationPoint" });
+export type CalibrationPoint = z.infer<typeof CalibrationPointSchema>;
+export const CalibrationPointJsonSchema: JSONSchema = convert(
+  CalibrationPointSchema
+);
+
+export const CalibrationSchema = z
+  .discriminatedUnion("type", [
+    z
+      .object({
+        type: z.literal("temperature"),
+        temperature: z
+          .number()
+          .describe(
+            "Must be greater than 0. Above 1 flattens an overconfident LLM's votes; below 1 sharpens an underconfident LLM's votes."
+          ),
+      })
+      .describe(
+        "Temperature scaling: each probability is raised to the power of 1 / temperature, then the distribution is re-normalized."
+      ),
+    z
+      .object({
+        type: z.literal("isotonic"),
+        points: z
+          .array(CalibrationPointSchema)
+          .describe(
+            "The points of the function, sorted by strictly increasing `x` with non-decreasing `y`."
+          ),
+      })
+      .describe(
+        "Isotonic mapping: each probability is mapped through a monotonic piecewise-linear function, then the distribution is re-normalized."
+      ),
+  ])
+  .describe(
+    "A transform applied to an LLM's vote distribution before it is combined."
+  )
+  .meta({ title: "VectorCompletionCalibration" });
+export type Calibration = z.infer<typeof CalibrationSchema>;
+export const CalibrationJsonSchema: JSONSchema = convert(CalibrationSchema);
+
 export const ProfileEntrySchema = z
   .object({
     weight: z
@@ -15,6 +61,11 @@ export const ProfileEntrySchema = z
       .describe(
         "If true, invert this LLM's vote distribution before combining. When omitted or false, the vote distribution is used as-is."
       ),
+    calibration: CalibrationSchema.optional()
+      .nullable()
+      .describe(
+        "If present, calibrates this LLM's vote distribution before it is inverted and combined."
+      ),
   })
   .describe(
     "An entry in a vector completion profile with an explicit weight and optional invert flag."
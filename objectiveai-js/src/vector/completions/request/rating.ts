@@ -0,0 +1,16 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../../../json_schema";
+
+export const RatingScaleSchema = z
+  .object({
+    min: z.int().describe("The lowest rating."),
+    max: z
+      .int()
+      .describe("The highest rating. Must be greater than `min`."),
+  })
+  .describe(
+    "An inclusive integer scale (e.g. 1 to 10) which each LLM rates the prompt on, instead of voting for one of the responses. The values of the scale become the responses, so each LLM's vote is a distribution over the values. The scale may have at most 101 values."
+  )
+  .meta({ title: "VectorCompletionRatingScale" });
+export type RatingScale = z.infer<typeof RatingScaleSchema>;
+export const RatingScaleJsonSchema: JSONSchema = convert(RatingScaleSchema);
@@ -1,8 +1,11 @@
 import { MessagesSchema } from "src/chat/completions/request/message";
 import { ProviderSchema } from "src/chat/completions/request/provider";
 import z from "zod";
+import { ComparisonModeSchema } from "./comparison_mode";
+import { EarlyStoppingSchema } from "./early_stopping";
 import { EnsembleSchema } from "./ensemble";
 import { ProfileSchema } from "./profile";
+import { RatingScaleSchema } from "./rating";
 import {
   BackoffMaxElapsedTimeSchema,
   FirstChunkTimeoutSchema,
@@ -49,7 +52,23 @@ export const VectorCompletionCreateParamsBaseSchema = z
       .describe(
         `${ToolsSchema.description} These are readonly and will only be useful for explaining prior tool calls or otherwise influencing behavior.`
       ),
-    responses: VectorResponsesSchema,
+    responses: VectorResponsesSchema.optional().describe(
+      `${VectorResponsesSchema.description} Must be omitted or empty if \`rating\` is set.`
+    ),
+    rating: RatingScaleSchema.optional()
+      .nullable()
+      .describe(
+        "If set, each LLM rates the prompt on this scale instead of voting for one of the responses, and the values of the scale are the responses. `responses` must then be omitted or empty. The response includes the combined rating."
+      ),
+    comparison_mode: ComparisonModeSchema.optional().nullable(),
+    early_stopping: EarlyStoppingSchema.optional().nullable(),
+    include_rationales: z
+      .boolean()
+      .optional()
+      .nullable()
+      .describe(
+        "If true, each vote carries the Ensemble LLM's rationale: its native reasoning and any synthetic reasoning it output before its response key."
+      ),
     backoff_max_elapsed_time: BackoffMaxElapsedTimeSchema.optional().nullable(),
     first_chunk_timeout: FirstChunkTimeoutSchema.optional().nullable(),
     other_chunk_timeout: OtherChunkTimeoutSchema.optional().nullable(),
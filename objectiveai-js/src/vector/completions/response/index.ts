@@ -1,4 +1,5 @@
 export * from "./ensemble";
+export * from "./rating";
 export * from "./scores";
 export * as Streaming from "./streaming";
 export * as Unary from "./unary";
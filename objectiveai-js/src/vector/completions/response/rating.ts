@@ -0,0 +1,20 @@
+import z from "zod";
+import { convert, type JSONSchema } from "../../../json_schema";
+
+export const RatingSchema = z
+  .object({
+    mean: z
+      .number()
+      .describe(
+        "The weighted mean rating: the expectation of the scores over the values of the scale."
+      ),
+    std_dev: z
+      .number()
+      .describe(
+        "The standard deviation of the scores over the values of the scale. Includes both each LLM's own uncertainty and disagreement between the LLMs."
+      ),
+  })
+  .describe("The ensemble's rating of the prompt, when the request has a rating scale.")
+  .meta({ title: "VectorCompletionRating" });
+export type Rating = z.infer<typeof RatingSchema>;
+export const RatingJsonSchema: JSONSchema = convert(RatingSchema);
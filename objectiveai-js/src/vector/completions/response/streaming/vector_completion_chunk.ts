@@ -8,6 +8,7 @@ import {
   ChatCompletionChunkSchema,
 } from "./chat_completion_chunk";
 import { UsageSchema } from "../usage";
+import { RatingSchema } from "../rating";
 import { ResponseObjectSchema } from "./response_object";
 import { merge } from "src/merge";
 import { convert, type JSONSchema } from "../../../../json_schema";
@@ -31,6 +32,15 @@ export const VectorCompletionChunkSchema = z
     ensemble: EnsembleSchema,
     object: ResponseObjectSchema,
     usage: UsageSchema.optional(),
+    skipped: z
+      .array(z.uint32())
+      .optional()
+      .describe(
+        "The flat ensemble indices of the Ensemble LLMs which were never run because early stopping decided the outcome first."
+      ),
+    rating: RatingSchema.optional().describe(
+      "The combined rating, present if the request had a rating scale."
+    ),
   })
   .describe("A chunk in a streaming vector completion response.");
 export type VectorCompletionChunk = z.infer<typeof VectorCompletionChunkSchema>;
@@ -53,12 +63,20 @@ export namespace VectorCompletionChunk {
     const ensemble = a.ensemble;
     const object = a.object;
     const [usage, usageChanged] = merge(a.usage, b.usage);
+    const skippedChanged = b.skipped !== undefined;
+    const skipped = skippedChanged
+      ? [...(a.skipped ?? []), ...b.skipped!]
+      : a.skipped;
+    const ratingChanged = b.rating !== undefined;
+    const rating = ratingChanged ? b.rating : a.rating;
     if (
       completionsChanged ||
       votesChanged ||
       scoresChanged ||
       weightsChanged ||
-      usageChanged
+      usageChanged ||
+      skippedChanged ||
+      ratingChanged
     ) {
       return [
         {
@@ -71,6 +89,8 @@ export namespace VectorCompletionChunk {
           ensemble,
           object,
           ...(usage !== undefined ? { usage } : {}),
+          ...(skipped !== undefined ? { skipped } : {}),
+          ...(rating !== undefined ? { rating } : {}),
         },
         true,
       ];
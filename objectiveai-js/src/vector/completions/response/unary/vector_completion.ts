@@ -5,6 +5,7 @@ import { ScoresSchema } from "../scores";
 import { WeightsSchema } from "../weights";
 import { EnsembleSchema } from "../ensemble";
 import { UsageSchema } from "../usage";
+import { RatingSchema } from "../rating";
 import { convert, type JSONSchema } from "../../../../json_schema";
 
 export const VectorCompletionSchema = z
@@ -26,6 +27,15 @@ export const VectorCompletionSchema = z
     ensemble: EnsembleSchema,
     object: z.literal("vector.completion"),
     usage: UsageSchema,
+    skipped: z
+      .array(z.uint32())
+      .optional()
+      .describe(
+        "The flat ensemble indices of the Ensemble LLMs which were never run because early stopping decided the outcome first."
+      ),
+    rating: RatingSchema.optional().describe(
+      "The combined rating, present if the request had a rating scale."
+    ),
   })
   .describe("A unary vector completion response.");
 export type VectorCompletion = z.infer<typeof VectorCompletionSchema>;
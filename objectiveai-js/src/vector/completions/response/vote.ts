@@ -1,6 +1,54 @@
 import z from "zod";
 import { convert, type JSONSchema } from "../../../json_schema";
 
+export const VotePermutationSchema = z
+  .object({
+    order: z
+      .array(z.uint32())
+      .describe("The index of the response presented at each position."),
+    keys: z
+      .array(z.string())
+      .describe("The key labeling the response at each position."),
+  })
+  .describe(
+    "One ordering and labeling of the responses presented to an Ensemble LLM."
+  );
+export type VotePermutation = z.infer<typeof VotePermutationSchema>;
+export const VotePermutationJsonSchema: JSONSchema = convert(
+  VotePermutationSchema
+);
+
+export const VoteComparisonSchema = z
+  .object({
+    responses: z
+      .array(z.uint32())
+      .describe("The indices of the compared responses."),
+    vote: z
+      .array(z.number())
+      .describe(
+        "The vote distribution over the compared responses, in the same order as `responses`."
+      ),
+    permutations: z
+      .array(VotePermutationSchema)
+      .optional()
+      .describe(
+        "The orderings and labels the compared responses were presented with, when the Ensemble LLM voted over multiple permutations. Orders are indices of the request's responses."
+      ),
+    rationale: z
+      .string()
+      .optional()
+      .describe(
+        "Why the Ensemble LLM voted as it did over this comparison, if the request set `include_rationales`."
+      ),
+  })
+  .describe(
+    "An Ensemble LLM's vote over one comparison of a subset of the responses."
+  );
+export type VoteComparison = z.infer<typeof VoteComparisonSchema>;
+export const VoteComparisonJsonSchema: JSONSchema = convert(
+  VoteComparisonSchema
+);
+
 export const VoteSchema = z
   .object({
     model: z
@@ -38,6 +86,30 @@ export const VoteSchema = z
       .boolean()
       .optional()
       .describe("Whether this vote was generated via RNG."),
+    permutations: z
+      .array(VotePermutationSchema)
+      .optional()
+      .describe(
+        "The orderings and labels the responses were presented with, one per permutation, when the Ensemble LLM voted over multiple permutations. The vote is the mean of the votes from each permutation."
+      ),
+    samples: z
+      .int()
+      .optional()
+      .describe(
+        "The number of completions the vote was drawn from, when the Ensemble LLM sampled multiple completions. The vote is the mean of their votes."
+      ),
+    comparisons: z
+      .array(VoteComparisonSchema)
+      .optional()
+      .describe(
+        "The votes the Ensemble LLM cast over each comparison of a subset of the responses, when the request used pairwise comparisons. The vote is the Bradley-Terry fit of these votes, after which the Ensemble LLM's calibration and inversion apply."
+      ),
+    rationale: z
+      .string()
+      .optional()
+      .describe(
+        "Why the Ensemble LLM voted as it did: its native reasoning and any synthetic reasoning it output before its response key. Present only if the request set `include_rationales`. For votes over multiple permutations or samples, the rationales of each, in order."
+      ),
   })
   .describe("A vote from an Ensemble LLM within a Vector Completion.");
 export type Vote = z.infer<typeof VoteSchema>;
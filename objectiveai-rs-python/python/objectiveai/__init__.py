@@ -0,0 +1,63 @@
+"""ObjectiveAI SDK for Python.
+
+Validation, content-addressed IDs, client-side Function compilation, and a
+client for the ObjectiveAI API, backed by the Rust ``objectiveai`` crate.
+"""
+
+from . import types
+from ._core import (
+    Client,
+    HttpError,
+    ObjectiveAIError,
+    Stream,
+    ValidationError,
+    compile_function_input_maps,
+    compile_function_input_merge,
+    compile_function_input_split,
+    compile_function_output_length,
+    compile_function_task,
+    compile_function_tasks,
+    prompt_id,
+    quality_check_branch_function,
+    quality_check_branch_scalar_function,
+    quality_check_branch_vector_function,
+    quality_check_leaf_function,
+    quality_check_leaf_scalar_function,
+    quality_check_leaf_vector_function,
+    quality_check_scalar_fields,
+    quality_check_vector_fields,
+    tools_id,
+    validate_ensemble,
+    validate_ensemble_llm,
+    validate_function_input,
+    vector_response_id,
+)
+
+__all__ = [
+    "Client",
+    "HttpError",
+    "ObjectiveAIError",
+    "Stream",
+    "ValidationError",
+    "compile_function_input_maps",
+    "compile_function_input_merge",
+    "compile_function_input_split",
+    "compile_function_output_length",
+    "compile_function_task",
+    "compile_function_tasks",
+    "prompt_id",
+    "quality_check_branch_function",
+    "quality_check_branch_scalar_function",
+    "quality_check_branch_vector_function",
+    "quality_check_leaf_function",
+    "quality_check_leaf_scalar_function",
+    "quality_check_leaf_vector_function",
+    "quality_check_scalar_fields",
+    "quality_check_vector_fields",
+    "tools_id",
+    "types",
+    "validate_ensemble",
+    "validate_ensemble_llm",
+    "validate_function_input",
+    "vector_response_id",
+]
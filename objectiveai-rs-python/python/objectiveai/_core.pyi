@@ -0,0 +1,225 @@
+from typing import Any, Dict, Generic, Iterator, List, Mapping, Optional, TypeVar
+
+from .types import (
+    ChatCompletion,
+    ChatCompletionChunk,
+    ChatCompletionCreateParams,
+    Ensemble,
+    EnsembleBase,
+    EnsembleLlm,
+    EnsembleLlmBase,
+    FunctionExecution,
+    FunctionExecutionChunk,
+    FunctionExecutionRequest,
+    GetCreditsResponse,
+    JsonValue,
+    Message,
+    Remote,
+    RichContent,
+    Tool,
+    VectorCompletion,
+    VectorCompletionChunk,
+    VectorCompletionCreateParams,
+)
+
+_T = TypeVar("_T")
+
+class ObjectiveAIError(Exception):
+    """Base class of all ObjectiveAI errors."""
+
+class ValidationError(ObjectiveAIError):
+    """A definition, input, or expression is invalid."""
+
+class HttpError(ObjectiveAIError):
+    """A request to the API failed. ``args`` is ``(message, status, body)``."""
+
+    args: tuple[str, int, JsonValue]
+
+# Ensembles
+
+def validate_ensemble_llm(llm: EnsembleLlmBase) -> EnsembleLlm: ...
+def validate_ensemble(ensemble: EnsembleBase) -> Ensemble: ...
+
+# Functions
+
+def validate_function_input(
+    function: Mapping[str, Any], input: JsonValue
+) -> Optional[bool]: ...
+def compile_function_input_maps(
+    function: Mapping[str, Any], input: JsonValue
+) -> Optional[List[List[JsonValue]]]: ...
+def compile_function_tasks(
+    function: Mapping[str, Any], input: JsonValue
+) -> List[Optional[Dict[str, Any]]]: ...
+def compile_function_task(
+    function: Mapping[str, Any],
+    index: int,
+    input: JsonValue,
+    task_outputs: List[Optional[JsonValue]],
+) -> Optional[Dict[str, Any]]: ...
+def compile_function_output_length(
+    function: Mapping[str, Any], input: JsonValue
+) -> Optional[int]: ...
+def compile_function_input_split(
+    function: Mapping[str, Any], input: JsonValue
+) -> Optional[List[JsonValue]]: ...
+def compile_function_input_merge(
+    function: Mapping[str, Any], input: List[JsonValue]
+) -> Optional[JsonValue]: ...
+
+# Quality checks
+
+def quality_check_vector_fields(fields: Mapping[str, Any]) -> None: ...
+def quality_check_scalar_fields(fields: Mapping[str, Any]) -> None: ...
+def quality_check_leaf_function(function: Mapping[str, Any]) -> None: ...
+def quality_check_branch_function(
+    function: Mapping[str, Any],
+    children: Optional[Mapping[str, Mapping[str, Any]]] = None,
+) -> None: ...
+def quality_check_leaf_scalar_function(function: Mapping[str, Any]) -> None: ...
+def quality_check_leaf_vector_function(function: Mapping[str, Any]) -> None: ...
+def quality_check_branch_scalar_function(
+    function: Mapping[str, Any],
+    children: Optional[Mapping[str, Mapping[str, Any]]] = None,
+) -> None: ...
+def quality_check_branch_vector_function(
+    function: Mapping[str, Any],
+    children: Optional[Mapping[str, Mapping[str, Any]]] = None,
+) -> None: ...
+
+# IDs
+
+def prompt_id(prompt: List[Message]) -> str: ...
+def tools_id(tools: List[Tool]) -> str: ...
+def vector_response_id(response: RichContent) -> str: ...
+
+# Client
+
+class Stream(Generic[_T]):
+    """The chunks of a streaming response."""
+
+    def __iter__(self) -> Iterator[_T]: ...
+    def __next__(self) -> _T: ...
+
+class Client:
+    """Client for the ObjectiveAI API. Calls block but release the GIL."""
+
+    def __init__(
+        self,
+        api_key: Optional[str] = None,
+        api_base: Optional[str] = None,
+        user_agent: Optional[str] = None,
+        x_title: Optional[str] = None,
+        referer: Optional[str] = None,
+        max_retries: Optional[int] = None,
+    ) -> None: ...
+
+    # Auth
+    def create_api_key(self, request: Mapping[str, Any]) -> Dict[str, Any]: ...
+    def create_openrouter_byok_api_key(
+        self, request: Mapping[str, Any]
+    ) -> Dict[str, Any]: ...
+    def disable_api_key(self, request: Mapping[str, Any]) -> Dict[str, Any]: ...
+    def delete_openrouter_byok_api_key(self) -> None: ...
+    def list_api_keys(self) -> Dict[str, Any]: ...
+    def get_openrouter_byok_api_key(self) -> Dict[str, Any]: ...
+    def get_credits(self) -> GetCreditsResponse: ...
+
+    # Chat completions
+    def create_chat_completion_unary(
+        self, params: ChatCompletionCreateParams
+    ) -> ChatCompletion: ...
+    def create_chat_completion_streaming(
+        self, params: ChatCompletionCreateParams
+    ) -> Stream[ChatCompletionChunk]: ...
+
+    # Vector completions
+    def create_vector_completion_unary(
+        self, params: VectorCompletionCreateParams
+    ) -> VectorCompletion: ...
+    def create_vector_completion_streaming(
+        self, params: VectorCompletionCreateParams
+    ) -> Stream[VectorCompletionChunk]: ...
+    def get_completion_votes(self, id: str) -> Dict[str, Any]: ...
+    def get_cache_vote(self, request: Mapping[str, Any]) -> Dict[str, Any]: ...
+
+    # Ensembles
+    def list_ensembles(self) -> Dict[str, Any]: ...
+    def get_ensemble(self, ensemble_id: str) -> Dict[str, Any]: ...
+    def get_ensemble_usage(self, ensemble_id: str) -> Dict[str, Any]: ...
+
+    # Ensemble LLMs
+    def list_ensemble_llms(self) -> Dict[str, Any]: ...
+    def get_ensemble_llm(self, ensemble_llm_id: str) -> Dict[str, Any]: ...
+    def get_ensemble_llm_usage(self, ensemble_llm_id: str) -> Dict[str, Any]: ...
+
+    # Functions
+    def list_functions(self) -> Dict[str, Any]: ...
+    def get_function(
+        self,
+        remote: Remote,
+        owner: str,
+        repository: str,
+        commit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+    def get_function_usage(
+        self,
+        fremote: Remote,
+        fowner: str,
+        frepository: str,
+        fcommit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+    def list_function_profile_pairs(self) -> Dict[str, Any]: ...
+    def get_function_profile_pair(
+        self,
+        fremote: Remote,
+        fowner: str,
+        frepository: str,
+        premote: Remote,
+        powner: str,
+        prepository: str,
+        fcommit: Optional[str] = None,
+        pcommit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+    def get_function_profile_pair_usage(
+        self,
+        fremote: Remote,
+        fowner: str,
+        frepository: str,
+        premote: Remote,
+        powner: str,
+        prepository: str,
+        fcommit: Optional[str] = None,
+        pcommit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+
+    # Function executions
+    def create_function_execution_unary(
+        self, request: FunctionExecutionRequest
+    ) -> FunctionExecution: ...
+    def create_function_execution_streaming(
+        self, request: FunctionExecutionRequest
+    ) -> Stream[FunctionExecutionChunk]: ...
+
+    # Profiles
+    def list_profiles(self) -> Dict[str, Any]: ...
+    def get_profile(
+        self,
+        remote: Remote,
+        owner: str,
+        repository: str,
+        commit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+    def get_profile_usage(
+        self,
+        premote: Remote,
+        powner: str,
+        prepository: str,
+        pcommit: Optional[str] = None,
+    ) -> Dict[str, Any]: ...
+    def compute_profile_unary(
+        self, request: Mapping[str, Any]
+    ) -> Dict[str, Any]: ...
+    def compute_profile_streaming(
+        self, request: Mapping[str, Any]
+    ) -> Stream[Dict[str, Any]]: ...
@@ -0,0 +1,311 @@
+"""Typed dictionaries for ObjectiveAI requests and responses.
+
+These mirror the JSON shapes accepted and returned by the API. Nested
+structures which are large or rarely constructed by hand are typed as
+``Dict[str, Any]``; see the API reference for their fields.
+"""
+
+from typing import Any, Dict, List, Literal, Optional, TypedDict, Union
+
+JsonValue = Any
+"""Any JSON-compatible value (dicts, lists, strings, numbers, booleans and
+``None``)."""
+
+Remote = Literal["github", "filesystem"]
+OutputMode = Literal["instruction", "json_schema", "tool_call"]
+
+# Chat messages
+
+
+class _MessageRequired(TypedDict):
+    role: Literal["developer", "system", "user", "assistant", "tool"]
+
+
+class Message(_MessageRequired, total=False):
+    """A chat message. ``content`` is a string or a list of content parts."""
+
+    content: Union[str, List[Dict[str, Any]], None]
+    name: str
+    tool_calls: List[Dict[str, Any]]
+    tool_call_id: str
+
+
+RichContent = Union[str, List[Dict[str, Any]]]
+"""A vector completion response option: text or a list of content parts."""
+
+Tool = Dict[str, Any]
+
+# Ensemble LLMs and Ensembles
+
+
+class _EnsembleLlmBaseRequired(TypedDict):
+    model: str
+
+
+class EnsembleLlmBase(_EnsembleLlmBaseRequired, total=False):
+    """An Ensemble LLM configuration before validation."""
+
+    output_mode: OutputMode
+    synthetic_reasoning: bool
+    top_logprobs: int
+    permutations: int
+    samples: int
+    labels: JsonValue
+    prompt_template: JsonValue
+    prefix_messages: List[Message]
+    suffix_messages: List[Message]
+    frequency_penalty: float
+    logit_bias: Dict[str, int]
+    max_completion_tokens: int
+    presence_penalty: float
+    stop: Union[str, List[str]]
+    temperature: float
+    top_p: float
+    max_tokens: int
+    min_p: float
+    provider: Dict[str, Any]
+    reasoning: Dict[str, Any]
+    repetition_penalty: float
+    top_a: float
+    top_k: int
+    verbosity: str
+
+
+class EnsembleLlm(EnsembleLlmBase):
+    """A validated Ensemble LLM with its content-addressed ID."""
+
+    id: str
+
+
+class EnsembleLlmBaseWithFallbacksAndCount(EnsembleLlmBase, total=False):
+    """An Ensemble member: an Ensemble LLM repeated ``count`` times, with
+    fallbacks tried if it fails."""
+
+    count: int
+    fallbacks: List[EnsembleLlmBase]
+
+
+class EnsembleBase(TypedDict):
+    """An Ensemble configuration before validation."""
+
+    llms: List[EnsembleLlmBaseWithFallbacksAndCount]
+
+
+class Ensemble(TypedDict):
+    """A validated Ensemble with its content-addressed ID."""
+
+    id: str
+    llms: List[Dict[str, Any]]
+
+
+# Usage
+
+
+class _UsageRequired(TypedDict):
+    completion_tokens: int
+    prompt_tokens: int
+    total_tokens: int
+    cost: float
+    total_cost: float
+
+
+class Usage(_UsageRequired, total=False):
+    completion_tokens_details: Dict[str, Any]
+    prompt_tokens_details: Dict[str, Any]
+    cost_details: Dict[str, Any]
+
+
+# Chat completions
+
+
+class _ChatCompletionCreateParamsRequired(TypedDict):
+    messages: List[Message]
+    model: Union[str, EnsembleLlmBase]
+
+
+class ChatCompletionCreateParams(
+    _ChatCompletionCreateParamsRequired, total=False
+):
+    provider: Dict[str, Any]
+    models: List[Union[str, EnsembleLlmBase]]
+    top_logprobs: int
+    response_format: Dict[str, Any]
+    seed: int
+    tool_choice: Union[str, Dict[str, Any]]
+    tools: List[Tool]
+    parallel_tool_calls: bool
+    prediction: Dict[str, Any]
+    backoff_max_elapsed_time: int
+    first_chunk_timeout: int
+    other_chunk_timeout: int
+
+
+class _ChatCompletionBase(TypedDict):
+    id: str
+    upstream_id: str
+    choices: List[Dict[str, Any]]
+    created: int
+    model: str
+    upstream_model: str
+
+
+class ChatCompletion(_ChatCompletionBase, total=False):
+    object: Literal["chat.completion"]
+    usage: Usage
+    service_tier: str
+    system_fingerprint: str
+    provider: str
+
+
+class ChatCompletionChunk(_ChatCompletionBase, total=False):
+    object: Literal["chat.completion.chunk"]
+    usage: Usage
+    service_tier: str
+    system_fingerprint: str
+    provider: str
+
+
+# Vector completions
+
+
+class _VectorCompletionCreateParamsRequired(TypedDict):
+    messages: List[Message]
+    ensemble: Union[str, EnsembleBase]
+    profile: List[Any]
+
+
+class VectorCompletionCreateParams(
+    _VectorCompletionCreateParamsRequired, total=False
+):
+    responses: List[RichContent]
+    retry: str
+    from_cache: bool
+    from_rng: bool
+    provider: Dict[str, Any]
+    seed: int
+    tools: List[Tool]
+    rating: Dict[str, Any]
+    comparison_mode: Dict[str, Any]
+    early_stopping: Dict[str, Any]
+    include_rationales: bool
+    backoff_max_elapsed_time: int
+    first_chunk_timeout: int
+    other_chunk_timeout: int
+
+
+class _VectorCompletionBase(TypedDict):
+    id: str
+    completions: List[Dict[str, Any]]
+    votes: List[Dict[str, Any]]
+    scores: List[float]
+    weights: List[float]
+    created: int
+    ensemble: str
+
+
+class VectorCompletion(_VectorCompletionBase, total=False):
+    object: Literal["vector.completion"]
+    usage: Usage
+    skipped: List[int]
+    rating: Dict[str, Any]
+
+
+class VectorCompletionChunk(_VectorCompletionBase, total=False):
+    object: Literal["vector.completion.chunk"]
+    usage: Usage
+    skipped: List[int]
+    rating: Dict[str, Any]
+
+
+# Function executions
+
+
+class _FunctionExecutionBodyRequired(TypedDict):
+    input: JsonValue
+
+
+class FunctionExecutionBody(_FunctionExecutionBodyRequired, total=False):
+    """The body of a Function execution request.
+
+    ``function`` and ``profile`` are given inline when the request has no
+    ``path`` for them.
+    """
+
+    function: Dict[str, Any]
+    profile: Dict[str, Any]
+    retry_token: str
+    from_cache: bool
+    from_rng: bool
+    reasoning: Dict[str, Any]
+    strategy: Dict[str, Any]
+    provider: Dict[str, Any]
+    seed: int
+    backoff_max_elapsed_time: int
+    first_chunk_timeout: int
+    other_chunk_timeout: int
+
+
+class FunctionExecutionPath(TypedDict, total=False):
+    """Where to fetch the Function (``f*``) and Profile (``p*``) from."""
+
+    fremote: Remote
+    fowner: str
+    frepository: str
+    fcommit: Optional[str]
+    premote: Remote
+    powner: str
+    prepository: str
+    pcommit: Optional[str]
+
+
+class _FunctionExecutionRequestRequired(TypedDict):
+    body: FunctionExecutionBody
+
+
+class FunctionExecutionRequest(_FunctionExecutionRequestRequired, total=False):
+    """A Function execution request. ``path`` is omitted when both the
+    Function and Profile are inline."""
+
+    path: FunctionExecutionPath
+
+
+class _FunctionExecutionBase(TypedDict):
+    id: str
+    created: int
+
+
+class FunctionExecution(_FunctionExecutionBase, total=False):
+    object: Literal["scalar.function.execution", "vector.function.execution"]
+    tasks: List[Dict[str, Any]]
+    tasks_errors: bool
+    reasoning: Dict[str, Any]
+    output: JsonValue
+    error: Dict[str, Any]
+    retry_token: str
+    function: str
+    profile: str
+    usage: Usage
+
+
+class FunctionExecutionChunk(_FunctionExecutionBase, total=False):
+    object: Literal[
+        "scalar.function.execution.chunk", "vector.function.execution.chunk"
+    ]
+    tasks: List[Dict[str, Any]]
+    tasks_errors: bool
+    reasoning: Dict[str, Any]
+    output: JsonValue
+    error: Dict[str, Any]
+    retry_token: str
+    function: str
+    profile: str
+    usage: Usage
+
+
+# Auth
+
+
+class GetCreditsResponse(TypedDict):
+    credits: float
+    total_credits_purchased: float
+    total_credits_used: float
@@ -0,0 +1,493 @@
+//! Blocking HTTP client for the ObjectiveAI API.
+
+use crate::{error, json};
+use objectiveai::blocking::{BlockingClient, BlockingStream};
+use objectiveai::functions::Remote;
+use objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::sync::{Mutex, PoisonError};
+
+/// Client for the ObjectiveAI API.
+///
+/// Requests are dicts shaped like the API's JSON bodies, and responses are
+/// returned as dicts. Calls block the calling thread but release the GIL
+/// while waiting on the network.
+#[pyclass(module = "objectiveai", frozen)]
+pub struct Client {
+    inner: BlockingClient,
+}
+
+impl Client {
+    /// Runs `f` without holding the GIL and converts its response.
+    fn call<'py, T: Serialize + Send>(
+        &self,
+        py: Python<'py>,
+        f: impl FnOnce(&BlockingClient) -> Result<T, objectiveai::HttpError> + Send,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let response = py
+            .allow_threads(|| f(&self.inner))
+            .map_err(error::http_error)?;
+        json::to_py(py, &response)
+    }
+
+    /// Opens a stream without holding the GIL.
+    fn stream<T: Serialize + 'static>(
+        &self,
+        py: Python<'_>,
+        f: impl FnOnce(
+            &BlockingClient,
+        ) -> Result<BlockingStream<T>, objectiveai::HttpError>
+        + Send,
+    ) -> PyResult<Stream> {
+        let stream = py
+            .allow_threads(|| f(&self.inner))
+            .map_err(error::http_error)?;
+        Ok(Stream::new(stream))
+    }
+}
+
+#[pymethods]
+impl Client {
+    /// Creates a client. `api_base` defaults to
+    /// `https://api.objective-ai.io`. `max_retries` overrides how many times
+    /// failed requests are retried.
+    #[new]
+    #[pyo3(signature = (
+        api_key=None,
+        api_base=None,
+        user_agent=None,
+        x_title=None,
+        referer=None,
+        max_retries=None,
+    ))]
+    fn new(
+        api_key: Option<String>,
+        api_base: Option<String>,
+        user_agent: Option<String>,
+        x_title: Option<String>,
+        referer: Option<String>,
+        max_retries: Option<u32>,
+    ) -> PyResult<Self> {
+        let mut client = objectiveai::HttpClient::new(
+            reqwest::Client::new(),
+            api_base,
+            api_key,
+            user_agent,
+            x_title,
+            referer,
+        );
+        if let Some(max_retries) = max_retries {
+            client.retry_policy.max_retries = max_retries;
+        }
+        let inner = BlockingClient::new(client)
+            .map_err(|e| error::ObjectiveAIError::new_err(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    // Auth
+
+    fn create_api_key<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.create_api_key(request))
+    }
+
+    fn create_openrouter_byok_api_key<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.create_openrouter_byok_api_key(request))
+    }
+
+    fn disable_api_key<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.disable_api_key(request))
+    }
+
+    fn delete_openrouter_byok_api_key(&self, py: Python<'_>) -> PyResult<()> {
+        py.allow_threads(|| self.inner.delete_openrouter_byok_api_key())
+            .map_err(error::http_error)
+    }
+
+    fn list_api_keys<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_api_keys())
+    }
+
+    fn get_openrouter_byok_api_key<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_openrouter_byok_api_key())
+    }
+
+    fn get_credits<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_credits())
+    }
+
+    // Chat completions
+
+    fn create_chat_completion_unary<'py>(
+        &self,
+        py: Python<'py>,
+        params: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let params = json::from_py(params)?;
+        self.call(py, |client| client.create_chat_completion_unary(params))
+    }
+
+    fn create_chat_completion_streaming(
+        &self,
+        py: Python<'_>,
+        params: &Bound<'_, PyAny>,
+    ) -> PyResult<Stream> {
+        let params = json::from_py(params)?;
+        self.stream(py, |client| {
+            client.create_chat_completion_streaming(params)
+        })
+    }
+
+    // Vector completions
+
+    fn create_vector_completion_unary<'py>(
+        &self,
+        py: Python<'py>,
+        params: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let params = json::from_py(params)?;
+        self.call(py, |client| client.create_vector_completion_unary(params))
+    }
+
+    fn create_vector_completion_streaming(
+        &self,
+        py: Python<'_>,
+        params: &Bound<'_, PyAny>,
+    ) -> PyResult<Stream> {
+        let params = json::from_py(params)?;
+        self.stream(py, |client| {
+            client.create_vector_completion_streaming(params)
+        })
+    }
+
+    fn get_completion_votes<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_completion_votes(id))
+    }
+
+    fn get_cache_vote<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.get_cache_vote(&request))
+    }
+
+    // Ensembles
+
+    fn list_ensembles<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_ensembles())
+    }
+
+    fn get_ensemble<'py>(
+        &self,
+        py: Python<'py>,
+        ensemble_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_ensemble(ensemble_id))
+    }
+
+    fn get_ensemble_usage<'py>(
+        &self,
+        py: Python<'py>,
+        ensemble_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_ensemble_usage(ensemble_id))
+    }
+
+    // Ensemble LLMs
+
+    fn list_ensemble_llms<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_ensemble_llms())
+    }
+
+    fn get_ensemble_llm<'py>(
+        &self,
+        py: Python<'py>,
+        ensemble_llm_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_ensemble_llm(ensemble_llm_id))
+    }
+
+    fn get_ensemble_llm_usage<'py>(
+        &self,
+        py: Python<'py>,
+        ensemble_llm_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.get_ensemble_llm_usage(ensemble_llm_id))
+    }
+
+    // Functions
+
+    fn list_functions<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_functions())
+    }
+
+    #[pyo3(signature = (remote, owner, repository, commit=None))]
+    fn get_function<'py>(
+        &self,
+        py: Python<'py>,
+        remote: &Bound<'py, PyAny>,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let remote: Remote = json::from_py(remote)?;
+        self.call(py, |client| {
+            client.get_function(remote, owner, repository, commit)
+        })
+    }
+
+    #[pyo3(signature = (fremote, fowner, frepository, fcommit=None))]
+    fn get_function_usage<'py>(
+        &self,
+        py: Python<'py>,
+        fremote: &Bound<'py, PyAny>,
+        fowner: &str,
+        frepository: &str,
+        fcommit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let fremote: Remote = json::from_py(fremote)?;
+        self.call(py, |client| {
+            client.get_function_usage(fremote, fowner, frepository, fcommit)
+        })
+    }
+
+    fn list_function_profile_pairs<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_function_profile_pairs())
+    }
+
+    #[pyo3(signature = (
+        fremote,
+        fowner,
+        frepository,
+        premote,
+        powner,
+        prepository,
+        fcommit=None,
+        pcommit=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn get_function_profile_pair<'py>(
+        &self,
+        py: Python<'py>,
+        fremote: &Bound<'py, PyAny>,
+        fowner: &str,
+        frepository: &str,
+        premote: &Bound<'py, PyAny>,
+        powner: &str,
+        prepository: &str,
+        fcommit: Option<&str>,
+        pcommit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let fremote: Remote = json::from_py(fremote)?;
+        let premote: Remote = json::from_py(premote)?;
+        let path = FunctionRemoteProfileRemoteRequestPath {
+            fremote,
+            fowner: fowner.to_string(),
+            frepository: frepository.to_string(),
+            fcommit: fcommit.map(str::to_string),
+            premote,
+            powner: powner.to_string(),
+            prepository: prepository.to_string(),
+            pcommit: pcommit.map(str::to_string),
+        };
+        self.call(py, |client| client.get_function_profile_pair(&path))
+    }
+
+    #[pyo3(signature = (
+        fremote,
+        fowner,
+        frepository,
+        premote,
+        powner,
+        prepository,
+        fcommit=None,
+        pcommit=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn get_function_profile_pair_usage<'py>(
+        &self,
+        py: Python<'py>,
+        fremote: &Bound<'py, PyAny>,
+        fowner: &str,
+        frepository: &str,
+        premote: &Bound<'py, PyAny>,
+        powner: &str,
+        prepository: &str,
+        fcommit: Option<&str>,
+        pcommit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let fremote: Remote = json::from_py(fremote)?;
+        let premote: Remote = json::from_py(premote)?;
+        let path = FunctionRemoteProfileRemoteRequestPath {
+            fremote,
+            fowner: fowner.to_string(),
+            frepository: frepository.to_string(),
+            fcommit: fcommit.map(str::to_string),
+            premote,
+            powner: powner.to_string(),
+            prepository: prepository.to_string(),
+            pcommit: pcommit.map(str::to_string),
+        };
+        self.call(py, |client| client.get_function_profile_pair_usage(&path))
+    }
+
+    // Function executions
+
+    fn create_function_execution_unary<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.create_function_execution_unary(request))
+    }
+
+    fn create_function_execution_streaming(
+        &self,
+        py: Python<'_>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Stream> {
+        let request = json::from_py(request)?;
+        self.stream(py, |client| {
+            client.create_function_execution_streaming(request)
+        })
+    }
+
+    // Profiles
+
+    fn list_profiles<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.call(py, |client| client.list_profiles())
+    }
+
+    #[pyo3(signature = (remote, owner, repository, commit=None))]
+    fn get_profile<'py>(
+        &self,
+        py: Python<'py>,
+        remote: &Bound<'py, PyAny>,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let remote: Remote = json::from_py(remote)?;
+        self.call(py, |client| {
+            client.get_profile(remote, owner, repository, commit)
+        })
+    }
+
+    #[pyo3(signature = (premote, powner, prepository, pcommit=None))]
+    fn get_profile_usage<'py>(
+        &self,
+        py: Python<'py>,
+        premote: &Bound<'py, PyAny>,
+        powner: &str,
+        prepository: &str,
+        pcommit: Option<&str>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let premote: Remote = json::from_py(premote)?;
+        self.call(py, |client| {
+            client.get_profile_usage(premote, powner, prepository, pcommit)
+        })
+    }
+
+    fn compute_profile_unary<'py>(
+        &self,
+        py: Python<'py>,
+        request: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request = json::from_py(request)?;
+        self.call(py, |client| client.compute_profile_unary(request))
+    }
+
+    fn compute_profile_streaming(
+        &self,
+        py: Python<'_>,
+        request: &Bound<'_, PyAny>,
+    ) -> PyResult<Stream> {
+        let request = json::from_py(request)?;
+        self.stream(py, |client| client.compute_profile_streaming(request))
+    }
+}
+
+/// The chunks of a streaming response. Iterating blocks until the next chunk
+/// arrives, releasing the GIL while waiting.
+#[pyclass(module = "objectiveai")]
+pub struct Stream {
+    chunks: Mutex<
+        Box<dyn Iterator<Item = PyResult<serde_json::Value>> + Send + 'static>,
+    >,
+}
+
+impl Stream {
+    fn new<T: Serialize + 'static>(stream: BlockingStream<T>) -> Self {
+        Self {
+            chunks: Mutex::new(Box::new(stream.map(|chunk| {
+                serde_json::to_value(chunk.map_err(error::http_error)?)
+                    .map_err(error::serialization_error)
+            }))),
+        }
+    }
+}
+
+#[pymethods]
+impl Stream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let chunk = py.allow_threads(|| {
+            self.chunks
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .next()
+        });
+        chunk
+            .map(|chunk| json::value_to_py(py, &chunk?))
+            .transpose()
+    }
+}
@@ -0,0 +1,130 @@
+//! Tests for the client and streams against a local server.
+
+#![cfg(test)]
+
+use crate::client::Client;
+use crate::error::HttpError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// Answers each connection with the next of `responses`, then closes it.
+/// Returns the server's base URL.
+fn serve(responses: Vec<String>) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_base = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for response in responses {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&mut socket);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            socket.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    api_base
+}
+
+fn response(status: u16, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {} Status\r\ncontent-type: {}\r\n\
+         content-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Creates a client for `api_base` which does not retry.
+fn client<'py>(py: Python<'py>, api_base: &str) -> Bound<'py, PyAny> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("api_base", api_base).unwrap();
+    kwargs.set_item("max_retries", 0).unwrap();
+    py.get_type::<Client>().call((), Some(&kwargs)).unwrap()
+}
+
+#[test]
+fn returns_unary_responses_as_dicts() {
+    let api_base = serve(vec![response(
+        200,
+        "application/json",
+        r#"{"credits":1.5,"total_credits_purchased":2,"total_credits_used":0.5}"#,
+    )]);
+    Python::with_gil(|py| {
+        let credits =
+            client(py, &api_base).call_method0("get_credits").unwrap();
+        let credits = credits.downcast::<PyDict>().unwrap();
+        let value = credits.get_item("credits").unwrap().unwrap();
+        assert_eq!(value.extract::<f64>().unwrap(), 1.5);
+    });
+}
+
+#[test]
+fn iterates_stream_chunks() {
+    let chunk = |id: &str| {
+        serde_json::json!({
+            "id": id,
+            "choices": [],
+            "created": 0,
+            "model": "model",
+            "upstream_id": "upstream",
+            "upstream_model": "upstream",
+            "object": "chat.completion.chunk",
+        })
+    };
+    let body = format!(
+        "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+        chunk("chtcpl-1"),
+        chunk("chtcpl-2")
+    );
+    let api_base = serve(vec![response(200, "text/event-stream", &body)]);
+    Python::with_gil(|py| {
+        let params = PyDict::new(py);
+        params.set_item("model", "openai/gpt-4o").unwrap();
+        let message = PyDict::new(py);
+        message.set_item("role", "user").unwrap();
+        message.set_item("content", "hello").unwrap();
+        params.set_item("messages", vec![message]).unwrap();
+        let stream = client(py, &api_base)
+            .call_method1("create_chat_completion_streaming", (params,))
+            .unwrap();
+        let ids = stream
+            .try_iter()
+            .unwrap()
+            .map(|chunk| chunk?.get_item("id")?.extract::<String>())
+            .collect::<PyResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids, ["chtcpl-1", "chtcpl-2"]);
+    });
+}
+
+#[test]
+fn raises_http_errors_with_status_and_body() {
+    let api_base = serve(vec![response(
+        404,
+        "application/json",
+        r#"{"code":404,"message":"not found"}"#,
+    )]);
+    Python::with_gil(|py| {
+        let err = client(py, &api_base)
+            .call_method1("get_completion_votes", ("missing",))
+            .unwrap_err();
+        assert!(err.is_instance_of::<HttpError>(py));
+        let args = err.value(py).getattr("args").unwrap();
+        assert_eq!(args.get_item(1).unwrap().extract::<u16>().unwrap(), 404);
+    });
+}
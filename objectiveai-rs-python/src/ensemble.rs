@@ -0,0 +1,39 @@
+//! Ensemble and Ensemble LLM validation.
+
+use crate::{error, json};
+use pyo3::prelude::*;
+
+/// Validates an Ensemble LLM configuration and computes its content-addressed
+/// ID.
+///
+/// Normalizes the definition (removes defaults, deduplicates), validates all
+/// fields, and returns the Ensemble LLM with its `id` populated.
+///
+/// Raises `ValidationError` if the configuration is invalid.
+#[pyfunction]
+pub fn validate_ensemble_llm<'py>(
+    llm: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let llm_base: objectiveai::ensemble_llm::EnsembleLlmBase =
+        json::from_py(llm)?;
+    let validated: objectiveai::ensemble_llm::EnsembleLlm =
+        llm_base.try_into().map_err(error::validation_error)?;
+    json::to_py(llm.py(), &validated)
+}
+
+/// Validates an Ensemble configuration and computes its content-addressed ID.
+///
+/// Validates each member LLM and returns the Ensemble with its `id` and the
+/// `id` of each member populated.
+///
+/// Raises `ValidationError` if any LLM or the Ensemble structure is invalid.
+#[pyfunction]
+pub fn validate_ensemble<'py>(
+    ensemble: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let ensemble_base: objectiveai::ensemble::EnsembleBase =
+        json::from_py(ensemble)?;
+    let validated: objectiveai::ensemble::Ensemble =
+        ensemble_base.try_into().map_err(error::validation_error)?;
+    json::to_py(ensemble.py(), &validated)
+}
@@ -0,0 +1,46 @@
+//! Python exception types.
+
+use crate::json::Json;
+use objectiveai::error::StatusError;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+create_exception!(
+    objectiveai,
+    ObjectiveAIError,
+    PyException,
+    "Base class of all ObjectiveAI errors."
+);
+
+create_exception!(
+    objectiveai,
+    ValidationError,
+    ObjectiveAIError,
+    "A definition, input, or expression is invalid."
+);
+
+create_exception!(
+    objectiveai,
+    HttpError,
+    ObjectiveAIError,
+    "A request to the API failed. `args` is `(message, status, body)`."
+);
+
+/// Raises a [`ValidationError`] with the message of `e`.
+pub fn validation_error(e: impl std::fmt::Display) -> PyErr {
+    ValidationError::new_err(e.to_string())
+}
+
+/// Raises an [`ObjectiveAIError`] for a response which could not be
+/// serialized.
+pub fn serialization_error(e: serde_json::Error) -> PyErr {
+    ObjectiveAIError::new_err(format!("serialization error: {}", e))
+}
+
+/// Raises an [`HttpError`] carrying the status and body of `e`.
+pub fn http_error(e: objectiveai::HttpError) -> PyErr {
+    let status = e.status();
+    let body = e.message().unwrap_or_default();
+    HttpError::new_err((e.to_string(), status, Json(body)))
+}
@@ -0,0 +1,144 @@
+//! Function input validation and client-side compilation.
+
+use crate::{error, json};
+use pyo3::prelude::*;
+
+/// Validates Function input against the Function's input schema.
+///
+/// Returns `None` for inline Functions, which have no schema.
+#[pyfunction]
+pub fn validate_function_input(
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<bool>> {
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    Ok(function.validate_input(&input))
+}
+
+/// Compiles a Function's `input_maps` expressions for a given input.
+///
+/// Returns `None` if the Function has no `input_maps`.
+#[pyfunction]
+pub fn compile_function_input_maps<'py>(
+    function: &Bound<'py, PyAny>,
+    input: &Bound<'py, PyAny>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = function.py();
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    function
+        .compile_input_maps(&input)
+        .map_err(error::validation_error)?
+        .map(|input_maps| json::to_py(py, &input_maps))
+        .transpose()
+}
+
+/// Compiles a Function's task expressions for a given input.
+///
+/// Returns one entry per task: `None` if the task was skipped or depends on
+/// other tasks, `{"One": task}` for non-mapped tasks, and
+/// `{"Many": [task, ...]}` for mapped tasks.
+#[pyfunction]
+pub fn compile_function_tasks<'py>(
+    function: &Bound<'py, PyAny>,
+    input: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = function.py();
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    let tasks = function
+        .compile_tasks(&input)
+        .map_err(error::validation_error)?;
+    json::to_py(py, &tasks)
+}
+
+/// Compiles a single task once the outputs of earlier tasks are known.
+///
+/// Use this for tasks with `depends_on`, which `compile_function_tasks`
+/// returns as `None`. `task_outputs` holds the output of each earlier task,
+/// or `None` if it was skipped.
+#[pyfunction]
+pub fn compile_function_task<'py>(
+    function: &Bound<'py, PyAny>,
+    index: usize,
+    input: &Bound<'py, PyAny>,
+    task_outputs: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = function.py();
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    let task_outputs: Vec<
+        Option<objectiveai::functions::expression::FunctionOutput>,
+    > = json::from_py(task_outputs)?;
+    if index >= function.tasks().len() {
+        return Err(error::validation_error(format!(
+            "task index {} out of range",
+            index
+        )));
+    }
+    let task = function
+        .compile_task(index, &input, &task_outputs)
+        .map_err(error::validation_error)?;
+    json::to_py(py, &task)
+}
+
+/// Computes the expected output length of a vector Function.
+///
+/// Returns `None` for scalar Functions and inline Functions.
+#[pyfunction]
+pub fn compile_function_output_length(
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<u64>> {
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    function
+        .compile_output_length(&input)
+        .map_err(error::validation_error)
+}
+
+/// Compiles the `input_split` expression, splitting input into sub-inputs.
+///
+/// Returns `None` for Functions without `input_split`.
+#[pyfunction]
+pub fn compile_function_input_split<'py>(
+    function: &Bound<'py, PyAny>,
+    input: &Bound<'py, PyAny>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = function.py();
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: objectiveai::functions::expression::Input =
+        json::from_py(input)?;
+    function
+        .compile_input_split(&input)
+        .map_err(error::validation_error)?
+        .map(|split| json::to_py(py, &split))
+        .transpose()
+}
+
+/// Compiles the `input_merge` expression, merging sub-inputs into one.
+///
+/// Returns `None` for Functions without `input_merge`.
+#[pyfunction]
+pub fn compile_function_input_merge<'py>(
+    function: &Bound<'py, PyAny>,
+    input: &Bound<'py, PyAny>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let py = function.py();
+    let function: objectiveai::functions::Function = json::from_py(function)?;
+    let input: Vec<objectiveai::functions::expression::Input> =
+        json::from_py(input)?;
+    function
+        .compile_input_merge(&objectiveai::functions::expression::Input::Array(
+            input,
+        ))
+        .map_err(error::validation_error)?
+        .map(|merge| json::to_py(py, &merge))
+        .transpose()
+}
@@ -0,0 +1,35 @@
+//! Content-addressed IDs for prompts, tools, and responses.
+
+use crate::json;
+use pyo3::prelude::*;
+
+/// Computes a content-addressed ID for chat messages.
+///
+/// The messages are normalized first (text parts consolidated, empty content
+/// removed), so equivalent prompts share an ID.
+#[pyfunction]
+pub fn prompt_id(prompt: &Bound<'_, PyAny>) -> PyResult<String> {
+    let mut prompt: Vec<objectiveai::chat::completions::request::Message> =
+        json::from_py(prompt)?;
+    objectiveai::chat::completions::request::prompt::prepare(&mut prompt);
+    Ok(objectiveai::chat::completions::request::prompt::id(&prompt))
+}
+
+/// Computes a content-addressed ID for a tools array.
+#[pyfunction]
+pub fn tools_id(tools: &Bound<'_, PyAny>) -> PyResult<String> {
+    let tools: Vec<objectiveai::chat::completions::request::Tool> =
+        json::from_py(tools)?;
+    Ok(objectiveai::chat::completions::request::tools::id(&tools))
+}
+
+/// Computes a content-addressed ID for a vector completion response option.
+///
+/// The content is normalized first, so equivalent responses share an ID.
+#[pyfunction]
+pub fn vector_response_id(response: &Bound<'_, PyAny>) -> PyResult<String> {
+    let mut response: objectiveai::chat::completions::request::RichContent =
+        json::from_py(response)?;
+    response.prepare();
+    Ok(response.id())
+}
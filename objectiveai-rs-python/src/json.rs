@@ -0,0 +1,126 @@
+//! Conversion between Python objects and JSON values.
+//!
+//! Requests are converted from Python to [`serde_json::Value`] and then
+//! deserialized into SDK types, so they accept exactly what the API accepts.
+//! Responses take the reverse path.
+
+use crate::error;
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A JSON value which converts into the equivalent Python object.
+pub struct Json(pub serde_json::Value);
+
+impl<'py> IntoPyObject<'py> for Json {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        value_to_py(py, &self.0)
+    }
+}
+
+/// Deserializes a Python object into an SDK type.
+pub fn from_py<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    serde_json::from_value(py_to_value(value)?).map_err(error::validation_error)
+}
+
+/// Serializes an SDK type into a Python object.
+pub fn to_py<'py, T: Serialize>(
+    py: Python<'py>,
+    value: &T,
+) -> PyResult<Bound<'py, PyAny>> {
+    let value =
+        serde_json::to_value(value).map_err(error::serialization_error)?;
+    value_to_py(py, &value)
+}
+
+/// Converts a JSON value into the equivalent Python object.
+pub fn value_to_py<'py>(
+    py: Python<'py>,
+    value: &serde_json::Value,
+) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        serde_json::Value::Null => py.None().into_bound(py),
+        serde_json::Value::Bool(b) => PyBool::new(py, *b).to_owned().into_any(),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.into_pyobject(py)?.into_any()
+            } else if let Some(u) = n.as_u64() {
+                u.into_pyobject(py)?.into_any()
+            } else {
+                n.as_f64().unwrap_or(f64::NAN).into_pyobject(py)?.into_any()
+            }
+        }
+        serde_json::Value::String(s) => PyString::new(py, s).into_any(),
+        serde_json::Value::Array(items) => PyList::new(
+            py,
+            items
+                .iter()
+                .map(|item| value_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?,
+        )?
+        .into_any(),
+        serde_json::Value::Object(fields) => {
+            let dict = PyDict::new(py);
+            for (key, value) in fields {
+                dict.set_item(key, value_to_py(py, value)?)?;
+            }
+            dict.into_any()
+        }
+    })
+}
+
+/// Converts a Python object made of dicts, lists, tuples, strings, numbers,
+/// booleans and `None` into a JSON value.
+pub fn py_to_value(value: &Bound<'_, PyAny>) -> PyResult<serde_json::Value> {
+    if value.is_none() {
+        Ok(serde_json::Value::Null)
+    } else if let Ok(b) = value.downcast::<PyBool>() {
+        Ok(serde_json::Value::Bool(b.is_true()))
+    } else if let Ok(i) = value.downcast::<PyInt>() {
+        if let Ok(i) = i.extract::<i64>() {
+            Ok(i.into())
+        } else {
+            Ok(i.extract::<u64>()
+                .map_err(|_| error::validation_error("integer out of range"))?
+                .into())
+        }
+    } else if let Ok(f) = value.downcast::<PyFloat>() {
+        serde_json::Number::from_f64(f.value())
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| {
+                error::validation_error("NaN and infinity are not valid JSON")
+            })
+    } else if let Ok(s) = value.downcast::<PyString>() {
+        Ok(serde_json::Value::String(s.extract()?))
+    } else if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut fields = serde_json::Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            let key = key.downcast::<PyString>().map_err(|_| {
+                error::validation_error("dict keys must be strings")
+            })?;
+            fields.insert(key.extract()?, py_to_value(&value)?);
+        }
+        Ok(serde_json::Value::Object(fields))
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        list.iter()
+            .map(|item| py_to_value(&item))
+            .collect::<PyResult<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    } else if let Ok(tuple) = value.downcast::<PyTuple>() {
+        tuple
+            .iter()
+            .map(|item| py_to_value(&item))
+            .collect::<PyResult<Vec<_>>>()
+            .map(serde_json::Value::Array)
+    } else {
+        Err(error::validation_error(format!(
+            "object of type {} is not JSON serializable",
+            value.get_type().name()?
+        )))
+    }
+}
@@ -0,0 +1,104 @@
+//! Tests for conversion between Python objects and JSON values.
+
+#![cfg(test)]
+
+use crate::json::{py_to_value, value_to_py};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat};
+
+/// Converts `value` to Python and back.
+fn round_trip(value: serde_json::Value) -> serde_json::Value {
+    Python::with_gil(|py| py_to_value(&value_to_py(py, &value).unwrap()))
+        .unwrap()
+}
+
+/// Evaluates a Python expression and converts its result to JSON.
+fn eval(expression: &str) -> PyResult<serde_json::Value> {
+    Python::with_gil(|py| {
+        let code = std::ffi::CString::new(expression).unwrap();
+        py_to_value(&py.eval(&code, None, None)?)
+    })
+}
+
+#[test]
+fn round_trips_scalars() {
+    for value in [
+        serde_json::json!(null),
+        serde_json::json!(true),
+        serde_json::json!(false),
+        serde_json::json!(0),
+        serde_json::json!(-42),
+        serde_json::json!(1.5),
+        serde_json::json!(-0.1),
+        serde_json::json!(""),
+        serde_json::json!("héllo"),
+    ] {
+        assert_eq!(round_trip(value.clone()), value);
+    }
+}
+
+#[test]
+fn round_trips_big_integers() {
+    for value in [
+        serde_json::json!(i64::MIN),
+        serde_json::json!(i64::MAX),
+        serde_json::json!(i64::MAX as u64 + 1),
+        serde_json::json!(u64::MAX),
+    ] {
+        assert_eq!(round_trip(value.clone()), value);
+    }
+    assert_eq!(eval("2 ** 64 - 1").unwrap(), serde_json::json!(u64::MAX));
+    assert!(eval("2 ** 64").is_err());
+    assert!(eval("-(2 ** 63) - 1").is_err());
+}
+
+#[test]
+fn round_trips_nested_containers() {
+    let value = serde_json::json!({
+        "list": [1, 2.5, "three", null, [true, { "deep": [] }]],
+        "dict": { "empty": {}, "none": null },
+        "z": 1,
+        "a": 2,
+    });
+    let converted = round_trip(value.clone());
+    assert_eq!(converted, value);
+    // key order is preserved
+    assert_eq!(
+        converted.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["list", "dict", "z", "a"]
+    );
+}
+
+#[test]
+fn converts_python_objects() {
+    assert_eq!(
+        eval("{'a': (1, 2.0), 'b': [None, True], 'c': {}}").unwrap(),
+        serde_json::json!({ "a": [1, 2.0], "b": [null, true], "c": {} }),
+    );
+    Python::with_gil(|py| {
+        let value =
+            value_to_py(py, &serde_json::json!({ "a": [1, null] })).unwrap();
+        let dict = value.downcast::<PyDict>().unwrap();
+        let list = dict.get_item("a").unwrap().unwrap();
+        assert_eq!(list.get_item(0).unwrap().extract::<i64>().unwrap(), 1);
+        assert!(list.get_item(1).unwrap().is_none());
+        assert!(
+            value_to_py(py, &serde_json::json!(1.0))
+                .unwrap()
+                .is_instance_of::<PyFloat>()
+        );
+    });
+}
+
+#[test]
+fn rejects_values_which_are_not_json() {
+    for expression in [
+        "float('nan')",
+        "float('inf')",
+        "{1: 'a'}",
+        "object()",
+        "{1, 2}",
+    ] {
+        assert!(eval(expression).is_err(), "{expression}");
+    }
+}
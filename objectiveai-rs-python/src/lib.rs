@@ -0,0 +1,107 @@
+//! Python bindings for ObjectiveAI.
+//!
+//! This crate exposes the `objectiveai` SDK to Python as the
+//! `objectiveai._core` extension module. It enables Python applications to:
+//!
+//! - Validate Ensemble LLM and Ensemble configurations
+//! - Compute content-addressed IDs (deterministic hashes)
+//! - Validate Function input and compile Function expressions
+//! - Run quality checks on Function definitions
+//! - Call the ObjectiveAI API through a blocking client
+//!
+//! # Usage
+//!
+//! This crate is built with [maturin](https://www.maturin.rs) and consumed
+//! via the `objectiveai` Python package, which re-exports these functions
+//! alongside typed dictionaries for requests and responses.
+//!
+//! Values cross the boundary as plain Python objects (dicts, lists, strings,
+//! numbers, booleans and `None`) shaped like the API's JSON.
+//!
+//! # Modules
+//!
+//! - [`ensemble`] - Ensemble and Ensemble LLM validation
+//! - [`functions`] - Function input validation and compilation
+//! - [`quality`] - Quality checks for Function definitions
+//! - [`ids`] - Prompt, tools, and response IDs
+//! - [`client`] - The blocking API client and streams
+//! - [`error`] - Python exception types
+//! - [`json`] - Conversion between Python objects and JSON values
+
+use pyo3::prelude::*;
+
+pub mod client;
+pub mod ensemble;
+pub mod error;
+pub mod functions;
+pub mod ids;
+pub mod json;
+pub mod quality;
+
+#[cfg(test)]
+mod client_tests;
+#[cfg(test)]
+mod json_tests;
+
+#[pymodule]
+fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("ObjectiveAIError", py.get_type::<error::ObjectiveAIError>())?;
+    m.add("ValidationError", py.get_type::<error::ValidationError>())?;
+    m.add("HttpError", py.get_type::<error::HttpError>())?;
+
+    m.add_function(wrap_pyfunction!(ensemble::validate_ensemble_llm, m)?)?;
+    m.add_function(wrap_pyfunction!(ensemble::validate_ensemble, m)?)?;
+
+    m.add_function(wrap_pyfunction!(functions::validate_function_input, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        functions::compile_function_input_maps,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(functions::compile_function_tasks, m)?)?;
+    m.add_function(wrap_pyfunction!(functions::compile_function_task, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        functions::compile_function_output_length,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        functions::compile_function_input_split,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        functions::compile_function_input_merge,
+        m
+    )?)?;
+
+    m.add_function(wrap_pyfunction!(quality::quality_check_vector_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(quality::quality_check_scalar_fields, m)?)?;
+    m.add_function(wrap_pyfunction!(quality::quality_check_leaf_function, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        quality::quality_check_branch_function,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        quality::quality_check_leaf_scalar_function,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        quality::quality_check_leaf_vector_function,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        quality::quality_check_branch_scalar_function,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        quality::quality_check_branch_vector_function,
+        m
+    )?)?;
+
+    m.add_function(wrap_pyfunction!(ids::prompt_id, m)?)?;
+    m.add_function(wrap_pyfunction!(ids::tools_id, m)?)?;
+    m.add_function(wrap_pyfunction!(ids::vector_response_id, m)?)?;
+
+    m.add_class::<client::Client>()?;
+    m.add_class::<client::Stream>()?;
+    Ok(())
+}
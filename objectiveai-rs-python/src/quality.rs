@@ -0,0 +1,110 @@
+//! Quality checks for Function definitions.
+//!
+//! Each check returns `None` on success and raises `ValidationError`
+//! describing the first problem found otherwise.
+
+use crate::{error, json};
+use objectiveai::functions::RemoteFunction;
+use objectiveai::functions::quality;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Deserializes the optional `"owner/repository"` → Function map of a
+/// branch check.
+fn children(
+    children: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<HashMap<String, RemoteFunction>>> {
+    children
+        .filter(|children| !children.is_none())
+        .map(json::from_py)
+        .transpose()
+}
+
+/// Validates vector Function fields (`output_length`, `input_split`,
+/// `input_merge`) by round-tripping generated example inputs.
+#[pyfunction]
+pub fn quality_check_vector_fields(fields: &Bound<'_, PyAny>) -> PyResult<()> {
+    quality::check_vector_fields(json::from_py(fields)?)
+        .map_err(error::validation_error)
+}
+
+/// Validates scalar Function fields (`input_schema`).
+#[pyfunction]
+pub fn quality_check_scalar_fields(fields: &Bound<'_, PyAny>) -> PyResult<()> {
+    quality::check_scalar_fields(json::from_py(fields)?)
+        .map_err(error::validation_error)
+}
+
+/// Quality check for a leaf Function, which contains only vector completion
+/// tasks.
+#[pyfunction]
+pub fn quality_check_leaf_function(
+    function: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    quality::check_leaf_function(&json::from_py(function)?)
+        .map_err(error::validation_error)
+}
+
+/// Quality check for a branch Function, which contains only Function and
+/// placeholder tasks.
+///
+/// `children` optionally maps `"owner/repository"` to the child Functions,
+/// to validate compiled task inputs against their input schemas.
+#[pyfunction]
+#[pyo3(signature = (function, children=None))]
+pub fn quality_check_branch_function(
+    function: &Bound<'_, PyAny>,
+    children: Option<&Bound<'_, PyAny>>,
+) -> PyResult<()> {
+    quality::check_branch_function(
+        &json::from_py(function)?,
+        self::children(children)?.as_ref(),
+    )
+    .map_err(error::validation_error)
+}
+
+/// Quality check for a leaf scalar Function.
+#[pyfunction]
+pub fn quality_check_leaf_scalar_function(
+    function: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    quality::check_leaf_scalar_function(&json::from_py(function)?)
+        .map_err(error::validation_error)
+}
+
+/// Quality check for a leaf vector Function.
+#[pyfunction]
+pub fn quality_check_leaf_vector_function(
+    function: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    quality::check_leaf_vector_function(&json::from_py(function)?)
+        .map_err(error::validation_error)
+}
+
+/// Quality check for a branch scalar Function.
+#[pyfunction]
+#[pyo3(signature = (function, children=None))]
+pub fn quality_check_branch_scalar_function(
+    function: &Bound<'_, PyAny>,
+    children: Option<&Bound<'_, PyAny>>,
+) -> PyResult<()> {
+    quality::check_branch_scalar_function(
+        &json::from_py(function)?,
+        self::children(children)?.as_ref(),
+    )
+    .map_err(error::validation_error)
+}
+
+/// Quality check for a branch vector Function.
+#[pyfunction]
+#[pyo3(signature = (function, children=None))]
+pub fn quality_check_branch_vector_function(
+    function: &Bound<'_, PyAny>,
+    children: Option<&Bound<'_, PyAny>>,
+) -> PyResult<()> {
+    quality::check_branch_vector_function(
+        &json::from_py(function)?,
+        self::children(children)?.as_ref(),
+    )
+    .map_err(error::validation_error)
+}
@@ -5,7 +5,7 @@
 //!
 //! - Validate Ensemble LLM and Ensemble configurations
 //! - Compute content-addressed IDs (deterministic hashes)
-//! - Compile Function expressions for previewing during authoring
+//! - Compile Function expressions (JMESPath, Starlark, or CEL) for previewing during authoring
 //! - Compute prompt, tools, and response IDs for caching/deduplication
 //!
 //! # Usage
@@ -165,7 +165,7 @@ pub fn compileFunctionInputMaps(
 
 /// Compiles a Function's task expressions for a given input.
 ///
-/// Evaluates all expressions (JMESPath or Starlark) in the function's tasks
+/// Evaluates all expressions (JMESPath, Starlark, or CEL) in the function's tasks
 /// using the provided input data. This is used for previewing how tasks will
 /// be executed during Function authoring.
 ///
@@ -203,6 +203,59 @@ pub fn compileFunctionTasks(
     Ok(tasks)
 }
 
+/// Compiles a single task once the outputs of earlier tasks are known.
+///
+/// Tasks with `depends_on` are returned as `null` by `compileFunctionTasks`.
+/// Use this function to preview them given the outputs of the tasks they
+/// depend on.
+///
+/// # Arguments
+///
+/// * `function` - JavaScript object representing a Function definition
+/// * `index` - Index of the task to compile
+/// * `input` - JavaScript object representing the function input
+/// * `task_outputs` - Array of earlier task outputs (`null` if skipped)
+///
+/// # Returns
+///
+/// - `null` if the task was skipped (skip expression evaluated to true)
+/// - `{ One: task }` for non-mapped tasks
+/// - `{ Many: [task, ...] }` for mapped tasks (expanded from input_maps)
+///
+/// # Errors
+///
+/// Returns an error string if `index` is out of range, expression evaluation
+/// fails, or types don't match.
+#[wasm_bindgen]
+pub fn compileFunctionTask(
+    function: JsValue,
+    index: usize,
+    input: JsValue,
+    task_outputs: JsValue,
+) -> Result<JsValue, JsValue> {
+    // deserialize
+    let function: objectiveai::functions::Function =
+        serde_wasm_bindgen::from_value(function)?;
+    let input: objectiveai::functions::expression::Input =
+        serde_wasm_bindgen::from_value(input)?;
+    let task_outputs: Vec<
+        Option<objectiveai::functions::expression::FunctionOutput>,
+    > = serde_wasm_bindgen::from_value(task_outputs)?;
+    if index >= function.tasks().len() {
+        return Err(JsValue::from_str(&format!(
+            "task index {} out of range",
+            index
+        )));
+    }
+    // compile task
+    let task = function
+        .compile_task(index, &input, &task_outputs)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    // serialize
+    let task: JsValue = serde_wasm_bindgen::to_value(&task)?;
+    Ok(task)
+}
+
 // TODO: Update for new per-task output expression architecture
 // /// Computes the final output of a Function given input and task results.
 // ///
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 /// * `name` - A user-provided name for identifying the key.
 /// * `description` - Optional description providing additional context.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateApiKeyRequest {
     /// The expiration timestamp for the API key, or `None` for a non-expiring key.
     pub expires: Option<chrono::DateTime<chrono::Utc>>,
@@ -27,6 +28,7 @@ pub struct CreateApiKeyRequest {
 /// Once disabled, the API key can no longer be used for authentication.
 /// This action is reversible only by creating a new key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DisableApiKeyRequest {
     /// The API key to disable.
     pub api_key: super::ApiKey,
@@ -37,6 +39,7 @@ pub struct DisableApiKeyRequest {
 /// This allows users to provide their own OpenRouter API key for routing
 /// requests through OpenRouter's model marketplace.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateOpenRouterByokApiKeyRequest {
     /// The OpenRouter API key to associate with the user's account.
     pub api_key: String,
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// Credits are the billing unit for ObjectiveAI. This response provides
 /// a complete view of the user's credit status.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetCreditsResponse {
     /// The current available credit balance.
     pub credits: rust_decimal::Decimal,
@@ -32,6 +33,7 @@ pub type DisableApiKeyResponse = super::ApiKeyWithMetadata;
 
 /// Response containing a list of API keys.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListApiKeyResponse {
     /// The list of API keys with their metadata and usage costs.
     pub data: Vec<ListApiKeyItem>,
@@ -42,6 +44,7 @@ pub struct ListApiKeyResponse {
 /// This extends [`ApiKeyWithMetadata`](super::ApiKeyWithMetadata) with
 /// the total cost incurred by requests using this key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListApiKeyItem {
     /// The API key and its metadata.
     #[serde(flatten)]
@@ -52,6 +55,7 @@ pub struct ListApiKeyItem {
 
 /// Response containing the user's OpenRouter BYOK API key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetOpenRouterByokApiKeyResponse {
     /// The OpenRouter API key, or `None` if not configured.
     pub api_key: Option<String>,
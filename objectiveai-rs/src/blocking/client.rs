@@ -0,0 +1,529 @@
+//! Blocking client implementation.
+
+use super::BlockingStream;
+use crate::functions::Remote;
+use crate::{HttpClient, HttpError};
+use futures::Stream;
+use std::sync::Arc;
+
+/// Blocking client for the ObjectiveAI API.
+///
+/// Mirrors the async HTTP functions of each module as methods of the same
+/// name, running them on a runtime owned by the client. Clones share the
+/// runtime.
+///
+/// Methods block the calling thread, so they must not be called from within
+/// an async context.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = BlockingClient::new(HttpClient::new(
+///     reqwest::Client::new(),
+///     None, // Use default API base
+///     Some("your-api-key"),
+///     None, // user_agent
+///     None, // x_title
+///     None, // referer
+/// ))?;
+/// for chunk in client.create_function_execution_streaming(request)? {
+///     println!("{:?}", chunk?);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockingClient {
+    /// The async client which sends the requests.
+    pub client: HttpClient,
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingClient {
+    /// Creates a blocking client with a new single-threaded runtime.
+    pub fn new(client: HttpClient) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self::with_runtime(client, Arc::new(runtime)))
+    }
+
+    /// Creates a blocking client which runs requests on `runtime`.
+    pub fn with_runtime(
+        client: HttpClient,
+        runtime: Arc<tokio::runtime::Runtime>,
+    ) -> Self {
+        Self { client, runtime }
+    }
+
+    /// Returns the runtime which runs requests.
+    pub fn runtime(&self) -> &Arc<tokio::runtime::Runtime> {
+        &self.runtime
+    }
+
+    fn stream<T, S>(
+        &self,
+        stream: impl Future<Output = Result<S, HttpError>>,
+    ) -> Result<BlockingStream<T>, HttpError>
+    where
+        S: Stream<Item = Result<T, HttpError>> + Send + 'static,
+    {
+        let stream = self.runtime.block_on(stream)?;
+        Ok(BlockingStream::new(self.runtime.clone(), Box::pin(stream)))
+    }
+
+    // Auth
+
+    /// Blocking version of [`crate::auth::create_api_key`].
+    pub fn create_api_key(
+        &self,
+        request: crate::auth::request::CreateApiKeyRequest,
+    ) -> Result<crate::auth::response::CreateApiKeyResponse, HttpError> {
+        self.runtime
+            .block_on(crate::auth::create_api_key(&self.client, request))
+    }
+
+    /// Blocking version of [`crate::auth::create_openrouter_byok_api_key`].
+    pub fn create_openrouter_byok_api_key(
+        &self,
+        request: crate::auth::request::CreateOpenRouterByokApiKeyRequest,
+    ) -> Result<
+        crate::auth::response::CreateOpenRouterByokApiKeyResponse,
+        HttpError,
+    > {
+        self.runtime
+            .block_on(crate::auth::create_openrouter_byok_api_key(
+                &self.client,
+                request,
+            ))
+    }
+
+    /// Blocking version of [`crate::auth::disable_api_key`].
+    pub fn disable_api_key(
+        &self,
+        request: crate::auth::request::DisableApiKeyRequest,
+    ) -> Result<crate::auth::response::DisableApiKeyResponse, HttpError> {
+        self.runtime
+            .block_on(crate::auth::disable_api_key(&self.client, request))
+    }
+
+    /// Blocking version of [`crate::auth::delete_openrouter_byok_api_key`].
+    pub fn delete_openrouter_byok_api_key(&self) -> Result<(), HttpError> {
+        self.runtime
+            .block_on(crate::auth::delete_openrouter_byok_api_key(&self.client))
+    }
+
+    /// Blocking version of [`crate::auth::list_api_keys`].
+    pub fn list_api_keys(
+        &self,
+    ) -> Result<crate::auth::response::ListApiKeyResponse, HttpError> {
+        self.runtime
+            .block_on(crate::auth::list_api_keys(&self.client))
+    }
+
+    /// Blocking version of [`crate::auth::get_openrouter_byok_api_key`].
+    pub fn get_openrouter_byok_api_key(
+        &self,
+    ) -> Result<crate::auth::response::GetOpenRouterByokApiKeyResponse, HttpError>
+    {
+        self.runtime
+            .block_on(crate::auth::get_openrouter_byok_api_key(&self.client))
+    }
+
+    /// Blocking version of [`crate::auth::get_credits`].
+    pub fn get_credits(
+        &self,
+    ) -> Result<crate::auth::response::GetCreditsResponse, HttpError> {
+        self.runtime
+            .block_on(crate::auth::get_credits(&self.client))
+    }
+
+    // Chat completions
+
+    /// Blocking version of
+    /// [`crate::chat::completions::create_chat_completion_unary`].
+    pub fn create_chat_completion_unary(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        crate::chat::completions::response::unary::ChatCompletion,
+        HttpError,
+    > {
+        self.runtime.block_on(
+            crate::chat::completions::create_chat_completion_unary(
+                &self.client,
+                params,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::chat::completions::create_chat_completion_streaming`].
+    pub fn create_chat_completion_streaming(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        BlockingStream<
+            crate::chat::completions::response::streaming::ChatCompletionChunk,
+        >,
+        HttpError,
+    > {
+        self.stream(crate::chat::completions::create_chat_completion_streaming(
+            &self.client,
+            params,
+        ))
+    }
+
+    // Vector completions
+
+    /// Blocking version of
+    /// [`crate::vector::completions::create_vector_completion_unary`].
+    pub fn create_vector_completion_unary(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        crate::vector::completions::response::unary::VectorCompletion,
+        HttpError,
+    > {
+        self.runtime.block_on(
+            crate::vector::completions::create_vector_completion_unary(
+                &self.client,
+                params,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::vector::completions::create_vector_completion_streaming`].
+    pub fn create_vector_completion_streaming(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        BlockingStream<
+            crate::vector::completions::response::streaming::VectorCompletionChunk,
+        >,
+        HttpError,
+    >{
+        self.stream(
+            crate::vector::completions::create_vector_completion_streaming(
+                &self.client,
+                params,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::vector::completions::cache::get_completion_votes`].
+    pub fn get_completion_votes(
+        &self,
+        id: &str,
+    ) -> Result<
+        crate::vector::completions::cache::response::CompletionVotes,
+        HttpError,
+    > {
+        self.runtime.block_on(
+            crate::vector::completions::cache::get_completion_votes(
+                &self.client,
+                id,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::vector::completions::cache::get_cache_vote`].
+    pub fn get_cache_vote(
+        &self,
+        request: &crate::vector::completions::cache::request::CacheVoteRequest<
+            '_,
+        >,
+    ) -> Result<crate::vector::completions::cache::response::CacheVote, HttpError>
+    {
+        self.runtime.block_on(
+            crate::vector::completions::cache::get_cache_vote(
+                &self.client,
+                request,
+            ),
+        )
+    }
+
+    // Ensembles
+
+    /// Blocking version of [`crate::ensemble::list_ensembles`].
+    pub fn list_ensembles(
+        &self,
+    ) -> Result<crate::ensemble::response::ListEnsemble, HttpError> {
+        self.runtime
+            .block_on(crate::ensemble::list_ensembles(&self.client))
+    }
+
+    /// Blocking version of [`crate::ensemble::get_ensemble`].
+    pub fn get_ensemble(
+        &self,
+        ensemble_id: &str,
+    ) -> Result<crate::ensemble::response::GetEnsemble, HttpError> {
+        self.runtime
+            .block_on(crate::ensemble::get_ensemble(&self.client, ensemble_id))
+    }
+
+    /// Blocking version of [`crate::ensemble::get_ensemble_usage`].
+    pub fn get_ensemble_usage(
+        &self,
+        ensemble_id: &str,
+    ) -> Result<crate::ensemble::response::UsageEnsemble, HttpError> {
+        self.runtime.block_on(crate::ensemble::get_ensemble_usage(
+            &self.client,
+            ensemble_id,
+        ))
+    }
+
+    // Ensemble LLMs
+
+    /// Blocking version of [`crate::ensemble_llm::list_ensemble_llms`].
+    pub fn list_ensemble_llms(
+        &self,
+    ) -> Result<crate::ensemble_llm::response::ListEnsembleLlm, HttpError> {
+        self.runtime
+            .block_on(crate::ensemble_llm::list_ensemble_llms(&self.client))
+    }
+
+    /// Blocking version of [`crate::ensemble_llm::get_ensemble_llm`].
+    pub fn get_ensemble_llm(
+        &self,
+        ensemble_llm_id: &str,
+    ) -> Result<crate::ensemble_llm::response::GetEnsembleLlm, HttpError> {
+        self.runtime.block_on(crate::ensemble_llm::get_ensemble_llm(
+            &self.client,
+            ensemble_llm_id,
+        ))
+    }
+
+    /// Blocking version of [`crate::ensemble_llm::get_ensemble_llm_usage`].
+    pub fn get_ensemble_llm_usage(
+        &self,
+        ensemble_llm_id: &str,
+    ) -> Result<crate::ensemble_llm::response::UsageEnsembleLlm, HttpError>
+    {
+        self.runtime
+            .block_on(crate::ensemble_llm::get_ensemble_llm_usage(
+                &self.client,
+                ensemble_llm_id,
+            ))
+    }
+
+    // Functions
+
+    /// Blocking version of [`crate::functions::list_functions`].
+    pub fn list_functions(
+        &self,
+    ) -> Result<crate::functions::response::ListFunction, HttpError> {
+        self.runtime
+            .block_on(crate::functions::list_functions(&self.client))
+    }
+
+    /// Blocking version of [`crate::functions::get_function`].
+    pub fn get_function(
+        &self,
+        remote: Remote,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<crate::functions::response::GetFunction, HttpError> {
+        self.runtime.block_on(crate::functions::get_function(
+            &self.client,
+            remote,
+            owner,
+            repository,
+            commit,
+        ))
+    }
+
+    /// Blocking version of [`crate::functions::get_function_usage`].
+    pub fn get_function_usage(
+        &self,
+        fremote: Remote,
+        fowner: &str,
+        frepository: &str,
+        fcommit: Option<&str>,
+    ) -> Result<crate::functions::response::UsageFunction, HttpError> {
+        self.runtime.block_on(crate::functions::get_function_usage(
+            &self.client,
+            fremote,
+            fowner,
+            frepository,
+            fcommit,
+        ))
+    }
+
+    /// Blocking version of [`crate::functions::list_function_profile_pairs`].
+    pub fn list_function_profile_pairs(
+        &self,
+    ) -> Result<crate::functions::response::ListFunctionProfilePair, HttpError>
+    {
+        self.runtime
+            .block_on(crate::functions::list_function_profile_pairs(
+                &self.client,
+            ))
+    }
+
+    /// Blocking version of [`crate::functions::get_function_profile_pair`],
+    /// with the Function and Profile given as a request path.
+    pub fn get_function_profile_pair(
+        &self,
+        path: &crate::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+    ) -> Result<crate::functions::response::GetFunctionProfilePair, HttpError>
+    {
+        self.runtime.block_on(crate::functions::get_function_profile_pair(
+            &self.client,
+            path.fremote,
+            &path.fowner,
+            &path.frepository,
+            path.fcommit.as_deref(),
+            path.premote,
+            &path.powner,
+            &path.prepository,
+            path.pcommit.as_deref(),
+        ))
+    }
+
+    /// Blocking version of
+    /// [`crate::functions::get_function_profile_pair_usage`], with the
+    /// Function and Profile given as a request path.
+    pub fn get_function_profile_pair_usage(
+        &self,
+        path: &crate::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+    ) -> Result<crate::functions::response::UsageFunctionProfilePair, HttpError>
+    {
+        self.runtime.block_on(crate::functions::get_function_profile_pair_usage(
+            &self.client,
+            path.fremote,
+            &path.fowner,
+            &path.frepository,
+            path.fcommit.as_deref(),
+            path.premote,
+            &path.powner,
+            &path.prepository,
+            path.pcommit.as_deref(),
+        ))
+    }
+
+    // Function executions
+
+    /// Blocking version of
+    /// [`crate::functions::executions::create_function_execution_unary`].
+    pub fn create_function_execution_unary(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> Result<
+        crate::functions::executions::response::unary::FunctionExecution,
+        HttpError,
+    > {
+        self.runtime.block_on(
+            crate::functions::executions::create_function_execution_unary(
+                &self.client,
+                request,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::functions::executions::create_function_execution_streaming`].
+    pub fn create_function_execution_streaming(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> Result<
+        BlockingStream<
+            crate::functions::executions::response::streaming::FunctionExecutionChunk,
+        >,
+        HttpError,
+    >{
+        self.stream(
+            crate::functions::executions::create_function_execution_streaming(
+                &self.client,
+                request,
+            ),
+        )
+    }
+
+    // Profiles
+
+    /// Blocking version of [`crate::functions::profiles::list_profiles`].
+    pub fn list_profiles(
+        &self,
+    ) -> Result<crate::functions::profiles::response::ListProfile, HttpError>
+    {
+        self.runtime
+            .block_on(crate::functions::profiles::list_profiles(&self.client))
+    }
+
+    /// Blocking version of [`crate::functions::profiles::get_profile`].
+    pub fn get_profile(
+        &self,
+        remote: Remote,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<crate::functions::profiles::response::GetProfile, HttpError>
+    {
+        self.runtime
+            .block_on(crate::functions::profiles::get_profile(
+                &self.client,
+                remote,
+                owner,
+                repository,
+                commit,
+            ))
+    }
+
+    /// Blocking version of [`crate::functions::profiles::get_profile_usage`].
+    pub fn get_profile_usage(
+        &self,
+        premote: Remote,
+        powner: &str,
+        prepository: &str,
+        pcommit: Option<&str>,
+    ) -> Result<crate::functions::profiles::response::UsageProfile, HttpError>
+    {
+        self.runtime
+            .block_on(crate::functions::profiles::get_profile_usage(
+                &self.client,
+                premote,
+                powner,
+                prepository,
+                pcommit,
+            ))
+    }
+
+    /// Blocking version of
+    /// [`crate::functions::profiles::computations::compute_profile_unary`].
+    pub fn compute_profile_unary(
+        &self,
+        request: crate::functions::profiles::computations::request::Request,
+    ) -> Result<
+        crate::functions::profiles::computations::response::unary::FunctionProfileComputation,
+        HttpError,
+    >{
+        self.runtime.block_on(
+            crate::functions::profiles::computations::compute_profile_unary(
+                &self.client,
+                request,
+            ),
+        )
+    }
+
+    /// Blocking version of
+    /// [`crate::functions::profiles::computations::compute_profile_streaming`].
+    pub fn compute_profile_streaming(
+        &self,
+        request: crate::functions::profiles::computations::request::Request,
+    ) -> Result<
+        BlockingStream<
+            crate::functions::profiles::computations::response::streaming::FunctionProfileComputationChunk,
+        >,
+        HttpError,
+    >{
+        self.stream(
+            crate::functions::profiles::computations::compute_profile_streaming(
+                &self.client,
+                request,
+            ),
+        )
+    }
+}
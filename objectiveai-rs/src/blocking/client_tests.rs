@@ -0,0 +1,70 @@
+//! Tests for the blocking client.
+
+#![cfg(test)]
+
+use super::BlockingClient;
+use crate::http::test_server::{TestServer, json_response, sse_response};
+use crate::{HttpClient, HttpError, RetryPolicy};
+
+fn server(responses: Vec<String>) -> (tokio::runtime::Runtime, TestServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(TestServer::start(responses));
+    (runtime, server)
+}
+
+fn client(server: &TestServer) -> BlockingClient {
+    BlockingClient::new(
+        HttpClient::new(
+            reqwest::Client::new(),
+            Some(server.api_base.clone()),
+            None::<String>,
+            None::<String>,
+            None::<String>,
+            None::<String>,
+        )
+        .with_retry_policy(RetryPolicy::none()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn sends_unary_requests() {
+    let (_runtime, server) = server(vec![json_response(
+        200,
+        r#"{"credits":1.5,"total_credits_purchased":2,"total_credits_used":0.5}"#,
+    )]);
+    let credits = client(&server).get_credits().unwrap();
+    assert_eq!(credits.credits, rust_decimal::dec!(1.5));
+    assert_eq!(
+        server.requests.lock().unwrap()[0],
+        "GET /auth/credits\n".to_string()
+    );
+}
+
+#[test]
+fn iterates_streaming_chunks() {
+    let chunk = serde_json::json!({
+        "id": "chtcpl-1",
+        "choices": [],
+        "created": 0,
+        "model": "model",
+        "upstream_id": "upstream-1",
+        "upstream_model": "upstream",
+        "object": "chat.completion.chunk",
+    })
+    .to_string();
+    let (_runtime, server) =
+        server(vec![sse_response(&[&chunk, &chunk, "[DONE]"])]);
+    let params = serde_json::from_value(serde_json::json!({
+        "messages": [{ "role": "user", "content": "hello" }],
+        "model": "openai/gpt-4o",
+    }))
+    .unwrap();
+    let chunks = client(&server)
+        .create_chat_completion_streaming(params)
+        .unwrap()
+        .collect::<Result<Vec<_>, HttpError>>()
+        .unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].id, "chtcpl-1");
+}
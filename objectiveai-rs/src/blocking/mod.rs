@@ -0,0 +1,24 @@
+//! Blocking client for the ObjectiveAI API.
+//!
+//! This module wraps [`HttpClient`](crate::HttpClient) for callers which are
+//! not async. Each [`BlockingClient`] owns a Tokio runtime and blocks the
+//! calling thread until the request completes. Streaming endpoints return a
+//! [`BlockingStream`], an [`Iterator`] over the chunks.
+//!
+//! # Feature Flag
+//!
+//! This module is only available when the `blocking` feature is enabled.
+//!
+//! # Components
+//!
+//! - [`BlockingClient`] - The blocking counterpart of the async HTTP functions
+//! - [`BlockingStream`] - An iterator over streamed chunks
+
+mod client;
+mod stream;
+
+pub use client::*;
+pub use stream::*;
+
+#[cfg(test)]
+mod client_tests;
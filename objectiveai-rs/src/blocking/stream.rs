@@ -0,0 +1,39 @@
+//! Iterator over the chunks of a streaming response.
+
+use crate::{ClientStream, HttpError};
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// A streaming response, iterated by blocking on the runtime of the
+/// [`BlockingClient`](super::BlockingClient) which created it.
+///
+/// Each call to [`next`](Iterator::next) blocks until the next chunk arrives.
+/// Iteration ends when the stream completes.
+pub struct BlockingStream<T> {
+    runtime: Arc<tokio::runtime::Runtime>,
+    stream: ClientStream<T, HttpError>,
+}
+
+impl<T> BlockingStream<T> {
+    /// Wraps `stream`, driving it on `runtime`.
+    pub fn new(
+        runtime: Arc<tokio::runtime::Runtime>,
+        stream: ClientStream<T, HttpError>,
+    ) -> Self {
+        Self { runtime, stream }
+    }
+}
+
+impl<T> Iterator for BlockingStream<T> {
+    type Item = Result<T, HttpError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> std::fmt::Debug for BlockingStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingStream").finish_non_exhaustive()
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Parameters for creating a chat completion.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionCreateParams {
     /// The conversation messages.
     pub messages: Vec<super::Message>,
@@ -26,6 +26,7 @@ pub mod prompt {
 
 /// A message in the conversation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "role")]
 pub enum Message {
     /// A developer message (similar to system, but from the developer).
@@ -93,6 +94,7 @@ impl FromStarlarkValue for Message {
 /// where message content can be computed from the function input at runtime.
 /// Supports both JMESPath and Starlark expressions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "role")]
 pub enum MessageExpression {
     #[serde(rename = "developer")]
@@ -168,6 +170,7 @@ impl FromStarlarkValue for MessageExpression {
 
 /// A developer message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeveloperMessage {
     /// The message content.
     pub content: SimpleContent,
@@ -211,6 +214,7 @@ impl FromStarlarkValue for DeveloperMessage {
 
 /// Expression variant of [`DeveloperMessage`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeveloperMessageExpression {
     /// The message content expression.
     pub content: functions::expression::WithExpression<SimpleContentExpression>,
@@ -264,6 +268,7 @@ impl FromStarlarkValue for DeveloperMessageExpression {
 
 /// A system message setting context or instructions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SystemMessage {
     /// The message content.
     pub content: SimpleContent,
@@ -307,6 +312,7 @@ impl FromStarlarkValue for SystemMessage {
 
 /// Expression variant of [`SystemMessage`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SystemMessageExpression {
     /// The message content expression.
     pub content: functions::expression::WithExpression<SimpleContentExpression>,
@@ -360,6 +366,7 @@ impl FromStarlarkValue for SystemMessageExpression {
 
 /// A user message from the end user.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UserMessage {
     /// The message content (supports text, images, audio, video, files).
     pub content: RichContent,
@@ -403,6 +410,7 @@ impl FromStarlarkValue for UserMessage {
 
 /// Expression variant of [`UserMessage`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UserMessageExpression {
     /// The message content expression.
     pub content: functions::expression::WithExpression<RichContentExpression>,
@@ -456,6 +464,7 @@ impl FromStarlarkValue for UserMessageExpression {
 
 /// A tool message containing the result of a tool call.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolMessage {
     /// The content of the tool response.
     pub content: RichContent,
@@ -498,6 +507,7 @@ impl FromStarlarkValue for ToolMessage {
 
 /// Expression variant of [`ToolMessage`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolMessageExpression {
     /// The content expression.
     pub content: functions::expression::WithExpression<RichContentExpression>,
@@ -548,6 +558,7 @@ impl FromStarlarkValue for ToolMessageExpression {
 
 /// An assistant message (model's previous response).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantMessage {
     /// The message content, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -621,6 +632,7 @@ impl FromStarlarkValue for AssistantMessage {
 
 /// Expression variant of [`AssistantMessage`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantMessageExpression {
     /// The content expression.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -771,6 +783,7 @@ impl FromStarlarkValue for AssistantMessageExpression {
 
 /// A tool call made by the assistant.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AssistantToolCall {
     /// A function call with an ID and function details.
@@ -821,6 +834,7 @@ impl FromStarlarkValue for AssistantToolCall {
 
 /// Expression variant of [`AssistantToolCall`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AssistantToolCallExpression {
     /// A function call expression.
@@ -869,6 +883,7 @@ impl FromStarlarkValue for AssistantToolCallExpression {
 
 /// Details of a function call made by the assistant.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolCallFunction {
     /// The name of the function to call.
     pub name: String,
@@ -911,6 +926,7 @@ impl FromStarlarkValue for AssistantToolCallFunction {
 
 /// Expression variant of [`AssistantToolCallFunction`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AssistantToolCallFunctionExpression {
     /// The function name expression.
     pub name: functions::expression::WithExpression<String>,
@@ -943,6 +959,7 @@ impl FromStarlarkValue for AssistantToolCallFunctionExpression {
 
 /// Simple text content for system/developer messages.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum SimpleContent {
     /// Plain text content.
@@ -989,6 +1006,7 @@ impl FromStarlarkValue for SimpleContent {
 
 /// Expression variant of [`SimpleContent`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum SimpleContentExpression {
     /// Plain text content.
@@ -1041,6 +1059,7 @@ impl FromStarlarkValue for SimpleContentExpression {
 
 /// A part of simple text content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SimpleContentPart {
     /// A text part.
@@ -1067,6 +1086,7 @@ impl FromStarlarkValue for SimpleContentPart {
 
 /// Expression variant of [`SimpleContentPart`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SimpleContentPartExpression {
     /// A text part expression.
@@ -1104,6 +1124,7 @@ impl FromStarlarkValue for SimpleContentPartExpression {
 
 /// Rich content for user/assistant messages (supports multimodal input).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum RichContent {
     /// Plain text content.
@@ -1196,6 +1217,7 @@ impl FromStarlarkValue for RichContent {
 
 /// Expression variant of [`RichContent`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum RichContentExpression {
     /// Plain text content.
@@ -1246,6 +1268,7 @@ impl FromStarlarkValue for RichContentExpression {
 
 /// A part of rich content.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum RichContentPart {
     /// Text content.
@@ -1384,6 +1407,7 @@ impl FromStarlarkValue for RichContentPart {
 
 /// Expression variant of [`RichContentPart`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum RichContentPartExpression {
     Text {
@@ -1469,6 +1493,7 @@ impl FromStarlarkValue for RichContentPartExpression {
 
 /// An image URL for multimodal input.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ImageUrl {
     /// The URL of the image (can be a data URL or HTTP URL).
     pub url: String,
@@ -1531,6 +1556,7 @@ impl FromStarlarkValue for ImageUrl {
 
 /// Detail level for image processing.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ImageUrlDetail {
     /// Let the model decide the detail level.
     #[serde(rename = "auto")]
@@ -1572,6 +1598,7 @@ impl FromStarlarkValue for ImageUrlDetail {
 
 /// Audio input for multimodal messages.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InputAudio {
     /// Base64-encoded audio data.
     pub data: String,
@@ -1626,6 +1653,7 @@ impl FromStarlarkValue for InputAudio {
 
 /// A video URL for multimodal input.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VideoUrl {
     /// The URL of the video.
     pub url: String,
@@ -1671,6 +1699,7 @@ impl FromStarlarkValue for VideoUrl {
 
 /// A file attachment for multimodal input.
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct File {
     /// Base64-encoded file data.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 /// when they are successfully used. "Previously used" means the ID exists in
 /// ObjectiveAI's database from any successful use by anyone.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Model {
     /// The content-addressed ID of an Ensemble LLM stored in ObjectiveAI's database.
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Provider routing and selection preferences.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Provider {
     /// Whether to allow providers to collect data.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -33,6 +34,7 @@ pub struct Provider {
 
 /// Data collection policy for providers.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderDataCollection {
     /// Do not allow data collection.
@@ -43,6 +45,7 @@ pub enum ProviderDataCollection {
 
 /// How to sort/prioritize providers.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderSort {
     /// Prioritize by price (cheapest first).
@@ -55,6 +58,7 @@ pub enum ProviderSort {
 
 /// Maximum price constraints per token type.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ProviderMaxPrice {
     /// Maximum price per prompt token.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// The format of the model's response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ResponseFormat {
     /// Plain text response (default).
@@ -20,6 +21,7 @@ pub enum ResponseFormat {
 
 /// A JSON schema for structured output.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct JsonSchema {
     /// The name of the schema.
     pub name: String,
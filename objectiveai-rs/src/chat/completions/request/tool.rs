@@ -21,6 +21,7 @@ pub mod tools {
 
 /// A tool that can be called by the model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Tool {
     /// A function tool.
@@ -49,6 +50,7 @@ impl FromStarlarkValue for Tool {
 
 /// Expression variant of [`Tool`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ToolExpression {
     /// A function tool expression.
@@ -96,6 +98,7 @@ impl FromStarlarkValue for ToolExpression {
 
 /// A function tool definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionTool {
     /// The name of the function.
     pub name: String,
@@ -150,6 +153,7 @@ impl FromStarlarkValue for FunctionTool {
 
 /// Expression variant of [`FunctionTool`] for dynamic content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionToolExpression {
     /// The function name expression.
     pub name: functions::expression::WithExpression<String>,
@@ -279,6 +283,7 @@ impl FromStarlarkValue for FunctionToolExpression {
 /// This allows JSON values to contain expressions (JMESPath or Starlark)
 /// that are evaluated at runtime.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ValueExpression {
     /// A null value.
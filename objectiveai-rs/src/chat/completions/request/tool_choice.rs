@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Controls how the model uses tools.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ToolChoice {
     /// The model will not call any tools.
     #[serde(rename = "none")]
@@ -21,6 +22,7 @@ pub enum ToolChoice {
 
 /// Specifies a specific function the model must call.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ToolChoiceFunction {
     /// A specific function to call.
@@ -31,6 +33,7 @@ pub enum ToolChoiceFunction {
 
 /// The function name for a forced tool choice.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolChoiceFunctionFunction {
     /// The name of the function to call.
     pub name: String,
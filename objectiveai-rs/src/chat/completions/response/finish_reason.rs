@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 #[derive(
     Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum FinishReason {
     /// The model reached a natural stop point or stop sequence.
     #[serde(rename = "stop")]
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Used when models generate images as part of their response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Image {
     /// Image provided as a URL.
@@ -25,6 +26,7 @@ impl Default for Image {
 
 /// URL reference to an image.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ImageUrl {
     /// URL where the image can be accessed.
     pub url: String,
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Log probabilities for generated tokens.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Logprobs {
     /// Log probabilities for content tokens.
     pub content: Option<Vec<Logprob>>,
@@ -37,6 +38,7 @@ impl Logprobs {
 
 /// Log probability information for a single token.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Logprob {
     /// The token string.
     pub token: String,
@@ -50,6 +52,7 @@ pub struct Logprob {
 
 /// A top alternative token with its log probability.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TopLogprob {
     /// The token string.
     pub token: String,
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// The role of a message in a response (always "assistant").
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Role {
     /// The assistant role.
     #[serde(rename = "assistant")]
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 /// accumulated into a complete [`ChatCompletion`](response::unary::ChatCompletion)
 /// using the [`push`](Self::push) method.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionChunk {
     /// ObjectiveAI's unique identifier for this completion.
     pub id: String,
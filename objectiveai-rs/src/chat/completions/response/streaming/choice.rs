@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A choice in a streaming chat completion chunk.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Choice {
     /// The content delta for this choice.
     pub delta: super::Delta,
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Each field contains only the new content since the last delta.
 /// Deltas can be accumulated using the [`push`](Self::push) method.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Delta {
     /// New content text since the last delta.
     #[serde(skip_serializing_if = "Option::is_none")]
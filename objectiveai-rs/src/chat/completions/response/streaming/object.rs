@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// The object type for streaming chat completion chunks.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Object {
     /// A chat completion chunk object.
     #[serde(rename = "chat.completion.chunk")]
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// A tool call delta in a streaming response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolCall {
     /// The index of this tool call.
     pub index: u64,
@@ -49,6 +50,7 @@ impl ToolCall {
 
 /// The type of tool call.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ToolCallType {
     /// A function call.
     #[serde(rename = "function")]
@@ -58,6 +60,7 @@ pub enum ToolCallType {
 
 /// Function call details in a streaming tool call.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolCallFunction {
     /// The function name (only present in the first delta).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A complete chat completion response.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletion {
     /// ObjectiveAI's unique identifier for this completion.
     pub id: String,
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A generated choice in a chat completion response.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Choice {
     /// The generated message.
     pub message: super::Message,
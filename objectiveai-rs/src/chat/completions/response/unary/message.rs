@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A message generated by the model.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Message {
     /// The text content of the message.
     pub content: Option<String>,
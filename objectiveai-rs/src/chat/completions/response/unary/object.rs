@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// The object type for chat completion responses.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Object {
     /// A chat completion object.
     #[serde(rename = "chat.completion")]
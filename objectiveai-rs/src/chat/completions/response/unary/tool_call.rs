@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// A tool call made by the model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ToolCall {
     /// A function call.
@@ -49,6 +50,7 @@ impl From<response::streaming::ToolCall> for ToolCall {
 
 /// A function call with its name and arguments.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolCallFunction {
     /// The name of the function to call.
     pub name: String,
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// Token usage and cost statistics for a completion.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Usage {
     /// Number of tokens in the completion.
     pub completion_tokens: u64,
@@ -79,6 +80,7 @@ impl Usage {
 
 /// Detailed breakdown of completion token usage.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompletionTokensDetails {
     /// Tokens from accepted predictions (speculative decoding).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -123,6 +125,7 @@ impl CompletionTokensDetails {
 
 /// Detailed breakdown of prompt token usage.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PromptTokensDetails {
     /// Audio input tokens.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -161,6 +164,7 @@ impl PromptTokensDetails {
 
 /// Detailed cost breakdown.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CostDetails {
     /// Cost charged by the immediate upstream (e.g., OpenRouter).
     pub upstream_inference_cost: rust_decimal::Decimal,
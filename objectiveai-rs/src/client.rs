@@ -0,0 +1,196 @@
+//! Transport-agnostic client for the ObjectiveAI API.
+
+use futures::Stream;
+use std::pin::Pin;
+
+/// A boxed stream of chunks returned by a [`Client`].
+pub type ClientStream<T, E> =
+    Pin<Box<dyn Stream<Item = Result<T, E>> + Send + 'static>>;
+
+/// A client which can create chat completions, vector completions, and
+/// Function executions.
+///
+/// Implemented by [`HttpClient`](crate::HttpClient), which sends requests to
+/// an ObjectiveAI server, and by `objectiveai-api`'s in-process client, which
+/// runs the pipelines directly. Code written against this trait works with
+/// either.
+///
+/// The `stream` field of each request is ignored; the method determines
+/// whether the response is streamed.
+pub trait Client: Send + Sync {
+    /// The error returned by the client.
+    type Error: std::error::Error
+        + crate::error::StatusError
+        + Send
+        + Sync
+        + 'static;
+
+    /// Creates a chat completion and waits for the complete response.
+    fn create_chat_completion_unary(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            crate::chat::completions::response::unary::ChatCompletion,
+            Self::Error,
+        >,
+    > + Send;
+
+    /// Creates a chat completion and streams its chunks.
+    fn create_chat_completion_streaming(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            ClientStream<
+                crate::chat::completions::response::streaming::ChatCompletionChunk,
+                Self::Error,
+            >,
+            Self::Error,
+        >,
+    > + Send;
+
+    /// Creates a vector completion and waits for the complete response.
+    fn create_vector_completion_unary(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            crate::vector::completions::response::unary::VectorCompletion,
+            Self::Error,
+        >,
+    > + Send;
+
+    /// Creates a vector completion and streams its chunks.
+    fn create_vector_completion_streaming(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            ClientStream<
+                crate::vector::completions::response::streaming::VectorCompletionChunk,
+                Self::Error,
+            >,
+            Self::Error,
+        >,
+    > + Send;
+
+    /// Executes a Function and waits for the complete response.
+    fn create_function_execution_unary(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> impl Future<
+        Output = Result<
+            crate::functions::executions::response::unary::FunctionExecution,
+            Self::Error,
+        >,
+    > + Send;
+
+    /// Executes a Function and streams its chunks.
+    fn create_function_execution_streaming(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> impl Future<
+        Output = Result<
+            ClientStream<
+                crate::functions::executions::response::streaming::FunctionExecutionChunk,
+                Self::Error,
+            >,
+            Self::Error,
+        >,
+    > + Send;
+}
+
+#[cfg(feature = "http")]
+impl Client for crate::HttpClient {
+    type Error = crate::HttpError;
+
+    async fn create_chat_completion_unary(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        crate::chat::completions::response::unary::ChatCompletion,
+        Self::Error,
+    > {
+        crate::chat::completions::create_chat_completion_unary(self, params)
+            .await
+    }
+
+    async fn create_chat_completion_streaming(
+        &self,
+        params: crate::chat::completions::request::ChatCompletionCreateParams,
+    ) -> Result<
+        ClientStream<
+            crate::chat::completions::response::streaming::ChatCompletionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    > {
+        let stream =
+            crate::chat::completions::create_chat_completion_streaming(
+                self, params,
+            )
+            .await?;
+        Ok(Box::pin(stream))
+    }
+
+    async fn create_vector_completion_unary(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        crate::vector::completions::response::unary::VectorCompletion,
+        Self::Error,
+    > {
+        crate::vector::completions::create_vector_completion_unary(self, params)
+            .await
+    }
+
+    async fn create_vector_completion_streaming(
+        &self,
+        params: crate::vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        ClientStream<
+            crate::vector::completions::response::streaming::VectorCompletionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    >{
+        let stream =
+            crate::vector::completions::create_vector_completion_streaming(
+                self, params,
+            )
+            .await?;
+        Ok(Box::pin(stream))
+    }
+
+    async fn create_function_execution_unary(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> Result<
+        crate::functions::executions::response::unary::FunctionExecution,
+        Self::Error,
+    > {
+        crate::functions::executions::create_function_execution_unary(
+            self, request,
+        )
+        .await
+    }
+
+    async fn create_function_execution_streaming(
+        &self,
+        request: crate::functions::executions::request::Request,
+    ) -> Result<
+        ClientStream<
+            crate::functions::executions::response::streaming::FunctionExecutionChunk,
+            Self::Error,
+        >,
+        Self::Error,
+    >{
+        let stream =
+            crate::functions::executions::create_function_execution_streaming(
+                self, request,
+            )
+            .await?;
+        Ok(Box::pin(stream))
+    }
+}
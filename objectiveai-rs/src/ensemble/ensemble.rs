@@ -1,7 +1,9 @@
 //! Core Ensemble types and validation logic.
 
 use crate::ensemble_llm;
-use crate::vector::completions::request::{Profile, ProfileEntry};
+use crate::vector::completions::request::{
+    Calibration, Profile, ProfileEntry,
+};
 use indexmap::IndexMap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,7 @@ use twox_hash::XxHash3_128;
 /// Contains a list of LLM configurations that will be validated, deduplicated,
 /// and sorted when converting to [`Ensemble`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EnsembleBase {
     /// The LLMs in this ensemble, with optional counts and fallbacks.
     pub llms: Vec<ensemble_llm::EnsembleLlmBaseWithFallbacksAndCount>,
@@ -30,6 +33,7 @@ pub struct EnsembleBase {
 /// - Individual LLMs with `count: 0` are skipped
 /// - Total LLM count (sum of all counts) must be between 1 and 128
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Ensemble {
     /// The deterministic content-addressed ID (22-character base62 string).
     pub id: String,
@@ -131,6 +135,10 @@ impl Ensemble {
 
         // normalize profile to (weight, invert) pairs
         let profile_pairs = profile.to_weights_and_invert();
+        let calibrations = profile.calibrations();
+        for calibration in calibrations.iter().flatten() {
+            calibration.validate()?;
+        }
 
         // zip base LLMs with profile entries, filter count-0, validate, and merge
         let mut llms_with_full_id: IndexMap<
@@ -140,12 +148,15 @@ impl Ensemble {
                 Decimal,  // weighted sum (weight * count)
                 u64,      // total count (for computing weighted average)
                 bool,     // invert
+                Option<Calibration>,
             ),
         > = IndexMap::with_capacity(base_llms.len());
         let mut count = 0u64;
 
-        for (base_llm, (weight, invert)) in
-            base_llms.into_iter().zip(profile_pairs.into_iter())
+        for ((base_llm, (weight, invert)), calibration) in base_llms
+            .into_iter()
+            .zip(profile_pairs.into_iter())
+            .zip(calibrations)
         {
             match base_llm.count {
                 0 => continue,
@@ -174,13 +185,25 @@ impl Ensemble {
             }
             let full_id = llm.full_id();
             match llms_with_full_id.get_mut(&full_id) {
-                Some((existing, weighted_sum, total_count, existing_invert)) => {
+                Some((
+                    existing,
+                    weighted_sum,
+                    total_count,
+                    existing_invert,
+                    existing_calibration,
+                )) => {
                     if *existing_invert != invert {
                         return Err(format!(
                             "conflicting invert flags for merged ensemble LLM with full_id: {}",
                             full_id
                         ));
                     }
+                    if *existing_calibration != calibration {
+                        return Err(format!(
+                            "conflicting calibrations for merged ensemble LLM with full_id: {}",
+                            full_id
+                        ));
+                    }
                     *weighted_sum += weight * Decimal::from(llm.count);
                     *total_count += llm.count;
                     existing.count += llm.count;
@@ -188,8 +211,10 @@ impl Ensemble {
                 None => {
                     let weighted_sum = weight * Decimal::from(llm.count);
                     let total_count = llm.count;
-                    llms_with_full_id
-                        .insert(full_id, (llm, weighted_sum, total_count, invert));
+                    llms_with_full_id.insert(
+                        full_id,
+                        (llm, weighted_sum, total_count, invert, calibration),
+                    );
                 }
             }
         }
@@ -207,7 +232,7 @@ impl Ensemble {
 
         // compute ensemble ID
         let mut hasher = XxHash3_128::with_seed(0);
-        for (full_id, (llm, _, _, _)) in &llms_with_full_id {
+        for (full_id, (llm, _, _, _, _)) in &llms_with_full_id {
             hasher.write(full_id.as_bytes());
             let count_bytes = llm.count.to_le_bytes();
             hasher.write(&count_bytes);
@@ -217,13 +242,15 @@ impl Ensemble {
         // collect LLMs and aligned profile entries
         let mut llms = Vec::with_capacity(llms_with_full_id.len());
         let mut entries = Vec::with_capacity(llms_with_full_id.len());
-        for (_, (llm, weighted_sum, total_count, invert)) in llms_with_full_id
+        for (_, (llm, weighted_sum, total_count, invert, calibration)) in
+            llms_with_full_id
         {
             llms.push(llm);
             let merged_weight = weighted_sum / Decimal::from(total_count);
             entries.push(ProfileEntry {
                 weight: merged_weight,
                 invert: if invert { Some(true) } else { None },
+                calibration,
             });
         }
 
@@ -259,9 +286,9 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.6), invert: None },
-            ProfileEntry { weight: dec!(0.9), invert: None }, // should be filtered
-            ProfileEntry { weight: dec!(0.4), invert: None },
+            ProfileEntry { weight: dec!(0.6), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.9), invert: None, calibration: None }, // should be filtered
+            ProfileEntry { weight: dec!(0.4), invert: None, calibration: None },
         ]);
 
         let (ensemble, aligned) = Ensemble::try_from_with_profile(base, profile).unwrap();
@@ -285,8 +312,8 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(1.0), invert: None },
-            ProfileEntry { weight: dec!(0.5), invert: None },
+            ProfileEntry { weight: dec!(1.0), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: None },
         ]);
 
         let (ensemble, aligned) = Ensemble::try_from_with_profile(base, profile).unwrap();
@@ -308,8 +335,8 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.7), invert: None },
-            ProfileEntry { weight: dec!(0.3), invert: Some(true) },
+            ProfileEntry { weight: dec!(0.7), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.3), invert: Some(true), calibration: None },
         ]);
 
         let (ensemble, aligned) = Ensemble::try_from_with_profile(base, profile).unwrap();
@@ -342,10 +369,10 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.8), invert: None },
-            ProfileEntry { weight: dec!(0.5), invert: None }, // filtered
-            ProfileEntry { weight: dec!(0.4), invert: None }, // merged: (0.8*2 + 0.4*2)/4 = 0.6
-            ProfileEntry { weight: dec!(0.9), invert: None },
+            ProfileEntry { weight: dec!(0.8), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: None }, // filtered
+            ProfileEntry { weight: dec!(0.4), invert: None, calibration: None }, // merged: (0.8*2 + 0.4*2)/4 = 0.6
+            ProfileEntry { weight: dec!(0.9), invert: None, calibration: None },
         ]);
 
         let (ensemble, aligned) = Ensemble::try_from_with_profile(base, profile).unwrap();
@@ -369,8 +396,8 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.5), invert: None },
-            ProfileEntry { weight: dec!(0.5), invert: Some(true) },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.5), invert: Some(true), calibration: None },
         ]);
 
         let result = Ensemble::try_from_with_profile(base, profile);
@@ -378,6 +405,30 @@ mod tests {
         assert!(result.unwrap_err().contains("conflicting invert flags"));
     }
 
+    #[test]
+    fn merges_matching_calibrations_and_rejects_conflicting_ones() {
+        let base = EnsembleBase {
+            llms: vec![
+                make_llm("openai/gpt-4o", 1),
+                make_llm("openai/gpt-4o", 1),
+            ],
+        };
+        let calibration = Calibration::Temperature { temperature: dec!(2) };
+        let profile = Profile::Entries(vec![
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: Some(calibration.clone()) },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: Some(calibration.clone()) },
+        ]);
+        let (_, aligned) = Ensemble::try_from_with_profile(base.clone(), profile).unwrap();
+        assert_eq!(aligned.calibrations(), vec![Some(calibration.clone())]);
+
+        let profile = Profile::Entries(vec![
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: Some(calibration) },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: None },
+        ]);
+        let result = Ensemble::try_from_with_profile(base, profile);
+        assert!(result.unwrap_err().contains("conflicting calibrations"));
+    }
+
     #[test]
     fn error_on_profile_length_mismatch() {
         let base = EnsembleBase {
@@ -387,7 +438,7 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.5), invert: None },
+            ProfileEntry { weight: dec!(0.5), invert: None, calibration: None },
         ]);
 
         let result = Ensemble::try_from_with_profile(base, profile);
@@ -695,9 +746,9 @@ mod tests {
             ],
         };
         let profile = Profile::Entries(vec![
-            ProfileEntry { weight: dec!(0.8), invert: Some(true) },
-            ProfileEntry { weight: dec!(0.3), invert: None },
-            ProfileEntry { weight: dec!(0.2), invert: Some(true) },
+            ProfileEntry { weight: dec!(0.8), invert: Some(true), calibration: None },
+            ProfileEntry { weight: dec!(0.3), invert: None, calibration: None },
+            ProfileEntry { weight: dec!(0.2), invert: Some(true), calibration: None },
         ]);
 
         let ensemble_try: Ensemble = base.clone().try_into().unwrap();
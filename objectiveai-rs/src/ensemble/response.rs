@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Response containing a list of Ensembles.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListEnsemble {
     /// The list of Ensemble summaries.
     pub data: Vec<ListEnsembleItem>,
@@ -11,6 +12,7 @@ pub struct ListEnsemble {
 
 /// Summary information for a listed Ensemble.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListEnsembleItem {
     /// The unique content-addressed ID of the Ensemble.
     pub id: String,
@@ -18,6 +20,7 @@ pub struct ListEnsembleItem {
 
 /// Response containing a single Ensemble with creation timestamp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetEnsemble {
     /// Unix timestamp when this Ensemble was first used.
     pub created: u64,
@@ -28,6 +31,7 @@ pub struct GetEnsemble {
 
 /// Usage statistics for an Ensemble.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UsageEnsemble {
     /// Total number of requests made with this Ensemble.
     pub requests: u64,
@@ -18,6 +18,7 @@ use twox_hash::XxHash3_128;
 /// - Empty collections are removed
 /// - Collections are sorted for deterministic ordering
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EnsembleLlmBase {
     /// The upstream language model identifier (e.g., `"gpt-4"`, `"claude-3-opus"`).
     pub model: String,
@@ -45,6 +46,49 @@ pub struct EnsembleLlmBase {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub top_logprobs: Option<u64>,
 
+    /// Number of permutations of the responses to vote over (1-10).
+    ///
+    /// **Vector completions only.** Ignored for chat completions.
+    ///
+    /// When greater than 1, the LLM votes once per permutation, each time
+    /// seeing the responses in a different order with different keys, and
+    /// its vote is the mean of those votes. Mitigates position bias at the
+    /// cost of one completion per permutation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permutations: Option<u64>,
+
+    /// Number of completions to sample for each vote (1-20).
+    ///
+    /// **Vector completions only.** Ignored for chat completions.
+    ///
+    /// When greater than 1, the LLM votes once per sample, and its vote is
+    /// the mean of those votes. For LLMs which do not provide logprobs, this
+    /// is the empirical distribution of the keys they selected, rather than
+    /// a vote for a single key. Samples are drawn with repeated requests,
+    /// each with a different seed if the request has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<u64>,
+
+    /// How response keys are labeled.
+    ///
+    /// **Vector completions only.** Ignored for chat completions.
+    ///
+    /// Unset fields fall back to defaults; see
+    /// [`label_alphabet`](Self::label_alphabet),
+    /// [`label_wrap`](Self::label_wrap), and
+    /// [`label_width`](Self::label_width).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<super::Labels>,
+
+    /// Overrides the wording of the voting prompt.
+    ///
+    /// **Vector completions only.** Ignored for chat completions.
+    ///
+    /// Unset templates keep the default wording. See
+    /// [`PromptTemplate`](super::PromptTemplate) for placeholders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_template: Option<super::PromptTemplate>,
+
     /// Messages prepended to the user's prompt.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prefix_messages: Option<Vec<chat::completions::request::Message>>,
@@ -110,6 +154,10 @@ impl Default for EnsembleLlmBase {
             output_mode: super::OutputMode::default(),
             synthetic_reasoning: None,
             top_logprobs: None,
+            permutations: None,
+            samples: None,
+            labels: None,
+            prompt_template: None,
             prefix_messages: None,
             suffix_messages: None,
             frequency_penalty: None,
@@ -145,6 +193,33 @@ impl EnsembleLlmBase {
             Some(0) | Some(1) => None,
             other => other,
         };
+        self.permutations = match self.permutations {
+            Some(0) | Some(1) => None,
+            other => other,
+        };
+        self.samples = match self.samples {
+            Some(0) | Some(1) => None,
+            other => other,
+        };
+        self.labels = match self.labels.take() {
+            Some(labels) => labels.prepare(),
+            None => None,
+        };
+        // every model once defaulted to backticks, so a different default is
+        // part of the ID, and LLMs whose labels changed get new IDs
+        let wrap = super::LabelWrap::default_for_model(&self.model);
+        if wrap != super::LabelWrap::Backtick
+            && self.labels.is_none_or(|labels| labels.wrap.is_none())
+        {
+            self.labels = Some(super::Labels {
+                wrap: Some(wrap),
+                ..self.labels.unwrap_or_default()
+            });
+        }
+        self.prompt_template = match self.prompt_template.take() {
+            Some(prompt_template) => prompt_template.prepare(),
+            None => None,
+        };
         self.prefix_messages = match self.prefix_messages.take() {
             Some(prefix_messages) if prefix_messages.is_empty() => None,
             Some(mut prefix_messages) => {
@@ -288,6 +363,22 @@ impl EnsembleLlmBase {
         {
             return Err("`top_logprobs` must be at most 20".to_string());
         }
+        if let Some(permutations) = self.permutations
+            && permutations > 10
+        {
+            return Err("`permutations` must be at most 10".to_string());
+        }
+        if let Some(samples) = self.samples
+            && samples > 20
+        {
+            return Err("`samples` must be at most 20".to_string());
+        }
+        if let Some(labels) = &self.labels {
+            labels.validate()?;
+        }
+        if let Some(prompt_template) = &self.prompt_template {
+            prompt_template.validate()?;
+        }
         validate_f64("frequency_penalty", self.frequency_penalty, -2.0, 2.0)?;
         if let Some(logit_bias) = &self.logit_bias {
             for (token, weight) in logit_bias {
@@ -340,6 +431,35 @@ impl EnsembleLlmBase {
         Ok(())
     }
 
+    /// The alphabet of response key labels, defaulting to letters.
+    pub fn label_alphabet(&self) -> super::LabelAlphabet {
+        self.labels
+            .and_then(|labels| labels.alphabet)
+            .unwrap_or(super::LabelAlphabet::Letters)
+    }
+
+    /// The delimiters of response key labels, defaulting by model family.
+    pub fn label_wrap(&self) -> super::LabelWrap {
+        self.labels
+            .and_then(|labels| labels.wrap)
+            .unwrap_or_else(|| super::LabelWrap::default_for_model(&self.model))
+    }
+
+    /// The maximum number of labels per level of a response key.
+    ///
+    /// Defaults to `top_logprobs` if set, so that each level fits in one
+    /// logprobs batch, and to 20 otherwise. Never exceeds the alphabet.
+    pub fn label_width(&self) -> usize {
+        let width = match self.labels.and_then(|labels| labels.width) {
+            Some(width) => width as usize,
+            None => match self.top_logprobs {
+                Some(0) | Some(1) | None => super::LABELS_MAX_WIDTH as usize,
+                Some(top_logprobs) => top_logprobs as usize,
+            },
+        };
+        width.min(self.label_alphabet().count())
+    }
+
     /// Computes the deterministic content-addressed ID.
     ///
     /// The ID is a base62-encoded XXHash3-128 hash of the JSON serialization,
@@ -356,6 +476,7 @@ impl EnsembleLlmBase {
 /// Created by converting from [`EnsembleLlmBase`] via [`TryFrom`].
 /// The conversion normalizes and validates the configuration, then computes the ID.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EnsembleLlm {
     /// The deterministic content-addressed ID (22-character base62 string).
     pub id: String,
@@ -379,6 +500,7 @@ impl TryFrom<EnsembleLlmBase> for EnsembleLlm {
 /// Used to specify how many instances of an LLM to include in an ensemble,
 /// along with fallback models to try if the primary fails.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WithFallbacksAndCount<T> {
     /// Number of instances of this LLM in the ensemble. Defaults to 1.
     #[serde(default = "WithFallbacksAndCount::<T>::default_count")]
@@ -0,0 +1,257 @@
+//! Response key label settings for Ensemble LLMs.
+//!
+//! Vector completions label each response with a key (e.g. `` `A` ``) which
+//! the LLM outputs to vote. Labels are drawn from an alphabet and wrapped in
+//! delimiters. When there are more responses than fit in one level, keys are
+//! nested (e.g. `` `A``B` ``), with at most `width` labels per level.
+
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Maximum number of labels per level of the prefix tree.
+pub const LABELS_MAX_WIDTH: u64 = 20;
+
+/// The labels which response keys are made of.
+///
+/// All labels of an alphabet have the same length, so a key can always be
+/// split back into its labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LabelAlphabet {
+    /// Uppercase letters `A` through `T`.
+    #[serde(rename = "letters")]
+    Letters,
+    /// Digits `0` through `9`.
+    #[serde(rename = "digits")]
+    Digits,
+    /// Two-letter codes `AA` through `TT`, for tokenizers which encode each
+    /// pair as a single token.
+    #[serde(rename = "letter_pairs")]
+    LetterPairs,
+}
+
+const LETTERS: [&str; 20] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O",
+    "P", "Q", "R", "S", "T",
+];
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+static LETTER_PAIRS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    LETTERS
+        .iter()
+        .flat_map(|a| LETTERS.iter().map(move |b| format!("{a}{b}")))
+        .collect()
+});
+
+impl LabelAlphabet {
+    /// All labels of the alphabet.
+    pub fn labels(&self) -> Vec<&'static str> {
+        match self {
+            LabelAlphabet::Letters => LETTERS.to_vec(),
+            LabelAlphabet::Digits => DIGITS.to_vec(),
+            LabelAlphabet::LetterPairs => {
+                LETTER_PAIRS.iter().map(String::as_str).collect()
+            }
+        }
+    }
+
+    /// The number of labels in the alphabet.
+    pub fn count(&self) -> usize {
+        match self {
+            LabelAlphabet::Letters => LETTERS.len(),
+            LabelAlphabet::Digits => DIGITS.len(),
+            LabelAlphabet::LetterPairs => LETTERS.len() * LETTERS.len(),
+        }
+    }
+}
+
+/// The delimiters wrapped around each label of a response key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LabelWrap {
+    /// `` `A` ``
+    #[serde(rename = "backtick")]
+    Backtick,
+    /// `[A]`
+    #[serde(rename = "bracket")]
+    Bracket,
+    /// `(A)`
+    #[serde(rename = "parenthesis")]
+    Parenthesis,
+}
+
+impl LabelWrap {
+    /// The default wrapping for a model.
+    ///
+    /// Llama, Mistral, and Gemma tokenizers tend to merge a backtick with
+    /// the label that follows it, which splits the label across tokens and
+    /// hides it from logprobs. Those families default to brackets, which
+    /// [`EnsembleLlmBase::prepare`](super::EnsembleLlmBase::prepare) writes
+    /// into their labels so that it is part of their IDs.
+    pub fn default_for_model(model: &str) -> Self {
+        let model = model.to_ascii_lowercase();
+        if model.starts_with("meta-llama/")
+            || model.starts_with("mistralai/")
+            || model.starts_with("google/gemma")
+        {
+            LabelWrap::Bracket
+        } else {
+            LabelWrap::Backtick
+        }
+    }
+
+    /// The opening delimiter.
+    pub fn open(&self) -> &'static str {
+        match self {
+            LabelWrap::Backtick => "`",
+            LabelWrap::Bracket => "[",
+            LabelWrap::Parenthesis => "(",
+        }
+    }
+
+    /// The closing delimiter.
+    pub fn close(&self) -> &'static str {
+        match self {
+            LabelWrap::Backtick => "`",
+            LabelWrap::Bracket => "]",
+            LabelWrap::Parenthesis => ")",
+        }
+    }
+
+    /// A plural name for the delimiters, for use in prompts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LabelWrap::Backtick => "backticks",
+            LabelWrap::Bracket => "square brackets",
+            LabelWrap::Parenthesis => "parentheses",
+        }
+    }
+}
+
+/// How an Ensemble LLM's response keys are labeled.
+///
+/// Unset fields fall back to defaults; see
+/// [`EnsembleLlmBase::label_alphabet`](super::EnsembleLlmBase::label_alphabet),
+/// [`EnsembleLlmBase::label_wrap`](super::EnsembleLlmBase::label_wrap), and
+/// [`EnsembleLlmBase::label_width`](super::EnsembleLlmBase::label_width).
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Labels {
+    /// The labels which response keys are made of.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alphabet: Option<LabelAlphabet>,
+    /// The delimiters wrapped around each label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap: Option<LabelWrap>,
+    /// Maximum number of labels per level (2-20). Responses beyond this are
+    /// given nested keys. Should not exceed the number of logprobs the
+    /// provider returns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u64>,
+}
+
+impl Labels {
+    /// Normalizes the labels for deterministic hashing.
+    ///
+    /// Labels with no fields set are normalized to `None`.
+    pub fn prepare(self) -> Option<Self> {
+        if self.alphabet.is_none()
+            && self.wrap.is_none()
+            && self.width.is_none()
+        {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Validates the labels.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(width) = self.width {
+            if !(2..=LABELS_MAX_WIDTH).contains(&width) {
+                return Err(format!(
+                    "`labels.width` must be between 2 and {}",
+                    LABELS_MAX_WIDTH
+                ));
+            }
+            if let Some(alphabet) = self.alphabet
+                && width as usize > alphabet.count()
+            {
+                return Err(format!(
+                    "`labels.width` must be at most {} for the chosen alphabet",
+                    alphabet.count()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alphabets_have_unique_labels_of_equal_length() {
+        for alphabet in [
+            LabelAlphabet::Letters,
+            LabelAlphabet::Digits,
+            LabelAlphabet::LetterPairs,
+        ] {
+            let labels = alphabet.labels();
+            assert_eq!(labels.len(), alphabet.count());
+            let len = labels[0].len();
+            assert!(labels.iter().all(|label| label.len() == len));
+            let mut deduped = labels.clone();
+            deduped.sort_unstable();
+            deduped.dedup();
+            assert_eq!(deduped.len(), labels.len());
+        }
+    }
+
+    #[test]
+    fn width_must_fit_alphabet() {
+        let labels = Labels {
+            alphabet: Some(LabelAlphabet::Digits),
+            wrap: None,
+            width: Some(12),
+        };
+        assert!(labels.validate().is_err());
+        let labels = Labels {
+            alphabet: None,
+            wrap: None,
+            width: Some(12),
+        };
+        assert!(labels.validate().is_ok());
+    }
+
+    #[test]
+    fn default_wrap_is_part_of_the_id() {
+        fn id(model: &str, wrap: Option<LabelWrap>) -> String {
+            let labels = wrap.map(|wrap| Labels {
+                wrap: Some(wrap),
+                ..Labels::default()
+            });
+            super::super::EnsembleLlm::try_from(super::super::EnsembleLlmBase {
+                model: model.to_string(),
+                labels,
+                ..Default::default()
+            })
+            .unwrap()
+            .id
+        }
+        let model = "meta-llama/llama-3.3-70b-instruct";
+        assert_eq!(id(model, None), id(model, Some(LabelWrap::Bracket)));
+        assert_ne!(id(model, None), id(model, Some(LabelWrap::Backtick)));
+        let model = "openai/gpt-4o";
+        assert_ne!(id(model, None), id(model, Some(LabelWrap::Bracket)));
+        let base = super::super::EnsembleLlmBase {
+            model: model.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(id(model, None), base.id());
+    }
+}
@@ -8,6 +8,7 @@
 //! - Decoding parameters (temperature, top_p, etc.)
 //! - Provider preferences and routing
 //! - Output mode, reasoning settings, and verbosity
+//! - Response key labels and voting prompt templates for vector completions
 //!
 //! # Content-Addressed Identity
 //!
@@ -43,7 +44,9 @@
 //! ```
 
 mod ensemble_llm;
+mod labels;
 mod output_mode;
+mod prompt_template;
 mod provider;
 mod reasoning;
 pub mod response;
@@ -51,7 +54,9 @@ mod stop;
 mod verbosity;
 
 pub use ensemble_llm::*;
+pub use labels::*;
 pub use output_mode::*;
+pub use prompt_template::*;
 pub use provider::*;
 pub use reasoning::*;
 pub use stop::*;
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 /// **Note:** This setting is only relevant for vector completions and is
 /// completely ignored for chat completions.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum OutputMode {
     /// The model is instructed via the prompt to output a specific key.
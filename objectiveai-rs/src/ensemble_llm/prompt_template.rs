@@ -0,0 +1,166 @@
+//! Voting prompt templates for Ensemble LLMs.
+
+use serde::{Deserialize, Serialize};
+
+/// Placeholder for the labeled responses.
+pub const PROMPT_TEMPLATE_RESPONSES: &str = "{responses}";
+/// Placeholder for the response keys, one per line, each prefixed by `- `.
+pub const PROMPT_TEMPLATE_KEYS: &str = "{keys}";
+/// Placeholder for the name of the delimiters around labels (e.g.
+/// `backticks`).
+pub const PROMPT_TEMPLATE_WRAP: &str = "{wrap}";
+/// Placeholder for the lowest value of a rating scale.
+pub const PROMPT_TEMPLATE_MIN: &str = "{min}";
+/// Placeholder for the highest value of a rating scale.
+pub const PROMPT_TEMPLATE_MAX: &str = "{max}";
+
+/// Overrides the wording of the prompt an Ensemble LLM votes with.
+///
+/// Each template replaces one piece of the default wording; unset templates
+/// keep it. Placeholders are replaced literally wherever they appear:
+///
+/// - `{responses}`: the labeled responses. If absent, they follow the text.
+/// - `{keys}`: the response keys, one per line, each prefixed by `- `.
+/// - `{wrap}`: the name of the delimiters around labels (e.g. `backticks`).
+/// - `{min}` and `{max}`: the bounds of the rating scale.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PromptTemplate {
+    /// Appended to the last user message to present the responses.
+    ///
+    /// Defaults to `"Select the response:\n\n{responses}"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select: Option<String>,
+    /// Appended to the last user message to present the values of the
+    /// scale, when the request has a rating scale.
+    ///
+    /// Defaults to `"Select the rating, from {min} to {max}:\n\n{responses}"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub select_rating: Option<String>,
+    /// Appended to the system message with the voting instructions, when
+    /// `output_mode` is `instruction`.
+    ///
+    /// Defaults to `"Output one response key including {wrap}\n{keys}"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction: Option<String>,
+}
+
+impl PromptTemplate {
+    /// Normalizes the template for deterministic hashing.
+    ///
+    /// Empty templates are removed, and a template with none left is
+    /// normalized to `None`.
+    pub fn prepare(mut self) -> Option<Self> {
+        for template in [
+            &mut self.select,
+            &mut self.select_rating,
+            &mut self.instruction,
+        ] {
+            if template.as_deref().is_some_and(str::is_empty) {
+                *template = None;
+            }
+        }
+        if self.select.is_none()
+            && self.select_rating.is_none()
+            && self.instruction.is_none()
+        {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// Validates the template.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, template) in [
+            ("select", &self.select),
+            ("select_rating", &self.select_rating),
+        ] {
+            if let Some(template) = template
+                && template.matches(PROMPT_TEMPLATE_RESPONSES).count() > 1
+            {
+                return Err(format!(
+                    "`prompt_template.{}` must contain `{}` at most once",
+                    name, PROMPT_TEMPLATE_RESPONSES
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills the placeholders of a template, other than `{responses}`.
+    pub fn fill(
+        template: &str,
+        keys: &[&str],
+        wrap: super::LabelWrap,
+        rating: Option<(i64, i64)>,
+    ) -> String {
+        let mut text = template
+            .replace(
+                PROMPT_TEMPLATE_KEYS,
+                &keys
+                    .iter()
+                    .map(|key| format!("- {}", key))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+            .replace(PROMPT_TEMPLATE_WRAP, wrap.name());
+        if let Some((min, max)) = rating {
+            text = text
+                .replace(PROMPT_TEMPLATE_MIN, &min.to_string())
+                .replace(PROMPT_TEMPLATE_MAX, &max.to_string());
+        }
+        text
+    }
+
+    /// Splits a filled `select` or `select_rating` template around the
+    /// `{responses}` placeholder. If absent, the responses follow the text.
+    pub fn split_responses(text: &str) -> (&str, &str) {
+        text.split_once(PROMPT_TEMPLATE_RESPONSES)
+            .unwrap_or((text, ""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ensemble_llm::LabelWrap;
+
+    #[test]
+    fn fill_replaces_placeholders() {
+        let text = PromptTemplate::fill(
+            "Note de {min} à {max}, avec {wrap} :\n{keys}\n{responses}",
+            &["`A`", "`B`"],
+            LabelWrap::Backtick,
+            Some((1, 5)),
+        );
+        assert_eq!(
+            text,
+            "Note de 1 à 5, avec backticks :\n- `A`\n- `B`\n{responses}"
+        );
+        assert_eq!(
+            PromptTemplate::split_responses(&text),
+            ("Note de 1 à 5, avec backticks :\n- `A`\n- `B`\n", "")
+        );
+    }
+
+    #[test]
+    fn prepare_removes_empty_templates() {
+        let template = PromptTemplate {
+            select: Some(String::new()),
+            select_rating: None,
+            instruction: None,
+        };
+        assert!(template.prepare().is_none());
+    }
+
+    #[test]
+    fn validate_rejects_repeated_responses() {
+        let template = PromptTemplate {
+            select: Some("{responses} or {responses}".to_string()),
+            select_rating: None,
+            instruction: None,
+        };
+        assert!(template.validate().is_err());
+    }
+}
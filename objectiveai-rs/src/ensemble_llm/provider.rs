@@ -11,6 +11,7 @@ use std::collections::HashSet;
 /// Controls which providers are used and in what order when routing
 /// requests to upstream model hosts.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Provider {
     /// Whether to allow fallback to other providers if preferred ones fail.
     /// Defaults to `true`.
@@ -109,6 +110,7 @@ impl Provider {
 /// Quantization reduces model precision to decrease memory usage and
 /// increase inference speed, potentially at the cost of output quality.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderQuantization {
     /// 4-bit integer quantization.
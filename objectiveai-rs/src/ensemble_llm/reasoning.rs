@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// **Note:** The `max_tokens`, `effort`, and `summary_verbosity` fields are
 /// only supported by some models. Unsupported fields are silently ignored.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Reasoning {
     /// Whether reasoning is enabled. Defaults to `true` if other fields are set.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -95,6 +96,7 @@ impl Reasoning {
 ///
 /// Only supported by some models.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReasoningEffort {
     /// No reasoning.
@@ -115,6 +117,7 @@ pub enum ReasoningEffort {
 ///
 /// Only supported by some models.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReasoningSummaryVerbosity {
     /// Let the model decide (default, normalized away).
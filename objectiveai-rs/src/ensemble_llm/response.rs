@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Response containing a list of Ensemble LLMs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListEnsembleLlm {
     /// The list of Ensemble LLM summaries.
     pub data: Vec<ListEnsembleLlmItem>,
@@ -11,6 +12,7 @@ pub struct ListEnsembleLlm {
 
 /// Summary information for a listed Ensemble LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListEnsembleLlmItem {
     /// The unique content-addressed ID of the Ensemble LLM.
     pub id: String,
@@ -18,6 +20,7 @@ pub struct ListEnsembleLlmItem {
 
 /// Response containing a single Ensemble LLM with creation timestamp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetEnsembleLlm {
     /// Unix timestamp when this Ensemble LLM was first used.
     pub created: u64,
@@ -28,6 +31,7 @@ pub struct GetEnsembleLlm {
 
 /// Usage statistics for an Ensemble LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UsageEnsembleLlm {
     /// Total number of requests made with this Ensemble LLM.
     pub requests: u64,
@@ -39,6 +39,7 @@ pub trait StatusError {
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[error("{}", &serde_json::to_string(self).unwrap_or_default())]
 pub struct ResponseError {
     /// The HTTP status code of the error response.
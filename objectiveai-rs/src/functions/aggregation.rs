@@ -0,0 +1,48 @@
+//! How a Function combines its task outputs into its final output.
+
+use serde::{Deserialize, Serialize};
+
+/// How a Function combines its task outputs into its final output.
+///
+/// Serializes as a method name (e.g. `"weighted_median"`) or as an
+/// expression (e.g. `{"$starlark": "..."}`).
+///
+/// Task outputs which were skipped or failed are excluded, as are tasks with
+/// a profile weight of zero. Vector results are L1-normalized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Aggregation {
+    /// A built-in aggregation method.
+    Method(AggregationMethod),
+    /// An expression computing the final output.
+    ///
+    /// Receives: `input`, `tasks` (the output of every task, `null` if
+    /// skipped or failed), `weights` (the profile weight of every task).
+    /// Must return a FunctionOutput valid for the Function's type.
+    Expression(super::expression::Expression),
+}
+
+/// A built-in method of combining task outputs.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMethod {
+    /// The weighted arithmetic mean of task outputs.
+    #[default]
+    WeightedMean,
+    /// The weighted median of task outputs, element-wise for vectors.
+    /// Robust to a minority of outlying tasks.
+    WeightedMedian,
+    /// The weighted geometric mean of task outputs, element-wise for vectors.
+    GeometricMean,
+    /// The maximum of task outputs, element-wise for vectors.
+    Max,
+    /// The minimum of task outputs, element-wise for vectors.
+    Min,
+    /// The product of task outputs, each raised to the power of its weight.
+    /// Scalar outputs are treated as the probability of a binary outcome.
+    ProductOfExperts,
+}
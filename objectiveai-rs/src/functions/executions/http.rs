@@ -1,7 +1,9 @@
 //! HTTP functions for function executions.
 
 use crate::{HttpClient, HttpError};
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use std::collections::HashSet;
+use std::pin::Pin;
 
 /// Creates a function execution (non-streaming).
 pub async fn create_function_execution_unary(
@@ -125,6 +127,17 @@ pub async fn create_function_execution_unary(
 }
 
 /// Creates a streaming function execution.
+///
+/// If the stream drops before it ends, the execution is re-issued, up to
+/// [`RetryPolicy::max_stream_resumes`](crate::RetryPolicy::max_stream_resumes)
+/// times, with a retry token covering every vector completion which already
+/// completed, so that their votes are reused rather than recomputed. The
+/// re-issued execution's chunks are yielded under the `id` of the first
+/// chunk, so the consumer sees a single execution: its replays of vector
+/// completions which were already yielded in full are dropped, and the first
+/// chunk of each vector completion which was yielded only in part is marked
+/// as a [`reset`](super::response::streaming::VectorCompletionTaskChunk::reset).
+/// Executions with the Swiss System strategy are not resumed.
 pub async fn create_function_execution_streaming(
     client: &HttpClient,
     request: super::request::Request,
@@ -139,6 +152,142 @@ pub async fn create_function_execution_streaming(
     + 'static
     + use<>,
     HttpError,
+> {
+    struct State<S> {
+        client: HttpClient,
+        request: super::request::Request,
+        stream: Pin<Box<S>>,
+        aggregate: Option<super::response::streaming::FunctionExecutionChunk>,
+        id: Option<String>,
+        retry_token: super::RetryToken,
+        /// Task indexes of the vector completions yielded in full.
+        completed: HashSet<u64>,
+        /// Task indexes of the vector completions yielded in part.
+        interrupted: HashSet<u64>,
+        resumes: u32,
+        done: bool,
+    }
+
+    let resumable = client.retry_policy.max_stream_resumes > 0
+        && matches!(
+            request.base().strategy,
+            None | Some(super::request::Strategy::Default)
+        );
+    let stream =
+        send_function_execution_streaming(client, request.clone()).await?;
+    if !resumable {
+        return Ok(futures::future::Either::Left(stream));
+    }
+
+    let retry_token = request
+        .base()
+        .retry_token
+        .as_deref()
+        .and_then(super::RetryToken::try_from_string)
+        .unwrap_or(super::RetryToken(Vec::new()));
+    let state = State {
+        client: client.clone(),
+        request,
+        stream: Box::pin(stream),
+        aggregate: None,
+        id: None,
+        retry_token,
+        completed: HashSet::new(),
+        interrupted: HashSet::new(),
+        resumes: 0,
+        done: false,
+    };
+    Ok(futures::future::Either::Right(futures::stream::unfold(
+        state,
+        |mut state| async move {
+            if state.done {
+                return None;
+            }
+            loop {
+                match state.stream.next().await? {
+                    Ok(mut chunk) => {
+                        // drop what the consumer already received
+                        if state.resumes > 0
+                            && !chunk.tasks.is_empty()
+                            && !replay_tasks(
+                                &mut chunk.tasks,
+                                &state.completed,
+                                &mut state.interrupted,
+                            )
+                            && is_empty(&chunk)
+                        {
+                            continue;
+                        }
+                        // keep the id of the execution which was resumed
+                        match &state.id {
+                            Some(id) => chunk.id = id.clone(),
+                            None => state.id = Some(chunk.id.clone()),
+                        }
+                        match &mut state.aggregate {
+                            Some(aggregate) => aggregate.push(&chunk),
+                            None => state.aggregate = Some(chunk.clone()),
+                        }
+                        return Some((Ok(chunk), state));
+                    }
+                    Err(e)
+                        if is_dropped_stream(&e)
+                            && state.resumes
+                                < state
+                                    .client
+                                    .retry_policy
+                                    .max_stream_resumes =>
+                    {
+                        // reuse every vector completion which completed
+                        if let Some(aggregate) = state.aggregate.take() {
+                            push_completed(
+                                &mut state.retry_token,
+                                &mut state.completed,
+                                &mut state.interrupted,
+                                &aggregate,
+                            );
+                        }
+                        state.request.base_mut().retry_token =
+                            Some(state.retry_token.to_string());
+                        tokio::time::sleep(
+                            state.client.retry_policy.delay(state.resumes),
+                        )
+                        .await;
+                        state.resumes += 1;
+                        match send_function_execution_streaming(
+                            &state.client,
+                            state.request.clone(),
+                        )
+                        .await
+                        {
+                            Ok(stream) => state.stream = Box::pin(stream),
+                            Err(e) => {
+                                state.done = true;
+                                return Some((Err(e), state));
+                            }
+                        }
+                    }
+                    Err(e) => return Some((Err(e), state)),
+                }
+            }
+        },
+    )))
+}
+
+/// Sends a single streaming function execution request.
+async fn send_function_execution_streaming(
+    client: &HttpClient,
+    request: super::request::Request,
+) -> Result<
+    impl Stream<
+        Item = Result<
+            super::response::streaming::FunctionExecutionChunk,
+            HttpError,
+        >,
+    >
+    + Send
+    + 'static
+    + use<>,
+    HttpError,
 > {
     match request {
         super::request::Request::FunctionInlineProfileInline { mut body } => {
@@ -275,3 +424,83 @@ pub async fn create_function_execution_streaming(
         }
     }
 }
+
+/// Returns whether an error means the stream dropped before it ended.
+fn is_dropped_stream(e: &HttpError) -> bool {
+    match e {
+        HttpError::HttpError(_) => true,
+        HttpError::StreamError(e) => matches!(
+            e.as_ref(),
+            reqwest_eventsource::Error::Transport(_)
+                | reqwest_eventsource::Error::StreamEnded
+        ),
+        _ => false,
+    }
+}
+
+/// Sets the retry token of every vector completion in `aggregate` which
+/// completed and was stored, keeping tokens which are already set, and adds
+/// its task index to `completed`. The task indexes of the other vector
+/// completions are added to `interrupted`.
+fn push_completed(
+    retry_token: &mut super::RetryToken,
+    completed: &mut HashSet<u64>,
+    interrupted: &mut HashSet<u64>,
+    aggregate: &super::response::streaming::FunctionExecutionChunk,
+) {
+    for task in aggregate.vector_completion_tasks() {
+        let is_completed = task.error.is_none()
+            && task.inner.usage.is_some()
+            && !task.inner.id.is_empty()
+            && task.inner.completions.iter().any(|c| c.error.is_none());
+        if !is_completed {
+            interrupted.insert(task.task_index);
+            continue;
+        }
+        completed.insert(task.task_index);
+        interrupted.remove(&task.task_index);
+        let index = task.task_index as usize;
+        if retry_token.0.len() <= index {
+            retry_token.0.resize(index + 1, None);
+        }
+        if retry_token.0[index].is_none() {
+            retry_token.0[index] = Some(task.inner.id.clone());
+        }
+    }
+}
+
+/// Removes the vector completions in `completed` from `tasks` and marks the
+/// first chunk of each one in `interrupted` as a reset. Returns whether any
+/// tasks are left.
+fn replay_tasks(
+    tasks: &mut Vec<super::response::streaming::TaskChunk>,
+    completed: &HashSet<u64>,
+    interrupted: &mut HashSet<u64>,
+) -> bool {
+    tasks.retain_mut(|task| match task {
+        super::response::streaming::TaskChunk::VectorCompletion(task) => {
+            if completed.contains(&task.task_index) {
+                return false;
+            }
+            if interrupted.remove(&task.task_index) {
+                task.reset = Some(true);
+            }
+            true
+        }
+        super::response::streaming::TaskChunk::FunctionExecution(task) => {
+            replay_tasks(&mut task.inner.tasks, completed, interrupted)
+                || !is_empty(&task.inner)
+        }
+    });
+    !tasks.is_empty()
+}
+
+/// Returns whether a chunk carries nothing besides its tasks.
+fn is_empty(chunk: &super::response::streaming::FunctionExecutionChunk) -> bool {
+    chunk.tasks.is_empty()
+        && chunk.reasoning.is_none()
+        && chunk.output.is_none()
+        && chunk.error.is_none()
+        && chunk.retry_token.is_none()
+        && chunk.usage.is_none()
+}
@@ -0,0 +1,306 @@
+//! Tests for resuming streaming function executions.
+
+#![cfg(test)]
+
+use super::response::streaming::{
+    FunctionExecutionChunk, Object, TaskChunk, VectorCompletionTaskChunk,
+};
+use crate::http::test_server::{TestServer, sse_response};
+use crate::{HttpClient, RetryPolicy, vector};
+use futures::StreamExt;
+use std::time::Duration;
+
+fn request(strategy: serde_json::Value) -> super::request::Request {
+    super::request::Request::FunctionRemoteProfileRemote {
+        path: serde_json::from_value(serde_json::json!({
+            "fremote": "github",
+            "fowner": "owner",
+            "frepository": "function",
+            "fcommit": null,
+            "premote": "github",
+            "powner": "owner",
+            "prepository": "profile",
+            "pcommit": null,
+        }))
+        .unwrap(),
+        body: serde_json::from_value(serde_json::json!({
+            "input": "input",
+            "strategy": strategy,
+        }))
+        .unwrap(),
+    }
+}
+
+fn chunk(id: &str, tasks: Vec<TaskChunk>) -> String {
+    serde_json::to_string(&FunctionExecutionChunk {
+        id: id.to_string(),
+        tasks,
+        tasks_errors: None,
+        reasoning: None,
+        output: None,
+        error: None,
+        retry_token: None,
+        created: 0,
+        function: None,
+        profile: None,
+        object: Object::ScalarFunctionExecutionChunk,
+        usage: None,
+    })
+    .unwrap()
+}
+
+fn vector_completion_task(
+    task_index: u64,
+    id: &str,
+    completed: bool,
+) -> TaskChunk {
+    TaskChunk::VectorCompletion(VectorCompletionTaskChunk {
+        index: task_index,
+        task_index,
+        task_path: vec![task_index],
+        inner:
+            vector::completions::response::streaming::VectorCompletionChunk {
+                id: id.to_string(),
+                completions: vec![Default::default()],
+                usage: completed.then(Default::default),
+                ..Default::default()
+            },
+        error: None,
+        reset: None,
+    })
+}
+
+/// A vector completion task with `votes` votes, which completed if it has
+/// `total_tokens`.
+fn voted_vector_completion_task(
+    task_index: u64,
+    id: &str,
+    votes: usize,
+    total_tokens: Option<u64>,
+) -> TaskChunk {
+    let mut task =
+        vector_completion_task(task_index, id, total_tokens.is_some());
+    if let TaskChunk::VectorCompletion(task) = &mut task {
+        let vote: vector::completions::response::Vote =
+            serde_json::from_value(serde_json::json!({
+                "model": "model",
+                "ensemble_index": 0,
+                "flat_ensemble_index": 0,
+                "prompt_id": "prompt",
+                "responses_ids": ["a", "b"],
+                "vote": [1, 0],
+                "weight": 1,
+            }))
+            .unwrap();
+        task.inner.votes = vec![vote; votes];
+        task.inner.usage =
+            total_tokens.map(|total_tokens| vector::completions::response::Usage {
+                total_tokens,
+                ..Default::default()
+            });
+    }
+    task
+}
+
+fn client(server: &TestServer) -> HttpClient {
+    HttpClient::new(
+        reqwest::Client::new(),
+        Some(server.api_base.clone()),
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        None::<String>,
+    )
+    .with_retry_policy(RetryPolicy {
+        initial_interval: Duration::from_millis(1),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn resumes_dropped_streams_with_completed_vector_completions() {
+    let first = chunk(
+        "fnexc-1",
+        vec![
+            vector_completion_task(0, "vctcpl-done", true),
+            vector_completion_task(1, "vctcpl-partial", false),
+        ],
+    );
+    let second = chunk("fnexc-2", Vec::new());
+    let server = TestServer::start(vec![
+        // drops without [DONE]
+        sse_response(&[&first]),
+        sse_response(&[&second, "[DONE]"]),
+    ])
+    .await;
+
+    let chunks: Vec<FunctionExecutionChunk> =
+        super::create_function_execution_streaming(
+            &client(&server),
+            request(serde_json::json!({ "type": "default" })),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].id, "fnexc-1");
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 2);
+    let body: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+    let retry_token = super::RetryToken::try_from_string(
+        body["retry_token"].as_str().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(retry_token.0, vec![Some("vctcpl-done".to_string())]);
+}
+
+#[tokio::test]
+async fn consumer_sees_one_execution_across_a_dropped_connection() {
+    let first = chunk(
+        "fnexc-1",
+        vec![
+            vector_completion_task(0, "vctcpl-a", true),
+            vector_completion_task(1, "vctcpl-b", false),
+        ],
+    );
+    // the connection drops in the middle of the next message
+    let dropped =
+        format!("{}data: {{\"id\":\"fnexc-1\",", sse_response(&[&first]));
+    let replayed = chunk(
+        "fnexc-2",
+        vec![
+            vector_completion_task(0, "vctcpl-a", true),
+            vector_completion_task(1, "vctcpl-c", true),
+        ],
+    );
+    let server =
+        TestServer::start(vec![dropped, sse_response(&[&replayed, "[DONE]"])])
+            .await;
+
+    let items: Vec<_> = super::create_function_execution_streaming(
+        &client(&server),
+        request(serde_json::json!({ "type": "default" })),
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await;
+    let chunks = items
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("the dropped connection should not reach the consumer");
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks.iter().all(|chunk| chunk.id == "fnexc-1"));
+    // the replay of the completed vector completion is dropped, and the
+    // interrupted one is reset
+    let tasks = chunks[1].vector_completion_tasks().collect::<Vec<_>>();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].inner.id, "vctcpl-c");
+    assert_eq!(tasks[0].reset, Some(true));
+    assert_eq!(server.bodies().len(), 2);
+}
+
+#[tokio::test]
+async fn aggregate_counts_votes_and_usage_once_across_a_dropped_connection() {
+    let first = chunk(
+        "fnexc-1",
+        vec![
+            voted_vector_completion_task(0, "vctcpl-a", 2, Some(10)),
+            voted_vector_completion_task(1, "vctcpl-b", 1, None),
+        ],
+    );
+    let replayed = chunk(
+        "fnexc-2",
+        vec![
+            voted_vector_completion_task(0, "vctcpl-a", 2, Some(10)),
+            voted_vector_completion_task(1, "vctcpl-c", 2, Some(20)),
+        ],
+    );
+    let server = TestServer::start(vec![
+        // drops without [DONE]
+        sse_response(&[&first]),
+        sse_response(&[&replayed, "[DONE]"]),
+    ])
+    .await;
+
+    let mut aggregate: Option<FunctionExecutionChunk> = None;
+    let mut stream = super::create_function_execution_streaming(
+        &client(&server),
+        request(serde_json::json!({ "type": "default" })),
+    )
+    .await
+    .unwrap()
+    .boxed();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.unwrap();
+        match &mut aggregate {
+            Some(aggregate) => aggregate.push(&chunk),
+            None => aggregate = Some(chunk),
+        }
+    }
+    let aggregate = aggregate.unwrap();
+
+    let tasks = aggregate.vector_completion_tasks().collect::<Vec<_>>();
+    let ids = tasks
+        .iter()
+        .map(|task| task.inner.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["vctcpl-a", "vctcpl-c"]);
+    let votes = tasks
+        .iter()
+        .map(|task| task.inner.votes.len())
+        .collect::<Vec<_>>();
+    assert_eq!(votes, [2, 2]);
+    let total_tokens = tasks
+        .iter()
+        .filter_map(|task| task.inner.usage.as_ref())
+        .map(|usage| usage.total_tokens)
+        .sum::<u64>();
+    assert_eq!(total_tokens, 30);
+    assert!(tasks.iter().all(|task| task.reset.is_none()));
+}
+
+#[tokio::test]
+async fn does_not_resume_swiss_system_executions() {
+    let first = chunk("fnexc-1", vec![vector_completion_task(0, "a", true)]);
+    let server = TestServer::start(vec![sse_response(&[&first])]).await;
+
+    let chunks: Vec<_> = super::create_function_execution_streaming(
+        &client(&server),
+        request(serde_json::json!({ "type": "swiss_system" })),
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await;
+    assert_eq!(chunks.len(), 2);
+    assert!(chunks[1].is_err());
+    assert_eq!(server.bodies().len(), 1);
+}
+
+#[tokio::test]
+async fn does_not_resume_after_malformed_data() {
+    let first = chunk("fnexc-1", vec![vector_completion_task(0, "a", true)]);
+    let mut response = sse_response(&[&first]).into_bytes();
+    response.extend_from_slice(b"data: \xff\n\n");
+    let server = TestServer::start(vec![response]).await;
+
+    let chunks: Vec<_> = super::create_function_execution_streaming(
+        &client(&server),
+        request(serde_json::json!({ "type": "default" })),
+    )
+    .await
+    .unwrap()
+    .collect()
+    .await;
+    assert_eq!(chunks.len(), 2);
+    assert!(matches!(
+        &chunks[1],
+        Err(crate::HttpError::StreamError(e))
+            if matches!(e.as_ref(), reqwest_eventsource::Error::Utf8(_))
+    ));
+    assert_eq!(server.bodies().len(), 1);
+}
@@ -7,10 +7,14 @@
 //! - Remote Function + Inline Profile
 //! - Inline Function + Remote Profile
 //! - Inline Function + Inline Profile
+//!
+//! Executions stream over server-sent events, or over a WebSocket which
+//! accepts control messages mid-run (see [`websocket`]).
 
 pub mod request;
 pub mod response;
 mod retry_token;
+pub mod websocket;
 
 pub use retry_token::*;
 
@@ -19,3 +23,6 @@ mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(all(test, feature = "http"))]
+mod http_tests;
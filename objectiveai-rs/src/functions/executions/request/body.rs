@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// Request body for inline Function with inline Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionInlineProfileInlineRequestBody {
     /// The inline Function definition.
     pub function: functions::InlineFunction,
@@ -17,6 +18,7 @@ pub struct FunctionInlineProfileInlineRequestBody {
 
 /// Request body for inline Function with remote Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionInlineProfileRemoteRequestBody {
     /// The inline Function definition.
     pub function: functions::InlineFunction,
@@ -27,6 +29,7 @@ pub struct FunctionInlineProfileRemoteRequestBody {
 
 /// Request body for remote Function with inline Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteProfileInlineRequestBody {
     /// The inline Profile definition.
     pub profile: functions::InlineProfile,
@@ -40,6 +43,7 @@ pub struct FunctionRemoteProfileInlineRequestBody {
 /// Used directly for remote Function + remote Profile, or flattened into
 /// other request body types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteProfileRemoteRequestBody {
     // --- Caching and retry options ---
     /// If present, reuses votes from a previous execution with this token.
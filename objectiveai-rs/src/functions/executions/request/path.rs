@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 
 /// Path parameters for remote Function with inline Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteProfileInlineRequestPath {
     /// Function remote source.
     pub fremote: Remote,
@@ -21,6 +22,7 @@ pub struct FunctionRemoteProfileInlineRequestPath {
 
 /// Path parameters for inline Function with remote Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionInlineProfileRemoteRequestPath {
     /// Profile remote source.
     pub premote: Remote,
@@ -34,6 +36,7 @@ pub struct FunctionInlineProfileRemoteRequestPath {
 
 /// Path parameters for remote Function with remote Profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteProfileRemoteRequestPath {
     /// Function remote source.
     pub fremote: Remote,
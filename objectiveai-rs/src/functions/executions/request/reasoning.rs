@@ -7,10 +7,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// When enabled, an LLM summarizes the execution's reasoning process.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Reasoning {
     /// The primary model to use for generating reasoning summaries.
     pub model: chat::completions::request::Model,
     /// Fallback models tried in order if the primary is rate-limited or errors.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub models: Option<Vec<chat::completions::request::Model>>,
+    /// If true, every vote in the execution's vector completion tasks
+    /// carries the rationale of the LLM which cast it, so reviewers can see
+    /// why each LLM voted as it did. The summary is based on these
+    /// rationales either way.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_rationales: Option<bool>,
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Used internally to route requests to the appropriate API endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Request {
     FunctionInlineProfileInline {
@@ -109,6 +110,7 @@ impl Request {
 /// Supports four combinations based on whether the Function and Profile
 /// are provided inline or referenced from remote repositories.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum FunctionExecutionCreateParams {
     /// Inline Function with inline Profile.
@@ -2,6 +2,7 @@ use crate::{error, vector};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionTaskChunk {
     pub index: u64,
     pub task_index: u64,
@@ -10,10 +11,22 @@ pub struct VectorCompletionTaskChunk {
     pub inner: vector::completions::response::streaming::VectorCompletionChunk,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<error::ResponseError>,
+    /// Set by the SDK on the first chunk of a task which was restarted after
+    /// the connection dropped part-way through it. The chunk replaces,
+    /// rather than extends, the chunks received for the task before. Never
+    /// sent by the server, so it is not serialized.
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub reset: Option<bool>,
 }
 
 impl VectorCompletionTaskChunk {
     pub fn push(&mut self, other: &VectorCompletionTaskChunk) {
+        if let Some(true) = other.reset {
+            *self = other.clone();
+            self.reset = None;
+            return;
+        }
         self.inner.push(&other.inner);
         match (&mut self.error, &other.error) {
             (None, Some(other_error)) => {
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 
 /// A complete function execution response (non-streaming).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionExecution {
     /// Unique identifier for this execution.
     pub id: String,
@@ -2,6 +2,7 @@ use crate::functions::executions::response;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionExecutionTask {
     pub index: u64,
     pub task_index: u64,
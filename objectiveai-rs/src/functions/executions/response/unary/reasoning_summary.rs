@@ -3,6 +3,7 @@ use crate::{chat, error};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ReasoningSummary {
     #[serde(flatten)]
     pub inner: chat::completions::response::unary::ChatCompletion,
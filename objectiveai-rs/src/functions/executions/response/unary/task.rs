@@ -2,6 +2,7 @@ use crate::functions::executions::response;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Task {
     FunctionExecution(super::FunctionExecutionTask),
@@ -2,6 +2,7 @@ use crate::{error, functions::executions::response, vector};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionTask {
     pub index: u64,
     pub task_index: u64,
@@ -21,6 +22,7 @@ impl From<response::streaming::VectorCompletionTaskChunk>
             task_path,
             inner,
             error,
+            ..
         }: response::streaming::VectorCompletionTaskChunk,
     ) -> Self {
         Self {
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Contains identifiers for each task's votes that can be reused in a
 /// subsequent execution. Serialized as base64-encoded JSON.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 pub struct RetryToken(pub Vec<Option<String>>);
 
@@ -0,0 +1,34 @@
+//! Messages sent by the client over a Function execution WebSocket.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Longest an execution stays paused before the server resumes it.
+pub const PAUSE_MAX_DURATION: Duration = Duration::from_secs(300);
+
+/// A message sent by the client over a Function execution WebSocket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Starts the execution. Must be the first message, and is only
+    /// accepted once per connection.
+    Start {
+        /// The execution to run. The request's `stream` field is ignored.
+        request: Box<super::super::request::Request>,
+    },
+    /// Holds chunks back until [`ClientMessage::Resume`]. No further tasks
+    /// are started while paused, but those which have started keep running
+    /// and their chunks are sent once resumed. The server resumes on its own
+    /// after [`PAUSE_MAX_DURATION`].
+    Pause,
+    /// Resumes a paused execution.
+    Resume,
+    /// Stops the execution. The server replies with
+    /// [`ServerMessage::Cancelled`](super::ServerMessage::Cancelled) and
+    /// closes the connection.
+    Cancel,
+    /// Skips the tasks which have not finished and aggregates the outputs of
+    /// those which have. The execution's final chunk carries the output.
+    SkipRemainingTasks,
+}
@@ -0,0 +1,28 @@
+//! Messages of the WebSocket Function execution protocol.
+//!
+//! The client starts an execution with [`ClientMessage::Start`] and may
+//! control it mid-run with the other [`ClientMessage`]s. The server replies
+//! with [`ServerMessage`]s, streaming the execution's chunks until it is
+//! done or cancelled. Every message is a JSON text frame.
+//!
+//! Clients which can't set the `Authorization` header, such as browsers,
+//! may instead offer their API key as a subprotocol, prefixed with
+//! [`API_KEY_PROTOCOL_PREFIX`], alongside [`PROTOCOL`].
+
+mod client_message;
+mod server_message;
+
+pub use client_message::*;
+pub use server_message::*;
+
+/// The subprotocol of the WebSocket Function execution protocol. The server
+/// selects it when the client offers it.
+pub const PROTOCOL: &str = "objectiveai";
+
+/// Prefix of the subprotocol which carries the client's API key, e.g.
+/// `objectiveai.bearer.apk1234567890abcdef1234567890abcdef`. Never selected
+/// by the server.
+pub const API_KEY_PROTOCOL_PREFIX: &str = "objectiveai.bearer.";
+
+#[cfg(test)]
+mod websocket_tests;
@@ -0,0 +1,30 @@
+//! Messages sent by the server over a Function execution WebSocket.
+
+use serde::{Deserialize, Serialize};
+
+/// A message sent by the server over a Function execution WebSocket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A chunk of the execution, as streamed over server-sent events.
+    Chunk {
+        /// The chunk.
+        chunk: Box<super::super::response::streaming::FunctionExecutionChunk>,
+    },
+    /// The execution could not start, or a client message was invalid.
+    Error {
+        /// The error.
+        error: crate::error::ResponseError,
+    },
+    /// The execution was paused.
+    Paused,
+    /// The execution was resumed, by the client or after being paused for
+    /// [`PAUSE_MAX_DURATION`](super::PAUSE_MAX_DURATION). Followed by the
+    /// chunks held back while paused.
+    Resumed,
+    /// The execution finished. The server closes the connection.
+    Done,
+    /// The execution was cancelled. The server closes the connection.
+    Cancelled,
+}
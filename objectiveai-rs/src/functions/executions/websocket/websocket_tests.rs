@@ -0,0 +1,54 @@
+//! Tests for the WebSocket Function execution messages.
+
+#![cfg(test)]
+
+use super::{ClientMessage, ServerMessage};
+use crate::functions::executions::request::Request;
+use serde_json::json;
+
+#[test]
+fn deserializes_start_message() {
+    let message: ClientMessage = serde_json::from_value(json!({
+        "type": "start",
+        "request": {
+            "path": {
+                "fremote": "github",
+                "fowner": "owner",
+                "frepository": "function",
+                "fcommit": null,
+                "premote": "github",
+                "powner": "owner",
+                "prepository": "profile",
+                "pcommit": null,
+            },
+            "body": { "input": "input" },
+        },
+    }))
+    .unwrap();
+    match message {
+        ClientMessage::Start { request } => assert!(matches!(
+            *request,
+            Request::FunctionRemoteProfileRemote { .. }
+        )),
+        other => panic!("expected start message, got {:?}", other),
+    }
+}
+
+#[test]
+fn serializes_control_messages_by_type() {
+    for (message, r#type) in [
+        (ClientMessage::Pause, "pause"),
+        (ClientMessage::Resume, "resume"),
+        (ClientMessage::Cancel, "cancel"),
+        (ClientMessage::SkipRemainingTasks, "skip_remaining_tasks"),
+    ] {
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            json!({ "type": r#type })
+        );
+    }
+    assert_eq!(
+        serde_json::to_value(ServerMessage::Done).unwrap(),
+        json!({ "type": "done" })
+    );
+}
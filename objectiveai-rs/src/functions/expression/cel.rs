@@ -0,0 +1,303 @@
+//! CEL (Common Expression Language) evaluation.
+//!
+//! Expressions are parsed and evaluated by the [`cel`](::cel) crate with the
+//! standard library and its `strings` and `math` extensions. The variables
+//! `input`, `output`, `map`, `tasks`, and `weights` are bound to the
+//! expression [`Params`] (`null` when absent), exactly as for JMESPath.
+//!
+//! JSON numbers become `int` when they fit in an `i64`, `uint` when they only
+//! fit in a `u64`, and `double` otherwise. As in the CEL specification,
+//! arithmetic never mixes numeric types, so `input.count * 0.5` must be
+//! written `double(input.count) * 0.5`; comparisons across numeric types are
+//! allowed. Results are converted back to JSON, with map keys sorted since
+//! CEL maps are unordered; bytes and other non-JSON values are an error.
+//!
+//! [`Params`]: super::Params
+
+use super::ExpressionError;
+use ::cel::objects::{Key, Map};
+use ::cel::{Context, Env, IdedExpr, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+/// The environment expressions are compiled and evaluated in.
+static ENV: LazyLock<Arc<Env>> = LazyLock::new(|| {
+    let mut env = Env::stdlib();
+    env.add_extension(::cel::extensions::strings)
+        .expect("strings extension registers");
+    env.add_extension(::cel::extensions::math)
+        .expect("math extension registers");
+    Arc::new(env)
+});
+
+/// Evaluates a CEL expression against the given parameters.
+pub fn cel_eval(
+    code: &str,
+    params: &super::Params,
+) -> Result<serde_json::Value, ExpressionError> {
+    let program = ENV
+        .compile(code)
+        .map_err(|e| ExpressionError::CelParseError(e.to_string()))?;
+    let serde_json::Value::Object(activation) = serde_json::to_value(params)?
+    else {
+        unreachable!("params serialize to an object");
+    };
+    let mut context = Context::with_env(ENV.clone());
+    for (name, value) in activation {
+        context.add_variable_from_value(name, from_json(value));
+    }
+    let value = program
+        .execute(&context)
+        .map_err(|e| ExpressionError::CelEvalError(e.to_string()))?;
+    to_json(value).map_err(ExpressionError::CelEvalError)
+}
+
+/// Parses a CEL expression without expanding macros, so `map`, `filter`,
+/// `has`, and the other macros remain calls.
+pub fn parse(code: &str) -> Result<IdedExpr, String> {
+    Env::default()
+        .with_stdlib()
+        .parser()
+        .parse(code)
+        .map_err(|e| e.to_string())
+}
+
+fn from_json(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(bool) => Value::Bool(bool),
+        serde_json::Value::Number(number) => {
+            if let Some(int) = number.as_i64() {
+                Value::Int(int)
+            } else if let Some(uint) = number.as_u64() {
+                Value::UInt(uint)
+            } else {
+                Value::Float(number.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        serde_json::Value::String(string) => Value::from(string),
+        serde_json::Value::Array(items) => {
+            Value::from(items.into_iter().map(from_json).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(entries) => Value::from(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, from_json(value)))
+                .collect::<HashMap<_, _>>(),
+        ),
+    }
+}
+
+fn to_json(value: Value) -> Result<serde_json::Value, String> {
+    Ok(match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(bool) => serde_json::Value::Bool(bool),
+        Value::Int(int) => serde_json::Value::from(int),
+        Value::UInt(uint) => serde_json::Value::from(uint),
+        Value::Float(float) => serde_json::Number::from_f64(float)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| format!("{} is not representable as JSON", float))?,
+        Value::String(string) => serde_json::Value::String(string.to_string()),
+        Value::List(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .cloned()
+                .map(to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(Map { map }) => {
+            let mut entries = map
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        Key::String(string) => string.to_string(),
+                        key => key.to_string(),
+                    };
+                    Ok((key, to_json(value.clone())?))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(entries.into_iter().collect())
+        }
+        Value::Bytes(_) => return Err("bytes are not representable as JSON".to_string()),
+        value => {
+            return Err(format!("{:?} is not representable as JSON", value));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::expression::{Input, Params, ParamsOwned};
+    use indexmap::IndexMap;
+    use serde_json::json;
+
+    fn params() -> Params<'static, 'static, 'static> {
+        let input: Input = serde_json::from_value(json!({
+            "name": "alice",
+            "count": 3,
+            "score": 0.5,
+            "tags": ["a", "b", "c"],
+            "items": [{"name": "x", "price": 2}, {"name": "y", "price": 5}],
+        }))
+        .unwrap();
+        Params::Owned(ParamsOwned {
+            input,
+            output: None,
+            map: None,
+            definitions: None,
+            tasks: None,
+            weights: None,
+        })
+    }
+
+    fn eval(code: &str) -> serde_json::Value {
+        cel_eval(code, &params()).unwrap()
+    }
+
+    fn eval_err(code: &str) -> String {
+        match cel_eval(code, &params()).unwrap_err() {
+            ExpressionError::CelParseError(e) | ExpressionError::CelEvalError(e) => e,
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    // The cases below follow the cel-spec conformance suites
+    // (https://github.com/google/cel-spec/tree/master/tests/simple/testdata)
+    // that apply to JSON values.
+
+    #[test]
+    fn basic() {
+        assert_eq!(eval("null"), json!(null));
+        assert_eq!(eval("true"), json!(true));
+        assert_eq!(eval("42"), json!(42));
+        assert_eq!(eval("-9223372036854775808"), json!(i64::MIN));
+        assert_eq!(eval("18446744073709551615u"), json!(u64::MAX));
+        assert_eq!(eval("0x1F"), json!(31));
+        assert_eq!(eval("1.5e2"), json!(150.0));
+        assert_eq!(eval(r#""a\tbé\x41\101""#), json!("a\tbéAA"));
+        assert_eq!(eval(r#"r'a\tb'"#), json!("a\\tb"));
+        assert_eq!(eval("'''multi\nline'''"), json!("multi\nline"));
+        assert_eq!(eval("[1, 'a', [true]]"), json!([1, "a", [true]]));
+        assert_eq!(eval("{'b': 1, 'a': {'c': null}}"), json!({"a": {"c": null}, "b": 1}));
+        assert_eq!(eval("{1: 'x'}"), json!({"1": "x"}));
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(eval("input.name"), json!("alice"));
+        assert_eq!(eval("input['name']"), json!("alice"));
+        assert_eq!(eval("input.items[1].price"), json!(5));
+        assert_eq!(eval("input.tags[size(input.tags) - 1]"), json!("c"));
+        assert_eq!(eval("output"), json!(null));
+        assert_eq!(eval("map"), json!(null));
+        assert!(eval_err("input.missing").contains("No such key: missing"));
+        assert!(eval_err("input.tags[3]").contains("Index out of bounds"));
+        assert!(eval_err("inputs").contains("Undeclared reference to 'inputs'"));
+    }
+
+    #[test]
+    fn integer_math() {
+        assert_eq!(eval("1 + 2 * 3"), json!(7));
+        assert_eq!(eval("(1 + 2) * 3"), json!(9));
+        assert_eq!(eval("7 / 2"), json!(3));
+        assert_eq!(eval("-7 % 3"), json!(-1));
+        assert_eq!(eval("2u + 3u"), json!(5));
+        assert!(eval_err("1 / 0").contains("Division by zero"));
+        assert!(eval_err("9223372036854775807 + 1").contains("Overflow"));
+        assert!(eval_err("18446744073709551615u + 1u").contains("Overflow"));
+        assert!(eval_err("input.count + 1u").contains("Unsupported binary operator"));
+    }
+
+    #[test]
+    fn fp_math() {
+        assert_eq!(eval("7.0 / 2.0"), json!(3.5));
+        assert_eq!(eval("double(input.count) * input.score"), json!(1.5));
+        assert!(eval_err("input.count * input.score").contains("Unsupported binary operator"));
+        assert!(eval_err("1.0 / 0.0").contains("not representable as JSON"));
+    }
+
+    #[test]
+    fn comparisons_and_logic() {
+        assert_eq!(eval("1 == 1.0"), json!(true));
+        assert_eq!(eval("1u < 2"), json!(true));
+        assert_eq!(eval("'a' < 'b'"), json!(true));
+        assert_eq!(eval("[1, 2] == [1, 2]"), json!(true));
+        assert_eq!(eval("'b' in input.tags"), json!(true));
+        assert_eq!(eval("'name' in input"), json!(true));
+        assert_eq!(eval("!(1 > 2) && input.count >= 3"), json!(true));
+        assert_eq!(eval("input.count > 5 ? 'big' : 'small'"), json!("small"));
+        // errors are absorbed when the other side decides the result
+        assert_eq!(eval("input.missing || true"), json!(true));
+        assert_eq!(eval("false && input.missing"), json!(false));
+        assert!(eval_err("input.missing || false").contains("No such key"));
+    }
+
+    #[test]
+    fn macros() {
+        assert_eq!(eval("has(input.name)"), json!(true));
+        assert_eq!(eval("has(input.nickname)"), json!(false));
+        assert_eq!(eval("input.items.map(i, i.name)"), json!(["x", "y"]));
+        assert_eq!(eval("input.items.map(i, i.price > 2, i.name)"), json!(["y"]));
+        assert_eq!(
+            eval("input.items.filter(i, i.price < 3)"),
+            json!([{"name": "x", "price": 2}])
+        );
+        assert_eq!(eval("input.tags.all(t, size(t) == 1)"), json!(true));
+        assert_eq!(eval("input.tags.exists(t, t == 'c')"), json!(true));
+        assert_eq!(eval("input.tags.exists_one(t, t != 'c')"), json!(false));
+        assert_eq!(
+            eval("input.tags.map(t, input.tags.map(u, t + u))[0]"),
+            json!(["aa", "ab", "ac"])
+        );
+    }
+
+    #[test]
+    fn strings_and_lists() {
+        assert_eq!(eval("size(input.tags)"), json!(3));
+        assert_eq!(eval("input.name.size()"), json!(5));
+        assert_eq!(eval("'a' + 'b'"), json!("ab"));
+        assert_eq!(eval("[1] + [2]"), json!([1, 2]));
+        assert_eq!(eval("int('12') + int(2.9)"), json!(14));
+        assert_eq!(eval("string(input.count)"), json!("3"));
+        assert_eq!(eval("input.name.startsWith('al')"), json!(true));
+        assert_eq!(eval("input.name.contains('ic')"), json!(true));
+        assert_eq!(eval("input.name.matches('^a.*e$')"), json!(true));
+        assert!(eval_err("'a' + 1").contains("Unsupported binary operator"));
+    }
+
+    #[test]
+    fn extensions() {
+        assert_eq!(eval("input.name.upperAscii()"), json!("ALICE"));
+        assert_eq!(eval("input.tags.join('-')"), json!("a-b-c"));
+        assert_eq!(eval("math.greatest(1, 5, 2)"), json!(5));
+        assert_eq!(eval("math.least(input.items.map(i, i.price))"), json!(2));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(eval_err("1 +").contains("Syntax error"));
+        assert!(eval_err("(1").contains("Syntax error"));
+        assert!(eval_err("'abc").contains("Syntax error"));
+        assert!(eval_err("b'abc'").contains("not representable as JSON"));
+        assert!(matches!(
+            cel_eval("1 +", &params()),
+            Err(ExpressionError::CelParseError(_))
+        ));
+    }
+
+    #[test]
+    fn map_and_output_params() {
+        let params = Params::Owned(ParamsOwned {
+            input: Input::Object(IndexMap::new()),
+            output: Some(serde_json::from_value(json!([0.25, 0.75])).unwrap()),
+            map: Some(Input::String("m".to_string())),
+            definitions: None,
+            tasks: None,
+            weights: None,
+        });
+        assert_eq!(cel_eval("output[1]", &params).unwrap(), json!(0.75));
+        assert_eq!(cel_eval("map + '!'", &params).unwrap(), json!("m!"));
+    }
+}
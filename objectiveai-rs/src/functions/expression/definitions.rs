@@ -0,0 +1,295 @@
+//! Named definitions shared by every expression in a Function.
+//!
+//! Large Functions tend to repeat the same snippet across tasks. Definitions
+//! let a Function declare such helpers once:
+//!
+//! ```json
+//! "definitions": {
+//!   "starlark": {
+//!     "clamp": "def clamp(x):\n    return max(0.0, min(1.0, x))"
+//!   },
+//!   "jmespath": {
+//!     "names": "items[*].name"
+//!   }
+//! }
+//! ```
+//!
+//! Starlark definitions are loaded into the module before each Starlark
+//! expression is evaluated, so `clamp(output['scores'][0])` works in any
+//! Starlark expression. JMESPath fragments are inlined wherever they are
+//! called: `names(input)` evaluates `items[*].name` with `input` as the
+//! current node, and `names()` evaluates it against the current node.
+
+use indexmap::IndexMap;
+use jmespath::ast::Ast;
+use serde::{Deserialize, Serialize};
+
+use super::ExpressionError;
+
+/// Named helpers available to every expression in a Function.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Definitions {
+    /// Starlark source, keyed by the name it defines (typically a `def`
+    /// statement or a `name = lambda ...` assignment). Loaded, in order,
+    /// into the module before each Starlark expression is evaluated.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub starlark: IndexMap<String, String>,
+    /// JMESPath fragments, keyed by the function name that inlines them.
+    /// Fragments may call earlier or later fragments, but not recursively.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub jmespath: IndexMap<String, String>,
+}
+
+impl Definitions {
+    /// Returns true if there are no definitions.
+    pub fn is_empty(&self) -> bool {
+        self.starlark.is_empty() && self.jmespath.is_empty()
+    }
+
+    /// Parses a JMESPath expression, inlining calls to fragments.
+    pub fn expand_jmespath(&self, code: &str) -> Result<Ast, ExpressionError> {
+        let ast = jmespath::parse(code)?;
+        if self.jmespath.is_empty() {
+            return Ok(ast);
+        }
+        self.expand(ast, &mut Vec::new())
+    }
+
+    fn expand(
+        &self,
+        ast: Ast,
+        stack: &mut Vec<String>,
+    ) -> Result<Ast, ExpressionError> {
+        let mut expand = |ast: Box<Ast>| self.expand(*ast, stack).map(Box::new);
+        Ok(match ast {
+            Ast::Function { offset, name, args } => {
+                let Some(fragment) = self.jmespath.get(&name) else {
+                    let args = args
+                        .into_iter()
+                        .map(|arg| self.expand(arg, stack))
+                        .collect::<Result<_, _>>()?;
+                    return Ok(Ast::Function { offset, name, args });
+                };
+                if stack.contains(&name) {
+                    return Err(ExpressionError::DefinitionError(format!(
+                        "JMESPath fragment '{}' calls itself",
+                        name
+                    )));
+                }
+                if args.len() > 1 {
+                    return Err(ExpressionError::DefinitionError(format!(
+                        "JMESPath fragment '{}' takes at most 1 argument, found {}",
+                        name,
+                        args.len()
+                    )));
+                }
+                let body = jmespath::parse(fragment).map_err(|e| {
+                    ExpressionError::DefinitionError(format!(
+                        "JMESPath fragment '{}': {}",
+                        name, e
+                    ))
+                })?;
+                stack.push(name);
+                let body = self.expand(body, stack)?;
+                stack.pop();
+                match args.into_iter().next() {
+                    Some(arg) => Ast::Subexpr {
+                        offset,
+                        lhs: Box::new(self.expand(arg, stack)?),
+                        rhs: Box::new(body),
+                    },
+                    None => body,
+                }
+            }
+            Ast::Subexpr { offset, lhs, rhs } => Ast::Subexpr {
+                offset,
+                lhs: expand(lhs)?,
+                rhs: expand(rhs)?,
+            },
+            Ast::Projection { offset, lhs, rhs } => Ast::Projection {
+                offset,
+                lhs: expand(lhs)?,
+                rhs: expand(rhs)?,
+            },
+            Ast::Comparison {
+                offset,
+                comparator,
+                lhs,
+                rhs,
+            } => Ast::Comparison {
+                offset,
+                comparator,
+                lhs: expand(lhs)?,
+                rhs: expand(rhs)?,
+            },
+            Ast::And { offset, lhs, rhs } => Ast::And {
+                offset,
+                lhs: expand(lhs)?,
+                rhs: expand(rhs)?,
+            },
+            Ast::Or { offset, lhs, rhs } => Ast::Or {
+                offset,
+                lhs: expand(lhs)?,
+                rhs: expand(rhs)?,
+            },
+            Ast::Condition {
+                offset,
+                predicate,
+                then,
+            } => Ast::Condition {
+                offset,
+                predicate: expand(predicate)?,
+                then: expand(then)?,
+            },
+            Ast::Not { offset, node } => Ast::Not {
+                offset,
+                node: expand(node)?,
+            },
+            Ast::Flatten { offset, node } => Ast::Flatten {
+                offset,
+                node: expand(node)?,
+            },
+            Ast::ObjectValues { offset, node } => Ast::ObjectValues {
+                offset,
+                node: expand(node)?,
+            },
+            Ast::Expref { offset, ast } => Ast::Expref {
+                offset,
+                ast: expand(ast)?,
+            },
+            Ast::MultiList { offset, elements } => Ast::MultiList {
+                offset,
+                elements: elements
+                    .into_iter()
+                    .map(|element| self.expand(element, stack))
+                    .collect::<Result<_, _>>()?,
+            },
+            Ast::MultiHash { offset, elements } => Ast::MultiHash {
+                offset,
+                elements: elements
+                    .into_iter()
+                    .map(|mut element| {
+                        element.value = self.expand(element.value, stack)?;
+                        Ok(element)
+                    })
+                    .collect::<Result<_, ExpressionError>>()?,
+            },
+            ast @ (Ast::Identity { .. }
+            | Ast::Field { .. }
+            | Ast::Index { .. }
+            | Ast::Literal { .. }
+            | Ast::Slice { .. }) => ast,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::expression::{
+        Expression, Input, Params, ParamsOwned,
+    };
+    use crate::util::index_map;
+
+    fn params(definitions: Definitions) -> Params<'static, 'static, 'static> {
+        let input: Input = serde_json::from_value(serde_json::json!({
+            "items": [{"name": "a", "score": 0.2}, {"name": "b", "score": 1.4}],
+        }))
+        .unwrap();
+        Params::Owned(ParamsOwned {
+            input,
+            output: None,
+            map: None,
+            definitions: Some(Box::new(definitions)),
+            tasks: None,
+            weights: None,
+        })
+    }
+
+    fn strings(map: IndexMap<String, &str>) -> IndexMap<String, String> {
+        map.into_iter().map(|(k, v)| (k, v.to_string())).collect()
+    }
+
+    fn definitions() -> Definitions {
+        Definitions {
+            starlark: strings(index_map! {
+                "clamp" => "def clamp(x):\n    return max(0.0, min(1.0, x))",
+                "names" => "names = lambda: [item['name'] for item in input['items']]",
+            }),
+            jmespath: strings(index_map! {
+                "names" => "items[*].name",
+                "first_name" => "names()[0]",
+                "loop_a" => "loop_b()",
+                "loop_b" => "loop_a()",
+            }),
+        }
+    }
+
+    #[test]
+    fn starlark_definitions_are_loaded() {
+        let params = params(definitions());
+        let scores: Vec<f64> = Expression::Starlark(
+            "[clamp(item['score']) for item in input['items']]".to_string(),
+        )
+        .compile_one(&params)
+        .unwrap();
+        assert_eq!(scores, vec![0.2, 1.0]);
+
+        let names: Vec<String> =
+            Expression::Starlark("names()".to_string())
+                .compile_one(&params)
+                .unwrap();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn starlark_definition_errors_name_the_definition() {
+        let mut definitions = definitions();
+        definitions
+            .starlark
+            .insert("broken".to_string(), "def broken(:".to_string());
+        let err = Expression::Starlark("1".to_string())
+            .compile_one::<i64>(&params(definitions))
+            .unwrap_err();
+        assert!(err.to_string().contains("'broken'"), "{}", err);
+    }
+
+    #[test]
+    fn jmespath_fragments_are_inlined() {
+        let params = params(definitions());
+        let names: Vec<String> =
+            Expression::JMESPath("names(input)".to_string())
+                .compile_one(&params)
+                .unwrap();
+        assert_eq!(names, vec!["a", "b"]);
+
+        let name: String =
+            Expression::JMESPath("input.first_name()".to_string())
+                .compile_one(&params)
+                .unwrap();
+        assert_eq!(name, "a");
+
+        let lengths: Vec<i64> = Expression::JMESPath(
+            "[input].map(&length(names()), @)".to_string(),
+        )
+        .compile_one(&params)
+        .unwrap();
+        assert_eq!(lengths, vec![2]);
+    }
+
+    #[test]
+    fn jmespath_recursive_fragments_are_rejected() {
+        let err = Expression::JMESPath("loop_a()".to_string())
+            .compile_one::<Input>(&params(definitions()))
+            .unwrap_err();
+        assert!(matches!(err, ExpressionError::DefinitionError(_)));
+    }
+
+    #[test]
+    fn definitions_are_optional_in_json() {
+        let definitions: Definitions = serde_json::from_str("{}").unwrap();
+        assert!(definitions.is_empty());
+        assert_eq!(serde_json::to_string(&definitions).unwrap(), "{}");
+    }
+}
@@ -15,6 +15,15 @@ pub enum ExpressionError {
     /// The Starlark result could not be converted to JSON.
     #[error("starlark conversion error: {0}")]
     StarlarkConversionError(String),
+    /// The CEL expression failed to parse.
+    #[error("cel parse error: {0}")]
+    CelParseError(String),
+    /// The CEL expression failed to evaluate.
+    #[error("cel evaluation error: {0}")]
+    CelEvalError(String),
+    /// A named definition is invalid or was used incorrectly.
+    #[error("definition error: {0}")]
+    DefinitionError(String),
     /// The expression result could not be deserialized to the expected type.
     #[error(transparent)]
     DeserializationError(#[from] serde_json::Error),
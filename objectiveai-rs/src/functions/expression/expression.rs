@@ -1,9 +1,10 @@
-//! Core expression types for JMESPath and Starlark evaluation.
+//! Core expression types for JMESPath, Starlark, and CEL evaluation.
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 /// Result of an expression that may produce one or many values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum OneOrMany<T> {
     /// A single value.
@@ -12,9 +13,10 @@ pub enum OneOrMany<T> {
     Many(Vec<T>),
 }
 
-/// An expression that can be either JMESPath or Starlark.
+/// An expression in one of the supported languages.
 ///
-/// Serializes as `{"$jmespath": "..."}` or `{"$starlark": "..."}` in JSON.
+/// Serializes as `{"$jmespath": "..."}`, `{"$starlark": "..."}`, or
+/// `{"$cel": "..."}` in JSON.
 ///
 /// # Examples
 ///
@@ -27,12 +29,19 @@ pub enum OneOrMany<T> {
 /// ```json
 /// {"$starlark": "input['items'][0]['name']"}
 /// ```
+///
+/// CEL:
+/// ```json
+/// {"$cel": "input.items[0].name"}
+/// ```
 #[derive(Debug, Clone)]
 pub enum Expression {
     /// A JMESPath expression.
     JMESPath(String),
     /// A Starlark expression.
     Starlark(String),
+    /// A CEL (Common Expression Language) expression.
+    Cel(String),
 }
 
 impl Serialize for Expression {
@@ -45,6 +54,7 @@ impl Serialize for Expression {
         match self {
             Expression::JMESPath(expr) => map.serialize_entry("$jmespath", expr)?,
             Expression::Starlark(expr) => map.serialize_entry("$starlark", expr)?,
+            Expression::Cel(expr) => map.serialize_entry("$cel", expr)?,
         }
         map.end()
     }
@@ -65,7 +75,7 @@ impl<'de> Deserialize<'de> for Expression {
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str(
-                    "a map with exactly one key '$jmespath' or '$starlark' containing a string",
+                    "a map with exactly one key '$jmespath', '$starlark', or '$cel' containing a string",
                 )
             }
 
@@ -76,7 +86,7 @@ impl<'de> Deserialize<'de> for Expression {
                 // Get the first (and should be only) key
                 let Some(key) = map.next_key::<String>()? else {
                     return Err(de::Error::custom(
-                        "expected '$jmespath' or '$starlark' key, found empty map",
+                        "expected '$jmespath', '$starlark', or '$cel' key, found empty map",
                     ));
                 };
 
@@ -93,8 +103,9 @@ impl<'de> Deserialize<'de> for Expression {
                 match key.as_str() {
                     "$jmespath" => Ok(Expression::JMESPath(expr)),
                     "$starlark" => Ok(Expression::Starlark(expr)),
+                    "$cel" => Ok(Expression::Cel(expr)),
                     other => Err(de::Error::custom(format!(
-                        "expected '$jmespath' or '$starlark', found '{}'",
+                        "expected '$jmespath', '$starlark', or '$cel', found '{}'",
                         other
                     ))),
                 }
@@ -105,6 +116,31 @@ impl<'de> Deserialize<'de> for Expression {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Expression {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Expression".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let variant = |key: &str| {
+            serde_json::json!({
+                "type": "object",
+                "properties": { key: { "type": "string" } },
+                "required": [key],
+                "additionalProperties": false,
+            })
+        };
+        schemars::json_schema!({
+            "oneOf": [
+                variant("$jmespath"),
+                variant("$starlark"),
+                variant("$cel"),
+            ],
+        })
+    }
+}
+
 impl Expression {
     /// Compiles the expression, allowing array results.
     ///
@@ -120,14 +156,28 @@ impl Expression {
     {
         match self {
             Expression::JMESPath(jmespath) => {
-                let expr = super::JMESPATH_RUNTIME.compile(jmespath)?;
-                let value = expr.search(params)?;
+                let value = match params.definitions() {
+                    Some(definitions) if !definitions.jmespath.is_empty() => {
+                        let ast = definitions.expand_jmespath(jmespath)?;
+                        jmespath::Expression::new(
+                            jmespath,
+                            ast,
+                            &super::JMESPATH_RUNTIME,
+                        )
+                        .search(params)?
+                    }
+                    _ => super::JMESPATH_RUNTIME.compile(jmespath)?.search(params)?,
+                };
                 let json = serde_json::to_value(value)?;
                 Self::deserialize_result(json)
             }
             Expression::Starlark(starlark) => {
                 super::starlark::starlark_eval_one_or_many::<T>(starlark, params)
             }
+            Expression::Cel(cel) => {
+                let json = super::cel::cel_eval(cel, params)?;
+                Self::deserialize_result(json)
+            }
         }
     }
 
@@ -198,9 +248,10 @@ impl Expression {
 /// {"$starlark": "input['greeting']"}
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum WithExpression<T> {
-    /// An expression (JMESPath or Starlark) to evaluate.
+    /// An expression (JMESPath, Starlark, or CEL) to evaluate.
     Expression(Expression),
     /// A literal value.
     Value(T),
@@ -279,6 +330,9 @@ mod tests {
             input: Input::Object(IndexMap::new()),
             output: None,
             map: None,
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -302,6 +356,9 @@ mod tests {
             input: Input::Object(map),
             output: None,
             map: None,
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -592,6 +649,71 @@ mod tests {
         assert_eq!(atcs.len(), 1);
     }
 
+    #[test]
+    fn expression_serde_round_trips_each_language() {
+        for (json, expected) in [
+            (r#"{"$jmespath":"input"}"#, "JMESPath"),
+            (r#"{"$starlark":"input"}"#, "Starlark"),
+            (r#"{"$cel":"input"}"#, "Cel"),
+        ] {
+            let expr: Expression = serde_json::from_str(json).unwrap();
+            assert!(format!("{:?}", expr).starts_with(expected));
+            assert_eq!(serde_json::to_string(&expr).unwrap(), json);
+        }
+        assert!(serde_json::from_str::<Expression>(r#"{"$cue":"input"}"#).is_err());
+    }
+
+    #[test]
+    fn expression_outputs_from_cel() {
+        let params = params_with_object(vec![
+            ("name", Input::String("alice".to_string())),
+            (
+                "scores",
+                Input::Array(vec![Input::Integer(1), Input::Integer(3)]),
+            ),
+        ]);
+        let cel = |code: &str| Expression::Cel(code.to_string());
+
+        let input: Input = cel("{'greeting': 'hi ' + input.name}")
+            .compile_one(&params)
+            .unwrap();
+        assert!(matches!(input, Input::Object(ref o) if o.contains_key("greeting")));
+
+        let messages: Vec<WithExpression<MessageExpression>> = cel(
+            "[{'role': 'user', 'content': [{'type': 'text', 'text': input.name}]}]",
+        )
+        .compile_one(&params)
+        .unwrap();
+        assert!(matches!(
+            messages[0],
+            WithExpression::Value(MessageExpression::User(_))
+        ));
+
+        let tools: Vec<WithExpression<ToolExpression>> = cel(
+            "[{'type': 'function', 'function': {'name': 'do_' + input.name}}]",
+        )
+        .compile_one(&params)
+        .unwrap();
+        assert_eq!(tools.len(), 1);
+
+        let vector: FunctionOutput = cel(
+            "input.scores.map(s, double(s) / double(input.scores[0] + input.scores[1]))",
+        )
+        .compile_one(&params)
+        .unwrap();
+        match vector {
+            FunctionOutput::Vector(v) => assert_eq!(v.len(), 2),
+            other => panic!("expected FunctionOutput::Vector, got {:?}", other),
+        }
+
+        let many: OneOrMany<i64> =
+            cel("input.scores.filter(s, s > 1)").compile_one_or_many(&params).unwrap();
+        assert!(matches!(many, OneOrMany::One(3)));
+
+        let err = cel("input.missing").compile_one::<Input>(&params).unwrap_err();
+        assert!(matches!(err, ExpressionError::CelEvalError(_)));
+    }
+
     #[test]
     fn expression_outputs_function_output_scalar_and_vector() {
         let params = empty_params();
@@ -17,6 +17,7 @@ use starlark::values::{Heap as StarlarkHeap, UnpackValue, Value as StarlarkValue
 /// Can be a single expression (producing one sub-array) or multiple
 /// expressions (each producing a sub-array).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum InputMaps {
     /// A single expression producing one sub-array.
@@ -63,6 +64,7 @@ impl InputMaps {
 /// Represents any JSON-like value that can be passed to a Function,
 /// including rich content types (images, audio, video, files).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Input {
     /// Rich content (image, audio, video, file).
@@ -414,6 +416,7 @@ impl Input {
 /// Similar to [`Input`] but object values and array elements can be
 /// expressions (JMESPath or Starlark) that are evaluated during compilation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum InputExpression {
     /// Rich content (image, audio, video, file).
@@ -785,8 +788,51 @@ impl Serialize for InputSchema {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for InputSchema {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "InputSchema".into()
+    }
+
+    fn json_schema(
+        generator: &mut schemars::SchemaGenerator,
+    ) -> schemars::Schema {
+        fn typed<T: schemars::JsonSchema>(
+            generator: &mut schemars::SchemaGenerator,
+            r#type: &str,
+        ) -> schemars::Schema {
+            schemars::json_schema!({
+                "allOf": [
+                    generator.subschema_for::<T>(),
+                    {
+                        "type": "object",
+                        "properties": { "type": { "const": r#type } },
+                        "required": ["type"],
+                    },
+                ],
+            })
+        }
+        schemars::json_schema!({
+            "anyOf": [
+                typed::<ObjectInputSchema>(generator, "object"),
+                typed::<ArrayInputSchema>(generator, "array"),
+                typed::<StringInputSchema>(generator, "string"),
+                typed::<IntegerInputSchema>(generator, "integer"),
+                typed::<NumberInputSchema>(generator, "number"),
+                typed::<BooleanInputSchema>(generator, "boolean"),
+                typed::<ImageInputSchema>(generator, "image"),
+                typed::<AudioInputSchema>(generator, "audio"),
+                typed::<VideoInputSchema>(generator, "video"),
+                typed::<FileInputSchema>(generator, "file"),
+                generator.subschema_for::<AnyOfInputSchema>(),
+            ],
+        })
+    }
+}
+
 /// Schema for a union of possible types - input must match at least one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AnyOfInputSchema {
     /// The possible schemas that the input can match.
@@ -804,6 +850,7 @@ impl AnyOfInputSchema {
 
 /// Schema for an object input with named properties.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectInputSchema {
     /// Human-readable description of the object.
@@ -836,6 +883,7 @@ impl ObjectInputSchema {
 
 /// Schema for an array input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ArrayInputSchema {
     /// Human-readable description of the array.
@@ -875,6 +923,7 @@ impl ArrayInputSchema {
 
 /// Schema for a string input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct StringInputSchema {
     /// Human-readable description of the string.
@@ -903,6 +952,7 @@ impl StringInputSchema {
 
 /// Schema for an integer input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct IntegerInputSchema {
     /// Human-readable description of the integer.
@@ -956,6 +1006,7 @@ impl IntegerInputSchema {
 
 /// Schema for a floating-point number input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct NumberInputSchema {
     /// Human-readable description of the number.
@@ -1007,6 +1058,7 @@ impl NumberInputSchema {
 
 /// Schema for a boolean input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BooleanInputSchema {
     /// Human-readable description of the boolean.
@@ -1026,6 +1078,7 @@ impl BooleanInputSchema {
 
 /// Schema for an image input (URL or base64-encoded).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ImageInputSchema {
     /// Human-readable description of the expected image.
@@ -1049,6 +1102,7 @@ impl ImageInputSchema {
 
 /// Schema for an audio input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AudioInputSchema {
     /// Human-readable description of the expected audio.
@@ -1072,6 +1126,7 @@ impl AudioInputSchema {
 
 /// Schema for a video input (URL or base64-encoded).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct VideoInputSchema {
     /// Human-readable description of the expected video.
@@ -1100,6 +1155,7 @@ impl VideoInputSchema {
 
 /// Schema for a file input.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FileInputSchema {
     /// Human-readable description of the expected file.
@@ -8,10 +8,11 @@
 //!
 //! - **JMESPath** (`{"$jmespath": "..."}`) - JSON query language
 //! - **Starlark** (`{"$starlark": "..."}`) - Python-like configuration language
+//! - **CEL** (`{"$cel": "..."}`) - Common Expression Language
 //!
 //! # Key Types
 //!
-//! - [`Expression`] - Either a JMESPath or Starlark expression
+//! - [`Expression`] - A JMESPath, Starlark, or CEL expression
 //! - [`WithExpression<T>`] - Either a literal value or an expression
 //! - [`Input`] - The input data structure passed to expressions
 //! - [`Params`] - Context available during expression evaluation
@@ -20,9 +21,17 @@
 //!
 //! Expressions can access:
 //! - `input` - The function's input data
-//! - `tasks` - Results from previously executed tasks
+//! - `output` - The task's raw result (in task output expressions)
 //! - `map` - Current map element (when in mapped task context)
+//! - `tasks` - Outputs of earlier tasks (in tasks with `depends_on`), or of
+//!   all tasks (in aggregation expressions)
+//! - `weights` - Profile weights of all tasks (in aggregation expressions)
+//!
+//! Functions may also declare [`Definitions`]: named Starlark functions and
+//! JMESPath fragments callable from any expression in the Function.
 
+pub(crate) mod cel;
+mod definitions;
 mod error;
 mod expression;
 mod input;
@@ -30,6 +39,7 @@ mod params;
 mod runtime;
 mod starlark;
 
+pub use definitions::*;
 pub use error::*;
 pub use expression::*;
 pub use input::*;
@@ -10,8 +10,10 @@ use starlark::values::{Heap as StarlarkHeap, UnpackValue, Value as StarlarkValue
 
 /// Context for evaluating expressions (JMESPath or Starlark).
 ///
-/// Contains all data accessible within expressions: `input`, `tasks`, and `map`.
+/// Contains all data accessible within expressions: `input`, `output`, `map`,
+/// `tasks`, and `weights`.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Params<'i, 'to, 'm> {
     /// Owned version (for deserialization).
@@ -30,8 +32,27 @@ impl<'de> serde::Deserialize<'de> for Params<'static, 'static, 'static> {
     }
 }
 
+impl Params<'_, '_, '_> {
+    /// Named definitions of the enclosing Function, if any.
+    pub fn definitions(&self) -> Option<&super::Definitions> {
+        match self {
+            Params::Owned(owned) => owned.definitions.as_deref(),
+            Params::Ref(r) => r.definitions,
+        }
+    }
+
+    /// Outputs of earlier tasks, if this is a dependent task expression.
+    pub fn tasks(&self) -> Option<&[Option<FunctionOutput>]> {
+        match self {
+            Params::Owned(owned) => owned.tasks.as_deref(),
+            Params::Ref(r) => r.tasks,
+        }
+    }
+}
+
 /// Owned version of expression parameters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ParamsOwned {
     /// The function's input data.
     pub input: super::Input,
@@ -39,10 +60,20 @@ pub struct ParamsOwned {
     pub output: Option<TaskOutputOwned>,
     /// Current map element. Only populated for mapped task expressions.
     pub map: Option<super::Input>,
+    /// Outputs of earlier tasks, indexed by task. Only populated for tasks
+    /// with `depends_on`; entries not listed there (or skipped) are `null`.
+    pub tasks: Option<Vec<Option<FunctionOutput>>>,
+    /// Profile weights of the Function's tasks, indexed by task. Only
+    /// populated for aggregation expressions.
+    pub weights: Option<Vec<rust_decimal::Decimal>>,
+    /// Named definitions of the enclosing Function, if any.
+    #[serde(skip)]
+    pub definitions: Option<Box<super::Definitions>>,
 }
 
 /// Borrowed version of expression parameters.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ParamsRef<'i, 'to, 'm> {
     /// The function's input data.
     pub input: &'i super::Input,
@@ -50,10 +81,20 @@ pub struct ParamsRef<'i, 'to, 'm> {
     pub output: Option<TaskOutput<'to>>,
     /// Current map element. Only populated for mapped task expressions.
     pub map: Option<&'m super::Input>,
+    /// Outputs of earlier tasks, indexed by task. Only populated for tasks
+    /// with `depends_on`; entries not listed there (or skipped) are `null`.
+    pub tasks: Option<&'to [Option<FunctionOutput>]>,
+    /// Profile weights of the Function's tasks, indexed by task. Only
+    /// populated for aggregation expressions.
+    pub weights: Option<&'to [rust_decimal::Decimal]>,
+    /// Named definitions of the enclosing Function, if any.
+    #[serde(skip)]
+    pub definitions: Option<&'i super::Definitions>,
 }
 
 /// Output from an executed task.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TaskOutput<'a> {
     /// Owned version.
@@ -84,6 +125,7 @@ impl<'de> serde::Deserialize<'de> for TaskOutput<'static> {
 
 /// Owned task output variants.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TaskOutputOwned {
     /// Output from a single function task.
@@ -109,6 +151,7 @@ impl ToStarlarkValue for TaskOutputOwned {
 
 /// Borrowed task output variants.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TaskOutputRef<'a> {
     /// Output from a single function task.
@@ -134,6 +177,7 @@ impl<'a> ToStarlarkValue for TaskOutputRef<'a> {
 
 /// Output from a vector completion task.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionOutput {
     /// Individual votes from each LLM.
     pub votes: Vec<vector::completions::response::Vote>,
@@ -141,6 +185,9 @@ pub struct VectorCompletionOutput {
     pub scores: Vec<rust_decimal::Decimal>,
     /// Total weight allocated to each response option.
     pub weights: Vec<rust_decimal::Decimal>,
+    /// The combined rating, if the task has a rating scale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<vector::completions::response::Rating>,
 }
 
 impl VectorCompletionOutput {
@@ -159,6 +206,7 @@ impl VectorCompletionOutput {
             votes: Vec::new(),
             scores,
             weights,
+            rating: None,
         }
     }
 }
@@ -171,6 +219,7 @@ impl From<vector::completions::response::streaming::VectorCompletionChunk>
             votes,
             scores,
             weights,
+            rating,
             ..
         }: vector::completions::response::streaming::VectorCompletionChunk,
     ) -> Self {
@@ -178,6 +227,7 @@ impl From<vector::completions::response::streaming::VectorCompletionChunk>
             votes,
             scores,
             weights,
+            rating,
         }
     }
 }
@@ -190,6 +240,7 @@ impl From<vector::completions::response::unary::VectorCompletion>
             votes,
             scores,
             weights,
+            rating,
             ..
         }: vector::completions::response::unary::VectorCompletion,
     ) -> Self {
@@ -197,6 +248,7 @@ impl From<vector::completions::response::unary::VectorCompletion>
             votes,
             scores,
             weights,
+            rating,
         }
     }
 }
@@ -209,12 +261,14 @@ impl ToStarlarkValue for VectorCompletionOutput {
             ("votes", self.votes.to_starlark_value(heap)),
             ("scores", self.scores.to_starlark_value(heap)),
             ("weights", self.weights.to_starlark_value(heap)),
+            ("rating", self.rating.to_starlark_value(heap)),
         ]))
     }
 }
 
 /// Output from a function (scalar or vector).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum FunctionOutput {
     /// A single score in [0, 1].
@@ -1,6 +1,7 @@
 //! Starlark expression evaluation engine.
 //! Provides a sandboxed Starlark runtime for evaluating expressions.
-//! Variables `input`, `output`, and `map` are injected into the global scope.
+//! Variables `input`, `output`, `map`, `tasks`, and `weights` are injected into
+//! the global scope.
 
 use serde_json::{Map as JsonMap, Number as JsonNumber, Value};
 use starlark::environment::{Globals, GlobalsBuilder, Module};
@@ -432,6 +433,18 @@ where
                         m.to_starlark_value(heap)
                     }),
                 );
+                module.set(
+                    "tasks",
+                    owned.tasks.as_ref().map_or(SValue::new_none(), |t| {
+                        t.to_starlark_value(heap)
+                    }),
+                );
+                module.set(
+                    "weights",
+                    owned.weights.as_ref().map_or(SValue::new_none(), |w| {
+                        w.to_starlark_value(heap)
+                    }),
+                );
             }
             super::Params::Ref(r) => {
                 module.set("input", r.input.to_starlark_value(heap));
@@ -447,13 +460,42 @@ where
                         m.to_starlark_value(heap)
                     }),
                 );
+                module.set(
+                    "tasks",
+                    r.tasks.map_or(SValue::new_none(), |t| {
+                        t.to_starlark_value(heap)
+                    }),
+                );
+                module.set(
+                    "weights",
+                    r.weights.map_or(SValue::new_none(), |w| {
+                        w.to_starlark_value(heap)
+                    }),
+                );
             }
         }
     }
+    let mut eval = Evaluator::new(&module);
+    if let Some(definitions) = params.definitions() {
+        for (name, source) in &definitions.starlark {
+            let ast = AstModule::parse(name, source.clone(), &Dialect::Extended)
+                .map_err(|e| {
+                    ExpressionError::StarlarkParseError(format!(
+                        "definition '{}': {}",
+                        name, e
+                    ))
+                })?;
+            eval.eval_module(ast, &STARLARK_GLOBALS).map_err(|e| {
+                ExpressionError::StarlarkEvalError(format!(
+                    "definition '{}': {}",
+                    name, e
+                ))
+            })?;
+        }
+    }
     let ast =
         AstModule::parse("expression", code.to_string(), &Dialect::Extended)
             .map_err(|e| ExpressionError::StarlarkParseError(e.to_string()))?;
-    let mut eval = Evaluator::new(&module);
     let result = eval
         .eval_module(ast, &STARLARK_GLOBALS)
         .map_err(|e| ExpressionError::StarlarkEvalError(e.to_string()))?;
@@ -551,6 +593,9 @@ mod tests {
             input,
             output: None,
             map: None,
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -562,6 +607,9 @@ mod tests {
             input,
             output: Some(output),
             map: None,
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -573,6 +621,9 @@ mod tests {
             input,
             output: None,
             map: Some(map),
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -585,6 +636,9 @@ mod tests {
             input,
             output: Some(output),
             map: Some(map),
+            definitions: None,
+            tasks: None,
+            weights: None,
         })
     }
 
@@ -872,6 +926,7 @@ mod tests {
                 votes: vec![],
                 scores: vec![dec!(0.25), dec!(0.25), dec!(0.5)],
                 weights: vec![dec!(1.0), dec!(1.0), dec!(2.0)],
+                rating: None,
             });
         let params = make_params_with_output(input, output);
 
@@ -914,6 +969,7 @@ mod tests {
                 votes: vec![],
                 scores: vec![dec!(0.3), dec!(0.7)],
                 weights: vec![dec!(1.0), dec!(1.0)],
+                rating: None,
             });
         let map = obj(vec![("index", Input::Integer(1))]);
         let params = make_full_params(input, output, map);
@@ -972,11 +1028,13 @@ mod tests {
                 votes: vec![],
                 scores: vec![dec!(0.5), dec!(0.5)],
                 weights: vec![dec!(1.0), dec!(1.0)],
+                rating: None,
             },
             VectorCompletionOutput {
                 votes: vec![],
                 scores: vec![dec!(0.3), dec!(0.7)],
                 weights: vec![dec!(0.5), dec!(0.5)],
+                rating: None,
             },
         ]);
         let params = make_params_with_output(input, output);
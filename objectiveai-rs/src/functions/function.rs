@@ -28,6 +28,7 @@ use serde::{Deserialize, Serialize};
 /// Use [`compile_tasks`](Self::compile_tasks) to preview how task expressions resolve
 /// for given inputs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Function {
     /// A remote function with metadata (description, schema, etc.).
@@ -82,19 +83,27 @@ impl Function {
         Option<Vec<Vec<super::expression::Input>>>,
         super::expression::ExpressionError,
     > {
-        let input_maps_expr = match self {
-            Function::Remote(RemoteFunction::Scalar { input_maps, .. }) => {
-                input_maps
-            }
-            Function::Remote(RemoteFunction::Vector { input_maps, .. }) => {
-                input_maps
-            }
-            Function::Inline(InlineFunction::Scalar { input_maps, .. }) => {
-                input_maps
-            }
-            Function::Inline(InlineFunction::Vector { input_maps, .. }) => {
-                input_maps
-            }
+        let (definitions, input_maps_expr) = match self {
+            Function::Remote(RemoteFunction::Scalar {
+                definitions,
+                input_maps,
+                ..
+            }) => (definitions, input_maps),
+            Function::Remote(RemoteFunction::Vector {
+                definitions,
+                input_maps,
+                ..
+            }) => (definitions, input_maps),
+            Function::Inline(InlineFunction::Scalar {
+                definitions,
+                input_maps,
+                ..
+            }) => (definitions, input_maps),
+            Function::Inline(InlineFunction::Vector {
+                definitions,
+                input_maps,
+                ..
+            }) => (definitions, input_maps),
         };
         match input_maps_expr {
             Some(input_maps_expr) => {
@@ -104,6 +113,9 @@ impl Function {
                         input,
                         output: None,
                         map: None,
+                        definitions: definitions.as_deref(),
+                        tasks: None,
+                        weights: None,
                     },
                 );
                 // compile input_maps
@@ -119,11 +131,13 @@ impl Function {
     /// Evaluates all expressions (JMESPath or Starlark) in the function's tasks
     /// using the provided input data. Tasks with `skip` expressions that evaluate
     /// to true return `None`. Tasks with `map` fields produce multiple task instances.
+    /// Tasks with `depends_on` are not compiled here; see
+    /// [`compile_task`](Self::compile_task).
     ///
     /// # Returns
     ///
     /// A vector where each element corresponds to a task definition:
-    /// - `None` if the task was skipped
+    /// - `None` if the task was skipped or has `depends_on`
     /// - `Some(CompiledTask::One(...))` for non-mapped tasks
     /// - `Some(CompiledTask::Many(...))` for mapped tasks
     pub fn compile_tasks(
@@ -134,27 +148,31 @@ impl Function {
         super::expression::ExpressionError,
     > {
         // extract input_maps expression and task expressions
-        let (input_maps_expr, task_exprs) = match self {
+        let (definitions, input_maps_expr, task_exprs) = match self {
             Function::Remote(RemoteFunction::Scalar {
+                definitions,
                 input_maps,
                 tasks,
                 ..
-            }) => (input_maps, tasks),
+            }) => (definitions, input_maps, tasks),
             Function::Remote(RemoteFunction::Vector {
+                definitions,
                 input_maps,
                 tasks,
                 ..
-            }) => (input_maps, tasks),
+            }) => (definitions, input_maps, tasks),
             Function::Inline(InlineFunction::Scalar {
+                definitions,
                 input_maps,
                 tasks,
                 ..
-            }) => (input_maps, tasks),
+            }) => (definitions, input_maps, tasks),
             Function::Inline(InlineFunction::Vector {
+                definitions,
                 input_maps,
                 tasks,
                 ..
-            }) => (input_maps, tasks),
+            }) => (definitions, input_maps, tasks),
         };
 
         // prepare params for compiling expressions
@@ -163,6 +181,9 @@ impl Function {
                 input,
                 output: None,
                 map: None,
+                definitions: definitions.as_deref(),
+                tasks: None,
+                weights: None,
             });
 
         // compile input_maps
@@ -172,57 +193,79 @@ impl Function {
             None
         };
 
-        // compile tasks
+        // compile tasks, leaving dependent tasks for compile_task
         let mut tasks = Vec::with_capacity(task_exprs.len());
-        for mut task_expr in task_exprs {
-            tasks.push(
-                if let Some(skip_expr) = task_expr.take_skip()
-                    && skip_expr.compile_one::<bool>(&params)?
-                {
-                    // None if task is skipped
-                    None
-                } else if let Some(input_map_index) = task_expr.input_map() {
-                    // for map tasks, map input to multiple instances of the task
-                    if let Some(input_maps) = &input_maps
-                        && let Some(input_map) =
-                            input_maps.get(input_map_index as usize)
-                    {
-                        // compile task for each map input
-                        let mut map_tasks = Vec::with_capacity(input_map.len());
-                        for input in input_map {
-                            // set map input
-                            match &mut params {
-                                super::expression::Params::Ref(params_ref) => {
-                                    params_ref.map = Some(input);
-                                }
-                                _ => unreachable!(),
-                            }
-                            // compile task with map input
-                            map_tasks.push(task_expr.clone().compile(&params)?);
-                            // reset map input
-                            match &mut params {
-                                super::expression::Params::Ref(params_ref) => {
-                                    params_ref.map = None;
-                                }
-                                _ => unreachable!(),
-                            }
-                        }
-                        Some(super::CompiledTask::Many(map_tasks))
-                    } else {
-                        // no map found is treated as empty map
-                        Some(super::CompiledTask::Many(Vec::new()))
-                    }
-                } else {
-                    // compile single task
-                    Some(super::CompiledTask::One(task_expr.compile(&params)?))
-                },
-            );
+        for task_expr in task_exprs {
+            tasks.push(if task_expr.depends_on().is_some() {
+                None
+            } else {
+                compile_task_expression(
+                    task_expr,
+                    &mut params,
+                    input_maps.as_deref(),
+                )?
+            });
         }
 
         // compiled tasks
         Ok(tasks)
     }
 
+    /// Compiles a single task once the outputs of earlier tasks are known.
+    ///
+    /// Tasks with `depends_on` are left as `None` by
+    /// [`compile_tasks`](Self::compile_tasks) and are compiled with this
+    /// method instead, after the tasks they depend on have finished.
+    /// `task_outputs[i]` is the output of task `i` (`None` if it was skipped);
+    /// only the outputs listed in `depends_on` are visible as `tasks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn compile_task(
+        &self,
+        index: usize,
+        input: &super::expression::Input,
+        task_outputs: &[Option<super::expression::FunctionOutput>],
+    ) -> Result<
+        Option<super::CompiledTask>,
+        super::expression::ExpressionError,
+    > {
+        let task_expr = self.tasks()[index].clone();
+        let prior_outputs = task_expr.depends_on().map(|depends_on| {
+            (0..index)
+                .map(|i| {
+                    if depends_on.contains(&(i as u64)) {
+                        task_outputs.get(i).cloned().flatten()
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // prepare params for compiling expressions
+        let mut params =
+            super::expression::Params::Ref(super::expression::ParamsRef {
+                input,
+                output: None,
+                map: None,
+                definitions: self.definitions(),
+                tasks: prior_outputs.as_deref(),
+                weights: None,
+            });
+
+        // compile input_maps, only needed for mapped tasks
+        let input_maps = match (task_expr.input_map(), self.input_maps()) {
+            (Some(_), Some(input_maps_expr)) => {
+                Some(input_maps_expr.clone().compile(&params)?)
+            }
+            _ => None,
+        };
+
+        compile_task_expression(task_expr, &mut params, input_maps.as_deref())
+    }
+
     // /// Computes the final output given input and task outputs.
     // ///
     // /// Evaluates the function's output expression using the provided input data
@@ -340,13 +383,15 @@ impl Function {
         self,
         input: &super::expression::Input,
     ) -> Result<Option<u64>, super::expression::ExpressionError> {
-        let output_length_expr = match self {
-            Function::Remote(RemoteFunction::Scalar { .. }) => None,
+        let (definitions, output_length_expr) = match self {
+            Function::Remote(RemoteFunction::Scalar { .. }) => (None, None),
             Function::Remote(RemoteFunction::Vector {
-                output_length, ..
-            }) => Some(output_length),
-            Function::Inline(InlineFunction::Scalar { .. }) => None,
-            Function::Inline(InlineFunction::Vector { .. }) => None,
+                definitions,
+                output_length,
+                ..
+            }) => (definitions, Some(output_length)),
+            Function::Inline(InlineFunction::Scalar { .. }) => (None, None),
+            Function::Inline(InlineFunction::Vector { .. }) => (None, None),
         };
         match output_length_expr {
             Some(output_length_expr) => {
@@ -356,6 +401,9 @@ impl Function {
                         input,
                         output: None,
                         map: None,
+                        definitions: definitions.as_deref(),
+                        tasks: None,
+                        weights: None,
                     },
                 );
                 // compile output_length
@@ -388,15 +436,19 @@ impl Function {
         Option<Vec<super::expression::Input>>,
         super::expression::ExpressionError,
     > {
-        let input_split_expr = match self {
-            Function::Remote(RemoteFunction::Scalar { .. }) => None,
+        let (definitions, input_split_expr) = match self {
+            Function::Remote(RemoteFunction::Scalar { .. }) => (None, None),
             Function::Remote(RemoteFunction::Vector {
-                input_split, ..
-            }) => Some(input_split),
-            Function::Inline(InlineFunction::Scalar { .. }) => None,
+                definitions,
+                input_split,
+                ..
+            }) => (definitions, Some(input_split)),
+            Function::Inline(InlineFunction::Scalar { .. }) => (None, None),
             Function::Inline(InlineFunction::Vector {
-                input_split, ..
-            }) => input_split,
+                definitions,
+                input_split,
+                ..
+            }) => (definitions, input_split),
         };
         match input_split_expr {
             Some(input_split_expr) => {
@@ -406,6 +458,9 @@ impl Function {
                         input,
                         output: None,
                         map: None,
+                        definitions: definitions.as_deref(),
+                        tasks: None,
+                        weights: None,
                     },
                 );
                 // compile input_split
@@ -438,15 +493,19 @@ impl Function {
         Option<super::expression::Input>,
         super::expression::ExpressionError,
     > {
-        let input_merge_expr = match self {
-            Function::Remote(RemoteFunction::Scalar { .. }) => None,
+        let (definitions, input_merge_expr) = match self {
+            Function::Remote(RemoteFunction::Scalar { .. }) => (None, None),
             Function::Remote(RemoteFunction::Vector {
-                input_merge, ..
-            }) => Some(input_merge),
-            Function::Inline(InlineFunction::Scalar { .. }) => None,
+                definitions,
+                input_merge,
+                ..
+            }) => (definitions, Some(input_merge)),
+            Function::Inline(InlineFunction::Scalar { .. }) => (None, None),
             Function::Inline(InlineFunction::Vector {
-                input_merge, ..
-            }) => input_merge,
+                definitions,
+                input_merge,
+                ..
+            }) => (definitions, input_merge),
         };
         match input_merge_expr {
             Some(input_merge_expr) => {
@@ -456,6 +515,9 @@ impl Function {
                         input,
                         output: None,
                         map: None,
+                        definitions: definitions.as_deref(),
+                        tasks: None,
+                        weights: None,
                     },
                 );
                 // compile input_merge
@@ -486,6 +548,14 @@ impl Function {
         }
     }
 
+    /// Returns the function's named definitions, if defined.
+    pub fn definitions(&self) -> Option<&super::expression::Definitions> {
+        match self {
+            Function::Remote(remote_function) => remote_function.definitions(),
+            Function::Inline(inline_function) => inline_function.definitions(),
+        }
+    }
+
     /// Returns the function's input maps, if defined.
     pub fn input_maps(&self) -> Option<&super::expression::InputMaps> {
         match self {
@@ -502,6 +572,14 @@ impl Function {
         }
     }
 
+    /// Returns how the function's task outputs are combined, if defined.
+    pub fn aggregation(&self) -> Option<&super::Aggregation> {
+        match self {
+            Function::Remote(remote_function) => remote_function.aggregation(),
+            Function::Inline(inline_function) => inline_function.aggregation(),
+        }
+    }
+
     /// Returns the function's expected output length expression, if defined.
     pub fn output_length(
         &self,
@@ -543,6 +621,7 @@ impl Function {
 /// referenced by `remote/owner/repository`. They include documentation fields
 /// that inline functions lack.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum RemoteFunction {
     /// Produces a single score in [0, 1].
@@ -552,6 +631,10 @@ pub enum RemoteFunction {
         description: String,
         /// JSON Schema defining the expected input structure.
         input_schema: super::expression::InputSchema,
+        /// Named Starlark functions and JMESPath fragments callable from
+        /// any expression in this function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definitions: Option<Box<super::expression::Definitions>>,
         /// Expressions that transform input into a 2D array for mapped tasks.
         /// Each sub-array can be referenced by tasks via their `map` index.
         /// Receives: `input`.
@@ -562,6 +645,10 @@ pub enum RemoteFunction {
         /// Each instance is compiled with `map` set to that element's value.
         /// Receives: `input`, `map` (if mapped).
         tasks: Vec<super::TaskExpression>,
+        /// How task outputs are combined into the final output. Defaults to
+        /// the weighted mean.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        aggregation: Option<Box<super::Aggregation>>,
     },
     /// Produces a vector of scores that sums to 1.
     #[serde(rename = "vector.function")]
@@ -570,6 +657,10 @@ pub enum RemoteFunction {
         description: String,
         /// JSON Schema defining the expected input structure.
         input_schema: super::expression::InputSchema,
+        /// Named Starlark functions and JMESPath fragments callable from
+        /// any expression in this function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definitions: Option<Box<super::expression::Definitions>>,
         /// Expressions that transform input into a 2D array for mapped tasks.
         /// Each sub-array can be referenced by tasks via their `map` index.
         /// Receives: `input`.
@@ -580,6 +671,10 @@ pub enum RemoteFunction {
         /// Each instance is compiled with `map` set to that element's value.
         /// Receives: `input`, `map` (if mapped).
         tasks: Vec<super::TaskExpression>,
+        /// How task outputs are combined into the final output. Defaults to
+        /// the weighted mean.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        aggregation: Option<Box<super::Aggregation>>,
         /// Expression computing the expected output vector length for task outputs.
         /// Receives: `input`.
         output_length: super::expression::WithExpression<u64>,
@@ -614,6 +709,14 @@ impl RemoteFunction {
         }
     }
 
+    /// Returns the function's named definitions, if defined.
+    pub fn definitions(&self) -> Option<&super::expression::Definitions> {
+        match self {
+            RemoteFunction::Scalar { definitions, .. } => definitions.as_deref(),
+            RemoteFunction::Vector { definitions, .. } => definitions.as_deref(),
+        }
+    }
+
     /// Returns the function's input maps, if defined.
     pub fn input_maps(&self) -> Option<&super::expression::InputMaps> {
         match self {
@@ -630,6 +733,14 @@ impl RemoteFunction {
         }
     }
 
+    /// Returns how the function's task outputs are combined, if defined.
+    pub fn aggregation(&self) -> Option<&super::Aggregation> {
+        match self {
+            RemoteFunction::Scalar { aggregation, .. } => aggregation.as_deref(),
+            RemoteFunction::Vector { aggregation, .. } => aggregation.as_deref(),
+        }
+    }
+
     /// Returns the function's expected output length, if defined (vector functions only).
     pub fn output_length(
         &self,
@@ -669,11 +780,16 @@ impl RemoteFunction {
 /// referencing a remote function. Lacks description and input
 /// schema fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum InlineFunction {
     /// Produces a single score in [0, 1].
     #[serde(rename = "scalar.function")]
     Scalar {
+        /// Named Starlark functions and JMESPath fragments callable from
+        /// any expression in this function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definitions: Option<Box<super::expression::Definitions>>,
         /// Expressions that transform input into a 2D array for mapped tasks.
         /// Each sub-array can be referenced by tasks via their `map` index.
         /// Receives: `input`.
@@ -684,10 +800,18 @@ pub enum InlineFunction {
         /// Each instance is compiled with `map` set to that element's value.
         /// Receives: `input`, `map` (if mapped).
         tasks: Vec<super::TaskExpression>,
+        /// How task outputs are combined into the final output. Defaults to
+        /// the weighted mean.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        aggregation: Option<Box<super::Aggregation>>,
     },
     /// Produces a vector of scores that sums to 1.
     #[serde(rename = "vector.function")]
     Vector {
+        /// Named Starlark functions and JMESPath fragments callable from
+        /// any expression in this function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definitions: Option<Box<super::expression::Definitions>>,
         /// Expressions that transform input into a 2D array for mapped tasks.
         /// Each sub-array can be referenced by tasks via their `map` index.
         /// Receives: `input`.
@@ -698,6 +822,10 @@ pub enum InlineFunction {
         /// Each instance is compiled with `map` set to that element's value.
         /// Receives: `input`, `map` (if mapped).
         tasks: Vec<super::TaskExpression>,
+        /// How task outputs are combined into the final output. Defaults to
+        /// the weighted mean.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        aggregation: Option<Box<super::Aggregation>>,
         /// Expression transforming input into an input array of the output_length
         /// When the Function is executed with any input from the array,
         /// The output_length should be 1.
@@ -716,6 +844,14 @@ pub enum InlineFunction {
 }
 
 impl InlineFunction {
+    /// Returns the function's named definitions, if defined.
+    pub fn definitions(&self) -> Option<&super::expression::Definitions> {
+        match self {
+            InlineFunction::Scalar { definitions, .. } => definitions.as_deref(),
+            InlineFunction::Vector { definitions, .. } => definitions.as_deref(),
+        }
+    }
+
     /// Returns the function's input maps, if defined.
     pub fn input_maps(&self) -> Option<&super::expression::InputMaps> {
         match self {
@@ -732,6 +868,14 @@ impl InlineFunction {
         }
     }
 
+    /// Returns how the function's task outputs are combined, if defined.
+    pub fn aggregation(&self) -> Option<&super::Aggregation> {
+        match self {
+            InlineFunction::Scalar { aggregation, .. } => aggregation.as_deref(),
+            InlineFunction::Vector { aggregation, .. } => aggregation.as_deref(),
+        }
+    }
+
     /// Returns the function's input_split expression, if defined (vector functions only).
     pub fn input_split(
         &self,
@@ -754,3 +898,50 @@ impl InlineFunction {
         }
     }
 }
+
+/// Compiles one task expression, returning `None` if it is skipped.
+fn compile_task_expression<'m>(
+    mut task_expr: super::TaskExpression,
+    params: &mut super::expression::Params<'_, '_, 'm>,
+    input_maps: Option<&'m [Vec<super::expression::Input>]>,
+) -> Result<Option<super::CompiledTask>, super::expression::ExpressionError> {
+    if let Some(skip_expr) = task_expr.take_skip()
+        && skip_expr.compile_one::<bool>(params)?
+    {
+        // None if task is skipped
+        Ok(None)
+    } else if let Some(input_map_index) = task_expr.input_map() {
+        // for map tasks, map input to multiple instances of the task
+        if let Some(input_maps) = input_maps
+            && let Some(input_map) = input_maps.get(input_map_index as usize)
+        {
+            // compile task for each map input
+            let mut map_tasks = Vec::with_capacity(input_map.len());
+            for input in input_map {
+                // set map input
+                match params {
+                    super::expression::Params::Ref(params_ref) => {
+                        params_ref.map = Some(input);
+                    }
+                    _ => unreachable!(),
+                }
+                // compile task with map input
+                map_tasks.push(task_expr.clone().compile(params)?);
+                // reset map input
+                match params {
+                    super::expression::Params::Ref(params_ref) => {
+                        params_ref.map = None;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Ok(Some(super::CompiledTask::Many(map_tasks)))
+        } else {
+            // no map found is treated as empty map
+            Ok(Some(super::CompiledTask::Many(Vec::new())))
+        }
+    } else {
+        // compile single task
+        Ok(Some(super::CompiledTask::One(task_expr.compile(params)?)))
+    }
+}
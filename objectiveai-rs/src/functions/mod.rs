@@ -9,7 +9,7 @@
 //! - **Input schema** - Defines expected input structure
 //! - **Input maps** - Optional expressions to transform input into arrays for mapped tasks
 //! - **Tasks** - A list of operations (Vector Completions or nested Functions)
-//! - **Output** - Expression that combines task results into final score(s)
+//! - **Aggregation** - How task results are combined into final score(s)
 //!
 //! # Function Types
 //!
@@ -30,6 +30,7 @@
 //! can compile these expressions client-side to preview results during Function authoring:
 //!
 //! - [`Function::compile_tasks`] - Resolves task expressions to show final tasks for a given input
+//! - [`Function::compile_task`] - Resolves a task with `depends_on` given earlier task outputs
 //! - [`Function::compile_output`] - Computes the final output given input and task outputs
 //!
 //! # Submodules
@@ -38,6 +39,7 @@
 //! - [`expression`] - Expression evaluation engine (JMESPath and Starlark)
 //! - [`profiles`] - Profile management and computation
 
+mod aggregation;
 pub mod executions;
 pub mod expression;
 mod function;
@@ -48,6 +50,7 @@ pub mod response;
 mod task;
 pub mod quality;
 
+pub use aggregation::*;
 pub use function::*;
 pub use profile::*;
 pub use remote::*;
@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 /// Profiles contain the weights and nested configurations needed to execute
 /// a Function. They correspond to a Function's task structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Profile {
     /// A remote profile with metadata.
@@ -22,6 +23,7 @@ pub enum Profile {
 
 /// A remote profile, either tasks-based or auto.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum RemoteProfile {
     /// Tasks-based profile with per-task configuration.
@@ -32,6 +34,7 @@ pub enum RemoteProfile {
 
 /// An inline profile, either tasks-based or auto.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum InlineProfile {
     /// Tasks-based profile with per-task configuration.
@@ -45,6 +48,7 @@ pub enum InlineProfile {
 /// Stored as `profile.json` in repositories and referenced by
 /// `remote/owner/repository`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RemoteTasksProfile {
     /// Human-readable description of the profile.
     pub description: String,
@@ -63,6 +67,7 @@ pub struct RemoteTasksProfile {
 /// Applies a single ensemble and weights to every vector completion task
 /// in the function, with equal task weights.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RemoteAutoProfile {
     /// Human-readable description of the profile.
     pub description: String,
@@ -74,6 +79,7 @@ pub struct RemoteAutoProfile {
 
 /// An inline tasks-based profile definition without metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InlineTasksProfile {
     /// Configuration for each task in the corresponding Function.
     pub tasks: Vec<TaskProfile>,
@@ -90,6 +96,7 @@ pub struct InlineTasksProfile {
 /// Applies a single ensemble and weights to every vector completion task
 /// in the function, with equal task weights.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InlineAutoProfile {
     /// The ensemble to use for all vector completion tasks.
     pub ensemble: vector::completions::request::Ensemble,
@@ -101,6 +108,7 @@ pub struct InlineAutoProfile {
 ///
 /// Each variant corresponds to a task type in the Function definition.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TaskProfile {
     /// Profile for a nested function task (references another profile).
@@ -2,6 +2,7 @@ use crate::{chat, functions, vector};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionInlineRequestBody {
     pub function: functions::InlineFunction,
     #[serde(flatten)]
@@ -9,6 +10,7 @@ pub struct FunctionInlineRequestBody {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteRequestBody {
     // if present, retries vector completions from previous request
     #[serde(skip_serializing_if = "Option::is_none")]
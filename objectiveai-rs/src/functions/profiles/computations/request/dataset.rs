@@ -4,12 +4,14 @@ use serde::{Deserialize, Serialize};
 pub type Dataset = Vec<DatasetItem>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DatasetItem {
     pub input: functions::expression::Input,
     pub target: Target,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Target {
     Scalar { value: rust_decimal::Decimal }, // desired scalar output
@@ -2,6 +2,7 @@ use crate::functions::Remote;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionRemoteRequestPath {
     pub fremote: Remote,
     pub fowner: String,
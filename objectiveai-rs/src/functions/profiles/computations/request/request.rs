@@ -2,6 +2,7 @@ use crate::functions;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Request {
     FunctionInline {
@@ -46,6 +47,7 @@ impl Request {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum FunctionProfileComputationCreateParams {
     FunctionInline(super::FunctionInlineRequestBody),
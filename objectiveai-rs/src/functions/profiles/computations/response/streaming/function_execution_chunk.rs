@@ -2,6 +2,7 @@ use crate::functions;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionExecutionChunk {
     pub index: u64,
     pub dataset: u64,
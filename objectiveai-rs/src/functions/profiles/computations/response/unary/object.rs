@@ -2,6 +2,7 @@ use crate::functions::profiles::computations::response;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Object {
     #[serde(rename = "function.profile.computation")]
     FunctionProfileComputation,
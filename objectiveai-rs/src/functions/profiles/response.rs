@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// Response from listing profiles.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListProfile {
     /// List of available profiles.
     pub data: Vec<ListProfileItem>,
@@ -12,6 +13,7 @@ pub struct ListProfile {
 
 /// A profile in a list response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListProfileItem {
     /// The remote source where the profile is hosted.
     pub remote: functions::Remote,
@@ -24,6 +26,7 @@ pub struct ListProfileItem {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetProfile {
     pub remote: functions::Remote,
     pub owner: String,
@@ -35,6 +38,7 @@ pub struct GetProfile {
 
 /// Usage statistics for a profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UsageProfile {
     /// Total number of requests made with this profile.
     pub requests: u64,
@@ -4,7 +4,10 @@ use std::collections::{HashMap, HashSet};
 
 use crate::functions::{CompiledTask, RemoteFunction, TaskExpression};
 
+use super::check_definitions::check_definitions;
+use super::check_depends_on::check_depends_on;
 use super::check_description::check_description;
+use super::check_expression_types::check_expression_types;
 use super::check_input_schema::check_input_schema;
 use super::check_scalar_fields::{ScalarFieldsValidation, check_scalar_fields};
 use super::check_output_expression::{
@@ -27,7 +30,8 @@ use super::example_inputs;
 /// 3. No `map` on any task — branch scalar tasks are never mapped
 /// 4. No `vector.completion` tasks (branch functions delegate to child functions)
 /// 5. No vector-like tasks (`vector.function`, `placeholder.vector.function`)
-/// 6. Example inputs compile successfully and placeholder task inputs match their schemas
+/// 6. Expressions type-check against the input schema
+/// 7. Example inputs compile successfully and placeholder task inputs match their schemas
 pub fn check_branch_scalar_function(
     function: &RemoteFunction,
     children: Option<&HashMap<String, RemoteFunction>>,
@@ -103,9 +107,24 @@ pub fn check_branch_scalar_function(
                     i
                 ));
             }
+            TaskExpression::ScalarCompletion(_) => {
+                return Err(format!(
+                    "BS12: Task [{}]: branch functions must not contain scalar.completion tasks",
+                    i
+                ));
+            }
         }
     }
 
+    // Definitions must load before any expression can use them
+    check_definitions(function.definitions())?;
+
+    // Tasks may only depend on earlier tasks
+    check_depends_on(function.tasks())?;
+
+    // Expressions must type-check against the input schema
+    check_expression_types(function)?;
+
     // --- Single generate() loop: compile + validate + diversity tracking ---
     let input_schema = function.input_schema();
     let task_count = tasks.len();
@@ -128,6 +147,7 @@ pub fn check_branch_scalar_function(
                     check_scalar_distribution(
                         j,
                         input,
+                        function.definitions(),
                         task,
                         &ScalarOutputShape::Scalar,
                     )?;
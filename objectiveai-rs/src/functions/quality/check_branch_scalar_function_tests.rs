@@ -12,7 +12,7 @@ use crate::functions::expression::{
 use crate::functions::quality::check_branch_scalar_function;
 use crate::functions::{
     PlaceholderScalarFunctionTaskExpression,
-    PlaceholderVectorFunctionTaskExpression, RemoteFunction,
+    PlaceholderVectorFunctionTaskExpression, Remote, RemoteFunction,
     ScalarFunctionTaskExpression, TaskExpression,
     VectorCompletionTaskExpression, VectorFunctionTaskExpression,
 };
@@ -50,8 +50,10 @@ fn wrong_type_vector() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -76,14 +78,17 @@ fn has_input_maps() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: Some(crate::functions::expression::InputMaps::One(
             Expression::Starlark("input".to_string()),
         )),
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -92,6 +97,7 @@ fn has_input_maps() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS02");
 }
@@ -105,12 +111,15 @@ fn scalar_function_has_map() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark(
@@ -121,6 +130,7 @@ fn scalar_function_has_map() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS04");
 }
@@ -134,6 +144,7 @@ fn placeholder_scalar_has_map() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderScalarFunction(
             PlaceholderScalarFunctionTaskExpression {
@@ -142,6 +153,7 @@ fn placeholder_scalar_has_map() {
                     minimum: Some(1),
                     maximum: Some(10),
                 }),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark(
@@ -152,6 +164,7 @@ fn placeholder_scalar_has_map() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS05");
 }
@@ -165,12 +178,15 @@ fn contains_vector_function() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -179,6 +195,7 @@ fn contains_vector_function() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS06");
 }
@@ -192,6 +209,7 @@ fn contains_placeholder_vector() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderVectorFunction(
             PlaceholderVectorFunctionTaskExpression {
@@ -213,6 +231,7 @@ fn contains_placeholder_vector() {
                 input_merge: WithExpression::Expression(Expression::Starlark(
                     "{'items': [x['items'][0] for x in input]}".to_string(),
                 )),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -221,6 +240,7 @@ fn contains_placeholder_vector() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS07");
 }
@@ -234,9 +254,11 @@ fn contains_vector_completion() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -281,6 +303,7 @@ fn contains_vector_completion() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BS08");
 }
@@ -296,12 +319,15 @@ fn valid_single_scalar_function() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -310,6 +336,7 @@ fn valid_single_scalar_function() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -323,6 +350,7 @@ fn valid_single_placeholder_scalar() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderScalarFunction(
             PlaceholderScalarFunctionTaskExpression {
@@ -331,6 +359,7 @@ fn valid_single_placeholder_scalar() {
                     minimum: Some(1),
                     maximum: Some(10),
                 }),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -339,6 +368,7 @@ fn valid_single_placeholder_scalar() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -352,12 +382,15 @@ fn valid_multiple_tasks() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -372,6 +405,7 @@ fn valid_multiple_tasks() {
                         minimum: Some(1),
                         maximum: Some(10),
                     }),
+                    depends_on: None,
                     skip: None,
                     map: None,
                     input: WithExpression::Expression(Expression::Starlark(
@@ -381,6 +415,7 @@ fn valid_multiple_tasks() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -394,8 +429,10 @@ fn rejects_no_tasks() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
     };
     test_err(&f, "BS03");
 }
@@ -410,12 +447,15 @@ fn description_too_long() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -424,6 +464,7 @@ fn description_too_long() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QD02");
 }
@@ -436,12 +477,15 @@ fn description_empty() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -450,6 +494,7 @@ fn description_empty() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QD01");
 }
@@ -464,12 +509,15 @@ fn scalar_diversity_fail_fixed_input() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -478,9 +526,11 @@ fn scalar_diversity_fail_fixed_input() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -489,6 +539,7 @@ fn scalar_diversity_fail_fixed_input() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test_err(&f, "BS10");
 }
@@ -502,12 +553,15 @@ fn scalar_diversity_fail_fixed_integer() {
             minimum: Some(1),
             maximum: Some(100),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -516,9 +570,11 @@ fn scalar_diversity_fail_fixed_integer() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -527,6 +583,7 @@ fn scalar_diversity_fail_fixed_integer() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test_err(&f, "BS10");
 }
@@ -550,12 +607,15 @@ fn scalar_diversity_fail_third_task_fixed_object() {
             },
             required: Some(vec!["name".to_string(), "score".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -564,9 +624,11 @@ fn scalar_diversity_fail_third_task_fixed_object() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -575,9 +637,11 @@ fn scalar_diversity_fail_third_task_fixed_object() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -586,6 +650,7 @@ fn scalar_diversity_fail_third_task_fixed_object() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test_err(&f, "BS10");
 }
@@ -600,12 +665,15 @@ fn scalar_diversity_pass_string_passthrough() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -614,9 +682,11 @@ fn scalar_diversity_pass_string_passthrough() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -625,6 +695,7 @@ fn scalar_diversity_pass_string_passthrough() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -638,12 +709,15 @@ fn scalar_diversity_pass_integer_derived() {
             minimum: Some(1),
             maximum: Some(1000),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -652,9 +726,11 @@ fn scalar_diversity_pass_integer_derived() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -663,6 +739,7 @@ fn scalar_diversity_pass_integer_derived() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -685,12 +762,15 @@ fn scalar_diversity_pass_object_extract_field() {
             },
             required: Some(vec!["title".to_string(), "author".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -699,9 +779,11 @@ fn scalar_diversity_pass_object_extract_field() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -710,6 +792,7 @@ fn scalar_diversity_pass_object_extract_field() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -732,6 +815,7 @@ fn scalar_diversity_pass_placeholder_with_transform() {
             },
             required: Some(vec!["text".to_string(), "category".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::PlaceholderScalarFunction(
@@ -740,6 +824,7 @@ fn scalar_diversity_pass_placeholder_with_transform() {
                         description: None,
                         r#enum: None,
                     }),
+                    depends_on: None,
                     skip: None,
                     map: None,
                     input: WithExpression::Expression(Expression::Starlark(
@@ -754,6 +839,7 @@ fn scalar_diversity_pass_placeholder_with_transform() {
                         description: None,
                         r#enum: None,
                     }),
+                    depends_on: None,
                     skip: None,
                     map: None,
                     input: WithExpression::Expression(Expression::Starlark(
@@ -764,6 +850,7 @@ fn scalar_diversity_pass_placeholder_with_transform() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -786,12 +873,15 @@ fn scalar_diversity_pass_optional_field_used() {
             },
             required: Some(vec!["name".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -800,9 +890,11 @@ fn scalar_diversity_pass_optional_field_used() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -811,6 +903,7 @@ fn scalar_diversity_pass_optional_field_used() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -828,12 +921,15 @@ fn scalar_diversity_pass_array_input() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -842,9 +938,11 @@ fn scalar_diversity_pass_array_input() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -853,6 +951,7 @@ fn scalar_diversity_pass_array_input() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -876,12 +975,15 @@ fn valid_with_skip_last_task_boolean() {
             },
             required: Some(vec!["text".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -890,9 +992,11 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("input.get('skip_last_task', False)".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -901,6 +1005,7 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -924,12 +1029,15 @@ fn valid_with_skip_on_low_priority() {
             },
             required: Some(vec!["text".to_string(), "priority".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -938,9 +1046,11 @@ fn valid_with_skip_on_low_priority() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['priority'] < 4".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -949,6 +1059,7 @@ fn valid_with_skip_on_low_priority() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -964,12 +1075,15 @@ fn output_distribution_fail_biased_output_expression() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -980,6 +1094,7 @@ fn output_distribution_fail_biased_output_expression() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "OD02");
 }
@@ -993,12 +1108,15 @@ fn output_distribution_pass_identity() {
             minimum: Some(1),
             maximum: Some(10),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -1007,6 +1125,7 @@ fn output_distribution_pass_identity() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1019,12 +1138,15 @@ fn rejects_single_permutation_string_enum() {
             description: None,
             r#enum: Some(vec!["only".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -1033,6 +1155,7 @@ fn rejects_single_permutation_string_enum() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QI01");
 }
@@ -1046,12 +1169,15 @@ fn rejects_single_permutation_integer() {
             minimum: Some(0),
             maximum: Some(0),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -1060,6 +1186,7 @@ fn rejects_single_permutation_integer() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QI01");
 }
@@ -1072,12 +1199,15 @@ fn all_tasks_skipped() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -1086,9 +1216,11 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test2".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -1097,6 +1229,7 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test_err(&f, "CV42");
 }
@@ -5,7 +5,10 @@ use std::collections::{HashMap, HashSet};
 use crate::functions::expression::{Input, Params, ParamsRef};
 use crate::functions::{CompiledTask, Function, RemoteFunction, TaskExpression};
 
+use super::check_definitions::check_definitions;
+use super::check_depends_on::check_depends_on;
 use super::check_description::check_description;
+use super::check_expression_types::check_expression_types;
 use super::check_input_schema::check_input_schema;
 use super::check_output_expression::{
     VectorOutputShape, check_vector_distribution,
@@ -37,7 +40,8 @@ use super::example_inputs;
 /// 5. If only 1 task, it must be unmapped vector
 /// 6. At most 50% of tasks may be mapped scalar
 /// 7. Vector fields (output_length, input_split, input_merge) round-trip correctly
-/// 8. Example inputs compile successfully and placeholder task inputs match their schemas
+/// 8. Expressions type-check against the input schema
+/// 9. Example inputs compile successfully and placeholder task inputs match their schemas
 pub fn check_branch_vector_function(
     function: &RemoteFunction,
     children: Option<&HashMap<String, RemoteFunction>>,
@@ -143,6 +147,12 @@ pub fn check_branch_vector_function(
                     i
                 ));
             }
+            TaskExpression::ScalarCompletion(_) => {
+                return Err(format!(
+                    "BV23: Task [{}]: branch functions must not contain scalar.completion tasks",
+                    i
+                ));
+            }
         }
     }
 
@@ -168,6 +178,15 @@ pub fn check_branch_vector_function(
         ));
     }
 
+    // Definitions must load before any expression can use them
+    check_definitions(function.definitions())?;
+
+    // Tasks may only depend on earlier tasks
+    check_depends_on(function.tasks())?;
+
+    // Expressions must type-check against the input schema
+    check_expression_types(function)?;
+
     // --- Single generate() loop ---
     let vector_fields = VectorFieldsValidation {
         input_schema: input_schema.clone(),
@@ -252,6 +271,9 @@ pub fn check_branch_vector_function(
                 input,
                 output: None,
                 map: None,
+                definitions: function.definitions(),
+                tasks: None,
+                weights: None,
             });
             let ol = output_length
                 .clone()
@@ -268,6 +290,7 @@ pub fn check_branch_vector_function(
                                 check_vector_distribution(
                                     j,
                                     input,
+                                    function.definitions(),
                                     first,
                                     &VectorOutputShape::MapScalar(tasks.len()),
                                     ol,
@@ -282,6 +305,7 @@ pub fn check_branch_vector_function(
                             check_vector_distribution(
                                 j,
                                 input,
+                                function.definitions(),
                                 task,
                                 &VectorOutputShape::Vector(ol as u64),
                                 ol,
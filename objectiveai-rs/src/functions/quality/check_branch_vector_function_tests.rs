@@ -13,7 +13,7 @@ use crate::functions::expression::{
 use crate::functions::quality::check_branch_vector_function;
 use crate::functions::{
     PlaceholderScalarFunctionTaskExpression,
-    PlaceholderVectorFunctionTaskExpression, RemoteFunction,
+    PlaceholderVectorFunctionTaskExpression, Remote, RemoteFunction,
     ScalarFunctionTaskExpression, TaskExpression,
     VectorCompletionTaskExpression, VectorFunctionTaskExpression,
 };
@@ -38,12 +38,15 @@ fn wrong_type_scalar() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -52,6 +55,7 @@ fn wrong_type_scalar() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "BV01");
 }
@@ -64,12 +68,15 @@ fn input_schema_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -78,6 +85,7 @@ fn input_schema_string() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -105,12 +113,15 @@ fn input_schema_object_no_required_array() {
             },
             required: Some(vec!["name".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -119,6 +130,7 @@ fn input_schema_object_no_required_array() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -155,27 +167,33 @@ fn scalar_function_without_map() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None, // missing map
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -206,6 +224,7 @@ fn placeholder_scalar_without_map() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::PlaceholderScalarFunction(PlaceholderScalarFunctionTaskExpression {
@@ -213,21 +232,25 @@ fn placeholder_scalar_without_map() {
                     description: None,
                     r#enum: None,
                 }),
+                depends_on: None,
                 skip: None,
                 map: None, // missing map
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -258,16 +281,20 @@ fn vector_function_with_map() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+            remote: Remote::Github,
             owner: "test".to_string(),
             repository: "test".to_string(),
             commit: "abc123".to_string(),
+            depends_on: None,
             skip: None,
             map: Some(0),
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
             output: Expression::Starlark("output".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -298,6 +325,7 @@ fn placeholder_vector_with_map() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderVectorFunction(PlaceholderVectorFunctionTaskExpression {
             input_schema: InputSchema::Object(ObjectInputSchema {
@@ -322,11 +350,13 @@ fn placeholder_vector_with_map() {
             output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
             input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
             input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
+            depends_on: None,
             skip: None,
             map: Some(0),
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
             output: Expression::Starlark("output".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -357,8 +387,10 @@ fn contains_vector_completion() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+            depends_on: None,
             skip: None,
             map: None,
             messages: WithExpression::Value(vec![WithExpression::Value(
@@ -378,6 +410,7 @@ fn contains_vector_completion() {
             ]),
             output: Expression::Starlark("output['scores']".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -408,18 +441,22 @@ fn single_mapped_scalar_task() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+            remote: Remote::Github,
             owner: "test".to_string(),
             repository: "test".to_string(),
             commit: "abc123".to_string(),
+            depends_on: None,
             skip: None,
             map: Some(0),
             input: WithExpression::Expression(Expression::Starlark("map".to_string())),
             output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -450,38 +487,46 @@ fn over_50_percent_mapped_scalar() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -514,16 +559,20 @@ fn valid_single_vector_function() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+            remote: Remote::Github,
             owner: "test".to_string(),
             repository: "test".to_string(),
             commit: "abc123".to_string(),
+            depends_on: None,
             skip: None,
             map: None,
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
             output: Expression::Starlark("output".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -554,6 +603,7 @@ fn valid_single_placeholder_vector() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderVectorFunction(PlaceholderVectorFunctionTaskExpression {
             input_schema: InputSchema::Object(ObjectInputSchema {
@@ -578,11 +628,13 @@ fn valid_single_placeholder_vector() {
             output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
             input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
             input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
+            depends_on: None,
             skip: None,
             map: None,
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
             output: Expression::Starlark("output".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -613,29 +665,35 @@ fn valid_50_50_split() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -666,38 +724,46 @@ fn valid_mixed_tasks() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -728,27 +794,33 @@ fn valid_all_unmapped_vector() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -777,12 +849,15 @@ fn description_too_long() {
             },
             required: Some(vec!["items".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -791,6 +866,7 @@ fn description_too_long() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -823,12 +899,15 @@ fn description_empty() {
             },
             required: Some(vec!["items".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -837,6 +916,7 @@ fn description_empty() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -875,13 +955,16 @@ fn input_diversity_fail_third_task_fixed_input() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             // Task 0: passes parent input through — OK
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
@@ -889,9 +972,11 @@ fn input_diversity_fail_third_task_fixed_input() {
             }),
             // Task 1: passes input with label modification — OK
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label'] + ' v2'}".to_string())),
@@ -899,15 +984,18 @@ fn input_diversity_fail_third_task_fixed_input() {
             }),
             // Task 2: FIXED input — ignores parent input
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': ['A', 'B'], 'label': 'fixed'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -938,47 +1026,57 @@ fn input_diversity_fail_third_task_mapped_fixed() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label'] + ' v2'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("'constant'".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': 'alt'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1011,27 +1109,33 @@ fn input_diversity_pass_vector_function_passthrough() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label']}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1062,29 +1166,35 @@ fn input_diversity_pass_mixed_mapped_and_unmapped() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1115,6 +1225,7 @@ fn input_diversity_pass_placeholder_vector_tasks() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::PlaceholderVectorFunction(PlaceholderVectorFunctionTaskExpression {
@@ -1136,6 +1247,7 @@ fn input_diversity_pass_placeholder_vector_tasks() {
                 output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
                 input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x]} for x in input['items']]".to_string())),
                 input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input]}".to_string())),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
@@ -1160,12 +1272,14 @@ fn input_diversity_pass_placeholder_vector_tasks() {
                 output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
                 input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x]} for x in input['items']]".to_string())),
                 input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input]}".to_string())),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': [x + ' alt' for x in input['items']]}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1196,38 +1310,46 @@ fn input_diversity_pass_mapped_scalar_with_two_vectors() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label'] + ' alt'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1274,12 +1396,15 @@ fn input_diversity_fail_child_min_items_3() {
     let f = RemoteFunction::Vector {
         description: "test".to_string(),
         input_schema: parent_schema,
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
@@ -1290,12 +1415,14 @@ fn input_diversity_fail_child_min_items_3() {
                 output_length: WithExpression::Expression(Expression::Starlark("len(input['entries'])".to_string())),
                 input_split: WithExpression::Expression(Expression::Starlark("[{'entries': [x]} for x in input['entries']]".to_string())),
                 input_merge: WithExpression::Expression(Expression::Starlark("{'entries': [x['entries'][0] for x in input]}".to_string())),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'entries': input['entries']}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['entries'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'entries': [e], 'tag': input['tag']} for e in input['entries']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'entries': [x['entries'][0] for x in input], 'tag': input[0]['tag']}".to_string())),
@@ -1326,27 +1453,33 @@ fn input_diversity_pass_no_input_maps() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label'] + ' v2'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1377,15 +1510,18 @@ fn input_diversity_fail_with_input_maps_fixed() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             // Task 0: unmapped vector passes input — OK
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
@@ -1393,15 +1529,18 @@ fn input_diversity_fail_with_input_maps_fixed() {
             }),
             // Task 1: mapped scalar uses FIXED input, ignoring map element
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("'always_same'".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1432,8 +1571,10 @@ fn rejects_no_tasks() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1466,29 +1607,35 @@ fn rejects_unused_input_maps() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label']}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1524,27 +1671,33 @@ fn valid_with_skip_last_task_boolean() {
             },
             required: Some(vec!["items".to_string(), "label".to_string(), "skip_last_task".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['skip_last_task']".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label'] + ' v2', 'skip_last_task': input['skip_last_task']}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label'], 'skip_last_task': input['skip_last_task']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label'], 'skip_last_task': input[0]['skip_last_task']}".to_string())),
@@ -1575,27 +1728,33 @@ fn valid_with_skip_on_quick_mode() {
             },
             required: Some(vec!["items".to_string(), "mode".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['mode'] == 'quick'".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'mode': input['mode'] + '-deep'}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'mode': input['mode']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'mode': input[0]['mode']}".to_string())),
@@ -1626,47 +1785,57 @@ fn rejects_out_of_bounds_map_index() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(1), // index 1 doesn't exist
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("{'items': input['items'], 'label': input['label']}".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1699,29 +1868,35 @@ fn output_distribution_pass_mapped_scalar_max_items_10() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1752,38 +1927,46 @@ fn output_distribution_pass_mixed_tasks_max_items_10() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) if sum(output) > 0 else 1.0 / len(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1814,14 +1997,17 @@ fn output_distribution_fail_biased_mapped_scalar() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
@@ -1830,15 +2016,18 @@ fn output_distribution_fail_biased_mapped_scalar() {
                 ),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1869,11 +2058,14 @@ fn output_distribution_fail_biased_unmapped_vector() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+            remote: Remote::Github,
             owner: "test".to_string(),
             repository: "test".to_string(),
             commit: "abc123".to_string(),
+            depends_on: None,
             skip: None,
             map: None,
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
@@ -1881,6 +2073,7 @@ fn output_distribution_fail_biased_unmapped_vector() {
                 "[x * 0.1 + 0.45 for x in output]".to_string(),
             ),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1911,29 +2104,35 @@ fn output_distribution_fail_mapped_scalar_division_by_zero() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::Many(vec![
             Expression::Starlark("input['items']".to_string()),
         ])),
         tasks: vec![
             TaskExpression::ScalarFunction(ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 input: WithExpression::Expression(Expression::Starlark("map".to_string())),
                 output: Expression::Starlark("[x / sum(output) for x in output]".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark("input".to_string())),
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1964,16 +2163,20 @@ fn output_distribution_pass_identity() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+            remote: Remote::Github,
             owner: "test".to_string(),
             repository: "test".to_string(),
             commit: "abc123".to_string(),
+            depends_on: None,
             skip: None,
             map: None,
             input: WithExpression::Expression(Expression::Starlark("input".to_string())),
             output: Expression::Starlark("output".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1994,12 +2197,15 @@ fn rejects_single_permutation_string_enum() {
                 r#enum: Some(vec!["only".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -2008,6 +2214,7 @@ fn rejects_single_permutation_string_enum() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2034,12 +2241,15 @@ fn all_tasks_skipped() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -2048,9 +2258,11 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output".to_string()),
             }),
             TaskExpression::VectorFunction(VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test2".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -2059,6 +2271,7 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2086,12 +2299,15 @@ fn rejects_single_permutation_integer() {
                 maximum: Some(0),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -2100,6 +2316,7 @@ fn rejects_single_permutation_integer() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2126,12 +2343,15 @@ fn output_length_less_than_2() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -2140,6 +2360,7 @@ fn output_length_less_than_2() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
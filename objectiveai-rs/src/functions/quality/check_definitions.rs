@@ -0,0 +1,93 @@
+//! Validation of a function's named definitions.
+//!
+//! Definitions are loaded before every expression, so a broken definition
+//! breaks every expression of its language. Checking them once here gives a
+//! single error pointing at the definition rather than one per expression.
+//!
+//! - DF01: definition name is not a valid identifier
+//! - DF02: definition name shadows `input`, `output`, `map`, `tasks`, or
+//!   `weights`
+//! - DF03: Starlark definitions fail to load
+//! - DF04: Starlark definition does not bind its name
+//! - DF05: JMESPath fragment fails to parse or calls itself
+
+use indexmap::IndexMap;
+
+use crate::functions::expression::{
+    Definitions, Expression, Input, Params, ParamsOwned,
+};
+
+/// Names bound by the expression context, which definitions must not shadow.
+const RESERVED: [&str; 5] = ["input", "output", "map", "tasks", "weights"];
+
+/// Validates a function's definitions, if any.
+///
+/// Starlark definitions are loaded with an empty `input` and no `output`,
+/// `map`, `tasks`, or `weights`, so they must not read the expression context
+/// at load time (reading it from inside a function body is fine).
+pub fn check_definitions(definitions: Option<&Definitions>) -> Result<(), String> {
+    let Some(definitions) = definitions else {
+        return Ok(());
+    };
+
+    for (language, names) in [
+        ("Starlark", &definitions.starlark),
+        ("JMESPath", &definitions.jmespath),
+    ] {
+        for name in names.keys() {
+            if !is_identifier(name) {
+                return Err(format!(
+                    "DF01: {} definition '{}': name must be a valid identifier",
+                    language, name
+                ));
+            }
+            if RESERVED.contains(&name.as_str()) {
+                return Err(format!(
+                    "DF02: {} definition '{}': name shadows the expression \
+                     variable of the same name",
+                    language, name
+                ));
+            }
+        }
+    }
+
+    if !definitions.starlark.is_empty() {
+        let params = Params::Owned(ParamsOwned {
+            input: Input::Object(IndexMap::new()),
+            output: None,
+            map: None,
+            definitions: Some(Box::new(definitions.clone())),
+            tasks: None,
+            weights: None,
+        });
+        Expression::Starlark("True".to_string())
+            .compile_one::<bool>(&params)
+            .map_err(|e| format!("DF03: Starlark definitions failed to load: {}", e))?;
+        for name in definitions.starlark.keys() {
+            Expression::Starlark(format!("type({})", name))
+                .compile_one::<String>(&params)
+                .map_err(|_| {
+                    format!(
+                        "DF04: Starlark definition '{}' does not define '{}'",
+                        name, name
+                    )
+                })?;
+        }
+    }
+
+    for name in definitions.jmespath.keys() {
+        definitions
+            .expand_jmespath(&format!("{}()", name))
+            .map_err(|e| format!("DF05: JMESPath definition '{}': {}", name, e))?;
+    }
+
+    Ok(())
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
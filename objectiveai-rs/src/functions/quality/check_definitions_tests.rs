@@ -0,0 +1,120 @@
+//! Tests for check_definitions.
+
+#![cfg(test)]
+
+use indexmap::IndexMap;
+
+use crate::functions::expression::Definitions;
+use crate::functions::quality::check_definitions;
+use crate::util::index_map;
+
+// ── wrappers ─────────────────────────────────────────────────────────
+
+fn test(definitions: Definitions) {
+    check_definitions(Some(&definitions)).unwrap();
+}
+
+fn test_err(definitions: Definitions, expected: &str) {
+    let err = check_definitions(Some(&definitions)).unwrap_err();
+    assert!(err.contains(expected), "expected '{expected}' in error, got: {err}");
+}
+
+// ── helpers ──────────────────────────────────────────────────────────
+
+fn strings(map: IndexMap<String, &str>) -> IndexMap<String, String> {
+    map.into_iter().map(|(k, v)| (k, v.to_string())).collect()
+}
+
+fn starlark(starlark: IndexMap<String, &str>) -> Definitions {
+    Definitions {
+        starlark: strings(starlark),
+        jmespath: IndexMap::new(),
+    }
+}
+
+fn jmespath(jmespath: IndexMap<String, &str>) -> Definitions {
+    Definitions {
+        starlark: IndexMap::new(),
+        jmespath: strings(jmespath),
+    }
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[test]
+fn no_definitions() {
+    check_definitions(None).unwrap();
+    test(Definitions::default());
+}
+
+#[test]
+fn valid_definitions() {
+    test(Definitions {
+        starlark: strings(index_map! {
+            "clamp" => "def clamp(x):\n    return max(0.0, min(1.0, x))",
+            "first" => "first = lambda: input['items'][0]",
+        }),
+        jmespath: strings(index_map! {
+            "names" => "items[*].name",
+            "first_name" => "names()[0]",
+        }),
+    });
+}
+
+#[test]
+fn invalid_name() {
+    test_err(
+        starlark(index_map! { "my-helper" => "def f():\n    return 1" }),
+        "DF01",
+    );
+    test_err(jmespath(index_map! { "1st" => "@[0]" }), "DF01");
+}
+
+#[test]
+fn shadows_expression_variable() {
+    test_err(
+        starlark(index_map! { "input" => "input = 1" }),
+        "DF02",
+    );
+    test_err(jmespath(index_map! { "output" => "@" }), "DF02");
+}
+
+#[test]
+fn starlark_parse_error() {
+    test_err(
+        starlark(index_map! { "clamp" => "def clamp(x:\n    return x" }),
+        "DF03",
+    );
+}
+
+#[test]
+fn starlark_reads_input_at_load_time() {
+    test_err(
+        starlark(index_map! { "title" => "title = input['title']" }),
+        "DF03",
+    );
+}
+
+#[test]
+fn starlark_does_not_bind_name() {
+    test_err(
+        starlark(index_map! { "clamp" => "def clip(x):\n    return x" }),
+        "DF04: Starlark definition 'clamp' does not define 'clamp'",
+    );
+}
+
+#[test]
+fn jmespath_parse_error() {
+    test_err(jmespath(index_map! { "names" => "items[*." }), "DF05");
+}
+
+#[test]
+fn jmespath_recursive() {
+    test_err(
+        jmespath(index_map! {
+            "a" => "b()",
+            "b" => "a(@)",
+        }),
+        "DF05",
+    );
+}
@@ -0,0 +1,42 @@
+//! Validation of task dependencies.
+//!
+//! A task with `depends_on` is compiled only after the tasks it lists have
+//! finished, so it may only list earlier tasks. This keeps the dependency
+//! graph acyclic and the order of execution the order of declaration.
+//!
+//! - TD01: `depends_on` is empty
+//! - TD02: `depends_on` lists the task itself or a later task
+//! - TD03: `depends_on` lists the same task twice
+
+use crate::functions::TaskExpression;
+
+/// Validates the `depends_on` of every task.
+pub fn check_depends_on(tasks: &[TaskExpression]) -> Result<(), String> {
+    for (i, task) in tasks.iter().enumerate() {
+        let Some(depends_on) = task.depends_on() else {
+            continue;
+        };
+        if depends_on.is_empty() {
+            return Err(format!(
+                "TD01: Task [{}]: depends_on must not be empty, omit it instead",
+                i
+            ));
+        }
+        for (k, &j) in depends_on.iter().enumerate() {
+            if j >= i as u64 {
+                return Err(format!(
+                    "TD02: Task [{}]: depends_on may only list earlier tasks, \
+                     found {}",
+                    i, j
+                ));
+            }
+            if depends_on[..k].contains(&j) {
+                return Err(format!(
+                    "TD03: Task [{}]: depends_on lists task {} more than once",
+                    i, j
+                ));
+            }
+        }
+    }
+    Ok(())
+}
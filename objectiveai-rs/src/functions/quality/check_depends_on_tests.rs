@@ -0,0 +1,67 @@
+//! Tests for check_depends_on.
+
+#![cfg(test)]
+
+use crate::functions::TaskExpression;
+use crate::functions::quality::check_depends_on;
+
+// ── wrappers ─────────────────────────────────────────────────────────
+
+fn test(depends_on: &[Option<Vec<u64>>]) {
+    check_depends_on(&tasks(depends_on)).unwrap();
+}
+
+fn test_err(depends_on: &[Option<Vec<u64>>], expected: &str) {
+    let err = check_depends_on(&tasks(depends_on)).unwrap_err();
+    assert!(err.contains(expected), "expected '{expected}' in error, got: {err}");
+}
+
+// ── helpers ──────────────────────────────────────────────────────────
+
+fn tasks(depends_on: &[Option<Vec<u64>>]) -> Vec<TaskExpression> {
+    depends_on
+        .iter()
+        .map(|depends_on| {
+            serde_json::from_value(serde_json::json!({
+                "type": "vector.completion",
+                "depends_on": depends_on,
+                "messages": [],
+                "responses": [],
+                "output": { "$starlark": "output['scores'][0]" },
+            }))
+            .unwrap()
+        })
+        .collect()
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[test]
+fn no_dependencies() {
+    test(&[None, None]);
+}
+
+#[test]
+fn earlier_tasks() {
+    test(&[None, Some(vec![0]), Some(vec![0, 1])]);
+}
+
+#[test]
+fn empty() {
+    test_err(&[None, Some(vec![])], "TD01");
+}
+
+#[test]
+fn itself() {
+    test_err(&[None, Some(vec![1])], "TD02");
+}
+
+#[test]
+fn later_task() {
+    test_err(&[Some(vec![1]), None], "TD02");
+}
+
+#[test]
+fn duplicate() {
+    test_err(&[None, None, Some(vec![0, 1, 0])], "TD03");
+}
@@ -0,0 +1,1008 @@
+//! Static type checking of expressions against the function's input schema.
+//!
+//! Infers the shape of `input` from the `input_schema` (and of `map` from
+//! `input_maps`) and walks every JMESPath, Starlark, and CEL expression in the
+//! function, reporting property accesses the schema does not define and
+//! subscripts applied to values of the wrong type. Unlike the per-input
+//! checks, no example inputs are evaluated, so errors in rarely-taken
+//! branches are found as well.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use starlark::syntax::{AstModule, Dialect};
+use starlark_syntax::syntax::ast::{
+    AssignTargetP, AstExpr, AstLiteral, AstNoPayload, AstStmt, ClauseP,
+    ExprP, ForClauseP, ParameterP, StmtP,
+};
+use starlark_syntax::lexer::TokenInt;
+use starlark_syntax::syntax::module::AstModuleFields;
+
+use crate::functions::expression::cel;
+use crate::functions::expression::{Expression, InputMaps, InputSchema};
+use crate::functions::{Aggregation, RemoteFunction, TaskExpression};
+
+/// Statically checks every expression in a function against the shape
+/// implied by its `input_schema`.
+///
+/// Checks `input_maps`, `output_length`, `input_split`, `aggregation`, and
+/// each task's `skip`, `input`, `messages`, `tools`, `responses`, and
+/// `output` expressions. Expressions that fail to parse are ignored here; they are
+/// reported by the per-input compilation checks.
+///
+/// All issues are reported at once, one per line.
+pub fn check_expression_types(function: &RemoteFunction) -> Result<(), String> {
+    let input = Shape::from_schema(function.input_schema());
+    let mut checker = Checker::default();
+
+    // input_maps: each expression produces one sub-array, whose elements
+    // become `map` for tasks referencing it
+    let mut map_shapes = Vec::new();
+    if let Some(input_maps) = function.input_maps() {
+        let expressions = match input_maps {
+            InputMaps::One(expression) => std::slice::from_ref(expression),
+            InputMaps::Many(expressions) => expressions.as_slice(),
+        };
+        for (i, expression) in expressions.iter().enumerate() {
+            let scope = Scope::new(&input, None, None);
+            let shape = checker.check(
+                &format!("input_maps[{}]", i),
+                expression,
+                &scope,
+            );
+            map_shapes.push(match shape {
+                Shape::Array(items) => *items,
+                _ => Shape::Unknown,
+            });
+        }
+    }
+
+    // function-level vector fields
+    if let Some(output_length) = function.output_length() {
+        let value = serde_json::to_value(output_length).unwrap_or_default();
+        let scope = Scope::new(&input, None, None);
+        checker.walk("output_length", &value, &scope);
+    }
+    if let Some(input_split) = function.input_split() {
+        let value = serde_json::to_value(input_split).unwrap_or_default();
+        let scope = Scope::new(&input, None, None);
+        checker.walk("input_split", &value, &scope);
+    }
+
+    // receives: `input`, `tasks`, `weights`
+    if let Some(Aggregation::Expression(expression)) = function.aggregation() {
+        let scope = Scope::new(&input, None, None);
+        checker.check("aggregation", expression, &scope);
+    }
+
+    for (i, task) in function.tasks().iter().enumerate() {
+        let map = task
+            .input_map()
+            .map(|index| map_shapes.get(index as usize).cloned())
+            .map(|shape| shape.unwrap_or(Shape::Unknown));
+        let output = task_output_shape(task);
+        let serde_json::Value::Object(fields) =
+            serde_json::to_value(task).unwrap_or_default()
+        else {
+            continue;
+        };
+        for (key, value) in &fields {
+            let location = format!("tasks[{}].{}", i, key);
+            match key.as_str() {
+                // receives: `input`, `tasks`
+                "skip" => {
+                    let scope = Scope::new(&input, None, None);
+                    checker.walk(&location, value, &scope);
+                }
+                // receives: `input`, `output`
+                "output" => {
+                    let scope = Scope::new(&input, None, Some(&output));
+                    checker.walk(&location, value, &scope);
+                }
+                // receives: `input`, `map` (if mapped), `tasks`
+                "input" | "messages" | "tools" | "responses" => {
+                    let scope = Scope::new(&input, map.as_ref(), None);
+                    checker.walk(&location, value, &scope);
+                }
+                // placeholder fields are evaluated against the child input
+                _ => {}
+            }
+        }
+    }
+
+    if checker.issues.is_empty() {
+        Ok(())
+    } else {
+        Err(checker.issues.join("\n"))
+    }
+}
+
+/// The shape of the raw task output received by a task's `output` expression.
+fn task_output_shape(task: &TaskExpression) -> Shape {
+    let number = || Shape::Number;
+    let vector = || Shape::Array(Box::new(Shape::Number));
+    let one = match task {
+        TaskExpression::VectorCompletion(_) => Shape::Object(IndexMap::from([
+            ("votes".to_string(), Shape::Array(Box::new(Shape::Unknown))),
+            ("scores".to_string(), vector()),
+            ("weights".to_string(), vector()),
+        ])),
+        TaskExpression::ScalarCompletion(_) => Shape::Object(IndexMap::from([
+            ("votes".to_string(), Shape::Array(Box::new(Shape::Unknown))),
+            ("scores".to_string(), vector()),
+            ("weights".to_string(), vector()),
+            (
+                "rating".to_string(),
+                Shape::Object(IndexMap::from([
+                    ("mean".to_string(), number()),
+                    ("std_dev".to_string(), number()),
+                ])),
+            ),
+        ])),
+        TaskExpression::ScalarFunction(_)
+        | TaskExpression::PlaceholderScalarFunction(_) => number(),
+        TaskExpression::VectorFunction(_)
+        | TaskExpression::PlaceholderVectorFunction(_) => vector(),
+    };
+    match task.input_map() {
+        Some(_) => Shape::Array(Box::new(one)),
+        None => one,
+    }
+}
+
+/// The statically known structure of a value.
+#[derive(Debug, Clone)]
+enum Shape {
+    /// Nothing is known about the value; every access is allowed.
+    Unknown,
+    /// An object with the given properties.
+    Object(IndexMap<String, Shape>),
+    /// An array whose items have the given shape.
+    Array(Box<Shape>),
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// Rich content (image, audio, video, file).
+    Media,
+    /// The value has one of several shapes.
+    AnyOf(Vec<Shape>),
+}
+
+impl Shape {
+    fn from_schema(schema: &InputSchema) -> Self {
+        match schema {
+            InputSchema::Object(schema) => Shape::Object(
+                schema
+                    .properties
+                    .iter()
+                    .map(|(key, schema)| {
+                        (key.clone(), Shape::from_schema(schema))
+                    })
+                    .collect(),
+            ),
+            InputSchema::Array(schema) => {
+                Shape::Array(Box::new(Shape::from_schema(&schema.items)))
+            }
+            InputSchema::String(_) => Shape::String,
+            InputSchema::Integer(_) => Shape::Integer,
+            InputSchema::Number(_) => Shape::Number,
+            InputSchema::Boolean(_) => Shape::Boolean,
+            InputSchema::Image(_)
+            | InputSchema::Audio(_)
+            | InputSchema::Video(_)
+            | InputSchema::File(_) => Shape::Media,
+            InputSchema::AnyOf(schema) => Shape::AnyOf(
+                schema.any_of.iter().map(Shape::from_schema).collect(),
+            ),
+        }
+    }
+
+    /// Human-readable type name, with article, for error messages.
+    fn describe(&self) -> &'static str {
+        match self {
+            Shape::Unknown => "an unknown value",
+            Shape::Object(_) => "an object",
+            Shape::Array(_) => "an array",
+            Shape::String => "a string",
+            Shape::Integer => "an integer",
+            Shape::Number => "a number",
+            Shape::Boolean => "a boolean",
+            Shape::Media => "a media content part",
+            Shape::AnyOf(_) => "a union",
+        }
+    }
+
+    fn any_of(mut shapes: Vec<Shape>) -> Shape {
+        if shapes.len() == 1 {
+            shapes.pop().unwrap()
+        } else {
+            Shape::AnyOf(shapes)
+        }
+    }
+}
+
+/// A shape together with the access path that produced it.
+///
+/// The path is `None` when the value was not derived from a known root, in
+/// which case no issues are reported for it.
+#[derive(Debug, Clone)]
+struct Typed {
+    shape: Shape,
+    path: Option<String>,
+}
+
+impl Typed {
+    fn unknown() -> Self {
+        Typed {
+            shape: Shape::Unknown,
+            path: None,
+        }
+    }
+
+    fn root(name: &str, shape: Shape) -> Self {
+        Typed {
+            shape,
+            path: Some(name.to_string()),
+        }
+    }
+
+    fn child(&self, shape: Shape, segment: std::fmt::Arguments) -> Self {
+        Typed {
+            shape,
+            path: self.path.as_ref().map(|path| format!("{}{}", path, segment)),
+        }
+    }
+}
+
+/// The variables available to an expression.
+struct Scope<'a> {
+    input: &'a Shape,
+    map: Option<&'a Shape>,
+    output: Option<&'a Shape>,
+}
+
+impl<'a> Scope<'a> {
+    fn new(
+        input: &'a Shape,
+        map: Option<&'a Shape>,
+        output: Option<&'a Shape>,
+    ) -> Self {
+        Self { input, map, output }
+    }
+
+    fn get(&self, name: &str) -> Option<Typed> {
+        match name {
+            "input" => Some(Typed::root("input", self.input.clone())),
+            "map" => Some(Typed::root(
+                "map",
+                self.map.cloned().unwrap_or(Shape::Unknown),
+            )),
+            "output" => Some(Typed::root(
+                "output",
+                self.output.cloned().unwrap_or(Shape::Unknown),
+            )),
+            "tasks" => Some(Typed::root(
+                "tasks",
+                Shape::Array(Box::new(Shape::Unknown)),
+            )),
+            "weights" => Some(Typed::root(
+                "weights",
+                Shape::Array(Box::new(Shape::Number)),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    issues: Vec<String>,
+}
+
+impl Checker {
+    /// Finds every expression nested in a serialized field and checks it.
+    fn walk(&mut self, location: &str, value: &serde_json::Value, scope: &Scope) {
+        if let Ok(expression) =
+            serde_json::from_value::<Expression>(value.clone())
+        {
+            self.check(location, &expression, scope);
+            return;
+        }
+        match value {
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.walk(&format!("{}[{}]", location, i), item, scope);
+                }
+            }
+            serde_json::Value::Object(fields) => {
+                for (key, item) in fields {
+                    self.walk(&format!("{}.{}", location, key), item, scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks a single expression and returns the shape of its result.
+    fn check(
+        &mut self,
+        location: &str,
+        expression: &Expression,
+        scope: &Scope,
+    ) -> Shape {
+        match expression {
+            Expression::JMESPath(code) => {
+                let Ok(ast) = jmespath::parse(code) else {
+                    return Shape::Unknown;
+                };
+                let mut root = IndexMap::new();
+                for name in ["input", "output", "map", "tasks", "weights"] {
+                    root.insert(name.to_string(), Shape::Unknown);
+                }
+                let context = Typed {
+                    shape: Shape::Object(root),
+                    path: Some(String::new()),
+                };
+                let mut jmespath = JmespathChecker {
+                    checker: self,
+                    location,
+                    scope,
+                };
+                jmespath.infer(&ast, &context).shape
+            }
+            Expression::Starlark(code) => {
+                let Ok(module) = AstModule::parse(
+                    "expression",
+                    code.clone(),
+                    &Dialect::Extended,
+                ) else {
+                    return Shape::Unknown;
+                };
+                let mut starlark = StarlarkChecker {
+                    checker: self,
+                    location,
+                    locals: vec![HashMap::new()],
+                    scope,
+                };
+                starlark.stmt(module.statement()).shape
+            }
+            Expression::Cel(code) => {
+                let Ok(expr) = cel::parse(code) else {
+                    return Shape::Unknown;
+                };
+                let mut cel = CelChecker {
+                    checker: self,
+                    location,
+                    locals: Vec::new(),
+                    scope,
+                };
+                cel.infer(&expr).shape
+            }
+        }
+    }
+
+    /// Accesses a named property, as `input.name` or `input['name']`.
+    fn property(&mut self, location: &str, typed: &Typed, name: &str) -> Typed {
+        let Some(path) = typed.path.as_deref() else {
+            return Typed::unknown();
+        };
+        let segment = if path.is_empty() {
+            name.to_string()
+        } else {
+            format!(".{}", name)
+        };
+        let candidates = match &typed.shape {
+            Shape::AnyOf(shapes) => shapes.iter().collect(),
+            shape => vec![shape],
+        };
+        let mut found = Vec::new();
+        let mut known = Vec::new();
+        for candidate in candidates {
+            match candidate {
+                Shape::Unknown | Shape::Media => {
+                    return typed.child(Shape::Unknown, format_args!("{}", segment));
+                }
+                Shape::Object(properties) => match properties.get(name) {
+                    Some(shape) => found.push(shape.clone()),
+                    None => known.extend(properties.keys().cloned()),
+                },
+                _ => {}
+            }
+        }
+        if !found.is_empty() {
+            return typed.child(Shape::any_of(found), format_args!("{}", segment));
+        }
+        let display = if path.is_empty() { "expression root" } else { path };
+        if matches!(typed.shape, Shape::Object(_) | Shape::AnyOf(_))
+            && (!known.is_empty() || matches!(typed.shape, Shape::Object(_)))
+        {
+            self.issues.push(format!(
+                "ET01: {}: `{}` has no property '{}' (known properties: {})",
+                location,
+                display,
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                },
+            ));
+        } else {
+            self.issues.push(format!(
+                "ET02: {}: `{}` is {}, cannot access property '{}'",
+                location,
+                display,
+                typed.shape.describe(),
+                name,
+            ));
+        }
+        Typed::unknown()
+    }
+
+    /// Accesses an element by position, as `input[0]`.
+    ///
+    /// Strings can be indexed in Starlark but not in JMESPath.
+    fn index(
+        &mut self,
+        location: &str,
+        typed: &Typed,
+        index: i64,
+        strings: bool,
+    ) -> Typed {
+        if typed.path.is_none() {
+            return Typed::unknown();
+        }
+        match self.items(typed) {
+            Some(items) => typed.child(items, format_args!("[{}]", index)),
+            None if strings && matches!(typed.shape, Shape::String) => {
+                typed.child(Shape::String, format_args!("[{}]", index))
+            }
+            None => {
+                self.issues.push(format!(
+                    "ET03: {}: `{}` is {}, cannot index element [{}]",
+                    location,
+                    typed.path.as_deref().unwrap_or_default(),
+                    typed.shape.describe(),
+                    index,
+                ));
+                Typed::unknown()
+            }
+        }
+    }
+
+    /// Iterates over a value, as in a projection or a `for` clause.
+    ///
+    /// Strings can be iterated in neither JMESPath nor Starlark. Objects can
+    /// be iterated by key in Starlark and CEL but not in JMESPath.
+    fn iterate(&mut self, location: &str, typed: &Typed, keys: bool) -> Typed {
+        if typed.path.is_none() {
+            return Typed::unknown();
+        }
+        match self.items(typed) {
+            Some(items) => typed.child(items, format_args!("[]")),
+            None if keys && self.is_object(typed) => Typed {
+                shape: Shape::String,
+                path: None,
+            },
+            None => {
+                self.issues.push(format!(
+                    "ET04: {}: `{}` is {}, cannot iterate over it",
+                    location,
+                    typed.path.as_deref().unwrap_or_default(),
+                    typed.shape.describe(),
+                ));
+                Typed::unknown()
+            }
+        }
+    }
+
+    /// Returns whether the value may be an object.
+    fn is_object(&self, typed: &Typed) -> bool {
+        match &typed.shape {
+            Shape::AnyOf(shapes) => {
+                shapes.iter().any(|shape| matches!(shape, Shape::Object(_)))
+            }
+            shape => matches!(shape, Shape::Object(_)),
+        }
+    }
+
+    /// Returns the item shape if the value may be an array.
+    fn items(&self, typed: &Typed) -> Option<Shape> {
+        let candidates = match &typed.shape {
+            Shape::AnyOf(shapes) => shapes.iter().collect(),
+            shape => vec![shape],
+        };
+        let mut found = Vec::new();
+        for candidate in candidates {
+            match candidate {
+                Shape::Unknown | Shape::Media => return Some(Shape::Unknown),
+                Shape::Array(items) => found.push((**items).clone()),
+                _ => {}
+            }
+        }
+        if found.is_empty() {
+            None
+        } else {
+            Some(Shape::any_of(found))
+        }
+    }
+}
+
+/// Infers shapes through a JMESPath AST.
+struct JmespathChecker<'c, 'l, 's> {
+    checker: &'c mut Checker,
+    location: &'l str,
+    scope: &'s Scope<'s>,
+}
+
+impl JmespathChecker<'_, '_, '_> {
+    fn infer(&mut self, ast: &jmespath::ast::Ast, current: &Typed) -> Typed {
+        use jmespath::ast::Ast;
+        match ast {
+            Ast::Identity { .. } => current.clone(),
+            Ast::Field { name, .. } => {
+                // the expression root holds the scope variables
+                if current.path.as_deref() == Some("")
+                    && let Some(variable) = self.scope.get(name)
+                {
+                    return variable;
+                }
+                self.checker.property(self.location, current, name)
+            }
+            Ast::Index { idx, .. } => {
+                self.checker
+                    .index(self.location, current, *idx as i64, false)
+            }
+            Ast::Subexpr { lhs, rhs, .. } => {
+                let lhs = self.infer(lhs, current);
+                self.infer(rhs, &lhs)
+            }
+            Ast::Projection { lhs, rhs, .. } => {
+                let lhs = self.infer(lhs, current);
+                let item = self.checker.iterate(self.location, &lhs, false);
+                let result = self.infer(rhs, &item);
+                Typed {
+                    shape: Shape::Array(Box::new(result.shape)),
+                    path: result.path,
+                }
+            }
+            Ast::Flatten { node, .. } => {
+                let node = self.infer(node, current);
+                match self.checker.items(&node) {
+                    Some(Shape::Array(items)) => {
+                        node.child(Shape::Array(items), format_args!(""))
+                    }
+                    Some(items) => {
+                        node.child(Shape::Array(Box::new(items)), format_args!(""))
+                    }
+                    None => {
+                        self.checker.iterate(self.location, &node, false);
+                        Typed::unknown()
+                    }
+                }
+            }
+            Ast::Slice { .. } => {
+                if current.path.is_some()
+                    && self.checker.items(current).is_none()
+                {
+                    self.checker.iterate(self.location, current, false);
+                    return Typed::unknown();
+                }
+                current.clone()
+            }
+            Ast::ObjectValues { node, .. } => {
+                self.infer(node, current);
+                Typed::unknown()
+            }
+            Ast::Condition {
+                predicate, then, ..
+            } => {
+                self.infer(predicate, current);
+                self.infer(then, current)
+            }
+            Ast::Comparison { lhs, rhs, .. } => {
+                self.infer(lhs, current);
+                self.infer(rhs, current);
+                Typed::unknown()
+            }
+            Ast::And { lhs, rhs, .. } | Ast::Or { lhs, rhs, .. } => {
+                self.infer(lhs, current);
+                self.infer(rhs, current);
+                Typed::unknown()
+            }
+            Ast::Not { node, .. } => {
+                self.infer(node, current);
+                Typed::unknown()
+            }
+            Ast::MultiList { elements, .. } => {
+                for element in elements {
+                    self.infer(element, current);
+                }
+                Typed::unknown()
+            }
+            Ast::MultiHash { elements, .. } => {
+                for element in elements {
+                    self.infer(&element.value, current);
+                }
+                Typed::unknown()
+            }
+            Ast::Function { args, .. } => {
+                // expression references are applied to the items of the
+                // first array argument (map, sort_by, max_by, zip_map, ...)
+                let mut items = None;
+                for arg in args {
+                    if !matches!(arg, Ast::Expref { .. }) {
+                        let arg = self.infer(arg, current);
+                        if items.is_none() {
+                            items = self
+                                .checker
+                                .items(&arg)
+                                .map(|shape| arg.child(shape, format_args!("[]")));
+                        }
+                    }
+                }
+                for arg in args {
+                    if let Ast::Expref { ast, .. } = arg {
+                        let item = items.clone().unwrap_or_else(Typed::unknown);
+                        self.infer(ast, &item);
+                    }
+                }
+                Typed::unknown()
+            }
+            Ast::Expref { .. } | Ast::Literal { .. } => Typed::unknown(),
+        }
+    }
+}
+
+/// Infers shapes through a Starlark AST.
+struct StarlarkChecker<'c, 'l, 's> {
+    checker: &'c mut Checker,
+    location: &'l str,
+    /// Local variable scopes, innermost last.
+    locals: Vec<HashMap<String, Typed>>,
+    scope: &'s Scope<'s>,
+}
+
+impl StarlarkChecker<'_, '_, '_> {
+    /// Checks a statement, returning the shape of its last expression.
+    fn stmt(&mut self, stmt: &AstStmt) -> Typed {
+        match &stmt.node {
+            StmtP::Statements(stmts) => {
+                let mut last = Typed::unknown();
+                for stmt in stmts {
+                    last = self.stmt(stmt);
+                }
+                last
+            }
+            StmtP::Expression(expr) => self.expr(expr),
+            StmtP::Assign(assign) => {
+                let rhs = self.expr(&assign.rhs);
+                self.bind(&assign.lhs.node, rhs);
+                Typed::unknown()
+            }
+            StmtP::For(for_) => {
+                let over = self.expr(&for_.over);
+                let item = self.checker.iterate(self.location, &over, true);
+                self.bind(&for_.var.node, item);
+                self.stmt(&for_.body);
+                Typed::unknown()
+            }
+            StmtP::Def(def) => {
+                let mut locals = HashMap::new();
+                for param in &def.params {
+                    if let ParameterP::Normal(ident, _, default) = &param.node {
+                        if let Some(default) = default {
+                            self.expr(default);
+                        }
+                        locals.insert(ident.node.ident.clone(), Typed::unknown());
+                    }
+                }
+                self.locals.push(locals);
+                self.stmt(&def.body);
+                self.locals.pop();
+                Typed::unknown()
+            }
+            _ => {
+                stmt.visit_expr(|expr| {
+                    self.expr(expr);
+                });
+                stmt.visit_stmt(|stmt| {
+                    self.stmt(stmt);
+                });
+                Typed::unknown()
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &AstExpr) -> Typed {
+        match &expr.node {
+            ExprP::Identifier(ident) => {
+                let name = ident.node.ident.as_str();
+                for locals in self.locals.iter().rev() {
+                    if let Some(typed) = locals.get(name) {
+                        return typed.clone();
+                    }
+                }
+                self.scope.get(name).unwrap_or_else(Typed::unknown)
+            }
+            ExprP::Index(pair) => {
+                let (base, key) = &**pair;
+                let base = self.expr(base);
+                self.expr(key);
+                if let ExprP::Literal(AstLiteral::String(name)) = &key.node {
+                    self.checker.property(self.location, &base, &name.node)
+                } else if let Some(index) = int_literal(key) {
+                    self.checker.index(self.location, &base, index, true)
+                } else {
+                    Typed::unknown()
+                }
+            }
+            ExprP::Call(function, args) => {
+                let receiver = match &function.node {
+                    ExprP::Dot(receiver, attribute)
+                        if attribute.node == "get" =>
+                    {
+                        Some(self.expr(receiver))
+                    }
+                    _ => {
+                        self.expr(function);
+                        None
+                    }
+                };
+                for arg in &args.args {
+                    self.expr(arg.expr());
+                }
+                // `x.get('key')` on a known object
+                if let Some(receiver) = receiver
+                    && matches!(receiver.shape, Shape::Object(_))
+                    && let Some(arg) = args.args.first()
+                    && let ExprP::Literal(AstLiteral::String(name)) =
+                        &arg.expr().node
+                {
+                    return self.checker.property(
+                        self.location,
+                        &receiver,
+                        &name.node,
+                    );
+                }
+                Typed::unknown()
+            }
+            ExprP::ListComprehension(element, first, clauses) => {
+                self.comprehension(first, clauses, |this| {
+                    this.expr(element);
+                });
+                Typed::unknown()
+            }
+            ExprP::DictComprehension(pair, first, clauses) => {
+                self.comprehension(first, clauses, |this| {
+                    this.expr(&pair.0);
+                    this.expr(&pair.1);
+                });
+                Typed::unknown()
+            }
+            ExprP::Lambda(lambda) => {
+                let mut locals = HashMap::new();
+                for param in &lambda.params {
+                    if let ParameterP::Normal(ident, _, _) = &param.node {
+                        locals.insert(ident.node.ident.clone(), Typed::unknown());
+                    }
+                }
+                self.locals.push(locals);
+                self.expr(&lambda.body);
+                self.locals.pop();
+                Typed::unknown()
+            }
+            ExprP::If(triple) => {
+                let (condition, then, otherwise) = &**triple;
+                self.expr(condition);
+                self.expr(then);
+                self.expr(otherwise);
+                Typed::unknown()
+            }
+            _ => {
+                expr.visit_expr(|expr| {
+                    self.expr(expr);
+                });
+                Typed::unknown()
+            }
+        }
+    }
+
+    /// Checks the clauses of a comprehension in order, binding loop variables
+    /// in a new local scope, then runs `body` within that scope.
+    fn comprehension(
+        &mut self,
+        first: &ForClauseP<AstNoPayload>,
+        clauses: &[ClauseP<AstNoPayload>],
+        body: impl FnOnce(&mut Self),
+    ) {
+        self.locals.push(HashMap::new());
+        self.for_clause(first);
+        for clause in clauses {
+            match clause {
+                ClauseP::For(clause) => self.for_clause(clause),
+                ClauseP::If(condition) => {
+                    self.expr(condition);
+                }
+            }
+        }
+        body(self);
+        self.locals.pop();
+    }
+
+    fn for_clause(&mut self, clause: &ForClauseP<AstNoPayload>) {
+        let over = self.expr(&clause.over);
+        let item = self.checker.iterate(self.location, &over, true);
+        self.bind(&clause.var.node, item);
+    }
+
+    /// Binds an assignment target in the innermost scope. Only plain
+    /// identifiers keep their shape; destructured targets become unknown.
+    fn bind(&mut self, target: &AssignTargetP<AstNoPayload>, typed: Typed) {
+        match target {
+            AssignTargetP::Identifier(ident) => {
+                let locals = self.locals.last_mut().unwrap();
+                locals.insert(ident.node.ident.clone(), typed);
+            }
+            AssignTargetP::Tuple(targets) => {
+                for target in targets {
+                    self.bind(&target.node, Typed::unknown());
+                }
+            }
+            AssignTargetP::Index(_) | AssignTargetP::Dot(..) => {}
+        }
+    }
+}
+
+/// Infers shapes through a CEL AST.
+struct CelChecker<'c, 'l, 's> {
+    checker: &'c mut Checker,
+    location: &'l str,
+    /// Macro variables, innermost last.
+    locals: Vec<(String, Typed)>,
+    scope: &'s Scope<'s>,
+}
+
+impl CelChecker<'_, '_, '_> {
+    fn infer(&mut self, expr: &::cel::IdedExpr) -> Typed {
+        use ::cel::common::ast::{EntryExpr, Expr, LiteralValue};
+        match &expr.expr {
+            Expr::Ident(name) => self.variable(name),
+            Expr::Select(select) => {
+                let operand = self.infer(&select.operand);
+                if select.test {
+                    return Typed::unknown();
+                }
+                self.checker.property(self.location, &operand, &select.field)
+            }
+            // `has()` tests for presence, so a missing property is expected
+            Expr::Call(call) if call.func_name == "has" && call.target.is_none() => {
+                for arg in &call.args {
+                    match &arg.expr {
+                        Expr::Select(select) => self.infer(&select.operand),
+                        _ => self.infer(arg),
+                    };
+                }
+                Typed::unknown()
+            }
+            Expr::Call(call) if call.func_name == "_[_]" && call.args.len() == 2 => {
+                let operand = self.infer(&call.args[0]);
+                self.infer(&call.args[1]);
+                match &call.args[1].expr {
+                    Expr::Literal(LiteralValue::String(name)) => {
+                        self.checker.property(self.location, &operand, name.inner())
+                    }
+                    Expr::Literal(LiteralValue::Int(index)) => {
+                        self.checker.index(self.location, &operand, *index.inner(), false)
+                    }
+                    _ => Typed::unknown(),
+                }
+            }
+            Expr::Call(call) => {
+                if let Some(target) = &call.target
+                    && let Some(typed) = self.comprehension(&call.func_name, target, &call.args)
+                {
+                    return typed;
+                }
+                // `math.greatest(...)` and friends are namespaced functions
+                let namespace = matches!(
+                    call.target.as_deref().map(|target| &target.expr),
+                    Some(Expr::Ident(name))
+                        if name == "math" && self.local(name).is_none()
+                );
+                if let Some(target) = &call.target
+                    && !namespace
+                {
+                    self.infer(target);
+                }
+                for arg in &call.args {
+                    self.infer(arg);
+                }
+                Typed::unknown()
+            }
+            Expr::List(list) => {
+                for element in &list.elements {
+                    self.infer(element);
+                }
+                Typed::unknown()
+            }
+            Expr::Map(map) => {
+                for entry in &map.entries {
+                    if let EntryExpr::MapEntry(entry) = &entry.expr {
+                        self.infer(&entry.key);
+                        self.infer(&entry.value);
+                    }
+                }
+                Typed::unknown()
+            }
+            Expr::Comprehension(_)
+            | Expr::Literal(_)
+            | Expr::Struct(_)
+            | Expr::Unspecified => Typed::unknown(),
+        }
+    }
+
+    /// Infers a macro iterating over `range`, binding each element (or map
+    /// key) to its first argument. Returns `None` if the call isn't one.
+    fn comprehension(
+        &mut self,
+        function: &str,
+        range: &::cel::IdedExpr,
+        args: &[::cel::IdedExpr],
+    ) -> Option<Typed> {
+        use ::cel::common::ast::Expr;
+        let arity = match function {
+            "map" => 2..=3,
+            "all" | "exists" | "exists_one" | "filter" => 2..=2,
+            _ => return None,
+        };
+        let (Expr::Ident(var), true) = (&args.first()?.expr, arity.contains(&args.len())) else {
+            return None;
+        };
+        let range = self.infer(range);
+        // maps are iterated by key
+        let item = self.checker.iterate(self.location, &range, true);
+        self.locals.push((var.clone(), item));
+        let mut result = Typed::unknown();
+        for arg in &args[1..] {
+            result = self.infer(arg);
+        }
+        self.locals.pop();
+        Some(match function {
+            "filter" => range,
+            "map" => Typed {
+                shape: Shape::Array(Box::new(result.shape)),
+                path: result.path,
+            },
+            _ => Typed::unknown(),
+        })
+    }
+
+    fn local(&self, name: &str) -> Option<&Typed> {
+        self.locals
+            .iter()
+            .rev()
+            .find(|(local, _)| local == name)
+            .map(|(_, typed)| typed)
+    }
+
+    fn variable(&self, name: &str) -> Typed {
+        match self.local(name) {
+            Some(typed) => typed.clone(),
+            None => self.scope.get(name).unwrap_or_else(Typed::unknown),
+        }
+    }
+}
+
+/// Reads an integer literal, including a negated one such as `-1`.
+fn int_literal(expr: &AstExpr) -> Option<i64> {
+    match &expr.node {
+        ExprP::Literal(AstLiteral::Int(int)) => match &int.node {
+            TokenInt::I32(int) => Some(*int as i64),
+            TokenInt::BigInt(_) => None,
+        },
+        ExprP::Minus(inner) => int_literal(inner).map(|int| -int),
+        _ => None,
+    }
+}
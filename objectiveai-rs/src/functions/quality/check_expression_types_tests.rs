@@ -0,0 +1,579 @@
+//! Tests for check_expression_types.
+
+#![cfg(test)]
+
+use crate::functions::expression::{
+    AnyOfInputSchema, ArrayInputSchema, Expression, InputMaps, InputSchema,
+    IntegerInputSchema, ObjectInputSchema, StringInputSchema, WithExpression,
+};
+use crate::functions::quality::check_expression_types;
+use crate::functions::{
+    RemoteFunction, TaskExpression, VectorCompletionTaskExpression,
+};
+use crate::util::index_map;
+
+// ── wrappers ─────────────────────────────────────────────────────────
+
+fn test(f: &RemoteFunction) {
+    check_expression_types(f).unwrap();
+}
+
+fn test_err(f: &RemoteFunction, expected: &str) {
+    let err = check_expression_types(f).unwrap_err();
+    assert!(err.contains(expected), "expected '{expected}' in error, got: {err}");
+}
+
+// ── helpers ──────────────────────────────────────────────────────────
+
+fn string() -> InputSchema {
+    InputSchema::String(StringInputSchema {
+        description: None,
+        r#enum: None,
+    })
+}
+
+fn integer() -> InputSchema {
+    InputSchema::Integer(IntegerInputSchema {
+        description: None,
+        minimum: None,
+        maximum: None,
+    })
+}
+
+fn array(items: InputSchema) -> InputSchema {
+    InputSchema::Array(ArrayInputSchema {
+        description: None,
+        min_items: Some(2),
+        max_items: Some(4),
+        items: Box::new(items),
+    })
+}
+
+/// `{ title: string, count: integer, items: [{ name: string }] }`
+fn schema() -> InputSchema {
+    InputSchema::Object(ObjectInputSchema {
+        description: None,
+        properties: index_map! {
+            "title" => string(),
+            "count" => integer(),
+            "items" => array(InputSchema::Object(ObjectInputSchema {
+                description: None,
+                properties: index_map! { "name" => string() },
+                required: Some(vec!["name".to_string()]),
+            })),
+        },
+        required: Some(vec![
+            "title".to_string(),
+            "count".to_string(),
+            "items".to_string(),
+        ]),
+    })
+}
+
+fn vc_task(
+    messages: Expression,
+    responses: Expression,
+    output: Expression,
+) -> VectorCompletionTaskExpression {
+    VectorCompletionTaskExpression {
+        depends_on: None,
+        skip: None,
+        map: None,
+        messages: WithExpression::Expression(messages),
+        tools: None,
+        responses: WithExpression::Expression(responses),
+        output,
+    }
+}
+
+fn function(
+    input_schema: InputSchema,
+    input_maps: Option<InputMaps>,
+    tasks: Vec<VectorCompletionTaskExpression>,
+) -> RemoteFunction {
+    RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema,
+        definitions: None,
+        input_maps,
+        tasks: tasks.into_iter().map(TaskExpression::VectorCompletion).collect(),
+        aggregation: None,
+    }
+}
+
+fn starlark(code: &str) -> Expression {
+    Expression::Starlark(code.to_string())
+}
+
+fn cel(code: &str) -> Expression {
+    Expression::Cel(code.to_string())
+}
+
+fn jmespath(code: &str) -> Expression {
+    Expression::JMESPath(code.to_string())
+}
+
+fn messages() -> Expression {
+    starlark(
+        "[{'role': 'user', 'content': [{'type': 'text', 'text': input['title']}]}]",
+    )
+}
+
+fn responses() -> Expression {
+    starlark(
+        "[[{'type': 'text', 'text': item['name']}] for item in input['items']]",
+    )
+}
+
+fn output() -> Expression {
+    starlark("output['scores'][0]")
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[test]
+fn valid_starlark() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(messages(), responses(), output())],
+    ));
+}
+
+#[test]
+fn valid_jmespath() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(
+            jmespath(
+                "[{role: 'user', content: [{type: 'text', text: input.title}]}]",
+            ),
+            jmespath("input.items[*].[{type: 'text', text: name}]"),
+            jmespath("output.scores[0]"),
+        )],
+    ));
+}
+
+#[test]
+fn starlark_unknown_property() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input['titel']}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET01: tasks[0].messages: `input` has no property 'titel'",
+    );
+}
+
+#[test]
+fn starlark_unknown_property_of_comprehension_variable() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                starlark(
+                    "[[{'type': 'text', 'text': item['nme']}] for item in input['items']]",
+                ),
+                output(),
+            )],
+        ),
+        "`input.items[]` has no property 'nme'",
+    );
+}
+
+#[test]
+fn starlark_unknown_property_of_assigned_variable() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                starlark(
+                    "items = input['items']\n[[{'type': 'text', 'text': items[0]['label']}]]",
+                ),
+                output(),
+            )],
+        ),
+        "`input.items[0]` has no property 'label'",
+    );
+}
+
+#[test]
+fn starlark_get_unknown_property() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input.get('subtitle', '')}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET01",
+    );
+}
+
+#[test]
+fn starlark_property_of_string() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input['title']['text']}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET02: tasks[0].messages: `input.title` is a string, cannot access property 'text'",
+    );
+}
+
+#[test]
+fn starlark_index_of_integer() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': str(input['count'][0])}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET03: tasks[0].messages: `input.count` is an integer, cannot index element [0]",
+    );
+}
+
+#[test]
+fn starlark_string_index_allowed() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(
+            starlark(
+                "[{'role': 'user', 'content': [{'type': 'text', 'text': input['title'][-1]}]}]",
+            ),
+            responses(),
+            output(),
+        )],
+    ));
+}
+
+#[test]
+fn starlark_iterate_integer() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                starlark("[[{'type': 'text', 'text': x}] for x in input['count']]"),
+                output(),
+            )],
+        ),
+        "ET04: tasks[0].responses: `input.count` is an integer, cannot iterate over it",
+    );
+}
+
+#[test]
+fn starlark_iterate_object_keys() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(
+            messages(),
+            starlark(
+                "[[{'type': 'text', 'text': k + ': ' + input['items'][0][k]}] for k in input['items'][0]]",
+            ),
+            output(),
+        )],
+    ));
+}
+
+#[test]
+fn starlark_shadowed_input_is_unknown() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(
+            messages(),
+            starlark(
+                "def f(input):\n    return input['anything']\n[[{'type': 'text', 'text': f({'anything': 'a'})}], [{'type': 'text', 'text': 'b'}]]",
+            ),
+            output(),
+        )],
+    ));
+}
+
+#[test]
+fn jmespath_unknown_property() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                jmespath("input.items[*].[{type: 'text', text: title}]"),
+                output(),
+            )],
+        ),
+        "ET01: tasks[0].responses: `input.items[]` has no property 'title'",
+    );
+}
+
+#[test]
+fn jmespath_field_of_array() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                jmespath("input.items.name"),
+                output(),
+            )],
+        ),
+        "ET02: tasks[0].responses: `input.items` is an array, cannot access property 'name'",
+    );
+}
+
+#[test]
+fn jmespath_unknown_root() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(messages(), jmespath("inputs.items"), output())],
+        ),
+        "`expression root` has no property 'inputs'",
+    );
+}
+
+#[test]
+fn jmespath_expref_item() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                jmespath("map(&[{type: 'text', text: nam}], input.items)"),
+                output(),
+            )],
+        ),
+        "`input.items[]` has no property 'nam'",
+    );
+}
+
+#[test]
+fn valid_cel() {
+    test(&function(
+        schema(),
+        None,
+        vec![vc_task(
+            cel(
+                "[{'role': 'user', 'content': [{'type': 'text', 'text': has(input.title) ? input.title.lowerAscii() : ''}]}]",
+            ),
+            cel("input.items.map(item, [{'type': 'text', 'text': item.name}])"),
+            cel("output.scores[0]"),
+        )],
+    ));
+}
+
+#[test]
+fn cel_unknown_property() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                cel("input.items.map(item, [{'type': 'text', 'text': item.nme}])"),
+                output(),
+            )],
+        ),
+        "ET01: tasks[0].responses: `input.items[]` has no property 'nme'",
+    );
+}
+
+#[test]
+fn cel_index_and_iterate() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                cel(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input.title[0]}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET03: tasks[0].messages: `input.title` is a string, cannot index element [0]",
+    );
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                messages(),
+                cel("input.count.map(x, [{'type': 'text', 'text': 'a'}])"),
+                output(),
+            )],
+        ),
+        "ET04: tasks[0].responses: `input.count` is an integer, cannot iterate over it",
+    );
+}
+
+#[test]
+fn cel_filter_keeps_item_shape() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(
+                cel(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input.items.filter(i, i.name != '')[0].title}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "`input.items[0]` has no property 'title'",
+    );
+}
+
+#[test]
+fn output_unknown_property() {
+    test_err(
+        &function(
+            schema(),
+            None,
+            vec![vc_task(messages(), responses(), starlark("output['score']"))],
+        ),
+        "ET01: tasks[0].output: `output` has no property 'score' \
+         (known properties: votes, scores, weights)",
+    );
+}
+
+#[test]
+fn skip_does_not_receive_map() {
+    let mut task = vc_task(messages(), responses(), output());
+    task.skip = Some(jmespath("input.title.length"));
+    test_err(&function(schema(), None, vec![task]), "ET02: tasks[0].skip");
+}
+
+#[test]
+fn mapped_task_map_shape() {
+    let mut task = vc_task(
+        starlark(
+            "[{'role': 'user', 'content': [{'type': 'text', 'text': map['name']}]}]",
+        ),
+        responses(),
+        starlark("output[0]['scores'][0]"),
+    );
+    task.map = Some(0);
+    test(&function(
+        schema(),
+        Some(InputMaps::One(starlark("input['items']"))),
+        vec![task.clone()],
+    ));
+
+    task.messages = WithExpression::Expression(starlark(
+        "[{'role': 'user', 'content': [{'type': 'text', 'text': map['title']}]}]",
+    ));
+    test_err(
+        &function(
+            schema(),
+            Some(InputMaps::One(starlark("input['items']"))),
+            vec![task],
+        ),
+        "ET01: tasks[0].messages: `map` has no property 'title'",
+    );
+}
+
+#[test]
+fn input_maps_checked() {
+    test_err(
+        &function(
+            schema(),
+            Some(InputMaps::Many(vec![jmespath("input.entries")])),
+            vec![vc_task(messages(), responses(), output())],
+        ),
+        "ET01: input_maps[0]: `input` has no property 'entries'",
+    );
+}
+
+#[test]
+fn any_of_property_in_one_variant() {
+    let schema = InputSchema::AnyOf(AnyOfInputSchema {
+        any_of: vec![
+            schema(),
+            InputSchema::Object(ObjectInputSchema {
+                description: None,
+                properties: index_map! { "subtitle" => string() },
+                required: None,
+            }),
+        ],
+    });
+    test(&function(
+        schema.clone(),
+        None,
+        vec![vc_task(
+            starlark(
+                "[{'role': 'user', 'content': [{'type': 'text', 'text': input['subtitle']}]}]",
+            ),
+            responses(),
+            output(),
+        )],
+    ));
+    test_err(
+        &function(
+            schema,
+            None,
+            vec![vc_task(
+                starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input['heading']}]}]",
+                ),
+                responses(),
+                output(),
+            )],
+        ),
+        "ET01",
+    );
+}
+
+#[test]
+fn reports_all_issues() {
+    let err = check_expression_types(&function(
+        schema(),
+        None,
+        vec![
+            vc_task(messages(), jmespath("input.itemz"), output()),
+            vc_task(messages(), responses(), starlark("output['scorez']")),
+        ],
+    ))
+    .unwrap_err();
+    assert!(err.contains("tasks[0].responses"), "got: {err}");
+    assert!(err.contains("tasks[1].output"), "got: {err}");
+}
@@ -1,4 +1,5 @@
-//! Quality checks for leaf scalar functions (depth 0: vector.completion tasks only).
+//! Quality checks for leaf scalar functions (depth 0: vector.completion and
+//! scalar.completion tasks only).
 
 use std::collections::HashSet;
 
@@ -7,11 +8,14 @@ use crate::chat::completions::request::{
 };
 use crate::functions::expression::WithExpression;
 use crate::functions::{
-    CompiledTask, RemoteFunction, Task, TaskExpression,
-    VectorCompletionTaskExpression,
+    CompiledTask, RemoteFunction, ScalarCompletionTaskExpression, Task,
+    TaskExpression, VectorCompletionTaskExpression,
 };
 
+use super::check_definitions::check_definitions;
+use super::check_depends_on::check_depends_on;
 use super::check_description::check_description;
+use super::check_expression_types::check_expression_types;
 use super::check_input_schema::check_input_schema;
 use super::check_output_expression::{
     ScalarOutputShape, check_scalar_distribution,
@@ -25,15 +29,17 @@ use super::example_inputs;
 
 /// Validates quality requirements for a leaf scalar function.
 ///
-/// Leaf scalar functions are at depth 0 and contain only vector.completion tasks.
+/// Leaf scalar functions are at depth 0 and contain only vector.completion and
+/// scalar.completion tasks.
 ///
 /// # Checks
 ///
 /// 1. No `input_maps` — scalar functions must not use input maps
-/// 2. All tasks must be `vector.completion`
+/// 2. All tasks must be `vector.completion` or `scalar.completion`
 /// 3. No `map` on any task — scalar leaf tasks are never mapped
 /// 4. Message content must be content parts arrays, not plain strings
 /// 5. Response content must be content parts arrays, not plain strings
+/// 6. Expressions type-check against the input schema
 pub fn check_leaf_scalar_function(
     function: &RemoteFunction,
 ) -> Result<(), String> {
@@ -67,7 +73,8 @@ pub fn check_leaf_scalar_function(
         return Err("LS03: Functions must have at least one task".to_string());
     }
 
-    // All tasks must be vector.completion, no map, content parts only
+    // All tasks must be vector.completion or scalar.completion, no map,
+    // content parts only
     for (i, task) in tasks.iter().enumerate() {
         match task {
             TaskExpression::VectorCompletion(vc) => {
@@ -82,6 +89,17 @@ pub fn check_leaf_scalar_function(
                 check_vector_completion_messages(i, vc)?;
                 check_scalar_vector_completion_responses(i, vc)?;
             }
+            TaskExpression::ScalarCompletion(sc) => {
+                // No map
+                if sc.map.is_some() {
+                    return Err(format!(
+                        "LS21: Task [{}]: scalar.completion tasks must not have map",
+                        i
+                    ));
+                }
+                // Check content parts and scale
+                check_scalar_completion(i, sc)?;
+            }
             TaskExpression::ScalarFunction(_) => {
                 return Err(format!(
                     "LS05: Task [{}]: leaf functions must only contain vector.completion tasks, \
@@ -113,6 +131,15 @@ pub fn check_leaf_scalar_function(
         }
     }
 
+    // Definitions must load before any expression can use them
+    check_definitions(function.definitions())?;
+
+    // Tasks may only depend on earlier tasks
+    check_depends_on(function.tasks())?;
+
+    // Expressions must type-check against the input schema
+    check_expression_types(function)?;
+
     // --- Single generate() loop: compile + validate + diversity tracking ---
     let input_schema = function.input_schema();
     let task_count = tasks.len();
@@ -143,9 +170,11 @@ pub fn check_leaf_scalar_function(
                     check_scalar_distribution(
                         j,
                         input,
+                        function.definitions(),
                         &Task::VectorCompletion(vc.clone()),
                         &ScalarOutputShape::VectorCompletion(
                             vc.responses.len(),
+                            vc.rating,
                         ),
                     )?;
                 }
@@ -203,7 +232,26 @@ pub(super) fn check_vector_completion_messages(
     task_index: usize,
     vc: &VectorCompletionTaskExpression,
 ) -> Result<(), String> {
-    if let WithExpression::Value(messages) = &vc.messages {
+    check_messages(task_index, &vc.messages)
+}
+
+/// Checks that a scalar completion task's messages use content parts, and
+/// that its scale is valid.
+fn check_scalar_completion(
+    task_index: usize,
+    sc: &ScalarCompletionTaskExpression,
+) -> Result<(), String> {
+    check_messages(task_index, &sc.messages)?;
+    sc.scale.validate().map_err(|e| {
+        format!("LS22: Task [{}]: invalid scale: {}", task_index, e)
+    })
+}
+
+fn check_messages(
+    task_index: usize,
+    messages: &WithExpression<Vec<WithExpression<MessageExpression>>>,
+) -> Result<(), String> {
+    if let WithExpression::Value(messages) = messages {
         if messages.is_empty() {
             return Err(format!(
                 "LS09: Task [{}]: messages must have at least 1 message",
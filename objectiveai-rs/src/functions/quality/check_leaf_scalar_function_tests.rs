@@ -9,18 +9,20 @@ use crate::chat::completions::request::{
     SystemMessageExpression, ToolMessageExpression, UserMessageExpression,
 };
 use crate::functions::expression::{
-    ArrayInputSchema, BooleanInputSchema, Expression, ImageInputSchema,
+    ArrayInputSchema, BooleanInputSchema, Definitions, Expression, ImageInputSchema,
     InputSchema, IntegerInputSchema, ObjectInputSchema, StringInputSchema,
     WithExpression,
 };
 use crate::functions::quality::check_leaf_scalar_function;
 use crate::functions::{
-    PlaceholderScalarFunctionTaskExpression,
-    PlaceholderVectorFunctionTaskExpression, RemoteFunction,
-    ScalarFunctionTaskExpression, TaskExpression,
-    VectorCompletionTaskExpression, VectorFunctionTaskExpression,
+    Aggregation, AggregationMethod, PlaceholderScalarFunctionTaskExpression,
+    PlaceholderVectorFunctionTaskExpression, Remote, RemoteFunction,
+    ScalarCompletionTaskExpression, ScalarFunctionTaskExpression,
+    TaskExpression, VectorCompletionTaskExpression,
+    VectorFunctionTaskExpression,
 };
 use crate::util::index_map;
+use crate::vector::completions::request::RatingScale;
 
 fn test(f: &RemoteFunction) {
     check_leaf_scalar_function(f).unwrap();
@@ -44,8 +46,10 @@ fn wrong_type_vector() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -67,11 +71,13 @@ fn has_input_maps() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: Some(crate::functions::expression::InputMaps::One(
             Expression::Starlark("input".to_string()),
         )),
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -116,6 +122,7 @@ fn has_input_maps() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS02");
 }
@@ -128,9 +135,11 @@ fn vc_task_has_map() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -175,6 +184,7 @@ fn vc_task_has_map() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS04");
 }
@@ -187,12 +197,15 @@ fn contains_scalar_function_task() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -201,6 +214,7 @@ fn contains_scalar_function_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS05");
 }
@@ -213,12 +227,15 @@ fn contains_vector_function_task() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -227,6 +244,7 @@ fn contains_vector_function_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS06");
 }
@@ -239,6 +257,7 @@ fn contains_placeholder_scalar_task() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderScalarFunction(
             PlaceholderScalarFunctionTaskExpression {
@@ -247,6 +266,7 @@ fn contains_placeholder_scalar_task() {
                     minimum: Some(1),
                     maximum: Some(10),
                 }),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -255,6 +275,7 @@ fn contains_placeholder_scalar_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS07");
 }
@@ -267,6 +288,7 @@ fn contains_placeholder_vector_task() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderVectorFunction(
             PlaceholderVectorFunctionTaskExpression {
@@ -296,6 +318,7 @@ fn contains_placeholder_vector_task() {
                             .to_string(),
                     ),
                 ),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -304,6 +327,7 @@ fn contains_placeholder_vector_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS08");
 }
@@ -316,9 +340,11 @@ fn empty_messages() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![]),
@@ -348,6 +374,7 @@ fn empty_messages() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS09");
 }
@@ -360,9 +387,11 @@ fn one_response() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -398,6 +427,7 @@ fn one_response() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS10");
 }
@@ -410,9 +440,11 @@ fn one_response_expression() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -442,6 +474,7 @@ fn one_response_expression() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS10");
 }
@@ -454,9 +487,11 @@ fn response_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -495,6 +530,7 @@ fn response_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS11");
 }
@@ -507,9 +543,11 @@ fn developer_message_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -550,6 +588,7 @@ fn developer_message_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS13");
 }
@@ -562,9 +601,11 @@ fn system_message_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -603,6 +644,7 @@ fn system_message_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS14");
 }
@@ -615,9 +657,11 @@ fn user_message_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -654,6 +698,7 @@ fn user_message_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS15");
 }
@@ -666,9 +711,11 @@ fn assistant_message_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -712,6 +759,7 @@ fn assistant_message_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS16");
 }
@@ -724,9 +772,11 @@ fn tool_message_plain_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -765,6 +815,7 @@ fn tool_message_plain_string() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS17");
 }
@@ -779,9 +830,11 @@ fn valid_single_task() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -814,10 +867,122 @@ fn valid_single_task() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
 
+#[test]
+fn valid_with_definitions() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: Some(Box::new(Definitions {
+            starlark: index_map! {
+                "prompt" => "def prompt(text):\n    return [{'role': 'user', 'content': [{'type': 'text', 'text': text}]}]"
+                    .to_string(),
+            },
+            jmespath: index_map! {
+                "first_score" => "scores[0]".to_string(),
+            },
+        })),
+        input_maps: None,
+        tasks: vec![TaskExpression::VectorCompletion(
+            VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "prompt(input)".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(
+                        vec![WithExpression::Value(
+                            RichContentPartExpression::Text {
+                                text: WithExpression::Value(
+                                    "Option A".to_string(),
+                                ),
+                            },
+                        )],
+                    )),
+                    WithExpression::Value(RichContentExpression::Parts(
+                        vec![WithExpression::Value(
+                            RichContentPartExpression::Text {
+                                text: WithExpression::Value(
+                                    "Option B".to_string(),
+                                ),
+                            },
+                        )],
+                    )),
+                ]),
+                output: Expression::JMESPath(
+                    "first_score(output)".to_string(),
+                ),
+            },
+        )],
+        aggregation: None,
+    };
+    test(&f);
+}
+
+#[test]
+fn invalid_definitions() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: Some(Box::new(Definitions {
+            starlark: index_map! {
+                "prompt" => "def prompt(text):\n    return [".to_string(),
+            },
+            jmespath: Default::default(),
+        })),
+        input_maps: None,
+        tasks: vec![TaskExpression::VectorCompletion(
+            VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "prompt(input)".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(
+                        vec![WithExpression::Value(
+                            RichContentPartExpression::Text {
+                                text: WithExpression::Value(
+                                    "Option A".to_string(),
+                                ),
+                            },
+                        )],
+                    )),
+                    WithExpression::Value(RichContentExpression::Parts(
+                        vec![WithExpression::Value(
+                            RichContentPartExpression::Text {
+                                text: WithExpression::Value(
+                                    "Option B".to_string(),
+                                ),
+                            },
+                        )],
+                    )),
+                ]),
+                output: Expression::Starlark(
+                    "output['scores'][0]".to_string(),
+                ),
+            },
+        )],
+        aggregation: None,
+    };
+    test_err(&f, "DF03");
+}
+
 #[test]
 fn valid_multiple_tasks() {
     let f = RemoteFunction::Scalar {
@@ -826,10 +991,12 @@ fn valid_multiple_tasks() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -866,6 +1033,7 @@ fn valid_multiple_tasks() {
             ),
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -902,6 +1070,7 @@ fn valid_multiple_tasks() {
             ),
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -937,6 +1106,7 @@ fn valid_multiple_tasks() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -949,8 +1119,10 @@ fn rejects_no_tasks() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
     };
     test_err(&f, "LS03");
 }
@@ -969,9 +1141,11 @@ fn valid_expression_messages_skip_structural_check() {
             },
             required: Some(vec!["text".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -988,6 +1162,7 @@ fn valid_expression_messages_skip_structural_check() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1002,9 +1177,11 @@ fn derived_scalar_output_expression_passes() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1037,6 +1214,7 @@ fn derived_scalar_output_expression_passes() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1049,9 +1227,11 @@ fn fixed_scalar_output_expression() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1094,6 +1274,7 @@ fn fixed_scalar_output_expression() {
                 output: Expression::Starlark("0.5".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "CV11");
 }
@@ -1106,9 +1287,11 @@ fn branching_scalar_output_three_values() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1154,6 +1337,7 @@ fn branching_scalar_output_three_values() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "CV11");
 }
@@ -1166,9 +1350,11 @@ fn description_too_long() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1201,6 +1387,7 @@ fn description_too_long() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QD02");
 }
@@ -1213,9 +1400,11 @@ fn description_empty() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1248,6 +1437,7 @@ fn description_empty() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QD01");
 }
@@ -1260,9 +1450,11 @@ fn valid_developer_message_parts() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1312,6 +1504,7 @@ fn valid_developer_message_parts() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1328,9 +1521,11 @@ fn diversity_fail_all_fixed_parameters() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1347,6 +1542,7 @@ fn diversity_fail_all_fixed_parameters() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS19");
 }
@@ -1359,10 +1555,12 @@ fn diversity_fail_second_task_fixed() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1385,6 +1583,7 @@ fn diversity_fail_second_task_fixed() {
             ),
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1406,6 +1605,7 @@ fn diversity_fail_second_task_fixed() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test_err(&f, "LS19");
 }
@@ -1429,9 +1629,11 @@ fn diversity_fail_object_input_ignored() {
             },
             required: Some(vec!["name".to_string(), "score".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1448,6 +1650,7 @@ fn diversity_fail_object_input_ignored() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS19");
 }
@@ -1462,9 +1665,11 @@ fn diversity_pass_message_derives_from_input() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1481,6 +1686,7 @@ fn diversity_pass_message_derives_from_input() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1493,9 +1699,11 @@ fn diversity_pass_responses_derive_from_input() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1512,6 +1720,7 @@ fn diversity_pass_responses_derive_from_input() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1537,10 +1746,12 @@ fn diversity_pass_object_fields_in_messages() {
                 "context".to_string(),
             ]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1563,6 +1774,7 @@ fn diversity_pass_object_fields_in_messages() {
             ),
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1584,6 +1796,7 @@ fn diversity_pass_object_fields_in_messages() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1596,10 +1809,12 @@ fn diversity_pass_both_messages_and_responses_derived() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1622,6 +1837,7 @@ fn diversity_pass_both_messages_and_responses_derived() {
             ),
             TaskExpression::VectorCompletion(
                 VectorCompletionTaskExpression {
+                    depends_on: None,
                     skip: None,
                     map: None,
                     messages: WithExpression::Expression(
@@ -1643,6 +1859,7 @@ fn diversity_pass_both_messages_and_responses_derived() {
                 },
             ),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1655,9 +1872,11 @@ fn diversity_pass_value_messages_with_expression_text() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1705,6 +1924,7 @@ fn diversity_pass_value_messages_with_expression_text() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1728,9 +1948,11 @@ fn valid_with_skip_last_task_boolean() {
             },
             required: Some(vec!["text".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1752,6 +1974,7 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("input.get('skip_last_task', False)".to_string())),
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1773,6 +1996,7 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1796,9 +2020,11 @@ fn valid_with_skip_on_high_confidence() {
             },
             required: Some(vec!["text".to_string(), "confidence".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1820,6 +2046,7 @@ fn valid_with_skip_on_high_confidence() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['confidence'] > 75".to_string())),
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1841,10 +2068,367 @@ fn valid_with_skip_on_high_confidence() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test(&f);
 }
 
+#[test]
+fn valid_with_depends_on_cascade() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: Some(vec![0]),
+                skip: Some(Expression::Starlark(
+                    "abs(tasks[0] - 0.5) > 0.3".to_string(),
+                )),
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': 'Look again: ' + input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Confirm".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Reject".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+        ],
+        aggregation: None,
+    };
+    test(&f);
+}
+
+#[test]
+fn valid_with_aggregation_method() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': 'Look again: ' + input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Confirm".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Reject".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+        ],
+        aggregation: Some(Box::new(Aggregation::Method(
+            AggregationMethod::WeightedMedian,
+        ))),
+    };
+    test(&f);
+}
+
+#[test]
+fn valid_with_aggregation_expression() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': 'Look again: ' + input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Confirm".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Reject".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+        ],
+        aggregation: Some(Box::new(Aggregation::Expression(
+            Expression::Starlark("min([t for t in tasks if t != None])".to_string()),
+        ))),
+    };
+    test(&f);
+}
+
+#[test]
+fn aggregation_expression_fails() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': 'Look again: ' + input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Confirm".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Reject".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+        ],
+        aggregation: Some(Box::new(Aggregation::Expression(
+            Expression::Starlark("tasks[5]".to_string()),
+        ))),
+    };
+    test_err(&f, "CV43");
+}
+
+#[test]
+fn aggregation_expression_out_of_range() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+            TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': 'Look again: ' + input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Confirm".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Reject".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            }),
+        ],
+        aggregation: Some(Box::new(Aggregation::Expression(
+            Expression::Starlark(
+                "sum([t for t in tasks if t != None]) + 1".to_string(),
+            ),
+        ))),
+    };
+    test_err(&f, "CV12");
+}
+
+#[test]
+fn depends_on_later_task() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![TaskExpression::VectorCompletion(
+            VectorCompletionTaskExpression {
+                depends_on: Some(vec![0]),
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]".to_string(),
+                )),
+                tools: None,
+                responses: WithExpression::Value(vec![
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Agree".to_string()),
+                        }),
+                    ])),
+                    WithExpression::Value(RichContentExpression::Parts(vec![
+                        WithExpression::Value(RichContentPartExpression::Text {
+                            text: WithExpression::Value("Disagree".to_string()),
+                        }),
+                    ])),
+                ]),
+                output: Expression::Starlark("output['scores'][0]".to_string()),
+            },
+        )],
+        aggregation: None,
+    };
+    test_err(&f, "TD02");
+}
+
 // --- Output expression distribution tests ---
 
 #[test]
@@ -1855,9 +2439,11 @@ fn output_distribution_fail_biased() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1882,6 +2468,7 @@ fn output_distribution_fail_biased() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "OD02");
 }
@@ -1894,9 +2481,11 @@ fn output_distribution_pass() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1921,6 +2510,7 @@ fn output_distribution_pass() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -1933,9 +2523,11 @@ fn output_distribution_fail_division_by_zero() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1960,6 +2552,7 @@ fn output_distribution_fail_division_by_zero() {
                 ),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "OD01");
 }
@@ -1972,9 +2565,11 @@ fn rejects_single_permutation_string_enum() {
             description: None,
             r#enum: Some(vec!["only".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2007,6 +2602,7 @@ fn rejects_single_permutation_string_enum() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QI01");
 }
@@ -2020,9 +2616,11 @@ fn rejects_single_permutation_integer() {
             minimum: Some(0),
             maximum: Some(0),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2055,6 +2653,7 @@ fn rejects_single_permutation_integer() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "QI01");
 }
@@ -2078,9 +2677,11 @@ fn modality_fail_image_in_schema_but_str_in_messages() {
             },
             required: Some(vec!["photo".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2103,6 +2704,7 @@ fn modality_fail_image_in_schema_but_str_in_messages() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS20");
 }
@@ -2124,9 +2726,11 @@ fn modality_fail_image_in_schema_but_text_only_responses() {
             },
             required: Some(vec!["image".to_string(), "text".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2141,6 +2745,7 @@ fn modality_fail_image_in_schema_but_text_only_responses() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test_err(&f, "LS20");
 }
@@ -2162,9 +2767,11 @@ fn modality_pass_image_in_messages() {
             },
             required: Some(vec!["photo".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2187,6 +2794,7 @@ fn modality_pass_image_in_messages() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -2208,9 +2816,11 @@ fn modality_pass_image_in_responses() {
             },
             required: Some(vec!["photo".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2225,6 +2835,7 @@ fn modality_pass_image_in_responses() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             },
         )],
+        aggregation: None,
     };
     test(&f);
 }
@@ -2237,9 +2848,11 @@ fn all_tasks_skipped() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2274,6 +2887,7 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("True".to_string())),
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2308,6 +2922,69 @@ fn all_tasks_skipped() {
                 output: Expression::Starlark("output['scores'][0]".to_string()),
             }),
         ],
+        aggregation: None,
     };
     test_err(&f, "CV42");
 }
+
+#[test]
+fn valid_scalar_completion() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![TaskExpression::ScalarCompletion(
+            ScalarCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]"
+                        .to_string(),
+                )),
+                tools: None,
+                scale: RatingScale { min: 1, max: 10 },
+                output: Expression::Starlark(
+                    "(output['rating']['mean'] - 1) / 9".to_string(),
+                ),
+            },
+        )],
+        aggregation: None,
+    };
+    test(&f);
+}
+
+#[test]
+fn scalar_completion_invalid_scale() {
+    let f = RemoteFunction::Scalar {
+        description: "test".to_string(),
+        input_schema: InputSchema::String(StringInputSchema {
+            description: None,
+            r#enum: None,
+        }),
+        definitions: None,
+        input_maps: None,
+        tasks: vec![TaskExpression::ScalarCompletion(
+            ScalarCompletionTaskExpression {
+                depends_on: None,
+                skip: None,
+                map: None,
+                messages: WithExpression::Expression(Expression::Starlark(
+                    "[{'role': 'user', 'content': [{'type': 'text', 'text': input}]}]"
+                        .to_string(),
+                )),
+                tools: None,
+                scale: RatingScale { min: 5, max: 5 },
+                output: Expression::Starlark(
+                    "(output['rating']['mean'] - 1) / 9".to_string(),
+                ),
+            },
+        )],
+        aggregation: None,
+    };
+    test_err(&f, "LS22");
+}
@@ -5,7 +5,10 @@ use std::collections::{HashMap, HashSet};
 use crate::functions::expression::{Input, InputSchema};
 use crate::functions::{CompiledTask, Function, RemoteFunction, Task, TaskExpression};
 
+use super::check_definitions::check_definitions;
+use super::check_depends_on::check_depends_on;
 use super::check_description::check_description;
+use super::check_expression_types::check_expression_types;
 use super::check_input_schema::check_input_schema;
 use super::check_output_expression::{
     VectorOutputShape, check_vector_distribution,
@@ -36,6 +39,7 @@ use super::example_inputs;
 /// 5. Message content must be content parts arrays, not plain strings
 /// 6. Response content must be content parts arrays, not plain strings
 /// 7. Vector fields (output_length, input_split, input_merge) round-trip correctly
+/// 8. Expressions type-check against the input schema
 pub fn check_leaf_vector_function(
     function: &RemoteFunction,
 ) -> Result<(), String> {
@@ -110,6 +114,13 @@ pub fn check_leaf_vector_function(
                 // Responses must be a single expression
                 check_vector_vector_completion_responses(i, vc)?;
             }
+            TaskExpression::ScalarCompletion(_) => {
+                return Err(format!(
+                    "LV19: Task [{}]: leaf vector functions must not contain scalar.completion \
+                     tasks",
+                    i
+                ));
+            }
             TaskExpression::ScalarFunction(_) => {
                 return Err(format!(
                     "LV05: Task [{}]: leaf functions must only contain vector.completion tasks, \
@@ -141,6 +152,15 @@ pub fn check_leaf_vector_function(
         }
     }
 
+    // Definitions must load before any expression can use them
+    check_definitions(function.definitions())?;
+
+    // Tasks may only depend on earlier tasks
+    check_depends_on(function.tasks())?;
+
+    // Expressions must type-check against the input schema
+    check_expression_types(function)?;
+
     // --- Single generate() loop ---
     let vector_fields = VectorFieldsValidation {
         input_schema: input_schema.clone(),
@@ -190,6 +210,7 @@ pub fn check_leaf_vector_function(
                     check_vector_distribution(
                         j,
                         input,
+                        function.definitions(),
                         &Task::VectorCompletion(vc.clone()),
                         &VectorOutputShape::VectorCompletion(
                             vc.responses.len(),
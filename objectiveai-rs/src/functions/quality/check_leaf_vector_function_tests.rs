@@ -15,7 +15,7 @@ use crate::functions::expression::{
 use crate::functions::quality::check_leaf_vector_function;
 use crate::functions::{
     PlaceholderScalarFunctionTaskExpression,
-    PlaceholderVectorFunctionTaskExpression, RemoteFunction,
+    PlaceholderVectorFunctionTaskExpression, Remote, RemoteFunction,
     ScalarFunctionTaskExpression, TaskExpression,
     VectorCompletionTaskExpression, VectorFunctionTaskExpression,
 };
@@ -41,8 +41,10 @@ fn wrong_type_scalar() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
     };
     test_err(&f, "LV01");
 }
@@ -61,11 +63,13 @@ fn rejects_input_maps() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: Some(InputMaps::One(Expression::Starlark(
             "input".to_string(),
         ))),
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -92,6 +96,7 @@ fn rejects_input_maps() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -113,8 +118,10 @@ fn input_schema_string() {
             description: None,
             r#enum: None,
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -142,8 +149,10 @@ fn input_schema_object_no_required_array() {
             },
             required: Some(vec!["name".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -170,12 +179,15 @@ fn contains_scalar_function_task() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::ScalarFunction(
             ScalarFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -184,6 +196,7 @@ fn contains_scalar_function_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -210,12 +223,15 @@ fn contains_vector_function_task() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorFunction(
             VectorFunctionTaskExpression {
+                remote: Remote::Github,
                 owner: "test".to_string(),
                 repository: "test".to_string(),
                 commit: "abc123".to_string(),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -224,6 +240,7 @@ fn contains_vector_function_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -250,6 +267,7 @@ fn contains_placeholder_scalar_task() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderScalarFunction(
             PlaceholderScalarFunctionTaskExpression {
@@ -258,6 +276,7 @@ fn contains_placeholder_scalar_task() {
                     minimum: Some(1),
                     maximum: Some(10),
                 }),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -266,6 +285,7 @@ fn contains_placeholder_scalar_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -292,6 +312,7 @@ fn contains_placeholder_vector_task() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::PlaceholderVectorFunction(
             PlaceholderVectorFunctionTaskExpression {
@@ -313,6 +334,7 @@ fn contains_placeholder_vector_task() {
                 input_merge: WithExpression::Expression(Expression::Starlark(
                     "{'items': [x['items'][0] for x in input]}".to_string(),
                 )),
+                depends_on: None,
                 skip: None,
                 map: None,
                 input: WithExpression::Expression(Expression::Starlark(
@@ -321,6 +343,7 @@ fn contains_placeholder_vector_task() {
                 output: Expression::Starlark("output".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -347,9 +370,11 @@ fn vc_task_has_map() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: Some(0),
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -376,6 +401,7 @@ fn vc_task_has_map() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -402,9 +428,11 @@ fn responses_fixed_array() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -447,6 +475,7 @@ fn responses_fixed_array() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -475,9 +504,11 @@ fn derived_vector_output_expression_passes() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -504,6 +535,7 @@ fn derived_vector_output_expression_passes() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -530,9 +562,11 @@ fn fixed_vector_output_expression() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -559,6 +593,7 @@ fn fixed_vector_output_expression() {
                 output: Expression::Starlark("[0.5, 0.5]".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -585,9 +620,11 @@ fn branching_vector_output_two_values() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -617,6 +654,7 @@ fn branching_vector_output_two_values() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -643,9 +681,11 @@ fn branching_vector_output_three_values() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -675,6 +715,7 @@ fn branching_vector_output_three_values() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -713,9 +754,11 @@ fn responses_fixed_expression_fails_diversity() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -744,6 +787,7 @@ fn responses_fixed_expression_fails_diversity() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -782,9 +826,11 @@ fn responses_fixed_pool_expression_fails_diversity() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -813,6 +859,7 @@ fn responses_fixed_pool_expression_fails_diversity() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -851,9 +898,11 @@ fn responses_derived_from_input_passes_diversity() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -882,6 +931,7 @@ fn responses_derived_from_input_passes_diversity() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['items'])".to_string(),
         )),
@@ -912,9 +962,11 @@ fn description_too_long() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -941,6 +993,7 @@ fn description_too_long() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -967,9 +1020,11 @@ fn description_empty() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -996,6 +1051,7 @@ fn description_empty() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1034,9 +1090,11 @@ fn diversity_fail_third_task_object_schema() {
             },
             required: Some(vec!["candidates".to_string(), "category".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1056,6 +1114,7 @@ fn diversity_fail_third_task_object_schema() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1075,6 +1134,7 @@ fn diversity_fail_third_task_object_schema() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1094,6 +1154,7 @@ fn diversity_fail_third_task_object_schema() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['candidates'])".to_string(),
         )),
@@ -1130,9 +1191,11 @@ fn diversity_fail_third_task_with_labels() {
             },
             required: Some(vec!["entries".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1140,6 +1203,7 @@ fn diversity_fail_third_task_with_labels() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1147,6 +1211,7 @@ fn diversity_fail_third_task_with_labels() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1154,6 +1219,7 @@ fn diversity_fail_third_task_with_labels() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['entries'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'entries': [e], 'label': input['label']} for e in input['entries']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'entries': [x['entries'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1183,9 +1249,11 @@ fn diversity_pass_ranking_with_enum_categories() {
             },
             required: Some(vec!["options".to_string(), "criterion".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1193,6 +1261,7 @@ fn diversity_pass_ranking_with_enum_categories() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1200,6 +1269,7 @@ fn diversity_pass_ranking_with_enum_categories() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['options'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'options': [o], 'criterion': input['criterion']} for o in input['options']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'options': [x['options'][0] for x in input], 'criterion': input[0]['criterion']}".to_string())),
@@ -1215,9 +1285,11 @@ fn diversity_pass_array_of_integers() {
             description: None, min_items: Some(2), max_items: Some(4),
             items: Box::new(InputSchema::Integer(IntegerInputSchema { description: None, minimum: Some(0), maximum: Some(999) })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1225,6 +1297,7 @@ fn diversity_pass_array_of_integers() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1232,6 +1305,7 @@ fn diversity_pass_array_of_integers() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1252,9 +1326,11 @@ fn diversity_pass_nested_object_with_descriptions() {
             },
             required: Some(vec!["items".to_string(), "descriptions".to_string(), "title".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1262,6 +1338,7 @@ fn diversity_pass_nested_object_with_descriptions() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1269,6 +1346,7 @@ fn diversity_pass_nested_object_with_descriptions() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1276,6 +1354,7 @@ fn diversity_pass_nested_object_with_descriptions() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [input['items'][i]], 'descriptions': [input['descriptions'][i]], 'title': input['title']} for i in range(len(input['items']))]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'descriptions': [x['descriptions'][0] for x in input], 'title': input[0]['title']}".to_string())),
@@ -1298,9 +1377,11 @@ fn diversity_pass_array_of_objects_with_nested_fields() {
                 required: Some(vec!["name".to_string(), "tags".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1308,6 +1389,7 @@ fn diversity_pass_array_of_objects_with_nested_fields() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1315,6 +1397,7 @@ fn diversity_pass_array_of_objects_with_nested_fields() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1335,9 +1418,11 @@ fn diversity_pass_object_with_context_and_choices() {
             },
             required: Some(vec!["context".to_string(), "choices".to_string(), "weight".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1345,6 +1430,7 @@ fn diversity_pass_object_with_context_and_choices() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1352,6 +1438,7 @@ fn diversity_pass_object_with_context_and_choices() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1359,6 +1446,7 @@ fn diversity_pass_object_with_context_and_choices() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['choices'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'context': input['context'], 'choices': [c], 'weight': input['weight']} for c in input['choices']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'context': input[0]['context'], 'choices': [x['choices'][0] for x in input], 'weight': input[0]['weight']}".to_string())),
@@ -1381,9 +1469,11 @@ fn within_input_responses_all_cloned() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1410,6 +1500,7 @@ fn within_input_responses_all_cloned() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1436,9 +1527,11 @@ fn within_input_responses_cloned_two_elements() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1465,6 +1558,7 @@ fn within_input_responses_cloned_two_elements() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1493,9 +1587,11 @@ fn valid_array_schema() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1522,6 +1618,7 @@ fn valid_array_schema() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1547,14 +1644,17 @@ fn valid_object_with_required_array() {
             },
             required: Some(vec!["items".to_string(), "label".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+            depends_on: None,
             skip: None, map: None,
             messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
             tools: None,
             responses: WithExpression::Expression(Expression::Starlark("[[{'type': 'text', 'text': x}] for x in input['items']]".to_string())),
             output: Expression::Starlark("output['scores']".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'label': input['label']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'label': input[0]['label']}".to_string())),
@@ -1575,9 +1675,11 @@ fn valid_multiple_tasks() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1604,6 +1706,7 @@ fn valid_multiple_tasks() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -1630,6 +1733,7 @@ fn valid_multiple_tasks() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1661,9 +1765,11 @@ fn valid_people_ranking_with_skip() {
                 required: Some(vec!["fullName".to_string(), "firstName".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
@@ -1672,6 +1778,7 @@ fn valid_people_ranking_with_skip() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
@@ -1680,6 +1787,7 @@ fn valid_people_ranking_with_skip() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("any([p.get('lastName') == None for p in input])".to_string())),
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
@@ -1688,6 +1796,7 @@ fn valid_people_ranking_with_skip() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1711,9 +1820,11 @@ fn people_ranking_null_lastname_replaced() {
                 required: Some(vec!["fullName".to_string(), "firstName".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1721,6 +1832,7 @@ fn people_ranking_null_lastname_replaced() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1728,6 +1840,7 @@ fn people_ranking_null_lastname_replaced() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1735,6 +1848,7 @@ fn people_ranking_null_lastname_replaced() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1755,8 +1869,10 @@ fn rejects_no_tasks() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1787,14 +1903,17 @@ fn response_diversity_pass_boolean_derived() {
                 required: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+            depends_on: None,
             skip: None, map: None,
             messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
             tools: None,
             responses: WithExpression::Expression(Expression::Starlark("[[{'type': 'text', 'text': str(x.get('flag', 'NULL'))}] for x in input]".to_string())),
             output: Expression::Starlark("output['scores']".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1817,14 +1936,17 @@ fn response_diversity_fail_fixed_responses() {
                 required: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+            depends_on: None,
             skip: None, map: None,
             messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
             tools: None,
             responses: WithExpression::Expression(Expression::Starlark("[[{'type': 'text', 'text': str(i)}] for i in range(len(input))]".to_string())),
             output: Expression::Starlark("output['scores']".to_string()),
         })],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input)".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[[x] for x in input]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("[x[0] for x in input]".to_string())),
@@ -1856,9 +1978,11 @@ fn valid_with_skip_last_task_boolean() {
             },
             required: Some(vec!["items".to_string(), "skip_last_task".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1866,6 +1990,7 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['skip_last_task']".to_string())),
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
@@ -1874,6 +1999,7 @@ fn valid_with_skip_last_task_boolean() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'skip_last_task': input['skip_last_task']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'skip_last_task': input[0]['skip_last_task']}".to_string())),
@@ -1904,9 +2030,11 @@ fn valid_with_skip_on_quick_mode() {
             },
             required: Some(vec!["items".to_string(), "mode".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None, map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
                 tools: None,
@@ -1914,6 +2042,7 @@ fn valid_with_skip_on_quick_mode() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark("input['mode'] == 'quick'".to_string())),
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(MessageExpression::User(UserMessageExpression { content: WithExpression::Value(RichContentExpression::Parts(vec![WithExpression::Value(RichContentPartExpression::Text { text: WithExpression::Value("Hello".to_string()) })])), name: None }))]),
@@ -1922,6 +2051,7 @@ fn valid_with_skip_on_quick_mode() {
                 output: Expression::Starlark("output['scores']".to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark("len(input['items'])".to_string())),
         input_split: WithExpression::Expression(Expression::Starlark("[{'items': [x], 'mode': input['mode']} for x in input['items']]".to_string())),
         input_merge: WithExpression::Expression(Expression::Starlark("{'items': [x['items'][0] for x in input], 'mode': input[0]['mode']}".to_string())),
@@ -1944,9 +2074,11 @@ fn output_distribution_fail_biased() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -1963,6 +2095,7 @@ fn output_distribution_fail_biased() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -1989,9 +2122,11 @@ fn output_distribution_pass() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2008,6 +2143,7 @@ fn output_distribution_pass() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2034,9 +2170,11 @@ fn output_distribution_pass_no_max_items() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2053,6 +2191,7 @@ fn output_distribution_pass_no_max_items() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2079,9 +2218,11 @@ fn output_distribution_fail_division_by_zero() {
                 r#enum: None,
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2098,6 +2239,7 @@ fn output_distribution_fail_division_by_zero() {
                 ),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2124,9 +2266,11 @@ fn rejects_single_permutation_string_enum() {
                 r#enum: Some(vec!["only".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2154,6 +2298,7 @@ fn rejects_single_permutation_string_enum() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2181,9 +2326,11 @@ fn rejects_single_permutation_integer() {
                 maximum: Some(0),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2212,6 +2359,7 @@ fn rejects_single_permutation_integer() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2249,9 +2397,11 @@ fn modality_fail_image_in_schema_but_text_only() {
                 required: Some(vec!["photo".to_string(), "name".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2278,6 +2428,7 @@ fn modality_fail_image_in_schema_but_text_only() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2319,9 +2470,11 @@ fn modality_fail_image_in_nested_schema_but_text_only() {
             },
             required: Some(vec!["candidates".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2343,6 +2496,7 @@ fn modality_fail_image_in_nested_schema_but_text_only() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input['candidates'])".to_string(),
         )),
@@ -2378,9 +2532,11 @@ fn modality_pass_image_in_responses() {
                 required: Some(vec!["photo".to_string(), "name".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2406,6 +2562,7 @@ fn modality_pass_image_in_responses() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2441,9 +2598,11 @@ fn modality_pass_image_in_messages() {
                 required: Some(vec!["photo".to_string(), "name".to_string()]),
             })),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![TaskExpression::VectorCompletion(
             VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Expression(Expression::Starlark(
@@ -2457,6 +2616,7 @@ fn modality_pass_image_in_messages() {
                 output: Expression::Starlark("output['scores']".to_string()),
             },
         )],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             "len(input)".to_string(),
         )),
@@ -2494,10 +2654,12 @@ fn job_application_ranker_1() {
             },
             required: Some(vec!["apps".to_string(), "job_description".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             // Task 1: skills alignment
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2522,6 +2684,7 @@ fn job_application_ranker_1() {
             }),
             // Task 2: skills alignment (skipped for single app)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2546,6 +2709,7 @@ fn job_application_ranker_1() {
             }),
             // Task 3: demonstrated impact
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2570,6 +2734,7 @@ fn job_application_ranker_1() {
             }),
             // Task 4: clarity of communication
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2594,6 +2759,7 @@ fn job_application_ranker_1() {
             }),
             // Task 5: career trajectory (system + user message)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2628,6 +2794,7 @@ fn job_application_ranker_1() {
             }),
             // Task 6: tailoring to the role
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: Some(Expression::Starlark(
                     r#"len(input["apps"]) < 3"#.to_string(),
                 )),
@@ -2651,6 +2818,7 @@ fn job_application_ranker_1() {
                 output: Expression::Starlark(r#"output["scores"]"#.to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             r#"len(input["apps"])"#.to_string(),
         )),
@@ -2687,10 +2855,12 @@ fn job_application_ranker_2() {
             },
             required: Some(vec!["apps".to_string(), "job_description".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             // Task 1: skills alignment
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2713,6 +2883,7 @@ fn job_application_ranker_2() {
             }),
             // Task 2: skills alignment (skipped for single app)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2735,6 +2906,7 @@ fn job_application_ranker_2() {
             }),
             // Task 3: demonstrated impact
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2757,6 +2929,7 @@ fn job_application_ranker_2() {
             }),
             // Task 4: clarity of communication
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2779,6 +2952,7 @@ fn job_application_ranker_2() {
             }),
             // Task 5: career trajectory (system + user message)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![
@@ -2811,6 +2985,7 @@ fn job_application_ranker_2() {
             }),
             // Task 6: tailoring to the role
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2832,6 +3007,7 @@ fn job_application_ranker_2() {
                 output: Expression::Starlark(r#"output["scores"]"#.to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             r#"len(input["apps"])"#.to_string(),
         )),
@@ -2869,10 +3045,12 @@ fn job_application_ranker_3() {
             },
             required: Some(vec!["apps".to_string(), "job_description".to_string()]),
         }),
+        definitions: None,
         input_maps: None,
         tasks: vec![
             // Task 1: skills alignment
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2895,6 +3073,7 @@ fn job_application_ranker_3() {
             }),
             // Task 2: skills alignment (skipped for single app)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2917,6 +3096,7 @@ fn job_application_ranker_3() {
             }),
             // Task 3: demonstrated impact
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2939,6 +3119,7 @@ fn job_application_ranker_3() {
             }),
             // Task 4: clarity of communication
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -2961,6 +3142,7 @@ fn job_application_ranker_3() {
             }),
             // Task 5: career trajectory (system + user message)
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![
@@ -2993,6 +3175,7 @@ fn job_application_ranker_3() {
             }),
             // Task 6: tailoring to the role
             TaskExpression::VectorCompletion(VectorCompletionTaskExpression {
+                depends_on: None,
                 skip: None,
                 map: None,
                 messages: WithExpression::Value(vec![WithExpression::Value(
@@ -3014,6 +3197,7 @@ fn job_application_ranker_3() {
                 output: Expression::Starlark(r#"output["scores"]"#.to_string()),
             }),
         ],
+        aggregation: None,
         output_length: WithExpression::Expression(Expression::Starlark(
             r#"len(input["apps"])"#.to_string(),
         )),
@@ -19,9 +19,12 @@
 use rust_decimal::Decimal;
 
 use crate::functions::expression::{
-    FunctionOutput, Input, TaskOutput, TaskOutputOwned, VectorCompletionOutput,
+    Definitions, FunctionOutput, Input, TaskOutput, TaskOutputOwned,
+    VectorCompletionOutput,
 };
 use crate::functions::Task;
+use crate::vector::completions::request::RatingScale;
+use crate::vector::completions::response::Rating;
 
 /// Number of systematic trials for distribution checks.
 const TRIALS: usize = 1000;
@@ -127,12 +130,18 @@ fn systematic_map_scalar(k: usize, len: usize) -> Vec<FunctionOutput> {
         .collect()
 }
 
-/// Generates a systematic VectorCompletionOutput with `n` scores.
-fn systematic_vc_output(k: usize, n: usize) -> VectorCompletionOutput {
+/// Generates a systematic VectorCompletionOutput with `n` scores, rated if
+/// the task has a rating scale.
+fn systematic_vc_output(
+    k: usize,
+    n: usize,
+    rating: Option<&RatingScale>,
+) -> VectorCompletionOutput {
     let scores = systematic_vector(k, n);
     let weights = systematic_vector(k, n);
     VectorCompletionOutput {
         votes: Vec::new(),
+        rating: rating.map(|scale| Rating::from_scores(scale, &scores)),
         scores,
         weights,
     }
@@ -146,8 +155,9 @@ fn systematic_vc_output(k: usize, n: usize) -> VectorCompletionOutput {
 pub(super) enum ScalarOutputShape {
     /// Raw output is a scalar function result.
     Scalar,
-    /// Raw output is a vector completion with `n` responses.
-    VectorCompletion(usize),
+    /// Raw output is a vector completion with `n` responses, and its rating
+    /// scale if any.
+    VectorCompletion(usize, Option<RatingScale>),
 }
 
 /// Runs 1000 systematic trials on a task's output expression and checks that
@@ -158,6 +168,7 @@ pub(super) enum ScalarOutputShape {
 pub(super) fn check_scalar_distribution(
     task_index: usize,
     input: &Input,
+    definitions: Option<&Definitions>,
     task: &Task,
     shape: &ScalarOutputShape,
 ) -> Result<(), String> {
@@ -172,12 +183,14 @@ pub(super) fn check_scalar_distribution(
                     systematic_scalar(k),
                 )),
             ),
-            ScalarOutputShape::VectorCompletion(n) => TaskOutput::Owned(
-                TaskOutputOwned::VectorCompletion(systematic_vc_output(k, *n)),
-            ),
+            ScalarOutputShape::VectorCompletion(n, rating) => {
+                TaskOutput::Owned(TaskOutputOwned::VectorCompletion(
+                    systematic_vc_output(k, *n, rating.as_ref()),
+                ))
+            }
         };
 
-        let result = task.compile_output(input, mock_output).map_err(|e| {
+        let result = task.compile_output(input, definitions, mock_output).map_err(|e| {
             format!(
                 "OD01: Task [{}]: output expression evaluation failed during \
                  distribution check (trial {}): {}",
@@ -258,6 +271,7 @@ pub(super) enum VectorOutputShape {
 pub(super) fn check_vector_distribution(
     task_index: usize,
     input: &Input,
+    definitions: Option<&Definitions>,
     task: &Task,
     shape: &VectorOutputShape,
     output_length: usize,
@@ -277,11 +291,13 @@ pub(super) fn check_vector_distribution(
                 )),
             ),
             VectorOutputShape::VectorCompletion(n) => TaskOutput::Owned(
-                TaskOutputOwned::VectorCompletion(systematic_vc_output(k, *n)),
+                TaskOutputOwned::VectorCompletion(systematic_vc_output(
+                    k, *n, None,
+                )),
             ),
         };
 
-        let result = task.compile_output(input, mock_output).map_err(|e| {
+        let result = task.compile_output(input, definitions, mock_output).map_err(|e| {
             // Special error for all-zero mapped scalar inputs (trial 0)
             if k == 0 && matches!(shape, VectorOutputShape::MapScalar(_)) {
                 format!(
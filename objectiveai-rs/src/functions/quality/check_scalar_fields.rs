@@ -10,6 +10,7 @@ use crate::functions::expression::InputSchema;
 
 /// The fields needed to validate a scalar function's input behavior.
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ScalarFieldsValidation {
     pub input_schema: InputSchema,
 }
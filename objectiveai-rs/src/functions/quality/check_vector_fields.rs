@@ -13,6 +13,7 @@ use crate::functions::{Function, RemoteFunction};
 
 /// The 4 fields needed to validate a vector function's split/merge behavior.
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorFieldsValidation {
     pub input_schema: InputSchema,
     pub output_length: WithExpression<u64>,
@@ -26,8 +27,10 @@ impl VectorFieldsValidation {
         Function::Remote(RemoteFunction::Vector {
             description: String::new(),
             input_schema: self.input_schema.clone(),
+            definitions: None,
             input_maps: None,
             tasks: vec![],
+            aggregation: None,
             output_length: self.output_length.clone(),
             input_split: self.input_split.clone(),
             input_merge: self.input_merge.clone(),
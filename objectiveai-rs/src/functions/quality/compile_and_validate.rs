@@ -2,21 +2,28 @@
 
 use std::collections::{HashMap, HashSet};
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use rust_decimal::Decimal;
 
 use crate::chat::completions::request::{Message, RichContent, SimpleContent};
 use crate::functions::expression::{
-    FunctionOutput, Params, ParamsRef, TaskOutput, TaskOutputOwned,
-    VectorCompletionOutput,
+    Expression, FunctionOutput, Params, ParamsRef, TaskOutput,
+    TaskOutputOwned, VectorCompletionOutput,
 };
 use crate::functions::{
-    CompiledTask, Function, RemoteFunction, Task, VectorCompletionTask,
+    Aggregation, CompiledTask, Function, RemoteFunction, Task,
+    VectorCompletionTask,
 };
+use crate::vector::completions::request::RatingScale;
+use crate::vector::completions::response::Rating;
 
 /// Number of randomized output expression evaluations to verify variance.
 const OUTPUT_EXPRESSION_TRIALS: usize = 100;
 
+/// Seed for the random task outputs that tasks with `depends_on` are
+/// compiled against, so that the same Function always validates the same way.
+const DEPENDENT_TASK_OUTPUTS_SEED: u64 = 0;
+
 /// Whether the parent function is scalar or vector (with a known output_length).
 enum FunctionType {
     Scalar,
@@ -39,6 +46,9 @@ pub(super) fn compile_and_validate_one_input(
                 input,
                 output: None,
                 map: None,
+                definitions: function.definitions(),
+                tasks: None,
+                weights: None,
             });
             let len =
                 output_length.clone().compile_one(&params).map_err(|e| {
@@ -53,7 +63,7 @@ pub(super) fn compile_and_validate_one_input(
 
     // compile_tasks takes self by value, so we clone into a Function
     let func = Function::Remote(function.clone());
-    let compiled_tasks = func.compile_tasks(input).map_err(|e| {
+    let mut compiled_tasks = func.clone().compile_tasks(input).map_err(|e| {
         format!(
             "CV03: Input {}: task compilation failed: {}\n\nInput: {}",
             input_label,
@@ -62,6 +72,36 @@ pub(super) fn compile_and_validate_one_input(
         )
     })?;
 
+    // Tasks with depends_on are compiled against random outputs of the tasks
+    // before them, standing in for the outputs they would see when executed
+    if function.tasks().iter().any(|t| t.depends_on().is_some()) {
+        let mut rng =
+            rand::rngs::StdRng::seed_from_u64(DEPENDENT_TASK_OUTPUTS_SEED);
+        let mut task_outputs = Vec::with_capacity(compiled_tasks.len());
+        for (j, compiled_task) in compiled_tasks.iter_mut().enumerate() {
+            if func.tasks()[j].depends_on().is_some() {
+                *compiled_task =
+                    func.compile_task(j, input, &task_outputs).map_err(|e| {
+                        format!(
+                            "CV03: Input {}: task [{}] compilation failed: {}\n\nInput: {}",
+                            input_label,
+                            j,
+                            e,
+                            serde_json::to_string(input).unwrap_or_default()
+                        )
+                    })?;
+            }
+            task_outputs.push(compiled_task.as_ref().map(|_| {
+                match &function_type {
+                    FunctionType::Scalar => random_scalar_output(&mut rng),
+                    FunctionType::Vector { output_length } => {
+                        random_vector_output(*output_length, &mut rng)
+                    }
+                }
+            }));
+        }
+    }
+
     // At least one task must not be skipped
     if compiled_tasks.iter().all(|t| t.is_none()) {
         return Err(format!(
@@ -84,8 +124,8 @@ pub(super) fn compile_and_validate_one_input(
                     input_label,
                     j,
                     input,
+                    function.definitions(),
                     compiled_task,
-                    task,
                     &function_type,
                     children,
                 )?;
@@ -100,26 +140,85 @@ pub(super) fn compile_and_validate_one_input(
                         children,
                     )?;
                 }
-                // Validate the mapped output expression using the first
-                // task as representative (all share the same output expr)
-                if let Some(first) = tasks.first() {
-                    validate_output_expression(
-                        input_label,
-                        j,
-                        input,
-                        compiled_task,
-                        first,
-                        &function_type,
-                        children,
-                    )?;
-                }
+                // Validate the mapped output expression
+                validate_output_expression(
+                    input_label,
+                    j,
+                    input,
+                    function.definitions(),
+                    compiled_task,
+                    &function_type,
+                    children,
+                )?;
             }
         }
     }
 
+    // Validate the aggregation expression, if any
+    if let Some(Aggregation::Expression(expression)) = function.aggregation() {
+        validate_aggregation_expression(
+            input_label,
+            input,
+            function.definitions(),
+            expression,
+            &compiled_tasks,
+            &function_type,
+        )?;
+    }
+
     Ok(compiled_tasks)
 }
 
+/// Evaluates the aggregation expression against randomized outputs of the
+/// tasks that ran and randomized profile weights, validating each result
+/// against the function type.
+fn validate_aggregation_expression(
+    input_label: &str,
+    input: &crate::functions::expression::Input,
+    definitions: Option<&crate::functions::expression::Definitions>,
+    expression: &Expression,
+    compiled_tasks: &[Option<CompiledTask>],
+    function_type: &FunctionType,
+) -> Result<(), String> {
+    let location = format!("Input {}, aggregation", input_label);
+    let mut rng = rand::rng();
+
+    for trial in 0..OUTPUT_EXPRESSION_TRIALS {
+        let task_outputs: Vec<Option<FunctionOutput>> = compiled_tasks
+            .iter()
+            .map(|task| {
+                task.as_ref().map(|_| match function_type {
+                    FunctionType::Scalar => random_scalar_output(&mut rng),
+                    FunctionType::Vector { output_length } => {
+                        random_vector_output(*output_length, &mut rng)
+                    }
+                })
+            })
+            .collect();
+        let weights = random_scores(compiled_tasks.len(), &mut rng);
+        let params = Params::Ref(ParamsRef {
+            input,
+            output: None,
+            map: None,
+            definitions,
+            tasks: Some(&task_outputs),
+            weights: Some(&weights),
+        });
+
+        let result =
+            expression.compile_one::<FunctionOutput>(&params).map_err(|e| {
+                format!(
+                    "CV43: {}: aggregation expression evaluation failed (trial {}): {}",
+                    location, trial, e
+                )
+            })?;
+
+        validate_function_output(&location, function_type, &result)?;
+    }
+
+    Ok(())
+}
+
 /// Validates a single compiled task's inputs.
 fn validate_compiled_task(
     input_label: &str,
@@ -214,21 +313,31 @@ fn validate_output_expression(
     input_label: &str,
     task_index: usize,
     input: &crate::functions::expression::Input,
+    definitions: Option<&crate::functions::expression::Definitions>,
     compiled_task: &CompiledTask,
-    representative_task: &Task,
     function_type: &FunctionType,
     children: Option<&HashMap<String, RemoteFunction>>,
 ) -> Result<(), String> {
     let location = format!("Input {}, task [{}]", input_label, task_index);
 
+    // Mapped tasks share the same output expression, so the first
+    // instance is representative of all of them
+    let representative_task = match compiled_task {
+        CompiledTask::One(task) => task,
+        CompiledTask::Many(tasks) => match tasks.first() {
+            Some(task) => task,
+            None => return Ok(()),
+        },
+    };
+
     // Determine the output shape info we need for random generation
     // (returns None if we can't construct mocks, e.g. vector.function without children)
     let shape = match compiled_task {
         CompiledTask::One(task) => {
-            task_output_shape(task, children, &location)?
+            task_output_shape(task, definitions, children, &location)?
         }
         CompiledTask::Many(tasks) => {
-            mapped_task_output_shape(tasks, children, &location)?
+            mapped_task_output_shape(tasks, definitions, children, &location)?
         }
     };
 
@@ -243,7 +352,7 @@ fn validate_output_expression(
         let mock_output = random_task_output(&shape, &mut rng);
 
         let result = representative_task
-            .compile_output(input, mock_output)
+            .compile_output(input, definitions, mock_output)
             .map_err(|e| {
                 format!(
                     "CV10: {}: output expression evaluation failed (trial {}): {}",
@@ -335,14 +444,14 @@ fn validate_function_output(
 
 /// Describes the shape of a task's raw output for random generation.
 enum OutputShape {
-    /// Vector completion with `n` responses.
-    VectorCompletion(usize),
+    /// Vector completion with `n` responses, and its rating scale if any.
+    VectorCompletion(usize, Option<RatingScale>),
     /// Scalar function output (single value in [0, 1]).
     Scalar,
     /// Vector function output with `n` elements.
     Vector(u64),
     /// Mapped vector completion — one VectorCompletion per task instance.
-    MapVectorCompletion(Vec<usize>),
+    MapVectorCompletion(Vec<(usize, Option<RatingScale>)>),
     /// Mapped scalar function — one scalar per task instance.
     MapScalar(usize),
     /// Mapped vector function — one vector per task instance.
@@ -353,12 +462,16 @@ enum OutputShape {
 /// Returns `None` if shape can't be determined (e.g., vector.function without children).
 fn task_output_shape(
     task: &Task,
+    definitions: Option<&crate::functions::expression::Definitions>,
     children: Option<&HashMap<String, RemoteFunction>>,
     location: &str,
 ) -> Result<Option<OutputShape>, String> {
     match task {
         Task::VectorCompletion(vc) => {
-            Ok(Some(OutputShape::VectorCompletion(vc.responses.len())))
+            Ok(Some(OutputShape::VectorCompletion(
+                vc.responses.len(),
+                vc.rating,
+            )))
         }
         Task::ScalarFunction(_) | Task::PlaceholderScalarFunction(_) => {
             Ok(Some(OutputShape::Scalar))
@@ -382,6 +495,9 @@ fn task_output_shape(
                 input: &t.input,
                 output: None,
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
             let n =
                 t.output_length.clone().compile_one(&params).map_err(|e| {
@@ -399,6 +515,7 @@ fn task_output_shape(
 /// Determines the output shape for a mapped (Many) compiled task.
 fn mapped_task_output_shape(
     tasks: &[Task],
+    definitions: Option<&crate::functions::expression::Definitions>,
     children: Option<&HashMap<String, RemoteFunction>>,
     location: &str,
 ) -> Result<Option<OutputShape>, String> {
@@ -411,10 +528,12 @@ fn mapped_task_output_shape(
 
     match &tasks[0] {
         Task::VectorCompletion(_) => {
-            let sizes: Vec<usize> = tasks
+            let sizes: Vec<(usize, Option<RatingScale>)> = tasks
                 .iter()
                 .map(|task| match task {
-                    Task::VectorCompletion(vc) => Ok(vc.responses.len()),
+                    Task::VectorCompletion(vc) => {
+                        Ok((vc.responses.len(), vc.rating))
+                    }
                     _ => Err(format!(
                         "CV20: {}: mixed task types in mapped task",
                         location
@@ -463,6 +582,9 @@ fn mapped_task_output_shape(
                             input: &t.input,
                             output: None,
                             map: None,
+                            definitions,
+                            tasks: None,
+                            weights: None,
                         });
                         t.output_length.clone().compile_one(&params).map_err(
                             |e| {
@@ -493,8 +615,12 @@ fn random_task_output<'a>(
     rng: &mut impl Rng,
 ) -> TaskOutput<'a> {
     match shape {
-        OutputShape::VectorCompletion(n) => TaskOutput::Owned(
-            TaskOutputOwned::VectorCompletion(random_vc_output(*n, rng)),
+        OutputShape::VectorCompletion(n, rating) => TaskOutput::Owned(
+            TaskOutputOwned::VectorCompletion(random_vc_output(
+                *n,
+                rating.as_ref(),
+                rng,
+            )),
         ),
         OutputShape::Scalar => TaskOutput::Owned(TaskOutputOwned::Function(
             random_scalar_output(rng),
@@ -503,8 +629,10 @@ fn random_task_output<'a>(
             random_vector_output(*n, rng),
         )),
         OutputShape::MapVectorCompletion(sizes) => {
-            let outputs =
-                sizes.iter().map(|&n| random_vc_output(n, rng)).collect();
+            let outputs = sizes
+                .iter()
+                .map(|(n, rating)| random_vc_output(*n, rating.as_ref(), rng))
+                .collect();
             TaskOutput::Owned(TaskOutputOwned::MapVectorCompletion(outputs))
         }
         OutputShape::MapScalar(count) => {
@@ -536,12 +664,18 @@ fn random_vector_output(n: u64, rng: &mut impl Rng) -> FunctionOutput {
     FunctionOutput::Vector(scores)
 }
 
-/// Random vector completion output with `n` responses.
-fn random_vc_output(n: usize, rng: &mut impl Rng) -> VectorCompletionOutput {
+/// Random vector completion output with `n` responses, rated if the task
+/// has a rating scale.
+fn random_vc_output(
+    n: usize,
+    rating: Option<&RatingScale>,
+    rng: &mut impl Rng,
+) -> VectorCompletionOutput {
     let scores = random_scores(n, rng);
     let weights = random_scores(n, rng);
     VectorCompletionOutput {
         votes: Vec::new(),
+        rating: rating.map(|scale| Rating::from_scores(scale, &scores)),
         scores,
         weights,
     }
@@ -592,6 +726,9 @@ fn resolve_vector_function_output_length(
         input: task_input,
         output: None,
         map: None,
+        definitions: child.definitions(),
+        tasks: None,
+        weights: None,
     });
     let n = output_length_expr
         .clone()
@@ -1,6 +1,9 @@
 //! Quality checks for function definitions.
 //!
 //! - [`example_inputs`] — RNG-based example input generation from an `InputSchema`
+//! - [`check_definitions`] — validates named Starlark and JMESPath definitions
+//! - [`check_depends_on`] — validates that tasks only depend on earlier tasks
+//! - [`check_expression_types`] — statically checks expressions against the input schema
 //! - [`check_vector_fields`] — validates output_length, input_split, and input_merge
 //! - [`check_leaf_function`] — validates a leaf function (depth 0, vector.completion tasks)
 //! - [`check_branch_function`] — validates a branch function (depth > 0, function/placeholder tasks)
@@ -8,7 +11,10 @@
 mod check_branch_function;
 mod check_branch_scalar_function;
 mod check_branch_vector_function;
+mod check_definitions;
+mod check_depends_on;
 mod check_description;
+mod check_expression_types;
 mod check_input_schema;
 mod check_modalities;
 mod check_scalar_fields;
@@ -23,6 +29,9 @@ mod example_inputs;
 pub use check_branch_function::check_branch_function;
 pub use check_branch_scalar_function::check_branch_scalar_function;
 pub use check_branch_vector_function::check_branch_vector_function;
+pub use check_definitions::check_definitions;
+pub use check_depends_on::check_depends_on;
+pub use check_expression_types::check_expression_types;
 pub use check_leaf_function::check_leaf_function;
 pub use check_leaf_scalar_function::check_leaf_scalar_function;
 pub use check_leaf_vector_function::check_leaf_vector_function;
@@ -35,6 +44,12 @@ mod check_branch_scalar_function_tests;
 #[cfg(test)]
 mod check_branch_vector_function_tests;
 #[cfg(test)]
+mod check_definitions_tests;
+#[cfg(test)]
+mod check_depends_on_tests;
+#[cfg(test)]
+mod check_expression_types_tests;
+#[cfg(test)]
 mod check_leaf_scalar_function_tests;
 #[cfg(test)]
 mod check_leaf_vector_function_tests;
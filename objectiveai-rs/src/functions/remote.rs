@@ -5,6 +5,7 @@ use std::fmt;
 
 /// The remote source where a function or profile is hosted.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Remote {
     /// GitHub repository.
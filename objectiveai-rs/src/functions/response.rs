@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 /// Response from listing functions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListFunction {
     /// List of available functions.
     pub data: Vec<ListFunctionItem>,
@@ -12,6 +13,7 @@ pub struct ListFunction {
 
 /// A function in a list response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListFunctionItem {
     /// The remote source where the function is hosted.
     pub remote: functions::Remote,
@@ -24,6 +26,7 @@ pub struct ListFunctionItem {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetFunction {
     pub remote: functions::Remote,
     pub owner: String,
@@ -35,6 +38,7 @@ pub struct GetFunction {
 
 /// Usage statistics for a function.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UsageFunction {
     /// Total number of requests made with this function.
     pub requests: u64,
@@ -48,6 +52,7 @@ pub struct UsageFunction {
 
 /// Response from listing function-profile pairs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListFunctionProfilePair {
     /// List of available function-profile pairs.
     pub data: Vec<ListFunctionProfilePairItem>,
@@ -55,6 +60,7 @@ pub struct ListFunctionProfilePair {
 
 /// A function-profile pair in a list response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ListFunctionProfilePairItem {
     /// The function.
     pub function: ListFunctionItem,
@@ -64,6 +70,7 @@ pub struct ListFunctionProfilePairItem {
 
 /// Response from getting a function-profile pair.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetFunctionProfilePair {
     /// The function.
     pub function: GetFunction,
@@ -73,6 +80,7 @@ pub struct GetFunctionProfilePair {
 
 /// Usage statistics for a function-profile pair.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct UsageFunctionProfilePair {
     /// Total number of requests made with this function-profile pair.
     pub requests: u64,
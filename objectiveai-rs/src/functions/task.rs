@@ -1,7 +1,8 @@
 //! Task types for Function definitions.
 //!
 //! Tasks are the building blocks of Functions. Each task either calls another
-//! Function or runs a Vector Completion. Tasks can be conditionally skipped
+//! Function or runs a Vector Completion, optionally rating the prompt on a
+//! numeric scale. Tasks can be conditionally skipped
 //! or mapped over arrays of inputs.
 //!
 //! # Output Expressions
@@ -34,6 +35,7 @@ use serde::{Deserialize, Serialize};
 /// resolved against input data during compilation. Use [`compile`](Self::compile)
 /// to produce a concrete [`Task`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum TaskExpression {
     #[serde(rename = "scalar.function")]
@@ -42,6 +44,8 @@ pub enum TaskExpression {
     VectorFunction(VectorFunctionTaskExpression),
     #[serde(rename = "vector.completion")]
     VectorCompletion(VectorCompletionTaskExpression),
+    #[serde(rename = "scalar.completion")]
+    ScalarCompletion(ScalarCompletionTaskExpression),
     #[serde(rename = "placeholder.scalar.function")]
     PlaceholderScalarFunction(PlaceholderScalarFunctionTaskExpression),
     #[serde(rename = "placeholder.vector.function")]
@@ -55,17 +59,39 @@ impl TaskExpression {
             TaskExpression::ScalarFunction(task) => task.skip.take(),
             TaskExpression::VectorFunction(task) => task.skip.take(),
             TaskExpression::VectorCompletion(task) => task.skip.take(),
+            TaskExpression::ScalarCompletion(task) => task.skip.take(),
             TaskExpression::PlaceholderScalarFunction(task) => task.skip.take(),
             TaskExpression::PlaceholderVectorFunction(task) => task.skip.take(),
         }
     }
 
+    /// Returns the indices of earlier tasks this task depends on, if any.
+    pub fn depends_on(&self) -> Option<&[u64]> {
+        match self {
+            TaskExpression::ScalarFunction(task) => task.depends_on.as_deref(),
+            TaskExpression::VectorFunction(task) => task.depends_on.as_deref(),
+            TaskExpression::VectorCompletion(task) => {
+                task.depends_on.as_deref()
+            }
+            TaskExpression::ScalarCompletion(task) => {
+                task.depends_on.as_deref()
+            }
+            TaskExpression::PlaceholderScalarFunction(task) => {
+                task.depends_on.as_deref()
+            }
+            TaskExpression::PlaceholderVectorFunction(task) => {
+                task.depends_on.as_deref()
+            }
+        }
+    }
+
     /// Returns the map index, if this is a mapped task.
     pub fn input_map(&self) -> Option<u64> {
         match self {
             TaskExpression::ScalarFunction(task) => task.map,
             TaskExpression::VectorFunction(task) => task.map,
             TaskExpression::VectorCompletion(task) => task.map,
+            TaskExpression::ScalarCompletion(task) => task.map,
             TaskExpression::PlaceholderScalarFunction(task) => task.map,
             TaskExpression::PlaceholderVectorFunction(task) => task.map,
         }
@@ -86,6 +112,9 @@ impl TaskExpression {
             TaskExpression::VectorCompletion(task) => {
                 task.compile(params).map(Task::VectorCompletion)
             }
+            TaskExpression::ScalarCompletion(task) => {
+                task.compile(params).map(Task::VectorCompletion)
+            }
             TaskExpression::PlaceholderScalarFunction(task) => {
                 task.compile(params).map(Task::PlaceholderScalarFunction)
             }
@@ -101,6 +130,7 @@ impl TaskExpression {
 /// Produced by compiling a [`TaskExpression`] against input data. All
 /// expressions have been resolved to concrete values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum Task {
     /// Calls a scalar function (produces a single score).
@@ -124,6 +154,7 @@ impl Task {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -131,19 +162,19 @@ impl Task {
     > {
         match self {
             Task::ScalarFunction(task) => {
-                task.compile_output(input, raw_output)
+                task.compile_output(input, definitions, raw_output)
             }
             Task::VectorFunction(task) => {
-                task.compile_output(input, raw_output)
+                task.compile_output(input, definitions, raw_output)
             }
             Task::VectorCompletion(task) => {
-                task.compile_output(input, raw_output)
+                task.compile_output(input, definitions, raw_output)
             }
             Task::PlaceholderScalarFunction(task) => {
-                task.compile_output(input, raw_output)
+                task.compile_output(input, definitions, raw_output)
             }
             Task::PlaceholderVectorFunction(task) => {
-                task.compile_output(input, raw_output)
+                task.compile_output(input, definitions, raw_output)
             }
         }
     }
@@ -151,6 +182,7 @@ impl Task {
 
 /// Expression for a task that calls a scalar function (pre-compilation).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ScalarFunctionTaskExpression {
     /// The remote source where the function is hosted.
     pub remote: super::Remote,
@@ -161,7 +193,14 @@ pub struct ScalarFunctionTaskExpression {
     /// Git commit SHA for the function version.
     pub commit: String,
 
-    /// If this expression evaluates to true, skip the task. Receives: `input`.
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip: Option<super::expression::Expression>,
 
@@ -171,7 +210,7 @@ pub struct ScalarFunctionTaskExpression {
     pub map: Option<u64>,
 
     /// Expression for the input to pass to the function.
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub input:
         super::expression::WithExpression<super::expression::InputExpression>,
 
@@ -214,6 +253,7 @@ impl ScalarFunctionTaskExpression {
 
 /// A compiled scalar function task ready for execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ScalarFunctionTask {
     /// The remote source where the function is hosted.
     pub remote: super::Remote,
@@ -237,6 +277,7 @@ impl ScalarFunctionTask {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -247,6 +288,9 @@ impl ScalarFunctionTask {
                 input,
                 output: Some(raw_output),
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
         let compiled_output = self.output.compile_one(&params)?;
         Ok(compiled_output)
@@ -255,6 +299,7 @@ impl ScalarFunctionTask {
 
 /// Expression for a task that calls a vector function (pre-compilation).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorFunctionTaskExpression {
     /// The remote source where the function is hosted.
     pub remote: super::Remote,
@@ -265,7 +310,14 @@ pub struct VectorFunctionTaskExpression {
     /// Git commit SHA for the function version.
     pub commit: String,
 
-    /// If this expression evaluates to true, skip the task. Receives: `input`.
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip: Option<super::expression::Expression>,
 
@@ -275,7 +327,7 @@ pub struct VectorFunctionTaskExpression {
     pub map: Option<u64>,
 
     /// Expression for the input to pass to the function.
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub input:
         super::expression::WithExpression<super::expression::InputExpression>,
 
@@ -318,6 +370,7 @@ impl VectorFunctionTaskExpression {
 
 /// A compiled vector function task ready for execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorFunctionTask {
     /// The remote source where the function is hosted.
     pub remote: super::Remote,
@@ -341,6 +394,7 @@ impl VectorFunctionTask {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -351,6 +405,9 @@ impl VectorFunctionTask {
                 input,
                 output: Some(raw_output),
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
         let compiled_output = self.output.compile_one(&params)?;
         Ok(compiled_output)
@@ -359,8 +416,16 @@ impl VectorFunctionTask {
 
 /// Expression for a task that runs a vector completion (pre-compilation).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionTaskExpression {
-    /// If this expression evaluates to true, skip the task. Receives: `input`.
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip: Option<super::expression::Expression>,
 
@@ -370,7 +435,7 @@ pub struct VectorCompletionTaskExpression {
     pub map: Option<u64>,
 
     /// Expression for the conversation messages (the prompt).
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub messages: super::expression::WithExpression<
         Vec<
             super::expression::WithExpression<
@@ -379,7 +444,7 @@ pub struct VectorCompletionTaskExpression {
         >,
     >,
     /// Expression for tools available to the completion (read-only context).
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<
         super::expression::WithExpression<
@@ -393,7 +458,7 @@ pub struct VectorCompletionTaskExpression {
         >,
     >,
     /// Expression for the possible responses the LLMs can vote for.
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub responses: super::expression::WithExpression<
         Vec<
             super::expression::WithExpression<
@@ -484,6 +549,7 @@ impl VectorCompletionTaskExpression {
             messages: compiled_messages,
             tools,
             responses: compiled_responses,
+            rating: None,
             output: self.output,
         })
     }
@@ -491,6 +557,7 @@ impl VectorCompletionTaskExpression {
 
 /// A compiled vector completion task ready for execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionTask {
     /// The resolved conversation messages.
     pub messages: Vec<chat::completions::request::Message>,
@@ -499,6 +566,10 @@ pub struct VectorCompletionTask {
     pub tools: Option<Vec<chat::completions::request::Tool>>,
     /// The resolved response options the LLMs can vote for.
     pub responses: Vec<chat::completions::request::RichContent>,
+    /// The rating scale, if compiled from a [`ScalarCompletionTaskExpression`].
+    /// The responses are then the values of the scale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<crate::vector::completions::request::RatingScale>,
     /// Expression to transform the task result into a valid function output.
     ///
     /// Receives `output` as `VectorCompletion(VectorCompletionOutput)` containing
@@ -512,6 +583,7 @@ impl VectorCompletionTask {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -522,22 +594,116 @@ impl VectorCompletionTask {
                 input,
                 output: Some(raw_output),
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
         let compiled_output = self.output.compile_one(&params)?;
         Ok(compiled_output)
     }
 }
 
+/// Expression for a task that has each LLM rate the prompt on a numeric scale
+/// (pre-compilation).
+///
+/// Compiles to a [`VectorCompletionTask`] whose responses are the values of
+/// the scale, so that each LLM's vote is a distribution over the ratings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ScalarCompletionTaskExpression {
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip: Option<super::expression::Expression>,
+
+    /// Index into `input_maps` for mapped execution. If set, this task is
+    /// expanded into multiple instances.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map: Option<u64>,
+
+    /// Expression for the conversation messages (the prompt).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
+    pub messages: super::expression::WithExpression<
+        Vec<
+            super::expression::WithExpression<
+                chat::completions::request::MessageExpression,
+            >,
+        >,
+    >,
+    /// Expression for tools available to the completion (read-only context).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<
+        super::expression::WithExpression<
+            Option<
+                Vec<
+                    super::expression::WithExpression<
+                        chat::completions::request::ToolExpression,
+                    >,
+                >,
+            >,
+        >,
+    >,
+    /// The scale the LLMs rate the prompt on.
+    pub scale: crate::vector::completions::request::RatingScale,
+
+    /// Expression to transform the task result into a valid function output.
+    ///
+    /// Receives `output` as `VectorCompletion(VectorCompletionOutput)`, with
+    /// `scores` over the values of the scale and `rating` holding the
+    /// weighted `mean` and `std_dev` of the ratings.
+    ///
+    /// See [`VectorCompletionTaskExpression::output`] for full documentation.
+    pub output: super::expression::Expression,
+}
+
+impl ScalarCompletionTaskExpression {
+    /// Compiles the expression into a concrete [`VectorCompletionTask`] over
+    /// the values of the scale.
+    pub fn compile(
+        self,
+        params: &super::expression::Params,
+    ) -> Result<VectorCompletionTask, super::expression::ExpressionError> {
+        let mut task = VectorCompletionTaskExpression {
+            depends_on: None,
+            skip: None,
+            map: None,
+            messages: self.messages,
+            tools: self.tools,
+            responses: super::expression::WithExpression::Value(Vec::new()),
+            output: self.output,
+        }
+        .compile(params)?;
+        task.responses = self.scale.responses();
+        task.rating = Some(self.scale);
+        Ok(task)
+    }
+}
+
 /// Expression for a placeholder scalar function task (pre-compilation).
 ///
 /// Like [`ScalarFunctionTaskExpression`] but without owner/repository/commit.
 /// Always produces a fixed output of 0.5.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceholderScalarFunctionTaskExpression {
     /// JSON Schema defining the expected input structure.
     pub input_schema: super::expression::InputSchema,
 
-    /// If this expression evaluates to true, skip the task. Receives: `input`.
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip: Option<super::expression::Expression>,
 
@@ -546,7 +712,7 @@ pub struct PlaceholderScalarFunctionTaskExpression {
     pub map: Option<u64>,
 
     /// Expression for the input to pass to the placeholder function.
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub input:
         super::expression::WithExpression<super::expression::InputExpression>,
 
@@ -575,6 +741,7 @@ impl PlaceholderScalarFunctionTaskExpression {
 /// Always produces `FunctionOutput::Scalar(0.5)` before the output expression
 /// is applied.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceholderScalarFunctionTask {
     /// JSON Schema defining the expected input structure.
     pub input_schema: super::expression::InputSchema,
@@ -588,6 +755,7 @@ impl PlaceholderScalarFunctionTask {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -598,6 +766,9 @@ impl PlaceholderScalarFunctionTask {
                 input,
                 output: Some(raw_output),
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
         let compiled_output = self.output.compile_one(&params)?;
         Ok(compiled_output)
@@ -609,6 +780,7 @@ impl PlaceholderScalarFunctionTask {
 /// Like [`VectorFunctionTaskExpression`] but without owner/repository/commit.
 /// Always produces an equalized vector of length `output_length`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceholderVectorFunctionTaskExpression {
     /// JSON Schema defining the expected input structure.
     pub input_schema: super::expression::InputSchema,
@@ -627,7 +799,14 @@ pub struct PlaceholderVectorFunctionTaskExpression {
     pub input_merge:
         super::expression::WithExpression<super::expression::Input>,
 
-    /// If this expression evaluates to true, skip the task. Receives: `input`.
+    /// Indices of earlier tasks whose outputs this task reads. If set, the
+    /// task is compiled only after those tasks finish, with their outputs
+    /// available as `tasks`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<u64>>,
+
+    /// If this expression evaluates to true, skip the task.
+    /// Receives: `input`, `tasks` (if `depends_on` is set).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip: Option<super::expression::Expression>,
 
@@ -636,7 +815,7 @@ pub struct PlaceholderVectorFunctionTaskExpression {
     pub map: Option<u64>,
 
     /// Expression for the input to pass to the placeholder function.
-    /// Receives: `input`, `map` (if mapped).
+    /// Receives: `input`, `map` (if mapped), `tasks` (if `depends_on` is set).
     pub input:
         super::expression::WithExpression<super::expression::InputExpression>,
 
@@ -668,6 +847,7 @@ impl PlaceholderVectorFunctionTaskExpression {
 /// Always produces `FunctionOutput::Vector(vec![1/N; output_length])` before
 /// the output expression is applied.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PlaceholderVectorFunctionTask {
     /// JSON Schema defining the expected input structure.
     pub input_schema: super::expression::InputSchema,
@@ -689,6 +869,7 @@ impl PlaceholderVectorFunctionTask {
     pub fn compile_output(
         &self,
         input: &super::expression::Input,
+        definitions: Option<&super::expression::Definitions>,
         raw_output: super::expression::TaskOutput,
     ) -> Result<
         super::expression::FunctionOutput,
@@ -699,6 +880,9 @@ impl PlaceholderVectorFunctionTask {
                 input,
                 output: Some(raw_output),
                 map: None,
+                definitions,
+                tasks: None,
+                weights: None,
             });
         let compiled_output = self.output.compile_one(&params)?;
         Ok(compiled_output)
@@ -711,6 +895,7 @@ impl PlaceholderVectorFunctionTask {
 /// field are expanded into multiple tasks, one per element in the referenced
 /// input map sub-array.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum CompiledTask {
     /// A single task (no mapping).
@@ -3,12 +3,14 @@
 use crate::error;
 use eventsource_stream::Event as MessageEvent;
 use futures::{Stream, StreamExt};
-use reqwest_eventsource::{Event, RequestBuilderExt};
+use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
+use std::time::Duration;
 
 /// HTTP client for making requests to the ObjectiveAI API.
 ///
 /// Handles authentication, request building, and response parsing for both
-/// unary and streaming endpoints.
+/// unary and streaming endpoints. Failed requests are retried according to
+/// [`retry_policy`](Self::retry_policy).
 ///
 /// # Example
 ///
@@ -36,6 +38,8 @@ pub struct HttpClient {
     pub x_title: Option<String>,
     /// Value for both `Referer` and `HTTP-Referer` headers.
     pub referer: Option<String>,
+    /// How failed requests are retried and dropped streams resumed.
+    pub retry_policy: super::RetryPolicy,
 }
 
 impl HttpClient {
@@ -67,9 +71,19 @@ impl HttpClient {
             user_agent: user_agent.map(Into::into),
             x_title: x_title.map(Into::into),
             referer: referer.map(Into::into),
+            retry_policy: super::RetryPolicy::default(),
         }
     }
 
+    /// Replaces the retry policy.
+    pub fn with_retry_policy(
+        mut self,
+        retry_policy: super::RetryPolicy,
+    ) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Builds a request with authentication and custom headers.
     fn request(
         &self,
@@ -103,6 +117,65 @@ impl HttpClient {
         request
     }
 
+    /// Sends a request, retrying it according to the retry policy. Returns
+    /// the last response, whatever its status.
+    async fn execute(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<impl serde::Serialize>,
+    ) -> Result<reqwest::Response, super::HttpError> {
+        let mut request = Some(
+            self.request(method.clone(), path, body)
+                .build()
+                .map_err(super::HttpError::RequestError)?,
+        );
+        let mut retry = 0;
+        loop {
+            let original = request.take().unwrap();
+            // keep the original for the next retry, if there can be one
+            let (attempt, next) = if retry < self.retry_policy.max_retries {
+                match original.try_clone() {
+                    Some(attempt) => (attempt, Some(original)),
+                    None => (original, None),
+                }
+            } else {
+                (original, None)
+            };
+            let result = self.http_client.execute(attempt).await;
+            let delay = match (&result, next.is_some()) {
+                (_, false) => None,
+                (Ok(response), true)
+                    if self
+                        .retry_policy
+                        .retries_status(&method, response.status()) =>
+                {
+                    Some(
+                        retry_after(response.headers())
+                            .map(|d| self.retry_policy.retry_after(d))
+                            .unwrap_or_else(|| self.retry_policy.delay(retry)),
+                    )
+                }
+                (Err(e), true)
+                    if self
+                        .retry_policy
+                        .retries_transport_error(&method, e) =>
+                {
+                    Some(self.retry_policy.delay(retry))
+                }
+                _ => None,
+            };
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    retry += 1;
+                }
+                None => return result.map_err(super::HttpError::HttpError),
+            }
+        }
+    }
+
     /// Sends a unary (request-response) API call and deserializes the response.
     ///
     /// # Type Parameters
@@ -125,15 +198,7 @@ impl HttpClient {
         path: impl AsRef<str>,
         body: Option<impl serde::Serialize>,
     ) -> Result<T, super::HttpError> {
-        let response = self
-            .http_client
-            .execute(
-                self.request(method, path.as_ref(), body)
-                    .build()
-                    .map_err(super::HttpError::RequestError)?,
-            )
-            .await
-            .map_err(super::HttpError::HttpError)?;
+        let response = self.execute(method, path.as_ref(), body).await?;
         let code = response.status();
         if code.is_success() {
             let text =
@@ -144,20 +209,7 @@ impl HttpClient {
                 Err(e) => Err(super::HttpError::DeserializationError(e)),
             }
         } else {
-            match response.text().await {
-                Ok(text) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: match serde_json::from_str::<serde_json::Value>(&text)
-                    {
-                        Ok(body) => body,
-                        Err(_) => serde_json::Value::String(text),
-                    },
-                }),
-                Err(_) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: serde_json::Value::Null,
-                }),
-            }
+            Err(bad_status(code, response).await)
         }
     }
 
@@ -180,44 +232,27 @@ impl HttpClient {
         path: impl AsRef<str>,
         body: Option<impl serde::Serialize>,
     ) -> Result<(), super::HttpError> {
-        let response = self
-            .http_client
-            .execute(
-                self.request(method, path.as_ref(), body)
-                    .build()
-                    .map_err(super::HttpError::RequestError)?,
-            )
-            .await
-            .map_err(super::HttpError::HttpError)?;
+        let response = self.execute(method, path.as_ref(), body).await?;
         let code = response.status();
         if code.is_success() {
             Ok(())
         } else {
-            match response.text().await {
-                Ok(text) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: match serde_json::from_str::<serde_json::Value>(&text)
-                    {
-                        Ok(body) => body,
-                        Err(_) => serde_json::Value::String(text),
-                    },
-                }),
-                Err(_) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: serde_json::Value::Null,
-                }),
-            }
+            Err(bad_status(code, response).await)
         }
     }
 
     /// Sends a streaming API call using Server-Sent Events (SSE).
     ///
     /// Returns a stream of deserialized chunks. The stream automatically handles:
-    /// - SSE `[DONE]` messages (filtered out)
+    /// - SSE `[DONE]` messages (which end the stream)
     /// - Comment lines starting with `:` (filtered out)
     /// - Empty data lines (filtered out)
     /// - API errors embedded in stream data
     ///
+    /// Failures to connect are retried according to the retry policy, until
+    /// the first message arrives. A stream which ends without `[DONE]` yields
+    /// a final [`super::HttpError::StreamError`].
+    ///
     /// # Type Parameters
     ///
     /// * `T` - The expected chunk type to deserialize each SSE message into
@@ -248,62 +283,174 @@ impl HttpClient {
         + use<T, P, B>,
         super::HttpError,
     > {
-        Ok(
-            self.request(method, path.as_ref(), body)
-                .eventsource()?
-                .then(|result| async {
-                    match result {
-                        Ok(Event::Open) => None,
-                        Ok(Event::Message(MessageEvent { data, .. }))
-                            if data == "[DONE]"
-                                || data.starts_with(":")
-                                || data.is_empty() =>
+        struct State {
+            request: reqwest::RequestBuilder,
+            method: reqwest::Method,
+            retry_policy: super::RetryPolicy,
+            event_source: EventSource,
+            retry: u32,
+            received: bool,
+            done: bool,
+        }
+
+        let request = self.request(method.clone(), path.as_ref(), body);
+        let event_source = open_event_source(&request)?;
+        let state = State {
+            request,
+            method,
+            retry_policy: self.retry_policy.clone(),
+            event_source,
+            retry: 0,
+            received: false,
+            done: false,
+        };
+        Ok(futures::stream::unfold(state, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            loop {
+                match state.event_source.next().await? {
+                    Ok(Event::Open) => {}
+                    Ok(Event::Message(MessageEvent { data, .. }))
+                        if data == "[DONE]" =>
+                    {
+                        state.event_source.close();
+                        return None;
+                    }
+                    Ok(Event::Message(MessageEvent { data, .. }))
+                        if data.starts_with(":") || data.is_empty() => {}
+                    Ok(Event::Message(MessageEvent { data, .. })) => {
+                        state.received = true;
+                        let mut de = serde_json::Deserializer::from_str(&data);
+                        let item = match serde_path_to_error::deserialize::<_, T>(
+                            &mut de,
+                        ) {
+                            Ok(value) => Ok(value),
+                            Err(e) => match serde_json::from_str::<
+                                error::ResponseError,
+                            >(&data)
+                            {
+                                Ok(err) => Err(super::HttpError::ApiError(err)),
+                                Err(_) => Err(
+                                    super::HttpError::DeserializationError(e),
+                                ),
+                            },
+                        };
+                        return Some((item, state));
+                    }
+                    Err(e) => {
+                        state.event_source.close();
+                        let delay = if state.received
+                            || state.retry >= state.retry_policy.max_retries
                         {
                             None
-                        }
-                        Ok(Event::Message(MessageEvent { data, .. })) => {
-                            let mut de =
-                                serde_json::Deserializer::from_str(&data);
-                            Some(
-                                match serde_path_to_error::deserialize::<_, T>(
-                                    &mut de,
-                                ) {
-                                    Ok(value) => Ok(value),
-                                    Err(e) => match serde_json::from_str::<error::ResponseError>(&data) {
-                                        Ok(err) => Err(super::HttpError::ApiError(err)),
-                                        Err(_) => Err(super::HttpError::DeserializationError(e)),
-                                    },
+                        } else {
+                            match &e {
+                                reqwest_eventsource::Error::Transport(e)
+                                    if state
+                                        .retry_policy
+                                        .retries_transport_error(
+                                            &state.method,
+                                            e,
+                                        ) =>
+                                {
+                                    Some(state.retry_policy.delay(state.retry))
                                 }
-                            )
-                        }
-                        Err(reqwest_eventsource::Error::InvalidStatusCode(
-                            code,
-                            response,
-                        )) => match response.text().await {
-                            Ok(body) => {
-                                Some(Err(super::HttpError::BadStatus {
+                                reqwest_eventsource::Error::InvalidStatusCode(
                                     code,
-                                    body: match serde_json::from_str::<
-                                        serde_json::Value,
-                                    >(
-                                        &body
-                                    ) {
-                                        Ok(body) => body,
-                                        Err(_) => {
-                                            serde_json::Value::String(body)
-                                        }
-                                    },
-                                }))
+                                    response,
+                                ) if state
+                                    .retry_policy
+                                    .retries_status(&state.method, *code) =>
+                                {
+                                    Some(retry_after(response.headers())
+                                        .map(|d| state.retry_policy.retry_after(d))
+                                        .unwrap_or_else(|| {
+                                            state.retry_policy.delay(state.retry)
+                                        }))
+                                }
+                                _ => None,
+                            }
+                        };
+                        let retried = match delay {
+                            Some(delay) => {
+                                tokio::time::sleep(delay).await;
+                                open_event_source(&state.request).ok()
+                            }
+                            None => None,
+                        };
+                        match retried {
+                            Some(event_source) => {
+                                state.event_source = event_source;
+                                state.retry += 1;
                             }
-                            Err(_) => Some(Err(super::HttpError::BadStatus {
-                                code,
-                                body: serde_json::Value::Null,
-                            })),
-                        },
-                        Err(e) => Some(Err(super::HttpError::StreamError(e))),
+                            None => {
+                                state.done = true;
+                                return Some((
+                                    Err(stream_error(e).await),
+                                    state,
+                                ));
+                            }
+                        }
                     }
-                })
-                .filter_map(|x| async { x }),
-        )
+                }
+            }
+        }))
+    }
+}
+
+/// Opens an event source for a copy of `request`, leaving retries to the
+/// caller.
+fn open_event_source(
+    request: &reqwest::RequestBuilder,
+) -> Result<EventSource, reqwest_eventsource::CannotCloneRequestError> {
+    let mut event_source = request
+        .try_clone()
+        .ok_or(reqwest_eventsource::CannotCloneRequestError)?
+        .eventsource()?;
+    event_source.set_retry_policy(Box::new(reqwest_eventsource::retry::Never));
+    Ok(event_source)
+}
+
+/// Returns the delay requested by a response's `Retry-After` header, if it
+/// is given in seconds.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Builds a [`super::HttpError::BadStatus`] from a non-success response.
+async fn bad_status(
+    code: reqwest::StatusCode,
+    response: reqwest::Response,
+) -> super::HttpError {
+    match response.text().await {
+        Ok(text) => super::HttpError::BadStatus {
+            code,
+            body: match serde_json::from_str::<serde_json::Value>(&text) {
+                Ok(body) => body,
+                Err(_) => serde_json::Value::String(text),
+            },
+        },
+        Err(_) => super::HttpError::BadStatus {
+            code,
+            body: serde_json::Value::Null,
+        },
+    }
+}
+
+/// Converts an event source error into a [`super::HttpError`].
+async fn stream_error(e: reqwest_eventsource::Error) -> super::HttpError {
+    match e {
+        reqwest_eventsource::Error::InvalidStatusCode(code, response) => {
+            bad_status(code, response).await
+        }
+        e => e.into(),
     }
 }
@@ -0,0 +1,122 @@
+//! Tests for HttpClient retries and streaming.
+
+#![cfg(test)]
+
+use super::test_server::{TestServer, json_response, sse_response};
+use super::{HttpClient, HttpError, RetryPolicy};
+use futures::StreamExt;
+use std::time::Duration;
+
+fn client(server: &TestServer) -> HttpClient {
+    HttpClient::new(
+        reqwest::Client::new(),
+        Some(server.api_base.clone()),
+        None::<String>,
+        None::<String>,
+        None::<String>,
+        None::<String>,
+    )
+    .with_retry_policy(RetryPolicy {
+        initial_interval: Duration::from_millis(1),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn retries_idempotent_requests_after_server_errors() {
+    let server = TestServer::start(vec![
+        json_response(503, r#"{"error":"unavailable"}"#),
+        json_response(200, r#"{"ok":true}"#),
+    ])
+    .await;
+    let value: serde_json::Value = client(&server)
+        .send_unary(reqwest::Method::GET, "status", None::<()>)
+        .await
+        .unwrap();
+    assert_eq!(value, serde_json::json!({ "ok": true }));
+    assert_eq!(server.requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn caps_retry_after() {
+    let server = TestServer::start(vec![
+        json_response(429, r#"{"error":"rate limited"}"#).replacen(
+            "connection: close",
+            "retry-after: 3600\r\nconnection: close",
+            1,
+        ),
+        json_response(200, r#"{"ok":true}"#),
+    ])
+    .await;
+    let client = client(&server).with_retry_policy(RetryPolicy {
+        max_retry_after: Duration::from_millis(1),
+        ..Default::default()
+    });
+    let value: serde_json::Value = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.send_unary(reqwest::Method::GET, "status", None::<()>),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(value, serde_json::json!({ "ok": true }));
+    assert_eq!(server.requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn does_not_retry_posts_after_server_errors() {
+    let server = TestServer::start(vec![
+        json_response(503, r#"{"error":"unavailable"}"#),
+        json_response(200, r#"{"ok":true}"#),
+    ])
+    .await;
+    let err = client(&server)
+        .send_unary::<serde_json::Value>(
+            reqwest::Method::POST,
+            "chat/completions",
+            Some(serde_json::json!({})),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        HttpError::BadStatus { code, .. } if code.as_u16() == 503
+    ));
+    assert_eq!(server.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_streams_before_the_first_message() {
+    let server = TestServer::start(vec![
+        json_response(503, r#"{"error":"unavailable"}"#),
+        sse_response(&[r#"{"n":1}"#, r#"{"n":2}"#, "[DONE]"]),
+    ])
+    .await;
+    let items: Vec<serde_json::Value> = client(&server)
+        .send_streaming(reqwest::Method::GET, "stream", None::<()>)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(
+        items,
+        vec![serde_json::json!({ "n": 1 }), serde_json::json!({ "n": 2 })]
+    );
+    assert_eq!(server.requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn streams_without_done_end_with_an_error() {
+    let server = TestServer::start(vec![sse_response(&[r#"{"n":1}"#])]).await;
+    let items: Vec<Result<serde_json::Value, HttpError>> = client(&server)
+        .send_streaming(reqwest::Method::POST, "stream", None::<()>)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert!(matches!(items[1], Err(HttpError::StreamError(_))));
+    assert_eq!(server.requests.lock().unwrap().len(), 1);
+}
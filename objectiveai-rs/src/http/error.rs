@@ -21,8 +21,10 @@ pub enum HttpError {
     },
 
     /// Error occurred while reading from an SSE stream.
+    ///
+    /// Boxed to keep `HttpError` small.
     #[error("error fetching stream: {0}")]
-    StreamError(#[from] reqwest_eventsource::Error),
+    StreamError(Box<reqwest_eventsource::Error>),
 
     /// Failed to build the HTTP request.
     #[error("request error: {0}")]
@@ -43,18 +45,26 @@ pub enum HttpError {
     ApiError(#[from] error::ResponseError),
 }
 
+impl From<reqwest_eventsource::Error> for HttpError {
+    fn from(e: reqwest_eventsource::Error) -> Self {
+        HttpError::StreamError(Box::new(e))
+    }
+}
+
 impl error::StatusError for HttpError {
     fn status(&self) -> u16 {
         match self {
             HttpError::DeserializationError(_) => 500,
             HttpError::BadStatus { code, .. } => code.as_u16(),
-            HttpError::StreamError(reqwest_eventsource::Error::Transport(
-                e,
-            )) => e.status().map(|s| s.as_u16()).unwrap_or(500),
-            HttpError::StreamError(
-                reqwest_eventsource::Error::InvalidStatusCode(code, _),
-            ) => code.as_u16(),
-            HttpError::StreamError(_) => 500,
+            HttpError::StreamError(e) => match e.as_ref() {
+                reqwest_eventsource::Error::Transport(e) => {
+                    e.status().map(|s| s.as_u16()).unwrap_or(500)
+                }
+                reqwest_eventsource::Error::InvalidStatusCode(code, _) => {
+                    code.as_u16()
+                }
+                _ => 500,
+            },
             HttpError::RequestError(e) => {
                 e.status().map(|s| s.as_u16()).unwrap_or(500)
             }
@@ -12,9 +12,18 @@
 //!
 //! - [`HttpClient`] - The main client for making API requests
 //! - [`HttpError`] - Error types for HTTP operations
+//! - [`RetryPolicy`] - How failed requests are retried and dropped streams
+//!   resumed
 
 mod client;
 mod error;
+mod retry;
 
 pub use client::*;
 pub use error::*;
+pub use retry::*;
+
+#[cfg(test)]
+mod client_tests;
+#[cfg(test)]
+pub mod test_server;
@@ -0,0 +1,166 @@
+//! Retry policy for HTTP requests.
+
+use std::time::Duration;
+
+/// How [`HttpClient`](super::HttpClient) retries failed requests and resumes
+/// dropped streams.
+///
+/// Idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) are
+/// retried after transport errors and after `408`, `429`, `500`, `502`, `503`
+/// and `504` responses. Other requests are only retried when the connection
+/// could not be established, since the server never saw them, unless
+/// [`retry_non_idempotent`](Self::retry_non_idempotent) is set. Streams are
+/// only retried before their first event.
+///
+/// Delays grow exponentially from [`initial_interval`](Self::initial_interval)
+/// up to [`max_interval`](Self::max_interval). A `Retry-After` header, in
+/// seconds, takes precedence, up to [`max_retry_after`](Self::max_retry_after).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a single request. `0` disables retries.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_interval: Duration,
+    /// Maximum delay between retries.
+    pub max_interval: Duration,
+    /// Maximum delay honoured from a `Retry-After` header.
+    pub max_retry_after: Duration,
+    /// Factor by which the delay grows after each retry.
+    pub multiplier: f64,
+    /// Whether to retry non-idempotent requests after errors which may have
+    /// reached the server.
+    pub retry_non_idempotent: bool,
+    /// Maximum number of times a dropped Function execution stream is
+    /// resumed. `0` disables resuming.
+    pub max_stream_resumes: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_interval: Duration::from_millis(250),
+            max_interval: Duration::from_secs(4),
+            max_retry_after: Duration::from_secs(60),
+            multiplier: 2.0,
+            retry_non_idempotent: false,
+            max_stream_resumes: 2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy which never retries or resumes.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            max_stream_resumes: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the delay before retry number `retry`, starting from `0`.
+    pub fn delay(&self, retry: u32) -> Duration {
+        // clamp the factor first, since `mul_f64` panics on overflow
+        let factor = self.multiplier.max(1.0).powf(retry as f64);
+        let max_factor = self.max_interval.as_secs_f64()
+            / self.initial_interval.as_secs_f64();
+        if factor < max_factor {
+            self.initial_interval.mul_f64(factor).min(self.max_interval)
+        } else {
+            self.max_interval
+        }
+    }
+
+    /// Returns the delay before a retry requested by a `Retry-After` header.
+    pub fn retry_after(&self, requested: Duration) -> Duration {
+        requested.min(self.max_retry_after)
+    }
+
+    /// Returns whether a request with `method` may be retried after a
+    /// response with status `code`.
+    pub fn retries_status(
+        &self,
+        method: &reqwest::Method,
+        code: reqwest::StatusCode,
+    ) -> bool {
+        self.retries_method(method)
+            && matches!(code.as_u16(), 408 | 429 | 500 | 502 | 503 | 504)
+    }
+
+    /// Returns whether a request with `method` may be retried after the
+    /// transport error `error`.
+    pub fn retries_transport_error(
+        &self,
+        method: &reqwest::Method,
+        error: &reqwest::Error,
+    ) -> bool {
+        error.is_connect()
+            || (self.retries_method(method)
+                && (error.is_timeout() || error.is_request()))
+    }
+
+    fn retries_method(&self, method: &reqwest::Method) -> bool {
+        self.retry_non_idempotent || method.is_idempotent()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_grows_up_to_max_interval() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(0), Duration::from_millis(250));
+        assert_eq!(policy.delay(1), Duration::from_millis(500));
+        assert_eq!(policy.delay(10), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn delay_does_not_overflow() {
+        let policy = RetryPolicy {
+            multiplier: 1e300,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(250));
+        assert_eq!(policy.delay(2), Duration::from_secs(4));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(4));
+        let policy = RetryPolicy {
+            initial_interval: Duration::ZERO,
+            max_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        assert_eq!(policy.delay(u32::MAX), Duration::ZERO);
+    }
+
+    #[test]
+    fn retry_after_is_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.retry_after(Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.retry_after(Duration::from_secs(u64::MAX)),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn only_idempotent_methods_retry_bad_statuses() {
+        let policy = RetryPolicy::default();
+        let unavailable = reqwest::StatusCode::SERVICE_UNAVAILABLE;
+        assert!(policy.retries_status(&reqwest::Method::GET, unavailable));
+        assert!(!policy.retries_status(&reqwest::Method::POST, unavailable));
+        assert!(!policy.retries_status(
+            &reqwest::Method::GET,
+            reqwest::StatusCode::BAD_REQUEST
+        ));
+        let policy = RetryPolicy {
+            retry_non_idempotent: true,
+            ..Default::default()
+        };
+        assert!(policy.retries_status(&reqwest::Method::POST, unavailable));
+    }
+}
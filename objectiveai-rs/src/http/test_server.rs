@@ -0,0 +1,105 @@
+//! A local HTTP server which stands in for the API in tests.
+
+#![cfg(test)]
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Answers each connection with the next canned response, then closes it.
+pub struct TestServer {
+    /// Base URL of the server.
+    pub api_base: String,
+    /// Each request received, as `"{method} {path}\n{body}"`.
+    pub requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestServer {
+    /// Starts a server which answers with `responses` in order.
+    pub async fn start<R>(responses: Vec<R>) -> Self
+    where
+        R: AsRef<[u8]> + Send + 'static,
+    {
+        let listener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api_base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut socket).await;
+                recorded.lock().unwrap().push(request);
+                socket.write_all(response.as_ref()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+        Self { api_base, requests }
+    }
+
+    /// Returns the bodies of the requests received so far.
+    pub fn bodies(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request.split_once('\n').unwrap().1.to_string())
+            .collect()
+    }
+}
+
+/// A response with a JSON body.
+pub fn json_response(status: u16, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {} Status\r\ncontent-type: application/json\r\n\
+         content-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// A Server-Sent Events response with one message per item of `data`. The
+/// stream ends when the connection closes.
+pub fn sse_response(data: &[&str]) -> String {
+    let mut response = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                        connection: close\r\n\r\n"
+        .to_string();
+    for data in data {
+        response.push_str(&format!("data: {}\n\n", data));
+    }
+    response
+}
+
+async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break i + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())
+                .flatten()
+        })
+        .unwrap_or(0);
+    while buf.len() < header_end + content_length {
+        let n = socket.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let request_line = head.lines().next().unwrap_or_default();
+    let mut parts = request_line.split(' ');
+    format!(
+        "{} {}\n{}",
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+        String::from_utf8_lossy(&buf[header_end..header_end + content_length])
+    )
+}
@@ -14,7 +14,13 @@
 //!
 //! # Features
 //!
+//! - `client`: Enables the [`Client`] trait, implemented by transports which
+//!   can run completions and Function executions
 //! - `http` (default): Enables the HTTP client for making API requests
+//! - `blocking`: Enables the [`blocking`] client for callers which are not
+//!   async
+//! - `schemars`: Derives `schemars::JsonSchema` for the request and response
+//!   types
 //!
 //! # Modules
 //!
@@ -27,9 +33,17 @@
 //! - [`prefixed_uuid`] - UUID utilities
 //! - [`vector`] - Vector completion APIs
 //!
+//! When the `client` feature is enabled:
+//! - [`Client`] - Transport-agnostic client trait
+//!
 //! When the `http` feature is enabled:
 //! - [`HttpClient`] - HTTP client for API requests
 //! - [`HttpError`] - HTTP error types
+//! - [`RetryPolicy`] - How failed requests are retried and dropped streams
+//!   resumed
+//!
+//! When the `blocking` feature is enabled:
+//! - [`blocking`] - Blocking client for callers which are not async
 
 pub mod auth;
 pub mod chat;
@@ -43,8 +57,17 @@ pub mod vector;
 #[cfg(test)]
 mod util;
 
+#[cfg(feature = "client")]
+mod client;
+
+#[cfg(feature = "client")]
+pub use client::*;
+
 #[cfg(feature = "http")]
 mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
@@ -161,3 +161,23 @@ impl<'de, const PFX_1: char, const PFX_2: char, const PFX_3: char>
         PrefixedUuid::from_str(&s).map_err(serde::de::Error::custom)
     }
 }
+
+#[cfg(feature = "schemars")]
+impl<const PFX_1: char, const PFX_2: char, const PFX_3: char>
+    schemars::JsonSchema for PrefixedUuid<PFX_1, PFX_2, PFX_3>
+{
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("PrefixedUuid_{}{}{}", PFX_1, PFX_2, PFX_3).into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": format!("^{}{}{}[0-9a-f]{{32}}$", PFX_1, PFX_2, PFX_3),
+        })
+    }
+}
@@ -2,6 +2,7 @@ use crate::chat;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CacheVoteRequest<'a> {
     Ref(CacheVoteRequestRef<'a>),
     Owned(CacheVoteRequestOwned),
@@ -18,6 +19,7 @@ impl<'de> serde::de::Deserialize<'de> for CacheVoteRequest<'static> {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CacheVoteRequestRef<'a> {
     pub model: &'a chat::completions::request::Model,
     pub models: Option<&'a [chat::completions::request::Model]>,
@@ -27,6 +29,7 @@ pub struct CacheVoteRequestRef<'a> {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CacheVoteRequestOwned {
     pub model: chat::completions::request::Model,
     pub models: Option<Vec<chat::completions::request::Model>>,
@@ -2,11 +2,13 @@ use crate::vector;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompletionVotes {
     pub data: Option<Vec<vector::completions::response::Vote>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CacheVote {
     pub vote: Option<vector::completions::response::Vote>,
 }
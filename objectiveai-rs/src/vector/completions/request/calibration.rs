@@ -0,0 +1,531 @@
+//! Per-LLM calibration of vote distributions.
+//!
+//! Votes derived from logprobs are often overconfident for some models and
+//! flat for others. A calibration transforms an LLM's vote distribution
+//! before it is combined with the other votes using the profile weights.
+//!
+//! Calibrations can be fit from labeled data with
+//! [`Calibration::fit_temperature`], [`Calibration::fit_isotonic`], or
+//! [`fit_calibrations`] over a dataset of
+//! [`Target::VectorWinner`](crate::functions::profiles::computations::request::Target::VectorWinner)
+//! items.
+
+use crate::functions::profiles::computations::request::Target;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// A transform applied to an LLM's vote distribution before it is combined.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Calibration {
+    /// Temperature scaling: each probability is raised to the power of
+    /// `1 / temperature`, then the distribution is re-normalized.
+    ///
+    /// A temperature above 1 flattens an overconfident LLM's votes; below 1
+    /// sharpens an underconfident LLM's votes.
+    Temperature {
+        /// Must be greater than 0.
+        temperature: Decimal,
+    },
+    /// Isotonic mapping: each probability is mapped through a monotonic
+    /// piecewise-linear function, then the distribution is re-normalized.
+    Isotonic {
+        /// The points of the function, sorted by `x` with non-decreasing `y`.
+        /// Probabilities outside of the points are mapped to the nearest
+        /// point's `y`.
+        points: Vec<CalibrationPoint>,
+    },
+}
+
+/// A point of an isotonic calibration function.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CalibrationPoint {
+    /// The vote probability, in [0, 1].
+    pub x: Decimal,
+    /// The calibrated probability, in [0, 1].
+    pub y: Decimal,
+}
+
+/// How to fit a calibration with [`fit_calibrations`].
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationMethod {
+    /// Fit a [`Calibration::Temperature`].
+    #[default]
+    Temperature,
+    /// Fit a [`Calibration::Isotonic`].
+    Isotonic,
+}
+
+/// A vote distribution and the index of the response which should have won.
+#[derive(Debug, Clone)]
+pub struct CalibrationSample {
+    /// The vote distribution.
+    pub vote: Vec<Decimal>,
+    /// The index of the correct response.
+    pub winner: usize,
+}
+
+/// Bounds of the temperature search in [`Calibration::fit_temperature`].
+const MIN_TEMPERATURE: f64 = 0.05;
+const MAX_TEMPERATURE: f64 = 20.0;
+
+/// Probabilities are clamped to at least this when computing likelihoods.
+const EPSILON: f64 = 1e-9;
+
+impl Calibration {
+    /// Validates the calibration.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Calibration::Temperature { temperature } => {
+                if *temperature <= Decimal::ZERO {
+                    return Err(
+                        "calibration `temperature` must be greater than 0"
+                            .to_string(),
+                    );
+                }
+            }
+            Calibration::Isotonic { points } => {
+                if points.is_empty() {
+                    return Err(
+                        "calibration `points` must not be empty".to_string()
+                    );
+                }
+                for point in points {
+                    if point.x < Decimal::ZERO
+                        || point.x > Decimal::ONE
+                        || point.y < Decimal::ZERO
+                        || point.y > Decimal::ONE
+                    {
+                        return Err(
+                            "calibration points must be between 0 and 1"
+                                .to_string(),
+                        );
+                    }
+                }
+                for pair in points.windows(2) {
+                    if pair[1].x <= pair[0].x {
+                        return Err(
+                            "calibration points must be sorted by strictly increasing `x`"
+                                .to_string(),
+                        );
+                    }
+                    if pair[1].y < pair[0].y {
+                        return Err(
+                            "calibration points must have non-decreasing `y`"
+                                .to_string(),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the calibration to a vote distribution, returning a
+    /// distribution which sums to 1.
+    ///
+    /// If every calibrated probability is 0, the vote is returned unchanged.
+    pub fn apply(&self, vote: &[Decimal]) -> Vec<Decimal> {
+        let calibrated = self.apply_f64(&to_f64s(vote));
+        if calibrated.iter().sum::<f64>() <= 0.0 {
+            return vote.to_vec();
+        }
+        calibrated.into_iter().map(decimal).collect()
+    }
+
+    /// Fits a temperature which maximizes the likelihood of the winners.
+    ///
+    /// Returns a temperature of 1 if there are no samples.
+    pub fn fit_temperature(samples: &[CalibrationSample]) -> Self {
+        let samples = samples
+            .iter()
+            .map(|sample| (to_f64s(&sample.vote), sample.winner))
+            .filter(|(vote, winner)| *winner < vote.len())
+            .collect::<Vec<_>>();
+        if samples.is_empty() {
+            return Calibration::Temperature {
+                temperature: Decimal::ONE,
+            };
+        }
+        let negative_log_likelihood = |log_temperature: f64| {
+            let inverse = (-log_temperature).exp();
+            samples
+                .iter()
+                .map(|(vote, winner)| {
+                    let scaled = vote
+                        .iter()
+                        .map(|p| p.max(EPSILON).powf(inverse))
+                        .collect::<Vec<_>>();
+                    let sum: f64 = scaled.iter().sum();
+                    -(scaled[*winner] / sum).ln()
+                })
+                .sum::<f64>()
+        };
+
+        // golden-section search over the log temperature
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut low, mut high) = (MIN_TEMPERATURE.ln(), MAX_TEMPERATURE.ln());
+        let mut a = high - ratio * (high - low);
+        let mut b = low + ratio * (high - low);
+        let (mut fa, mut fb) =
+            (negative_log_likelihood(a), negative_log_likelihood(b));
+        for _ in 0..100 {
+            if fa < fb {
+                high = b;
+                b = a;
+                fb = fa;
+                a = high - ratio * (high - low);
+                fa = negative_log_likelihood(a);
+            } else {
+                low = a;
+                a = b;
+                fa = fb;
+                b = low + ratio * (high - low);
+                fb = negative_log_likelihood(b);
+            }
+        }
+        Calibration::Temperature {
+            temperature: decimal(((low + high) / 2.0).exp()).round_dp(6),
+        }
+    }
+
+    /// Fits an isotonic mapping from each vote probability to the frequency
+    /// with which the response it was given to was the winner, using the
+    /// pool-adjacent-violators algorithm.
+    ///
+    /// Returns the identity mapping if there are no samples.
+    pub fn fit_isotonic(samples: &[CalibrationSample]) -> Self {
+        let mut observations = samples
+            .iter()
+            .filter(|sample| sample.winner < sample.vote.len())
+            .flat_map(|sample| {
+                sample.vote.iter().enumerate().map(|(i, p)| {
+                    (
+                        p.to_f64().unwrap_or(0.0).clamp(0.0, 1.0),
+                        if i == sample.winner { 1.0 } else { 0.0 },
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if observations.is_empty() {
+            return Calibration::Isotonic {
+                points: vec![
+                    CalibrationPoint {
+                        x: Decimal::ZERO,
+                        y: Decimal::ZERO,
+                    },
+                    CalibrationPoint {
+                        x: Decimal::ONE,
+                        y: Decimal::ONE,
+                    },
+                ],
+            };
+        }
+        observations.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // blocks of (sum of x, sum of y, count), merged while y decreases
+        let mut blocks: Vec<(f64, f64, f64)> = Vec::new();
+        for (x, y) in observations {
+            match blocks.last_mut() {
+                // observations with equal x always share a block
+                Some(last) if last.0 / last.2 == x => {
+                    last.0 += x;
+                    last.1 += y;
+                    last.2 += 1.0;
+                }
+                _ => blocks.push((x, y, 1.0)),
+            }
+            while blocks.len() > 1 {
+                let last = blocks[blocks.len() - 1];
+                let previous = blocks[blocks.len() - 2];
+                if previous.1 / previous.2 <= last.1 / last.2 {
+                    break;
+                }
+                blocks.pop();
+                let merged = blocks.last_mut().unwrap();
+                merged.0 += last.0;
+                merged.1 += last.1;
+                merged.2 += last.2;
+            }
+        }
+
+        let mut points: Vec<CalibrationPoint> =
+            Vec::with_capacity(blocks.len());
+        for (x, y, count) in blocks {
+            let point = CalibrationPoint {
+                x: decimal(x / count).round_dp(6),
+                y: decimal(y / count).round_dp(6),
+            };
+            // rounding may collapse neighbouring blocks
+            match points.last_mut() {
+                Some(last) if last.x >= point.x => last.y = point.y,
+                _ => points.push(point),
+            }
+        }
+        Calibration::Isotonic { points }
+    }
+
+    fn apply_f64(&self, vote: &[f64]) -> Vec<f64> {
+        let mut calibrated = match self {
+            Calibration::Temperature { temperature } => {
+                let inverse = 1.0 / temperature.to_f64().unwrap_or(1.0);
+                vote.iter()
+                    .map(|p| if *p > 0.0 { p.powf(inverse) } else { 0.0 })
+                    .collect::<Vec<_>>()
+            }
+            Calibration::Isotonic { points } => {
+                let points = points
+                    .iter()
+                    .map(|point| {
+                        (
+                            point.x.to_f64().unwrap_or(0.0),
+                            point.y.to_f64().unwrap_or(0.0),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                vote.iter().map(|p| interpolate(&points, *p)).collect()
+            }
+        };
+        let sum: f64 = calibrated.iter().sum();
+        if sum > 0.0 {
+            for p in &mut calibrated {
+                *p /= sum;
+            }
+        }
+        calibrated
+    }
+}
+
+/// Fits a calibration for each LLM of an Ensemble from the votes of vector
+/// completions over a labeled dataset.
+///
+/// `items` pairs the votes of each completion with its target. Only
+/// [`Target::VectorWinner`] items are used. Votes are grouped by their
+/// `ensemble_index`, and RNG-generated votes are ignored. The result has
+/// one entry per LLM of the Ensemble, `None` for LLMs with no votes.
+pub fn fit_calibrations<'a>(
+    items: impl IntoIterator<
+        Item = (&'a [crate::vector::completions::response::Vote], &'a Target),
+    >,
+    llms_len: usize,
+    method: CalibrationMethod,
+) -> Vec<Option<Calibration>> {
+    let mut samples = vec![Vec::new(); llms_len];
+    for (votes, target) in items {
+        let Target::VectorWinner { value: winner } = target else {
+            continue;
+        };
+        for vote in votes {
+            if vote.from_rng == Some(true) {
+                continue;
+            }
+            if let Some(samples) = samples.get_mut(vote.ensemble_index as usize)
+            {
+                samples.push(CalibrationSample {
+                    vote: vote.vote.clone(),
+                    winner: *winner,
+                });
+            }
+        }
+    }
+    samples
+        .into_iter()
+        .map(|samples| {
+            if samples.is_empty() {
+                None
+            } else {
+                Some(match method {
+                    CalibrationMethod::Temperature => {
+                        Calibration::fit_temperature(&samples)
+                    }
+                    CalibrationMethod::Isotonic => {
+                        Calibration::fit_isotonic(&samples)
+                    }
+                })
+            }
+        })
+        .collect()
+}
+
+/// Maps `x` through the piecewise-linear function defined by `points`.
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    let Some(first) = points.first() else {
+        return x;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    for pair in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        if x <= x1 {
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+    }
+    points[points.len() - 1].1
+}
+
+fn to_f64s(vote: &[Decimal]) -> Vec<f64> {
+    vote.iter()
+        .map(|v| v.to_f64().unwrap_or(0.0).max(0.0))
+        .collect()
+}
+
+fn decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::dec;
+
+    fn sample(vote: Vec<Decimal>, winner: usize) -> CalibrationSample {
+        CalibrationSample { vote, winner }
+    }
+
+    #[test]
+    fn temperature_of_one_is_identity() {
+        let calibration = Calibration::Temperature {
+            temperature: dec!(1),
+        };
+        let vote = calibration.apply(&[dec!(0.75), dec!(0.25)]);
+        assert!((vote[0] - dec!(0.75)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn high_temperature_flattens() {
+        let calibration = Calibration::Temperature {
+            temperature: dec!(2),
+        };
+        let vote = calibration.apply(&[dec!(0.9), dec!(0.1)]);
+        assert!(vote[0] < dec!(0.9) && vote[0] > dec!(0.5));
+        let sum: Decimal = vote.iter().sum();
+        assert!((sum - Decimal::ONE).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn isotonic_interpolates_and_normalizes() {
+        let calibration = Calibration::Isotonic {
+            points: vec![
+                CalibrationPoint {
+                    x: dec!(0),
+                    y: dec!(0.2),
+                },
+                CalibrationPoint {
+                    x: dec!(1),
+                    y: dec!(0.6),
+                },
+            ],
+        };
+        // 0.5 -> 0.4, 0.5 -> 0.4, normalized to 0.5 each
+        let vote = calibration.apply(&[dec!(0.5), dec!(0.5)]);
+        assert_eq!(vote, vec![dec!(0.5), dec!(0.5)]);
+        // 1 -> 0.6, 0 -> 0.2
+        let vote = calibration.apply(&[dec!(1), dec!(0)]);
+        assert!((vote[0] - dec!(0.75)).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn validate_rejects_bad_calibrations() {
+        assert!(
+            Calibration::Temperature {
+                temperature: dec!(0)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            Calibration::Isotonic {
+                points: vec![
+                    CalibrationPoint {
+                        x: dec!(0.5),
+                        y: dec!(0.5),
+                    },
+                    CalibrationPoint {
+                        x: dec!(0.4),
+                        y: dec!(0.6),
+                    },
+                ],
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn fit_temperature_flattens_overconfident_votes() {
+        // confident 0.9 votes which are right only 60% of the time
+        let mut samples = Vec::new();
+        for i in 0..10 {
+            samples.push(sample(vec![dec!(0.9), dec!(0.1)], (i >= 6) as usize));
+        }
+        let Calibration::Temperature { temperature } =
+            Calibration::fit_temperature(&samples)
+        else {
+            panic!("expected temperature calibration");
+        };
+        assert!(temperature > dec!(1));
+        // the fit maps 0.9 close to the observed 0.6
+        let vote = Calibration::Temperature { temperature }
+            .apply(&[dec!(0.9), dec!(0.1)]);
+        assert!((vote[0] - dec!(0.6)).abs() < dec!(0.01));
+    }
+
+    #[test]
+    fn fit_isotonic_is_monotonic_and_valid() {
+        let samples = vec![
+            sample(vec![dec!(0.9), dec!(0.1)], 0),
+            sample(vec![dec!(0.7), dec!(0.3)], 1),
+            sample(vec![dec!(0.6), dec!(0.4)], 0),
+            sample(vec![dec!(0.8), dec!(0.2)], 0),
+        ];
+        let calibration = Calibration::fit_isotonic(&samples);
+        calibration.validate().unwrap();
+        let Calibration::Isotonic { points } = calibration else {
+            panic!("expected isotonic calibration");
+        };
+        assert_eq!(points.first().unwrap().y, dec!(0));
+        assert_eq!(points.last().unwrap().y, dec!(1));
+    }
+
+    #[test]
+    fn fit_calibrations_groups_by_llm() {
+        use crate::vector::completions::response::Vote;
+        let vote = |ensemble_index: u64, from_rng: Option<bool>| Vote {
+            model: String::new(),
+            ensemble_index,
+            flat_ensemble_index: ensemble_index,
+            prompt_id: String::new(),
+            tools_id: None,
+            responses_ids: Vec::new(),
+            vote: vec![dec!(0.9), dec!(0.1)],
+            weight: dec!(1),
+            retry: None,
+            from_cache: None,
+            from_rng,
+            permutations: None,
+            samples: None,
+            comparisons: None,
+            rationale: None,
+            completion_index: None,
+        };
+        let votes = vec![vote(0, None), vote(2, Some(true))];
+        let winner = Target::VectorWinner { value: 0 };
+        let scalar = Target::Scalar { value: dec!(1) };
+        let calibrations = fit_calibrations(
+            [(votes.as_slice(), &winner), (votes.as_slice(), &scalar)],
+            3,
+            CalibrationMethod::Temperature,
+        );
+        assert!(calibrations[0].is_some());
+        assert!(calibrations[1].is_none());
+        assert!(calibrations[2].is_none());
+    }
+}
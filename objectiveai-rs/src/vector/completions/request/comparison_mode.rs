@@ -0,0 +1,45 @@
+//! How the LLMs of a vector completion compare the responses.
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of responses of a [`ComparisonMode::Pairwise`] request.
+pub const COMPARISON_MODE_PAIRWISE_MAX_RESPONSES: u64 = 16;
+
+/// How each LLM in the Ensemble compares the responses.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonMode {
+    /// Each LLM votes once, over all of the responses at once.
+    #[default]
+    All,
+    /// Each LLM votes once for every pair of responses, and its vote is the
+    /// Bradley-Terry fit of those pairwise votes.
+    ///
+    /// More reliable than voting over many responses at once when candidates
+    /// are close, at the cost of `n * (n - 1) / 2` completions per LLM. At
+    /// most [`COMPARISON_MODE_PAIRWISE_MAX_RESPONSES`] responses.
+    Pairwise,
+}
+
+impl ComparisonMode {
+    /// Validates the comparison mode for a request with `responses_len`
+    /// responses.
+    pub fn validate(&self, responses_len: usize) -> Result<(), String> {
+        match self {
+            ComparisonMode::All => Ok(()),
+            ComparisonMode::Pairwise
+                if responses_len as u64
+                    > COMPARISON_MODE_PAIRWISE_MAX_RESPONSES =>
+            {
+                Err(format!(
+                    "pairwise comparisons allow at most {} responses, got {}",
+                    COMPARISON_MODE_PAIRWISE_MAX_RESPONSES, responses_len
+                ))
+            }
+            ComparisonMode::Pairwise => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Adaptive early stopping of ensemble voting.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Default number of LLMs run in each wave.
+pub const EARLY_STOPPING_WAVE_SIZE: u64 = 4;
+
+/// Default confidence required to stop early.
+pub const EARLY_STOPPING_CONFIDENCE: Decimal =
+    Decimal::from_parts(95, 0, 0, false, 2);
+
+/// Default minimum number of votes before stopping early.
+pub const EARLY_STOPPING_MIN_VOTES: u64 = 4;
+
+/// Runs the LLMs of the Ensemble in waves, stopping once the outcome is
+/// decided.
+///
+/// LLMs are run in order of descending profile weight. After each wave, the
+/// remaining waves are skipped if either:
+/// - the leading response's weight exceeds the runner-up's by more than the
+///   total weight of the remaining LLMs, or
+/// - at least `min_votes` votes have been cast, and the weighted mean margin
+///   between the leading response and the runner-up across the votes is
+///   positive at the given `confidence`.
+///
+/// Skipped LLMs are never run, and are reported in the response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EarlyStopping {
+    /// Number of LLMs to run in each wave. Defaults to 4.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wave_size: Option<u64>,
+    /// Confidence (exclusive between 0.5 and 1) that the leading response
+    /// would remain ahead, required to stop early. Defaults to 0.95.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<Decimal>,
+    /// Minimum number of votes before stopping on confidence. Defaults to 4.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_votes: Option<u64>,
+}
+
+impl EarlyStopping {
+    /// Validates the configuration.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.wave_size == Some(0) {
+            return Err(
+                "`early_stopping.wave_size` must be at least 1".to_string()
+            );
+        }
+        if let Some(confidence) = self.confidence
+            && (confidence <= Decimal::new(5, 1) || confidence >= Decimal::ONE)
+        {
+            return Err(
+                "`early_stopping.confidence` must be between 0.5 and 1, exclusive"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the wave size, or the default.
+    pub fn wave_size(&self) -> u64 {
+        self.wave_size.unwrap_or(EARLY_STOPPING_WAVE_SIZE).max(1)
+    }
+
+    /// Returns the confidence, or the default.
+    pub fn confidence(&self) -> Decimal {
+        self.confidence.unwrap_or(EARLY_STOPPING_CONFIDENCE)
+    }
+
+    /// Returns the minimum number of votes, or the default.
+    pub fn min_votes(&self) -> u64 {
+        self.min_votes.unwrap_or(EARLY_STOPPING_MIN_VOTES)
+    }
+}
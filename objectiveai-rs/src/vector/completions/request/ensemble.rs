@@ -28,6 +28,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// [`EnsembleBase`]: crate::ensemble::EnsembleBase
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Ensemble {
     /// Reference an existing Ensemble by its ID.
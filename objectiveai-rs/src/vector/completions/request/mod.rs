@@ -2,11 +2,23 @@
 //!
 //! - [`VectorCompletionCreateParams`] - The main request structure
 //! - [`Ensemble`] - Ensemble specification for the request
+//! - [`ComparisonMode`] - How the LLMs compare the responses
+//! - [`Calibration`] - Per-LLM calibration of vote distributions
+//! - [`EarlyStopping`] - Adaptive early stopping of ensemble voting
+//! - [`RatingScale`] - Scalar rating of the prompt on a numeric scale
 
+mod calibration;
+mod comparison_mode;
+mod early_stopping;
 mod ensemble;
 mod profile;
+mod rating;
 mod vector_completion_create_params;
 
+pub use calibration::*;
+pub use comparison_mode::*;
+pub use early_stopping::*;
 pub use ensemble::*;
 pub use profile::*;
+pub use rating::*;
 pub use vector_completion_create_params::*;
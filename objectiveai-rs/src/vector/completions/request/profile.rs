@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 
 /// An entry in a profile with an explicit weight and optional invert flag.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ProfileEntry {
     /// The weight for this LLM in the ensemble. Must be in [0, 1].
     pub weight: Decimal,
@@ -23,6 +24,10 @@ pub struct ProfileEntry {
     /// When omitted or false, the vote distribution is used as-is.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invert: Option<bool>,
+    /// If present, calibrates this LLM's vote distribution before it is
+    /// inverted and combined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calibration: Option<super::Calibration>,
 }
 
 /// Profile weights for a vector completion.
@@ -33,6 +38,7 @@ pub struct ProfileEntry {
 /// - `Weights(Vec<Decimal>)` - legacy representation (no inversion)
 /// - `Entries(Vec<ProfileEntry>)` - weights with optional per-LLM `invert`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Profile {
     /// Simple vector of decimal weights.
@@ -69,5 +75,18 @@ impl Profile {
                 .collect(),
         }
     }
+
+    /// Returns the calibration of each LLM, if any.
+    ///
+    /// For the legacy `Weights` variant, all calibrations are `None`.
+    pub fn calibrations(&self) -> Vec<Option<super::Calibration>> {
+        match self {
+            Profile::Weights(weights) => vec![None; weights.len()],
+            Profile::Entries(entries) => entries
+                .iter()
+                .map(|entry| entry.calibration.clone())
+                .collect(),
+        }
+    }
 }
 
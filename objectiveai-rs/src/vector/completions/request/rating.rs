@@ -0,0 +1,126 @@
+//! Scalar rating of a prompt on a numeric scale.
+
+use crate::chat;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of values in a [`RatingScale`].
+pub const RATING_SCALE_MAX_LEN: u64 = 101;
+
+/// An inclusive integer scale (e.g. 1 to 10) which each LLM rates the prompt
+/// on, instead of voting for one of the request's responses.
+///
+/// The values of the scale become the responses. Each LLM's vote is then a
+/// distribution over the values, weighted by logprobs when available, and
+/// its rating is the expectation of that distribution. The combined rating
+/// is reported as a [`Rating`](crate::vector::completions::response::Rating).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RatingScale {
+    /// The lowest rating.
+    pub min: i64,
+    /// The highest rating. Must be greater than `min`.
+    pub max: i64,
+}
+
+impl RatingScale {
+    /// Validates the scale.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max <= self.min {
+            return Err(
+                "`rating.max` must be greater than `rating.min`".to_string()
+            );
+        }
+        if self.max.abs_diff(self.min) >= RATING_SCALE_MAX_LEN {
+            return Err(format!(
+                "`rating` must have at most {} values",
+                RATING_SCALE_MAX_LEN
+            ));
+        }
+        Ok(())
+    }
+
+    /// The number of values in the scale.
+    pub fn count(&self) -> usize {
+        self.max.abs_diff(self.min) as usize + 1
+    }
+
+    /// The values of the scale, in ascending order.
+    pub fn values(&self) -> impl Iterator<Item = i64> {
+        self.min..=self.max
+    }
+
+    /// The values of the scale as responses for the LLMs to vote for.
+    pub fn responses(&self) -> Vec<chat::completions::request::RichContent> {
+        self.values()
+            .map(|value| {
+                chat::completions::request::RichContent::Parts(vec![
+                    chat::completions::request::RichContentPart::Text {
+                        text: value.to_string(),
+                    },
+                ])
+            })
+            .collect()
+    }
+
+    /// The expected rating of a vote over the values of the scale.
+    pub fn expected(&self, vote: &[Decimal]) -> Decimal {
+        let sum: Decimal = vote.iter().sum();
+        if sum <= Decimal::ZERO {
+            return (Decimal::from(self.min) + Decimal::from(self.max))
+                / Decimal::TWO;
+        }
+        self.values()
+            .zip(vote)
+            .map(|(value, v)| Decimal::from(value) * *v)
+            .sum::<Decimal>()
+            / sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::dec;
+
+    #[test]
+    fn validate_rejects_empty_and_huge_scales() {
+        assert!(RatingScale { min: 1, max: 10 }.validate().is_ok());
+        assert!(RatingScale { min: 1, max: 1 }.validate().is_err());
+        assert!(RatingScale { min: 0, max: 101 }.validate().is_err());
+    }
+
+    #[test]
+    fn responses_are_values() {
+        let scale = RatingScale { min: -1, max: 1 };
+        assert_eq!(scale.count(), 3);
+        let responses = scale
+            .responses()
+            .into_iter()
+            .map(|response| match response {
+                chat::completions::request::RichContent::Parts(mut parts) => {
+                    match parts.pop() {
+                        Some(
+                            chat::completions::request::RichContentPart::Text {
+                                text,
+                            },
+                        ) => text,
+                        _ => unreachable!(),
+                    }
+                }
+                chat::completions::request::RichContent::Text(_) => {
+                    unreachable!()
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(responses, vec!["-1", "0", "1"]);
+    }
+
+    #[test]
+    fn expected_is_weighted_mean_of_values() {
+        let scale = RatingScale { min: 1, max: 5 };
+        let vote = [dec!(0), dec!(0), dec!(0.5), dec!(0.5), dec!(0)];
+        assert_eq!(scale.expected(&vote), dec!(3.5));
+        assert_eq!(scale.expected(&[]), dec!(3));
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 /// ensemble), force each to vote for one of the predefined responses, and
 /// combine votes using the provided profile weights to produce final scores.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionCreateParams {
     // --- Caching and retry options ---
 
@@ -47,8 +48,26 @@ pub struct VectorCompletionCreateParams {
     /// Tools available (read-only context, not callable in vector completions).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<chat::completions::request::Tool>>,
-    /// The possible responses the LLMs can vote for.
+    /// The possible responses the LLMs can vote for. Must be empty if
+    /// `rating` is set, as the values of the scale are the responses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub responses: Vec<chat::completions::request::RichContent>,
+    /// If present, each LLM rates the prompt on this scale instead of voting
+    /// for one of `responses`, and the response includes the combined rating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<super::RatingScale>,
+    /// How each LLM compares the responses. Defaults to voting over all
+    /// of them at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparison_mode: Option<super::ComparisonMode>,
+    /// If present, runs the LLMs in waves and stops once the outcome is
+    /// decided, skipping the remaining LLMs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub early_stopping: Option<super::EarlyStopping>,
+    /// If true, each vote carries the LLM's rationale: its native reasoning
+    /// and any synthetic reasoning it output before its response key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_rationales: Option<bool>,
 
     // --- Retry configuration ---
 
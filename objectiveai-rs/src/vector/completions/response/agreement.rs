@@ -0,0 +1,287 @@
+//! Ensemble agreement and uncertainty metrics computed from votes.
+
+use rand::Rng;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Default confidence level of [`Agreement::confidence_intervals`].
+pub const AGREEMENT_CONFIDENCE: f64 = 0.95;
+
+/// Default number of bootstrap resamples used for
+/// [`Agreement::confidence_intervals`].
+pub const AGREEMENT_RESAMPLES: usize = 1000;
+
+/// How much the LLMs of an ensemble agreed in a vector completion.
+///
+/// Computed from the votes of a vector completion. Votes with a weight of
+/// zero are ignored. Entropies and divergences are in bits.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Agreement {
+    /// Weighted variance of the votes for each response. Zero when every
+    /// LLM gave the response the same share of its vote.
+    pub variance: Vec<Decimal>,
+    /// Entropy of the combined vote distribution. Zero when all weight is on
+    /// one response; `log2(n)` when it is spread evenly across `n`.
+    pub entropy: Decimal,
+    /// Weighted Jensen-Shannon divergence between the votes: the entropy of
+    /// the combined distribution minus the weighted mean entropy of each
+    /// vote. Zero when every LLM voted identically; at most `log2(n)`.
+    pub jensen_shannon_divergence: Decimal,
+    /// Bootstrap confidence interval on each score, from resampling the
+    /// votes with replacement.
+    pub confidence_intervals: Vec<ConfidenceInterval>,
+}
+
+/// A confidence interval on a score.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConfidenceInterval {
+    /// Lower bound of the interval.
+    pub lower: Decimal,
+    /// Upper bound of the interval.
+    pub upper: Decimal,
+}
+
+impl Agreement {
+    /// Computes agreement metrics from votes, with a 95% confidence interval
+    /// on each score from 1000 bootstrap resamples.
+    pub fn from_votes(votes: &[super::Vote]) -> Self {
+        Self::from_votes_with(
+            votes,
+            AGREEMENT_CONFIDENCE,
+            AGREEMENT_RESAMPLES,
+            &mut rand::rng(),
+        )
+    }
+
+    /// Computes agreement metrics from votes, with confidence intervals at
+    /// the given `confidence` level (e.g. `0.95`) from `resamples` bootstrap
+    /// resamples drawn with `rng`.
+    pub fn from_votes_with(
+        votes: &[super::Vote],
+        confidence: f64,
+        resamples: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let votes: Vec<(Vec<f64>, f64)> = votes
+            .iter()
+            .filter(|vote| vote.weight > Decimal::ZERO)
+            .map(|vote| {
+                (
+                    vote.vote
+                        .iter()
+                        .map(|v| v.to_f64().unwrap_or(0.0))
+                        .collect(),
+                    vote.weight.to_f64().unwrap_or(0.0),
+                )
+            })
+            .collect();
+        let Some(len) = votes.iter().map(|(vote, _)| vote.len()).max() else {
+            return Self::default();
+        };
+
+        let combined =
+            mixture(votes.iter().map(|(v, w)| (v.as_slice(), *w)), len);
+        let total_weight: f64 = votes.iter().map(|(_, w)| w).sum();
+
+        // weighted variance of each response's share of the votes
+        let variance = (0..len)
+            .map(|j| {
+                votes
+                    .iter()
+                    .map(|(vote, weight)| {
+                        let d =
+                            vote.get(j).copied().unwrap_or(0.0) - combined[j];
+                        weight * d * d
+                    })
+                    .sum::<f64>()
+                    / total_weight
+            })
+            .collect::<Vec<_>>();
+
+        // entropy of the combined distribution, and of each vote
+        let entropy = entropy(&combined);
+        let mean_vote_entropy = votes
+            .iter()
+            .map(|(vote, weight)| weight * self::entropy(vote))
+            .sum::<f64>()
+            / total_weight;
+
+        // percentile bootstrap over resampled votes
+        let mut samples = vec![Vec::with_capacity(resamples); len];
+        for _ in 0..resamples {
+            let resampled = (0..votes.len())
+                .map(|_| &votes[rng.random_range(0..votes.len())])
+                .map(|(v, w)| (v.as_slice(), *w));
+            for (j, score) in mixture(resampled, len).into_iter().enumerate() {
+                samples[j].push(score);
+            }
+        }
+        let alpha = (1.0 - confidence.clamp(0.0, 1.0)) / 2.0;
+        let confidence_intervals = samples
+            .into_iter()
+            .zip(&combined)
+            .map(|(mut samples, score)| {
+                if samples.is_empty() {
+                    return ConfidenceInterval {
+                        lower: decimal(*score),
+                        upper: decimal(*score),
+                    };
+                }
+                samples.sort_by(f64::total_cmp);
+                ConfidenceInterval {
+                    lower: decimal(percentile(&samples, alpha)),
+                    upper: decimal(percentile(&samples, 1.0 - alpha)),
+                }
+            })
+            .collect();
+
+        Self {
+            variance: variance.into_iter().map(decimal).collect(),
+            entropy: decimal(entropy),
+            jensen_shannon_divergence: decimal(
+                (entropy - mean_vote_entropy).max(0.0),
+            ),
+            confidence_intervals,
+        }
+    }
+}
+
+/// The weighted mean of votes, normalized to sum to 1.
+fn mixture<'a>(
+    votes: impl Iterator<Item = (&'a [f64], f64)>,
+    len: usize,
+) -> Vec<f64> {
+    let mut combined = vec![0.0; len];
+    for (vote, weight) in votes {
+        for (c, v) in combined.iter_mut().zip(vote) {
+            *c += weight * v;
+        }
+    }
+    let sum: f64 = combined.iter().sum();
+    if sum > 0.0 {
+        for c in &mut combined {
+            *c /= sum;
+        }
+    }
+    combined
+}
+
+/// Shannon entropy of a distribution, in bits.
+fn entropy(distribution: &[f64]) -> f64 {
+    let sum: f64 = distribution.iter().sum();
+    if sum <= 0.0 {
+        return 0.0;
+    }
+    distribution
+        .iter()
+        .map(|p| p / sum)
+        .filter(|p| *p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+/// The value at quantile `q` of sorted samples, interpolating linearly.
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+fn decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::completions::response::Vote;
+    use rand::SeedableRng;
+    use rust_decimal::dec;
+
+    fn make_vote(vote: Vec<Decimal>, weight: Decimal) -> Vote {
+        Vote {
+            model: "openai/gpt-4o".to_string(),
+            ensemble_index: 0,
+            flat_ensemble_index: 0,
+            prompt_id: String::new(),
+            tools_id: None,
+            responses_ids: Vec::new(),
+            vote,
+            weight,
+            retry: None,
+            from_cache: None,
+            from_rng: None,
+            permutations: None,
+            samples: None,
+            comparisons: None,
+            rationale: None,
+            completion_index: None,
+        }
+    }
+
+    fn agreement(votes: &[Vote]) -> Agreement {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        Agreement::from_votes_with(votes, 0.95, 200, &mut rng)
+    }
+
+    #[test]
+    fn unanimous_votes_agree() {
+        let votes = vec![
+            make_vote(vec![dec!(1), dec!(0)], dec!(1)),
+            make_vote(vec![dec!(1), dec!(0)], dec!(2)),
+        ];
+        let agreement = agreement(&votes);
+        assert_eq!(agreement.variance, vec![dec!(0), dec!(0)]);
+        assert_eq!(agreement.entropy, dec!(0));
+        assert_eq!(agreement.jensen_shannon_divergence, dec!(0));
+        assert_eq!(
+            agreement.confidence_intervals[0],
+            ConfidenceInterval {
+                lower: dec!(1),
+                upper: dec!(1),
+            },
+        );
+    }
+
+    #[test]
+    fn split_votes_disagree() {
+        let votes = vec![
+            make_vote(vec![dec!(1), dec!(0)], dec!(1)),
+            make_vote(vec![dec!(0), dec!(1)], dec!(1)),
+        ];
+        let agreement = agreement(&votes);
+        assert_eq!(agreement.variance, vec![dec!(0.25), dec!(0.25)]);
+        assert_eq!(agreement.entropy, dec!(1));
+        assert_eq!(agreement.jensen_shannon_divergence, dec!(1));
+        let interval = agreement.confidence_intervals[0];
+        assert_eq!(interval.lower, dec!(0));
+        assert_eq!(interval.upper, dec!(1));
+    }
+
+    #[test]
+    fn identical_probabilistic_votes_have_entropy_but_no_divergence() {
+        let votes = vec![
+            make_vote(vec![dec!(0.5), dec!(0.5)], dec!(1)),
+            make_vote(vec![dec!(0.5), dec!(0.5)], dec!(1)),
+        ];
+        let agreement = agreement(&votes);
+        assert_eq!(agreement.entropy, dec!(1));
+        assert_eq!(agreement.jensen_shannon_divergence, dec!(0));
+    }
+
+    #[test]
+    fn zero_weight_votes_are_ignored() {
+        let votes = vec![
+            make_vote(vec![dec!(1), dec!(0)], dec!(1)),
+            make_vote(vec![dec!(0), dec!(1)], dec!(0)),
+        ];
+        let agreement = agreement(&votes);
+        assert_eq!(agreement.entropy, dec!(0));
+        assert!(Agreement::from_votes(&[]).variance.is_empty());
+    }
+}
@@ -3,12 +3,18 @@
 //! - [`unary`] - Complete (non-streaming) responses
 //! - [`streaming`] - Incremental chunk-based responses
 //! - [`Vote`] - Individual LLM vote data
+//! - [`Agreement`] - Ensemble agreement and uncertainty metrics over votes
+//! - [`Rating`] - The combined rating over a rating scale
 //! - [`Usage`] - Aggregated token and cost statistics
 
+mod agreement;
+mod rating;
 pub mod streaming;
 pub mod unary;
 mod usage;
 mod vote;
 
+pub use agreement::*;
+pub use rating::*;
 pub use usage::*;
 pub use vote::*;
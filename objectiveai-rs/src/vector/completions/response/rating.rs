@@ -0,0 +1,90 @@
+//! The combined rating of a vector completion over a rating scale.
+
+use crate::functions::expression::ToStarlarkValue;
+use crate::vector::completions::request::RatingScale;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use starlark::values::dict::AllocDict as StarlarkAllocDict;
+use starlark::values::{Heap as StarlarkHeap, Value as StarlarkValue};
+
+/// The ensemble's rating of the prompt, when the request has a
+/// [`RatingScale`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Rating {
+    /// The weighted mean rating: the expectation of the scores over the
+    /// values of the scale.
+    pub mean: Decimal,
+    /// The standard deviation of the scores over the values of the scale.
+    /// Includes both each LLM's own uncertainty and disagreement between
+    /// the LLMs.
+    pub std_dev: Decimal,
+}
+
+impl Rating {
+    /// Computes the rating from the scores of a vector completion, one for
+    /// each value of the scale.
+    pub fn from_scores(scale: &RatingScale, scores: &[Decimal]) -> Self {
+        let mean = scale.expected(scores);
+        let sum: Decimal = scores.iter().sum();
+        if sum <= Decimal::ZERO {
+            return Self {
+                mean,
+                std_dev: Decimal::ZERO,
+            };
+        }
+        let variance = scale
+            .values()
+            .zip(scores)
+            .map(|(value, score)| {
+                let d = Decimal::from(value) - mean;
+                d * d * *score
+            })
+            .sum::<Decimal>()
+            / sum;
+        let std_dev = variance
+            .to_f64()
+            .map(f64::sqrt)
+            .and_then(Decimal::from_f64)
+            .unwrap_or_default();
+        Self { mean, std_dev }
+    }
+}
+
+impl ToStarlarkValue for Rating {
+    fn to_starlark_value<'v>(
+        &self,
+        heap: &'v StarlarkHeap,
+    ) -> StarlarkValue<'v> {
+        heap.alloc(StarlarkAllocDict([
+            ("mean", self.mean.to_starlark_value(heap)),
+            ("std_dev", self.std_dev.to_starlark_value(heap)),
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::dec;
+
+    #[test]
+    fn unanimous_rating_has_no_spread() {
+        let scale = RatingScale { min: 1, max: 3 };
+        let rating = Rating::from_scores(&scale, &[dec!(0), dec!(1), dec!(0)]);
+        assert_eq!(rating.mean, dec!(2));
+        assert_eq!(rating.std_dev, dec!(0));
+    }
+
+    #[test]
+    fn split_rating_has_spread() {
+        let scale = RatingScale { min: 1, max: 10 };
+        let mut scores = vec![dec!(0); 10];
+        scores[1] = dec!(0.5);
+        scores[5] = dec!(0.5);
+        let rating = Rating::from_scores(&scale, &scores);
+        assert_eq!(rating.mean, dec!(4));
+        assert_eq!(rating.std_dev, dec!(2));
+    }
+}
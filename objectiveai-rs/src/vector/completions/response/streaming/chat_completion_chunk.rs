@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// The `index` field is used to correlate chunks belonging to the same
 /// underlying completion when accumulating via [`push`](Self::push).
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletionChunk {
     /// Index used to correlate chunks from the same completion.
     pub index: u64,
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Serializes to `"vector.completion.chunk"` in JSON.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Object {
     /// A streaming vector completion chunk.
     #[serde(rename = "vector.completion.chunk")]
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Each chunk contains incremental updates to the completion. Use the
 /// [`push`](Self::push) method to accumulate chunks into a complete response.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletionChunk {
     /// Unique identifier for this vector completion.
     pub id: String,
@@ -28,6 +29,14 @@ pub struct VectorCompletionChunk {
     /// Aggregated usage statistics. Typically present only in the final chunk.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<response::Usage>,
+    /// Flat ensemble indices of the LLMs which were never run because early
+    /// stopping decided the outcome first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<Vec<u64>>,
+    /// The combined rating, if the request has a rating scale. Present only
+    /// in the final chunk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<response::Rating>,
 }
 
 impl VectorCompletionChunk {
@@ -52,12 +61,14 @@ impl VectorCompletionChunk {
             ensemble: String::new(),
             object: super::Object::default(),
             usage: None,
+            skipped: None,
+            rating: None,
         }
     }
 
     /// Accumulates another chunk into this one.
     ///
-    /// Updates scores, weights, and usage, appends new votes.
+    /// Updates scores, weights, usage, and rating, appends new votes.
     pub fn push(
         &mut self,
         VectorCompletionChunk {
@@ -66,6 +77,8 @@ impl VectorCompletionChunk {
             scores,
             weights,
             usage,
+            skipped,
+            rating,
             ..
         }: &VectorCompletionChunk,
     ) {
@@ -82,6 +95,14 @@ impl VectorCompletionChunk {
             }
             _ => {}
         }
+        if let Some(skipped) = skipped {
+            self.skipped
+                .get_or_insert_with(Vec::new)
+                .extend_from_slice(skipped);
+        }
+        if let Some(rating) = rating {
+            self.rating = Some(*rating);
+        }
     }
 
     fn push_completions(
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 /// which LLM in the ensemble produced it, and an optional error if the
 /// completion failed.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChatCompletion {
     /// Index of this completion within the vector completion.
     pub index: u64,
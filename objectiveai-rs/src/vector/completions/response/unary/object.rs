@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Serializes to `"vector.completion"` in JSON.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Object {
     /// A complete vector completion response.
     #[serde(rename = "vector.completion")]
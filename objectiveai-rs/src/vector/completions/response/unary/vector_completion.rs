@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Contains the final scores, all votes from the ensemble, and the underlying
 /// chat completions that produced those votes.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct VectorCompletion {
     /// Unique identifier for this vector completion.
     pub id: String,
@@ -29,6 +30,13 @@ pub struct VectorCompletion {
     pub object: super::Object,
     /// Aggregated token and cost usage across all completions.
     pub usage: response::Usage,
+    /// Flat ensemble indices of the LLMs which were never run because early
+    /// stopping decided the outcome first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<Vec<u64>>,
+    /// The combined rating, if the request has a rating scale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<response::Rating>,
 }
 
 impl VectorCompletion {
@@ -53,8 +61,17 @@ impl VectorCompletion {
             ensemble: String::new(),
             object: super::Object::default(),
             usage: response::Usage::default(),
+            skipped: None,
+            rating: None,
         }
     }
+
+    /// Computes how much the ensemble agreed, from the votes.
+    ///
+    /// See [`response::Agreement`].
+    pub fn agreement(&self) -> response::Agreement {
+        response::Agreement::from_votes(&self.votes)
+    }
 }
 
 impl From<response::streaming::VectorCompletionChunk> for VectorCompletion {
@@ -69,6 +86,8 @@ impl From<response::streaming::VectorCompletionChunk> for VectorCompletion {
             ensemble,
             object,
             usage,
+            skipped,
+            rating,
         }: response::streaming::VectorCompletionChunk,
     ) -> Self {
         Self {
@@ -84,6 +103,8 @@ impl From<response::streaming::VectorCompletionChunk> for VectorCompletion {
             ensemble,
             object: object.into(),
             usage: usage.unwrap_or_default(),
+            skipped,
+            rating,
         }
     }
 }
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Since vector completions run multiple chat completions (one per LLM in the
 /// ensemble), this struct aggregates usage across all underlying completions.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Usage {
     /// Total tokens generated across all completions.
     pub completion_tokens: u64,
@@ -18,6 +18,7 @@ use starlark::values::{Heap as StarlarkHeap, Value as StarlarkValue};
 /// selection), but when `top_logprobs` is used, votes may be probability
 /// distributions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Vote {
     // --- Identifiers ---
 
@@ -59,6 +60,31 @@ pub struct Vote {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub from_rng: Option<bool>,
 
+    /// The orderings and labels the responses were presented with, one per
+    /// permutation, when the LLM voted over multiple permutations. The vote
+    /// is the mean of the votes from each permutation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permutations: Option<Vec<VotePermutation>>,
+
+    /// The number of completions the vote was drawn from, when the LLM
+    /// sampled multiple completions. The vote is the mean of their votes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<u64>,
+
+    /// The votes the LLM cast over each comparison of a subset of the
+    /// responses, when the request used pairwise comparisons. The vote is
+    /// the Bradley-Terry fit of these votes, after which the LLM's
+    /// calibration and inversion apply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparisons: Option<Vec<VoteComparison>>,
+
+    /// Why the LLM voted as it did: its native reasoning and any synthetic
+    /// reasoning it output before its response key. Present only if the
+    /// request set `include_rationales`. For votes over multiple
+    /// permutations or samples, the rationales of each, in order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+
     // --- Internal ---
 
     /// Internal index for correlating with completions. Not serialized.
@@ -80,6 +106,71 @@ impl ToStarlarkValue for Vote {
             ("retry", self.retry.to_starlark_value(heap)),
             ("from_cache", self.from_cache.to_starlark_value(heap)),
             ("from_rng", self.from_rng.to_starlark_value(heap)),
+            ("permutations", self.permutations.to_starlark_value(heap)),
+            ("samples", self.samples.to_starlark_value(heap)),
+            ("comparisons", self.comparisons.to_starlark_value(heap)),
+            ("rationale", self.rationale.to_starlark_value(heap)),
+        ]))
+    }
+}
+
+/// One ordering and labeling of the responses presented to an LLM.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VotePermutation {
+    /// The index of the response presented at each position.
+    pub order: Vec<u64>,
+    /// The key labeling the response at each position (e.g. `` `A` ``).
+    pub keys: Vec<String>,
+}
+
+impl VotePermutation {
+    /// Creates a permutation from the (key, response index) pairs in the
+    /// order they were presented.
+    pub fn from_pfx_indices(pfx_indices: &[(String, usize)]) -> Self {
+        Self {
+            order: pfx_indices.iter().map(|(_, i)| *i as u64).collect(),
+            keys: pfx_indices.iter().map(|(key, _)| key.clone()).collect(),
+        }
+    }
+}
+
+impl ToStarlarkValue for VotePermutation {
+    fn to_starlark_value<'v>(&self, heap: &'v StarlarkHeap) -> StarlarkValue<'v> {
+        heap.alloc(StarlarkAllocDict([
+            ("order", self.order.to_starlark_value(heap)),
+            ("keys", self.keys.to_starlark_value(heap)),
+        ]))
+    }
+}
+
+/// An LLM's vote over one comparison of a subset of the responses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VoteComparison {
+    /// The indices of the compared responses.
+    pub responses: Vec<u64>,
+    /// The vote distribution over the compared responses, in the same order
+    /// as `responses`.
+    pub vote: Vec<rust_decimal::Decimal>,
+    /// The orderings and labels the compared responses were presented with,
+    /// when the LLM voted over multiple permutations. Orders are indices of
+    /// the request's responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permutations: Option<Vec<VotePermutation>>,
+    /// Why the LLM voted as it did over this comparison, if the request set
+    /// `include_rationales`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+}
+
+impl ToStarlarkValue for VoteComparison {
+    fn to_starlark_value<'v>(&self, heap: &'v StarlarkHeap) -> StarlarkValue<'v> {
+        heap.alloc(StarlarkAllocDict([
+            ("responses", self.responses.to_starlark_value(heap)),
+            ("vote", self.vote.to_starlark_value(heap)),
+            ("permutations", self.permutations.to_starlark_value(heap)),
+            ("rationale", self.rationale.to_starlark_value(heap)),
         ]))
     }
 }
@@ -128,6 +128,38 @@ export interface Vote {
   from_cache?: boolean;
   /** Whether this vote was generated via RNG (simulated) */
   from_rng?: boolean;
+  /** Response orderings and keys, one per permutation voted over */
+  permutations?: VotePermutation[];
+  /** Number of completions sampled for this vote, when sampled */
+  samples?: number;
+  /** Votes over each pairwise comparison, when compared pairwise */
+  comparisons?: VoteComparison[];
+  /** Why the LLM voted as it did, when rationales were requested */
+  rationale?: string;
+}
+
+/**
+ * An LLM's vote over one comparison of a subset of the responses.
+ */
+export interface VoteComparison {
+  /** Indices of the compared responses */
+  responses: number[];
+  /** Vote distribution over the compared responses */
+  vote: number[];
+  /** Response orderings and keys, one per permutation voted over */
+  permutations?: VotePermutation[];
+  /** Why the LLM voted as it did, when rationales were requested */
+  rationale?: string;
+}
+
+/**
+ * One ordering and labeling of the responses presented to an LLM.
+ */
+export interface VotePermutation {
+  /** Index of the response presented at each position */
+  order: number[];
+  /** Key labeling the response at each position */
+  keys: string[];
 }
 
 // ============================================================================
@@ -301,6 +333,26 @@ export interface VectorCompletion {
   ensemble: Ensemble;
   object: "vector.completion";
   usage: Usage;
+  /** Combined rating, present if the request had a rating scale */
+  rating?: Rating;
+}
+
+/**
+ * An inclusive integer scale which each LLM rates the prompt on.
+ */
+export interface RatingScale {
+  min: number;
+  max: number;
+}
+
+/**
+ * The ensemble's rating of the prompt over a rating scale.
+ */
+export interface Rating {
+  /** Weighted mean rating */
+  mean: number;
+  /** Spread of the ratings, including disagreement between LLMs */
+  std_dev: number;
 }
 
 /**
@@ -339,6 +391,26 @@ export interface ProviderConfig {
   quantizations?: string[];
 }
 
+/**
+ * How an Ensemble LLM's response keys are labeled.
+ */
+export interface Labels {
+  alphabet?: "letters" | "digits" | "letter_pairs" | null;
+  wrap?: "backtick" | "bracket" | "parenthesis" | null;
+  /** Maximum labels per level (2-20) */
+  width?: number | null;
+}
+
+/**
+ * Overrides the wording of an Ensemble LLM's voting prompt.
+ * Placeholders: {responses}, {keys}, {wrap}, {min}, {max}.
+ */
+export interface PromptTemplate {
+  select?: string | null;
+  select_rating?: string | null;
+  instruction?: string | null;
+}
+
 /**
  * An Ensemble LLM configuration.
  * Fully specifies how to use a single upstream LLM.
@@ -351,6 +423,10 @@ export interface EnsembleLlm {
   output_mode: OutputMode;
   synthetic_reasoning?: boolean | null;
   top_logprobs?: number | null;
+  permutations?: number | null;
+  samples?: number | null;
+  labels?: Labels | null;
+  prompt_template?: PromptTemplate | null;
   prefix_messages?: ChatMessage[] | null;
   suffix_messages?: ChatMessage[] | null;
   frequency_penalty?: number | null;
@@ -411,11 +487,12 @@ export type FunctionRef = GitHubRef;
 export type ProfileRef = GitHubRef;
 
 /**
- * Expression type for JMESPath or Starlark expressions.
+ * Expression type for JMESPath, Starlark, or CEL expressions.
  */
 export type Expression =
   | { $jmespath: string }
-  | { $starlark: string };
+  | { $starlark: string }
+  | { $cel: string };
 
 /**
  * JSON schema type for function input schemas.
@@ -446,8 +523,23 @@ export interface FunctionDefinition {
   type: FunctionType;
   description: string;
   input_schema: JsonSchema;
+  /** Named Starlark functions and JMESPath fragments */
+  definitions?: {
+    starlark?: Record<string, string>;
+    jmespath?: Record<string, string>;
+  } | null;
   input_maps?: Expression[] | null;
   tasks: TaskExpression[];
+  /** How task outputs are combined (defaults to "weighted_mean") */
+  aggregation?:
+    | "weighted_mean"
+    | "weighted_median"
+    | "geometric_mean"
+    | "max"
+    | "min"
+    | "product_of_experts"
+    | Expression
+    | null;
   output: Expression;
   /** For vector functions: output length specification */
   output_length?: number | Expression;
@@ -472,6 +564,7 @@ export interface TaskExpression {
  */
 export type TaskDefinition =
   | VectorCompletionTaskDefinition
+  | ScalarCompletionTaskDefinition
   | FunctionTaskDefinition;
 
 /**
@@ -483,6 +576,15 @@ export interface VectorCompletionTaskDefinition {
   responses: Expression;
 }
 
+/**
+ * Scalar completion task definition. The LLMs rate the prompt on a scale.
+ */
+export interface ScalarCompletionTaskDefinition {
+  type: "scalar.completion";
+  messages: Expression;
+  scale: RatingScale;
+}
+
 /**
  * Nested function task definition.
  */
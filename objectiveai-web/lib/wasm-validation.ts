@@ -74,11 +74,12 @@ export type FunctionType = "scalar.function" | "vector.function";
 export type TaskType = "vector.completion" | "scalar.function" | "vector.function";
 
 /**
- * JMESPath or Starlark expression
+ * JMESPath, Starlark, or CEL expression
  */
 export type Expression =
   | { $jmespath: string }
   | { $starlark: string }
+  | { $cel: string }
   | string
   | number
   | boolean